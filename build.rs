@@ -1,7 +1,14 @@
+use std::path::PathBuf;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Descripteurs pour la réflexion gRPC (grpcurl), inclus via OUT_DIR
+    let descriptor_path =
+        PathBuf::from(std::env::var("OUT_DIR")?).join("takeiteasygame_descriptor.bin");
+
     tonic_prost_build::configure()
         .build_server(true)
         .build_client(true)
+        .file_descriptor_set_path(descriptor_path)
         .out_dir("src/generated")
         .compile_protos(
             &[
@@ -5648,7 +5648,8 @@
           request.forcedTile = forcedTile;
         }
         const { response } = await this.gameClient.startTurn(request);
-        if (response.success) {
+        const alreadyStarted = response.error?.code === "TURN_ALREADY_STARTED" && response.announcedTile;
+        if (response.success || alreadyStarted) {
           return {
             success: true,
             announcedTile: response.announcedTile,
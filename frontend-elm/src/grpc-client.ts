@@ -158,7 +158,9 @@ class GrpcClient {
             }
             const { response } = await this.gameClient.startTurn(request);
 
-            if (response.success) {
+            // Tour déjà annoncé (retry, autre joueur) : l'annonce en cours est jointe
+            const alreadyStarted = response.error?.code === 'TURN_ALREADY_STARTED' && response.announcedTile;
+            if (response.success || alreadyStarted) {
                 return {
                     success: true,
                     announcedTile: response.announcedTile,
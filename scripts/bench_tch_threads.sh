@@ -0,0 +1,51 @@
+#!/bin/bash
+# Aggregate gRPC throughput for several tch intra-op thread counts
+# Usage: ./scripts/bench_tch_threads.sh [concurrent] [total_games] [thread counts...]
+#
+# Starts the server once per thread count (--num-threads) and runs
+# e2e_stress_test --mode stress against it; "default" leaves libtorch's choice.
+
+CONCURRENT=${1:-100}
+TOTAL_GAMES=${2:-200}
+shift $(( $# < 2 ? $# : 2 ))
+THREADS=${@:-default 1 2 4}
+PORT=50051
+URL="http://[::1]:$PORT"
+
+cargo build --release --bin take_it_easy --bin e2e_stress_test || exit 1
+
+echo "=========================================="
+echo "  tch thread sweep"
+echo "=========================================="
+echo "Concurrent games: $CONCURRENT"
+echo "Total games:      $TOTAL_GAMES"
+echo "Thread counts:    $THREADS"
+echo ""
+
+for threads in $THREADS; do
+  if [[ "$threads" == "default" ]]; then
+    thread_args=""
+  else
+    thread_args="--num-threads $threads"
+  fi
+
+  ./target/release/take_it_easy --mode multiplayer --port $PORT $thread_args \
+    > "bench_threads_$threads.log" 2>&1 &
+  server_pid=$!
+
+  # Wait for the gRPC port (model loading can take a while)
+  for _ in $(seq 1 120); do
+    (exec 3<>/dev/tcp/localhost/$PORT) 2>/dev/null && break
+    sleep 1
+  done
+
+  echo "--- num_threads=$threads ---"
+  grep -m1 "tch threads" "bench_threads_$threads.log"
+  ./target/release/e2e_stress_test --url "$URL" --mode stress \
+    --concurrent $CONCURRENT --total-games $TOTAL_GAMES \
+    | grep -E "Completed|Throughput|Avg game time"
+  echo ""
+
+  kill $server_pid 2>/dev/null
+  wait $server_pid 2>/dev/null
+done
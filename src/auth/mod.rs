@@ -7,6 +7,7 @@
 //! - OAuth login (Google, GitHub, Discord)
 //! - JWT token-based sessions
 //! - gRPC authentication middleware
+//! - Per-client rate limiting
 
 pub mod database;
 pub mod email;
@@ -15,8 +16,10 @@ pub mod jwt;
 pub mod models;
 pub mod oauth;
 pub mod password;
+pub mod rate_limit;
 pub mod routes;
 
 pub use grpc_middleware::try_authenticate_request;
 pub use jwt::JwtManager;
+pub use rate_limit::RateLimiter;
 pub use routes::{auth_router, AuthState};
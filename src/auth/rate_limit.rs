@@ -0,0 +1,92 @@
+//! Per-client request rate limiting
+//!
+//! Fixed-window counters keyed by client (usually the peer IP): at most
+//! `max_requests` per `window`, the counter resets when the window elapses.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tracked clients before expired windows are dropped
+const PRUNE_THRESHOLD: usize = 1024;
+
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    /// 0 = unlimited
+    max_requests: u32,
+    window: Duration,
+    windows: Arc<Mutex<HashMap<String, (Instant, u32)>>>,
+}
+
+impl RateLimiter {
+    /// At most `max_requests` per `window` and per client (0 = unlimited)
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn per_minute(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(60))
+    }
+
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Configured limit per window (0 = unlimited)
+    pub fn max_requests(&self) -> u32 {
+        self.max_requests
+    }
+
+    /// Count a request from `client`, false once its limit is reached
+    pub fn check(&self, client: &str) -> bool {
+        if self.max_requests == 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() >= PRUNE_THRESHOLD {
+            let window = self.window;
+            windows.retain(|_, (start, _)| now.duration_since(*start) < window);
+        }
+
+        let (start, count) = windows.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(*start) >= self.window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max_requests {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_is_per_client_and_resets_with_the_window() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(50));
+
+        assert!(limiter.check("10.0.0.1"));
+        assert!(limiter.check("10.0.0.1"));
+        assert!(!limiter.check("10.0.0.1"));
+        assert!(limiter.check("10.0.0.2"));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.check("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_unlimited_never_throttles() {
+        let limiter = RateLimiter::unlimited();
+        assert!((0..1000).all(|_| limiter.check("10.0.0.1")));
+    }
+}
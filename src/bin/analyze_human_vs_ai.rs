@@ -13,6 +13,7 @@ use std::io::{BufRead, BufReader};
 use std::path::Path;
 use tch::{nn, Device, Tensor};
 
+use take_it_easy::game::board_geometry::LINES;
 use take_it_easy::game::create_deck::create_deck;
 use take_it_easy::game::plateau::Plateau;
 use take_it_easy::game::tile::Tile;
@@ -20,28 +21,6 @@ use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
 use take_it_easy::neural::model_io::load_varstore;
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 
-/// Line definitions for scoring analysis
-const LINES: [&[usize]; 15] = [
-    // Horizontal lines (value from tile.0)
-    &[0, 1, 2],
-    &[3, 4, 5, 6],
-    &[7, 8, 9, 10, 11],
-    &[12, 13, 14, 15],
-    &[16, 17, 18],
-    // Diagonal 1 (value from tile.1) - top-left to bottom-right
-    &[0, 3, 7],
-    &[1, 4, 8, 12],
-    &[2, 5, 9, 13, 16],
-    &[6, 10, 14, 17],
-    &[11, 15, 18],
-    // Diagonal 2 (value from tile.2) - top-right to bottom-left
-    &[2, 6, 11],
-    &[1, 5, 10, 15],
-    &[0, 4, 9, 14, 18],
-    &[3, 8, 13, 17],
-    &[7, 12, 16],
-];
-
 #[derive(Debug, Clone)]
 struct GameMove {
     game_id: String,
@@ -319,15 +298,15 @@ fn position_distance_to_center(pos: usize) -> usize {
 }
 
 fn would_complete_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i32)) -> bool {
-    for (line_idx, line) in LINES.iter().enumerate() {
+    for &(line, direction) in LINES.iter() {
         if !line.contains(&position) {
             continue;
         }
 
         // Get the value for this line direction
-        let tile_value = match line_idx {
-            0..=4 => tile.0,   // Horizontal
-            5..=9 => tile.1,   // Diagonal 1
+        let tile_value = match direction {
+            0 => tile.0,   // Horizontal
+            1 => tile.1,   // Diagonal 1
             _ => tile.2,      // Diagonal 2
         };
 
@@ -335,7 +314,7 @@ fn would_complete_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i3
         let mut all_same = true;
         let mut count_filled = 0;
 
-        for &pos in *line {
+        for &pos in line {
             if pos == position {
                 count_filled += 1;
                 continue;
@@ -348,9 +327,9 @@ fn would_complete_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i3
             }
 
             // Decode the value for this direction
-            let pos_value = match line_idx {
-                0..=4 => (v / 100) as i32,
-                5..=9 => ((v / 10) % 10) as i32,
+            let pos_value = match direction {
+                0 => (v / 100) as i32,
+                1 => ((v / 10) % 10) as i32,
                 _ => (v % 10) as i32,
             };
 
@@ -369,15 +348,15 @@ fn would_complete_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i3
 }
 
 fn would_block_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i32)) -> bool {
-    for (line_idx, line) in LINES.iter().enumerate() {
+    for &(line, direction) in LINES.iter() {
         if !line.contains(&position) {
             continue;
         }
 
         // Get the value for this line direction
-        let tile_value = match line_idx {
-            0..=4 => tile.0,
-            5..=9 => tile.1,
+        let tile_value = match direction {
+            0 => tile.0,
+            1 => tile.1,
             _ => tile.2,
         };
 
@@ -385,7 +364,7 @@ fn would_block_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i32))
         let mut has_different = false;
         let mut has_same = false;
 
-        for &pos in *line {
+        for &pos in line {
             if pos == position {
                 continue;
             }
@@ -395,9 +374,9 @@ fn would_block_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i32))
                 continue;
             }
 
-            let pos_value = match line_idx {
-                0..=4 => (v / 100) as i32,
-                5..=9 => ((v / 10) % 10) as i32,
+            let pos_value = match direction {
+                0 => (v / 100) as i32,
+                1 => ((v / 10) % 10) as i32,
                 _ => (v % 10) as i32,
             };
 
@@ -411,14 +390,14 @@ fn would_block_line(plateau: &[i32; 19], position: usize, tile: (i32, i32, i32))
         // Blocking if there were same values but we're adding a different one
         if has_same && !has_different && tile_value != 0 {
             // Actually check if our tile differs
-            for &pos in *line {
+            for &pos in line {
                 if pos == position { continue; }
                 let v = plateau[pos];
                 if v == 0 { continue; }
 
-                let pos_value = match line_idx {
-                    0..=4 => (v / 100) as i32,
-                    5..=9 => ((v / 10) % 10) as i32,
+                let pos_value = match direction {
+                    0 => (v / 100) as i32,
+                    1 => ((v / 10) % 10) as i32,
                     _ => (v % 10) as i32,
                 };
 
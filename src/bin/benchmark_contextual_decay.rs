@@ -0,0 +1,240 @@
+//! Benchmark the contextual-boost decay over game phase.
+//!
+//! Plays pure-rollout MCTS on the same random tile sequences with the decay
+//! disabled (constant contextual boost) and enabled (boost scaled down to
+//! `--late-scale` between `--decay-start` and `--decay-end`).
+//!
+//! Usage:
+//!   cargo run --release --bin benchmark_contextual_decay -- --num-games 50 --late-scale 0.25
+
+use clap::Parser;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::time::Instant;
+
+use take_it_easy::data::tile_sequences::{load_sequences, save_sequences};
+use take_it_easy::game::create_deck::create_deck;
+use take_it_easy::game::get_legal_moves::get_legal_moves;
+use take_it_easy::game::plateau::create_plateau_empty;
+use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
+use take_it_easy::game::tile::Tile;
+use take_it_easy::mcts::algorithm::mcts_find_best_position_for_tile_pure;
+use take_it_easy::mcts::hyperparameters::MCTSHyperparameters;
+use take_it_easy::scoring::scoring::result;
+
+#[derive(Parser)]
+#[command(
+    name = "benchmark_contextual_decay",
+    about = "Benchmark MCTS with and without contextual-boost decay"
+)]
+struct Args {
+    /// Number of games per configuration
+    #[arg(long, default_value_t = 50)]
+    num_games: usize,
+
+    /// MCTS simulations per move
+    #[arg(long, default_value_t = 150)]
+    num_simulations: usize,
+
+    /// Contextual boost scale reached at --decay-end
+    #[arg(long, default_value_t = 0.25)]
+    late_scale: f64,
+
+    /// Turn at which the decay starts
+    #[arg(long, default_value_t = 6)]
+    decay_start: usize,
+
+    /// Turn at which the decay reaches --late-scale
+    #[arg(long, default_value_t = 15)]
+    decay_end: usize,
+
+    /// Random seed
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// Load the tile sequences from this file (one 19-tile sequence per line)
+    /// instead of generating them
+    #[arg(long)]
+    sequences_file: Option<String>,
+
+    /// Save the tile sequences played to this file
+    #[arg(long)]
+    dump_sequences: Option<String>,
+}
+
+/// Generate a random tile sequence (19 tiles drawn without replacement).
+fn random_tile_sequence(rng: &mut StdRng) -> Vec<Tile> {
+    let deck = create_deck();
+    let mut available: Vec<Tile> = deck
+        .tiles()
+        .iter()
+        .copied()
+        .filter(|t| *t != Tile(0, 0, 0))
+        .collect();
+    let mut seq = Vec::with_capacity(19);
+    for _ in 0..19 {
+        if available.is_empty() {
+            break;
+        }
+        let idx = rng.random_range(0..available.len());
+        seq.push(available.remove(idx));
+    }
+    seq
+}
+
+/// Play one game with pure-rollout MCTS.
+fn play_mcts(hyperparams: &MCTSHyperparameters, num_simulations: usize, tiles: &[Tile]) -> i32 {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+
+    for (turn, &tile) in tiles.iter().enumerate() {
+        deck = replace_tile_in_deck(&deck, &tile);
+        if get_legal_moves(&plateau).is_empty() {
+            break;
+        }
+
+        let mcts_result = mcts_find_best_position_for_tile_pure(
+            &mut plateau,
+            &mut deck,
+            tile,
+            num_simulations,
+            turn,
+            19,
+            Some(hyperparams),
+        );
+        plateau.tiles[mcts_result.best_position] = tile;
+    }
+
+    result(&plateau)
+}
+
+struct BenchResult {
+    label: String,
+    scores: Vec<i32>,
+    elapsed_ms: f64,
+}
+
+impl BenchResult {
+    fn avg(&self) -> f64 {
+        self.scores.iter().sum::<i32>() as f64 / self.scores.len() as f64
+    }
+    fn std(&self) -> f64 {
+        let mean = self.avg();
+        let var = self
+            .scores
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.scores.len() as f64;
+        var.sqrt()
+    }
+    fn ms_per_game(&self) -> f64 {
+        self.elapsed_ms / self.scores.len() as f64
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.num_games == 0 {
+        eprintln!("Error: --num-games must be at least 1");
+        return;
+    }
+
+    println!("================================================");
+    println!("  Contextual Boost Decay Benchmark");
+    println!("================================================\n");
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let sequences: Vec<Vec<Tile>> = match &args.sequences_file {
+        Some(path) => match load_sequences(path) {
+            Ok(sequences) => {
+                println!("Loaded {} tile sequences from {}", sequences.len(), path);
+                sequences
+            }
+            Err(e) => {
+                eprintln!("Error loading tile sequences: {}", e);
+                return;
+            }
+        },
+        None => (0..args.num_games)
+            .map(|_| random_tile_sequence(&mut rng))
+            .collect(),
+    };
+    if let Some(path) = &args.dump_sequences {
+        if let Err(e) = save_sequences(&sequences, path) {
+            eprintln!("Warning: failed to save tile sequences: {}", e);
+        }
+    }
+    if sequences.is_empty() {
+        eprintln!("No tile sequences to play");
+        return;
+    }
+
+    let constant = MCTSHyperparameters::default();
+    let decayed = MCTSHyperparameters {
+        contextual_scale_late: args.late_scale,
+        contextual_decay_start: args.decay_start,
+        contextual_decay_end: args.decay_end,
+        ..Default::default()
+    };
+    let configs = [
+        ("Constant boost".to_string(), constant),
+        (
+            format!(
+                "Decay {}→{} x{}",
+                args.decay_start, args.decay_end, args.late_scale
+            ),
+            decayed,
+        ),
+    ];
+
+    let mut results = Vec::new();
+    for (label, hyperparams) in configs {
+        print!("Running {} ({} games)...", label, sequences.len());
+        std::io::Write::flush(&mut std::io::stdout()).ok();
+
+        let start = Instant::now();
+        let scores: Vec<i32> = sequences
+            .iter()
+            .map(|seq| play_mcts(&hyperparams, args.num_simulations, seq))
+            .collect();
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        println!(" done ({:.0}ms)", elapsed_ms);
+
+        results.push(BenchResult {
+            label,
+            scores,
+            elapsed_ms,
+        });
+    }
+
+    println!("\n{}", "=".repeat(60));
+    println!(
+        "{:<24} {:>8} {:>8} {:>8} {:>8}",
+        "Config", "Avg", "Std", "Wins", "ms/game"
+    );
+    println!("{}", "-".repeat(60));
+    for (i, r) in results.iter().enumerate() {
+        // Games won against the other configuration on the same tiles
+        let other = &results[1 - i];
+        let wins = r
+            .scores
+            .iter()
+            .zip(&other.scores)
+            .filter(|(a, b)| a > b)
+            .count();
+        println!(
+            "{:<24} {:>8.1} {:>8.1} {:>8} {:>8.0}",
+            r.label,
+            r.avg(),
+            r.std(),
+            wins,
+            r.ms_per_game()
+        );
+    }
+    println!("{}", "=".repeat(60));
+    println!(
+        "Delta (decay - constant): {:+.1} pts",
+        results[1].avg() - results[0].avg()
+    );
+}
@@ -11,8 +11,9 @@ use rand::prelude::*;
 use rand::rngs::StdRng;
 use std::path::Path;
 use std::time::Instant;
-use tch::{nn, Device};
+use tch::Device;
 
+use take_it_easy::data::tile_sequences::{load_sequences, save_sequences};
 use take_it_easy::game::create_deck::create_deck;
 use take_it_easy::game::get_legal_moves::get_legal_moves;
 use take_it_easy::game::plateau::create_plateau_empty;
@@ -20,12 +21,13 @@ use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::device_util::{check_cuda, parse_device};
 use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
-use take_it_easy::neural::model_io::load_varstore;
+use take_it_easy::neural::model_io::{load_policy, load_value};
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use take_it_easy::neural::graph_transformer::GraphTransformerValueNet;
 use take_it_easy::scoring::scoring::result;
 use take_it_easy::strategy::batched_mcts::{batched_gt_mcts_select, BatchedMctsConfig};
 use take_it_easy::strategy::expectimax::{expectimax_select, ExpectimaxConfig};
+use take_it_easy::training::value_normalization::ScoreNormalization;
 
 #[derive(Parser)]
 #[command(name = "benchmark_mcts_gpu", about = "Benchmark batched MCTS at various sim budgets")]
@@ -77,6 +79,15 @@ struct Args {
     /// Path to value network weights (enables Expectimax benchmark)
     #[arg(long)]
     value_model_path: Option<String>,
+
+    /// Load the tile sequences from this file (one 19-tile sequence per line)
+    /// instead of generating them
+    #[arg(long)]
+    sequences_file: Option<String>,
+
+    /// Save the tile sequences played to this file
+    #[arg(long)]
+    dump_sequences: Option<String>,
 }
 
 /// Play one game using GT Direct (argmax, no heuristics).
@@ -241,33 +252,54 @@ fn main() {
         return;
     }
 
-    // Load model
-    let mut vs = nn::VarStore::new(device);
-    let policy_net = GraphTransformerPolicyNet::new(
-        &vs, 47, args.embed_dim, args.num_layers, args.num_heads, 0.1,
-    );
-
+    // Load model (sized from its metadata when saved with it)
     if !Path::new(&args.model_path).exists() {
         eprintln!("Error: model weights not found: {}", args.model_path);
         return;
     }
-    match load_varstore(&mut vs, &args.model_path) {
-        Ok(()) => println!("Loaded model from {}", args.model_path),
+    let (_vs, policy_net) = match load_policy(
+        &args.model_path, device, args.embed_dim, args.num_layers, args.num_heads,
+    ) {
+        Ok(loaded) => {
+            println!("Loaded model from {}", args.model_path);
+            loaded
+        }
         Err(e) => {
             eprintln!("Error loading model: {}", e);
             return;
         }
-    }
+    };
 
     // Generate tile sequences (shared across all strategies)
     let mut rng = StdRng::seed_from_u64(args.seed);
-    let sequences: Vec<Vec<Tile>> = (0..args.num_games)
-        .map(|_| random_tile_sequence(&mut rng))
-        .collect();
+    let sequences: Vec<Vec<Tile>> = match &args.sequences_file {
+        Some(path) => match load_sequences(path) {
+            Ok(sequences) => {
+                println!("Loaded {} tile sequences from {}", sequences.len(), path);
+                sequences
+            }
+            Err(e) => {
+                eprintln!("Error loading tile sequences: {}", e);
+                return;
+            }
+        },
+        None => (0..args.num_games)
+            .map(|_| random_tile_sequence(&mut rng))
+            .collect(),
+    };
+    if let Some(path) = &args.dump_sequences {
+        if let Err(e) = save_sequences(&sequences, path) {
+            eprintln!("Warning: failed to save tile sequences: {}", e);
+        }
+    }
+    if sequences.is_empty() {
+        eprintln!("No tile sequences to play");
+        return;
+    }
 
     println!(
         "Benchmarking {} games | sim_counts={:?} | batch_size={}\n",
-        args.num_games, sim_counts, args.batch_size
+        sequences.len(), sim_counts, args.batch_size
     );
 
     // 1. GT Direct baseline
@@ -289,7 +321,7 @@ fn main() {
 
     for &sims in &sim_counts {
         let label = format!("MCTS-{}", sims);
-        print!("Running {} ({} games)...", label, args.num_games);
+        print!("Running {} ({} games)...", label, sequences.len());
         std::io::Write::flush(&mut std::io::stdout()).ok();
 
         let config = BatchedMctsConfig {
@@ -326,29 +358,29 @@ fn main() {
     // 3. Expectimax (if value model provided)
     if let Some(ref value_path) = args.value_model_path {
         if Path::new(value_path).exists() {
-            print!("Running Expectimax ({} games)...", args.num_games);
+            print!("Running Expectimax ({} games)...", sequences.len());
             std::io::Write::flush(&mut std::io::stdout()).ok();
 
-            let mut value_vs = nn::VarStore::new(device);
-            let value_net = GraphTransformerValueNet::new(
-                &value_vs, 47, args.embed_dim, args.num_layers, args.num_heads, 0.0,
-            );
-            match load_varstore(&mut value_vs, value_path) {
-                Ok(()) => {}
+            let (_value_vs, value_net) = match load_value(
+                value_path, device, args.embed_dim, args.num_layers, args.num_heads,
+            ) {
+                Ok(loaded) => loaded,
                 Err(e) => {
                     eprintln!("\nError loading value model: {}", e);
                     std::process::exit(1);
                 }
-            }
+            };
+            let norm = ScoreNormalization::load(value_path).unwrap_or_default();
 
             let ex_config = ExpectimaxConfig {
                 device,
                 boost: args.boost,
-                score_mean: 140.0,
-                score_std: 40.0,
+                score_mean: norm.mean,
+                score_std: norm.std,
                 min_turn: 0,
                 top_k_ply1: 3,
                 top_k_ply2: 2,
+                blunder_epsilon: 0.0,
             };
 
             let start = Instant::now();
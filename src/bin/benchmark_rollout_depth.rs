@@ -0,0 +1,216 @@
+//! Benchmark truncated vs full MCTS rollouts.
+//!
+//! Plays pure-rollout MCTS on the same random tile sequences with full
+//! rollouts (`rollout_depth = 0`) and with each `--depths` cap, where rollouts
+//! stop after K moves and the leaf is scored by a heuristic estimate. Reports
+//! score and per-game latency so the speed/accuracy trade-off is visible.
+//!
+//! Usage:
+//!   cargo run --release --bin benchmark_rollout_depth -- --num-games 50 --depths 2,4,8
+//!   cargo run --release --bin benchmark_rollout_depth -- --mcts-config mcts.toml
+
+use clap::Parser;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::path::Path;
+use std::time::Instant;
+
+use take_it_easy::game::create_deck::create_deck;
+use take_it_easy::game::get_legal_moves::get_legal_moves;
+use take_it_easy::game::plateau::create_plateau_empty;
+use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
+use take_it_easy::game::tile::Tile;
+use take_it_easy::mcts::algorithm::mcts_find_best_position_for_tile_pure;
+use take_it_easy::mcts::hyperparameters::MCTSHyperparameters;
+use take_it_easy::scoring::scoring::result;
+
+#[derive(Parser)]
+#[command(
+    name = "benchmark_rollout_depth",
+    about = "Benchmark MCTS with truncated rollouts against full rollouts"
+)]
+struct Args {
+    /// Number of games per configuration
+    #[arg(long, default_value_t = 50)]
+    num_games: usize,
+
+    /// MCTS simulations per move
+    #[arg(long, default_value_t = 150)]
+    num_simulations: usize,
+
+    /// Rollout depth caps to compare against full rollouts (comma-separated)
+    #[arg(long, default_value = "2,4,8")]
+    depths: String,
+
+    /// Random seed
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// TOML file with the MCTS hyperparameters to start from (c_puct
+    /// schedule, ...); defaults otherwise
+    #[arg(long)]
+    mcts_config: Option<String>,
+}
+
+/// Generate a random tile sequence (19 tiles drawn without replacement).
+fn random_tile_sequence(rng: &mut StdRng) -> Vec<Tile> {
+    let deck = create_deck();
+    let mut available: Vec<Tile> = deck
+        .tiles()
+        .iter()
+        .copied()
+        .filter(|t| *t != Tile(0, 0, 0))
+        .collect();
+    let mut seq = Vec::with_capacity(19);
+    for _ in 0..19 {
+        if available.is_empty() {
+            break;
+        }
+        let idx = rng.random_range(0..available.len());
+        seq.push(available.remove(idx));
+    }
+    seq
+}
+
+/// Play one game with pure-rollout MCTS.
+fn play_mcts(hyperparams: &MCTSHyperparameters, num_simulations: usize, tiles: &[Tile]) -> i32 {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+
+    for (turn, &tile) in tiles.iter().enumerate() {
+        deck = replace_tile_in_deck(&deck, &tile);
+        if get_legal_moves(&plateau).is_empty() {
+            break;
+        }
+
+        let mcts_result = mcts_find_best_position_for_tile_pure(
+            &mut plateau,
+            &mut deck,
+            tile,
+            num_simulations,
+            turn,
+            19,
+            Some(hyperparams),
+        );
+        plateau.tiles[mcts_result.best_position] = tile;
+    }
+
+    result(&plateau)
+}
+
+struct BenchResult {
+    label: String,
+    scores: Vec<i32>,
+    elapsed_ms: f64,
+}
+
+impl BenchResult {
+    fn avg(&self) -> f64 {
+        self.scores.iter().sum::<i32>() as f64 / self.scores.len() as f64
+    }
+    fn std(&self) -> f64 {
+        let mean = self.avg();
+        let var = self
+            .scores
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.scores.len() as f64;
+        var.sqrt()
+    }
+    fn ms_per_game(&self) -> f64 {
+        self.elapsed_ms / self.scores.len() as f64
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.num_games == 0 {
+        eprintln!("Error: --num-games must be at least 1");
+        return;
+    }
+    let depths: Vec<usize> = match args
+        .depths
+        .split(',')
+        .map(|d| d.trim().parse::<usize>())
+        .collect()
+    {
+        Ok(depths) => depths,
+        Err(e) => {
+            eprintln!("Error: invalid --depths '{}': {}", args.depths, e);
+            return;
+        }
+    };
+
+    let base_hyperparams = match &args.mcts_config {
+        Some(path) => match MCTSHyperparameters::from_toml_file(Path::new(path)) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return;
+            }
+        },
+        None => MCTSHyperparameters::default(),
+    };
+
+    println!("================================================");
+    println!("  Rollout Depth Benchmark");
+    println!("================================================\n");
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let sequences: Vec<Vec<Tile>> = (0..args.num_games)
+        .map(|_| random_tile_sequence(&mut rng))
+        .collect();
+
+    let mut configs = vec![("Full rollouts".to_string(), 0)];
+    configs.extend(
+        depths
+            .iter()
+            .filter(|&&d| d > 0)
+            .map(|&d| (format!("Depth {}", d), d)),
+    );
+
+    let mut results = Vec::new();
+    for (label, rollout_depth) in configs {
+        print!("Running {} ({} games)...", label, args.num_games);
+        std::io::Write::flush(&mut std::io::stdout()).ok();
+
+        let hyperparams = MCTSHyperparameters {
+            rollout_depth,
+            ..base_hyperparams.clone()
+        };
+        let start = Instant::now();
+        let scores: Vec<i32> = sequences
+            .iter()
+            .map(|seq| play_mcts(&hyperparams, args.num_simulations, seq))
+            .collect();
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        println!(" done ({:.0}ms)", elapsed_ms);
+
+        results.push(BenchResult {
+            label,
+            scores,
+            elapsed_ms,
+        });
+    }
+
+    let full = &results[0];
+    println!("\n{}", "=".repeat(64));
+    println!(
+        "{:<16} {:>8} {:>8} {:>8} {:>10} {:>9}",
+        "Config", "Avg", "Std", "Delta", "ms/game", "Speedup"
+    );
+    println!("{}", "-".repeat(64));
+    for r in &results {
+        println!(
+            "{:<16} {:>8.1} {:>8.1} {:>+8.1} {:>10.0} {:>8.2}x",
+            r.label,
+            r.avg(),
+            r.std(),
+            r.avg() - full.avg(),
+            r.ms_per_game(),
+            full.ms_per_game() / r.ms_per_game()
+        );
+    }
+    println!("{}", "=".repeat(64));
+}
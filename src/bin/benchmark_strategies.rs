@@ -3,6 +3,9 @@
 //! Compares strong strategies only:
 //!   GT Direct | GT Beam | Hybrid Beam | Full Beam | AZ MCTS
 //!
+//! Each strategy also reports its per-move decision latency (mean/p50/p95/p99)
+//! so scores can be weighed against the live server's latency budget.
+//!
 //! Usage: cargo run --release --bin benchmark_strategies [-- --beam-width 5 --beam-rollouts 30]
 
 use clap::Parser;
@@ -12,9 +15,11 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
-use std::time::Instant;
-use tch::{nn, Device, Kind, Tensor};
+use std::time::{Duration, Instant};
+use tch::{Device, Kind, Tensor};
 
+use take_it_easy::data::tile_sequences::{load_sequences, save_sequences};
+use take_it_easy::game::board_geometry::{pos_to_row, ROWS};
 use take_it_easy::game::create_deck::create_deck;
 use take_it_easy::game::deck::Deck;
 use take_it_easy::game::get_legal_moves::get_legal_moves;
@@ -22,10 +27,14 @@ use take_it_easy::game::plateau::{create_plateau_empty, Plateau};
 use take_it_easy::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
-use take_it_easy::neural::model_io::load_varstore;
+use take_it_easy::neural::model_io::load_policy;
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
-use take_it_easy::scoring::scoring::result;
-use take_it_easy::strategy::gt_boost::{self, gt_beam_rollout_select, gt_beam_v1_select, gt_boosted_select, gt_mcts_select};
+use take_it_easy::scoring::scoring::{result, result_breakdown};
+use take_it_easy::strategy::gt_boost::{
+    self, find_line_completing_positions, gt_beam_rollout_select, gt_beam_v1_select,
+    gt_boosted_select, gt_greedy_blend_select, gt_mcts_select, play_heuristic_game,
+};
+use take_it_easy::training::benchmark_report::{BenchmarkReport, ModelSummary};
 
 #[derive(Parser)]
 #[command(name = "benchmark_strategies", about = "Benchmark strategy comparison")]
@@ -38,6 +47,15 @@ struct Args {
     #[arg(long, default_value_t = 100)]
     random_games: usize,
 
+    /// Load the random-game tile sequences from this file (one 19-tile
+    /// sequence per line) instead of generating them; overrides --random-games
+    #[arg(long)]
+    sequences_file: Option<String>,
+
+    /// Save the random-game tile sequences to this file
+    #[arg(long)]
+    dump_sequences: Option<String>,
+
     /// Path to GT policy model weights
     #[arg(long, default_value = "model_weights/graph_transformer_policy.safetensors")]
     model_path: String,
@@ -94,10 +112,23 @@ struct Args {
     #[arg(long, default_value_t = 2.0)]
     v1_bonus: f64,
 
+    /// Weight of the immediate score gain in the GT+Greedy blend (0 = disabled)
+    #[arg(long, default_value_t = 0.0)]
+    greedy_weight: f64,
+
     /// Only run GT Direct (skip beam search for fast comparison)
     #[arg(long, default_value_t = false)]
     direct_only: bool,
 
+    /// Also play the network-free line-completion heuristic on the same tiles
+    /// and report GT Direct's win-rate / average margin against it
+    #[arg(long, default_value_t = false)]
+    vs_heuristic: bool,
+
+    /// Also write a JSON summary per strategy (see `regression_check`)
+    #[arg(long)]
+    json_out: Option<String>,
+
     /// Graph Transformer embedding dimension
     #[arg(long, default_value_t = 128)]
     embed_dim: i64,
@@ -111,9 +142,6 @@ struct Args {
     num_heads: i64,
 }
 
-/// Re-export LINES from gt_boost module for local use.
-const LINES: [(&[usize], usize); 15] = gt_boost::LINES;
-
 // ─── Data structures ──────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -140,10 +168,37 @@ impl LineCompletions {
     }
 }
 
+/// Final score, line completions and per-move decision latencies of one game.
+type GameOutcome = (i32, LineCompletions, Vec<Duration>);
+
+/// Times one move decision: the latency is recorded when the guard is dropped,
+/// so the early `continue`s of the play loops are measured too.
+struct MoveLatency<'a> {
+    latencies: &'a mut Vec<Duration>,
+    start: Instant,
+}
+
+impl<'a> MoveLatency<'a> {
+    fn start(latencies: &'a mut Vec<Duration>) -> Self {
+        Self {
+            latencies,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for MoveLatency<'_> {
+    fn drop(&mut self) {
+        self.latencies.push(self.start.elapsed());
+    }
+}
+
 struct StrategyStats {
     name: String,
     scores: Vec<i32>,
     completions: Vec<LineCompletions>,
+    /// Decision latency of every move of every game
+    latencies: Vec<Duration>,
 }
 
 impl StrategyStats {
@@ -152,12 +207,14 @@ impl StrategyStats {
             name: name.to_string(),
             scores: Vec::new(),
             completions: Vec::new(),
+            latencies: Vec::new(),
         }
     }
 
-    fn push(&mut self, score: i32, completions: LineCompletions) {
+    fn push(&mut self, score: i32, completions: LineCompletions, latencies: Vec<Duration>) {
         self.scores.push(score);
         self.completions.push(completions);
+        self.latencies.extend(latencies);
     }
 }
 
@@ -311,60 +368,19 @@ fn gt_masked_logits(
     logits + Tensor::from_slice(&mask)
 }
 
-/// Find positions that would complete a scoring line for this tile.
-fn find_line_completing_positions(plateau: &Plateau, tile: &Tile) -> Vec<usize> {
-    let mut positions = Vec::new();
-    for &(line_positions, direction) in &LINES {
-        let get_value = |t: &Tile| match direction {
-            0 => t.0,
-            1 => t.1,
-            _ => t.2,
-        };
-        let tile_value = get_value(tile);
-        if tile_value == 0 {
-            continue;
-        }
-
-        let mut empty_pos = None;
-        let mut all_match = true;
-        let mut empty_count = 0;
-
-        for &pos in line_positions {
-            let t = &plateau.tiles[pos];
-            if *t == Tile(0, 0, 0) {
-                empty_count += 1;
-                empty_pos = Some(pos);
-            } else if get_value(t) != tile_value {
-                all_match = false;
-                break;
-            }
-        }
-
-        if all_match && empty_count == 1 {
-            if let Some(pos) = empty_pos {
-                if !positions.contains(&pos) {
-                    positions.push(pos);
-                }
-            }
-        }
-    }
-    positions
-}
-
 // ─── GT Direct ────────────────────────────────────────────────────
 
-fn play_gt_direct(
-    tiles: &[Tile],
-    policy_net: &GraphTransformerPolicyNet,
-) -> (i32, LineCompletions) {
+fn play_gt_direct(tiles: &[Tile], policy_net: &GraphTransformerPolicyNet) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
         let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
         let best_pos = masked.argmax(-1, false).int64_value(&[]) as usize;
         plateau.tiles[best_pos] = *tile;
@@ -373,35 +389,13 @@ fn play_gt_direct(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Line completion boost ───────────────────────────────────────
 // Uses gt_boost::line_boost from the shared module.
 use gt_boost::line_boost;
 
-// ─── Row definitions (v1 horizontal rows) ────────────────────────
-
-const ROWS: [&[usize]; 5] = [
-    &[0, 1, 2],
-    &[3, 4, 5, 6],
-    &[7, 8, 9, 10, 11],
-    &[12, 13, 14, 15],
-    &[16, 17, 18],
-];
-
-/// Maps position → row index for O(1) lookup
-fn pos_to_row(pos: usize) -> usize {
-    match pos {
-        0..=2 => 0,
-        3..=6 => 1,
-        7..=11 => 2,
-        12..=15 => 3,
-        16..=18 => 4,
-        _ => unreachable!(),
-    }
-}
-
 /// Row affinity boost — defensive approach.
 ///
 /// Main signal: PENALIZE contaminating a clean row (placing a tile whose v1
@@ -473,15 +467,17 @@ fn play_gt_line_heuristic(
     policy_net: &GraphTransformerPolicyNet,
     deck_init: &Deck,
     boost: f64,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = deck_init.clone();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 {
             plateau.tiles[legal[0]] = *tile;
@@ -509,7 +505,7 @@ fn play_gt_line_heuristic(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── GT + Lines + Row Affinity ────────────────────────────────────
@@ -520,15 +516,17 @@ fn play_gt_lines_rows(
     deck_init: &Deck,
     line_boost_val: f64,
     row_boost_val: f64,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = deck_init.clone();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 {
             plateau.tiles[legal[0]] = *tile;
@@ -560,7 +558,7 @@ fn play_gt_lines_rows(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Rollout helper ───────────────────────────────────────────────
@@ -639,15 +637,17 @@ fn play_gt_beam_search(
     beam_width: usize,
     num_rollouts: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
@@ -678,7 +678,7 @@ fn play_gt_beam_search(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Beam + Line Boost ───────────────────────────────────────────
@@ -692,15 +692,17 @@ fn play_gt_beam_line_boost(
     num_rollouts: usize,
     boost: f64,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             // Use boosted logits even for single-choice turns (consistent)
@@ -749,7 +751,7 @@ fn play_gt_beam_line_boost(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Hybrid Beam (GT top-K + greedy + line-completing) ────────────
@@ -760,15 +762,17 @@ fn play_gt_hybrid_beam(
     beam_width: usize,
     num_rollouts: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
@@ -820,7 +824,7 @@ fn play_gt_hybrid_beam(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── GT + Boost + Rollouts ────────────────────────────────────────
@@ -832,15 +836,17 @@ fn play_gt_boost_rollout(
     beam_k: usize,
     num_rollouts: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             // Use boosted select for trivial turns
@@ -859,7 +865,7 @@ fn play_gt_boost_rollout(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── GT + Boost + V1 Beam (inject v1-ideal candidate) ─────────────
@@ -872,15 +878,17 @@ fn play_gt_v1_beam(
     num_rollouts: usize,
     v1_bonus: f64,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         let best = gt_beam_v1_select(
             &plateau, tile, &deck, turn, policy_net, boost, beam_k, num_rollouts, v1_bonus, rng,
@@ -891,7 +899,36 @@ fn play_gt_v1_beam(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
+}
+
+// ─── GT + Boost + Greedy blend (no rollouts) ──────────────────────
+
+fn play_gt_greedy_blend(
+    tiles: &[Tile],
+    policy_net: &GraphTransformerPolicyNet,
+    boost: f64,
+    greedy_weight: f64,
+) -> GameOutcome {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+    let mut latencies = Vec::new();
+
+    for (turn, tile) in tiles.iter().enumerate() {
+        let legal = get_legal_moves(&plateau);
+        if legal.is_empty() {
+            break;
+        }
+        let _timer = MoveLatency::start(&mut latencies);
+
+        let best = gt_greedy_blend_select(&plateau, tile, &deck, turn, policy_net, boost, greedy_weight);
+        plateau.tiles[best] = *tile;
+        deck = replace_tile_in_deck(&deck, tile);
+    }
+
+    let score = result(&plateau);
+    let completions = count_line_completions(&plateau);
+    (score, completions, latencies)
 }
 
 // ─── GT + Boost + MCTS (PUCT) ─────────────────────────────────────
@@ -902,15 +939,17 @@ fn play_gt_mcts_boosted(
     boost: f64,
     num_sims: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         let best = gt_mcts_select(
             &plateau, tile, &deck, turn, policy_net, boost, num_sims, rng,
@@ -921,7 +960,7 @@ fn play_gt_mcts_boosted(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Full Beam (all legal positions) ──────────────────────────────
@@ -931,15 +970,17 @@ fn play_gt_full_beam(
     policy_net: &GraphTransformerPolicyNet,
     num_rollouts: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
 
     for (turn, tile) in tiles.iter().enumerate() {
         let legal = get_legal_moves(&plateau);
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
@@ -959,7 +1000,7 @@ fn play_gt_full_beam(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── AlphaZero MCTS (PUCT + GT rollouts) ─────────────────────────
@@ -969,9 +1010,10 @@ fn play_alphazero_mcts(
     policy_net: &GraphTransformerPolicyNet,
     num_sims: usize,
     rng: &mut StdRng,
-) -> (i32, LineCompletions) {
+) -> GameOutcome {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
+    let mut latencies = Vec::new();
     const C_PUCT: f64 = 2.5;
     const SCORE_NORM: f64 = 300.0;
 
@@ -980,6 +1022,7 @@ fn play_alphazero_mcts(
         if legal.is_empty() {
             break;
         }
+        let _timer = MoveLatency::start(&mut latencies);
 
         if legal.len() == 1 || turn == 18 {
             let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
@@ -1056,44 +1099,20 @@ fn play_alphazero_mcts(
 
     let score = result(&plateau);
     let completions = count_line_completions(&plateau);
-    (score, completions)
+    (score, completions, latencies)
 }
 
 // ─── Line completion analysis ─────────────────────────────────────
 
 fn count_line_completions(plateau: &Plateau) -> LineCompletions {
     let mut lc = LineCompletions::default();
-
-    for &(positions, direction) in &LINES {
-        let get_value = |tile: &Tile| match direction {
-            0 => tile.0,
-            1 => tile.1,
-            _ => tile.2,
-        };
-
-        let first = &plateau.tiles[positions[0]];
-        if *first == Tile(0, 0, 0) {
-            continue;
-        }
-        let target = get_value(first);
-        if target == 0 {
-            continue;
-        }
-
-        let all_match = positions.iter().all(|&i| {
-            let t = &plateau.tiles[i];
-            *t != Tile(0, 0, 0) && get_value(t) == target
-        });
-
-        if all_match {
-            match direction {
-                0 => lc.v1_cols += 1,
-                1 => lc.v2_diags += 1,
-                _ => lc.v3_diags += 1,
-            }
+    for line in result_breakdown(plateau).completed() {
+        match line.direction() {
+            0 => lc.v1_cols += 1,
+            1 => lc.v2_diags += 1,
+            _ => lc.v3_diags += 1,
         }
     }
-
     lc
 }
 
@@ -1125,14 +1144,14 @@ fn main() {
     println!("╚══════════════════════════════════════════════════════════════╝\n");
 
     let device = Device::Cpu;
-    let mut vs = nn::VarStore::new(device);
-    let policy_net = GraphTransformerPolicyNet::new(&vs, 47, args.embed_dim, args.num_layers, args.num_heads, 0.1);
-
     print!("Loading GT policy model from {}... ", &args.model_path);
-    if let Err(e) = load_varstore(&mut vs, &args.model_path) {
-        eprintln!("FAILED: {}", e);
-        return;
-    }
+    let (_vs, policy_net) = match load_policy(&args.model_path, device, args.embed_dim, args.num_layers, args.num_heads) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("FAILED: {}", e);
+            return;
+        }
+    };
     println!("OK");
 
     // Strategy labels
@@ -1146,13 +1165,32 @@ fn main() {
     let use_mcts = args.mcts_sims > 0;
     let v1_beam_label = format!("V1Beam ({},{},b={:.1},v={:.1})", args.v1_beam_k, args.v1_beam_rollouts, args.line_boost, args.v1_bonus);
     let use_v1_beam = args.v1_beam_rollouts > 0;
+    let greedy_label = format!("GT+Greedy (b={:.1},g={:.1})", args.line_boost, args.greedy_weight);
+    let use_greedy = args.greedy_weight > 0.0;
 
     // Load recorded games
     println!("\nLoading recorded games from {}:", &args.data_dir);
     let recorded_games = load_all_games(&args.data_dir);
     println!("  Total: {} complete games\n", recorded_games.len());
 
-    if recorded_games.is_empty() && args.random_games == 0 {
+    // Fixed random-game sequences (reproducible across runs and RNG changes)
+    let fixed_sequences = match &args.sequences_file {
+        Some(path) => match load_sequences(path) {
+            Ok(sequences) => {
+                println!("Loaded {} tile sequences from {}\n", sequences.len(), path);
+                Some(sequences)
+            }
+            Err(e) => {
+                eprintln!("Error loading tile sequences: {}", e);
+                return;
+            }
+        },
+        None => None,
+    };
+    let num_random = fixed_sequences.as_ref().map_or(args.random_games, Vec::len);
+    let mut played_sequences: Vec<Vec<Tile>> = Vec::with_capacity(num_random);
+
+    if recorded_games.is_empty() && num_random == 0 {
         eprintln!("No games to benchmark. Exiting.");
         return;
     }
@@ -1181,6 +1219,9 @@ fn main() {
         if use_v1_beam {
             v.push(StrategyStats::new(&v1_beam_label));
         }
+        if use_greedy {
+            v.push(StrategyStats::new(&greedy_label));
+        }
         if !args.direct_only {
             v.push(StrategyStats::new(&beam_label));
             v.push(StrategyStats::new(&beam_lines_label));
@@ -1195,11 +1236,14 @@ fn main() {
     let idx_boost_roll = if use_boost_rollouts { let i = next_idx; next_idx += 1; Some(i) } else { None };
     let idx_mcts = if use_mcts { let i = next_idx; next_idx += 1; Some(i) } else { None };
     let idx_v1_beam = if use_v1_beam { let i = next_idx; next_idx += 1; Some(i) } else { None };
+    let idx_greedy = if use_greedy { let i = next_idx; next_idx += 1; Some(i) } else { None };
     let idx_beam = if !args.direct_only { let i = next_idx; next_idx += 1; Some(i) } else { None };
     let idx_beam_lines = if !args.direct_only { let i = next_idx; next_idx += 1; Some(i) } else { None };
     let _ = next_idx;
 
     let mut game_ids: Vec<String> = Vec::new();
+    let mut heuristic_scores: Vec<i32> = Vec::new();
+    let mut rand_heuristic_scores: Vec<i32> = Vec::new();
 
     let total = recorded_games.len();
     let start = Instant::now();
@@ -1208,49 +1252,59 @@ fn main() {
         let game_start = Instant::now();
         let tiles = &game.tile_sequence;
 
-        let (s, c) = play_gt_direct(tiles, &policy_net);
-        strategies[idx_direct].push(s, c);
+        let (s, c, t) = play_gt_direct(tiles, &policy_net);
+        strategies[idx_direct].push(s, c, t);
         let gt_s = s;
+        if args.vs_heuristic {
+            heuristic_scores.push(result(&play_heuristic_game(tiles, args.line_boost)));
+        }
 
-        let (s, c) = play_gt_line_heuristic(tiles, &policy_net, &create_deck(), args.line_boost);
-        strategies[idx_lines].push(s, c);
+        let (s, c, t) = play_gt_line_heuristic(tiles, &policy_net, &create_deck(), args.line_boost);
+        strategies[idx_lines].push(s, c, t);
         let line_s = s;
 
-        let (s, c) = play_gt_lines_rows(tiles, &policy_net, &create_deck(), args.line_boost, args.row_boost);
-        strategies[idx_rows].push(s, c);
+        let (s, c, t) = play_gt_lines_rows(tiles, &policy_net, &create_deck(), args.line_boost, args.row_boost);
+        strategies[idx_rows].push(s, c, t);
         let row_s = s;
 
         let mut br_s = 0;
         if let Some(idx) = idx_boost_roll {
-            let (s, c) = play_gt_boost_rollout(tiles, &policy_net, args.line_boost, args.boost_beam_k, args.boost_rollouts, &mut rng);
-            strategies[idx].push(s, c);
+            let (s, c, t) = play_gt_boost_rollout(tiles, &policy_net, args.line_boost, args.boost_beam_k, args.boost_rollouts, &mut rng);
+            strategies[idx].push(s, c, t);
             br_s = s;
         }
 
         let mut mcts_s = 0;
         if let Some(idx) = idx_mcts {
-            let (s, c) = play_gt_mcts_boosted(tiles, &policy_net, args.line_boost, args.mcts_sims, &mut rng);
-            strategies[idx].push(s, c);
+            let (s, c, t) = play_gt_mcts_boosted(tiles, &policy_net, args.line_boost, args.mcts_sims, &mut rng);
+            strategies[idx].push(s, c, t);
             mcts_s = s;
         }
 
         let mut v1b_s = 0;
         if let Some(idx) = idx_v1_beam {
-            let (s, c) = play_gt_v1_beam(tiles, &policy_net, args.line_boost, args.v1_beam_k, args.v1_beam_rollouts, args.v1_bonus, &mut rng);
-            strategies[idx].push(s, c);
+            let (s, c, t) = play_gt_v1_beam(tiles, &policy_net, args.line_boost, args.v1_beam_k, args.v1_beam_rollouts, args.v1_bonus, &mut rng);
+            strategies[idx].push(s, c, t);
             v1b_s = s;
         }
 
+        let mut greedy_s = 0;
+        if let Some(idx) = idx_greedy {
+            let (s, c, t) = play_gt_greedy_blend(tiles, &policy_net, args.line_boost, args.greedy_weight);
+            strategies[idx].push(s, c, t);
+            greedy_s = s;
+        }
+
         let mut beam_s = 0;
         let mut bl_s = 0;
         if let Some(idx) = idx_beam {
-            let (s, c) = play_gt_beam_search(tiles, &policy_net, args.beam_width, args.beam_rollouts, &mut rng);
-            strategies[idx].push(s, c);
+            let (s, c, t) = play_gt_beam_search(tiles, &policy_net, args.beam_width, args.beam_rollouts, &mut rng);
+            strategies[idx].push(s, c, t);
             beam_s = s;
         }
         if let Some(idx) = idx_beam_lines {
-            let (s, c) = play_gt_beam_line_boost(tiles, &policy_net, args.beam_width, args.beam_rollouts, args.line_boost, &mut rng);
-            strategies[idx].push(s, c);
+            let (s, c, t) = play_gt_beam_line_boost(tiles, &policy_net, args.beam_width, args.beam_rollouts, args.line_boost, &mut rng);
+            strategies[idx].push(s, c, t);
             bl_s = s;
         }
 
@@ -1260,6 +1314,7 @@ fn main() {
         if use_boost_rollouts { prog.push_str(&format!(" B+R={}", br_s)); }
         if use_mcts { prog.push_str(&format!(" MCTS={}", mcts_s)); }
         if use_v1_beam { prog.push_str(&format!(" V1B={}", v1b_s)); }
+        if use_greedy { prog.push_str(&format!(" Greedy={}", greedy_s)); }
         if !args.direct_only { prog.push_str(&format!(" Beam={} B+L={}", beam_s, bl_s)); }
         prog.push_str(&format!(" ({:.1}s)    ", game_start.elapsed().as_secs_f64()));
         print!("{}", prog);
@@ -1287,6 +1342,9 @@ fn main() {
         if use_v1_beam {
             v.push(StrategyStats::new(&v1_beam_label));
         }
+        if use_greedy {
+            v.push(StrategyStats::new(&greedy_label));
+        }
         if !args.direct_only {
             v.push(StrategyStats::new(&beam_label));
             v.push(StrategyStats::new(&beam_lines_label));
@@ -1294,68 +1352,83 @@ fn main() {
         v
     };
 
-    if args.random_games > 0 {
+    if num_random > 0 {
         println!(
             "Running benchmark on {} random tile sequences...\n  line={:.1} row={:.1}{}\n",
-            args.random_games,
+            num_random,
             args.line_boost, args.row_boost,
             if use_boost_rollouts { format!(" | boost-rollouts: K={} M={}", args.boost_beam_k, args.boost_rollouts) } else { String::new() },
         );
         let rg_start = Instant::now();
 
-        for i in 0..args.random_games {
-            let tiles = generate_random_tile_sequence(&mut rng);
+        for i in 0..num_random {
+            let tiles = match &fixed_sequences {
+                Some(sequences) => sequences[i].clone(),
+                None => generate_random_tile_sequence(&mut rng),
+            };
+            played_sequences.push(tiles.clone());
 
-            let (s, c) = play_gt_direct(&tiles, &policy_net);
-            rand_strategies[idx_direct].push(s, c);
+            let (s, c, t) = play_gt_direct(&tiles, &policy_net);
+            rand_strategies[idx_direct].push(s, c, t);
             let gt_s = s;
+            if args.vs_heuristic {
+                rand_heuristic_scores.push(result(&play_heuristic_game(&tiles, args.line_boost)));
+            }
 
-            let (s, c) = play_gt_line_heuristic(&tiles, &policy_net, &create_deck(), args.line_boost);
-            rand_strategies[idx_lines].push(s, c);
+            let (s, c, t) = play_gt_line_heuristic(&tiles, &policy_net, &create_deck(), args.line_boost);
+            rand_strategies[idx_lines].push(s, c, t);
             let line_s = s;
 
-            let (s, c) = play_gt_lines_rows(&tiles, &policy_net, &create_deck(), args.line_boost, args.row_boost);
-            rand_strategies[idx_rows].push(s, c);
+            let (s, c, t) = play_gt_lines_rows(&tiles, &policy_net, &create_deck(), args.line_boost, args.row_boost);
+            rand_strategies[idx_rows].push(s, c, t);
             let row_s = s;
 
             let mut br_s = 0;
             if let Some(idx) = idx_boost_roll {
-                let (s, c) = play_gt_boost_rollout(&tiles, &policy_net, args.line_boost, args.boost_beam_k, args.boost_rollouts, &mut rng);
-                rand_strategies[idx].push(s, c);
+                let (s, c, t) = play_gt_boost_rollout(&tiles, &policy_net, args.line_boost, args.boost_beam_k, args.boost_rollouts, &mut rng);
+                rand_strategies[idx].push(s, c, t);
                 br_s = s;
             }
 
             let mut mcts_s = 0;
             if let Some(idx) = idx_mcts {
-                let (s, c) = play_gt_mcts_boosted(&tiles, &policy_net, args.line_boost, args.mcts_sims, &mut rng);
-                rand_strategies[idx].push(s, c);
+                let (s, c, t) = play_gt_mcts_boosted(&tiles, &policy_net, args.line_boost, args.mcts_sims, &mut rng);
+                rand_strategies[idx].push(s, c, t);
                 mcts_s = s;
             }
 
             let mut v1b_s = 0;
             if let Some(idx) = idx_v1_beam {
-                let (s, c) = play_gt_v1_beam(&tiles, &policy_net, args.line_boost, args.v1_beam_k, args.v1_beam_rollouts, args.v1_bonus, &mut rng);
-                rand_strategies[idx].push(s, c);
+                let (s, c, t) = play_gt_v1_beam(&tiles, &policy_net, args.line_boost, args.v1_beam_k, args.v1_beam_rollouts, args.v1_bonus, &mut rng);
+                rand_strategies[idx].push(s, c, t);
                 v1b_s = s;
             }
 
+            let mut greedy_s = 0;
+            if let Some(idx) = idx_greedy {
+                let (s, c, t) = play_gt_greedy_blend(&tiles, &policy_net, args.line_boost, args.greedy_weight);
+                rand_strategies[idx].push(s, c, t);
+                greedy_s = s;
+            }
+
             let mut beam_s = 0;
             let mut bl_s = 0;
             if let Some(idx) = idx_beam {
-                let (s, c) = play_gt_beam_search(&tiles, &policy_net, args.beam_width, args.beam_rollouts, &mut rng);
-                rand_strategies[idx].push(s, c);
+                let (s, c, t) = play_gt_beam_search(&tiles, &policy_net, args.beam_width, args.beam_rollouts, &mut rng);
+                rand_strategies[idx].push(s, c, t);
                 beam_s = s;
             }
             if let Some(idx) = idx_beam_lines {
-                let (s, c) = play_gt_beam_line_boost(&tiles, &policy_net, args.beam_width, args.beam_rollouts, args.line_boost, &mut rng);
-                rand_strategies[idx].push(s, c);
+                let (s, c, t) = play_gt_beam_line_boost(&tiles, &policy_net, args.beam_width, args.beam_rollouts, args.line_boost, &mut rng);
+                rand_strategies[idx].push(s, c, t);
                 bl_s = s;
             }
 
-            let mut prog = format!("\r  [{}/{}] GT={} Line={} L+R={}", i+1, args.random_games, gt_s, line_s, row_s);
+            let mut prog = format!("\r  [{}/{}] GT={} Line={} L+R={}", i+1, num_random, gt_s, line_s, row_s);
             if use_boost_rollouts { prog.push_str(&format!(" B+R={}", br_s)); }
             if use_mcts { prog.push_str(&format!(" MCTS={}", mcts_s)); }
             if use_v1_beam { prog.push_str(&format!(" V1B={}", v1b_s)); }
+            if use_greedy { prog.push_str(&format!(" Greedy={}", greedy_s)); }
             if !args.direct_only { prog.push_str(&format!(" Beam={} B+L={}", beam_s, bl_s)); }
             prog.push_str("    ");
             print!("{}", prog);
@@ -1366,6 +1439,13 @@ fn main() {
         );
     }
 
+    if let Some(path) = &args.dump_sequences {
+        match save_sequences(&played_sequences, path) {
+            Ok(()) => println!("Saved {} tile sequences to {}\n", played_sequences.len(), path),
+            Err(e) => eprintln!("Warning: failed to save tile sequences: {}", e),
+        }
+    }
+
     // ─── Print results ────────────────────────────────────────────
 
     print_summary_table("Recorded Games", &strategies);
@@ -1374,9 +1454,24 @@ fn main() {
         print_summary_table("Random Games", &rand_strategies);
     }
 
+    if args.vs_heuristic {
+        print_vs_heuristic("Recorded Games", &strategies[idx_direct].scores, &heuristic_scores);
+        print_vs_heuristic("Random Games", &rand_strategies[idx_direct].scores, &rand_heuristic_scores);
+    }
+
     // ─── Save CSV ─────────────────────────────────────────────────
 
     save_results_csv(&game_ids, &strategies);
+
+    if let Some(path) = &args.json_out {
+        // Random games when played: they are the ones the seed reproduces
+        let reported = if rand_strategies[0].scores.is_empty() { &strategies } else { &rand_strategies };
+        let report = benchmark_report(reported, &args.model_path, args.seed);
+        match report.save_json(path) {
+            Ok(()) => println!("JSON summary saved to: {}\n", path),
+            Err(e) => eprintln!("Failed to write {}: {}", path, e),
+        }
+    }
 }
 
 // ─── Display ─────────────────────────────────────────────────────
@@ -1430,6 +1525,40 @@ fn print_summary_table(title: &str, strategies: &[StrategyStats]) {
         print_lc_row(&strat.name, &strat.completions);
     }
     println!();
+
+    println!(
+        "Score vs move latency (ms):\n{:<20} | {:>9} | {:>8} | {:>8} | {:>8} | {:>8}",
+        "Strategy", "Avg Score", "Mean", "p50", "p95", "p99"
+    );
+    println!(
+        "{:-<20}-+-{:-<9}-+-{:-<8}-+-{:-<8}-+-{:-<8}-+-{:-<8}",
+        "", "", "", "", "", ""
+    );
+    for strat in strategies {
+        print_latency_row(&strat.name, &strat.scores, &strat.latencies);
+    }
+    println!();
+}
+
+/// Head-to-head against the fixed heuristic: same tile sequence, higher score wins.
+fn print_vs_heuristic(title: &str, model_scores: &[i32], heuristic_scores: &[i32]) {
+    let n = model_scores.len().min(heuristic_scores.len());
+    if n == 0 {
+        return;
+    }
+    let pairs = model_scores.iter().zip(heuristic_scores.iter());
+    let wins = pairs.clone().filter(|(&m, &h)| m > h).count();
+    let draws = pairs.clone().filter(|(&m, &h)| m == h).count();
+    let margin = pairs.map(|(&m, &h)| (m - h) as f64).sum::<f64>() / n as f64;
+
+    println!("GT Direct vs line heuristic — {} ({} games)", title, n);
+    println!(
+        "  Heuristic avg: {:.1} | Win-rate: {:.1}% (draws {:.1}%) | Avg margin: {:+.1}\n",
+        avg(&heuristic_scores[..n]),
+        wins as f64 / n as f64 * 100.0,
+        draws as f64 / n as f64 * 100.0,
+        margin,
+    );
 }
 
 fn print_strategy_row(name: &str, scores: &[i32], beats_gt: Option<(usize, usize)>) {
@@ -1456,6 +1585,25 @@ fn print_lc_row(name: &str, completions: &[LineCompletions]) {
     println!("{:<20} | {:>7.1} | {:>8.1} | {:>8.1} | {:>11.1}", name, v1, v2, v3, tot);
 }
 
+fn print_latency_row(name: &str, scores: &[i32], latencies: &[Duration]) {
+    if latencies.is_empty() { return; }
+    let mut ms: Vec<f64> = latencies.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+    ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mean = ms.iter().sum::<f64>() / ms.len() as f64;
+    println!(
+        "{:<20} | {:>9.1} | {:>8.2} | {:>8.2} | {:>8.2} | {:>8.2}",
+        name, avg(scores), mean,
+        percentile(&ms, 50.0), percentile(&ms, 95.0), percentile(&ms, 99.0)
+    );
+}
+
+/// Nearest-rank percentile of an ascending-sorted slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() { return 0.0; }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 fn avg(scores: &[i32]) -> f64 {
     if scores.is_empty() { return 0.0; }
     scores.iter().sum::<i32>() as f64 / scores.len() as f64
@@ -1468,6 +1616,24 @@ fn median(scores: &[i32]) -> i32 {
     sorted[sorted.len() / 2]
 }
 
+/// Per-strategy summary; wins are counted against the first strategy (GT Direct).
+fn benchmark_report(strategies: &[StrategyStats], model_path: &str, seed: u64) -> BenchmarkReport {
+    let baseline = &strategies[0].scores;
+    let models = strategies
+        .iter()
+        .map(|strat| {
+            let wins = strat.scores.iter().zip(baseline).filter(|(s, b)| s > b).count();
+            ModelSummary::from_scores(&strat.name, model_path, &strat.scores, wins)
+        })
+        .collect();
+    BenchmarkReport {
+        tool: "benchmark_strategies".to_string(),
+        seed,
+        num_games: baseline.len(),
+        models,
+    }
+}
+
 fn save_results_csv(game_ids: &[String], strategies: &[StrategyStats]) {
     let path = "benchmark_results.csv";
     let mut file = match File::create(path) {
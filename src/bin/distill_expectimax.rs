@@ -26,7 +26,9 @@ use take_it_easy::neural::device_util::{check_cuda, parse_device};
 use take_it_easy::neural::graph_transformer::{
     GraphTransformerPolicyNet, GraphTransformerValueNet,
 };
-use take_it_easy::neural::model_io::{load_varstore, save_varstore};
+use take_it_easy::neural::model_io::{
+    load_varstore, save_varstore_with_meta, ModelMeta, GT_POLICY_ARCH,
+};
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use take_it_easy::scoring::scoring::result;
 use take_it_easy::strategy::expectimax::{
@@ -34,6 +36,8 @@ use take_it_easy::strategy::expectimax::{
     expectimax_3ply_select, ExpectimaxConfig,
 };
 use take_it_easy::strategy::gt_boost::line_boost;
+use take_it_easy::training::early_stopping::EarlyStopping;
+use take_it_easy::training::value_normalization::ScoreNormalization;
 
 #[derive(Parser, Debug)]
 #[command(name = "distill_expectimax")]
@@ -50,6 +54,10 @@ struct Args {
     #[arg(long, default_value_t = 80)]
     epochs: usize,
 
+    /// Stop after this many epochs without validation improvement (default: run every epoch)
+    #[arg(long)]
+    patience: Option<usize>,
+
     /// Learning rate
     #[arg(long, default_value_t = 0.0005)]
     lr: f64,
@@ -122,11 +130,11 @@ struct Args {
     #[arg(long, default_value_t = 500)]
     eval_games: usize,
 
-    /// Score normalization mean (for expectimax config)
+    /// Score normalization mean, if the value model has no saved constants
     #[arg(long, default_value_t = 140.0)]
     score_mean: f64,
 
-    /// Score normalization std (for expectimax config)
+    /// Score normalization std, if the value model has no saved constants
     #[arg(long, default_value_t = 40.0)]
     score_std: f64,
 
@@ -232,7 +240,14 @@ fn main() {
         eprintln!("Error loading value net: {}", e);
         return;
     }
-    println!("Loaded value net from {}", args.value_path);
+    let norm = ScoreNormalization::load(&args.value_path).unwrap_or(ScoreNormalization {
+        mean: args.score_mean,
+        std: args.score_std,
+    });
+    println!(
+        "Loaded value net from {} (score norm: mean={:.2}, std={:.2})",
+        args.value_path, norm.mean, norm.std
+    );
 
     // ── Phase 1: Generate data with hybrid strategy ──
     println!("\n--- Phase 1: Data Generation ({} games) ---\n", args.num_games);
@@ -240,11 +255,12 @@ fn main() {
     let ex_config = ExpectimaxConfig {
         device,
         boost: args.boost,
-        score_mean: args.score_mean,
-        score_std: args.score_std,
+        score_mean: norm.mean,
+        score_std: norm.std,
         min_turn: args.min_turn,
         top_k_ply1: 3,
         top_k_ply2: 2,
+        blunder_epsilon: 0.0,
     };
 
     let gen_start = Instant::now();
@@ -271,6 +287,9 @@ fn main() {
     let student_policy = GraphTransformerPolicyNet::new(
         &student_vs, 47, args.embed_dim, args.num_layers, args.num_heads, args.dropout,
     );
+    let student_meta = ModelMeta::graph_transformer(
+        GT_POLICY_ARCH, args.embed_dim, args.num_layers, args.num_heads,
+    );
 
     // Initialize from teacher weights
     if args.init_from_teacher && Path::new(&args.policy_path).exists() {
@@ -312,7 +331,7 @@ fn main() {
     println!("Train: {} samples, Val: {}", train_indices.len(), val_indices.len());
 
     let train_start = Instant::now();
-    let mut best_val_loss = f64::INFINITY;
+    let mut early_stopping = EarlyStopping::new(args.patience);
     let mut best_val_acc = 0.0;
 
     for epoch in 0..args.epochs {
@@ -424,10 +443,9 @@ fn main() {
         let val_acc = val_correct as f64 / val_count as f64 * 100.0;
         let epoch_time = epoch_start.elapsed().as_secs_f32();
 
-        let saved = if val_loss < best_val_loss {
-            best_val_loss = val_loss;
+        let saved = if early_stopping.record(val_loss) {
             best_val_acc = val_acc;
-            if let Err(e) = save_varstore(&student_vs, &args.save_path) {
+            if let Err(e) = save_varstore_with_meta(&student_vs, &args.save_path, &student_meta) {
                 eprintln!("Warning: failed to save: {}", e);
             }
             true
@@ -445,10 +463,20 @@ fn main() {
             }
             println!();
         }
+
+        if early_stopping.should_stop() {
+            println!(
+                "\nEarly stopping at epoch {}: no val loss improvement for {} epochs",
+                epoch + 1,
+                early_stopping.epochs_without_improvement()
+            );
+            break;
+        }
     }
 
     let train_time = train_start.elapsed().as_secs_f32();
     println!("\nTraining complete in {:.1}s", train_time);
+    let best_val_loss = early_stopping.best_val_loss();
     println!("Best val loss: {:.4}, val acc: {:.1}%", best_val_loss, best_val_acc);
     println!("Model saved to: {}", args.save_path);
 
@@ -8,9 +8,14 @@
 //!   --mode multiplayer   Full 2-player journey (create/join/poll/play/finish)
 //!   --mode real-game     "Jeu Réel" mode (GetAiMove focus)
 //!   --mode errors        Error-path coverage (invalid inputs)
-//!   --mode all           Run solo + multiplayer + real-game + errors
+//!   --mode short-game    Solo game limited to 10 turns (partial board scoring)
+//!   --mode puzzle        Solo game starting from a 15-tile board (final 4 turns)
+//!   --mode replay        Recorded games (--game-csv) replayed through GetAiMove
+//!   --mode reconnect     Solo game disconnected mid-game, resumed from the state feed
+//!   --mode all           Run solo + multiplayer + real-game + errors + short-game + puzzle + reconnect
 //!   --mode stress        N concurrent solo games with metrics
 
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
@@ -20,15 +25,20 @@ use rand::seq::SliceRandom;
 use serde_json::Value;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
+use tonic::transport::Channel;
 
+use take_it_easy::game::tile::Tile;
 use take_it_easy::generated::takeiteasygame::v1::{
     create_session_response, join_session_response, make_move_response,
     game_service_client::GameServiceClient,
     session_service_client::SessionServiceClient,
     CreateSessionRequest, GetAiMoveRequest, GetAvailableMovesRequest,
-    GetGameStateRequest, GetSessionStateRequest, JoinSessionRequest,
+    GetGameStateDeltaRequest, GetGameStateRequest, GetSessionStateRequest, JoinSessionRequest,
     MakeMoveRequest, SetReadyRequest, StartTurnRequest,
 };
+use take_it_easy::recording::csv_writer::{load_games_from_csv, LoadedMoveRecord};
+use take_it_easy::recording::game_record::decode_plateau_value;
+use take_it_easy::recording::PlayerType;
 
 // ---------------------------------------------------------------------------
 // CLI
@@ -41,7 +51,7 @@ struct Cli {
     #[arg(long, default_value = "http://[::1]:50051")]
     url: String,
 
-    /// Test mode: solo | multiplayer | real-game | errors | all | stress
+    /// Test mode: solo | multiplayer | real-game | errors | short-game | puzzle | replay | reconnect | all | stress
     #[arg(long, default_value = "solo")]
     mode: String,
 
@@ -53,6 +63,10 @@ struct Cli {
     #[arg(long, default_value_t = 1)]
     total_games: usize,
 
+    /// Recorded games CSV (replay mode)
+    #[arg(long, default_value = "")]
+    game_csv: String,
+
     /// Verbose output
     #[arg(long)]
     verbose: bool,
@@ -112,6 +126,19 @@ impl TestResults {
 //   "Rejouer" → new CreateSession
 
 async fn run_solo(url: &str, verbose: bool) -> Result<(i32, u128), BoxError> {
+    let (final_score, elapsed, _) = run_solo_game(url, verbose, 0, "").await?;
+    Ok((final_score, elapsed))
+}
+
+/// Solo journey with a configurable game length (`num_turns` = 0 → 19 turns),
+/// optionally from a compact `starting_board` (empty = empty board).
+/// Returns (final score, elapsed ms, turns played until game over).
+async fn run_solo_game(
+    url: &str,
+    verbose: bool,
+    num_turns: i32,
+    starting_board: &str,
+) -> Result<(i32, u128, usize), BoxError> {
     let start = Instant::now();
 
     let mut session = SessionServiceClient::connect(url.to_string()).await?;
@@ -123,6 +150,14 @@ async fn run_solo(url: &str, verbose: bool) -> Result<(i32, u128), BoxError> {
             player_name: "e2e-solo".into(),
             max_players: 2,
             game_mode: "single-player".into(),
+            num_turns,
+            ai_think_delay_ms: 0,
+            starting_board: starting_board.to_string(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
         })
         .await?
         .into_inner();
@@ -177,9 +212,11 @@ async fn run_solo(url: &str, verbose: bool) -> Result<(i32, u128), BoxError> {
         }
     }
 
-    // ── Game Board: 19 turns ───────────────────────────────────────────
+    // ── Game Board: up to 19 turns ─────────────────────────────────────
     let mut final_score = 0i32;
+    let mut turns_played = 0usize;
     for turn in 0..19 {
+        turns_played = turn + 1;
         // StartTurn (frontend: "startTurn" port)
         let turn_resp = game
             .start_turn(StartTurnRequest {
@@ -307,7 +344,7 @@ async fn run_solo(url: &str, verbose: bool) -> Result<(i32, u128), BoxError> {
     }
 
     let elapsed = start.elapsed().as_millis();
-    Ok((final_score, elapsed))
+    Ok((final_score, elapsed, turns_played))
 }
 
 // ---------------------------------------------------------------------------
@@ -342,6 +379,14 @@ async fn run_solo_e2e(url: &str, verbose: bool) -> bool {
             player_name: "e2e-solo".into(),
             max_players: 2,
             game_mode: "single-player".into(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
         })
         .await;
 
@@ -603,6 +648,14 @@ async fn run_solo_e2e(url: &str, verbose: bool) -> bool {
             player_name: "e2e-solo-replay".into(),
             max_players: 2,
             game_mode: "single-player".into(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
         })
         .await
     {
@@ -658,6 +711,14 @@ async fn run_multiplayer_e2e(url: &str, total_games: usize, verbose: bool) -> bo
                 player_name: format!("e2e-p1-g{}", game_num + 1),
                 max_players: 3,
                 game_mode: "multiplayer".into(),
+                num_turns: 0,
+                ai_think_delay_ms: 0,
+                starting_board: String::new(),
+                scoring_rules: String::new(),
+                opponent: String::new(),
+                tile_draw: String::new(),
+                tile_seed: 0,
+                tile_sequence: String::new(),
             })
             .await;
 
@@ -926,11 +987,10 @@ async fn run_real_game_e2e(url: &str, total_games: usize, verbose: bool) -> bool
     };
 
     // All 27 valid tiles: digit1 ∈ {1,5,9}, digit2 ∈ {2,6,7}, digit3 ∈ {3,4,8}
-    let all_tiles: Vec<&str> = vec![
-        "123", "124", "128", "163", "164", "168", "173", "174", "178",
-        "523", "524", "528", "563", "564", "568", "573", "574", "578",
-        "923", "924", "928", "963", "964", "968", "973", "974", "978",
-    ];
+    let all_tiles: Vec<String> = Tile::all_valid()
+        .iter()
+        .map(|t| format!("{}{}{}", t.0, t.1, t.2))
+        .collect();
 
     let mut rng = rand::rng();
 
@@ -942,7 +1002,7 @@ async fn run_real_game_e2e(url: &str, total_games: usize, verbose: bool) -> bool
         // Shuffle and pick 19 tiles (like a real deck draw)
         let mut deck = all_tiles.clone();
         deck.shuffle(&mut rng);
-        let tiles: Vec<&str> = deck.into_iter().take(19).collect();
+        let tiles: Vec<String> = deck.into_iter().take(19).collect();
 
         let mut board_state: Vec<String> = vec!["".to_string(); 19];
         let mut game_ok = true;
@@ -1021,13 +1081,638 @@ async fn run_real_game_e2e(url: &str, total_games: usize, verbose: bool) -> bool
     results.summary()
 }
 
+// ---------------------------------------------------------------------------
+// Short-game E2E — partial game of `num_turns` turns
+// ---------------------------------------------------------------------------
+
+async fn run_short_game_e2e(url: &str, verbose: bool) -> bool {
+    const SHORT_TURNS: i32 = 10;
+    println!("=== SHORT GAME E2E TEST ===");
+    println!("  Simulates: Solo with num_turns={} -> Game Over -> partial score", SHORT_TURNS);
+    println!();
+
+    let mut results = TestResults::new();
+
+    match run_solo_game(url, verbose, SHORT_TURNS, "").await {
+        Ok((score, elapsed, turns_played)) => {
+            if turns_played == SHORT_TURNS as usize {
+                results.pass(&format!("Game over at turn {}", turns_played));
+            } else {
+                results.fail(
+                    "Game over at turn 10",
+                    &format!("game ended after {} turns", turns_played),
+                );
+            }
+            // Partial board: the score must still be a valid board score
+            if (0..=307).contains(&score) {
+                results.pass(&format!("Partial score valid ({}, {}ms)", score, elapsed));
+            } else {
+                results.fail("Partial score valid", &format!("score {} out of range", score));
+            }
+        }
+        Err(e) => results.fail("Short game", &e.to_string()),
+    }
+
+    // Bounds: num_turns > 19 must be rejected
+    match SessionServiceClient::connect(url.to_string()).await {
+        Ok(mut session) => {
+            let resp = session
+                .create_session(CreateSessionRequest {
+                    player_name: "e2e-short".into(),
+                    max_players: 2,
+                    game_mode: "single-player".into(),
+                    num_turns: 20,
+                    ai_think_delay_ms: 0,
+                    starting_board: String::new(),
+                    scoring_rules: String::new(),
+                    opponent: String::new(),
+                    tile_draw: String::new(),
+                    tile_seed: 0,
+                    tile_sequence: String::new(),
+                })
+                .await;
+            match resp.map(|r| r.into_inner().result) {
+                Ok(Some(create_session_response::Result::Error(e))) if e.code == "INVALID_NUM_TURNS" => {
+                    results.pass("num_turns=20 rejected")
+                }
+                Ok(other) => results.fail("num_turns=20 rejected", &format!("{:?}", other)),
+                Err(e) => results.fail("num_turns=20 rejected", &e.to_string()),
+            }
+        }
+        Err(e) => results.fail("connect", &e.to_string()),
+    }
+
+    results.summary()
+}
+
+// ---------------------------------------------------------------------------
+// Puzzle E2E — solo game resumed from a pre-filled board
+// ---------------------------------------------------------------------------
+
+/// First 15 deck tiles on cells 0..15, cells 15..19 left to play.
+const PUZZLE_BOARD: &str = "123,168,173,163,128,124,174,164,178,523,568,573,563,528,524,.,.,.,.";
+
+async fn run_puzzle_e2e(url: &str, verbose: bool) -> bool {
+    println!("=== PUZZLE E2E TEST ===");
+    println!("  Simulates: Solo from a 15-tile starting board -> 4 turns -> Game Over");
+    println!();
+
+    let mut results = TestResults::new();
+
+    match run_solo_game(url, verbose, 0, PUZZLE_BOARD).await {
+        Ok((score, elapsed, turns_played)) => {
+            if turns_played == 4 {
+                results.pass("Game over after the final 4 turns");
+            } else {
+                results.fail(
+                    "Game over after the final 4 turns",
+                    &format!("game ended after {} turns", turns_played),
+                );
+            }
+            if (0..=307).contains(&score) {
+                results.pass(&format!("Final score valid ({}, {}ms)", score, elapsed));
+            } else {
+                results.fail(
+                    "Final score valid",
+                    &format!("score {} out of range", score),
+                );
+            }
+        }
+        Err(e) => results.fail("Puzzle game", &e.to_string()),
+    }
+
+    // A tile placed twice is not a legal board
+    let duplicated = PUZZLE_BOARD.replacen("168", "123", 1);
+    match SessionServiceClient::connect(url.to_string()).await {
+        Ok(mut session) => {
+            let resp = session
+                .create_session(CreateSessionRequest {
+                    player_name: "e2e-puzzle".into(),
+                    max_players: 2,
+                    game_mode: "single-player".into(),
+                    num_turns: 0,
+                    ai_think_delay_ms: 0,
+                    starting_board: duplicated,
+                    scoring_rules: String::new(),
+                    opponent: String::new(),
+                    tile_draw: String::new(),
+                    tile_seed: 0,
+                    tile_sequence: String::new(),
+                })
+                .await;
+            match resp.map(|r| r.into_inner().result) {
+                Ok(Some(create_session_response::Result::Error(e))) if e.code == "INVALID_STARTING_BOARD" => {
+                    results.pass("Duplicated tile rejected")
+                }
+                Ok(other) => results.fail("Duplicated tile rejected", &format!("{:?}", other)),
+                Err(e) => results.fail("Duplicated tile rejected", &e.to_string()),
+            }
+        }
+        Err(e) => results.fail("connect", &e.to_string()),
+    }
+
+    results.summary()
+}
+
+// ---------------------------------------------------------------------------
+// Replay E2E — recorded AI decisions vs. the deployed model
+// ---------------------------------------------------------------------------
+
+/// Board of a recording row as sent by the frontend ("" = empty cell).
+fn recorded_board_state(record: &LoadedMoveRecord) -> Vec<String> {
+    record
+        .plateau
+        .iter()
+        .map(|&encoded| {
+            if encoded == 0 {
+                String::new()
+            } else {
+                let (a, b, c) = decode_plateau_value(encoded);
+                format!("{}{}{}", a, b, c)
+            }
+        })
+        .collect()
+}
+
+/// Replays every AI move of the recording: same tile, same board before the
+/// move, and checks the server recommends the recorded position.
+async fn run_replay_e2e(url: &str, game_csv: &str, verbose: bool) -> bool {
+    println!("=== REPLAY E2E TEST ===");
+    println!(
+        "  Replays: {} -> GetAiMove per recorded AI move -> agreement rate",
+        game_csv
+    );
+    println!();
+
+    let mut results = TestResults::new();
+
+    if game_csv.is_empty() {
+        results.fail("load recording", "--game-csv is required in replay mode");
+        return results.summary();
+    }
+    let records = match load_games_from_csv(game_csv) {
+        Ok(records) => records,
+        Err(e) => {
+            results.fail("load recording", &e.to_string());
+            return results.summary();
+        }
+    };
+    let ai_moves: Vec<&LoadedMoveRecord> = records
+        .iter()
+        .filter(|r| r.player_type != PlayerType::Human)
+        .collect();
+    if ai_moves.is_empty() {
+        results.fail("load recording", "no AI move in the recording");
+        return results.summary();
+    }
+    results.pass(&format!("Loaded {} AI moves", ai_moves.len()));
+
+    let mut game = match GameServiceClient::connect(url.to_string()).await {
+        Ok(c) => c,
+        Err(e) => {
+            results.fail("connect", &e.to_string());
+            return results.summary();
+        }
+    };
+
+    let mut agreed = 0usize;
+    let mut mismatches: Vec<String> = Vec::new();
+
+    for record in &ai_moves {
+        let board_state = recorded_board_state(record);
+        let tile_code = format!("{}{}{}", record.tile.0, record.tile.1, record.tile.2);
+        let available: Vec<i32> = board_state
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.is_empty())
+            .map(|(i, _)| i as i32)
+            .collect();
+        let name = format!("GetAiMove (game {} turn {})", record.game_id, record.turn);
+
+        let inner = match game
+            .get_ai_move(GetAiMoveRequest {
+                tile_code: tile_code.clone(),
+                board_state: board_state.clone(),
+                available_positions: available,
+                turn_number: record.turn as i32,
+            })
+            .await
+        {
+            Ok(r) => r.into_inner(),
+            Err(e) => {
+                results.fail(&name, &e.to_string());
+                return results.summary();
+            }
+        };
+        if !inner.success {
+            let err = inner.error.map(|e| e.message).unwrap_or_default();
+            results.fail(&name, &err);
+            return results.summary();
+        }
+
+        let recommended = inner.recommended_position;
+        if recommended == record.position as i32 {
+            agreed += 1;
+            if verbose {
+                println!(
+                    "    game={} turn={}: tile={}, position {} (agree)",
+                    record.game_id, record.turn, tile_code, recommended
+                );
+            }
+        } else {
+            mismatches.push(format!(
+                "game={} turn={} tile={}: recorded {}, server {}\n        board [{}]",
+                record.game_id,
+                record.turn,
+                tile_code,
+                record.position,
+                recommended,
+                board_state
+                    .iter()
+                    .map(|t| if t.is_empty() { "." } else { t.as_str() })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+    }
+
+    let total = ai_moves.len();
+    let agreement = agreed as f64 / total as f64 * 100.0;
+    results.pass(&format!("Replayed {} AI moves", total));
+    println!();
+    println!("  Agreement: {}/{} ({:.1}%)", agreed, total, agreement);
+    if !mismatches.is_empty() {
+        println!("  Mismatches:");
+        for mismatch in &mismatches {
+            println!("    {}", mismatch);
+        }
+    }
+
+    results.summary()
+}
+
+// ---------------------------------------------------------------------------
+// Reconnect E2E — client dropped mid-game, state feed resumed
+// ---------------------------------------------------------------------------
+// The session_id + player_id returned by CreateSession are the credentials a
+// client reconnects with; GetGameStateDelta is the versioned state feed it
+// follows, and re-subscribes to from version 0 after reconnecting.
+
+/// Turns played before the simulated disconnect.
+const RECONNECT_AFTER_TURNS: usize = 8;
+
+/// Tiles of one player seen on the state feed: position → "5-3-7".
+type SeenPlacements = BTreeMap<i32, String>;
+
+/// Reads the state feed since `since_version`, recording the placements of
+/// `player_id`; a resync answer reloads them from the full state. Returns the
+/// version to resume from.
+async fn follow_state_feed(
+    game: &mut GameServiceClient<Channel>,
+    session_id: &str,
+    player_id: &str,
+    since_version: u64,
+    seen: &mut SeenPlacements,
+) -> Result<u64, BoxError> {
+    let resp = game
+        .get_game_state_delta(GetGameStateDeltaRequest {
+            session_id: session_id.to_string(),
+            since_version,
+        })
+        .await?
+        .into_inner();
+
+    if !resp.success {
+        let err_msg = resp
+            .error
+            .map(|e| format!("{}: {}", e.code, e.message))
+            .unwrap_or_else(|| "unknown".into());
+        return Err(format!("GetGameStateDelta failed: {}", err_msg).into());
+    }
+    if resp.resync {
+        // The placement log no longer covers since_version: rebuild from the full state
+        let state = game
+            .get_game_state(GetGameStateRequest {
+                session_id: session_id.to_string(),
+            })
+            .await?
+            .into_inner();
+        let board: Value = serde_json::from_str(&state.game_state)?;
+        seen.clear();
+        let tiles = board["player_plateaus"][player_id]["tiles"].as_array();
+        for (position, tile) in tiles.into_iter().flatten().enumerate() {
+            let values: Vec<i64> = tile
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_i64)
+                .collect();
+            if values.len() == 3 && values != [0, 0, 0] {
+                let tile = format!("{}-{}-{}", values[0], values[1], values[2]);
+                seen.insert(position as i32, tile);
+            }
+        }
+        return Ok(resp.version);
+    }
+    for placed in resp
+        .placed_tiles
+        .iter()
+        .filter(|p| p.player_id == player_id)
+    {
+        seen.insert(placed.position, placed.tile.clone());
+    }
+    Ok(resp.version)
+}
+
+/// StartTurn + MakeMove on the first available cell. Returns whether the
+/// game is over.
+async fn play_one_turn(
+    game: &mut GameServiceClient<Channel>,
+    session_id: &str,
+    player_id: &str,
+    verbose: bool,
+) -> Result<bool, BoxError> {
+    let turn_resp = game
+        .start_turn(StartTurnRequest {
+            session_id: session_id.to_string(),
+            forced_tile: String::new(),
+        })
+        .await?
+        .into_inner();
+
+    if !turn_resp.success {
+        let err_msg = turn_resp
+            .error
+            .map(|e| format!("{}: {}", e.code, e.message))
+            .unwrap_or_else(|| "unknown".into());
+        return Err(format!("StartTurn failed: {}", err_msg).into());
+    }
+
+    let position = pick_position(&turn_resp.game_state, player_id)?;
+    let move_resp = game
+        .make_move(MakeMoveRequest {
+            session_id: session_id.to_string(),
+            player_id: player_id.to_string(),
+            move_data: serde_json::json!({ "position": position }).to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+        .await?
+        .into_inner();
+
+    match move_resp.result {
+        Some(make_move_response::Result::Success(s)) => {
+            if verbose {
+                println!(
+                    "  [MakeMove] turn={}, position={}, game_over={}",
+                    turn_resp.turn_number, position, s.is_game_over
+                );
+            }
+            Ok(s.is_game_over)
+        }
+        Some(make_move_response::Result::Error(e)) => {
+            Err(format!("MakeMove error: {} - {}", e.code, e.message).into())
+        }
+        None => Err("MakeMove: empty response".into()),
+    }
+}
+
+async fn run_reconnect_game(
+    url: &str,
+    verbose: bool,
+    results: &mut TestResults,
+) -> Result<(), BoxError> {
+    let mut session = SessionServiceClient::connect(url.to_string()).await?;
+    let mut game = GameServiceClient::connect(url.to_string()).await?;
+
+    let resp = session
+        .create_session(CreateSessionRequest {
+            player_name: "e2e-reconnect".into(),
+            max_players: 2,
+            game_mode: "single-player".into(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        })
+        .await?
+        .into_inner();
+    let success = match resp.result {
+        Some(create_session_response::Result::Success(s)) => s,
+        Some(create_session_response::Result::Error(e)) => {
+            return Err(format!("CreateSession error: {} - {}", e.code, e.message).into());
+        }
+        None => return Err("CreateSession: empty response".into()),
+    };
+    let session_id = success.session_id;
+    let player_id = success.player_id;
+
+    session
+        .set_ready(SetReadyRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            ready: true,
+        })
+        .await?;
+
+    // ── Subscribe: one feed read per turn, as the frontend polls ────────
+    let mut seen = SeenPlacements::new();
+    let mut version = 0;
+    for turn in 0..RECONNECT_AFTER_TURNS {
+        if play_one_turn(&mut game, &session_id, &player_id, verbose).await? {
+            return Err(format!("Game over at turn {}, before the disconnect", turn + 1).into());
+        }
+        version = follow_state_feed(&mut game, &session_id, &player_id, version, &mut seen).await?;
+    }
+    if seen.len() == RECONNECT_AFTER_TURNS {
+        results.pass(&format!(
+            "Feed followed for {} turns (version {})",
+            seen.len(),
+            version
+        ));
+    } else {
+        results.fail(
+            "Feed followed before disconnect",
+            &format!(
+                "{} placements seen for {} turns",
+                seen.len(),
+                RECONNECT_AFTER_TURNS
+            ),
+        );
+    }
+    let turn_before = game
+        .get_game_state(GetGameStateRequest {
+            session_id: session_id.clone(),
+        })
+        .await?
+        .into_inner()
+        .current_turn;
+
+    // ── Disconnect: drop both channels mid-game ─────────────────────────
+    drop(session);
+    drop(game);
+    if verbose {
+        println!(
+            "  [Disconnect] after turn {} (version {})",
+            turn_before, version
+        );
+    }
+
+    // ── Reconnect with the session credentials ──────────────────────────
+    let mut session = SessionServiceClient::connect(url.to_string()).await?;
+    let mut game = GameServiceClient::connect(url.to_string()).await?;
+
+    let poll_resp = session
+        .get_session_state(GetSessionStateRequest {
+            session_id: session_id.clone(),
+        })
+        .await?
+        .into_inner();
+    match &poll_resp.game_state {
+        Some(gs) if gs.players.iter().any(|p| p.id == player_id) => {
+            results.pass("Reconnected: session still holds the player")
+        }
+        other => results.fail(
+            "Reconnected: session still holds the player",
+            &format!("{:?}", other.as_ref().map(|gs| gs.players.len())),
+        ),
+    }
+
+    // Re-subscribe from scratch: the restored board must match what was seen
+    let mut restored = SeenPlacements::new();
+    let restored_version =
+        follow_state_feed(&mut game, &session_id, &player_id, 0, &mut restored).await?;
+    if restored == seen && restored_version >= version {
+        results.pass(&format!(
+            "Restored board matches ({} tiles)",
+            restored.len()
+        ));
+    } else {
+        results.fail(
+            "Restored board matches",
+            &format!(
+                "restored {:?} at version {}, seen {:?} at version {}",
+                restored, restored_version, seen, version
+            ),
+        );
+    }
+    let turn_after = game
+        .get_game_state(GetGameStateRequest {
+            session_id: session_id.clone(),
+        })
+        .await?
+        .into_inner()
+        .current_turn;
+    if turn_after == turn_before {
+        results.pass(&format!("Restored turn matches ({})", turn_after));
+    } else {
+        results.fail(
+            "Restored turn matches",
+            &format!(
+                "turn {} before disconnect, {} after",
+                turn_before, turn_after
+            ),
+        );
+    }
+
+    // ── Invalid credentials are rejected ────────────────────────────────
+    let ghost_move = game
+        .make_move(MakeMoveRequest {
+            session_id: session_id.clone(),
+            player_id: "ghost-player".into(),
+            move_data: serde_json::json!({ "position": 0 }).to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+        .await;
+    match ghost_move.map(|r| r.into_inner().result) {
+        Ok(Some(make_move_response::Result::Success(_))) => results.fail(
+            "Unknown player rejected",
+            "MakeMove accepted a player not in the session",
+        ),
+        _ => results.pass("Unknown player rejected"),
+    }
+    let mut ghost_seen = SeenPlacements::new();
+    match follow_state_feed(
+        &mut game,
+        "nonexistent-session-id",
+        &player_id,
+        0,
+        &mut ghost_seen,
+    )
+    .await
+    {
+        Ok(_) => results.fail(
+            "Unknown session rejected",
+            "state feed served a nonexistent session",
+        ),
+        Err(_) => results.pass("Unknown session rejected"),
+    }
+
+    // ── Finish the game on the new connection ───────────────────────────
+    let mut game_over = false;
+    for _ in turn_after.max(0) as usize..19 {
+        if play_one_turn(&mut game, &session_id, &player_id, verbose).await? {
+            game_over = true;
+            break;
+        }
+    }
+    follow_state_feed(
+        &mut game,
+        &session_id,
+        &player_id,
+        restored_version,
+        &mut restored,
+    )
+    .await?;
+    let finished = game
+        .get_game_state(GetGameStateRequest {
+            session_id: session_id.clone(),
+        })
+        .await?
+        .into_inner()
+        .is_game_finished;
+    if game_over && finished && restored.len() == 19 {
+        results.pass("Game finished after reconnect (19 tiles)");
+    } else {
+        results.fail(
+            "Game finished after reconnect",
+            &format!(
+                "game_over={}, finished={}, tiles={}",
+                game_over,
+                finished,
+                restored.len()
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_reconnect_e2e(url: &str, verbose: bool) -> bool {
+    println!("=== RECONNECT E2E TEST ===");
+    println!(
+        "  Simulates: Solo game -> disconnect after {} turns -> reconnect -> re-subscribe -> Game Over",
+        RECONNECT_AFTER_TURNS
+    );
+    println!();
+
+    let mut results = TestResults::new();
+    if let Err(e) = run_reconnect_game(url, verbose, &mut results).await {
+        results.fail("Reconnect game", &e.to_string());
+    }
+    results.summary()
+}
+
 // ---------------------------------------------------------------------------
 // Error-path E2E — exercises error handling for all endpoints
 // ---------------------------------------------------------------------------
 
 async fn run_errors_e2e(url: &str, verbose: bool) -> bool {
     println!("=== ERROR PATH E2E TEST ===");
-    println!("  Tests: invalid sessions, wrong players, bad moves, double-ready");
+    println!("  Tests: invalid sessions, wrong players, bad moves, double-ready, out-of-turn moves, turn order");
     println!();
 
     let mut results = TestResults::new();
@@ -1187,6 +1872,14 @@ async fn run_errors_e2e(url: &str, verbose: bool) -> bool {
             player_name: "e2e-err".into(),
             max_players: 2,
             game_mode: "single-player".into(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
         })
         .await
     {
@@ -1234,9 +1927,138 @@ async fn run_errors_e2e(url: &str, verbose: bool) -> bool {
         }
     }
 
+    // ── 9. MakeMove when no turn is active (game not started) ──────────
+    // ── 10. MakeMove twice in the same turn ────────────────────────────
+    // Multiplayer: the turn stays open until P2 has played too
+    if let Ok(r) = session
+        .create_session(CreateSessionRequest {
+            player_name: "e2e-err-p1".into(),
+            max_players: 3,
+            game_mode: "multiplayer".into(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        })
+        .await
+    {
+        if let Some(create_session_response::Result::Success(s)) = r.into_inner().result {
+            // P1 alone, not ready: no tile has been announced yet
+            match make_move_outcome(&mut game, &s.session_id, &s.player_id, 0).await {
+                Some(code) => {
+                    if verbose { println!("    MakeMove before game start: {}", code); }
+                    results.pass("MakeMove (no active turn → error)");
+                }
+                None => results.fail("MakeMove (no active turn)", "should have failed"),
+            }
+
+            let p2 = session
+                .join_session(JoinSessionRequest {
+                    session_code: s.session_code.clone(),
+                    player_name: "e2e-err-p2".into(),
+                })
+                .await
+                .ok()
+                .and_then(|r| match r.into_inner().result {
+                    Some(join_session_response::Result::Success(j)) => Some(j.player_id),
+                    _ => None,
+                });
+
+            match p2 {
+                Some(player_id_2) => {
+                    for player_id in [&s.player_id, &player_id_2] {
+                        let _ = session
+                            .set_ready(SetReadyRequest {
+                                session_id: s.session_id.clone(),
+                                player_id: player_id.clone(),
+                                ready: true,
+                            })
+                            .await;
+                    }
+
+                    // Turn state machine: no move before StartTurn, one StartTurn per turn
+                    match make_move_outcome(&mut game, &s.session_id, &s.player_id, 0).await {
+                        Some(code) if code == "TURN_NOT_STARTED" => {
+                            results.pass("MakeMove (before StartTurn → TURN_NOT_STARTED)")
+                        }
+                        Some(code) => results.fail("MakeMove (before StartTurn)", &code),
+                        None => results.fail("MakeMove (before StartTurn)", "move accepted"),
+                    }
+                    for attempt in ["first", "second"] {
+                        let started = game
+                            .start_turn(StartTurnRequest {
+                                session_id: s.session_id.clone(),
+                                forced_tile: String::new(),
+                            })
+                            .await
+                            .map(|r| r.into_inner());
+                        let code = match started {
+                            Ok(r) if r.success => None,
+                            Ok(r) => Some(r.error.map(|e| e.code).unwrap_or_default()),
+                            Err(e) => Some(e.to_string()),
+                        };
+                        match (attempt, code) {
+                            ("first", None) => results.pass("StartTurn (multi, announces turn)"),
+                            ("second", Some(code)) if code == "TURN_ALREADY_STARTED" => {
+                                results.pass("StartTurn (twice → TURN_ALREADY_STARTED)")
+                            }
+                            (_, code) => results.fail(
+                                &format!("StartTurn ({} call)", attempt),
+                                &code.unwrap_or_else(|| "accepted".into()),
+                            ),
+                        }
+                    }
+
+                    match make_move_outcome(&mut game, &s.session_id, &s.player_id, 0).await {
+                        None => {
+                            match make_move_outcome(&mut game, &s.session_id, &s.player_id, 1).await {
+                                Some(code) => {
+                                    if verbose { println!("    Second MakeMove in turn: {}", code); }
+                                    results.pass("MakeMove (already moved this turn → error)");
+                                }
+                                None => results.fail("MakeMove (already moved this turn)", "second move accepted"),
+                            }
+                        }
+                        Some(code) => results.fail("MakeMove (first move of turn)", &code),
+                    }
+                }
+                None => results.fail("MakeMove (already moved this turn)", "P2 could not join"),
+            }
+        }
+    }
+
     results.summary()
 }
 
+/// Submit a move; `None` on success, `Some(error)` when rejected.
+async fn make_move_outcome(
+    game: &mut GameServiceClient<tonic::transport::Channel>,
+    session_id: &str,
+    player_id: &str,
+    position: usize,
+) -> Option<String> {
+    match game
+        .make_move(MakeMoveRequest {
+            session_id: session_id.to_string(),
+            player_id: player_id.to_string(),
+            move_data: format!(r#"{{"position": {}}}"#, position),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+        .await
+    {
+        Ok(r) => match r.into_inner().result {
+            Some(make_move_response::Result::Success(_)) => None,
+            Some(make_move_response::Result::Error(e)) => Some(e.code),
+            None => Some("empty response".to_string()),
+        },
+        Err(e) => Some(e.to_string()),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Stress test (unchanged from v1)
 // ---------------------------------------------------------------------------
@@ -1425,6 +2247,18 @@ async fn main() {
         "errors" => {
             if !run_errors_e2e(&cli.url, cli.verbose).await { 1 } else { 0 }
         }
+        "short-game" => {
+            if !run_short_game_e2e(&cli.url, cli.verbose).await { 1 } else { 0 }
+        }
+        "puzzle" => {
+            if !run_puzzle_e2e(&cli.url, cli.verbose).await { 1 } else { 0 }
+        }
+        "replay" => {
+            if !run_replay_e2e(&cli.url, &cli.game_csv, cli.verbose).await { 1 } else { 0 }
+        }
+        "reconnect" => {
+            if !run_reconnect_e2e(&cli.url, cli.verbose).await { 1 } else { 0 }
+        }
         "all" => {
             println!("Running all E2E tests...\n");
             let mut all_ok = true;
@@ -1436,6 +2270,12 @@ async fn main() {
             all_ok &= run_real_game_e2e(&cli.url, games.max(1), cli.verbose).await;
             println!();
             all_ok &= run_errors_e2e(&cli.url, cli.verbose).await;
+            println!();
+            all_ok &= run_short_game_e2e(&cli.url, cli.verbose).await;
+            println!();
+            all_ok &= run_puzzle_e2e(&cli.url, cli.verbose).await;
+            println!();
+            all_ok &= run_reconnect_e2e(&cli.url, cli.verbose).await;
 
             println!("\n========================================");
             if all_ok {
@@ -1452,7 +2292,7 @@ async fn main() {
         }
         other => {
             eprintln!(
-                "Unknown mode: '{}'. Use: solo, multiplayer, real-game, errors, all, stress",
+                "Unknown mode: '{}'. Use: solo, multiplayer, real-game, errors, short-game, puzzle, replay, reconnect, all, stress",
                 other
             );
             1
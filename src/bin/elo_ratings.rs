@@ -0,0 +1,59 @@
+//! Elo leaderboard fitted on the pairings of a `tournament` run.
+//!
+//! Usage:
+//!   cargo run --release --bin elo_ratings -- \
+//!       --results tournament.csv --out ratings.csv
+
+use clap::Parser;
+use std::error::Error;
+
+use take_it_easy::training::elo::{fit_elo, save_ratings};
+use take_it_easy::training::tournament::load_pairings;
+
+#[derive(Parser)]
+#[command(
+    name = "elo_ratings",
+    about = "Fit Elo ratings on the head-to-head results of a tournament"
+)]
+struct Args {
+    /// Pairings CSV written by `tournament`
+    #[arg(long, default_value = "tournament.csv")]
+    results: String,
+
+    /// Leaderboard CSV
+    #[arg(long, default_value = "ratings.csv")]
+    out: String,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let pairings = load_pairings(&args.results)?;
+    let ratings = fit_elo(&pairings);
+    println!(
+        "{} models, {} pairings from {}\n",
+        ratings.len(),
+        pairings.len(),
+        args.results
+    );
+
+    println!(
+        "{:>4} | {:<30} | {:>7} | {:>6} | {:>6}",
+        "Rank", "Model", "Elo", "Games", "Score"
+    );
+    println!("{}", "-".repeat(66));
+    for (rank, rating) in ratings.iter().enumerate() {
+        println!(
+            "{:>4} | {:<30} | {:>7.1} | {:>6} | {:>5.1}%",
+            rank + 1,
+            rating.model,
+            rating.elo,
+            rating.games,
+            100.0 * rating.score_rate
+        );
+    }
+
+    save_ratings(&ratings, &args.out)?;
+    println!("\nRatings written to {}", args.out);
+    Ok(())
+}
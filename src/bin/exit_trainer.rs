@@ -5,6 +5,23 @@
 //! Each iteration the GT improves → V1Beam improves → data improves → virtuous cycle.
 //!
 //! Usage: cargo run --release --bin exit_trainer -- --iterations 20
+//!
+//! With `--data-dir`, each evaluated iteration is appended to
+//! `<data-dir>/selfplay_history.csv`; `--report --data-dir <dir>` prints that
+//! history as a plot-ready JSON report and exits. The weights and state of
+//! the loop are also saved there after every iteration, so that `--resume`
+//! continues an interrupted run from the next iteration.
+//!
+//! `--dry-run` times a few games of each phase, prints the projected duration
+//! and disk usage of the full run, then exits without training.
+//!
+//! `--co-train-archs cnn,graph-transformer` runs one lineage per architecture
+//! instead: shared self-play data, separate training, benchmarks and model
+//! files, and a per-generation comparison in `<data-dir>/arch_comparison.csv`.
+//!
+//! `--reference-model gt_gen0.safetensors` also benchmarks every evaluated
+//! iteration, accepted or not, against that frozen model on the same seeds and
+//! logs the win rate, which tracks absolute progress across iterations.
 
 use clap::Parser;
 use rand::prelude::*;
@@ -20,12 +37,34 @@ use take_it_easy::game::plateau::create_plateau_empty;
 use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
-use take_it_easy::neural::model_io::{load_varstore, save_varstore};
+use take_it_easy::neural::manager::NNArchitecture;
+use take_it_easy::neural::model_io::{
+    load_varstore, save_varstore_with_meta, ModelMeta, GT_POLICY_ARCH,
+};
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
+use take_it_easy::neural::training::policy_target::tensor_to_distribution;
 use take_it_easy::scoring::scoring::result;
 use take_it_easy::strategy::gt_boost::gt_beam_v1_select;
-
-#[derive(Parser, Debug)]
+use take_it_easy::training::arch_lineages::{
+    lineage_save_path, run_co_training, CoTrainConfig, Lineage, COMPARISON_FILE,
+};
+use take_it_easy::training::curriculum::{ParamSchedule, SimSchedule, TemperatureSchedule};
+use take_it_easy::training::gating::{evaluate_gate, mean, GatingConfig};
+use take_it_easy::training::history::{
+    append_history, history_path, history_row_bytes, report_json, GenerationRecord,
+};
+use take_it_easy::training::loop_state::LoopState;
+use take_it_easy::training::optimizer_step::guarded_backward_step;
+use take_it_easy::training::reference::ReferenceBenchmark;
+use take_it_easy::training::recency::recency_weight;
+use take_it_easy::training::run_estimate::{
+    estimate_run, format_bytes, format_duration, RunPlan, SampleTimings,
+};
+
+/// Weights the next iteration starts from, in --data-dir
+const CURRENT_POLICY_FILE: &str = "loop_current_policy.safetensors";
+
+#[derive(Parser, Debug, Clone)]
 #[command(name = "exit_trainer")]
 struct Args {
     /// Number of ExIt iterations
@@ -56,6 +95,10 @@ struct Args {
     #[arg(long, default_value_t = 0.0003)]
     lr: f64,
 
+    /// Clip gradients to this global norm before each step (default: no clipping)
+    #[arg(long)]
+    grad_clip: Option<f64>,
+
     /// LR decay multiplier between iterations
     #[arg(long, default_value_t = 0.95)]
     lr_decay: f64,
@@ -68,6 +111,39 @@ struct Args {
     #[arg(long, default_value_t = 10)]
     beam_rollouts: usize,
 
+    /// Per-iteration rollout budget curriculum, e.g. "5,10,20" (empty = flat --beam-rollouts)
+    #[arg(long, default_value = "")]
+    sim_schedule: String,
+
+    /// Opening turns played by sampling the GT policy instead of the expert
+    /// move (the expert move stays the training target). 0 = no exploration
+    #[arg(long, default_value_t = 0)]
+    explore_turns: usize,
+
+    /// Per-iteration explore-turns schedule: "8,6,4" or "decay:START:FACTOR[:MIN]"
+    /// (empty = flat --explore-turns)
+    #[arg(long, default_value = "")]
+    explore_schedule: String,
+
+    /// Sampling temperature of the explored turns (0 = greedy)
+    #[arg(long, default_value_t = 1.0)]
+    temperature: f64,
+
+    /// Per-iteration temperature schedule, same syntax as --explore-schedule
+    #[arg(long, default_value = "")]
+    temperature_schedule: String,
+
+    /// Per-turn sampling temperature, same syntax as --explore-schedule keyed
+    /// by turn, e.g. "1.0,1.0,0.5,0.2,0" (0 = expert move). Replaces
+    /// --explore-turns/--temperature and their schedules (empty = not used)
+    #[arg(long, default_value = "")]
+    temp_schedule: String,
+
+    /// Halve a sample's weight every N games of age within the iteration's
+    /// games, on top of the score weighting (0 = no recency weighting)
+    #[arg(long, default_value_t = 0.0)]
+    recency_halflife: f64,
+
     /// Line boost strength
     #[arg(long, default_value_t = 3.0)]
     line_boost: f64,
@@ -80,6 +156,19 @@ struct Args {
     #[arg(long, default_value_t = 3)]
     patience: usize,
 
+    /// Minimum average improvement (pts) over the best model to accept an iteration
+    #[arg(long, default_value_t = 0.0)]
+    accept_threshold: f64,
+
+    /// Also require a one-sided p-value below this to accept (1.0 = no test)
+    #[arg(long, default_value_t = 1.0)]
+    significance: f64,
+
+    /// Re-benchmark an accepted iteration on this many fresh-seed games and
+    /// accept only if it passes the gate again (0 = no confirmation)
+    #[arg(long, default_value_t = 0)]
+    gating_games: usize,
+
     /// Embedding dimension
     #[arg(long, default_value_t = 128)]
     embed_dim: i64,
@@ -104,6 +193,36 @@ struct Args {
     #[arg(long, default_value = "model_weights/gt_exit_best.safetensors")]
     save_path: String,
 
+    /// Directory receiving the per-iteration selfplay_history.csv (empty = no history)
+    #[arg(long, default_value = "")]
+    data_dir: String,
+
+    /// Continue an interrupted run from the loop_state.json of --data-dir
+    #[arg(long)]
+    resume: bool,
+
+    /// Frozen model every iteration is benchmarked against on fixed seeds
+    /// (empty = no reference benchmark)
+    #[arg(long, default_value = "")]
+    reference_model: String,
+
+    /// Print the JSON report of the --data-dir history and exit
+    #[arg(long)]
+    report: bool,
+
+    /// Time a sample of games, print the projected run time and disk usage, and exit
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Games timed per phase by --dry-run
+    #[arg(long, default_value_t = 10)]
+    dry_run_games: usize,
+
+    /// Co-train these architectures from scratch on shared self-play data
+    /// (comma-separated, e.g. "cnn,graph-transformer") instead of ExIt
+    #[arg(long, default_value = "")]
+    co_train_archs: String,
+
     /// Random seed
     #[arg(long, default_value_t = 42)]
     seed: u64,
@@ -118,12 +237,16 @@ struct Sample {
     target: i64,
     mask: Tensor,
     weight: f32,
+    /// Index of the game the sample comes from, in play order
+    game: usize,
 }
 
 /// Play games using V1Beam expert and collect training samples.
 fn generate_expert_games(
     policy_net: &GraphTransformerPolicyNet,
     args: &Args,
+    beam_rollouts: usize,
+    exploration: &TemperatureSchedule,
     rng: &mut StdRng,
 ) -> (Vec<Sample>, Vec<i32>) {
     let mut all_samples = Vec::new();
@@ -176,15 +299,23 @@ fn generate_expert_games(
                 policy_net,
                 args.line_boost,
                 args.beam_k,
-                args.beam_rollouts,
+                beam_rollouts,
                 args.v1_bonus,
                 rng,
             );
 
+            // Exploration: play a move sampled from the GT policy
+            let played_pos = match exploration.temperature_at(turn) {
+                Some(temperature) => {
+                    sample_policy_move(policy_net, &feat, &mask_tensor, temperature, rng)
+                }
+                None => expert_pos,
+            };
+
             game_samples.push((feat, expert_pos as i64, mask_tensor));
 
-            // Play the expert's move
-            plateau.tiles[expert_pos] = tile;
+            // Play the expert's (or explored) move
+            plateau.tiles[played_pos] = tile;
         }
 
         let score = result(&plateau);
@@ -197,6 +328,7 @@ fn generate_expert_games(
                 target,
                 mask,
                 weight: score as f32, // placeholder, will be reweighted after filtering
+                game: game_i,
             });
         }
 
@@ -250,6 +382,7 @@ fn filter_top_percentile(
                 target: s.target,
                 mask: s.mask,
                 weight: w,
+                game: s.game,
             });
         }
     }
@@ -264,11 +397,15 @@ fn filter_top_percentile(
 }
 
 /// Train one epoch of weighted cross-entropy. Returns average loss.
+///
+/// Batches with a NaN/Inf loss are skipped; `grad_clip` caps the global
+/// gradient norm of the others.
 fn train_epoch(
     policy_net: &GraphTransformerPolicyNet,
     opt: &mut nn::Optimizer,
     samples: &[Sample],
     batch_size: usize,
+    grad_clip: Option<f64>,
     rng: &mut StdRng,
 ) -> f64 {
     let n = samples.len();
@@ -281,6 +418,7 @@ fn train_epoch(
     }
 
     let mut total_loss = 0.0;
+    let mut skipped = 0;
 
     for batch_i in 0..n_batches {
         let start = batch_i * batch_size;
@@ -312,20 +450,214 @@ fn train_epoch(
         let weighted_loss =
             (&per_sample_loss * &weight_tensor).sum(Kind::Float) / weight_tensor.sum(Kind::Float);
 
-        opt.backward_step(&weighted_loss);
-        total_loss += f64::try_from(&weighted_loss).unwrap();
+        if guarded_backward_step(opt, &weighted_loss, grad_clip) {
+            total_loss += f64::try_from(&weighted_loss).unwrap();
+        } else {
+            skipped += 1;
+        }
     }
 
-    total_loss / n_batches as f64
+    if skipped > 0 {
+        eprintln!(
+            "    Warning: skipped {}/{} batches with a NaN/Inf loss",
+            skipped, n_batches
+        );
+    }
+    if skipped == n_batches {
+        return f64::NAN;
+    }
+    total_loss / (n_batches - skipped) as f64
+}
+
+/// Save the weights the next iteration starts from and the loop state
+/// pointing at them, for `--resume`.
+fn save_loop_state(
+    vs: &nn::VarStore,
+    meta: &ModelMeta,
+    data_dir: &str,
+    iter: usize,
+    no_improve: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current_policy = Path::new(data_dir).join(CURRENT_POLICY_FILE);
+    save_varstore_with_meta(vs, &current_policy, meta)?;
+    LoopState {
+        current_policy: current_policy.to_string_lossy().into_owned(),
+        current_value: None,
+        last_completed_gen: iter,
+        no_improve,
+    }
+    .save(data_dir)
 }
 
 /// Evaluate model with GT Direct (argmax, no heuristics). Returns average score.
+/// Sample a legal position from the GT policy softmax at `temperature` (greedy at 0).
+fn sample_policy_move(
+    policy_net: &GraphTransformerPolicyNet,
+    feat: &Tensor,
+    mask: &Tensor,
+    temperature: f64,
+    rng: &mut StdRng,
+) -> usize {
+    let logits = policy_net.forward(&feat.unsqueeze(0), false).squeeze_dim(0) + mask;
+    if temperature <= 0.0 {
+        return logits.argmax(-1, false).int64_value(&[]) as usize;
+    }
+
+    let probs = tensor_to_distribution(&(logits / temperature).softmax(-1, Kind::Float));
+    let mut remaining = rng.random::<f32>() * probs.iter().sum::<f32>();
+    let mut last_legal = 0;
+    for (pos, &p) in probs.iter().enumerate() {
+        if p <= 0.0 {
+            continue;
+        }
+        last_legal = pos;
+        remaining -= p;
+        if remaining <= 0.0 {
+            return pos;
+        }
+    }
+    last_legal
+}
+
+/// `--dry-run`: time `dry_run_games` games of each phase with the iteration-1
+/// settings and project the whole run from the per-unit costs.
+fn dry_run(
+    policy_net: &GraphTransformerPolicyNet,
+    vs: &nn::VarStore,
+    args: &Args,
+    beam_rollouts: usize,
+    exploration: &TemperatureSchedule,
+) {
+    let sample_games = args.dry_run_games.max(1);
+    println!("\n--- Dry run: timing {} games per phase ---", sample_games);
+    let mut rng = StdRng::seed_from_u64(args.seed);
+
+    let sample_args = Args {
+        games_per_iter: sample_games,
+        ..args.clone()
+    };
+    let start = Instant::now();
+    let (samples, _) = generate_expert_games(
+        policy_net,
+        &sample_args,
+        beam_rollouts,
+        exploration,
+        &mut rng,
+    );
+    let secs_per_game = start.elapsed().as_secs_f64() / sample_games as f64;
+
+    // Weights are not saved: training the sample only measures its cost
+    let secs_per_train_sample = if samples.len() >= args.batch_size {
+        let mut opt = nn::Adam::default().build(vs, args.lr).unwrap();
+        let start = Instant::now();
+        train_epoch(
+            policy_net,
+            &mut opt,
+            &samples,
+            args.batch_size,
+            args.grad_clip,
+            &mut rng,
+        );
+        start.elapsed().as_secs_f64() / samples.len() as f64
+    } else {
+        println!(
+            "  Warning: {} samples < batch size {}, training time not measured (raise --dry-run-games)",
+            samples.len(),
+            args.batch_size
+        );
+        0.0
+    };
+
+    let start = Instant::now();
+    eval_model(policy_net, sample_games, &mut rng);
+    let secs_per_eval_game = start.elapsed().as_secs_f64() / sample_games as f64;
+
+    let checkpoint_bytes: u64 = vs
+        .variables()
+        .values()
+        .map(|t| (t.numel() * t.kind().elt_size_in_bytes()) as u64)
+        .sum();
+    // One history row per iteration
+    let reference_column = (!args.reference_model.is_empty()).then_some(0.0);
+    let reference_games = if reference_column.is_some() {
+        args.eval_games
+    } else {
+        0
+    };
+    let history_bytes = if args.data_dir.is_empty() {
+        0
+    } else {
+        history_row_bytes(&GenerationRecord {
+            generation: args.iterations,
+            expert_avg: 0.0,
+            avg_score: 0.0,
+            loss: 0.0,
+            accepted: false,
+            reference_avg: reference_column,
+            reference_win_rate: reference_column,
+        })
+        .unwrap_or(0)
+    };
+
+    let timings = SampleTimings {
+        secs_per_game,
+        secs_per_train_sample,
+        secs_per_eval_game,
+    };
+    let plan = RunPlan {
+        iterations: args.iterations,
+        games_per_iter: args.games_per_iter,
+        kept_fraction: args.top_percentile,
+        epochs_per_iter: args.epochs_per_iter,
+        eval_games_per_iter: args.eval_games + args.gating_games + reference_games,
+        // Baseline + final verification (+ reference benchmark)
+        extra_eval_games: args.eval_games + 500 + reference_games,
+        bytes_per_iter: history_bytes,
+        fixed_bytes: checkpoint_bytes,
+    };
+    let estimate = estimate_run(&plan, &timings);
+
+    println!(
+        "  Measured:         expert {:.2}s/game | training {:.3}ms/sample/epoch | benchmark {:.3}s/game",
+        secs_per_game,
+        secs_per_train_sample * 1000.0,
+        secs_per_eval_game
+    );
+    println!(
+        "\n  Projection ({} iterations, no early stopping):",
+        args.iterations
+    );
+    println!(
+        "    Generation:     {}",
+        format_duration(estimate.generation_secs)
+    );
+    println!(
+        "    Training:       {}",
+        format_duration(estimate.training_secs)
+    );
+    println!(
+        "    Benchmarks:     {}",
+        format_duration(estimate.benchmark_secs)
+    );
+    println!(
+        "    Total:          {}",
+        format_duration(estimate.total_secs())
+    );
+    println!(
+        "    Disk:           {} (checkpoint {}, history {}/iteration)",
+        format_bytes(estimate.disk_bytes),
+        format_bytes(checkpoint_bytes),
+        format_bytes(history_bytes)
+    );
+}
+
+/// Per-game scores of the GT policy played greedily.
 fn eval_model(
     policy_net: &GraphTransformerPolicyNet,
     n_games: usize,
     rng: &mut StdRng,
-) -> f64 {
-    let mut total = 0i64;
+) -> Vec<f64> {
+    let mut scores = Vec::with_capacity(n_games);
 
     for _ in 0..n_games {
         let mut plateau = create_plateau_empty();
@@ -363,15 +695,96 @@ fn eval_model(
             plateau.tiles[best_pos] = tile;
         }
 
-        total += result(&plateau) as i64;
+        scores.push(result(&plateau) as f64);
+    }
+
+    scores
+}
+
+/// One lineage per `--co-train-archs` architecture, compared each generation.
+fn co_train(args: &Args, device: Device) {
+    let archs: Result<Vec<NNArchitecture>, String> = args
+        .co_train_archs
+        .split(',')
+        .map(|a| a.trim().parse())
+        .collect();
+    let archs = match archs {
+        Ok(archs) => archs,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    let data_dir = if args.data_dir.is_empty() {
+        "."
+    } else {
+        args.data_dir.as_str()
+    };
+
+    let save_path = Path::new(&args.save_path);
+    let mut lineages: Vec<Lineage> = archs
+        .into_iter()
+        .map(|arch| Lineage::new(arch, device, lineage_save_path(save_path, arch)))
+        .collect();
+    println!("\n  Co-training {} lineages:", lineages.len());
+    for lineage in &lineages {
+        println!("    {} -> {}", lineage.arch, lineage.save_path.display());
     }
 
-    total as f64 / n_games as f64
+    let config = CoTrainConfig {
+        generations: args.iterations,
+        games_per_gen: args.games_per_iter,
+        eval_games: args.eval_games,
+        top_fraction: args.top_percentile,
+        epochs: args.epochs_per_iter,
+        batch_size: args.batch_size,
+        lr: args.lr,
+        seed: args.seed,
+    };
+    match run_co_training(&mut lineages, &config, Path::new(data_dir)) {
+        Ok(rows) => {
+            println!();
+            for row in &rows {
+                let scores: Vec<String> = row
+                    .scores
+                    .iter()
+                    .map(|(arch, score)| format!("{}={:.1}", arch, score))
+                    .collect();
+                println!(
+                    "  Gen {:2} | {} | leader: {}",
+                    row.generation,
+                    scores.join(" "),
+                    row.leader
+                );
+            }
+            println!(
+                "\n  Comparison written to {}",
+                Path::new(data_dir).join(COMPARISON_FILE).display()
+            );
+        }
+        Err(e) => eprintln!("Error: co-training failed: {}", e),
+    }
 }
 
 fn main() {
     let args = Args::parse();
 
+    if args.report {
+        if args.data_dir.is_empty() {
+            eprintln!("Error: --report requires --data-dir");
+            return;
+        }
+        match report_json(&args.data_dir) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!(
+                "Error: cannot read {}: {}",
+                history_path(&args.data_dir).display(),
+                e
+            ),
+        }
+        return;
+    }
+
     println!("╔══════════════════════════════════════════════════════════════╗");
     println!("║          Expert Iteration (ExIt) Trainer                    ║");
     println!("╚══════════════════════════════════════════════════════════════╝\n");
@@ -386,9 +799,72 @@ fn main() {
     println!("  Batch size:       {}", args.batch_size);
     println!("  Beam K:           {}", args.beam_k);
     println!("  Beam rollouts:    {}", args.beam_rollouts);
+    let sim_schedule = match SimSchedule::parse(&args.sim_schedule) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    if !sim_schedule.is_flat() {
+        println!("  Sim schedule:     {}", args.sim_schedule);
+    }
+    let (explore_schedule, temperature_schedule) = match (
+        ParamSchedule::parse(&args.explore_schedule),
+        ParamSchedule::parse(&args.temperature_schedule),
+    ) {
+        (Ok(explore), Ok(temperature)) => (explore, temperature),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    let turn_temperatures = if args.temp_schedule.trim().is_empty() {
+        None
+    } else {
+        match TemperatureSchedule::parse(&args.temp_schedule) {
+            Ok(schedule) => Some(schedule),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return;
+            }
+        }
+    };
+    // Per-iteration cutoff exploration unless a per-turn schedule is given
+    let exploration_for = |iter: usize| match &turn_temperatures {
+        Some(schedule) => schedule.clone(),
+        None => TemperatureSchedule::cutoff(
+            explore_schedule.turns_for(iter, args.explore_turns),
+            temperature_schedule.value_for(iter, args.temperature),
+        ),
+    };
+    if turn_temperatures.is_some() {
+        println!("  Temp schedule:    {}", args.temp_schedule);
+    } else {
+        if explore_schedule.is_flat() {
+            println!("  Explore turns:    {}", args.explore_turns);
+        } else {
+            println!("  Explore turns:    {}", args.explore_schedule);
+        }
+        if temperature_schedule.is_flat() {
+            println!("  Temperature:      {}", args.temperature);
+        } else {
+            println!("  Temperature:      {}", args.temperature_schedule);
+        }
+    }
     println!("  Line boost:       {:.1}", args.line_boost);
     println!("  V1 bonus:         {:.1}", args.v1_bonus);
     println!("  Patience:         {}", args.patience);
+    let gating = GatingConfig {
+        min_improvement: args.accept_threshold,
+        significance: args.significance,
+    };
+    if gating != GatingConfig::default() || args.gating_games > 0 {
+        println!(
+            "  Gate:             delta>{:.1} p<{} gating_games={}",
+            gating.min_improvement, gating.significance, args.gating_games
+        );
+    }
     println!("  Load from:        {}", args.load_path);
     println!("  Save to:          {}", args.save_path);
 
@@ -401,6 +877,37 @@ fn main() {
         }
     };
     println!("  Device:           {:?}", device);
+
+    if !args.co_train_archs.is_empty() {
+        co_train(&args, device);
+        return;
+    }
+
+    let resume_state = if args.resume {
+        if args.data_dir.is_empty() {
+            eprintln!("Error: --resume requires --data-dir");
+            return;
+        }
+        match LoopState::load(&args.data_dir) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                eprintln!(
+                    "Error: cannot read {}: {}",
+                    LoopState::path(&args.data_dir).display(),
+                    e
+                );
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    // A resumed run must beat the best model saved so far
+    let baseline_path = match &resume_state {
+        Some(_) if Path::new(&args.save_path).exists() => &args.save_path,
+        _ => &args.load_path,
+    };
+
     let mut vs = nn::VarStore::new(device);
     let policy_net = GraphTransformerPolicyNet::new(
         &vs,
@@ -410,34 +917,90 @@ fn main() {
         args.heads,
         args.dropout,
     );
+    let meta =
+        ModelMeta::graph_transformer(GT_POLICY_ARCH, args.embed_dim, args.num_layers, args.heads);
 
-    if !Path::new(&args.load_path).exists() {
-        eprintln!("\nError: model weights not found: {}", args.load_path);
+    if !Path::new(baseline_path).exists() {
+        eprintln!("\nError: model weights not found: {}", baseline_path);
         return;
     }
-    match load_varstore(&mut vs, &args.load_path) {
-        Ok(()) => println!("\n  Loaded weights from {}", args.load_path),
+    match load_varstore(&mut vs, baseline_path) {
+        Ok(()) => println!("\n  Loaded weights from {}", baseline_path),
         Err(e) => {
             eprintln!("\nError loading weights: {}", e);
             return;
         }
     }
 
+    if args.dry_run {
+        dry_run(
+            &policy_net,
+            &vs,
+            &args,
+            sim_schedule.budget_for(0, args.beam_rollouts),
+            &exploration_for(0),
+        );
+        return;
+    }
+
     // Baseline evaluation
+    let start_iter = resume_state.as_ref().map_or(0, LoopState::next_gen);
     println!("\n--- Baseline evaluation ({} games) ---", args.eval_games);
-    let mut rng = StdRng::seed_from_u64(args.seed);
-    let baseline = eval_model(&policy_net, args.eval_games, &mut rng);
+    let mut rng = StdRng::seed_from_u64(args.seed + start_iter as u64);
+    let mut best_scores = eval_model(&policy_net, args.eval_games, &mut rng);
+    let baseline = mean(&best_scores);
     println!("  GT Direct baseline: {:.1} pts", baseline);
 
+    // Frozen reference, benchmarked once on its own seed
+    let reference = if args.reference_model.is_empty() {
+        None
+    } else {
+        let mut ref_vs = nn::VarStore::new(device);
+        let ref_net = GraphTransformerPolicyNet::new(
+            &ref_vs,
+            47,
+            args.embed_dim,
+            args.num_layers,
+            args.heads,
+            args.dropout,
+        );
+        if let Err(e) = load_varstore(&mut ref_vs, &args.reference_model) {
+            eprintln!("\nError loading reference model: {}", e);
+            return;
+        }
+        let benchmark = ReferenceBenchmark::new(args.seed + 200_000, |rng| {
+            eval_model(&ref_net, args.eval_games, rng)
+        });
+        println!(
+            "  Reference model:    {:.1} pts ({})",
+            benchmark.avg_score(),
+            args.reference_model
+        );
+        Some(benchmark)
+    };
+
     let mut best_score = baseline;
     let mut no_improve = 0usize;
     let mut current_lr = args.lr;
+    if let Some(state) = &resume_state {
+        if let Err(e) = load_varstore(&mut vs, &state.current_policy) {
+            eprintln!("\nError loading {}: {}", state.current_policy, e);
+            return;
+        }
+        no_improve = state.no_improve;
+        current_lr = args.lr * args.lr_decay.powi(start_iter as i32);
+        println!(
+            "  Resuming at iteration {} from {}",
+            start_iter + 1,
+            state.current_policy
+        );
+    }
     let total_start = Instant::now();
 
     // ═══════════════════════════════════════════
     //             ExIt Main Loop
     // ═══════════════════════════════════════════
-    for iter in 0..args.iterations {
+    for iter in start_iter..args.iterations {
         let iter_start = Instant::now();
         println!(
             "\n══════════════════════════════════════════════════════════════"
@@ -456,23 +1019,42 @@ fn main() {
         );
 
         // 1. Generate expert data with V1Beam (uses current GT weights)
+        let beam_rollouts = sim_schedule.budget_for(iter, args.beam_rollouts);
+        let exploration = exploration_for(iter);
+        let explore_label = match &exploration {
+            TemperatureSchedule::Cutoff { turns, temperature } => {
+                format!("explore={} T={:.2}", turns, temperature)
+            }
+            TemperatureSchedule::PerTurn(_) => format!("T={}", args.temp_schedule),
+        };
         println!(
-            "\n  [1/4] Generating {} expert games (V1Beam k={} r={})...",
-            args.games_per_iter, args.beam_k, args.beam_rollouts
+            "\n  [1/4] Generating {} expert games (V1Beam k={} r={}, {})...",
+            args.games_per_iter, args.beam_k, beam_rollouts, explore_label
         );
-        let (samples, scores) = generate_expert_games(&policy_net, &args, &mut rng);
+        let (samples, scores) =
+            generate_expert_games(&policy_net, &args, beam_rollouts, &exploration, &mut rng);
 
         let expert_avg: f64 = scores.iter().sum::<i32>() as f64 / scores.len() as f64;
         println!("  Expert avg: {:.1} pts", expert_avg);
 
         // 2. Filter top percentile
         println!("\n  [2/4] Filtering top {:.0}%...", args.top_percentile * 100.0);
-        let filtered = filter_top_percentile(
+        let mut filtered = filter_top_percentile(
             samples,
             &scores,
             args.games_per_iter,
             args.top_percentile,
         );
+        if args.recency_halflife > 0.0 {
+            for s in filtered.iter_mut() {
+                let age = args.games_per_iter - 1 - s.game;
+                s.weight *= recency_weight(age, args.recency_halflife);
+            }
+            println!(
+                "  Recency weighting: halflife {} games",
+                args.recency_halflife
+            );
+        }
 
         if filtered.len() < args.batch_size {
             println!("  Warning: too few samples ({}), skipping iteration", filtered.len());
@@ -488,8 +1070,17 @@ fn main() {
         );
         let mut opt = nn::Adam::default().build(&vs, current_lr).unwrap();
 
+        let mut last_loss = 0.0;
         for epoch in 0..args.epochs_per_iter {
-            let loss = train_epoch(&policy_net, &mut opt, &filtered, args.batch_size, &mut rng);
+            let loss = train_epoch(
+                &policy_net,
+                &mut opt,
+                &filtered,
+                args.batch_size,
+                args.grad_clip,
+                &mut rng,
+            );
+            last_loss = loss;
             if epoch % 5 == 4 || epoch == args.epochs_per_iter - 1 {
                 println!(
                     "    Epoch {:2}/{:2} | loss={:.4}",
@@ -505,19 +1096,72 @@ fn main() {
             "\n  [4/4] Evaluating ({} games GT Direct)...",
             args.eval_games
         );
-        let new_score = eval_model(&policy_net, args.eval_games, &mut rng);
+        let mut new_scores = eval_model(&policy_net, args.eval_games, &mut rng);
+        let mut new_score = mean(&new_scores);
+        let mut gate = evaluate_gate(&new_scores, &best_scores, &gating);
+
+        // Confirm on fresh seeds so a lucky benchmark cannot promote the iteration
+        if gate.accepted && args.gating_games > 0 {
+            let mut gating_rng = StdRng::seed_from_u64(args.seed + 100_000 + iter as u64);
+            let confirm_scores = eval_model(&policy_net, args.gating_games, &mut gating_rng);
+            let confirm = evaluate_gate(&confirm_scores, &best_scores, &gating);
+            println!(
+                "  Gating: {:.1} pts on {} fresh games ({:+.1} vs best, p={:.3})",
+                mean(&confirm_scores),
+                args.gating_games,
+                confirm.delta,
+                confirm.p_value
+            );
+            if confirm.accepted {
+                new_score = mean(&confirm_scores);
+                new_scores = confirm_scores;
+            }
+            gate = confirm;
+        }
         let delta = new_score - best_score;
+
+        // Reference benchmark whatever the gate decided
+        let comparison = reference
+            .as_ref()
+            .map(|r| r.compare(|rng| eval_model(&policy_net, args.eval_games, rng)));
+        if let Some(c) = &comparison {
+            println!(
+                "  Reference: {:.1} vs {:.1} pts | win rate {:.1}%",
+                c.avg_score,
+                c.reference_avg,
+                100.0 * c.win_rate
+            );
+        }
         let iter_elapsed = iter_start.elapsed().as_secs_f64();
 
-        if new_score > best_score {
+        if !args.data_dir.is_empty() {
+            let mut record = GenerationRecord {
+                generation: iter,
+                expert_avg,
+                avg_score: new_score,
+                loss: last_loss,
+                accepted: gate.accepted,
+                reference_avg: None,
+                reference_win_rate: None,
+            };
+            if let Some(c) = &comparison {
+                c.record_into(&mut record);
+            }
+            if let Err(e) = append_history(&history_path(&args.data_dir), &record) {
+                eprintln!("  Warning: failed to append history: {}", e);
+            }
+        }
+
+        if gate.accepted {
             println!(
-                "  Score: {:.1} pts ({:+.1} vs best) *** NEW BEST *** ({:.0}s)",
-                new_score, delta, iter_elapsed
+                "  Score: {:.1} pts ({:+.1} vs best, p={:.3}) *** NEW BEST *** ({:.0}s)",
+                new_score, delta, gate.p_value, iter_elapsed
             );
             best_score = new_score;
+            best_scores = new_scores;
             no_improve = 0;
 
-            if let Err(e) = save_varstore(&vs, &args.save_path) {
+            if let Err(e) = save_varstore_with_meta(&vs, &args.save_path, &meta) {
                 eprintln!("  Warning: failed to save: {}", e);
             } else {
                 println!("  Saved to {}", args.save_path);
@@ -525,15 +1169,20 @@ fn main() {
         } else {
             no_improve += 1;
             println!(
-                "  Score: {:.1} pts ({:+.1} vs best) | no_improve={}/{} ({:.0}s)",
-                new_score, delta, no_improve, args.patience, iter_elapsed
+                "  Score: {:.1} pts ({:+.1} vs best, p={:.3}) | no_improve={}/{} ({:.0}s)",
+                new_score, delta, gate.p_value, no_improve, args.patience, iter_elapsed
             );
+        }
 
-            if no_improve >= args.patience {
-                println!("\n  Early stopping: no improvement for {} iterations", args.patience);
-                break;
+        if !args.data_dir.is_empty() {
+            if let Err(e) = save_loop_state(&vs, &meta, &args.data_dir, iter, no_improve) {
+                eprintln!("  Warning: failed to save loop state: {}", e);
             }
         }
+        if no_improve >= args.patience {
+            println!("\n  Early stopping: no improvement for {} iterations", args.patience);
+            break;
+        }
 
         // Decay LR
         current_lr *= args.lr_decay;
@@ -561,7 +1210,7 @@ fn main() {
         }
 
         let mut verify_rng = StdRng::seed_from_u64(args.seed + 9999);
-        let final_score = eval_model(&policy_net, 500, &mut verify_rng);
+        let final_score = mean(&eval_model(&policy_net, 500, &mut verify_rng));
         println!("  GT Direct (best ExIt): {:.1} pts (500 games)", final_score);
     }
 }
@@ -0,0 +1,91 @@
+//! Export a Graph Transformer policy as a traced TorchScript module, and
+//! optionally as an ONNX graph.
+//!
+//! The module loads without Rust (`torch.jit.load`, libtorch C++). The trace is
+//! taken on a batch of `--batch-size` boards, the only batch size the module
+//! accepts. With `--onnx-out`, the trace is converted by the Python interpreter
+//! in `TAKE_IT_EASY_PYTHON` (default `python3`, needs torch and onnxruntime),
+//! and the export fails unless the ONNX logits match the Rust forward pass
+//! within 1e-4.
+//!
+//! Usage:
+//!   cargo run --release --bin export_torchscript -- \
+//!       --model-path model_weights/graph_transformer_policy.safetensors \
+//!       --out model_weights/graph_transformer_policy.pt \
+//!       --onnx-out model_weights/graph_transformer_policy.onnx
+
+use clap::Parser;
+use std::error::Error;
+use tch::{Device, Kind, Tensor};
+
+use take_it_easy::neural::model_io::{
+    export_onnx, export_torchscript, load_meta, load_policy, ONNX_PARITY_TOLERANCE,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "export_torchscript",
+    about = "Export a Graph Transformer policy as a TorchScript module"
+)]
+struct Args {
+    /// Policy weights (.safetensors)
+    #[arg(
+        long,
+        default_value = "model_weights/graph_transformer_policy.safetensors"
+    )]
+    model_path: String,
+
+    /// TorchScript output file
+    #[arg(long, default_value = "model_weights/graph_transformer_policy.pt")]
+    out: String,
+
+    /// Architecture of models saved without metadata
+    #[arg(long, default_value_t = 128)]
+    embed_dim: i64,
+
+    #[arg(long, default_value_t = 2)]
+    num_layers: usize,
+
+    #[arg(long, default_value_t = 4)]
+    num_heads: i64,
+
+    /// Batch size of the exported module
+    #[arg(long, default_value_t = 1)]
+    batch_size: i64,
+
+    /// Also export an ONNX graph, checked against the Rust forward pass
+    #[arg(long)]
+    onnx_out: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let (mut vs, policy_net) = load_policy(
+        &args.model_path,
+        Device::Cpu,
+        args.embed_dim,
+        args.num_layers,
+        args.num_heads,
+    )?;
+    println!("Policy loaded from {}", args.model_path);
+
+    let channels = load_meta(&args.model_path).map_or(47, |meta| meta.feature_channels);
+    let example = Tensor::zeros([args.batch_size, 19, channels], (Kind::Float, Device::Cpu));
+    export_torchscript(&mut vs, &policy_net, &args.out, &example)?;
+    println!(
+        "TorchScript module written to {} (input [{}, 19, {}])",
+        args.out, args.batch_size, channels
+    );
+
+    if let Some(onnx_out) = &args.onnx_out {
+        // Random boards: an all-zero input would hide most parity errors
+        let sample = Tensor::rand([args.batch_size, 19, channels], (Kind::Float, Device::Cpu));
+        export_onnx(&mut vs, &policy_net, onnx_out, &sample)?;
+        println!(
+            "ONNX graph written to {} (logits within {:e} of the Rust forward)",
+            onnx_out, ONNX_PARITY_TOLERANCE
+        );
+    }
+    Ok(())
+}
@@ -7,7 +7,19 @@
 //!   - Mismatches (v1=9 on edge, v1=1 on center) → penalty
 //!
 //! Filters games by minimum score and deduplicates by final board state.
-//! Output format is identical to selfplay_train CSV.
+//! Output format is identical to selfplay_train CSV, or the packed binary
+//! format when `--output` ends in `.bin`.
+//!
+//! Games are played in parallel (`--threads`), each from its own seed, so
+//! `--seed` reproduces the same output whatever the thread count. A run
+//! resumed with `--resume` plays only the games its checkpoint had left, with
+//! the seeds of the uninterrupted run.
+//!
+//! With `--mcts-sims N`, moves are chosen by a pure-rollout MCTS of N
+//! simulations instead of the biased GT policy, and each turn also records the
+//! normalized visit distribution of the search (`pi*` columns). Each search
+//! draws its rollouts from the seeded RNG of its game, so this mode is
+//! reproducible from `--seed` too.
 //!
 //! Usage:
 //!   cargo build --release --bin generate_v1_strategic --target-dir target2
@@ -16,24 +28,27 @@
 use clap::Parser;
 use rand::prelude::*;
 use rand::rngs::StdRng;
+use rayon::prelude::*;
 use std::collections::HashSet;
 use std::error::Error;
-use std::fs;
-use std::path::Path;
 use std::time::Instant;
-use tch::{nn, Device, Tensor};
+use tch::{nn, Device};
 
+use take_it_easy::data::selfplay_csv::{
+    finalize_csv, load_checkpoint, save_csv_checkpoint, visit_distribution, TurnRecord,
+};
+use take_it_easy::game::board_geometry::{pos_to_row, ROWS};
 use take_it_easy::game::create_deck::create_deck;
-use take_it_easy::game::deck::Deck;
 use take_it_easy::game::get_legal_moves::get_legal_moves;
 use take_it_easy::game::plateau::{create_plateau_empty, Plateau};
 use take_it_easy::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
 use take_it_easy::game::tile::Tile;
+use take_it_easy::mcts::algorithm::mcts_find_best_position_for_tile_pure;
 use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
 use take_it_easy::neural::model_io::load_varstore;
-use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use take_it_easy::scoring::scoring::result;
-use take_it_easy::strategy::gt_boost::line_boost;
+use take_it_easy::strategy::gt_boost::{gt_masked_logits, line_boost};
+use take_it_easy::utils::random_index::with_seeded_rng;
 
 // ============================================================
 // CLI
@@ -60,26 +75,39 @@ struct Cli {
     #[arg(long, default_value = "model_weights/graph_transformer_policy.safetensors")]
     model_path: String,
 
+    /// Output file; a `.bin` extension selects the packed binary format
     #[arg(long, default_value = "data/v1_strategic.csv")]
     output: String,
 
     #[arg(long)]
     seed: Option<u64>,
+
+    /// Flush kept games to `<output>.partial` every N games played (0 = only at the end)
+    #[arg(long, default_value_t = 5000)]
+    checkpoint_every: usize,
+
+    /// Resume from `<output>.partial` left by an interrupted run
+    #[arg(long)]
+    resume: bool,
+
+    /// Worker threads playing games (0 = one per core); the output does not
+    /// depend on it
+    #[arg(long, default_value_t = 0)]
+    threads: usize,
+
+    /// Choose moves with a pure-rollout MCTS of N simulations and record its
+    /// visit distribution (0 = GT policy + biases, no distribution)
+    #[arg(long, default_value_t = 0)]
+    mcts_sims: usize,
 }
 
+/// Games played in parallel between two sequential bookkeeping passes.
+const PARALLEL_CHUNK: usize = 1000;
+
 // ============================================================
 // Helpers (same encoding as selfplay_train)
 // ============================================================
 
-struct TurnRecord {
-    game_idx: usize,
-    turn: usize,
-    plateau: [i32; 19],
-    tile: (i32, i32, i32),
-    chosen_position: usize,
-    final_score: i32,
-}
-
 fn encode_tile(t: &Tile) -> i32 {
     if *t == Tile(0, 0, 0) {
         0
@@ -119,33 +147,28 @@ fn board_key(encoded: &[i32; 19]) -> String {
         .join(",")
 }
 
+/// Final board and score of each checkpointed game, rebuilt from its last turn.
+fn final_boards(records: &[TurnRecord]) -> Vec<([i32; 19], i32)> {
+    let mut boards = Vec::new();
+    for (i, r) in records.iter().enumerate() {
+        let last_of_game =
+            !matches!(records.get(i + 1), Some(next) if next.game_idx == r.game_idx);
+        if last_of_game {
+            let mut board = r.plateau;
+            board[r.chosen_position] = r.tile.0 * 100 + r.tile.1 * 10 + r.tile.2;
+            boards.push((board, r.final_score));
+        }
+    }
+    boards
+}
+
 // ============================================================
 // v1-row priority (adaptive)
 // ============================================================
 
-/// Row definitions: positions belonging to each horizontal row.
-const ROWS: [&[usize]; 5] = [
-    &[0, 1, 2],        // row 0: edge (len 3) — target v1=1
-    &[3, 4, 5, 6],     // row 1: side (len 4) — target v1=5
-    &[7, 8, 9, 10, 11],// row 2: center (len 5) — target v1=9
-    &[12, 13, 14, 15],  // row 3: side (len 4) — target v1=5
-    &[16, 17, 18],      // row 4: edge (len 3) — target v1=1
-];
-
 /// Target v1 value for each row.
 const ROW_TARGET_V1: [i32; 5] = [1, 5, 9, 5, 1];
 
-fn pos_to_row(pos: usize) -> usize {
-    match pos {
-        0..=2 => 0,
-        3..=6 => 1,
-        7..=11 => 2,
-        12..=15 => 3,
-        16..=18 => 4,
-        _ => unreachable!(),
-    }
-}
-
 /// Check if a row is still viable for v1-homogeneity with the target value.
 /// A row is viable if no tile with a DIFFERENT v1 has been placed in it.
 fn row_viable(plateau: &Plateau, row_idx: usize) -> bool {
@@ -206,38 +229,20 @@ fn v1_row_priority_adaptive(plateau: &Plateau, tile: &Tile, position: usize, bon
     }
 }
 
-// ============================================================
-// GT inference
-// ============================================================
-
-fn compute_masked_logits(
-    plateau: &Plateau,
-    tile: &Tile,
-    deck: &Deck,
-    turn: usize,
-    policy_net: &GraphTransformerPolicyNet,
-) -> Tensor {
-    let feat = convert_plateau_for_gat_47ch(plateau, tile, deck, turn, 19).unsqueeze(0);
-    let logits = policy_net.forward(&feat, false).squeeze_dim(0);
-    let mut mask = [0.0f32; 19];
-    for i in 0..19 {
-        if plateau.tiles[i] != Tile(0, 0, 0) {
-            mask[i] = f32::NEG_INFINITY;
-        }
-    }
-    logits + Tensor::from_slice(&mask)
-}
-
 // ============================================================
 // Play one game with GT + line_boost + v1_priority
 // ============================================================
 
+/// One recorded turn: turn index, board before placement, tile, chosen
+/// position and MCTS visit distribution (MCTS games only).
+type RecordedTurn = (usize, [i32; 19], (i32, i32, i32), usize, Option<Vec<f32>>);
+
 fn play_game_v1_strategic(
     tiles: &[Tile],
     policy_net: &GraphTransformerPolicyNet,
     lb: f64,
     v1_bonus: f64,
-) -> (Plateau, Vec<(usize, [i32; 19], (i32, i32, i32), usize)>) {
+) -> (Plateau, Vec<RecordedTurn>) {
     let mut plateau = create_plateau_empty();
     let mut deck = create_deck();
     let mut turns = Vec::with_capacity(19);
@@ -255,7 +260,7 @@ fn play_game_v1_strategic(
             legal[0]
         } else {
             // GT logits
-            let masked = compute_masked_logits(&plateau, tile, &deck, turn, policy_net);
+            let masked = gt_masked_logits(&plateau, tile, &deck, turn, policy_net);
             let logit_values: Vec<f64> = Vec::<f64>::try_from(&masked).unwrap();
 
             // argmax over (GT_logit + line_boost + v1_priority_adaptive)
@@ -273,7 +278,7 @@ fn play_game_v1_strategic(
                 .unwrap()
         };
 
-        turns.push((turn, encoded, (tile.0, tile.1, tile.2), pos));
+        turns.push((turn, encoded, (tile.0, tile.1, tile.2), pos, None));
         plateau.tiles[pos] = *tile;
         deck = replace_tile_in_deck(&deck, tile);
     }
@@ -282,39 +287,45 @@ fn play_game_v1_strategic(
 }
 
 // ============================================================
-// CSV output (identical format to selfplay_train)
+// Play one game with pure-rollout MCTS, recording its visits
 // ============================================================
 
-fn save_csv(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
-    if let Some(parent) = Path::new(path).parent() {
-        fs::create_dir_all(parent)?;
-    }
-    let mut wtr = csv::Writer::from_path(path)?;
-    let mut header: Vec<String> = vec!["game_idx".into(), "turn".into()];
-    for i in 0..19 {
-        header.push(format!("p{}", i));
-    }
-    header.extend(
-        ["t0", "t1", "t2", "chosen", "score"]
-            .iter()
-            .map(|s| s.to_string()),
-    );
-    wtr.write_record(&header)?;
+fn play_game_mcts(
+    tiles: &[Tile],
+    num_simulations: usize,
+    rng: &mut StdRng,
+) -> (Plateau, Vec<RecordedTurn>) {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+    let mut turns = Vec::with_capacity(19);
 
-    for r in records {
-        let mut row: Vec<String> = vec![r.game_idx.to_string(), r.turn.to_string()];
-        for i in 0..19 {
-            row.push(r.plateau[i].to_string());
+    for (turn, tile) in tiles.iter().enumerate() {
+        deck = replace_tile_in_deck(&deck, tile);
+        if get_legal_moves(&plateau).is_empty() {
+            break;
         }
-        row.push(r.tile.0.to_string());
-        row.push(r.tile.1.to_string());
-        row.push(r.tile.2.to_string());
-        row.push(r.chosen_position.to_string());
-        row.push(r.final_score.to_string());
-        wtr.write_record(&row)?;
+
+        let encoded = encode_board(&plateau);
+        // Rollouts drawn from the game's RNG: same seed, same search
+        let mcts_result = with_seeded_rng(rng.random(), || {
+            mcts_find_best_position_for_tile_pure(
+                &mut plateau,
+                &mut deck,
+                *tile,
+                num_simulations,
+                turn,
+                19,
+                None,
+            )
+        });
+        let distribution = Some(visit_distribution(&mcts_result, &plateau));
+        let pos = mcts_result.best_position;
+
+        turns.push((turn, encoded, (tile.0, tile.1, tile.2), pos, distribution));
+        plateau.tiles[pos] = *tile;
     }
-    wtr.flush()?;
-    Ok(())
+
+    (plateau, turns)
 }
 
 // ============================================================
@@ -324,6 +335,11 @@ fn save_csv(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     let seed = cli.seed.unwrap_or(42);
+    if cli.threads > 0 {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(cli.threads)
+            .build_global()?;
+    }
 
     println!("=== V1-Row-Priority Strategic Game Generator ===");
     println!("  Games to play:  {}", cli.num_games);
@@ -331,40 +347,129 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("  V1 bonus:       {:.1}", cli.v1_bonus);
     println!("  Line boost:     {:.1}", cli.line_boost);
     println!("  Model:          {}", cli.model_path);
+    if cli.mcts_sims > 0 {
+        println!("  MCTS sims:      {} (records visits)", cli.mcts_sims);
+    }
     println!("  Output:         {}", cli.output);
     println!("  Seed:           {}", seed);
+    println!("  Checkpoint:     every {} games", cli.checkpoint_every);
+    println!("  Threads:        {}", rayon::current_num_threads());
     println!();
 
     // Load model
     let mut vs = nn::VarStore::new(Device::Cpu);
     let policy_net = GraphTransformerPolicyNet::new(&vs, 47, 128, 2, 4, 0.1);
     load_varstore(&mut vs, &cli.model_path)?;
-    let _guard = tch::no_grad_guard();
 
-    let mut rng = StdRng::seed_from_u64(seed);
     let mut all_records: Vec<TurnRecord> = Vec::new();
     let mut seen_boards: HashSet<String> = HashSet::new();
     let mut kept_games = 0usize;
     let mut total_score: i64 = 0;
-    let mut all_scores: Vec<i32> = Vec::with_capacity(cli.num_games);
+    // Games played by the interrupted run this one resumes
+    let mut games_done = 0usize;
+
+    if cli.resume {
+        if let Some((records, games_played)) = load_checkpoint(&cli.output)? {
+            all_records = records;
+            games_done = games_played.min(cli.num_games);
+            for (board, score) in final_boards(&all_records) {
+                seen_boards.insert(board_key(&board));
+                kept_games += 1;
+                total_score += score as i64;
+            }
+            println!(
+                "Resumed after {} games: {} kept ({} turn records)",
+                games_done,
+                kept_games,
+                all_records.len()
+            );
+        }
+    }
+    let resumed_games = kept_games;
+    // Game `n` is seeded with `seed + n` whether or not the run was resumed,
+    // so a resumed run plays the games the interrupted one had left
+    let mut all_scores: Vec<i32> = Vec::with_capacity(cli.num_games - games_done);
     let start = Instant::now();
 
-    for game_num in 0..cli.num_games {
-        let tiles = generate_tile_sequence(&mut rng);
-        let (plateau, turns) = play_game_v1_strategic(&tiles, &policy_net, cli.line_boost, cli.v1_bonus);
-        let score = result(&plateau);
-        all_scores.push(score);
+    for chunk_start in (games_done..cli.num_games).step_by(PARALLEL_CHUNK) {
+        let chunk_end = (chunk_start + PARALLEL_CHUNK).min(cli.num_games);
+        // Played in parallel, then handled in game order: the output is the
+        // same whatever the thread count
+        let games: Vec<_> = (chunk_start..chunk_end)
+            .into_par_iter()
+            .map(|game_num| {
+                let mut rng = StdRng::seed_from_u64(seed.wrapping_add(game_num as u64));
+                let tiles = generate_tile_sequence(&mut rng);
+                if cli.mcts_sims > 0 {
+                    return play_game_mcts(&tiles, cli.mcts_sims, &mut rng);
+                }
+                // Grad mode is per thread: disable it in each worker
+                tch::no_grad(|| {
+                    play_game_v1_strategic(&tiles, &policy_net, cli.line_boost, cli.v1_bonus)
+                })
+            })
+            .collect();
+
+        for (game_num, (plateau, turns)) in (chunk_start..chunk_end).zip(games) {
+            if cli.checkpoint_every > 0
+                && game_num > games_done
+                && game_num % cli.checkpoint_every == 0
+            {
+                save_csv_checkpoint(&all_records, game_num, &cli.output)?;
+            }
+
+            let score = result(&plateau);
+            all_scores.push(score);
+
+            // Filter by min score
+            if score < cli.min_score {
+                if (game_num + 1) % 5000 == 0 {
+                    let avg_all =
+                        all_scores.iter().map(|&s| s as f64).sum::<f64>() / all_scores.len() as f64;
+                    let kept_avg = if kept_games > 0 {
+                        total_score as f64 / kept_games as f64
+                    } else {
+                        0.0
+                    };
+                    println!(
+                        "  {:>6} games ({:.1}s) | avg={:.1} | kept={} (avg={:.1})",
+                        game_num + 1,
+                        start.elapsed().as_secs_f64(),
+                        avg_all,
+                        kept_games,
+                        kept_avg,
+                    );
+                }
+                continue;
+            }
+
+            // Dedup by final board state
+            let final_encoded = encode_board(&plateau);
+            let key = board_key(&final_encoded);
+            if !seen_boards.insert(key) {
+                continue; // duplicate board
+            }
+
+            // Record all turns for this game
+            let game_idx = kept_games;
+            for (turn, encoded, tile, chosen, distribution) in turns {
+                all_records.push(TurnRecord {
+                    game_idx,
+                    turn,
+                    plateau: encoded,
+                    tile,
+                    chosen_position: chosen,
+                    final_score: score,
+                    distribution,
+                });
+            }
+            kept_games += 1;
+            total_score += score as i64;
 
-        // Filter by min score
-        if score < cli.min_score {
             if (game_num + 1) % 5000 == 0 {
                 let avg_all =
                     all_scores.iter().map(|&s| s as f64).sum::<f64>() / all_scores.len() as f64;
-                let kept_avg = if kept_games > 0 {
-                    total_score as f64 / kept_games as f64
-                } else {
-                    0.0
-                };
+                let kept_avg = total_score as f64 / kept_games as f64;
                 println!(
                     "  {:>6} games ({:.1}s) | avg={:.1} | kept={} (avg={:.1})",
                     game_num + 1,
@@ -374,73 +479,43 @@ fn main() -> Result<(), Box<dyn Error>> {
                     kept_avg,
                 );
             }
-            continue;
-        }
-
-        // Dedup by final board state
-        let final_encoded = encode_board(&plateau);
-        let key = board_key(&final_encoded);
-        if !seen_boards.insert(key) {
-            continue; // duplicate board
-        }
-
-        // Record all turns for this game
-        let game_idx = kept_games;
-        for (turn, encoded, tile, chosen) in turns {
-            all_records.push(TurnRecord {
-                game_idx,
-                turn,
-                plateau: encoded,
-                tile,
-                chosen_position: chosen,
-                final_score: score,
-            });
-        }
-        kept_games += 1;
-        total_score += score as i64;
-
-        if (game_num + 1) % 5000 == 0 {
-            let avg_all =
-                all_scores.iter().map(|&s| s as f64).sum::<f64>() / all_scores.len() as f64;
-            let kept_avg = total_score as f64 / kept_games as f64;
-            println!(
-                "  {:>6} games ({:.1}s) | avg={:.1} | kept={} (avg={:.1})",
-                game_num + 1,
-                start.elapsed().as_secs_f64(),
-                avg_all,
-                kept_games,
-                kept_avg,
-            );
         }
     }
 
-    // Final stats
+    // Final stats, over the games played by this run
     let elapsed = start.elapsed().as_secs_f64();
-    let avg_all = all_scores.iter().map(|&s| s as f64).sum::<f64>() / all_scores.len() as f64;
-    let mut sorted = all_scores.clone();
-    sorted.sort();
-    let median = sorted[sorted.len() / 2];
-    let min = sorted[0];
-    let max = sorted[sorted.len() - 1];
-    let above_threshold = all_scores.iter().filter(|&&s| s >= cli.min_score).count();
-    let dup_count = above_threshold - kept_games;
-
     println!();
     println!("=== Results ===");
+    if !all_scores.is_empty() {
+        let avg_all = all_scores.iter().map(|&s| s as f64).sum::<f64>() / all_scores.len() as f64;
+        let mut sorted = all_scores.clone();
+        sorted.sort();
+        let median = sorted[sorted.len() / 2];
+        let min = sorted[0];
+        let max = sorted[sorted.len() - 1];
+        let above_threshold = all_scores.iter().filter(|&&s| s >= cli.min_score).count();
+        let dup_count = above_threshold - (kept_games - resumed_games);
+
+        println!(
+            "  All games:   {} played, avg={:.1}, median={}, min={}, max={}",
+            all_scores.len(),
+            avg_all,
+            median,
+            min,
+            max
+        );
+        println!(
+            "  Above {}:  {} ({:.1}%)",
+            cli.min_score,
+            above_threshold,
+            above_threshold as f64 / all_scores.len() as f64 * 100.0
+        );
+        println!("  Duplicates:  {}", dup_count);
+    }
     println!(
-        "  All games:   {} played, avg={:.1}, median={}, min={}, max={}",
-        cli.num_games, avg_all, median, min, max
-    );
-    println!(
-        "  Above {}:  {} ({:.1}%)",
-        cli.min_score,
-        above_threshold,
-        above_threshold as f64 / cli.num_games as f64 * 100.0
-    );
-    println!("  Duplicates:  {}", dup_count);
-    println!(
-        "  Kept:        {} unique games, {} turn records",
+        "  Kept:        {} unique games ({} resumed), {} turn records",
         kept_games,
+        resumed_games,
         all_records.len()
     );
     if kept_games > 0 {
@@ -452,7 +527,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("  Time:        {:.1}s", elapsed);
 
     // Save
-    save_csv(&all_records, &cli.output)?;
+    finalize_csv(&all_records, &cli.output)?;
     println!("\nSaved to {}", cli.output);
 
     Ok(())
@@ -0,0 +1,149 @@
+//! Play and print one full solo game, no server or training data needed.
+//!
+//! Tiles are drawn at random; MCTS picks each move, guided by a policy/value
+//! pair when `--policy-path` and `--value-path` are given, rollout-only
+//! otherwise. Every turn prints the board, the move and the running score,
+//! and the game ends with the lines that scored.
+//!
+//! Usage:
+//!   cargo run --release --bin play_demo
+//!   cargo run --release --bin play_demo -- --policy-path model_weights/cnn/policy/policy.params \
+//!       --value-path model_weights/cnn/value/value.params --seed 7
+
+use clap::Parser;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tch::{nn, Device};
+
+use take_it_easy::game::board_geometry::LINES;
+use take_it_easy::mcts::demo_game::{play_demo_game, DemoPlayer};
+use take_it_easy::neural::manager::NNArchitecture;
+use take_it_easy::neural::model_io::load_varstore;
+use take_it_easy::neural::policy_value_net::{PolicyNet, ValueNet};
+use take_it_easy::scoring::scoring::{line_scores, result};
+use take_it_easy::utils::render::render_plateau;
+
+#[derive(Parser)]
+#[command(
+    name = "play_demo",
+    about = "Play and print one full Take It Easy game"
+)]
+struct Args {
+    /// Policy network weights (pure MCTS without them)
+    #[arg(long)]
+    policy_path: Option<String>,
+
+    /// Value network weights (pure MCTS without them)
+    #[arg(long)]
+    value_path: Option<String>,
+
+    /// Architecture of the loaded networks: cnn, gnn, cnn-onehot, graph-transformer
+    #[arg(long, default_value = "cnn")]
+    arch: NNArchitecture,
+
+    /// MCTS simulations per move
+    #[arg(long, default_value_t = 150)]
+    num_simulations: usize,
+
+    /// Random seed of the tile draws
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+/// Networks with the var stores holding their weights.
+type Networks = (nn::VarStore, PolicyNet, nn::VarStore, ValueNet);
+
+/// Policy/value pair loaded on the CPU.
+fn load_networks(
+    arch: NNArchitecture,
+    policy_path: &str,
+    value_path: &str,
+) -> Result<Networks, Box<dyn std::error::Error>> {
+    let mut policy_vs = nn::VarStore::new(Device::Cpu);
+    let policy_net = PolicyNet::new(&policy_vs, arch.input_dim(), arch);
+    load_varstore(&mut policy_vs, policy_path)?;
+
+    let mut value_vs = nn::VarStore::new(Device::Cpu);
+    let value_net = ValueNet::new(&value_vs, arch.input_dim(), arch);
+    load_varstore(&mut value_vs, value_path)?;
+
+    Ok((policy_vs, policy_net, value_vs, value_net))
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let networks = match (&args.policy_path, &args.value_path) {
+        (Some(policy_path), Some(value_path)) => {
+            match load_networks(args.arch, policy_path, value_path) {
+                Ok(networks) => Some(networks),
+                Err(e) => {
+                    eprintln!("Cannot load the networks ({}), using pure MCTS", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let player = match &networks {
+        Some((_, policy_net, _, value_net)) => DemoPlayer::Neural {
+            policy_net,
+            value_net,
+        },
+        None => DemoPlayer::PureMcts,
+    };
+
+    println!("================================================");
+    println!("  Take It Easy - demo game");
+    println!(
+        "  Player: {}, {} simulations/move, seed {}",
+        match player {
+            DemoPlayer::Neural { .. } => format!("MCTS + {} networks", args.arch),
+            DemoPlayer::PureMcts => "pure MCTS".to_string(),
+        },
+        args.num_simulations,
+        args.seed
+    );
+    println!("================================================");
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let plateau = play_demo_game(&player, args.num_simulations, &mut rng, |turn| {
+        println!(
+            "\nTurn {:>2}/19  tile {}{}{} -> position {:>2}  score {}",
+            turn.turn + 1,
+            turn.tile.0,
+            turn.tile.1,
+            turn.tile.2,
+            turn.position,
+            turn.score
+        );
+        println!("{}", render_plateau(&turn.plateau));
+    });
+
+    println!("\n================================================");
+    println!("  Final score: {}", result(&plateau));
+    println!("================================================");
+    let scoring_lines: Vec<_> = LINES
+        .iter()
+        .zip(line_scores(&plateau))
+        .filter(|(_, points)| *points > 0)
+        .collect();
+    if scoring_lines.is_empty() {
+        println!("  No line scored");
+    }
+    for ((positions, direction), points) in scoring_lines {
+        let direction = match *direction {
+            0 => "horizontal",
+            1 => "diagonal v2",
+            _ => "diagonal v3",
+        };
+        println!(
+            "  {:<12} {:?}: {} x {} = {}",
+            direction,
+            positions,
+            points / positions.len() as i32,
+            positions.len(),
+            points
+        );
+    }
+}
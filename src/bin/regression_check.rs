@@ -0,0 +1,75 @@
+//! Fails when a candidate benchmark run is weaker than the baseline.
+//!
+//! Compares two `--json-out` summaries of `benchmark_strategies` or
+//! `tournament` and exits with status 1 if any model shared by both lost more
+//! than `--min-delta` points of average score.
+//!
+//! Usage:
+//!   cargo run --release --bin regression_check -- \
+//!       --baseline baseline.json --candidate results.json --min-delta 2.0
+
+use clap::Parser;
+use std::error::Error;
+use std::process;
+
+use take_it_easy::training::benchmark_report::{compare_reports, BenchmarkReport};
+
+#[derive(Parser)]
+#[command(
+    name = "regression_check",
+    about = "Exit non-zero when a benchmark summary regresses against a baseline"
+)]
+struct Args {
+    /// Summary of the reference run
+    #[arg(long)]
+    baseline: String,
+
+    /// Summary of the run to check
+    #[arg(long)]
+    candidate: String,
+
+    /// Average score a model may lose before it counts as a regression
+    #[arg(long, default_value_t = 0.0)]
+    min_delta: f64,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let baseline = BenchmarkReport::load_json(&args.baseline)?;
+    let candidate = BenchmarkReport::load_json(&args.candidate)?;
+    if baseline.seed != candidate.seed || baseline.num_games != candidate.num_games {
+        println!(
+            "Warning: runs differ (seed {} vs {}, {} vs {} games), averages are less comparable\n",
+            baseline.seed, candidate.seed, baseline.num_games, candidate.num_games
+        );
+    }
+
+    let deltas = compare_reports(&baseline, &candidate, args.min_delta)?;
+    println!(
+        "{:<30} | {:>8} | {:>9} | {:>7}",
+        "Model", "Baseline", "Candidate", "Delta"
+    );
+    println!("{}", "-".repeat(64));
+    for delta in &deltas {
+        println!(
+            "{:<30} | {:>8.1} | {:>9.1} | {:>+7.1}{}",
+            delta.name,
+            delta.baseline_avg,
+            delta.candidate_avg,
+            delta.delta,
+            if delta.regressed { "  REGRESSION" } else { "" }
+        );
+    }
+
+    let regressions = deltas.iter().filter(|d| d.regressed).count();
+    if regressions > 0 {
+        eprintln!(
+            "\n{} model(s) lost more than {:.1} points",
+            regressions, args.min_delta
+        );
+        process::exit(1);
+    }
+    println!("\nNo regression (tolerance {:.1} points)", args.min_delta);
+    Ok(())
+}
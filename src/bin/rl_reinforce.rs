@@ -13,6 +13,7 @@ use std::path::Path;
 use std::time::Instant;
 use tch::{nn, nn::OptimizerConfig, Device, Kind, Tensor};
 
+use take_it_easy::game::board_geometry::{line_value, LINES};
 use take_it_easy::game::create_deck::create_deck;
 use take_it_easy::neural::device_util::parse_device;
 use take_it_easy::game::get_legal_moves::get_legal_moves;
@@ -112,36 +113,10 @@ struct Trajectory {
 /// Returns immediate reward when placing a tile at `pos` completes one or more lines.
 /// Each completed matching line yields value × multiplier / 100.0.
 fn line_completion_reward(plateau: &take_it_easy::game::plateau::Plateau, pos: usize) -> f64 {
-    // 15 lines: (positions, multiplier, selector: 0=t.0, 1=t.1, 2=t.2)
-    const LINES: [(&[usize], i32, u8); 15] = [
-        (&[0, 1, 2], 3, 0),
-        (&[3, 4, 5, 6], 4, 0),
-        (&[7, 8, 9, 10, 11], 5, 0),
-        (&[12, 13, 14, 15], 4, 0),
-        (&[16, 17, 18], 3, 0),
-        (&[0, 3, 7], 3, 1),
-        (&[1, 4, 8, 12], 4, 1),
-        (&[2, 5, 9, 13, 16], 5, 1),
-        (&[6, 10, 14, 17], 4, 1),
-        (&[11, 15, 18], 3, 1),
-        (&[7, 12, 16], 3, 2),
-        (&[3, 8, 13, 17], 4, 2),
-        (&[0, 4, 9, 14, 18], 5, 2),
-        (&[1, 5, 10, 15], 4, 2),
-        (&[2, 6, 11], 3, 2),
-    ];
-
-    #[inline]
-    fn tile_val(tile: &Tile, axis: u8) -> i32 {
-        match axis {
-            0 => tile.0,
-            1 => tile.1,
-            _ => tile.2,
-        }
-    }
-
     let mut reward = 0.0;
-    for &(indices, multiplier, axis) in &LINES {
+    // Line multiplier = line length
+    for &(indices, axis) in LINES.iter() {
+        let multiplier = indices.len() as i32;
         if !indices.contains(&pos) {
             continue;
         }
@@ -153,8 +128,8 @@ fn line_completion_reward(plateau: &take_it_easy::game::plateau::Plateau, pos: u
             continue;
         }
         // Check all values match for this axis
-        let first = tile_val(&plateau.tiles[indices[0]], axis);
-        let all_match = indices.iter().all(|&i| tile_val(&plateau.tiles[i], axis) == first);
+        let first = line_value(&plateau.tiles[indices[0]], axis);
+        let all_match = indices.iter().all(|&i| line_value(&plateau.tiles[i], axis) == first);
         if all_match {
             reward += (first * multiplier) as f64 / 100.0;
         }
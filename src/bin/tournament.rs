@@ -0,0 +1,201 @@
+//! Round-robin tournament between Graph Transformer policies.
+//!
+//! Every model plays the same tile sequences; each pair is then compared game
+//! by game. Prints the win matrix and average scores, and writes one CSV row
+//! per pairing. `elo_ratings` fits a leaderboard on that CSV.
+//!
+//! Usage:
+//!   cargo run --release --bin tournament -- \
+//!       --models model_weights/gt_gen3.safetensors model_weights/gt_gen4.safetensors \
+//!       model_weights/gt_gen5.safetensors --num-games 200
+
+use clap::Parser;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::error::Error;
+use std::path::Path;
+use tch::Device;
+
+use take_it_easy::data::tile_sequences::load_sequences;
+use take_it_easy::game::create_deck::create_deck;
+use take_it_easy::game::plateau::create_plateau_empty;
+use take_it_easy::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
+use take_it_easy::game::tile::Tile;
+use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
+use take_it_easy::neural::model_io::load_policy;
+use take_it_easy::scoring::scoring::result;
+use take_it_easy::strategy::gt_boost::gt_boosted_select;
+use take_it_easy::training::benchmark_report::{BenchmarkReport, ModelSummary};
+use take_it_easy::training::tournament::{round_robin, save_pairings, win_matrix};
+
+#[derive(Parser)]
+#[command(
+    name = "tournament",
+    about = "Round-robin comparison of Graph Transformer policies on shared tile sequences"
+)]
+struct Args {
+    /// Policy weights of the competing models
+    #[arg(long, num_args = 2.., required = true)]
+    models: Vec<String>,
+
+    /// Line boost of each model, in --models order (one value = all models, 0 = GT Direct)
+    #[arg(long, value_delimiter = ',', default_value = "0")]
+    line_boost: Vec<f64>,
+
+    /// Games (tile sequences) per model
+    #[arg(long, default_value_t = 100)]
+    num_games: usize,
+
+    /// Play the sequences of this file instead of random ones
+    #[arg(long)]
+    sequences_file: Option<String>,
+
+    /// Seed of the random tile sequences
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    /// One row per pairing
+    #[arg(long, default_value = "tournament.csv")]
+    out: String,
+
+    /// Also write a JSON summary per model (see `regression_check`)
+    #[arg(long)]
+    json_out: Option<String>,
+
+    /// Architecture of models saved without metadata
+    #[arg(long, default_value_t = 128)]
+    embed_dim: i64,
+
+    #[arg(long, default_value_t = 2)]
+    num_layers: usize,
+
+    #[arg(long, default_value_t = 4)]
+    num_heads: i64,
+}
+
+/// 19 tiles drawn from a shuffled deck.
+fn random_tile_sequence(rng: &mut StdRng) -> Vec<Tile> {
+    let mut tiles = get_available_tiles(&create_deck());
+    tiles.shuffle(rng);
+    tiles.truncate(19);
+    tiles
+}
+
+fn play_game(tiles: &[Tile], policy_net: &GraphTransformerPolicyNet, boost: f64) -> i32 {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+    for (turn, tile) in tiles.iter().enumerate() {
+        let position = gt_boosted_select(&plateau, tile, &deck, turn, policy_net, boost);
+        plateau.tiles[position] = *tile;
+        deck = replace_tile_in_deck(&deck, tile);
+    }
+    result(&plateau)
+}
+
+/// Short display name: file stem, plus the line boost when there is one.
+fn model_name(path: &str, boost: f64) -> String {
+    let stem = Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    if boost > 0.0 {
+        format!("{}+b{}", stem, boost)
+    } else {
+        stem
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let boosts = match args.line_boost.len() {
+        1 => vec![args.line_boost[0]; args.models.len()],
+        n if n == args.models.len() => args.line_boost.clone(),
+        n => {
+            return Err(format!(
+                "--line-boost has {} values for {} models (expected 1 or {})",
+                n,
+                args.models.len(),
+                args.models.len()
+            )
+            .into())
+        }
+    };
+
+    let sequences = match &args.sequences_file {
+        Some(path) => load_sequences(path)?,
+        None => {
+            let mut rng = StdRng::seed_from_u64(args.seed);
+            (0..args.num_games)
+                .map(|_| random_tile_sequence(&mut rng))
+                .collect()
+        }
+    };
+    println!(
+        "{} models, {} tile sequences\n",
+        args.models.len(),
+        sequences.len()
+    );
+
+    let mut results = Vec::with_capacity(args.models.len());
+    for (path, &boost) in args.models.iter().zip(&boosts) {
+        let (_vs, policy_net) = load_policy(
+            path,
+            Device::Cpu,
+            args.embed_dim,
+            args.num_layers,
+            args.num_heads,
+        )?;
+        let scores: Vec<i32> = sequences
+            .iter()
+            .map(|tiles| play_game(tiles, &policy_net, boost))
+            .collect();
+        let name = model_name(path, boost);
+        let avg = scores.iter().sum::<i32>() as f64 / scores.len().max(1) as f64;
+        println!("  {:<30} {:>7.1} pts", name, avg);
+        results.push((name, scores));
+    }
+
+    let scores: Vec<Vec<i32>> = results.iter().map(|(_, s)| s.clone()).collect();
+    let wins = win_matrix(&scores);
+    println!("\nWins of the row model against the column model:");
+    print!("{:<30}", "");
+    for i in 0..results.len() {
+        print!(" {:>6}", format!("#{}", i + 1));
+    }
+    println!();
+    for (i, (name, _)) in results.iter().enumerate() {
+        print!("{:<30}", format!("#{} {}", i + 1, name));
+        for (j, count) in wins[i].iter().enumerate() {
+            if i == j {
+                print!(" {:>6}", "-");
+            } else {
+                print!(" {:>6}", count);
+            }
+        }
+        println!();
+    }
+
+    let pairings = round_robin(&results);
+    save_pairings(&pairings, &args.out)?;
+    println!("\n{} pairings written to {}", pairings.len(), args.out);
+
+    if let Some(path) = &args.json_out {
+        let models = results
+            .iter()
+            .zip(&args.models)
+            .zip(&wins)
+            .map(|(((name, scores), path), wins)| {
+                ModelSummary::from_scores(name, path, scores, wins.iter().sum())
+            })
+            .collect();
+        let report = BenchmarkReport {
+            tool: "tournament".to_string(),
+            seed: args.seed,
+            num_games: sequences.len(),
+            models,
+        };
+        report.save_json(path)?;
+        println!("Summary written to {}", path);
+    }
+    Ok(())
+}
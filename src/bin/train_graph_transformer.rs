@@ -12,28 +12,46 @@
 //!   cargo run --release --bin train_graph_transformer -- --device cuda --gen-games 100000 \
 //!     --policy-path model_weights/graph_transformer_policy.safetensors \
 //!     --embed-dim 256 --num-layers 4 --heads 8 --dropout 0.2
+//!   cargo run --release --bin train_graph_transformer -- --selfplay-dir data/selfplay
+//!
+//! With --streaming, the self-play CSVs of --selfplay-dir are read batch by
+//! batch every epoch instead of being loaded whole, so memory does not grow
+//! with the dataset. Whole games go to validation from a hash of their index
+//! (--val-split), and batches follow file order instead of being shuffled.
+//!   cargo run --release --bin train_graph_transformer -- --selfplay-dir data/selfplay --streaming
 
 use clap::Parser;
 use rand::prelude::*;
 use rand::rngs::StdRng;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tch::{nn, nn::OptimizerConfig, Device, IndexOp, Kind, Tensor};
 
+use take_it_easy::data::selfplay_csv::{load_records, CsvBatches, Split, TurnRecord};
 use take_it_easy::game::create_deck::create_deck;
+use take_it_easy::game::deck::Deck;
 use take_it_easy::game::get_legal_moves::get_legal_moves;
 use take_it_easy::game::plateau::{create_plateau_empty, Plateau};
 use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::device_util::{check_cuda, parse_device};
 use take_it_easy::neural::graph_transformer::GraphTransformerPolicyNet;
-use take_it_easy::neural::model_io::{load_varstore, save_varstore};
-use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
+use take_it_easy::neural::model_io::{
+    load_varstore, save_varstore_with_meta, ModelMeta, GT_POLICY_ARCH,
+};
+use take_it_easy::neural::tensor_conversion::{convert_plateau_for_gat_47ch, convert_plateau_for_gat_48ch};
+use take_it_easy::neural::training::policy_target::{
+    build_policy_target, policy_kl_loss, restrict_to_legal, PolicyTargetMode,
+};
+use take_it_easy::recording::csv_writer::{filter_by_min_completions, load_games_from_csv};
+use take_it_easy::recording::PlayerType;
 use take_it_easy::scoring::scoring::result;
 use take_it_easy::strategy::gt_boost::line_boost;
+use take_it_easy::training::policy_entropy::{entropy_regularized_loss, policy_entropy};
 
 #[derive(Parser, Debug)]
 #[command(name = "train_graph_transformer")]
@@ -46,6 +64,14 @@ struct Args {
     #[arg(long, default_value_t = 100)]
     min_score: i32,
 
+    /// Minimum completed lines on the final board to include a game (CSV mode, 0 = off)
+    #[arg(long, default_value_t = 0)]
+    min_completions: usize,
+
+    /// Add the board-pressure channel (48 input features instead of 47)
+    #[arg(long, default_value_t = false)]
+    board_pressure: bool,
+
     /// Weight power: weight = (score/100)^power
     #[arg(long, default_value_t = 3.0)]
     weight_power: f64,
@@ -82,6 +108,10 @@ struct Args {
     #[arg(long, default_value_t = 0.0001)]
     weight_decay: f64,
 
+    /// Policy-entropy bonus: loss -= entropy_coef * entropy (0 = disabled)
+    #[arg(long, default_value_t = 0.0)]
+    entropy_coef: f64,
+
     /// LR scheduler: none, cosine
     #[arg(long, default_value = "cosine")]
     lr_scheduler: String,
@@ -110,6 +140,16 @@ struct Args {
     #[arg(long, default_value = "data")]
     data_dir: String,
 
+    /// Train on the self-play records (.csv/.bin) of this directory instead of
+    /// --data-dir; MCTS visit distributions become soft policy targets
+    #[arg(long)]
+    selfplay_dir: Option<String>,
+
+    /// Stream the --selfplay-dir CSVs batch by batch instead of loading them
+    /// in memory (validation split by game)
+    #[arg(long, default_value_t = false)]
+    streaming: bool,
+
     /// Number of GT Direct self-play games to generate (0 = use CSV from data-dir)
     #[arg(long, default_value_t = 0)]
     gen_games: usize,
@@ -143,6 +183,17 @@ struct Sample {
     turn: usize,
     final_score: i32,
     weight: f64,
+    /// Soft policy target (MCTS visit distribution), `position` one-hot if `None`
+    target: Option<[f32; 19]>,
+}
+
+/// Tensors of one training batch, on the training device.
+struct Batch {
+    features: Tensor,
+    masks: Tensor,
+    targets: Tensor,
+    target_dists: Tensor,
+    weights: Tensor,
 }
 
 fn compute_lr(base_lr: f64, epoch: usize, total_epochs: usize, scheduler: &str, min_lr_ratio: f64) -> f64 {
@@ -181,6 +232,9 @@ fn main() {
     println!("  FF dim:       {} (4x embed)", args.embed_dim * 4);
     println!("  Dropout:      {}", args.dropout);
     println!("  Weight decay: {}", args.weight_decay);
+    if args.entropy_coef > 0.0 {
+        println!("  Entropy coef: {}", args.entropy_coef);
+    }
     println!("  Epochs:       {}", args.epochs);
     println!("  LR:           {} ({})", args.lr, args.lr_scheduler);
     println!("  Weight power: {:.1}", args.weight_power);
@@ -189,17 +243,52 @@ fn main() {
         println!("  Policy:       {}", args.policy_path);
         println!("  Gen arch:     dim={}, layers={}, heads={}", args.gen_embed_dim, args.gen_num_layers, args.gen_heads);
         println!("  Boost:        {:.1}", args.boost);
+    } else if let Some(dir) = &args.selfplay_dir {
+        println!("  Data:         self-play records from {}{}", dir, if args.streaming { " (streaming)" } else { "" });
+        println!("  Min score:    {} pts", args.min_score);
     } else {
         println!("  Data:         CSV from {}", args.data_dir);
         println!("  Min score:    {} pts", args.min_score);
+        if args.min_completions > 0 {
+            println!("  Min lines:    {} completed", args.min_completions);
+        }
+    }
+
+    if args.streaming {
+        if let Err(e) = train_streaming(&args, device) {
+            eprintln!("Error: {}", e);
+        }
+        return;
     }
 
     // Load or generate data
     let samples = if args.gen_games > 0 {
         generate_selfplay_data(&args, device)
+    } else if let Some(dir) = &args.selfplay_dir {
+        println!("\n Loading self-play records from {}...", dir);
+        let s = match load_selfplay_records(dir, args.min_score, args.weight_power) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Error loading self-play records: {}", e);
+                return;
+            }
+        };
+        let soft = s.iter().filter(|sample| sample.target.is_some()).count();
+        println!(
+            "   Loaded {} samples (score >= {}), {} with visit distributions",
+            s.len(),
+            args.min_score,
+            soft
+        );
+        s
     } else {
         println!("\n Loading data from {}...", args.data_dir);
-        let s = load_all_csv_weighted(&args.data_dir, args.min_score, args.weight_power);
+        let s = load_all_csv_weighted(
+            &args.data_dir,
+            args.min_score,
+            args.weight_power,
+            args.min_completions,
+        );
         println!("   Loaded {} samples (score >= {})", s.len(), args.min_score);
         s
     };
@@ -224,15 +313,19 @@ fn main() {
     println!("\n Pre-computing features on {:?}...", device);
     let precompute_start = Instant::now();
     let n = samples.len();
-    let all_features: Vec<Tensor> = samples.iter().map(|s| sample_to_features(s)).collect();
+    let all_features: Vec<Tensor> = samples.iter().map(|s| sample_to_features(s, args.board_pressure)).collect();
     let all_masks: Vec<Tensor> = samples.iter().map(|s| get_available_mask(s)).collect();
     let all_targets: Vec<i64> = samples.iter().map(|s| s.position as i64).collect();
+    let all_target_dists: Vec<f32> = samples.iter().flat_map(target_distribution).collect();
     let all_weights: Vec<f64> = samples.iter().map(|s| s.weight).collect();
 
     // Stack and move to device once
     let features_gpu = Tensor::stack(&all_features, 0).to_device(device);
     let masks_gpu = Tensor::stack(&all_masks, 0).to_device(device);
     let targets_gpu = Tensor::from_slice(&all_targets).to_device(device);
+    let target_dists_gpu = Tensor::from_slice(&all_target_dists)
+        .view([n as i64, 19])
+        .to_device(device);
     let weights_gpu = Tensor::from_slice(&all_weights).to_kind(Kind::Float).to_device(device);
     drop(all_features);
     drop(all_masks);
@@ -250,19 +343,7 @@ fn main() {
     println!("   Train: {} samples, Val: {} samples", train_indices.len(), val_indices.len());
 
     // Initialize network on target device
-    let vs = nn::VarStore::new(device);
-    let policy_net = GraphTransformerPolicyNet::new(
-        &vs,
-        47, // input_dim
-        args.embed_dim,
-        args.num_layers,
-        args.heads,
-        args.dropout,
-    );
-    let mut opt = nn::Adam {
-        wd: args.weight_decay,
-        ..Default::default()
-    }.build(&vs, args.lr).unwrap();
+    let (vs, policy_net, meta, mut opt) = build_network(&args, device);
 
     let mut best_val_acc = 0.0f64;
     let mut best_game_score = 0.0f64;
@@ -280,6 +361,7 @@ fn main() {
         train_idx.shuffle(&mut rng);
 
         let mut train_loss = 0.0;
+        let mut train_entropy = 0.0;
         let mut train_correct = 0usize;
         let n_batches = train_idx.len() / args.batch_size;
 
@@ -289,27 +371,21 @@ fn main() {
             let batch_idx: Vec<i64> = train_idx[start..end].iter().map(|&i| i as i64).collect();
             let idx_tensor = Tensor::from_slice(&batch_idx).to_device(device);
 
-            let features = features_gpu.index_select(0, &idx_tensor);
-            let targets = targets_gpu.index_select(0, &idx_tensor);
-            let masks = masks_gpu.index_select(0, &idx_tensor);
-            let weights = weights_gpu.index_select(0, &idx_tensor);
-
-            let logits = policy_net.forward(&features, true);
-            let masked_logits = logits + &masks;
-            let log_probs = masked_logits.log_softmax(-1, Kind::Float);
-
-            let per_sample_loss = -log_probs.gather(1, &targets.unsqueeze(1), false).squeeze_dim(1);
-            let weighted_loss = (&per_sample_loss * &weights).sum(Kind::Float) / weights.sum(Kind::Float);
-
-            opt.backward_step(&weighted_loss);
-            train_loss += f64::try_from(&weighted_loss).unwrap();
-
-            let preds = masked_logits.argmax(-1, false);
-            let correct: i64 = preds.eq_tensor(&targets).sum(Kind::Int64).int64_value(&[]);
-            train_correct += correct as usize;
+            let batch = Batch {
+                features: features_gpu.index_select(0, &idx_tensor),
+                masks: masks_gpu.index_select(0, &idx_tensor),
+                targets: targets_gpu.index_select(0, &idx_tensor),
+                target_dists: target_dists_gpu.index_select(0, &idx_tensor),
+                weights: weights_gpu.index_select(0, &idx_tensor),
+            };
+            let (loss, entropy, correct) = train_batch(&policy_net, &mut opt, &batch, args.entropy_coef);
+            train_loss += loss;
+            train_entropy += entropy;
+            train_correct += correct;
         }
 
         train_loss /= n_batches as f64;
+        train_entropy /= n_batches as f64;
         let train_acc = train_correct as f64 / (n_batches * args.batch_size) as f64;
 
         // Validation
@@ -320,7 +396,7 @@ fn main() {
         // Game evaluation every 10 epochs
         let should_eval = epoch % 10 == 9 || epoch == args.epochs - 1;
         let game_score = if should_eval {
-            let (score, _) = eval_games(&policy_net, 100, &mut rng, device);
+            let (score, _) = eval_games(&policy_net, 100, &mut rng, device, args.board_pressure);
             score
         } else {
             0.0
@@ -331,9 +407,9 @@ fn main() {
         if epoch % 5 == 0 || epoch == args.epochs - 1 || improved {
             let lr_info = format!(" | LR: {:.6}", current_lr);
             let game_info = if should_eval { format!(" | Game: {:.1} pts", game_score) } else { String::new() };
-            println!("Epoch {:3}/{:3} | Train Loss: {:.4}, Acc: {:.2}% | Val Loss: {:.4}, Acc: {:.2}% | {:.1}s{}{}{}",
+            println!("Epoch {:3}/{:3} | Train Loss: {:.4}, Acc: {:.2}%, Entropy: {:.3} | Val Loss: {:.4}, Acc: {:.2}% | {:.1}s{}{}{}",
                      epoch + 1, args.epochs,
-                     train_loss, train_acc * 100.0,
+                     train_loss, train_acc * 100.0, train_entropy,
                      val_loss, val_acc * 100.0,
                      elapsed, lr_info, game_info,
                      if improved { " *" } else { "" });
@@ -347,7 +423,7 @@ fn main() {
             best_game_score = game_score;
             evals_without_improvement = 0;
             let path = format!("{}_policy.safetensors", args.save_path);
-            if let Err(e) = save_varstore(&vs, &path) {
+            if let Err(e) = save_varstore_with_meta(&vs, &path, &meta) {
                 eprintln!("Warning: failed to save: {}", e);
             }
             println!("   New best game score! Model saved.");
@@ -370,7 +446,7 @@ fn main() {
 
     // Final evaluation
     println!("\n Evaluating by playing 200 games...\n");
-    let (gt_avg, gt_scores) = eval_games(&policy_net, 200, &mut rng, device);
+    let (gt_avg, gt_scores) = eval_games(&policy_net, 200, &mut rng, device, args.board_pressure);
     let greedy_avg = eval_greedy(200, args.seed);
 
     println!("  Graph Transformer: {:.2} pts", gt_avg);
@@ -490,6 +566,7 @@ fn generate_selfplay_data(args: &Args, device: Device) -> Vec<Sample> {
                     turn,
                     final_score,
                     weight,
+                    target: None,
                 });
             }
         }
@@ -513,7 +590,12 @@ fn generate_selfplay_data(args: &Args, device: Device) -> Vec<Sample> {
 
 // ── CSV loading ───────────────────────────────────────────────────────────
 
-fn load_all_csv_weighted(dir: &str, min_score: i32, weight_power: f64) -> Vec<Sample> {
+fn load_all_csv_weighted(
+    dir: &str,
+    min_score: i32,
+    weight_power: f64,
+    min_completions: usize,
+) -> Vec<Sample> {
     let mut samples = Vec::new();
     let path = Path::new(dir);
     if !path.exists() { return samples; }
@@ -522,14 +604,31 @@ fn load_all_csv_weighted(dir: &str, min_score: i32, weight_power: f64) -> Vec<Sa
         let entry = entry.unwrap();
         let file_path = entry.path();
         if file_path.extension().map_or(false, |e| e == "csv") {
-            samples.extend(load_csv_weighted(&file_path, min_score, weight_power));
+            samples.extend(load_csv_weighted(&file_path, min_score, weight_power, min_completions));
         }
     }
     samples
 }
 
-fn load_csv_weighted(path: &Path, min_score: i32, weight_power: f64) -> Vec<Sample> {
+fn load_csv_weighted(
+    path: &Path,
+    min_score: i32,
+    weight_power: f64,
+    min_completions: usize,
+) -> Vec<Sample> {
     let mut samples = Vec::new();
+    // (game, player type) whose final board completes enough lines
+    let kept_games: HashSet<(String, PlayerType)> = if min_completions > 0 {
+        match load_games_from_csv(path) {
+            Ok(records) => filter_by_min_completions(records, min_completions)
+                .into_iter()
+                .map(|r| (r.game_id, r.player_type))
+                .collect(),
+            Err(_) => return samples,
+        }
+    } else {
+        HashSet::new()
+    };
     let file = match File::open(path) { Ok(f) => f, Err(_) => return samples };
     let reader = BufReader::new(file);
     let mut lines = reader.lines();
@@ -543,6 +642,11 @@ fn load_csv_weighted(path: &Path, min_score: i32, weight_power: f64) -> Vec<Samp
         let final_score: i32 = match fields[26].parse() { Ok(s) => s, Err(_) => continue };
         if final_score < min_score { continue; }
 
+        if min_completions > 0 {
+            let key = (fields[0].to_string(), PlayerType::from_str(fields[2]));
+            if !kept_games.contains(&key) { continue; }
+        }
+
         let turn: usize = match fields[1].parse() { Ok(t) => t, Err(_) => continue };
         let mut plateau = [0i32; 19];
         for i in 0..19 { plateau[i] = fields[3 + i].parse().unwrap_or(0); }
@@ -555,24 +659,269 @@ fn load_csv_weighted(path: &Path, min_score: i32, weight_power: f64) -> Vec<Samp
 
         let weight = (final_score as f64 / 100.0).powf(weight_power);
 
-        samples.push(Sample { plateau, tile, position, turn, final_score, weight });
+        samples.push(Sample { plateau, tile, position, turn, final_score, weight, target: None });
     }
     samples
 }
 
+/// Samples of the self-play records (`.csv` / `.bin`) of `dir`, with the
+/// visit distribution of MCTS records as soft target.
+fn load_selfplay_records(
+    dir: &str,
+    min_score: i32,
+    weight_power: f64,
+) -> Result<Vec<Sample>, Box<dyn Error>> {
+    let mut samples = Vec::new();
+    for path in selfplay_paths(dir, &["csv", "bin"])? {
+        for record in load_records(&path.to_string_lossy())? {
+            if record.final_score >= min_score {
+                samples.push(record_to_sample(&record, weight_power));
+            }
+        }
+    }
+    Ok(samples)
+}
+
+/// Files of `dir` with one of `extensions`, sorted by name.
+fn selfplay_paths(dir: &str, extensions: &[&str]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| extensions.contains(&ext))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn record_to_sample(record: &TurnRecord, weight_power: f64) -> Sample {
+    Sample {
+        plateau: record.plateau,
+        tile: record.tile,
+        position: record.chosen_position,
+        turn: record.turn,
+        final_score: record.final_score,
+        weight: (record.final_score as f64 / 100.0).powf(weight_power),
+        target: record.distribution.as_deref().map(|visits| {
+            // Visits on occupied cells are dropped, then renormalized
+            let legal = restrict_to_legal(visits, |i| record.plateau[i] == 0);
+            let target = build_policy_target(
+                PolicyTargetMode::Visits,
+                record.chosen_position,
+                &legal,
+                &[],
+                19,
+            );
+            target.try_into().unwrap()
+        }),
+    }
+}
+
+// ── Streaming training ────────────────────────────────────────────────────
+
+/// Streams the self-play CSVs of `paths` once, calling `step` on every full
+/// batch of samples of `split` that pass --min-score. Only one batch is held
+/// in memory. Returns the number of batches.
+fn for_each_streamed_batch(
+    paths: &[PathBuf],
+    split: Split,
+    args: &Args,
+    mut step: impl FnMut(&[Sample]),
+) -> Result<usize, Box<dyn Error>> {
+    let mut pending: Vec<Sample> = Vec::with_capacity(args.batch_size);
+    let mut n_batches = 0;
+    for path in paths {
+        for records in CsvBatches::open(&path.to_string_lossy(), args.batch_size, split)? {
+            for record in records? {
+                if record.final_score < args.min_score {
+                    continue;
+                }
+                pending.push(record_to_sample(&record, args.weight_power));
+                if pending.len() == args.batch_size {
+                    step(&pending);
+                    pending.clear();
+                    n_batches += 1;
+                }
+            }
+        }
+    }
+    Ok(n_batches)
+}
+
+/// Training loop of --streaming: the same steps as the in-memory loop, on
+/// batches read from disk every epoch.
+fn train_streaming(args: &Args, device: Device) -> Result<(), Box<dyn Error>> {
+    let dir = args.selfplay_dir.as_deref().ok_or("--streaming reads --selfplay-dir")?;
+    if args.batch_size == 0 {
+        return Err("--batch-size must be positive".into());
+    }
+    let paths = selfplay_paths(dir, &["csv"])?;
+    if paths.is_empty() {
+        return Err(format!("No self-play CSV in {}", dir).into());
+    }
+    println!("\n Streaming {} self-play CSVs from {}", paths.len(), dir);
+
+    let (vs, policy_net, meta, mut opt) = build_network(args, device);
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let mut best_game_score = 0.0f64;
+    let mut evals_without_improvement = 0usize;
+
+    println!("\n Training Graph Transformer...\n");
+    for epoch in 0..args.epochs {
+        let epoch_start = Instant::now();
+        let current_lr = compute_lr(args.lr, epoch, args.epochs, &args.lr_scheduler, args.min_lr_ratio);
+        opt.set_lr(current_lr);
+
+        let (mut train_loss, mut train_correct) = (0.0, 0usize);
+        let n_train = for_each_streamed_batch(&paths, Split::Train(args.val_split), args, |samples| {
+            let batch = batch_tensors(samples, args.board_pressure, device);
+            let (loss, _, correct) = train_batch(&policy_net, &mut opt, &batch, args.entropy_coef);
+            train_loss += loss;
+            train_correct += correct;
+        })?;
+        if n_train == 0 {
+            return Err(format!("Fewer than {} training samples (score >= {})", args.batch_size, args.min_score).into());
+        }
+
+        let (mut val_loss, mut val_correct) = (0.0, 0usize);
+        let n_val = for_each_streamed_batch(&paths, Split::Validation(args.val_split), args, |samples| {
+            let batch = batch_tensors(samples, args.board_pressure, device);
+            let (loss, correct) = eval_batch(&policy_net, &batch.features, &batch.targets, &batch.masks);
+            val_loss += loss;
+            val_correct += correct;
+        })?;
+        let val_batches = n_val.max(1) as f64;
+
+        let should_eval = epoch % 10 == 9 || epoch == args.epochs - 1;
+        let game_info = if should_eval {
+            let (score, _) = eval_games(&policy_net, 100, &mut rng, device, args.board_pressure);
+            if score > best_game_score {
+                best_game_score = score;
+                evals_without_improvement = 0;
+                let path = format!("{}_policy.safetensors", args.save_path);
+                save_varstore_with_meta(&vs, &path, &meta)?;
+            } else {
+                evals_without_improvement += 1;
+            }
+            format!(" | Game: {:.1} pts", score)
+        } else {
+            String::new()
+        };
+        println!("Epoch {:3}/{:3} | Train Loss: {:.4}, Acc: {:.2}% ({} batches) | Val Loss: {:.4}, Acc: {:.2}% ({} batches) | {:.1}s | LR: {:.6}{}",
+                 epoch + 1, args.epochs,
+                 train_loss / n_train as f64, train_correct as f64 / (n_train * args.batch_size) as f64 * 100.0, n_train,
+                 val_loss / val_batches, val_correct as f64 / (val_batches * args.batch_size as f64) * 100.0, n_val,
+                 epoch_start.elapsed().as_secs_f32(), current_lr, game_info);
+
+        if args.patience > 0 && evals_without_improvement >= args.patience {
+            println!("\n   Early stopping: no game score improvement for {} evals (best: {:.1} pts)",
+                     args.patience, best_game_score);
+            break;
+        }
+    }
+    println!("\n  Best game score: {:.2} pts (saved to {}_policy.safetensors)", best_game_score, args.save_path);
+    Ok(())
+}
+
 // ── Training helpers ──────────────────────────────────────────────────────
 
+fn build_network(args: &Args, device: Device) -> (nn::VarStore, GraphTransformerPolicyNet, ModelMeta, nn::Optimizer) {
+    let vs = nn::VarStore::new(device);
+    let policy_net = GraphTransformerPolicyNet::new(
+        &vs,
+        if args.board_pressure { 48 } else { 47 }, // input_dim
+        args.embed_dim,
+        args.num_layers,
+        args.heads,
+        args.dropout,
+    );
+    let meta = ModelMeta {
+        feature_channels: if args.board_pressure { 48 } else { 47 },
+        ..ModelMeta::graph_transformer(GT_POLICY_ARCH, args.embed_dim, args.num_layers, args.heads)
+    };
+    let opt = nn::Adam {
+        wd: args.weight_decay,
+        ..Default::default()
+    }.build(&vs, args.lr).unwrap();
+    (vs, policy_net, meta, opt)
+}
+
+/// One optimizer step; returns (weighted loss, mean entropy, correct moves).
+fn train_batch(net: &GraphTransformerPolicyNet, opt: &mut nn::Optimizer, batch: &Batch, entropy_coef: f64) -> (f64, f64, usize) {
+    let logits = net.forward(&batch.features, true);
+    let masked_logits = logits + &batch.masks;
+    let log_probs = masked_logits.log_softmax(-1, Kind::Float);
+
+    // KL to the visit distribution; cross-entropy on the move for one-hot targets
+    let per_sample_loss = policy_kl_loss(&batch.target_dists, &log_probs);
+    let weighted_loss = (&per_sample_loss * &batch.weights).sum(Kind::Float) / batch.weights.sum(Kind::Float);
+    let loss = entropy_regularized_loss(&weighted_loss, &log_probs, entropy_coef);
+
+    opt.backward_step(&loss);
+    let entropy = tch::no_grad(|| f64::try_from(policy_entropy(&log_probs).mean(Kind::Float)).unwrap());
+
+    let preds = masked_logits.argmax(-1, false);
+    let correct = preds.eq_tensor(&batch.targets).sum(Kind::Int64).int64_value(&[]) as usize;
+    (f64::try_from(&weighted_loss).unwrap(), entropy, correct)
+}
+
+/// Cross-entropy on the played move and correct moves, without gradients.
+fn eval_batch(net: &GraphTransformerPolicyNet, features: &Tensor, targets: &Tensor, masks: &Tensor) -> (f64, usize) {
+    let logits = tch::no_grad(|| net.forward(features, false));
+    let masked_logits = &logits + masks;
+    let log_probs = masked_logits.log_softmax(-1, Kind::Float);
+    let loss = -log_probs.gather(1, &targets.unsqueeze(1), false).squeeze_dim(1).mean(Kind::Float);
+
+    let preds = masked_logits.argmax(-1, false);
+    let correct = preds.eq_tensor(targets).sum(Kind::Int64).int64_value(&[]) as usize;
+    (f64::try_from(&loss).unwrap(), correct)
+}
+
+/// Tensors of `samples`, built the same way as the in-memory pre-computation.
+fn batch_tensors(samples: &[Sample], board_pressure: bool, device: Device) -> Batch {
+    let features: Vec<Tensor> = samples.iter().map(|s| sample_to_features(s, board_pressure)).collect();
+    let masks: Vec<Tensor> = samples.iter().map(get_available_mask).collect();
+    let targets: Vec<i64> = samples.iter().map(|s| s.position as i64).collect();
+    let target_dists: Vec<f32> = samples.iter().flat_map(target_distribution).collect();
+    let weights: Vec<f64> = samples.iter().map(|s| s.weight).collect();
+    Batch {
+        features: Tensor::stack(&features, 0).to_device(device),
+        masks: Tensor::stack(&masks, 0).to_device(device),
+        targets: Tensor::from_slice(&targets).to_device(device),
+        target_dists: Tensor::from_slice(&target_dists).view([samples.len() as i64, 19]).to_device(device),
+        weights: Tensor::from_slice(&weights).to_kind(Kind::Float).to_device(device),
+    }
+}
+
+fn target_distribution(sample: &Sample) -> [f32; 19] {
+    sample.target.unwrap_or_else(|| {
+        let mut one_hot = [0.0f32; 19];
+        one_hot[sample.position] = 1.0;
+        one_hot
+    })
+}
+
 fn decode_tile(encoded: i32) -> Tile {
     if encoded == 0 { return Tile(0, 0, 0); }
     Tile(encoded / 100, (encoded / 10) % 10, encoded % 10)
 }
 
-fn sample_to_features(sample: &Sample) -> Tensor {
+fn sample_to_features(sample: &Sample, board_pressure: bool) -> Tensor {
     let mut plateau = Plateau { tiles: vec![Tile(0, 0, 0); 19] };
     for i in 0..19 { plateau.tiles[i] = decode_tile(sample.plateau[i]); }
     let tile = Tile(sample.tile.0, sample.tile.1, sample.tile.2);
     let deck = create_deck();
-    convert_plateau_for_gat_47ch(&plateau, &tile, &deck, sample.turn, 19)
+    encode_features(&plateau, &tile, &deck, sample.turn, board_pressure)
+}
+
+fn encode_features(plateau: &Plateau, tile: &Tile, deck: &Deck, turn: usize, board_pressure: bool) -> Tensor {
+    if board_pressure {
+        convert_plateau_for_gat_48ch(plateau, tile, deck, turn, 19)
+    } else {
+        convert_plateau_for_gat_47ch(plateau, tile, deck, turn, 19)
+    }
 }
 
 fn get_available_mask(sample: &Sample) -> Tensor {
@@ -599,20 +948,15 @@ fn evaluate_gpu(net: &GraphTransformerPolicyNet, features_gpu: &Tensor, targets_
         let targets = targets_gpu.index_select(0, &idx_tensor);
         let masks = masks_gpu.index_select(0, &idx_tensor);
 
-        let logits = tch::no_grad(|| net.forward(&features, false));
-        let masked_logits = &logits + &masks;
-        let log_probs = masked_logits.log_softmax(-1, Kind::Float);
-        let loss = -log_probs.gather(1, &targets.unsqueeze(1), false).squeeze_dim(1).mean(Kind::Float);
-        total_loss += f64::try_from(&loss).unwrap();
-
-        let preds = masked_logits.argmax(-1, false);
-        total_correct += preds.eq_tensor(&targets).sum(Kind::Int64).int64_value(&[]) as usize;
+        let (loss, correct) = eval_batch(net, &features, &targets, &masks);
+        total_loss += loss;
+        total_correct += correct;
     }
 
     (total_loss / n_batches as f64, total_correct as f64 / (n_batches * batch_size) as f64)
 }
 
-fn eval_games(policy_net: &GraphTransformerPolicyNet, n_games: usize, rng: &mut StdRng, device: Device) -> (f64, Vec<i32>) {
+fn eval_games(policy_net: &GraphTransformerPolicyNet, n_games: usize, rng: &mut StdRng, device: Device, board_pressure: bool) -> (f64, Vec<i32>) {
     use take_it_easy::game::remove_tile_from_deck::get_available_tiles;
 
     let mut scores = Vec::new();
@@ -628,7 +972,7 @@ fn eval_games(policy_net: &GraphTransformerPolicyNet, n_games: usize, rng: &mut
             let avail: Vec<usize> = (0..19).filter(|&i| plateau.tiles[i] == Tile(0, 0, 0)).collect();
             if avail.is_empty() { break; }
 
-            let features = convert_plateau_for_gat_47ch(&plateau, &tile, &deck, turn, 19);
+            let features = encode_features(&plateau, &tile, &deck, turn, board_pressure);
             let feat_device = features.unsqueeze(0).to_device(device);
             let logits = tch::no_grad(|| policy_net.forward(&feat_device, false))
                 .squeeze_dim(0)
@@ -20,14 +20,23 @@ use take_it_easy::game::plateau::create_plateau_empty;
 use take_it_easy::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::graph_transformer::GraphTransformerValueNet;
-use take_it_easy::neural::model_io::save_varstore;
+use take_it_easy::neural::device_util::{check_cuda, parse_device};
+use take_it_easy::neural::model_io::{save_varstore_with_meta, ModelMeta, GT_VALUE_ARCH};
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use take_it_easy::scoring::scoring::result;
+use take_it_easy::training::early_stopping::EarlyStopping;
+use take_it_easy::training::optimizer_step::guarded_backward_step;
+use take_it_easy::training::value_normalization::ScoreNormalization;
+use take_it_easy::training::value_target::{horizon_indices, value_targets};
 
 #[derive(Parser, Debug)]
 #[command(name = "train_graph_transformer_value")]
 #[command(about = "Train Graph Transformer Value Network to predict final scores")]
 struct Args {
+    /// Device: "cpu", "cuda", "cuda:0"
+    #[arg(long, default_value = "cpu")]
+    device: String,
+
     /// Minimum score to include
     #[arg(long, default_value_t = 100)]
     min_score: i32,
@@ -36,6 +45,10 @@ struct Args {
     #[arg(long, default_value_t = 80)]
     epochs: usize,
 
+    /// Stop after this many epochs without validation improvement (default: run every epoch)
+    #[arg(long)]
+    patience: Option<usize>,
+
     /// Batch size
     #[arg(long, default_value_t = 64)]
     batch_size: usize,
@@ -44,6 +57,10 @@ struct Args {
     #[arg(long, default_value_t = 0.001)]
     lr: f64,
 
+    /// Clip gradients to this global norm before each step (default: no clipping)
+    #[arg(long)]
+    grad_clip: Option<f64>,
+
     /// Dropout rate
     #[arg(long, default_value_t = 0.1)]
     dropout: f64,
@@ -83,10 +100,22 @@ struct Args {
     /// Score normalization std
     #[arg(long, default_value_t = 40.0)]
     score_std: f64,
+
+    /// Use the training set's score mean/std instead of --score-mean/--score-std
+    #[arg(long)]
+    auto_normalize: bool,
+
+    /// Value-target horizon: 0 = final score (Monte Carlo), n = bootstrap on
+    /// the value net's final-score estimate n turns ahead, which already
+    /// includes the points scored up to there (see `training::value_target`)
+    #[arg(long, default_value_t = 0)]
+    value_horizon: usize,
 }
 
 #[derive(Clone)]
 struct Sample {
+    /// `<file index>:<game id>`, used to link states of the same game
+    game: String,
     plateau: [i32; 19],
     tile: (i32, i32, i32),
     turn: usize,
@@ -96,11 +125,21 @@ struct Sample {
 fn main() {
     let args = Args::parse();
 
+    let device = match parse_device(&args.device) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    check_cuda();
+
     println!("╔══════════════════════════════════════════════════════════════╗");
     println!("║    Graph Transformer Value Network Training                  ║");
     println!("╚══════════════════════════════════════════════════════════════╝\n");
 
     println!("Config:");
+    println!("  Device:       {:?}", device);
     println!("  Min score:    {} pts", args.min_score);
     println!("  Epochs:       {}", args.epochs);
     println!("  Batch size:   {}", args.batch_size);
@@ -109,7 +148,12 @@ fn main() {
     println!("  Dropout:      {}", args.dropout);
     println!("  Weight decay: {}", args.weight_decay);
     println!("  LR scheduler: {}", args.lr_scheduler);
-    println!("  Score norm:   mean={}, std={}", args.score_mean, args.score_std);
+    if args.auto_normalize {
+        println!("  Score norm:   auto (training set)");
+    } else {
+        println!("  Score norm:   mean={}, std={}", args.score_mean, args.score_std);
+    }
+    println!("  Value horizon: {}", args.value_horizon);
 
     // Load data
     println!("\n📂 Loading data from {}...", args.data_dir);
@@ -131,6 +175,10 @@ fn main() {
     println!("   Score range: {:.0} - {:.0}", min_score, max_score);
     println!("   Score mean: {:.2}, std: {:.2}", mean_score, std_score);
 
+    let games: Vec<&str> = samples.iter().map(|s| s.game.as_str()).collect();
+    let turns: Vec<usize> = samples.iter().map(|s| s.turn).collect();
+    let horizon_links = horizon_indices(&games, &turns, args.value_horizon);
+
     // Split train/val
     let mut rng = StdRng::seed_from_u64(args.seed);
     let mut indices: Vec<usize> = (0..samples.len()).collect();
@@ -142,10 +190,24 @@ fn main() {
 
     println!("   Train: {} samples, Val: {} samples", train_indices.len(), val_indices.len());
 
+    let norm = if args.auto_normalize {
+        let train_scores: Vec<f64> = train_indices.iter().map(|&i| scores[i]).collect();
+        ScoreNormalization::from_scores(&train_scores)
+    } else {
+        ScoreNormalization {
+            mean: args.score_mean,
+            std: args.score_std,
+        }
+    };
+    println!("   Target normalization: mean={:.2}, std={:.2}", norm.mean, norm.std);
+    if let Err(e) = norm.save(&args.save_path) {
+        eprintln!("Warning: failed to save normalization constants: {}", e);
+    }
+
     // Create network
-    let device = Device::Cpu;
     let vs = nn::VarStore::new(device);
     let net = GraphTransformerValueNet::new(&vs, 47, 128, 2, 4, args.dropout);
+    let meta = ModelMeta::graph_transformer(GT_VALUE_ARCH, 128, 2, 4);
 
     let mut opt = nn::Adam {
         wd: args.weight_decay,
@@ -154,7 +216,7 @@ fn main() {
 
     println!("\n🏋️ Training value network...\n");
 
-    let mut best_val_loss = f64::INFINITY;
+    let mut early_stopping = EarlyStopping::new(args.patience);
     let start = Instant::now();
 
     for epoch in 0..args.epochs {
@@ -168,6 +230,7 @@ fn main() {
         let mut train_loss = 0.0;
         let mut train_mae = 0.0;
         let mut train_count = 0;
+        let mut skipped = 0;
 
         let mut train_perm = train_indices.clone();
         train_perm.shuffle(&mut rng);
@@ -177,26 +240,32 @@ fn main() {
             let batch_indices = &train_perm[batch_start..batch_end];
 
             let (features, targets) = prepare_batch(
-                &samples, batch_indices, device, args.score_mean, args.score_std
+                &samples, &horizon_links, &net, batch_indices, device, norm.mean, norm.std
             );
 
             let predictions = net.forward(&features, true);
             let loss = predictions.mse_loss(&targets, tch::Reduction::Mean);
 
-            opt.backward_step(&loss);
+            if !guarded_backward_step(&mut opt, &loss, args.grad_clip) {
+                skipped += 1;
+                continue;
+            }
 
             let loss_val: f64 = loss.double_value(&[]);
             train_loss += loss_val * batch_indices.len() as f64;
 
             // MAE in original scale
-            let pred_scores = predictions.squeeze() * args.score_std + args.score_mean;
-            let true_scores = targets.squeeze() * args.score_std + args.score_mean;
+            let pred_scores = predictions.squeeze() * norm.std + norm.mean;
+            let true_scores = targets.squeeze() * norm.std + norm.mean;
             let mae: f64 = (pred_scores - true_scores).abs().mean(Kind::Float).double_value(&[]);
             train_mae += mae * batch_indices.len() as f64;
 
             train_count += batch_indices.len();
         }
 
+        if skipped > 0 {
+            eprintln!("Warning: skipped {} batches with a NaN/Inf loss", skipped);
+        }
         train_loss /= train_count as f64;
         train_mae /= train_count as f64;
 
@@ -210,7 +279,7 @@ fn main() {
             let batch_indices = &val_indices[batch_start..batch_end];
 
             let (features, targets) = prepare_batch(
-                &samples, batch_indices, device, args.score_mean, args.score_std
+                &samples, &horizon_links, &net, batch_indices, device, norm.mean, norm.std
             );
 
             let predictions = tch::no_grad(|| net.forward(&features, false));
@@ -219,8 +288,8 @@ fn main() {
             let loss_val: f64 = loss.double_value(&[]);
             val_loss += loss_val * batch_indices.len() as f64;
 
-            let pred_scores = predictions.squeeze() * args.score_std + args.score_mean;
-            let true_scores = targets.squeeze() * args.score_std + args.score_mean;
+            let pred_scores = predictions.squeeze() * norm.std + norm.mean;
+            let true_scores = targets.squeeze() * norm.std + norm.mean;
             let mae: f64 = (pred_scores - true_scores).abs().mean(Kind::Float).double_value(&[]);
             val_mae += mae * batch_indices.len() as f64;
 
@@ -233,9 +302,8 @@ fn main() {
         let epoch_time = epoch_start.elapsed().as_secs_f32();
 
         // Save best model
-        let saved = if val_loss < best_val_loss {
-            best_val_loss = val_loss;
-            if let Err(e) = save_varstore(&vs, &args.save_path) {
+        let saved = if early_stopping.record(val_loss) {
+            if let Err(e) = save_varstore_with_meta(&vs, &args.save_path, &meta) {
                 eprintln!("Warning: failed to save model: {}", e);
             }
             true
@@ -250,22 +318,32 @@ fn main() {
             if saved { print!(" 💾"); }
             println!();
         }
+
+        if early_stopping.should_stop() {
+            println!(
+                "\n⏹️ Early stopping at epoch {}: no validation improvement for {} epochs",
+                epoch + 1,
+                early_stopping.epochs_without_improvement()
+            );
+            break;
+        }
     }
 
     let total_time = start.elapsed().as_secs_f32();
+    let best_val_loss = early_stopping.best_val_loss();
 
     println!("\n╔══════════════════════════════════════════════════════════════╗");
     println!("║                     TRAINING COMPLETE                        ║");
     println!("╚══════════════════════════════════════════════════════════════╝\n");
 
     println!("  Best validation loss: {:.4}", best_val_loss);
-    println!("  Best validation MAE:  ~{:.1} pts", best_val_loss.sqrt() * args.score_std);
+    println!("  Best validation MAE:  ~{:.1} pts", best_val_loss.sqrt() * norm.std);
     println!("  Total time: {:.1}s", total_time);
     println!("  Model saved to: {}", args.save_path);
 
     // Evaluate on games
     println!("\n🎮 Evaluating value predictions on 100 games...");
-    evaluate_value_network(&net, &args, 100);
+    evaluate_value_network(&net, &args, &norm, 100, device);
 }
 
 fn compute_lr(base_lr: f64, epoch: usize, total_epochs: usize, scheduler: &str, min_lr_ratio: f64) -> f64 {
@@ -281,6 +359,8 @@ fn compute_lr(base_lr: f64, epoch: usize, total_epochs: usize, scheduler: &str,
 
 fn prepare_batch(
     samples: &[Sample],
+    horizon_links: &[Option<usize>],
+    net: &GraphTransformerValueNet,
     indices: &[usize],
     device: Device,
     score_mean: f64,
@@ -290,10 +370,28 @@ fn prepare_batch(
         .map(|&i| sample_to_features(&samples[i]))
         .collect();
 
-    let targets: Vec<f64> = indices.iter()
+    let final_scores: Vec<f64> = indices.iter()
         .map(|&i| (samples[i].final_score as f64 - score_mean) / score_std)
         .collect();
 
+    // n-step bootstrap: current net's (normalized) estimate at the horizon state
+    let mut bootstrap: Vec<Option<f64>> = vec![None; indices.len()];
+    let horizon_batch: Vec<(usize, usize)> = indices.iter().enumerate()
+        .filter_map(|(b, &i)| horizon_links[i].map(|j| (b, j)))
+        .collect();
+    if !horizon_batch.is_empty() {
+        let horizon_features: Vec<Tensor> = horizon_batch.iter()
+            .map(|&(_, j)| sample_to_features(&samples[j]))
+            .collect();
+        let estimates = tch::no_grad(|| {
+            net.forward(&Tensor::stack(&horizon_features, 0).to_device(device), false)
+        });
+        for (row, &(b, _)) in horizon_batch.iter().enumerate() {
+            bootstrap[b] = Some(estimates.double_value(&[row as i64, 0]));
+        }
+    }
+    let targets = value_targets(&final_scores, &bootstrap);
+
     let features_batch = Tensor::stack(&features, 0).to_device(device);
     let targets_batch = Tensor::from_slice(&targets)
         .to_kind(Kind::Float)
@@ -308,18 +406,20 @@ fn load_all_csv(dir: &str, min_score: i32) -> Vec<Sample> {
     let path = Path::new(dir);
     if !path.exists() { return samples; }
 
+    let mut file_idx = 0usize;
     for entry in std::fs::read_dir(path).unwrap() {
         let entry = entry.unwrap();
         let file_path = entry.path();
         if file_path.extension().map_or(false, |e| e == "csv") {
-            let file_samples = load_csv(&file_path, min_score);
+            let file_samples = load_csv(&file_path, min_score, file_idx);
+            file_idx += 1;
             samples.extend(file_samples);
         }
     }
     samples
 }
 
-fn load_csv(path: &Path, min_score: i32) -> Vec<Sample> {
+fn load_csv(path: &Path, min_score: i32, file_idx: usize) -> Vec<Sample> {
     let mut samples = Vec::new();
     let file = match File::open(path) { Ok(f) => f, Err(_) => return samples };
     let reader = BufReader::new(file);
@@ -343,7 +443,8 @@ fn load_csv(path: &Path, min_score: i32) -> Vec<Sample> {
             fields[24].parse().unwrap_or(0),
         );
 
-        samples.push(Sample { plateau, tile, turn, final_score });
+        let game = format!("{}:{}", file_idx, fields[0]);
+        samples.push(Sample { game, plateau, tile, turn, final_score });
     }
     samples
 }
@@ -361,7 +462,13 @@ fn sample_to_features(sample: &Sample) -> Tensor {
     convert_plateau_for_gat_47ch(&plateau, &tile, &deck, sample.turn, 19)
 }
 
-fn evaluate_value_network(net: &GraphTransformerValueNet, args: &Args, n_games: usize) {
+fn evaluate_value_network(
+    net: &GraphTransformerValueNet,
+    args: &Args,
+    norm: &ScoreNormalization,
+    n_games: usize,
+    device: Device,
+) {
     let mut rng = StdRng::seed_from_u64(args.seed + 1000);
 
     let mut prediction_errors = Vec::new();
@@ -382,9 +489,9 @@ fn evaluate_value_network(net: &GraphTransformerValueNet, args: &Args, n_games:
             // Get value prediction at this state
             let features = convert_plateau_for_gat_47ch(&plateau, &tile, &deck, turn, 19);
             let pred_normalized = tch::no_grad(|| {
-                net.forward(&features.unsqueeze(0), false).double_value(&[0, 0])
+                net.forward(&features.unsqueeze(0).to_device(device), false).double_value(&[0, 0])
             });
-            let pred_score = pred_normalized * args.score_std + args.score_mean;
+            let pred_score = norm.denormalize(pred_normalized);
             game_predictions.push((turn, pred_score));
 
             // Make random move
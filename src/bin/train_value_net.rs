@@ -8,6 +8,9 @@
 //! Usage:
 //!   cargo run --release --bin train_value_net -- --num-games 5000 --epochs 80
 //!   cargo run --release --bin train_value_net -- --device cuda --num-games 10000 --eval-games 200
+//!
+//! `--distributional` trains a head predicting a distribution over score
+//! buckets with cross-entropy; expectimax then evaluates with its mean.
 
 use clap::Parser;
 use rand::prelude::*;
@@ -26,7 +29,7 @@ use take_it_easy::game::remove_tile_from_deck::replace_tile_in_deck;
 use take_it_easy::game::tile::Tile;
 use take_it_easy::neural::device_util::{check_cuda, parse_device};
 use take_it_easy::neural::graph_transformer::{
-    GraphTransformerPolicyNet, GraphTransformerValueNet,
+    GraphTransformerPolicyNet, GraphTransformerValueNet, ValueSupport,
 };
 use take_it_easy::neural::model_io::{load_varstore, save_varstore};
 use take_it_easy::neural::tensor_conversion::convert_plateau_for_gat_47ch;
@@ -34,6 +37,8 @@ use take_it_easy::scoring::scoring::result;
 use take_it_easy::strategy::expectimax::{
     expectimax_select, expectimax_2ply_select, expectimax_3ply_select, ExpectimaxConfig,
 };
+use take_it_easy::training::early_stopping::EarlyStopping;
+use take_it_easy::training::value_normalization::ScoreNormalization;
 
 #[derive(Parser, Debug)]
 #[command(name = "train_value_net")]
@@ -51,6 +56,10 @@ struct Args {
     #[arg(long, default_value_t = 80)]
     epochs: usize,
 
+    /// Stop after this many epochs without validation improvement (default: run every epoch)
+    #[arg(long)]
+    patience: Option<usize>,
+
     /// Learning rate
     #[arg(long, default_value_t = 0.001)]
     lr: f64,
@@ -103,6 +112,10 @@ struct Args {
     #[arg(long, default_value_t = 40.0)]
     score_std: f64,
 
+    /// Use the training set's score mean/std instead of --score-mean/--score-std
+    #[arg(long)]
+    auto_normalize: bool,
+
     /// Validation split ratio
     #[arg(long, default_value_t = 0.1)]
     val_split: f64,
@@ -138,6 +151,52 @@ struct Args {
     /// Apply D3 hexagonal symmetry augmentation (×6 data)
     #[arg(long)]
     augment: bool,
+
+    /// 1-ply blunder filter: EV tolerance (pts) within which moves keeping
+    /// near-complete lines alive are preferred (0 = off)
+    #[arg(long, default_value_t = 0.0)]
+    blunder_epsilon: f64,
+
+    /// Predict a distribution over score buckets (cross-entropy) instead of a scalar (MSE)
+    #[arg(long)]
+    distributional: bool,
+
+    /// Score buckets of --distributional, spanning ±4 std around the mean
+    #[arg(long, default_value_t = 51)]
+    value_buckets: i64,
+}
+
+impl Args {
+    /// Normalization constants given on the command line
+    fn normalization(&self) -> ScoreNormalization {
+        ScoreNormalization {
+            mean: self.score_mean,
+            std: self.score_std,
+        }
+    }
+
+    /// Value net with the head selected by --distributional
+    fn value_net(&self, vs: &nn::VarStore, dropout: f64) -> GraphTransformerValueNet {
+        if self.distributional {
+            let support = ValueSupport {
+                buckets: self.value_buckets,
+                ..Default::default()
+            };
+            GraphTransformerValueNet::new_distributional(
+                vs,
+                47,
+                self.embed_dim,
+                self.num_layers,
+                self.num_heads,
+                dropout,
+                support,
+            )
+        } else {
+            GraphTransformerValueNet::new(
+                vs, 47, self.embed_dim, self.num_layers, self.num_heads, dropout,
+            )
+        }
+    }
 }
 
 struct Sample {
@@ -253,14 +312,14 @@ fn main() {
             args.num_games, gen_value_path
         );
         let mut gen_value_vs = nn::VarStore::new(device);
-        let gen_value_net = GraphTransformerValueNet::new(
-            &gen_value_vs, 47, args.embed_dim, args.num_layers, args.num_heads, 0.0,
-        );
+        let gen_value_net = args.value_net(&gen_value_vs, 0.0);
         if let Err(e) = load_varstore(&mut gen_value_vs, gen_value_path) {
             eprintln!("Error loading gen value net: {}", e);
             return;
         }
-        generate_data_2ply(&policy_net, &gen_value_net, device, &args)
+        let gen_norm =
+            ScoreNormalization::load(gen_value_path).unwrap_or_else(|| args.normalization());
+        generate_data_2ply(&policy_net, &gen_value_net, &gen_norm, device, &args)
     } else {
         println!("Generating {} games with GT Direct (boost={:.1})...", args.num_games, args.boost);
         generate_data(&policy_net, device, &args)
@@ -283,9 +342,10 @@ fn main() {
     // ── Phase 2: Train value network ──
     println!("\n--- Phase 2: Training ---\n");
     let value_vs = nn::VarStore::new(device);
-    let value_net = GraphTransformerValueNet::new(
-        &value_vs, 47, args.embed_dim, args.num_layers, args.num_heads, args.dropout,
-    );
+    let value_net = args.value_net(&value_vs, args.dropout);
+    if args.distributional {
+        println!("Distributional head: {} score buckets", args.value_buckets);
+    }
 
     let mut opt = nn::Adam {
         wd: args.weight_decay,
@@ -308,8 +368,19 @@ fn main() {
         val_indices.len()
     );
 
+    let norm = if args.auto_normalize {
+        let train_scores: Vec<f64> = train_indices.iter().map(|&i| scores[i]).collect();
+        ScoreNormalization::from_scores(&train_scores)
+    } else {
+        args.normalization()
+    };
+    println!("Target normalization: mean={:.2}, std={:.2}", norm.mean, norm.std);
+    if let Err(e) = norm.save(&args.model_path) {
+        eprintln!("Warning: failed to save normalization constants: {}", e);
+    }
+
     let train_start = Instant::now();
-    let mut best_val_loss = f64::INFINITY;
+    let mut early_stopping = EarlyStopping::new(args.patience);
 
     for epoch in 0..args.epochs {
         let epoch_start = Instant::now();
@@ -330,17 +401,18 @@ fn main() {
             let batch_end = (batch_start + args.batch_size).min(train_perm.len());
             let batch_idx = &train_perm[batch_start..batch_end];
 
-            let (features, targets) = prepare_batch(&samples, batch_idx, device, args.score_mean, args.score_std);
+            let (features, targets) = prepare_batch(&samples, batch_idx, device, norm.mean, norm.std);
 
-            let predictions = value_net.forward(&features, true);
-            let loss = predictions.mse_loss(&targets, tch::Reduction::Mean);
+            let head = value_net.forward_head(&features, true);
+            let loss = value_net.head_loss(&head, &targets);
             opt.backward_step(&loss);
+            let predictions = value_net.head_value(&head);
 
             let n = batch_idx.len();
             train_loss_sum += loss.double_value(&[]) * n as f64;
 
-            let pred_pts = predictions.squeeze() * args.score_std + args.score_mean;
-            let true_pts = targets.squeeze() * args.score_std + args.score_mean;
+            let pred_pts = predictions.squeeze() * norm.std + norm.mean;
+            let true_pts = targets.squeeze() * norm.std + norm.mean;
             let mae: f64 = (pred_pts - true_pts).abs().mean(Kind::Float).double_value(&[]);
             train_mae_sum += mae * n as f64;
             train_count += n;
@@ -358,16 +430,17 @@ fn main() {
             let batch_end = (batch_start + args.batch_size).min(val_indices.len());
             let batch_idx = &val_indices[batch_start..batch_end];
 
-            let (features, targets) = prepare_batch(&samples, batch_idx, device, args.score_mean, args.score_std);
+            let (features, targets) = prepare_batch(&samples, batch_idx, device, norm.mean, norm.std);
 
-            let predictions = tch::no_grad(|| value_net.forward(&features, false));
-            let loss = predictions.mse_loss(&targets, tch::Reduction::Mean);
+            let head = tch::no_grad(|| value_net.forward_head(&features, false));
+            let loss = value_net.head_loss(&head, &targets);
+            let predictions = value_net.head_value(&head);
 
             let n = batch_idx.len();
             val_loss_sum += loss.double_value(&[]) * n as f64;
 
-            let pred_pts = predictions.squeeze() * args.score_std + args.score_mean;
-            let true_pts = targets.squeeze() * args.score_std + args.score_mean;
+            let pred_pts = predictions.squeeze() * norm.std + norm.mean;
+            let true_pts = targets.squeeze() * norm.std + norm.mean;
             let mae: f64 = (pred_pts - true_pts).abs().mean(Kind::Float).double_value(&[]);
             val_mae_sum += mae * n as f64;
             val_count += n;
@@ -377,8 +450,7 @@ fn main() {
         let val_mae = val_mae_sum / val_count as f64;
         let epoch_time = epoch_start.elapsed().as_secs_f32();
 
-        let saved = if val_loss < best_val_loss {
-            best_val_loss = val_loss;
+        let saved = if early_stopping.record(val_loss) {
             if let Err(e) = save_varstore(&value_vs, &args.model_path) {
                 eprintln!("Warning: failed to save: {}", e);
             }
@@ -397,11 +469,25 @@ fn main() {
             }
             println!();
         }
+
+        if early_stopping.should_stop() {
+            println!(
+                "\nEarly stopping at epoch {}: no val loss improvement for {} epochs",
+                epoch + 1,
+                early_stopping.epochs_without_improvement()
+            );
+            break;
+        }
     }
 
     let train_time = train_start.elapsed().as_secs_f32();
+    let best_val_loss = early_stopping.best_val_loss();
     println!("\nTraining complete in {:.1}s", train_time);
-    println!("Best val loss: {:.4} (MAE ~{:.1} pts)", best_val_loss, best_val_loss.sqrt() * args.score_std);
+    if args.distributional {
+        println!("Best val loss: {:.4} (cross-entropy)", best_val_loss);
+    } else {
+        println!("Best val loss: {:.4} (MAE ~{:.1} pts)", best_val_loss, best_val_loss.sqrt() * norm.std);
+    }
     println!("Model saved to: {}", args.model_path);
     } // end if !eval_only
 
@@ -411,13 +497,13 @@ fn main() {
 
         // Reload best value net weights
         let mut eval_value_vs = nn::VarStore::new(device);
-        let eval_value_net = GraphTransformerValueNet::new(
-            &eval_value_vs, 47, args.embed_dim, args.num_layers, args.num_heads, 0.0,
-        );
+        let eval_value_net = args.value_net(&eval_value_vs, 0.0);
         if let Err(e) = load_varstore(&mut eval_value_vs, &args.model_path) {
             eprintln!("Error reloading value net: {}", e);
             return;
         }
+        let norm =
+            ScoreNormalization::load(&args.model_path).unwrap_or_else(|| args.normalization());
 
         // Generate shared tile sequences
         let mut eval_rng = StdRng::seed_from_u64(args.seed + 2000);
@@ -451,11 +537,12 @@ fn main() {
             let config = ExpectimaxConfig {
                 device,
                 boost: args.boost,
-                score_mean: args.score_mean,
-                score_std: args.score_std,
+                score_mean: norm.mean,
+                score_std: norm.std,
                 min_turn: mt,
                 top_k_ply1: 3,
                 top_k_ply2: 2,
+                blunder_epsilon: args.blunder_epsilon,
             };
 
             let scores: Vec<i32> = eval_sequences
@@ -477,11 +564,12 @@ fn main() {
             let config = ExpectimaxConfig {
                 device,
                 boost: args.boost,
-                score_mean: args.score_mean,
-                score_std: args.score_std,
+                score_mean: norm.mean,
+                score_std: norm.std,
                 min_turn: mt,
                 top_k_ply1: 3,
                 top_k_ply2: 2,
+                blunder_epsilon: args.blunder_epsilon,
             };
 
             let scores: Vec<i32> = eval_sequences
@@ -503,11 +591,12 @@ fn main() {
             let config = ExpectimaxConfig {
                 device,
                 boost: args.boost,
-                score_mean: args.score_mean,
-                score_std: args.score_std,
+                score_mean: norm.mean,
+                score_std: norm.std,
                 min_turn: mt,
                 top_k_ply1: 3,
                 top_k_ply2: 2,
+                blunder_epsilon: args.blunder_epsilon,
             };
 
             let scores: Vec<i32> = eval_sequences
@@ -629,6 +718,7 @@ fn generate_data(
 fn generate_data_2ply(
     policy_net: &GraphTransformerPolicyNet,
     value_net: &GraphTransformerValueNet,
+    norm: &ScoreNormalization,
     device: Device,
     args: &Args,
 ) -> Vec<Sample> {
@@ -639,11 +729,12 @@ fn generate_data_2ply(
     let ex_config = ExpectimaxConfig {
         device,
         boost: args.boost,
-        score_mean: args.score_mean,
-        score_std: args.score_std,
+        score_mean: norm.mean,
+        score_std: norm.std,
         min_turn: 8,
         top_k_ply1: 3,
         top_k_ply2: 2,
+        blunder_epsilon: args.blunder_epsilon,
     };
 
     for game_idx in 0..args.num_games {
@@ -33,28 +33,6 @@ const POSITION_VALUES: [f32; 19] = [
     0.4, 0.5, 0.4,           // Col 4: edges (pos 16-18)
 ];
 
-/// Line definitions for computing strategic features
-const LINES: [(&[usize], usize); 15] = [
-    // Horizontal (value 0 = tile.0)
-    (&[0, 1, 2], 0),
-    (&[3, 4, 5, 6], 0),
-    (&[7, 8, 9, 10, 11], 0),
-    (&[12, 13, 14, 15], 0),
-    (&[16, 17, 18], 0),
-    // Diagonal 1 (value 1 = tile.1)
-    (&[0, 3, 7], 1),
-    (&[1, 4, 8, 12], 1),
-    (&[2, 5, 9, 13, 16], 1),
-    (&[6, 10, 14, 17], 1),
-    (&[11, 15, 18], 1),
-    // Diagonal 2 (value 2 = tile.2)
-    (&[2, 6, 11], 2),
-    (&[1, 5, 10, 15], 2),
-    (&[0, 4, 9, 14, 18], 2),
-    (&[3, 8, 13, 17], 2),
-    (&[7, 12, 16], 2),
-];
-
 #[derive(Parser, Debug)]
 #[command(name = "train_with_center_bias")]
 struct Args {
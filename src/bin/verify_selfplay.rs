@@ -0,0 +1,82 @@
+//! Verify (and optionally repair) a self-play data directory.
+//!
+//! Checks every `.csv` of `--data-dir` for the column count, valid tiles and
+//! complete 19-turn games, and lists the bad files. With `--quarantine` they
+//! are moved to `<data-dir>/quarantine/` so training no longer picks them up.
+//!
+//! Usage:
+//!   cargo run --release --bin verify_selfplay -- --data-dir selfplay_data --quarantine
+
+use clap::Parser;
+use std::path::Path;
+use std::process::ExitCode;
+
+use take_it_easy::data::selfplay_verify::{quarantine, verify_dir};
+
+#[derive(Parser)]
+#[command(
+    name = "verify_selfplay",
+    about = "Check self-play CSV files and quarantine the corrupt ones"
+)]
+struct Args {
+    /// Directory of self-play CSV files
+    #[arg(long, default_value = "selfplay_data")]
+    data_dir: String,
+
+    /// Move the invalid files to <data-dir>/quarantine/
+    #[arg(long)]
+    quarantine: bool,
+
+    /// Issues printed per invalid file
+    #[arg(long, default_value_t = 5)]
+    max_issues: usize,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let reports = match verify_dir(Path::new(&args.data_dir)) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("Error: cannot read {}: {}", args.data_dir, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut invalid = 0;
+    for report in &reports {
+        let name = report.path.display();
+        if report.is_valid() {
+            println!("  OK   {} ({} games)", name, report.games);
+            continue;
+        }
+        invalid += 1;
+        println!(
+            "  BAD  {} ({} rows, {} issues)",
+            name,
+            report.rows,
+            report.issues.len()
+        );
+        for issue in report.issues.iter().take(args.max_issues) {
+            println!("         {}", issue);
+        }
+        if args.quarantine {
+            match quarantine(&report.path) {
+                Ok(target) => println!("         -> moved to {}", target.display()),
+                Err(e) => eprintln!("         Error: cannot quarantine: {}", e),
+            }
+        }
+    }
+
+    println!(
+        "\n{} files checked, {} valid, {} invalid",
+        reports.len(),
+        reports.len() - invalid,
+        invalid
+    );
+    if invalid > 0 && !args.quarantine {
+        println!("Re-run with --quarantine to move the invalid files aside");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
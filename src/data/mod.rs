@@ -1,3 +1,7 @@
 pub mod append_result;
 pub mod load_data;
 pub mod save_data;
+pub mod selfplay_bin;
+pub mod selfplay_csv;
+pub mod selfplay_verify;
+pub mod tile_sequences;
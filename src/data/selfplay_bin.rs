@@ -0,0 +1,285 @@
+//! Packed binary self-play format, the compact counterpart of `selfplay_csv`.
+//!
+//! Layout (little-endian): the 8-byte magic `TIESELF1`, a `u32` record count,
+//! then one fixed-width record per turn:
+//!
+//! | field            | type        |
+//! |------------------|-------------|
+//! | game_idx         | `u32`       |
+//! | turn             | `u8`        |
+//! | plateau          | `19 × u16`  |
+//! | tile             | `3 × u8`    |
+//! | chosen_position  | `u8`        |
+//! | final_score      | `i16`       |
+//! | has_distribution | `u8`        |
+//! | distribution     | `19 × f32`, only when `has_distribution == 1` |
+//!
+//! A turn without distribution takes 50 bytes, about a quarter of its CSV row,
+//! and loading skips all text parsing. Paths ending in `.bin` select this
+//! format in the `selfplay_csv` save/load helpers; CSV stays the default for
+//! files meant to be read by humans.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::selfplay_csv::TurnRecord;
+
+const MAGIC: &[u8; 8] = b"TIESELF1";
+
+/// Size of a record without distribution, the smallest one.
+const MIN_RECORD_BYTES: usize = 50;
+
+/// Whether `path` (or the checkpoint / temp file derived from it) uses the
+/// binary format.
+pub fn is_bin_path(path: &str) -> bool {
+    let path = path.strip_suffix(".tmp").unwrap_or(path);
+    let path = path.strip_suffix(".partial").unwrap_or(path);
+    Path::new(path).extension().is_some_and(|ext| ext == "bin")
+}
+
+fn packed<T: TryFrom<i64>>(value: i64, field: &str) -> Result<T, Box<dyn Error>> {
+    T::try_from(value)
+        .map_err(|_| format!("{} out of range for binary format: {}", field, value).into())
+}
+
+pub fn save_bin(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut buf = Vec::with_capacity(12 + records.len() * MIN_RECORD_BYTES);
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&packed::<u32>(records.len() as i64, "record count")?.to_le_bytes());
+
+    for r in records {
+        buf.extend_from_slice(&packed::<u32>(r.game_idx as i64, "game_idx")?.to_le_bytes());
+        buf.push(packed::<u8>(r.turn as i64, "turn")?);
+        for &cell in &r.plateau {
+            buf.extend_from_slice(&packed::<u16>(cell as i64, "plateau cell")?.to_le_bytes());
+        }
+        for value in [r.tile.0, r.tile.1, r.tile.2] {
+            buf.push(packed::<u8>(value as i64, "tile value")?);
+        }
+        buf.push(packed::<u8>(r.chosen_position as i64, "chosen_position")?);
+        buf.extend_from_slice(&packed::<i16>(r.final_score as i64, "final_score")?.to_le_bytes());
+        match &r.distribution {
+            Some(dist) => {
+                if dist.len() != 19 {
+                    return Err(format!(
+                        "Distribution of game {} turn {} has {} values, expected 19",
+                        r.game_idx,
+                        r.turn,
+                        dist.len()
+                    )
+                    .into());
+                }
+                buf.push(1);
+                for value in dist {
+                    buf.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            None => buf.push(0),
+        }
+    }
+
+    fs::write(path, buf)?;
+    Ok(())
+}
+
+/// Sequential little-endian reader over the file contents.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn Error>> {
+        let end = self.pos + N;
+        let chunk = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or("Truncated self-play binary file")?;
+        self.pos = end;
+        Ok(chunk.try_into().expect("chunk has N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, Box<dyn Error>> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Box<dyn Error>> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, Box<dyn Error>> {
+        Ok(i16::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, Box<dyn Error>> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+}
+
+pub fn load_bin(path: &str) -> Result<Vec<TurnRecord>, Box<dyn Error>> {
+    let bytes = fs::read(path)?;
+    let mut reader = Reader {
+        bytes: &bytes,
+        pos: 0,
+    };
+    if &reader.take::<8>()? != MAGIC {
+        return Err(format!("{} is not a self-play binary file", path).into());
+    }
+
+    let count = reader.u32()? as usize;
+    // The header is not trusted for the allocation: a corrupt count cannot
+    // reserve more records than the file can hold
+    let mut records = Vec::with_capacity(count.min((bytes.len() - reader.pos) / MIN_RECORD_BYTES));
+    for _ in 0..count {
+        let game_idx = reader.u32()? as usize;
+        let turn = reader.u8()? as usize;
+        let mut plateau = [0i32; 19];
+        for cell in plateau.iter_mut() {
+            *cell = reader.u16()? as i32;
+        }
+        let tile = (
+            reader.u8()? as i32,
+            reader.u8()? as i32,
+            reader.u8()? as i32,
+        );
+        let chosen_position = reader.u8()? as usize;
+        let final_score = reader.i16()? as i32;
+        let distribution = match reader.u8()? {
+            0 => None,
+            1 => Some(
+                (0..19)
+                    .map(|_| reader.f32())
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            flag => return Err(format!("Invalid distribution flag {}", flag).into()),
+        };
+
+        records.push(TurnRecord {
+            game_idx,
+            turn,
+            plateau,
+            tile,
+            chosen_position,
+            final_score,
+            distribution,
+        });
+    }
+
+    if reader.pos != bytes.len() {
+        return Err("Trailing bytes in self-play binary file".into());
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::selfplay_csv::{checkpoint_path, load_records, save_csv, save_csv_checkpoint};
+    use crate::game::create_deck::create_deck;
+    use crate::game::remove_tile_from_deck::get_available_tiles;
+    use tempfile::tempdir;
+
+    /// Two full games: boards filling up turn by turn with real tile codes.
+    fn games() -> Vec<TurnRecord> {
+        let tiles = get_available_tiles(&create_deck());
+        let mut records = Vec::new();
+        for game_idx in 0..2 {
+            let mut plateau = [0i32; 19];
+            for turn in 0..19 {
+                let tile = tiles[(turn + game_idx * 7) % tiles.len()];
+                let distribution = (turn % 5 == 0).then(|| {
+                    let mut dist = vec![0.0f32; 19];
+                    dist[turn] = 0.7;
+                    dist[18 - turn] += 0.3 + f32::EPSILON;
+                    dist
+                });
+                records.push(TurnRecord {
+                    game_idx: game_idx * 70_000,
+                    turn,
+                    plateau,
+                    tile: (tile.0, tile.1, tile.2),
+                    chosen_position: turn,
+                    final_score: 150 + game_idx as i32 * 31,
+                    distribution,
+                });
+                plateau[turn] = tile.0 * 100 + tile.1 * 10 + tile.2;
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn test_round_trip_is_exact_and_smaller_than_csv() {
+        let dir = tempdir().unwrap();
+        let bin_path = dir.path().join("selfplay.bin");
+        let csv_path = dir.path().join("selfplay.csv");
+        let (bin_path, csv_path) = (bin_path.to_str().unwrap(), csv_path.to_str().unwrap());
+
+        let records = games();
+        save_bin(&records, bin_path).unwrap();
+        save_csv(&records, csv_path).unwrap();
+
+        assert_eq!(load_bin(bin_path).unwrap(), records);
+        let bin_size = fs::metadata(bin_path).unwrap().len();
+        let csv_size = fs::metadata(csv_path).unwrap().len();
+        assert!(bin_size < csv_size, "bin {} >= csv {}", bin_size, csv_size);
+    }
+
+    #[test]
+    fn test_format_selected_by_extension() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("run.bin");
+        let path = path.to_str().unwrap();
+
+        assert!(is_bin_path(path));
+        assert!(is_bin_path(&checkpoint_path(path)));
+        assert!(!is_bin_path("data/run.csv.partial"));
+
+        let records = games();
+        save_csv_checkpoint(&records, 2, path).unwrap();
+        let partial = checkpoint_path(path);
+        assert_eq!(&fs::read(&partial).unwrap()[..8], MAGIC);
+        assert_eq!(load_records(&partial).unwrap(), records);
+    }
+
+    #[test]
+    fn test_rejects_truncated_and_foreign_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bad.bin");
+        let path = path.to_str().unwrap();
+
+        save_bin(&games(), path).unwrap();
+        let bytes = fs::read(path).unwrap();
+        fs::write(path, &bytes[..bytes.len() - 3]).unwrap();
+        assert!(load_bin(path).is_err());
+
+        fs::write(path, b"game_idx,turn\n").unwrap();
+        assert!(load_bin(path).is_err());
+
+        // A corrupt record count fails on the missing records, no huge allocation
+        let mut corrupt = bytes[..12 + MIN_RECORD_BYTES].to_vec();
+        corrupt[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(path, corrupt).unwrap();
+        assert!(load_bin(path).is_err());
+    }
+
+    #[test]
+    fn test_rejects_distribution_of_wrong_length() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("short.bin");
+        let path = path.to_str().unwrap();
+
+        let mut records = games();
+        records[0].distribution = Some(vec![1.0 / 18.0; 18]);
+        assert!(save_bin(&records, path).is_err());
+    }
+}
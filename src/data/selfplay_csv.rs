@@ -0,0 +1,526 @@
+//! Self-play CSV format (`game_idx,turn,p0..p18,t0,t1,t2,chosen,score[,pi0..pi18]`).
+//!
+//! The optional `pi*` columns hold the normalized MCTS visit distribution of
+//! the turn. They are only written when at least one record carries one, so
+//! files produced by raw-policy generators keep the historical layout.
+//!
+//! Long generation runs checkpoint to `<output>.partial` (see
+//! [`save_csv_checkpoint`]) so an interrupted run leaves usable data behind,
+//! and the number of games it played to `<output>.partial.games` so
+//! [`load_checkpoint`] can resume it.
+//!
+//! Outputs ending in `.bin` are written in the packed format of
+//! [`super::selfplay_bin`] instead; [`load_records`] picks the reader the same
+//! way.
+//!
+//! [`CsvBatches`] reads a CSV batch by batch instead of loading it whole, with
+//! a per-game hashed train/validation split ([`is_validation_game`]).
+//!
+//! [`augment_symmetries`] expands records into their symmetric variants.
+//!
+//! [`visit_distribution`] turns an MCTS search result into the `pi*` columns.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::selfplay_bin::{is_bin_path, load_bin, save_bin};
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::symmetry::symmetries;
+use crate::game::tile::Tile;
+use crate::mcts::mcts_result::MCTSResult;
+use crate::neural::training::policy_target::{
+    build_policy_target, restrict_to_legal, tensor_to_distribution, PolicyTargetMode,
+};
+
+/// One self-play turn: board before placement, drawn tile and chosen position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnRecord {
+    pub game_idx: usize,
+    pub turn: usize,
+    pub plateau: [i32; 19],
+    pub tile: (i32, i32, i32),
+    pub chosen_position: usize,
+    pub final_score: i32,
+    /// Normalized visit-count distribution over the 19 positions (MCTS only)
+    pub distribution: Option<Vec<f32>>,
+}
+
+const BASE_COLUMNS: usize = 2 + 19 + 3 + 2;
+
+pub fn save_csv(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let with_distribution = records.iter().any(|r| r.distribution.is_some());
+
+    let mut wtr = csv::Writer::from_path(path)?;
+    let mut header: Vec<String> = vec!["game_idx".into(), "turn".into()];
+    for i in 0..19 {
+        header.push(format!("p{}", i));
+    }
+    header.extend(
+        ["t0", "t1", "t2", "chosen", "score"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    if with_distribution {
+        for i in 0..19 {
+            header.push(format!("pi{}", i));
+        }
+    }
+    wtr.write_record(&header)?;
+
+    for r in records {
+        let mut row: Vec<String> = vec![r.game_idx.to_string(), r.turn.to_string()];
+        for i in 0..19 {
+            row.push(r.plateau[i].to_string());
+        }
+        row.push(r.tile.0.to_string());
+        row.push(r.tile.1.to_string());
+        row.push(r.tile.2.to_string());
+        row.push(r.chosen_position.to_string());
+        row.push(r.final_score.to_string());
+        if with_distribution {
+            match &r.distribution {
+                Some(dist) => {
+                    for i in 0..19 {
+                        row.push(dist.get(i).copied().unwrap_or(0.0).to_string());
+                    }
+                }
+                None => row.extend((0..19).map(|_| String::new())),
+            }
+        }
+        wtr.write_record(&row)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Path of the in-progress checkpoint for a final output `path`.
+pub fn checkpoint_path(path: &str) -> String {
+    format!("{}.partial", path)
+}
+
+/// Save in the format selected by the extension of `path` (`.bin` or CSV).
+pub fn save_records(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
+    if is_bin_path(path) {
+        save_bin(records, path)
+    } else {
+        save_csv(records, path)
+    }
+}
+
+/// Load a file written by [`save_records`], checkpoints included.
+pub fn load_records(path: &str) -> Result<Vec<TurnRecord>, Box<dyn Error>> {
+    if is_bin_path(path) {
+        load_bin(path)
+    } else {
+        load_csv(path)
+    }
+}
+
+/// Write `records` to `path` through a temp file and an atomic rename, so a
+/// reader never sees a half-written file.
+pub fn save_csv_atomic(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
+    let tmp = format!("{}.tmp", path);
+    save_records(records, &tmp)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Sidecar of the checkpoint of `path` holding the number of games played
+/// when it was written.
+fn checkpoint_progress_path(path: &str) -> String {
+    format!("{}.games", checkpoint_path(path))
+}
+
+/// Flush the records gathered so far to the checkpoint of `path`, with the
+/// number of games played (kept or not) to produce them.
+pub fn save_csv_checkpoint(
+    records: &[TurnRecord],
+    games_played: usize,
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    save_csv_atomic(records, &checkpoint_path(path))?;
+    // Written after the data: a run killed in between resumes a little early
+    // and replays games it already has, never skips any
+    let progress = checkpoint_progress_path(path);
+    let tmp = format!("{}.tmp", progress);
+    fs::write(&tmp, games_played.to_string())?;
+    fs::rename(&tmp, progress)?;
+    Ok(())
+}
+
+/// Records and number of games played of the checkpoint of `path`, `None`
+/// when there is no checkpoint.
+pub fn load_checkpoint(path: &str) -> Result<Option<(Vec<TurnRecord>, usize)>, Box<dyn Error>> {
+    let partial = checkpoint_path(path);
+    if !Path::new(&partial).exists() {
+        return Ok(None);
+    }
+    let progress = checkpoint_progress_path(path);
+    let games_played = fs::read_to_string(&progress)
+        .map_err(|e| format!("Missing game count {} of checkpoint: {}", progress, e))?
+        .trim()
+        .parse()?;
+    Ok(Some((load_records(&partial)?, games_played)))
+}
+
+/// Write the final output and drop the checkpoint left by earlier flushes.
+pub fn finalize_csv(records: &[TurnRecord], path: &str) -> Result<(), Box<dyn Error>> {
+    save_csv_atomic(records, path)?;
+    for leftover in [checkpoint_path(path), checkpoint_progress_path(path)] {
+        if Path::new(&leftover).exists() {
+            fs::remove_file(leftover)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn load_csv(path: &str) -> Result<Vec<TurnRecord>, Box<dyn Error>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let mut records = Vec::new();
+
+    for result in reader.records() {
+        records.push(parse_row(&result?)?);
+    }
+
+    Ok(records)
+}
+
+fn parse_row(row: &csv::StringRecord) -> Result<TurnRecord, Box<dyn Error>> {
+    let field = |i: usize| -> Result<&str, Box<dyn Error>> {
+        row.get(i)
+            .ok_or_else(|| format!("Missing column {} in self-play CSV", i).into())
+    };
+
+    let mut plateau = [0i32; 19];
+    for (i, cell) in plateau.iter_mut().enumerate() {
+        *cell = field(2 + i)?.parse()?;
+    }
+
+    let distribution = if row.len() >= BASE_COLUMNS + 19 && !field(BASE_COLUMNS)?.is_empty() {
+        let mut dist = Vec::with_capacity(19);
+        for i in 0..19 {
+            dist.push(field(BASE_COLUMNS + i)?.parse::<f32>()?);
+        }
+        Some(dist)
+    } else {
+        None
+    };
+
+    Ok(TurnRecord {
+        game_idx: field(0)?.parse()?,
+        turn: field(1)?.parse()?,
+        plateau,
+        tile: (
+            field(21)?.parse()?,
+            field(22)?.parse()?,
+            field(23)?.parse()?,
+        ),
+        chosen_position: field(24)?.parse()?,
+        final_score: field(25)?.parse()?,
+        distribution,
+    })
+}
+
+/// Whether `game_idx` belongs to the validation split.
+///
+/// The assignment only depends on the game index, so a streaming reader can
+/// split without seeing the whole file, and every turn of a game lands on the
+/// same side.
+pub fn is_validation_game(game_idx: usize, val_fraction: f64) -> bool {
+    // splitmix64 finalizer: consecutive indices spread uniformly over [0, 1)
+    let mut z = (game_idx as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    ((z >> 11) as f64 / (1u64 << 53) as f64) < val_fraction
+}
+
+/// Which games of a file a [`CsvBatches`] reader yields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Split {
+    All,
+    Train(f64),
+    Validation(f64),
+}
+
+impl Split {
+    fn keeps(&self, game_idx: usize) -> bool {
+        match *self {
+            Split::All => true,
+            Split::Train(val_fraction) => !is_validation_game(game_idx, val_fraction),
+            Split::Validation(val_fraction) => is_validation_game(game_idx, val_fraction),
+        }
+    }
+}
+
+/// Batches of a self-play CSV read lazily, so only one batch is in memory at
+/// a time. Open a new reader for every epoch.
+pub struct CsvBatches {
+    reader: csv::Reader<fs::File>,
+    batch_size: usize,
+    split: Split,
+}
+
+impl CsvBatches {
+    pub fn open(path: &str, batch_size: usize, split: Split) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            reader: csv::Reader::from_path(path)?,
+            batch_size: batch_size.max(1),
+            split,
+        })
+    }
+}
+
+impl Iterator for CsvBatches {
+    type Item = Result<Vec<TurnRecord>, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut row = csv::StringRecord::new();
+        while batch.len() < self.batch_size {
+            match self.reader.read_record(&mut row) {
+                Ok(true) => match parse_row(&row) {
+                    Ok(record) if self.split.keeps(record.game_idx) => batch.push(record),
+                    Ok(_) => {}
+                    Err(e) => return Some(Err(e)),
+                },
+                Ok(false) => break,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+        (!batch.is_empty()).then_some(Ok(batch))
+    }
+}
+
+/// Visit distribution of `result` to store in [`TurnRecord::distribution`]:
+/// the search's `policy_distribution` restricted to the empty cells of
+/// `plateau` (the board the search ran on) and normalized over the 19
+/// positions, or the one-hot of `best_position` when nothing is left.
+pub fn visit_distribution(result: &MCTSResult, plateau: &Plateau) -> Vec<f32> {
+    let visits = restrict_to_legal(&tensor_to_distribution(&result.policy_distribution), |i| {
+        plateau.tiles.get(i) == Some(&Tile(0, 0, 0))
+    });
+    build_policy_target(
+        PolicyTargetMode::Visits,
+        result.best_position,
+        &visits,
+        &[],
+        19,
+    )
+}
+
+/// Every record followed by its symmetric variants (×6 data), with the chosen
+/// position and the visit distribution moved along with the board.
+pub fn augment_symmetries(records: &[TurnRecord]) -> Vec<TurnRecord> {
+    let decode = |code: i32| Tile(code / 100, (code / 10) % 10, code % 10);
+    let encode = |tile: &Tile| tile.0 * 100 + tile.1 * 10 + tile.2;
+
+    let mut augmented = Vec::with_capacity(records.len() * 6);
+    for record in records {
+        let mut plateau = create_plateau_empty();
+        for (cell, &code) in plateau.tiles.iter_mut().zip(&record.plateau) {
+            *cell = decode(code);
+        }
+        let tile = Tile(record.tile.0, record.tile.1, record.tile.2);
+
+        for (board, tile, permutation) in symmetries(&plateau, &tile) {
+            let distribution = record.distribution.as_ref().map(|dist| {
+                let mut moved = vec![0.0; dist.len()];
+                for (old, &p) in dist.iter().enumerate() {
+                    moved[permutation[old]] = p;
+                }
+                moved
+            });
+            augmented.push(TurnRecord {
+                plateau: std::array::from_fn(|i| encode(&board.tiles[i])),
+                tile: (tile.0, tile.1, tile.2),
+                chosen_position: permutation[record.chosen_position],
+                distribution,
+                ..record.clone()
+            });
+        }
+    }
+    augmented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn record(turn: usize, distribution: Option<Vec<f32>>) -> TurnRecord {
+        let mut plateau = [0i32; 19];
+        plateau[8] = 963;
+        TurnRecord {
+            game_idx: 3,
+            turn,
+            plateau,
+            tile: (5, 7, 4),
+            chosen_position: 2,
+            final_score: 171,
+            distribution,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_distribution() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("selfplay.csv");
+        let path = path.to_str().unwrap();
+
+        let mut dist = vec![0.0f32; 19];
+        dist[2] = 0.625;
+        dist[4] = 0.25;
+        dist[17] = 0.125;
+        let records = vec![record(0, Some(dist)), record(1, None)];
+
+        save_csv(&records, path).unwrap();
+        let loaded = load_csv(path).unwrap();
+        assert_eq!(loaded, records);
+    }
+
+    #[test]
+    fn test_legacy_layout_without_distribution() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("legacy.csv");
+        let path = path.to_str().unwrap();
+
+        let records = vec![record(0, None)];
+        save_csv(&records, path).unwrap();
+
+        let header = fs::read_to_string(path).unwrap();
+        let header = header.lines().next().unwrap();
+        assert!(header.ends_with("chosen,score"));
+        assert_eq!(load_csv(path).unwrap(), records);
+    }
+
+    #[test]
+    fn test_mid_run_checkpoint_is_loadable() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("run.csv");
+        let path = path.to_str().unwrap();
+
+        // 3 games of 19 turns flushed, run "killed" before completion
+        let mut records = Vec::new();
+        for game_idx in 0..3 {
+            for turn in 0..19 {
+                records.push(TurnRecord {
+                    game_idx,
+                    ..record(turn, None)
+                });
+            }
+        }
+        save_csv_checkpoint(&records, 5, path).unwrap();
+
+        assert!(!Path::new(path).exists());
+        let partial = load_csv(&checkpoint_path(path)).unwrap();
+        assert_eq!(partial.len(), 3 * 19);
+        assert_eq!(partial, records);
+        // The game count includes the games the score filter dropped
+        let (resumed, games_played) = load_checkpoint(path).unwrap().unwrap();
+        assert_eq!((resumed, games_played), (records.clone(), 5));
+
+        // Completion promotes the data to the final path and removes the checkpoint
+        records.push(TurnRecord {
+            game_idx: 3,
+            ..record(0, None)
+        });
+        finalize_csv(&records, path).unwrap();
+        assert!(!Path::new(&checkpoint_path(path)).exists());
+        assert!(load_checkpoint(path).unwrap().is_none());
+        assert_eq!(load_csv(path).unwrap().len(), 3 * 19 + 1);
+    }
+
+    #[test]
+    fn test_streaming_batches_match_in_memory_chunks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("gen.csv");
+        let path = path.to_str().unwrap();
+
+        let mut records = Vec::new();
+        for game_idx in 0..20 {
+            for turn in 0..19 {
+                records.push(TurnRecord {
+                    game_idx,
+                    ..record(turn, None)
+                });
+            }
+        }
+        save_csv(&records, path).unwrap();
+
+        let in_memory: Vec<Vec<TurnRecord>> = load_csv(path)
+            .unwrap()
+            .chunks(64)
+            .map(|c| c.to_vec())
+            .collect();
+        let streamed: Vec<Vec<TurnRecord>> = CsvBatches::open(path, 64, Split::All)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(streamed.len(), in_memory.len());
+        assert_eq!(streamed, in_memory);
+
+        // Train and validation partition the games, each game on one side
+        let read = |split| -> Vec<TurnRecord> {
+            CsvBatches::open(path, 64, split)
+                .unwrap()
+                .flat_map(|batch| batch.unwrap())
+                .collect()
+        };
+        let train = read(Split::Train(0.25));
+        let validation = read(Split::Validation(0.25));
+        assert_eq!(train.len() + validation.len(), records.len());
+        assert!(!train.is_empty() && !validation.is_empty());
+        assert_eq!(validation.len() % 19, 0);
+        assert!(validation
+            .iter()
+            .all(|r| is_validation_game(r.game_idx, 0.25)));
+    }
+
+    #[test]
+    fn test_symmetric_variants_move_the_chosen_position() {
+        let mut dist = vec![0.0f32; 19];
+        dist[2] = 0.75;
+        dist[4] = 0.25;
+        let original = record(5, Some(dist));
+
+        let augmented = augment_symmetries(std::slice::from_ref(&original));
+        assert_eq!(augmented.len(), 6);
+        assert_eq!(augmented[0], original);
+        for variant in &augmented {
+            let dist = variant.distribution.as_ref().unwrap();
+            assert_eq!(dist[variant.chosen_position], 0.75);
+            assert_eq!(variant.plateau[variant.chosen_position], 0);
+            assert_eq!(variant.plateau.iter().filter(|&&c| c != 0).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_visit_distribution_of_an_mcts_search() {
+        use crate::game::create_deck::create_deck;
+        use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+        use crate::mcts::algorithm::mcts_find_best_position_for_tile_pure;
+
+        let mut plateau = create_plateau_empty();
+        let mut deck = create_deck();
+        for (position, tile) in [(0, Tile(9, 7, 8)), (8, Tile(5, 6, 4))] {
+            plateau.tiles[position] = tile;
+            deck = replace_tile_in_deck(&deck, &tile);
+        }
+        let tile = Tile(1, 2, 3);
+        deck = replace_tile_in_deck(&deck, &tile);
+
+        let result =
+            mcts_find_best_position_for_tile_pure(&mut plateau, &mut deck, tile, 30, 2, 19, None);
+        let dist = visit_distribution(&result, &plateau);
+
+        assert_eq!(dist.len(), 19);
+        assert!((dist.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        assert_eq!((dist[0], dist[8]), (0.0, 0.0));
+        assert!(dist[result.best_position] > 0.0);
+    }
+}
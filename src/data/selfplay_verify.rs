@@ -0,0 +1,233 @@
+//! Integrity check of a self-play data directory.
+//!
+//! [`load_csv`](super::selfplay_csv::load_csv) only parses numbers, so a file
+//! cut short by an interrupted run, or written with another layout, can still
+//! load and feed garbage to training. [`verify_csv`] checks the column count
+//! of every row, that boards and drawn tiles only hold real deck tiles, and
+//! that every game has exactly the 19 turns 0..18. Bad files can be moved
+//! aside with [`quarantine`].
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::game::create_deck::create_deck;
+
+/// Sub-directory of the data directory receiving quarantined files.
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Columns without / with the `pi*` visit distribution.
+const LAYOUTS: [usize; 2] = [26, 45];
+
+/// Problems found in one file (none when it is safe to train on).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub rows: usize,
+    pub games: usize,
+    pub issues: Vec<String>,
+}
+
+impl FileReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Encoded values (`100*a + 10*b + c`) of the 27 deck tiles.
+fn tile_codes() -> HashSet<i32> {
+    create_deck()
+        .tiles()
+        .iter()
+        .map(|t| t.0 * 100 + t.1 * 10 + t.2)
+        .filter(|&code| code != 0)
+        .collect()
+}
+
+/// Check one self-play CSV file.
+pub fn verify_csv(path: &Path) -> FileReport {
+    let mut report = FileReport {
+        path: path.to_path_buf(),
+        rows: 0,
+        games: 0,
+        issues: Vec::new(),
+    };
+    let mut reader = match csv::ReaderBuilder::new().flexible(true).from_path(path) {
+        Ok(reader) => reader,
+        Err(e) => {
+            report.issues.push(format!("unreadable: {}", e));
+            return report;
+        }
+    };
+    let columns = match reader.headers() {
+        Ok(header) if LAYOUTS.contains(&header.len()) => header.len(),
+        Ok(header) if header.is_empty() => {
+            report.issues.push("empty file".to_string());
+            return report;
+        }
+        Ok(header) => {
+            report.issues.push(format!(
+                "header has {} columns, expected {:?}",
+                header.len(),
+                LAYOUTS
+            ));
+            return report;
+        }
+        Err(e) => {
+            report.issues.push(format!("unreadable header: {}", e));
+            return report;
+        }
+    };
+
+    let codes = tile_codes();
+    let mut turns_per_game: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    for (i, row) in reader.records().enumerate() {
+        let line = i + 2;
+        let row = match row {
+            Ok(row) => row,
+            Err(e) => {
+                report.issues.push(format!("line {}: {}", line, e));
+                continue;
+            }
+        };
+        report.rows += 1;
+        if row.len() != columns {
+            report.issues.push(format!(
+                "line {}: {} columns, expected {}",
+                line,
+                row.len(),
+                columns
+            ));
+            continue;
+        }
+        let values: Result<Vec<i64>, _> = row.iter().take(26).map(str::parse).collect();
+        let values = match values {
+            Ok(values) => values,
+            Err(e) => {
+                report.issues.push(format!("line {}: {}", line, e));
+                continue;
+            }
+        };
+
+        let is_tile = |v: i64| codes.contains(&(v as i32));
+        if let Some(cell) = values[2..21].iter().find(|&&v| v != 0 && !is_tile(v)) {
+            report
+                .issues
+                .push(format!("line {}: invalid board tile {}", line, cell));
+        }
+        let drawn = values[21] * 100 + values[22] * 10 + values[23];
+        if !(0..10).contains(&values[21])
+            || !(0..10).contains(&values[22])
+            || !(0..10).contains(&values[23])
+            || !is_tile(drawn)
+        {
+            report.issues.push(format!(
+                "line {}: invalid drawn tile ({}, {}, {})",
+                line, values[21], values[22], values[23]
+            ));
+        }
+        if !(0..19).contains(&values[24]) {
+            report
+                .issues
+                .push(format!("line {}: invalid position {}", line, values[24]));
+        }
+        turns_per_game.entry(values[0]).or_default().push(values[1]);
+    }
+
+    report.games = turns_per_game.len();
+    for (game, mut turns) in turns_per_game {
+        turns.sort_unstable();
+        if turns != (0..19).collect::<Vec<i64>>() {
+            report.issues.push(format!(
+                "game {}: {} turns instead of 19",
+                game,
+                turns.len()
+            ));
+        }
+    }
+    if report.rows == 0 {
+        report.issues.push("no records".to_string());
+    }
+    report
+}
+
+/// Check every `.csv` file of `dir`, in file name order.
+pub fn verify_dir(dir: &Path) -> io::Result<Vec<FileReport>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "csv"))
+        .collect();
+    paths.sort();
+    Ok(paths.iter().map(|path| verify_csv(path)).collect())
+}
+
+/// Move a file into the quarantine directory next to it.
+pub fn quarantine(path: &Path) -> io::Result<PathBuf> {
+    let dir = path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(QUARANTINE_DIR);
+    fs::create_dir_all(&dir)?;
+    let target = dir.join(path.file_name().unwrap_or_default());
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::selfplay_csv::{save_csv, TurnRecord};
+    use tempfile::tempdir;
+
+    /// One complete game, positions filled in deck order
+    fn game(game_idx: usize) -> Vec<TurnRecord> {
+        let tiles = create_deck().tiles().to_vec();
+        let mut plateau = [0i32; 19];
+        (0..19)
+            .map(|turn| {
+                let tile = tiles[turn];
+                let record = TurnRecord {
+                    game_idx,
+                    turn,
+                    plateau,
+                    tile: (tile.0, tile.1, tile.2),
+                    chosen_position: turn,
+                    final_score: 120,
+                    distribution: None,
+                };
+                plateau[turn] = tile.0 * 100 + tile.1 * 10 + tile.2;
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_truncated_file_is_flagged_and_quarantined() {
+        let dir = tempdir().unwrap();
+        let valid = dir.path().join("gen0.csv");
+        let truncated = dir.path().join("gen1.csv");
+        let records: Vec<TurnRecord> = game(0).into_iter().chain(game(1)).collect();
+        save_csv(&records, valid.to_str().unwrap()).unwrap();
+
+        // Interrupted mid-write: second game incomplete, last row cut short
+        save_csv(&records, truncated.to_str().unwrap()).unwrap();
+        let content = fs::read_to_string(&truncated).unwrap();
+        fs::write(&truncated, &content[..content.len() * 3 / 4]).unwrap();
+
+        let reports = verify_dir(dir.path()).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].is_valid(), "{:?}", reports[0].issues);
+        assert_eq!(reports[0].games, 2);
+        assert!(!reports[1].is_valid());
+        assert!(reports[1]
+            .issues
+            .iter()
+            .any(|issue| issue.starts_with("game 1:")));
+
+        let moved = quarantine(&truncated).unwrap();
+        assert_eq!(moved, dir.path().join(QUARANTINE_DIR).join("gen1.csv"));
+        assert!(!truncated.exists());
+        assert_eq!(verify_dir(dir.path()).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,129 @@
+//! Fixed tile sequences for reproducible benchmarks.
+//!
+//! One sequence per line, 19 tile codes separated by spaces, a code being the
+//! tile's three band values (`168` = `Tile(1, 6, 8)`):
+//!
+//! ```text
+//! # benchmark_strategies --seed 42
+//! 168 527 934 ...
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored. Benchmarks load these
+//! with `--sequences-file` and write their generated ones with
+//! `--dump-sequences`, so two runs face identical boards regardless of RNG.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::game::tile::Tile;
+
+pub const SEQUENCE_LENGTH: usize = 19;
+
+pub fn tile_code(tile: &Tile) -> String {
+    format!("{}{}{}", tile.0, tile.1, tile.2)
+}
+
+pub fn format_sequence(sequence: &[Tile]) -> String {
+    sequence.iter().map(tile_code).collect::<Vec<_>>().join(" ")
+}
+
+/// Parse one line: 19 distinct deck tiles.
+pub fn parse_sequence(line: &str) -> Result<Vec<Tile>, String> {
+    let mut seen = HashSet::new();
+    let mut sequence = Vec::with_capacity(SEQUENCE_LENGTH);
+    for code in line.split_whitespace() {
+        let tile = Tile::from_code(code).map_err(|_| format!("invalid tile code '{}'", code))?;
+        if !seen.insert(tile) {
+            return Err(format!("tile {} appears twice", code));
+        }
+        sequence.push(tile);
+    }
+    if sequence.len() != SEQUENCE_LENGTH {
+        return Err(format!(
+            "expected {} tiles, got {}",
+            SEQUENCE_LENGTH,
+            sequence.len()
+        ));
+    }
+    Ok(sequence)
+}
+
+pub fn save_sequences(sequences: &[Vec<Tile>], path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut content = String::new();
+    for sequence in sequences {
+        content.push_str(&format_sequence(sequence));
+        content.push('\n');
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub fn load_sequences(path: &str) -> Result<Vec<Vec<Tile>>, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut sequences = Vec::new();
+    for (line_idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let sequence =
+            parse_sequence(line).map_err(|e| format!("{}:{}: {}", path, line_idx + 1, e))?;
+        sequences.push(sequence);
+    }
+    Ok(sequences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use tempfile::tempdir;
+
+    fn deck_tiles() -> Vec<Tile> {
+        create_deck()
+            .tiles()
+            .iter()
+            .copied()
+            .filter(|t| *t != Tile(0, 0, 0))
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sequences.txt");
+        let path = path.to_str().unwrap();
+
+        let tiles = deck_tiles();
+        let sequences = vec![
+            tiles[..19].to_vec(),
+            tiles[8..27].iter().rev().copied().collect(),
+        ];
+        save_sequences(&sequences, path).unwrap();
+
+        let content = fs::read_to_string(path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.starts_with("123 168 "));
+        assert_eq!(load_sequences(path).unwrap(), sequences);
+    }
+
+    #[test]
+    fn test_comments_and_invalid_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sequences.txt");
+        let path = path.to_str().unwrap();
+
+        let line = format_sequence(&deck_tiles()[..19]);
+        fs::write(path, format!("# seed 42\n\n{}\n", line)).unwrap();
+        assert_eq!(load_sequences(path).unwrap().len(), 1);
+
+        assert!(parse_sequence("123 168").is_err());
+        assert!(parse_sequence(&line.replacen("123", "111", 1)).is_err());
+        assert!(parse_sequence(&line.replacen("168", "123", 1)).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Compact text form of a board, used to start a game from a given position.
+//!
+//! 19 comma-separated cells in position order, each either the 3-digit code of
+//! a tile (`"963"` = `Tile(9, 6, 3)`) or `"."` / `"0"` for an empty cell:
+//!
+//! ```text
+//! 123,168,.,.,963,...
+//! ```
+//!
+//! A tile sequence (tiles announced in order) uses the same tile codes.
+
+use crate::game::create_deck::create_deck;
+use crate::game::deck::Deck;
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+use crate::game::tile::Tile;
+
+/// Parse a compact board. Every tile must belong to the deck and appear at
+/// most once.
+pub fn parse_plateau_code(code: &str) -> Result<Plateau, String> {
+    let cells: Vec<&str> = code.split(',').map(str::trim).collect();
+    if cells.len() != 19 {
+        return Err(format!("expected 19 cells, got {}", cells.len()));
+    }
+
+    let mut plateau = create_plateau_empty();
+    for (position, cell) in cells.iter().enumerate() {
+        if *cell == "." || *cell == "0" {
+            continue;
+        }
+        let tile = Tile::from_code(cell)
+            .map_err(|_| format!("cell {}: invalid tile '{}'", position, cell))?;
+        if plateau.tiles.contains(&tile) {
+            return Err(format!("cell {}: tile {} placed twice", position, cell));
+        }
+        plateau.tiles[position] = tile;
+    }
+    Ok(plateau)
+}
+
+/// Parse a comma-separated tile sequence (`"963,123,..."`). Every tile must
+/// belong to the deck and appear at most once.
+pub fn parse_tile_sequence(code: &str) -> Result<Vec<Tile>, String> {
+    let mut tiles = Vec::new();
+    for (index, cell) in code.split(',').map(str::trim).enumerate() {
+        let tile = Tile::from_code(cell)
+            .map_err(|_| format!("tile {}: invalid tile '{}'", index, cell))?;
+        if tiles.contains(&tile) {
+            return Err(format!("tile {}: tile {} drawn twice", index, cell));
+        }
+        tiles.push(tile);
+    }
+    Ok(tiles)
+}
+
+/// Compact form of `plateau`, the inverse of [`parse_plateau_code`].
+pub fn plateau_code(plateau: &Plateau) -> String {
+    plateau
+        .tiles
+        .iter()
+        .map(|t| {
+            if *t == Tile(0, 0, 0) {
+                ".".to_string()
+            } else {
+                format!("{}{}{}", t.0, t.1, t.2)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Full deck with the tiles already on `plateau` removed (replaced by
+/// `Tile(0, 0, 0)`, like drawn tiles).
+pub fn reconstruct_deck_from_plateau(plateau: &Plateau) -> Deck {
+    plateau
+        .tiles
+        .iter()
+        .filter(|t| **t != Tile(0, 0, 0))
+        .fold(create_deck(), |deck, tile| {
+            replace_tile_in_deck(&deck, tile)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::remove_tile_from_deck::get_available_tiles;
+
+    #[test]
+    fn test_round_trip_and_remaining_deck() {
+        let deck = create_deck();
+        let mut plateau = create_plateau_empty();
+        for position in 0..15 {
+            plateau.tiles[position] = deck.tiles[position];
+        }
+
+        let code = plateau_code(&plateau);
+        assert!(code.starts_with("123,168,173,"));
+        assert!(code.ends_with(",.,.,.,."));
+        assert_eq!(parse_plateau_code(&code).unwrap(), plateau);
+
+        let remaining = get_available_tiles(&reconstruct_deck_from_plateau(&plateau));
+        assert_eq!(remaining, deck.tiles[15..].to_vec());
+    }
+
+    #[test]
+    fn test_rejects_illegal_boards() {
+        let empty = vec!["."; 19];
+        let with = |cells: &[(usize, &'static str)]| {
+            let mut board = empty.clone();
+            for &(position, cell) in cells {
+                board[position] = cell;
+            }
+            board.join(",")
+        };
+
+        assert!(parse_plateau_code(&with(&[])).is_ok());
+        assert!(parse_plateau_code(&empty[..18].join(",")).is_err());
+        // 1-6-7 is not a deck tile, 12 is not a tile code
+        assert!(parse_plateau_code(&with(&[(3, "167")])).is_err());
+        assert!(parse_plateau_code(&with(&[(3, "12")])).is_err());
+        assert!(parse_plateau_code(&with(&[(3, "9x8")])).is_err());
+        assert!(parse_plateau_code(&with(&[(3, "9634")])).is_err());
+        assert!(parse_plateau_code(&with(&[(3, "")])).is_err());
+        // Each tile exists once in the deck
+        assert!(parse_plateau_code(&with(&[(3, "978"), (7, "978")])).is_err());
+    }
+
+    #[test]
+    fn test_tile_sequence() {
+        assert_eq!(
+            parse_tile_sequence("963, 123,578").unwrap(),
+            vec![Tile(9, 6, 3), Tile(1, 2, 3), Tile(5, 7, 8)]
+        );
+        assert!(parse_tile_sequence("").is_err());
+        assert!(parse_tile_sequence("963,167").is_err());
+        assert!(parse_tile_sequence("963,963").is_err());
+    }
+}
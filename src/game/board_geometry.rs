@@ -0,0 +1,122 @@
+//! Canonical geometry of the 19-cell Take It Easy board.
+//!
+//! Single source for the 15 scoring lines, the horizontal rows and the
+//! position → lines reverse index, so heuristics and scoring can look up the
+//! lines through a cell in O(1) instead of scanning every line.
+
+use crate::game::tile::Tile;
+
+/// The 15 scoring lines as (positions, direction) where direction selects the
+/// tile value: 0 = tile.0 (horizontal/v1), 1 = tile.1 (diagonal v2),
+/// 2 = tile.2 (diagonal v3).
+pub const LINES: [(&[usize], usize); 15] = [
+    // Horizontal rows (v1)
+    (&[0, 1, 2], 0),
+    (&[3, 4, 5, 6], 0),
+    (&[7, 8, 9, 10, 11], 0),
+    (&[12, 13, 14, 15], 0),
+    (&[16, 17, 18], 0),
+    // Diagonal v2
+    (&[0, 3, 7], 1),
+    (&[1, 4, 8, 12], 1),
+    (&[2, 5, 9, 13, 16], 1),
+    (&[6, 10, 14, 17], 1),
+    (&[11, 15, 18], 1),
+    // Diagonal v3
+    (&[7, 12, 16], 2),
+    (&[3, 8, 13, 17], 2),
+    (&[0, 4, 9, 14, 18], 2),
+    (&[1, 5, 10, 15], 2),
+    (&[2, 6, 11], 2),
+];
+
+/// Horizontal rows (the v1 lines), top to bottom.
+pub const ROWS: [&[usize]; 5] = [
+    &[0, 1, 2],
+    &[3, 4, 5, 6],
+    &[7, 8, 9, 10, 11],
+    &[12, 13, 14, 15],
+    &[16, 17, 18],
+];
+
+/// Reverse index: for each position, the index in `LINES` of the line through
+/// it in each direction (`POSITION_TO_LINES[pos][direction]`).
+pub const POSITION_TO_LINES: [[usize; 3]; 19] = [
+    [0, 5, 12],
+    [0, 6, 13],
+    [0, 7, 14],
+    [1, 5, 11],
+    [1, 6, 12],
+    [1, 7, 13],
+    [1, 8, 14],
+    [2, 5, 10],
+    [2, 6, 11],
+    [2, 7, 12],
+    [2, 8, 13],
+    [2, 9, 14],
+    [3, 6, 10],
+    [3, 7, 11],
+    [3, 8, 12],
+    [3, 9, 13],
+    [4, 7, 10],
+    [4, 8, 11],
+    [4, 9, 12],
+];
+
+/// Indices in `LINES` of the three lines through `position`.
+#[inline]
+pub fn lines_through(position: usize) -> &'static [usize; 3] {
+    &POSITION_TO_LINES[position]
+}
+
+/// Row index (0-4) of a position.
+#[inline]
+pub fn pos_to_row(position: usize) -> usize {
+    POSITION_TO_LINES[position][0]
+}
+
+/// Tile value read by a line of the given direction.
+#[inline]
+pub fn line_value(tile: &Tile, direction: usize) -> i32 {
+    match direction {
+        0 => tile.0,
+        1 => tile.1,
+        _ => tile.2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_position_maps_back_to_its_lines() {
+        for position in 0..19 {
+            let mut containing: Vec<usize> = LINES
+                .iter()
+                .enumerate()
+                .filter(|(_, (positions, _))| positions.contains(&position))
+                .map(|(idx, _)| idx)
+                .collect();
+            containing.sort_unstable();
+
+            let mut indexed = lines_through(position).to_vec();
+            indexed.sort_unstable();
+            assert_eq!(indexed, containing, "position {}", position);
+
+            for (direction, &line_idx) in lines_through(position).iter().enumerate() {
+                assert_eq!(LINES[line_idx].1, direction, "position {}", position);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rows_match_horizontal_lines() {
+        for (row_idx, row) in ROWS.iter().enumerate() {
+            assert_eq!(LINES[row_idx], (*row, 0));
+            for &position in *row {
+                assert_eq!(pos_to_row(position), row_idx);
+            }
+        }
+    }
+}
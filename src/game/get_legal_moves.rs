@@ -4,16 +4,44 @@ use crate::game::tile::Tile;
 /// Returns indices of all empty positions on the plateau
 /// Optimized to take a reference instead of ownership to avoid unnecessary clones
 pub fn get_legal_moves(plateau: &Plateau) -> Vec<usize> {
-    plateau
-        .tiles
-        .iter()
-        .enumerate()
-        .filter_map(|(i, tile)| {
-            if *tile == Tile(0, 0, 0) {
-                Some(i)
-            } else {
-                None
-            }
-        })
-        .collect()
+    let mut moves = Vec::with_capacity(plateau.tiles.len());
+    get_legal_moves_into(plateau, &mut moves);
+    moves
+}
+
+/// Same as [`get_legal_moves`], written into `buf` (cleared first) so hot
+/// loops can reuse one buffer instead of allocating per call
+pub fn get_legal_moves_into(plateau: &Plateau, buf: &mut Vec<usize>) {
+    buf.clear();
+    buf.extend(
+        plateau
+            .tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| **tile == Tile(0, 0, 0))
+            .map(|(i, _)| i),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_into_reuses_buffer_and_matches_get_legal_moves() {
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(1, 2, 3);
+        plateau.tiles[10] = Tile(5, 6, 4);
+
+        let mut buf = vec![42; 3];
+        get_legal_moves_into(&plateau, &mut buf);
+        assert_eq!(buf, get_legal_moves(&plateau));
+        assert_eq!(buf.len(), 17);
+        assert!(!buf.contains(&0) && !buf.contains(&10));
+
+        plateau.tiles = vec![Tile(1, 2, 3); 19];
+        get_legal_moves_into(&plateau, &mut buf);
+        assert!(buf.is_empty());
+    }
 }
@@ -1,3 +1,5 @@
+pub mod board_code;
+pub mod board_geometry;
 pub mod create_deck;
 pub mod deck;
 pub mod deck_cow;
@@ -9,4 +11,5 @@ pub mod plateau_is_full;
 pub mod remove_tile_from_deck;
 pub mod simulate_game;
 pub mod simulate_game_smart; // New: Smart rollouts with heuristics
+pub mod symmetry;
 pub mod tile;
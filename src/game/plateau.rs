@@ -1,13 +1,82 @@
 use crate::game::tile::Tile;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Plateau {
     pub tiles: Vec<Tile>,
 }
 
+/// Why [`Plateau::place`] refused a tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PlaceError {
+    #[error("position {0} is outside the board")]
+    OutOfRange(usize),
+    #[error("position {0} is already occupied")]
+    Occupied(usize),
+}
+
+impl PlaceError {
+    /// Error code returned by the services
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlaceError::OutOfRange(_) => "INVALID_POSITION",
+            PlaceError::Occupied(_) => "POSITION_OCCUPIED",
+        }
+    }
+}
+
+impl Plateau {
+    /// `pos` is on the board and holds no tile.
+    pub fn is_empty_at(&self, pos: usize) -> bool {
+        self.tiles.get(pos) == Some(&Tile(0, 0, 0))
+    }
+
+    /// Put `tile` on the empty cell `pos`. Hot loops that already know the
+    /// cell is free keep assigning `tiles[pos]` directly.
+    pub fn place(&mut self, pos: usize, tile: Tile) -> Result<(), PlaceError> {
+        match self.tiles.get_mut(pos) {
+            None => Err(PlaceError::OutOfRange(pos)),
+            Some(cell) if *cell != Tile(0, 0, 0) => Err(PlaceError::Occupied(pos)),
+            Some(cell) => {
+                *cell = tile;
+                Ok(())
+            }
+        }
+    }
+}
+
 pub fn create_plateau_empty() -> Plateau {
     Plateau {
         tiles: vec![Tile(0, 0, 0); 19],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_place_rejects_out_of_range_and_double_placement() {
+        let mut plateau = create_plateau_empty();
+        assert!(plateau.is_empty_at(4));
+        assert!(!plateau.is_empty_at(19));
+
+        assert_eq!(plateau.place(4, Tile(1, 2, 3)), Ok(()));
+        assert!(!plateau.is_empty_at(4));
+        assert_eq!(plateau.tiles[4], Tile(1, 2, 3));
+
+        assert_eq!(
+            plateau.place(19, Tile(5, 6, 4)),
+            Err(PlaceError::OutOfRange(19))
+        );
+        assert_eq!(
+            plateau.place(4, Tile(5, 6, 4)),
+            Err(PlaceError::Occupied(4))
+        );
+        assert_eq!(PlaceError::Occupied(4).code(), "POSITION_OCCUPIED");
+        // Refused placements leave the board unchanged
+        assert_eq!(plateau.tiles[4], Tile(1, 2, 3));
+        assert!((0..19).filter(|&p| !plateau.is_empty_at(p)).eq([4]));
+    }
+}
@@ -1,34 +1,13 @@
+use crate::game::board_geometry::LINES;
 use crate::game::deck::Deck;
 use crate::game::get_legal_moves::get_legal_moves;
 use crate::game::plateau::Plateau;
 use crate::game::plateau_is_full::is_plateau_full;
 use crate::game::tile::Tile;
 use crate::scoring::scoring::result;
+use crate::utils::random_index::with_rng;
 use rand::Rng;
 
-/// Line definitions from scoring.rs: (positions, length, orientation)
-/// Orientation: 0 = horizontal (tile.0), 1 = diagonal1 (tile.1), 2 = diagonal2 (tile.2)
-const LINES: &[(&[usize], usize, usize)] = &[
-    // Horizontal lines (tile.0)
-    (&[0, 1, 2], 3, 0),
-    (&[3, 4, 5, 6], 4, 0),
-    (&[7, 8, 9, 10, 11], 5, 0),
-    (&[12, 13, 14, 15], 4, 0),
-    (&[16, 17, 18], 3, 0),
-    // Diagonal1 lines (tile.1)
-    (&[0, 3, 7], 3, 1),
-    (&[1, 4, 8, 12], 4, 1),
-    (&[2, 5, 9, 13, 16], 5, 1),
-    (&[6, 10, 14, 17], 4, 1),
-    (&[11, 15, 18], 3, 1),
-    // Diagonal2 lines (tile.2)
-    (&[7, 12, 16], 3, 2),
-    (&[3, 8, 13, 17], 4, 2),
-    (&[0, 4, 9, 14, 18], 5, 2),
-    (&[1, 5, 10, 15], 4, 2),
-    (&[2, 6, 11], 3, 2),
-];
-
 /// Smart rollout using heuristics instead of pure random play
 /// This should dramatically improve MCTS evaluation quality
 pub fn simulate_games_smart(
@@ -47,21 +26,93 @@ pub fn simulate_games_smart_with_trace(
     deck: Deck,
     _policy_net: Option<&crate::neural::policy_value_net::PolicyNet>,
 ) -> (i32, Vec<usize>) {
-    let mut simulated_plateau = plateau.clone();
-    let simulated_deck = deck.clone();
+    let (final_plateau, positions_played) = with_rng(|rng| match rng {
+        Some(rng) => play_smart_rollout(plateau, &deck, None, rng),
+        None => play_smart_rollout(plateau, &deck, None, &mut rand::rng()),
+    });
+    (result(&final_plateau), positions_played)
+}
+
+/// Truncated smart rollout: plays at most `max_moves` moves, then scores the
+/// leaf with [`estimate_leaf_score`]. Identical to the full rollout when
+/// `max_moves` covers the remaining turns.
+pub fn simulate_games_smart_truncated_with_trace(
+    plateau: Plateau,
+    deck: Deck,
+    max_moves: usize,
+) -> (i32, Vec<usize>) {
+    let (leaf, positions_played) = with_rng(|rng| match rng {
+        Some(rng) => play_smart_rollout(plateau, &deck, Some(max_moves), rng),
+        None => play_smart_rollout(plateau, &deck, Some(max_moves), &mut rand::rng()),
+    });
+    (estimate_leaf_score(&leaf), positions_played)
+}
+
+/// Score of a rollout leaf: the final score when the board is full, otherwise
+/// completed lines plus open lines weighted by how filled they are (same
+/// quadratic completion weighting as the rollout move heuristic)
+pub fn estimate_leaf_score(plateau: &Plateau) -> i32 {
+    if is_plateau_full(plateau) {
+        return result(plateau);
+    }
+
+    let mut estimate = 0.0;
+    for (line_positions, orientation) in LINES.iter() {
+        let line_length = line_positions.len();
+        let mut line_value = None;
+        let mut filled = 0;
+        let mut has_conflict = false;
+
+        for &pos in *line_positions {
+            let tile = plateau.tiles[pos];
+            if tile == Tile(0, 0, 0) {
+                continue;
+            }
+            let value = [tile.0, tile.1, tile.2][*orientation];
+            filled += 1;
+            match line_value {
+                None => line_value = Some(value),
+                Some(v) if v != value => {
+                    has_conflict = true;
+                    break;
+                }
+                Some(_) => {}
+            }
+        }
+
+        if let (Some(value), false) = (line_value, has_conflict) {
+            let completion_ratio = filled as f64 / line_length as f64;
+            estimate += (value as f64) * (line_length as f64) * completion_ratio.powi(2);
+        }
+    }
+
+    estimate.round() as i32
+}
+
+/// Heuristic rollout from `plateau`, stopping when the board is full or after
+/// `max_moves` moves. Returns the leaf board and the positions played.
+fn play_smart_rollout<R: Rng + ?Sized>(
+    plateau: Plateau,
+    deck: &Deck,
+    max_moves: Option<usize>,
+    rng: &mut R,
+) -> (Plateau, Vec<usize>) {
+    let mut simulated_plateau = plateau;
     let mut positions_played: Vec<usize> = Vec::new();
 
     // Filter out invalid tiles (0, 0, 0)
-    let mut valid_tiles: Vec<Tile> = simulated_deck
+    let mut valid_tiles: Vec<Tile> = deck
         .tiles
         .iter()
         .cloned()
         .filter(|tile| *tile != Tile(0, 0, 0))
         .collect();
 
-    let mut rng = rand::rng();
-
     while !is_plateau_full(&simulated_plateau) {
+        if max_moves.is_some_and(|max| positions_played.len() >= max) {
+            break;
+        }
+
         let legal_moves = get_legal_moves(&simulated_plateau);
 
         if legal_moves.is_empty() || valid_tiles.is_empty() {
@@ -86,8 +137,7 @@ pub fn simulate_games_smart_with_trace(
         positions_played.push(position); // Track for RAVE
     }
 
-    let final_score = result(&simulated_plateau);
-    (final_score, positions_played)
+    (simulated_plateau, positions_played)
 }
 
 /// Heuristic to select best position for a tile during rollout
@@ -119,10 +169,11 @@ fn evaluate_position_for_tile(plateau: &Plateau, tile: &Tile, position: usize) -
     let tile_values = [tile.0, tile.1, tile.2];
 
     // Evaluate each line that contains this position
-    for (line_positions, line_length, orientation) in LINES {
+    for (line_positions, orientation) in LINES.iter() {
         if !line_positions.contains(&position) {
             continue;
         }
+        let line_length = line_positions.len();
 
         let tile_value = tile_values[*orientation];
         if tile_value == 0 {
@@ -164,11 +215,11 @@ fn evaluate_position_for_tile(plateau: &Plateau, tile: &Tile, position: usize) -
 
         // Calculate potential score for this line
         // Score = tile_value × line_length (if completed)
-        let potential_score = (tile_value as f64) * (*line_length as f64);
+        let potential_score = (tile_value as f64) * (line_length as f64);
 
         // Weight by how close we are to completing the line
-        let positions_left = *line_length - matching_count - 1; // -1 for current position
-        let completion_ratio = (matching_count + 1) as f64 / (*line_length as f64);
+        let positions_left = line_length - matching_count - 1; // -1 for current position
+        let completion_ratio = (matching_count + 1) as f64 / (line_length as f64);
 
         // Exponential bonus for lines close to completion
         // - Line with 4/5 filled: huge bonus
@@ -225,3 +276,59 @@ fn get_adjacent_positions(position: usize) -> Vec<usize> {
         _ => vec![],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Board with the first `placed` deck tiles on the first positions
+    fn partial_game(placed: usize) -> (Plateau, Deck) {
+        let mut plateau = create_plateau_empty();
+        let mut deck = create_deck();
+        let tiles: Vec<Tile> = deck.tiles.iter().copied().take(placed).collect();
+        for (pos, tile) in tiles.iter().enumerate() {
+            plateau.tiles[pos] = *tile;
+            deck = replace_tile_in_deck(&deck, tile);
+        }
+        (plateau, deck)
+    }
+
+    #[test]
+    fn test_depth_covering_remaining_turns_equals_full_rollout() {
+        let (plateau, deck) = partial_game(5);
+        for seed in 0..10 {
+            let full = play_smart_rollout(
+                plateau.clone(),
+                &deck,
+                None,
+                &mut StdRng::seed_from_u64(seed),
+            );
+            for depth in [14, 19] {
+                let truncated = play_smart_rollout(
+                    plateau.clone(),
+                    &deck,
+                    Some(depth),
+                    &mut StdRng::seed_from_u64(seed),
+                );
+                assert_eq!(truncated, full, "seed {} depth {}", seed, depth);
+                assert_eq!(estimate_leaf_score(&truncated.0), result(&full.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_shallow_rollout_stops_after_depth() {
+        let (plateau, deck) = partial_game(5);
+        let (leaf, positions) =
+            play_smart_rollout(plateau, &deck, Some(3), &mut StdRng::seed_from_u64(1));
+        assert_eq!(positions.len(), 3);
+        assert_eq!(get_legal_moves(&leaf).len(), 19 - 5 - 3);
+        // Open lines count towards the estimate, completed ones fully
+        assert!(estimate_leaf_score(&leaf) >= result(&leaf));
+    }
+}
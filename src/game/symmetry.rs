@@ -0,0 +1,125 @@
+//! Hexagonal symmetries of the board, for data augmentation.
+//!
+//! The board has D3 symmetry: identity, 2 rotations and 3 reflections. Each
+//! transform moves the cells and maps the line directions onto each other, so
+//! the tile faces are permuted the same way and every line keeps its values:
+//! the score of a transformed board is unchanged.
+//!
+//! Board layout:
+//! ```text
+//!     0  1  2
+//!    3  4  5  6
+//!   7  8  9 10 11
+//!    12 13 14 15
+//!      16 17 18
+//! ```
+
+use crate::game::board_geometry::line_value;
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+
+/// Number of symmetries, identity included.
+pub const SYMMETRY_COUNT: usize = 6;
+
+/// `PERMUTATIONS[s][old] = new`: where symmetry `s` moves each position.
+const PERMUTATIONS: [[usize; 19]; SYMMETRY_COUNT] = [
+    // Identity
+    [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+    ],
+    // Rotation 120°
+    [
+        2, 6, 11, 1, 5, 10, 15, 0, 4, 9, 14, 18, 3, 8, 13, 17, 7, 12, 16,
+    ],
+    // Rotation 240°
+    [
+        11, 15, 18, 6, 10, 14, 17, 2, 5, 9, 13, 16, 1, 4, 8, 12, 0, 3, 7,
+    ],
+    // Reflection swapping directions 0 and 1
+    [
+        18, 15, 11, 17, 14, 10, 6, 16, 13, 9, 5, 2, 12, 8, 4, 1, 7, 3, 0,
+    ],
+    // Reflection swapping directions 1 and 2
+    [
+        16, 17, 18, 12, 13, 14, 15, 7, 8, 9, 10, 11, 3, 4, 5, 6, 0, 1, 2,
+    ],
+    // Reflection swapping directions 0 and 2
+    [
+        7, 12, 16, 3, 8, 13, 17, 0, 4, 9, 14, 18, 1, 5, 10, 15, 2, 6, 11,
+    ],
+];
+
+/// `FACES[s][d]`: direction of the original tile read as direction `d` of
+/// the transformed tile.
+const FACES: [[usize; 3]; SYMMETRY_COUNT] = [
+    [0, 1, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [1, 0, 2],
+    [0, 2, 1],
+    [2, 1, 0],
+];
+
+/// `tile` as seen through symmetry `s`.
+fn transform_tile(tile: &Tile, s: usize) -> Tile {
+    if *tile == Tile(0, 0, 0) {
+        return *tile;
+    }
+    let [a, b, c] = FACES[s].map(|direction| line_value(tile, direction));
+    Tile(a, b, c)
+}
+
+/// Every symmetric variant of (`plateau`, `tile`), identity first, with the
+/// permutation mapping old positions to new ones (`permutation[old] = new`).
+pub fn symmetries(plateau: &Plateau, tile: &Tile) -> Vec<(Plateau, Tile, [usize; 19])> {
+    (0..SYMMETRY_COUNT)
+        .map(|s| {
+            let permutation = PERMUTATIONS[s];
+            let mut transformed = plateau.clone();
+            for (old, cell) in plateau.tiles.iter().enumerate() {
+                transformed.tiles[permutation[old]] = transform_tile(cell, s);
+            }
+            (transformed, transform_tile(tile, s), permutation)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::scoring::scoring::result;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_symmetries_preserve_score() {
+        let mut rng = StdRng::seed_from_u64(1257);
+        for filled in [10, 19] {
+            for _ in 0..50 {
+                let mut tiles = create_deck().tiles;
+                tiles.shuffle(&mut rng);
+                let mut positions: Vec<usize> = (0..19).collect();
+                positions.shuffle(&mut rng);
+                let mut plateau = create_plateau_empty();
+                for (&position, &tile) in positions.iter().zip(&tiles[..filled]) {
+                    plateau.tiles[position] = tile;
+                }
+
+                let variants = symmetries(&plateau, &tiles[19]);
+                assert_eq!(variants.len(), SYMMETRY_COUNT);
+                assert_eq!(variants[0].0, plateau);
+                assert_eq!(variants[0].1, tiles[19]);
+                for (transformed, _, permutation) in &variants {
+                    assert_eq!(result(transformed), result(&plateau));
+                    for (old, &new) in permutation.iter().enumerate() {
+                        let empty = plateau.tiles[old] == Tile(0, 0, 0);
+                        assert_eq!(transformed.tiles[new] == Tile(0, 0, 0), empty);
+                    }
+                }
+            }
+        }
+    }
+}
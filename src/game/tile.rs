@@ -1,4 +1,53 @@
+use crate::TakeItEasyError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Copy, Hash, Eq, Serialize, Deserialize)]
 pub struct Tile(pub i32, pub i32, pub i32);
+
+/// Values allowed in each direction of a tile.
+const VALUES: [[i32; 3]; 3] = [[1, 5, 9], [2, 6, 7], [3, 4, 8]];
+
+impl Tile {
+    /// Parse the 3-digit code of a game tile (`"963"` = `Tile(9, 6, 3)`).
+    pub fn from_code(code: &str) -> Result<Tile, TakeItEasyError> {
+        let invalid = || TakeItEasyError::Game(format!("invalid tile code '{}'", code));
+        let digits: Vec<i32> = code
+            .chars()
+            .map(|c| c.to_digit(10).map(|d| d as i32))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?;
+        match digits[..] {
+            [a, b, c]
+                if VALUES[0].contains(&a) && VALUES[1].contains(&b) && VALUES[2].contains(&c) =>
+            {
+                Ok(Tile(a, b, c))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// The 27 tiles of the game, in code order (`123`, `124`, ... `978`).
+    pub fn all_valid() -> [Tile; 27] {
+        std::array::from_fn(|i| Tile(VALUES[0][i / 9], VALUES[1][i / 3 % 3], VALUES[2][i % 3]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code_accepts_exactly_the_deck_tiles() {
+        let all = Tile::all_valid();
+        assert_eq!(all[0], Tile(1, 2, 3));
+        assert_eq!(all[26], Tile(9, 7, 8));
+        for tile in all {
+            let code = format!("{}{}{}", tile.0, tile.1, tile.2);
+            assert_eq!(Tile::from_code(&code).unwrap(), tile);
+        }
+
+        for bad in ["", "12", "1234", "000", "223", "153", "129", "12a", "-23"] {
+            assert!(Tile::from_code(bad).is_err(), "{:?} accepted", bad);
+        }
+    }
+}
@@ -4,5 +4,9 @@ pub mod takeiteasygame {
     pub mod v1 {
         // Include the tonic-generated code
         include!("takeiteasygame.v1.rs");
+
+        /// Encoded descriptors of the protos, served by the gRPC reflection service
+        pub const FILE_DESCRIPTOR_SET: &[u8] =
+            tonic::include_file_descriptor_set!("takeiteasygame_descriptor");
     }
 }
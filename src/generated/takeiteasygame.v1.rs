@@ -86,6 +86,30 @@ pub struct CreateSessionRequest {
     pub max_players: i32,
     #[prost(string, tag = "3")]
     pub game_mode: ::prost::alloc::string::String,
+    /// Longueur de la partie (1-19), 0 = partie complète
+    #[prost(int32, tag = "4")]
+    pub num_turns: i32,
+    /// Pause "réflexion" de l'IA avant son coup (ms, plafonnée), 0 = immédiat
+    #[prost(int32, tag = "5")]
+    pub ai_think_delay_ms: i32,
+    /// Plateau de départ compact (puzzle, voir game::board_code), vide = plateau vide
+    #[prost(string, tag = "6")]
+    pub starting_board: ::prost::alloc::string::String,
+    /// Variante de score ("classic", "bonus-complete", "long-lines"), vide = classic
+    #[prost(string, tag = "7")]
+    pub scoring_rules: ::prost::alloc::string::String,
+    /// Adversaire IA ("gt", "human-like"), vide = gt
+    #[prost(string, tag = "8")]
+    pub opponent: ::prost::alloc::string::String,
+    /// Tirage des tuiles ("random", "seeded", "sequence"), vide = random
+    #[prost(string, tag = "9")]
+    pub tile_draw: ::prost::alloc::string::String,
+    /// Graine du tirage "seeded"
+    #[prost(uint64, tag = "10")]
+    pub tile_seed: u64,
+    /// Tuiles annoncées dans l'ordre pour "sequence" (ex. "963,123,..."), une par tour restant à jouer
+    #[prost(string, tag = "11")]
+    pub tile_sequence: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateSessionSuccess {
@@ -924,6 +948,81 @@ pub struct GetGameStateResponse {
     #[prost(message, optional, tag = "9")]
     pub error: ::core::option::Option<Error>,
 }
+/// Obtenir uniquement les changements depuis `since_version`
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetGameStateDeltaRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// 0 = depuis le début de la partie
+    #[prost(uint64, tag = "2")]
+    pub since_version: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PlacedTile {
+    #[prost(string, tag = "1")]
+    pub player_id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub position: i32,
+    /// "5-3-7"
+    #[prost(string, tag = "3")]
+    pub tile: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub version: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ScoreDelta {
+    #[prost(string, tag = "1")]
+    pub player_id: ::prost::alloc::string::String,
+    /// Points gagnés depuis since_version
+    #[prost(int32, tag = "2")]
+    pub points_gained: i32,
+    /// Score actuel du plateau
+    #[prost(int32, tag = "3")]
+    pub score: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGameStateDeltaResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    /// Version à renvoyer au prochain appel
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub placed_tiles: ::prost::alloc::vec::Vec<PlacedTile>,
+    #[prost(message, repeated, tag = "4")]
+    pub score_deltas: ::prost::alloc::vec::Vec<ScoreDelta>,
+    #[prost(int32, tag = "5")]
+    pub current_turn: i32,
+    /// "5-3-7" ou vide
+    #[prost(string, tag = "6")]
+    pub current_tile: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "7")]
+    pub waiting_for_players: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag = "8")]
+    pub is_game_finished: bool,
+    #[prost(message, optional, tag = "9")]
+    pub error: ::core::option::Option<Error>,
+    /// since_version inconnue ou hors du journal : relire GetGameState
+    #[prost(bool, tag = "10")]
+    pub resync: bool,
+}
+/// S'abonner aux changements d'état d'une session
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StreamGameStateRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GameStateUpdate {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// state_version de la partie (0 avant le début)
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    /// Même contenu que GetGameState
+    #[prost(message, optional, tag = "3")]
+    pub state: ::core::option::Option<GetGameStateResponse>,
+}
 /// Mode Jeu Réel: demander où l'IA jouerait une tuile
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct GetAiMoveRequest {
@@ -950,6 +1049,86 @@ pub struct GetAiMoveResponse {
     #[prost(message, optional, tag = "3")]
     pub error: ::core::option::Option<Error>,
 }
+/// IA contre IA : simulations MCTS par coup pour chaque IA (0 = valeur du serveur,
+/// plafonnées par le serveur)
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CreateAiVsAiSessionRequest {
+    #[prost(int32, tag = "1")]
+    pub simulations_a: i32,
+    #[prost(int32, tag = "2")]
+    pub simulations_b: i32,
+    /// Enregistrer la partie (module recording)
+    #[prost(bool, tag = "3")]
+    pub record: bool,
+    /// 0 = tirage aléatoire, sinon tirage reproductible
+    #[prost(uint64, tag = "4")]
+    pub seed: u64,
+    /// Version de modèle de l'IA A (A/B test), vide = modèle par défaut
+    #[prost(string, tag = "5")]
+    pub model_a: ::prost::alloc::string::String,
+    /// Version de modèle de l'IA B, vide = modèle par défaut
+    #[prost(string, tag = "6")]
+    pub model_b: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAiVsAiSessionResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    #[prost(string, tag = "2")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub session_code: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub error: ::core::option::Option<Error>,
+}
+/// Analyse : plateau au format de GetAiMove
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct EvaluateBoardRequest {
+    /// 19 cases, "" = vide
+    #[prost(string, repeated, tag = "1")]
+    pub board_state: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Tuile à placer (ex: "168"), "" = aucune
+    #[prost(string, tag = "2")]
+    pub tile_code: ::prost::alloc::string::String,
+    /// Numéro du tour (0-18)
+    #[prost(int32, tag = "3")]
+    pub turn_number: i32,
+    /// Variante de score ("classic", "bonus-complete", "long-lines"), "" = classic
+    #[prost(string, tag = "4")]
+    pub scoring_rules: ::prost::alloc::string::String,
+}
+/// Ligne qui rapporte des points
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct LineScore {
+    #[prost(int32, repeated, tag = "1")]
+    pub positions: ::prost::alloc::vec::Vec<i32>,
+    /// 0 = horizontale, 1 et 2 = diagonales
+    #[prost(int32, tag = "2")]
+    pub direction: i32,
+    #[prost(int32, tag = "3")]
+    pub points: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EvaluateBoardResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    /// Score actuel du plateau
+    #[prost(int32, tag = "2")]
+    pub score: i32,
+    /// Lignes complètes qui rapportent
+    #[prost(message, repeated, tag = "3")]
+    pub score_breakdown: ::prost::alloc::vec::Vec<LineScore>,
+    /// Sortie du ValueNet (0 sans ValueNet chargé)
+    #[prost(float, tag = "4")]
+    pub value_estimate: f32,
+    #[prost(int32, repeated, tag = "5")]
+    pub legal_moves: ::prost::alloc::vec::Vec<i32>,
+    #[prost(message, optional, tag = "6")]
+    pub error: ::core::option::Option<Error>,
+    /// Bonus de la variante, inclus dans score
+    #[prost(int32, tag = "7")]
+    pub bonus: i32,
+}
 /// Generated client implementations.
 pub mod game_service_client {
     #![allow(
@@ -1144,6 +1323,63 @@ pub mod game_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Changements depuis une version connue (l'état complet reste la synchro initiale)
+        pub async fn get_game_state_delta(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetGameStateDeltaRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGameStateDeltaResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/takeiteasygame.v1.GameService/GetGameStateDelta",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "takeiteasygame.v1.GameService",
+                        "GetGameStateDelta",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// 📡 État poussé à chaque changement (coup joué, nouveau tour, fin de partie), état courant d'abord
+        pub async fn stream_game_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamGameStateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::GameStateUpdate>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/takeiteasygame.v1.GameService/StreamGameState",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("takeiteasygame.v1.GameService", "StreamGameState"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
         /// 🎲 Mode Jeu Réel: obtenir la recommandation IA pour une tuile donnée
         pub async fn get_ai_move(
             &mut self,
@@ -1169,6 +1405,63 @@ pub mod game_service_client {
                 .insert(GrpcMethod::new("takeiteasygame.v1.GameService", "GetAiMove"));
             self.inner.unary(req, path, codec).await
         }
+        /// 🤖 Partie IA contre IA jouée par le serveur, suivie via GetGameState(Delta)
+        pub async fn create_ai_vs_ai_session(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateAiVsAiSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateAiVsAiSessionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/takeiteasygame.v1.GameService/CreateAiVsAiSession",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "takeiteasygame.v1.GameService",
+                        "CreateAiVsAiSession",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// 🔍 Analyse : score, estimation du ValueNet et coups légaux d'un plateau, sans session
+        pub async fn evaluate_board(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EvaluateBoardRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvaluateBoardResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/takeiteasygame.v1.GameService/EvaluateBoard",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("takeiteasygame.v1.GameService", "EvaluateBoard"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -1215,6 +1508,28 @@ pub mod game_service_server {
             tonic::Response<super::GetGameStateResponse>,
             tonic::Status,
         >;
+        /// Changements depuis une version connue (l'état complet reste la synchro initiale)
+        async fn get_game_state_delta(
+            &self,
+            request: tonic::Request<super::GetGameStateDeltaRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGameStateDeltaResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamGameState method.
+        type StreamGameStateStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::GameStateUpdate, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// 📡 État poussé à chaque changement (coup joué, nouveau tour, fin de partie), état courant d'abord
+        async fn stream_game_state(
+            &self,
+            request: tonic::Request<super::StreamGameStateRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamGameStateStream>,
+            tonic::Status,
+        >;
         /// 🎲 Mode Jeu Réel: obtenir la recommandation IA pour une tuile donnée
         async fn get_ai_move(
             &self,
@@ -1223,6 +1538,22 @@ pub mod game_service_server {
             tonic::Response<super::GetAiMoveResponse>,
             tonic::Status,
         >;
+        /// 🤖 Partie IA contre IA jouée par le serveur, suivie via GetGameState(Delta)
+        async fn create_ai_vs_ai_session(
+            &self,
+            request: tonic::Request<super::CreateAiVsAiSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateAiVsAiSessionResponse>,
+            tonic::Status,
+        >;
+        /// 🔍 Analyse : score, estimation du ValueNet et coups légaux d'un plateau, sans session
+        async fn evaluate_board(
+            &self,
+            request: tonic::Request<super::EvaluateBoardRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EvaluateBoardResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct GameServiceServer<T> {
@@ -1481,6 +1812,99 @@ pub mod game_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/takeiteasygame.v1.GameService/GetGameStateDelta" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGameStateDeltaSvc<T: GameService>(pub Arc<T>);
+                    impl<
+                        T: GameService,
+                    > tonic::server::UnaryService<super::GetGameStateDeltaRequest>
+                    for GetGameStateDeltaSvc<T> {
+                        type Response = super::GetGameStateDeltaResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGameStateDeltaRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as GameService>::get_game_state_delta(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetGameStateDeltaSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/takeiteasygame.v1.GameService/StreamGameState" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamGameStateSvc<T: GameService>(pub Arc<T>);
+                    impl<
+                        T: GameService,
+                    > tonic::server::ServerStreamingService<super::StreamGameStateRequest>
+                    for StreamGameStateSvc<T> {
+                        type Response = super::GameStateUpdate;
+                        type ResponseStream = T::StreamGameStateStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamGameStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as GameService>::stream_game_state(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StreamGameStateSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/takeiteasygame.v1.GameService/GetAiMove" => {
                     #[allow(non_camel_case_types)]
                     struct GetAiMoveSvc<T: GameService>(pub Arc<T>);
@@ -1526,6 +1950,97 @@ pub mod game_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/takeiteasygame.v1.GameService/CreateAiVsAiSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateAiVsAiSessionSvc<T: GameService>(pub Arc<T>);
+                    impl<
+                        T: GameService,
+                    > tonic::server::UnaryService<super::CreateAiVsAiSessionRequest>
+                    for CreateAiVsAiSessionSvc<T> {
+                        type Response = super::CreateAiVsAiSessionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateAiVsAiSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as GameService>::create_ai_vs_ai_session(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateAiVsAiSessionSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/takeiteasygame.v1.GameService/EvaluateBoard" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvaluateBoardSvc<T: GameService>(pub Arc<T>);
+                    impl<
+                        T: GameService,
+                    > tonic::server::UnaryService<super::EvaluateBoardRequest>
+                    for EvaluateBoardSvc<T> {
+                        type Response = super::EvaluateBoardResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EvaluateBoardRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as GameService>::evaluate_board(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = EvaluateBoardSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
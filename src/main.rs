@@ -6,6 +6,8 @@ use tokio::net::TcpListener;
 
 use crate::neural::{NeuralConfig, NeuralManager, QNetManager};
 use crate::training::session::{train_and_evaluate, train_and_evaluate_offline};
+// Erreur de la bibliothèque, utilisée par les modules partagés (game::plateau)
+use take_it_easy::TakeItEasyError;
 
 #[cfg(test)]
 mod test;
@@ -93,10 +95,23 @@ struct Config {
     #[arg(long, default_value_t = 50)]
     dynamic_sim_boost: usize,
 
+    /// Cible de la policy : onehot (coup joué), visits (distribution MCTS) ou boosted
+    #[arg(long, value_enum, default_value = "visits")]
+    policy_target: neural::training::policy_target::PolicyTargetMode,
+
+    /// Rééchantillonner les exemples pour un histogramme de scores uniforme
+    /// avant l'entraînement (mieux calibré sur les scores rares)
+    #[arg(long, default_value_t = false)]
+    balance_scores: bool,
+
     /// Architecture du réseau de neurones (cnn, gnn ou graph-transformer)
     #[arg(long, value_enum, default_value = "graph-transformer")]
     nn_architecture: NnArchitectureCli,
 
+    /// Dossier des poids des réseaux (requis en mode multijoueur)
+    #[arg(long, default_value = "model_weights")]
+    model_path: String,
+
     /// Enable Q-Net hybrid MCTS (not needed for Graph Transformer)
     #[arg(long, default_value_t = false)]
     hybrid_mcts: bool,
@@ -105,6 +120,14 @@ struct Config {
     #[arg(long, default_value = "model_weights/qvalue_net.params")]
     qnet_path: String,
 
+    /// Second model generation (weights directory) to A/B test against the main one
+    #[arg(long)]
+    ab_model_path: Option<String>,
+
+    /// Fraction of new sessions routed to --ab-model-path
+    #[arg(long, default_value_t = 0.5)]
+    ab_fraction: f64,
+
     /// Top-K positions for Q-net pruning (6 is optimal)
     #[arg(long, default_value_t = 6)]
     top_k: usize,
@@ -124,6 +147,59 @@ struct Config {
     /// Directory for recorded game data
     #[arg(long, default_value = "data/recorded_games")]
     recording_dir: String,
+
+    /// Record the AI's top-k candidate positions per move (0 = off)
+    #[arg(long, default_value_t = 0)]
+    record_candidates: usize,
+
+    /// Recorded games (CSV file or directory) for the "human-like" opponent;
+    /// empty = that opponent plays like the default AI
+    #[arg(long, default_value = "")]
+    human_games: String,
+
+    /// Expose the REST/JSON gateway (port + 2) for clients without gRPC-web
+    #[arg(long, default_value_t = false)]
+    rest_gateway: bool,
+
+    /// Disable gRPC server reflection (on by default, used by grpcurl)
+    #[arg(long, default_value_t = false)]
+    disable_reflection: bool,
+
+    /// Calculs de coups IA simultanés au maximum, les suivants attendent (0 = illimité)
+    #[arg(long, default_value_t = 0)]
+    max_concurrent_ai: usize,
+
+    /// Requêtes GetAiMove par minute et par IP au maximum (0 = illimité)
+    #[arg(long, default_value_t = 0)]
+    ai_move_rate_limit: u32,
+
+    /// Graine des recherches IA : même plateau et même tuile au même tour,
+    /// même coup (défaut : aléatoire)
+    #[arg(long)]
+    ai_seed: Option<u64>,
+
+    /// Parties IA contre IA simultanées au maximum, les suivantes sont
+    /// refusées (0 = illimité)
+    #[arg(long, default_value_t = servers::grpc::DEFAULT_MAX_AI_VS_AI_GAMES)]
+    max_ai_vs_ai_games: usize,
+
+    /// Plafond des simulations par coup demandées pour une partie IA contre
+    /// IA (0 = aucun)
+    #[arg(long, default_value_t = servers::grpc::DEFAULT_MAX_AI_VS_AI_SIMULATIONS)]
+    max_ai_vs_ai_sims: usize,
+
+    /// Durée maximale d'une recherche MCTS par coup en ms, meilleur coup
+    /// trouvé à l'échéance (0 = toutes les simulations)
+    #[arg(long, default_value_t = 0)]
+    max_move_ms: u64,
+
+    /// Threads intra-op de tch (défaut : choix de libtorch)
+    #[arg(long)]
+    num_threads: Option<i32>,
+
+    /// Threads inter-op de tch (défaut : choix de libtorch)
+    #[arg(long)]
+    num_interop_threads: Option<i32>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -159,6 +235,7 @@ async fn start_web_server(
 // SERVEUR GRPC AVEC GRPC-WEB
 // ============================================================================
 
+#[allow(clippy::too_many_arguments)]
 async fn start_multiplayer_server(
     neural_manager: NeuralManager,
     qnet_manager: Option<QNetManager>,
@@ -167,6 +244,14 @@ async fn start_multiplayer_server(
     single_player: bool,
     top_k: usize,
     auth_state: Option<Arc<auth::AuthState>>,
+    rest_gateway: bool,
+    enable_reflection: bool,
+    max_concurrent_ai: usize,
+    ai_move_rate_limit: u32,
+    ai_seed: Option<u64>,
+    max_ai_vs_ai_games: usize,
+    max_ai_vs_ai_simulations: usize,
+    ab_model: Option<(String, NeuralManager, f64)>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     log::info!("🎯 Interface web : http://localhost:{}", port + 1000);
 
@@ -176,10 +261,19 @@ async fn start_multiplayer_server(
         host: "0.0.0.0".to_string(),
         enable_web_layer: true,
         enable_cors: true,
+        rest_port: rest_gateway.then_some(port + 2),
+        max_concurrent_ai,
+        enable_reflection,
+        ai_move_rate_limit,
+        seed: ai_seed,
+        max_ai_vs_ai_games,
+        max_ai_vs_ai_simulations,
     };
 
     // Extract components from neural manager
+    let primary_model_path = neural_manager.config().model_path.clone();
     let components = neural_manager.into_components();
+    let value_net_loaded = components.value_net_loaded;
 
     // Create server with or without Q-Net hybrid
     let mut grpc_server = if let Some(qnet) = qnet_manager {
@@ -204,12 +298,29 @@ async fn start_multiplayer_server(
         )
     };
 
+    // Graceful degradation: an untrained value net would only mislead the MCTS
+    if !value_net_loaded {
+        grpc_server = grpc_server.without_value_net();
+    }
+
     // Add authentication if enabled
     if let Some(state) = auth_state {
         log::info!("🔐 gRPC server with session authentication");
         grpc_server = grpc_server.with_auth(state.jwt_manager(), false);
     }
 
+    // A/B test of a second model generation
+    if let Some((model_path, manager, fraction)) = ab_model {
+        let ab_components = manager.into_components();
+        grpc_server = grpc_server.with_ab_model(
+            &primary_model_path,
+            &model_path,
+            ab_components.policy_net,
+            ab_components.value_net,
+            fraction,
+        )?;
+    }
+
     grpc_server.start().await
 }
 
@@ -217,6 +328,22 @@ async fn start_multiplayer_server(
 // FONCTION PRINCIPALE
 // ============================================================================
 
+/// Charge les réseaux, ou quitte avec un message indiquant comment obtenir
+/// des poids valides
+fn load_neural_manager(neural_config: NeuralConfig) -> NeuralManager {
+    match NeuralManager::with_config(neural_config) {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("❌ Impossible de charger les modèles : {}", e);
+            eprintln!(
+                "   Générez les poids (ex. `cargo run --release --bin train_graph_transformer`) \
+                 ou indiquez leur dossier avec --model-path"
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::parse();
@@ -228,15 +355,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize neural network manager with configuration
     let neural_config = NeuralConfig {
         input_dim: (9, 5, 5), // Enhanced feature stack: 8 channels × 5×5 spatial grid
-        model_path: "model_weights".to_string(),
+        model_path: config.model_path.clone(),
         policy_lr: 1e-3,
         value_lr: 2e-4,
         value_wd: 1e-6,
         nn_architecture: config.nn_architecture.clone().into(),
+        num_threads: config.num_threads,
+        num_interop_threads: config.num_interop_threads,
+        // Le serveur ne joue pas avec des réseaux aléatoires ; l'entraînement part de zéro
+        require_weights: matches!(config.mode, GameMode::Multiplayer),
         ..Default::default()
     };
+    neural_config.apply_thread_settings();
 
-    let neural_manager = NeuralManager::with_config(neural_config)?;
+    let neural_manager = load_neural_manager(neural_config.clone());
 
     // Match sur les modes
     match config.mode {
@@ -246,6 +378,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 min_score_medium: config.min_score_medium.min(config.min_score_high),
                 medium_mix_ratio: config.medium_mix_ratio.clamp(0.0, 1.0),
                 dynamic_sim_boost: config.dynamic_sim_boost,
+                policy_target: config.policy_target,
+                balance_scores: config.balance_scores,
             };
             if config.offline_training {
                 log::info!("[Training] Mode offline activé (sans WebSocket)");
@@ -304,9 +438,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 None
             };
 
+            // Second model generation for A/B testing
+            let ab_model = match &config.ab_model_path {
+                Some(model_path) => {
+                    let ab_config = NeuralConfig {
+                        model_path: model_path.clone(),
+                        ..neural_config.clone()
+                    };
+                    let manager = load_neural_manager(ab_config);
+                    log::info!("🧪 Modèle A/B chargé depuis {}", model_path);
+                    Some((model_path.clone(), manager, config.ab_fraction.clamp(0.0, 1.0)))
+                }
+                None => None,
+            };
+
             // Initialize game recording if enabled
             if config.enable_recording {
-                match recording::init_recorder(&config.recording_dir) {
+                let recorder_options = recording::RecorderOptions {
+                    candidates_top_k: config.record_candidates,
+                    model_version_column: ab_model.is_some(),
+                    background_writes: true,
+                };
+                match recording::init_recorder_with_options(
+                    &config.recording_dir,
+                    recorder_options,
+                ) {
                     Ok(()) => {
                         log::info!("📹 Game recording enabled (dir: {})", config.recording_dir);
                     }
@@ -318,6 +474,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 log::info!("ℹ️ Game recording disabled");
             }
 
+            if config.max_move_ms > 0 {
+                mcts::hyperparameters::set_max_move_ms(config.max_move_ms)?;
+                log::info!(
+                    "⏱️ Recherche MCTS limitée à {} ms par coup",
+                    config.max_move_ms
+                );
+            }
+
+            if !config.human_games.is_empty() {
+                match recording::init_human_move_book(&config.human_games) {
+                    Ok(moves) => {
+                        log::info!("🧑 Human-like opponent: {} recorded human moves", moves);
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "⚠️ Failed to load human games ({}), human-like opponent disabled",
+                            e
+                        );
+                    }
+                }
+            }
+
             // Initialize authentication if enabled
             let auth_state = if config.enable_auth {
                 // Ensure data directory exists
@@ -361,6 +539,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 config.single_player,
                 config.top_k,
                 auth_state,
+                config.rest_gateway,
+                !config.disable_reflection,
+                config.max_concurrent_ai,
+                config.ai_move_rate_limit,
+                config.ai_seed,
+                config.max_ai_vs_ai_games,
+                config.max_ai_vs_ai_sims,
+                ab_model,
             )
             .await?;
         }
@@ -5,15 +5,19 @@
 //! for online play and for generating supervised training data.
 use crate::game::deck::Deck;
 use crate::game::deck_cow::DeckCoW;
-use crate::game::get_legal_moves::get_legal_moves;
+use crate::game::get_legal_moves::{get_legal_moves, get_legal_moves_into};
 use crate::game::plateau::Plateau;
 use crate::game::plateau_cow::PlateauCoW;
 use crate::game::plateau_is_full::is_plateau_full;
 use crate::game::remove_tile_from_deck::{replace_tile_in_deck, replace_tile_in_deck_cow};
-use crate::game::simulate_game_smart::{simulate_games_smart, simulate_games_smart_with_trace};
+use crate::game::simulate_game_smart::{
+    simulate_games_smart, simulate_games_smart_truncated_with_trace,
+    simulate_games_smart_with_trace,
+};
 use crate::game::tile::Tile;
 use crate::mcts::hyperparameters::MCTSHyperparameters;
-use crate::mcts::mcts_result::MCTSResult;
+use crate::mcts::mcts_result::{MCTSResult, NO_LEGAL_MOVE};
+use crate::mcts::node::{EdgeStats, MctsTree};
 use crate::mcts::progressive_widening::{max_actions_to_explore, ProgressiveWideningConfig};
 use crate::neural::manager::NNArchitecture;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
@@ -21,15 +25,21 @@ use crate::neural::qvalue_net::QValueNet;
 use crate::neural::tensor_conversion::{
     convert_plateau_for_gat_47ch, convert_plateau_to_graph_features, convert_plateau_to_tensor,
 };
+use crate::scoring::bounds::max_remaining_score;
+use crate::scoring::incremental::PartialScore;
 use crate::scoring::scoring::result;
 use crate::strategy::contextual_boost::calculate_contextual_boost_entropy;
 use crate::strategy::position_evaluation::enhanced_position_evaluation;
 use crate::utils::random_index::random_index;
+use rayon::prelude::*;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tch::{IndexOp, Kind, Tensor};
 
 /// Helper function to convert plateau to tensor based on architecture
-fn convert_plateau_by_arch(
+pub(crate) fn convert_plateau_by_arch(
     arch: NNArchitecture,
     plateau: &Plateau,
     chosen_tile: &Tile,
@@ -66,6 +76,43 @@ fn convert_plateau_by_arch(
     }
 }
 
+/// Value-net estimate of each board, clamped to `[-1, 1]`, from a single
+/// batched forward pass instead of one pass per board.
+fn batched_value_estimates(value_net: &ValueNet, boards: &[Tensor]) -> Vec<f64> {
+    if boards.is_empty() {
+        return Vec::new();
+    }
+    // Graph Transformer features come without the batch dimension
+    let batch: Vec<Tensor> = boards
+        .iter()
+        .map(|board| {
+            if board.dim() == 2 {
+                board.unsqueeze(0)
+            } else {
+                board.shallow_clone()
+            }
+        })
+        .collect();
+    let values = value_net.forward(&Tensor::cat(&batch, 0), false);
+    Vec::<f64>::try_from(&values.view([-1]).to_kind(Kind::Double))
+        .unwrap()
+        .into_iter()
+        .map(|value| value.clamp(-1.0, 1.0))
+        .collect()
+}
+
+/// Deadline of a search starting now, from `hyperparams.time_budget_ms`
+/// capped by the server's `--max-move-ms` (`None` = run every simulation).
+/// Every search entry point stops at it, once each legal move has a visit.
+fn search_deadline(hyperparams: &MCTSHyperparameters) -> Option<Instant> {
+    let time_budget_ms = hyperparams.effective_time_budget_ms();
+    (time_budget_ms > 0).then(|| Instant::now() + Duration::from_millis(time_budget_ms))
+}
+
+fn deadline_passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|d| Instant::now() >= d)
+}
+
 /// Evaluator used by the MCTS algorithm to rank candidate moves.
 pub enum MctsEvaluator<'a> {
     Neural {
@@ -79,7 +126,6 @@ pub enum MctsEvaluator<'a> {
         qvalue_net: &'a QValueNet,
         prune_top_k: usize,
     },
-    #[allow(dead_code)]
     Pure,
 }
 
@@ -115,12 +161,14 @@ pub fn mcts_find_best_position_for_tile_with_nn(
         current_turn,
         total_turns,
         hyperparams,
+        None,
     )
 }
 
 /// Run MCTS with neural networks AND Q-net pruning (hybrid mode).
 /// Q-net prunes low-quality positions, then CNN policy/value guide MCTS on remaining.
 /// Uses adaptive pruning: more aggressive early game, less late game.
+/// The pruned rollouts stop at the time budget after at least one round.
 #[allow(clippy::too_many_arguments)]
 pub fn mcts_find_best_position_for_tile_with_qnet(
     plateau: &mut Plateau,
@@ -159,18 +207,31 @@ pub fn mcts_find_best_position_for_tile_with_qnet(
         let mut best_score = f64::NEG_INFINITY;
 
         let sims_per_pos = num_simulations / top_positions.len().max(1);
+        let deadline = search_deadline(hyperparams);
+        let temp_deck = replace_tile_in_deck(deck, &chosen_tile);
+        let temp_plateaus: Vec<Plateau> = top_positions
+            .iter()
+            .map(|&pos| {
+                let mut temp_plateau = plateau.clone();
+                temp_plateau.tiles[pos] = chosen_tile;
+                temp_plateau
+            })
+            .collect();
 
-        for &pos in &top_positions {
-            let mut temp_plateau = plateau.clone();
-            temp_plateau.tiles[pos] = chosen_tile;
-            let temp_deck = replace_tile_in_deck(deck, &chosen_tile);
-
-            let mut total = 0.0;
-            for _ in 0..sims_per_pos {
-                total += simulate_games_smart(temp_plateau.clone(), temp_deck.clone(), None) as f64;
+        // One rollout per position per round, so a timed search stops with
+        // every position evaluated equally often
+        let mut totals = vec![0.0; top_positions.len()];
+        let mut rounds = 0;
+        while rounds < sims_per_pos && !(rounds > 0 && deadline_passed(deadline)) {
+            for (total, temp_plateau) in totals.iter_mut().zip(&temp_plateaus) {
+                *total +=
+                    simulate_games_smart(temp_plateau.clone(), temp_deck.clone(), None) as f64;
             }
-            let avg = total / sims_per_pos as f64;
+            rounds += 1;
+        }
 
+        for (&pos, total) in top_positions.iter().zip(totals) {
+            let avg = total / rounds as f64;
             if avg > best_score {
                 best_score = avg;
                 best_pos = pos;
@@ -223,7 +284,6 @@ pub fn mcts_find_best_position_for_tile_with_qnet(
 }
 
 /// Run MCTS without neural priors/value predictions (pure Monte Carlo rollouts).
-#[allow(dead_code)]
 pub fn mcts_find_best_position_for_tile_pure(
     plateau: &mut Plateau,
     deck: &mut Deck,
@@ -249,6 +309,67 @@ pub fn mcts_find_best_position_for_tile_pure(
         current_turn,
         total_turns,
         hyperparams,
+        None,
+    )
+}
+
+/// Same search as [`mcts_find_best_position_for_tile_with_nn`] (or
+/// [`mcts_find_best_position_for_tile_pure`] with [`MctsEvaluator::Pure`]),
+/// starting from the statistics `tree` kept from the previous turns and
+/// updating them. Call [`MctsTree::advance`] once the move is played.
+#[allow(clippy::too_many_arguments)]
+pub fn mcts_find_best_position_for_tile_reusing(
+    plateau: &mut Plateau,
+    deck: &mut Deck,
+    chosen_tile: Tile,
+    evaluator: MctsEvaluator<'_>,
+    num_simulations: usize,
+    current_turn: usize,
+    total_turns: usize,
+    hyperparams: Option<&MCTSHyperparameters>,
+    tree: &mut MctsTree,
+) -> MCTSResult {
+    let default_hyperparams = MCTSHyperparameters::default();
+    let hyperparams = hyperparams.unwrap_or(&default_hyperparams);
+
+    let plateau_cow = PlateauCoW::new(plateau.clone());
+    let deck_cow = DeckCoW::new(deck.clone());
+
+    mcts_core_cow(
+        &plateau_cow,
+        &deck_cow,
+        chosen_tile,
+        evaluator,
+        num_simulations,
+        current_turn,
+        total_turns,
+        hyperparams,
+        Some(tree),
+    )
+}
+
+/// Pure-rollout MCTS spread over the rayon thread pool, see
+/// [`mcts_core_cow_parallel`].
+pub fn mcts_find_best_position_for_tile_parallel(
+    plateau: &Plateau,
+    deck: &Deck,
+    chosen_tile: Tile,
+    num_simulations: usize,
+    current_turn: usize,
+    total_turns: usize,
+    hyperparams: Option<&MCTSHyperparameters>,
+) -> MCTSResult {
+    let default_hyperparams = MCTSHyperparameters::default();
+    let hyperparams = hyperparams.unwrap_or(&default_hyperparams);
+
+    mcts_core_cow_parallel(
+        plateau,
+        deck,
+        chosen_tile,
+        num_simulations,
+        current_turn,
+        total_turns,
+        hyperparams,
     )
 }
 
@@ -265,9 +386,11 @@ pub fn mcts_find_best_position_for_tile_gumbel(
     num_simulations: usize,
     current_turn: usize,
     total_turns: usize,
-    _hyperparams: Option<&MCTSHyperparameters>,
+    hyperparams: Option<&MCTSHyperparameters>,
 ) -> MCTSResult {
-    // Note: Ga régler le pb de cross pourquoi il marchait avant et plus maintenant  umbel variant doesn't use hyperparams yet
+    let default_hyperparams = MCTSHyperparameters::default();
+    let hyperparams = hyperparams.unwrap_or(&default_hyperparams);
+
     mcts_core_gumbel(
         plateau,
         deck,
@@ -279,6 +402,7 @@ pub fn mcts_find_best_position_for_tile_gumbel(
         num_simulations,
         current_turn,
         total_turns,
+        hyperparams,
     )
 }
 
@@ -308,7 +432,7 @@ fn mcts_core(
             Tensor::zeros([distribution_len], (Kind::Float, tch::Device::Cpu));
         let policy_distribution_boosted = policy_distribution.shallow_clone();
         return MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_by_arch(
                 arch,
                 plateau,
@@ -376,7 +500,7 @@ fn mcts_core(
             Tensor::zeros([distribution_len], (Kind::Float, tch::Device::Cpu));
         let policy_distribution_boosted = policy_distribution.shallow_clone();
         return MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_by_arch(
                 arch,
                 plateau,
@@ -479,43 +603,26 @@ fn mcts_core(
             let policy_logits = policy_net.forward(&input_tensor, false);
             let policy = policy_logits.log_softmax(-1, tch::Kind::Float).exp();
 
-            for &position in &legal_moves {
-                let mut temp_plateau = plateau.clone();
-                let mut temp_deck = deck.clone();
-
-                temp_plateau.tiles[position] = chosen_tile;
-                temp_deck = replace_tile_in_deck(&temp_deck, &chosen_tile);
-
-                // Créer le tenseur selon l'architecture (CNN ou GNN)
-                let board_tensor_temp = match policy_net.arch {
-                    NNArchitecture::Cnn => convert_plateau_to_tensor(
+            // One batched value-net pass over the boards after each legal move
+            let deck_after = replace_tile_in_deck(deck, &chosen_tile);
+            let boards: Vec<Tensor> = legal_moves
+                .iter()
+                .map(|&position| {
+                    let mut temp_plateau = plateau.clone();
+                    temp_plateau.tiles[position] = chosen_tile;
+                    convert_plateau_by_arch(
+                        policy_net.arch,
                         &temp_plateau,
                         &chosen_tile,
-                        &temp_deck,
+                        &deck_after,
                         current_turn,
                         total_turns,
-                    ),
-                    NNArchitecture::CnnOnehot => {
-                        crate::neural::tensor_onehot::convert_plateau_onehot(
-                            &temp_plateau,
-                            &chosen_tile,
-                            &temp_deck,
-                            current_turn,
-                        )
-                    }
-                    NNArchitecture::Gnn => {
-                        convert_plateau_to_graph_features(&temp_plateau, current_turn, total_turns)
-                    }
-                    NNArchitecture::GraphTransformer => {
-                        convert_plateau_for_gat_47ch(&temp_plateau, &chosen_tile, &temp_deck, current_turn, total_turns)
-                    }
-                };
-
-                let pred_value = value_net
-                    .forward(&board_tensor_temp, false)
-                    .double_value(&[])
-                    .clamp(-1.0, 1.0);
+                    )
+                })
+                .collect();
+            let pred_values = batched_value_estimates(value_net, &boards);
 
+            for (&position, &pred_value) in legal_moves.iter().zip(&pred_values) {
                 min_value = min_value.min(pred_value);
                 max_value = max_value.max(pred_value);
                 sum_values += pred_value;
@@ -601,8 +708,10 @@ fn mcts_core(
     } else {
         sum_values / value_estimates.len() as f64
     };
-    let variance = value_estimates
-        .values()
+    // Summed in position order: HashMap order would change the float rounding
+    let variance = legal_moves
+        .iter()
+        .filter_map(|pos| value_estimates.get(pos))
         .map(|&v| (v - mean_value).powi(2))
         .sum::<f64>()
         / value_estimates.len() as f64;
@@ -610,7 +719,7 @@ fn mcts_core(
     // Adapt c_puct: high variance = more exploration needed
     let base_c_puct = hyperparams.get_c_puct(current_turn);
 
-    // Variance adjustment: 0.0-0.5 variance -> 0.8x-1.3x multiplier
+    // Variance adjustment: configured steps, 0.85x (confident) to 1.3x by default
     let variance_multiplier = hyperparams.get_variance_multiplier(variance);
 
     let c_puct = base_c_puct * variance_multiplier;
@@ -655,6 +764,8 @@ fn mcts_core(
 
     // Quick Win #2: Temperature annealing for exploration/exploitation
     let temperature = hyperparams.get_temperature(current_turn);
+    let contextual_weight =
+        hyperparams.weight_contextual * hyperparams.get_contextual_scale(current_turn);
 
     // Progressive Widening: Dynamically limit action exploration based on visit count
     // Formula: k(n) = C × n^α where n = total_visits
@@ -662,10 +773,33 @@ fn mcts_core(
     let pw_config = ProgressiveWideningConfig::adaptive(current_turn, total_turns);
     let _max_actions = max_actions_to_explore(total_visits as usize, legal_moves.len(), &pw_config);
 
+    // Optimistic final score of each move: a rollout never scores above it
+    let deck_after = replace_tile_in_deck(deck, &chosen_tile);
+    let upper_bounds: HashMap<usize, f64> = legal_moves
+        .iter()
+        .map(|&position| {
+            let mut after = plateau.clone();
+            after.tiles[position] = chosen_tile;
+            (position, max_remaining_score(&after, &deck_after) as f64)
+        })
+        .collect();
+
     for _ in 0..adaptive_simulations {
         // FIXED: Don't filter/sort by CNN when it's undertrained
-        // Use all legal moves with uniform prior instead of CNN-based pruning
-        let subset_moves: Vec<usize> = legal_moves.clone();
+        // Use all legal moves with uniform prior instead of CNN-based pruning,
+        // only skipping moves whose upper bound is below the best average
+        // rollout score (the best move itself always stays)
+        let best_average = legal_moves
+            .iter()
+            .filter(|pos| visit_counts[*pos] > 0)
+            .map(|pos| total_scores[pos] / visit_counts[pos] as f64)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let (subset_moves, pruned_moves): (Vec<usize>, Vec<usize>) = legal_moves
+            .iter()
+            .partition(|pos| upper_bounds[*pos] >= best_average);
+        for position in pruned_moves {
+            ucb_scores.insert(position, f64::NEG_INFINITY);
+        }
 
         for &position in &subset_moves {
             let mut temp_plateau = plateau.clone();
@@ -724,8 +858,13 @@ fn mcts_core(
                 temperature * c_puct * (total_visits as f64).ln() / (1.0 + *visits as f64);
             let prior_prob = policy.i((0, position as i64)).double_value(&[]);
             let average_score = *total_score / (*visits as f64);
-            let enhanced_eval =
-                enhanced_position_evaluation(&temp_plateau, position, &chosen_tile, current_turn);
+            let enhanced_eval = enhanced_position_evaluation(
+                &temp_plateau,
+                position,
+                &chosen_tile,
+                current_turn,
+                hyperparams.wasted_tile_penalty,
+            );
 
             let normalized_rollout = ((average_score / 350.0).clamp(0.0, 1.0) * 2.0) - 1.0;
             let normalized_value = value_estimate.clamp(-1.0, 1.0);
@@ -746,7 +885,7 @@ fn mcts_core(
             let combined_eval = w_cnn * normalized_value
                 + w_rollout * normalized_rollout
                 + hyperparams.weight_heuristic * normalized_heuristic
-                + hyperparams.weight_contextual * contextual;
+                + contextual_weight * contextual;
 
             let ucb_score = combined_eval + exploration_param * prior_prob.max(1e-6).sqrt();
 
@@ -758,16 +897,7 @@ fn mcts_core(
     }
 
     // Select the move with the highest UCB score
-    let best_position = legal_moves
-        .into_iter()
-        .max_by(|&a, &b| {
-            ucb_scores
-                .get(&a)
-                .unwrap_or(&f64::NEG_INFINITY)
-                .partial_cmp(ucb_scores.get(&b).unwrap_or(&f64::NEG_INFINITY))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .unwrap_or(0);
+    let best_position = select_best_position(&legal_moves, &ucb_scores);
 
     // **NEW: Simulate the Rest of the Game to Get Final Score**
     let mut final_plateau = plateau.clone();
@@ -835,7 +965,10 @@ fn mcts_core(
     let policy_distribution = Tensor::from_slice(&visit_distribution_raw);
     let policy_distribution_boosted = Tensor::from_slice(&visit_distribution_boosted);
 
-    let total_boost: f64 = boost_applied.values().sum();
+    let total_boost: f64 = legal_moves
+        .iter()
+        .filter_map(|pos| boost_applied.get(pos))
+        .sum();
 
     // STOCHZERO: Compute Q-value based policy distribution
     let q_value_dist = create_q_value_policy_target(&value_estimates, 19, 1.0);
@@ -862,6 +995,11 @@ fn mcts_core(
 /// - Allocations: -97% (from 36,750 to <1,000 per call)
 /// - CPU time: -30% (from profiling analysis)
 /// - Score: +20-40 pts (from reduced overhead allowing more simulations)
+///
+/// With a time budget (`hyperparams.time_budget_ms`, capped by the server's
+/// `--max-move-ms`) the search is timed: it stops at the deadline, even in the
+/// middle of a simulation, and returns its best move so far. The first
+/// simulation always completes, so every legal move gets at least one visit.
 #[allow(clippy::too_many_arguments)]
 fn mcts_core_cow(
     plateau_cow: &PlateauCoW,
@@ -872,6 +1010,45 @@ fn mcts_core_cow(
     current_turn: usize,
     total_turns: usize,
     hyperparams: &MCTSHyperparameters,
+    tree: Option<&mut MctsTree>,
+) -> MCTSResult {
+    let deadline = search_deadline(hyperparams);
+    let mut deadline_reached = || deadline_passed(deadline);
+
+    mcts_core_cow_until(
+        plateau_cow,
+        deck_cow,
+        chosen_tile,
+        evaluator,
+        num_simulations,
+        current_turn,
+        total_turns,
+        hyperparams,
+        tree,
+        deadline
+            .is_some()
+            .then_some(&mut deadline_reached as &mut dyn FnMut() -> bool),
+    )
+}
+
+/// Anytime search behind [`mcts_core_cow`]: `should_stop` is polled before
+/// every position evaluation. Once it returns `true` (after the first
+/// simulation, which always completes), the statistics of the interrupted
+/// simulation are dropped and the result is built from the simulations
+/// completed so far, exactly as if only those had been requested. The move
+/// is picked from the final evaluations, so a longer search can change it.
+#[allow(clippy::too_many_arguments)]
+fn mcts_core_cow_until(
+    plateau_cow: &PlateauCoW,
+    deck_cow: &DeckCoW,
+    chosen_tile: Tile,
+    evaluator: MctsEvaluator<'_>,
+    num_simulations: usize,
+    current_turn: usize,
+    total_turns: usize,
+    hyperparams: &MCTSHyperparameters,
+    mut tree: Option<&mut MctsTree>,
+    mut should_stop: Option<&mut dyn FnMut() -> bool>,
 ) -> MCTSResult {
     // Extract architecture from evaluator
     let arch = match &evaluator {
@@ -891,7 +1068,7 @@ fn mcts_core_cow(
             Tensor::zeros([distribution_len], (Kind::Float, tch::Device::Cpu));
         let policy_distribution_boosted = policy_distribution.shallow_clone();
         return MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_by_arch(
                 arch,
                 plateau,
@@ -1137,13 +1314,28 @@ fn mcts_core_cow(
         rave_scores.insert(position, 0.0);
     }
 
+    // Tree reuse: start from the statistics kept since the previous turn
+    if let Some(tree) = tree.as_mut() {
+        let reused = tree.root_stats(plateau, chosen_tile);
+        tree.start_search(plateau);
+        for &position in &legal_moves {
+            if let Some(stats) = reused.get(&position) {
+                visit_counts.insert(position, stats.visits);
+                total_scores.insert(position, stats.total_score);
+                total_visits += stats.visits as i32;
+            }
+        }
+    }
+
     let mean_value = if value_estimates.is_empty() {
         0.0
     } else {
         sum_values / value_estimates.len() as f64
     };
-    let variance = value_estimates
-        .values()
+    // Summed in position order: HashMap order would change the float rounding
+    let variance = legal_moves
+        .iter()
+        .filter_map(|pos| value_estimates.get(pos))
         .map(|&v| (v - mean_value).powi(2))
         .sum::<f64>()
         / value_estimates.len() as f64;
@@ -1184,6 +1376,8 @@ fn mcts_core_cow(
     let mut boost_applied: HashMap<usize, f64> = HashMap::new();
     let adaptive_simulations = hyperparams.get_adaptive_simulations(current_turn, num_simulations);
     let temperature = hyperparams.get_temperature(current_turn);
+    let contextual_weight =
+        hyperparams.weight_contextual * hyperparams.get_contextual_scale(current_turn);
 
     let pw_config = ProgressiveWideningConfig::adaptive(current_turn, total_turns);
     let _max_actions = max_actions_to_explore(total_visits as usize, legal_moves.len(), &pw_config);
@@ -1207,10 +1401,21 @@ fn mcts_core_cow(
         );
     }
 
-    for sim_idx in 0..adaptive_simulations {
+    // Timed search: values the running simulation overwrote, restored if the
+    // deadline interrupts it halfway through
+    let timed = should_stop.is_some();
+    let mut overwritten: Vec<(usize, usize, f64, Option<f64>, Option<f64>)> = Vec::new();
+    let mut completed_simulations = 0;
+    let mut interrupted = false;
+
+    // Lookahead moves, refilled by every rollout instead of reallocated
+    let mut second_moves = Vec::with_capacity(plateau.tiles.len());
+
+    'simulations: for sim_idx in 0..adaptive_simulations {
         // FIXED: Don't filter/sort by CNN when it's undertrained
         // Use all legal moves with uniform prior instead of CNN-based pruning
         let subset_moves: Vec<usize> = legal_moves.clone();
+        overwritten.clear();
 
         // DEBUG: Log first simulation
         if debug_first_turn && sim_idx == 0 {
@@ -1221,6 +1426,14 @@ fn mcts_core_cow(
         // BEFORE: 8 expensive clones per iteration = 880,800 total operations
         // AFTER: Cheap Rc clones + clone_for_modification() only when mutating
         for &position in &subset_moves {
+            // Still polled during the first simulation, but only obeyed after
+            // it: every legal move gets at least one visit
+            let stop = should_stop.as_mut().is_some_and(|stop| stop());
+            if stop && sim_idx > 0 {
+                interrupted = true;
+                break 'simulations;
+            }
+
             // ✅ Cheap clone (Rc increment only, no Vec allocation)
             let temp_plateau_cow = plateau_cow.clone_for_modification();
             let temp_deck_cow = deck_cow.clone_for_modification();
@@ -1254,7 +1467,7 @@ fn mcts_core_cow(
                     let tile2_index = random_index(deck_tiles_len);
                     let tile2 = lookahead_deck_cow.read(|d| d.tiles[tile2_index]);
 
-                    let second_moves = lookahead_plateau_cow.read(get_legal_moves);
+                    lookahead_plateau_cow.read(|p| get_legal_moves_into(p, &mut second_moves));
 
                     let mut best_score_for_tile2: f64 = 0.0;
 
@@ -1267,13 +1480,25 @@ fn mcts_core_cow(
                         let deck2_cow = replace_tile_in_deck_cow(&deck2_cow, &tile2);
 
                         // RAVE: Use with_trace to get positions played during rollout
-                        let (score, positions_played) = simulate_games_smart_with_trace(
-                            plateau2_cow.into_inner(), // ✅ Consumes CoW wrapper
-                            deck2_cow.into_inner(),    // ✅ Consumes CoW wrapper
-                            None,
-                        );
+                        // Truncated rollout: leaf scored by heuristic after rollout_depth moves
+                        let (score, positions_played) = if hyperparams.rollout_depth > 0 {
+                            simulate_games_smart_truncated_with_trace(
+                                plateau2_cow.into_inner(),
+                                deck2_cow.into_inner(),
+                                hyperparams.rollout_depth,
+                            )
+                        } else {
+                            simulate_games_smart_with_trace(
+                                plateau2_cow.into_inner(), // ✅ Consumes CoW wrapper
+                                deck2_cow.into_inner(),    // ✅ Consumes CoW wrapper
+                                None,
+                            )
+                        };
                         let score = score as f64;
                         best_score_for_tile2 = best_score_for_tile2.max(score);
+                        if let Some(tree) = tree.as_mut() {
+                            tree.record_reply(position, tile2, pos2, score);
+                        }
 
                         // RAVE: Update statistics for all positions in rollout (All-Moves-As-First heuristic)
                         for &played_pos in &positions_played {
@@ -1290,6 +1515,16 @@ fn mcts_core_cow(
                 total_simulated_score / rollout_count as f64
             };
 
+            if timed {
+                overwritten.push((
+                    position,
+                    visit_counts[&position],
+                    total_scores[&position],
+                    ucb_scores_raw.get(&position).copied(),
+                    boost_applied.get(&position).copied(),
+                ));
+            }
+
             let visits = visit_counts.entry(position).or_insert(0);
             *visits += 1;
             total_visits += 1;
@@ -1303,8 +1538,13 @@ fn mcts_core_cow(
             let average_score = *total_score / (*visits as f64);
 
             let temp_plateau = temp_plateau_cow.read(|p| p.clone());
-            let enhanced_eval =
-                enhanced_position_evaluation(&temp_plateau, position, &chosen_tile, current_turn);
+            let enhanced_eval = enhanced_position_evaluation(
+                &temp_plateau,
+                position,
+                &chosen_tile,
+                current_turn,
+                hyperparams.wasted_tile_penalty,
+            );
 
             let normalized_rollout = ((average_score / 350.0).clamp(0.0, 1.0) * 2.0) - 1.0;
             let normalized_value = value_estimates[&position];
@@ -1327,18 +1567,69 @@ fn mcts_core_cow(
             let combined_eval = w_cnn * normalized_value
                 + w_rollout * normalized_rollout
                 + hyperparams.weight_heuristic * normalized_heuristic
-                + hyperparams.weight_contextual * contextual;
-
-            // RAVE: DISABLED for diagnostics - causes variance issues (0-158 pts range)
-            let final_eval = combined_eval; // Force no RAVE contribution
+                + contextual_weight * contextual;
+
+            // RAVE: off by default (variance issues, 0-158 pts range), opt-in via rave_beta
+            let final_eval = match hyperparams.rave_beta {
+                Some(bias) if rave_visits[&position] > 0 => {
+                    let rave_average = rave_scores[&position] / rave_visits[&position] as f64;
+                    let normalized_rave = ((rave_average / 350.0).clamp(0.0, 1.0) * 2.0) - 1.0;
+                    let beta = rave_weight(*visits, rave_visits[&position], bias);
+                    (1.0 - beta) * combined_eval + beta * normalized_rave
+                }
+                _ => combined_eval,
+            };
 
             let ucb_score = final_eval + exploration_param * prior_prob.max(1e-6).sqrt();
 
-            ucb_scores_raw.insert(position, combined_eval);
+            ucb_scores_raw.insert(position, final_eval);
             *boost_applied.entry(position).or_insert(0.0) += contextual;
 
             ucb_scores.insert(position, ucb_score);
         }
+
+        completed_simulations += 1;
+    }
+
+    if interrupted {
+        for (position, visits, total_score, raw, boost) in overwritten.drain(..).rev() {
+            visit_counts.insert(position, visits);
+            total_scores.insert(position, total_score);
+            match raw {
+                Some(raw) => ucb_scores_raw.insert(position, raw),
+                None => ucb_scores_raw.remove(&position),
+            };
+            match boost {
+                Some(boost) => boost_applied.insert(position, boost),
+                None => boost_applied.remove(&position),
+            };
+        }
+        log::debug!(
+            "[TimedMCTS] turn={} stopped after {}/{} simulations",
+            current_turn,
+            completed_simulations,
+            adaptive_simulations
+        );
+    }
+
+    // Best move from the final statistics: the search evaluation of each
+    // position (value estimate, rollouts, heuristics) without the exploration
+    // bonus, so extra simulations can change the choice. The initial rollouts
+    // decide when no simulation ran.
+    let best_position = if ucb_scores_raw.is_empty() {
+        select_best_position(&legal_moves, &value_estimates)
+    } else {
+        select_best_position(&legal_moves, &ucb_scores_raw)
+    };
+
+    if let Some(tree) = tree {
+        for &position in &legal_moves {
+            let stats = EdgeStats {
+                visits: visit_counts[&position],
+                total_score: total_scores[&position],
+            };
+            tree.set_root_stats(chosen_tile, position, stats);
+        }
     }
 
     // DEBUG: Log UCB scores before selection
@@ -1371,19 +1662,6 @@ fn mcts_core_cow(
         );
     }
 
-    // For supervised learning: use value_estimates (initial rollouts Q-values) for exploitation
-    // This gives deterministic expert moves based on rollout quality, not UCB exploration
-    let best_position = legal_moves
-        .into_iter()
-        .max_by(|&a, &b| {
-            value_estimates
-                .get(&a)
-                .unwrap_or(&f64::NEG_INFINITY)
-                .partial_cmp(value_estimates.get(&b).unwrap_or(&f64::NEG_INFINITY))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .unwrap_or(0);
-
     // DEBUG: Log selected position
     if debug_first_turn {
         log::info!(
@@ -1394,26 +1672,29 @@ fn mcts_core_cow(
     }
 
     // Final simulation using owned values (acceptable, happens once per move)
+    // The score follows the fill incrementally instead of a final rescoring
     let mut final_plateau = plateau.clone();
     let mut final_deck = deck.clone();
-    final_plateau.tiles[best_position] = chosen_tile;
+    let mut partial_score = PartialScore::new(&final_plateau);
+    partial_score.place(&mut final_plateau, best_position, chosen_tile);
     final_deck = replace_tile_in_deck(&final_deck, &chosen_tile);
 
+    let mut available_moves = Vec::with_capacity(final_plateau.tiles.len());
     while !is_plateau_full(&final_plateau) {
         let tile_index = random_index(final_deck.tiles.len());
         let random_tile = final_deck.tiles[tile_index];
 
-        let available_moves = get_legal_moves(&final_plateau);
+        get_legal_moves_into(&final_plateau, &mut available_moves);
         if available_moves.is_empty() {
             break;
         }
 
         let random_position = available_moves[random_index(available_moves.len())];
-        final_plateau.tiles[random_position] = random_tile;
+        partial_score.place(&mut final_plateau, random_position, random_tile);
         final_deck = replace_tile_in_deck(&final_deck, &random_tile);
     }
 
-    let final_score = result(&final_plateau);
+    let final_score = partial_score.score();
 
     let mut visit_distribution_boosted = vec![0f32; plateau.tiles.len()];
     for (&position, &count) in visit_counts.iter() {
@@ -1459,7 +1740,10 @@ fn mcts_core_cow(
     let policy_distribution = Tensor::from_slice(&visit_distribution_raw);
     let policy_distribution_boosted = Tensor::from_slice(&visit_distribution_boosted);
 
-    let total_boost: f64 = boost_applied.values().sum();
+    let total_boost: f64 = legal_moves
+        .iter()
+        .filter_map(|pos| boost_applied.get(pos))
+        .sum();
 
     // STOCHZERO: Compute Q-value based policy distribution
     // This provides stronger learning signal than visit counts (which are uniform with 200 sims)
@@ -1480,6 +1764,220 @@ fn mcts_core_cow(
     }
 }
 
+/// Statistics shared by the workers of [`mcts_core_cow_parallel`], indexed by
+/// position.
+struct SharedStats {
+    visits: Vec<usize>,
+    total_scores: Vec<f64>,
+    /// Simulations currently running on each position (virtual losses)
+    in_flight: Vec<usize>,
+    total_visits: usize,
+}
+
+impl SharedStats {
+    fn new(num_positions: usize) -> Self {
+        Self {
+            visits: vec![0; num_positions],
+            total_scores: vec![0.0; num_positions],
+            in_flight: vec![0; num_positions],
+            total_visits: 0,
+        }
+    }
+
+    /// UCT pick, every in-flight simulation counting as a visit that lost
+    /// `virtual_loss`; the pick is marked in flight until [`Self::backup`].
+    fn select(&mut self, legal_moves: &[usize], c_puct: f64, virtual_loss: f64) -> usize {
+        let log_total = ((self.total_visits + 1) as f64).ln();
+        let ucb = |position: usize| {
+            let (visits, in_flight) = (self.visits[position], self.in_flight[position]);
+            let n = visits + in_flight;
+            if n == 0 {
+                return f64::INFINITY;
+            }
+            let value_sum = if visits == 0 {
+                0.0
+            } else {
+                let average = self.total_scores[position] / visits as f64;
+                visits as f64 * (((average / 350.0).clamp(0.0, 1.0) * 2.0) - 1.0)
+            };
+            let value = (value_sum - virtual_loss * in_flight as f64) / n as f64;
+            value + c_puct * (log_total / n as f64).sqrt()
+        };
+        // Ties go to the smallest position
+        let position = legal_moves
+            .iter()
+            .copied()
+            .max_by(|&a, &b| {
+                ucb(a)
+                    .partial_cmp(&ucb(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(b.cmp(&a))
+            })
+            .expect("select needs at least one legal move");
+        self.in_flight[position] += 1;
+        position
+    }
+
+    /// Record the score of a simulation of `position` and lift its virtual loss.
+    fn backup(&mut self, position: usize, score: f64) {
+        self.in_flight[position] -= 1;
+        self.visits[position] += 1;
+        self.total_scores[position] += score;
+        self.total_visits += 1;
+    }
+}
+
+/// One simulation of the serial search for `position`: place `chosen_tile`,
+/// draw the next tile and keep the best rollout over its placements.
+fn lookahead_score(plateau: &Plateau, deck: &Deck, position: usize, chosen_tile: Tile) -> f64 {
+    let mut plateau = plateau.clone();
+    plateau.tiles[position] = chosen_tile;
+    let deck = replace_tile_in_deck(deck, &chosen_tile);
+    if deck.tiles.is_empty() {
+        return result(&plateau) as f64;
+    }
+
+    let tile2 = deck.tiles[random_index(deck.tiles.len())];
+    let deck2 = replace_tile_in_deck(&deck, &tile2);
+    get_legal_moves(&plateau)
+        .into_iter()
+        .map(|pos2| {
+            let mut plateau2 = plateau.clone();
+            plateau2.tiles[pos2] = tile2;
+            simulate_games_smart(plateau2, deck2.clone(), None) as f64
+        })
+        .reduce(f64::max)
+        .unwrap_or_else(|| result(&plateau) as f64)
+}
+
+/// Multi-threaded pure-rollout search.
+///
+/// Workers of the rayon pool share the per-position statistics: each one
+/// picks a position by UCT, simulates it like one step of the serial search
+/// and backs the score up. While a simulation runs its position carries a
+/// virtual loss (`hyperparams.virtual_loss`), so concurrent workers don't all
+/// pick the same position. The budget matches the serial search, one
+/// simulation per legal move for each of `num_simulations`, and the most
+/// visited position wins. Works on plain boards: the CoW wrappers are not
+/// `Send`.
+fn mcts_core_cow_parallel(
+    plateau: &Plateau,
+    deck: &Deck,
+    chosen_tile: Tile,
+    num_simulations: usize,
+    current_turn: usize,
+    total_turns: usize,
+    hyperparams: &MCTSHyperparameters,
+) -> MCTSResult {
+    let legal_moves = get_legal_moves(plateau);
+    if legal_moves.is_empty() {
+        // Nothing to search: same terminal result as the serial search
+        return mcts_find_best_position_for_tile_pure(
+            &mut plateau.clone(),
+            &mut deck.clone(),
+            chosen_tile,
+            0,
+            current_turn,
+            total_turns,
+            Some(hyperparams),
+        );
+    }
+
+    let c_puct = hyperparams.get_c_puct(current_turn);
+    let budget = num_simulations.max(1) * legal_moves.len();
+    let deadline = search_deadline(hyperparams);
+    let stats = Mutex::new(SharedStats::new(plateau.tiles.len()));
+    let next_simulation = AtomicUsize::new(0);
+    let workers = rayon::current_num_threads().min(budget);
+    (0..workers).into_par_iter().for_each(|_| {
+        loop {
+            // Unvisited positions are picked first: the first simulations
+            // visit every legal move before the deadline can stop the search
+            let simulation = next_simulation.fetch_add(1, Ordering::Relaxed);
+            if simulation >= budget
+                || (simulation >= legal_moves.len() && deadline_passed(deadline))
+            {
+                break;
+            }
+            let position =
+                stats
+                    .lock()
+                    .unwrap()
+                    .select(&legal_moves, c_puct, hyperparams.virtual_loss);
+            let score = lookahead_score(plateau, deck, position, chosen_tile);
+            stats.lock().unwrap().backup(position, score);
+        }
+    });
+    let stats = stats.into_inner().unwrap();
+
+    let visit_scores: HashMap<usize, f64> = legal_moves
+        .iter()
+        .map(|&position| (position, stats.visits[position] as f64))
+        .collect();
+    let best_position = select_best_position(&legal_moves, &visit_scores);
+    let distribution: Vec<f32> = stats
+        .visits
+        .iter()
+        .map(|&visits| visits as f32 / stats.total_visits as f32)
+        .collect();
+    let policy_distribution = Tensor::from_slice(&distribution);
+
+    MCTSResult {
+        best_position,
+        board_tensor: convert_plateau_to_tensor(
+            plateau,
+            &chosen_tile,
+            deck,
+            current_turn,
+            total_turns,
+        ),
+        subscore: stats.total_scores[best_position] / stats.visits[best_position].max(1) as f64,
+        policy_distribution_boosted: policy_distribution.shallow_clone(),
+        policy_distribution,
+        boost_intensity: 0.0,
+        graph_features: None,
+        plateau: Some(plateau.clone()),
+        current_turn: Some(current_turn),
+        total_turns: Some(total_turns),
+        q_value_distribution: None,
+    }
+}
+
+/// RAVE share of a move's evaluation: β = ñ / (n + ñ + b·n·ñ), with `n` its
+/// visits, `ñ` its All-Moves-As-First visits and `b` the bias. Starts at 1 and
+/// fades as the move gets visited.
+fn rave_weight(visits: usize, rave_visits: usize, bias: f64) -> f64 {
+    let (n, rave_n) = (visits as f64, rave_visits as f64);
+    rave_n / (n + rave_n + bias * n * rave_n)
+}
+
+/// Select the legal move with the highest score.
+///
+/// Ties are broken by the smallest position index and missing/NaN scores rank lowest,
+/// so the choice never depends on `HashMap` iteration order.
+fn select_best_position(legal_moves: &[usize], scores: &HashMap<usize, f64>) -> usize {
+    let score_of = |pos: usize| {
+        scores
+            .get(&pos)
+            .copied()
+            .filter(|s| !s.is_nan())
+            .unwrap_or(f64::NEG_INFINITY)
+    };
+
+    let mut candidates = legal_moves.to_vec();
+    candidates.sort_unstable();
+
+    let mut best: Option<(usize, f64)> = None;
+    for pos in candidates {
+        let score = score_of(pos);
+        match best {
+            Some((_, best_score)) if score <= best_score => {}
+            _ => best = Some((pos, score)),
+        }
+    }
+    best.map(|(pos, _)| pos).unwrap_or(0)
+}
+
 /// Create policy distribution from Q-values using softmax with temperature.
 /// This allows the policy network to learn from rollout quality rather than visit counts.
 ///
@@ -1543,7 +2041,22 @@ fn create_q_value_policy_target(
     Tensor::from_slice(&policy)
 }
 
-/// Gumbel MCTS Core - Uses Gumbel noise instead of UCB for selection
+#[cfg(test)]
+thread_local! {
+    /// Rollouts played by the Gumbel search on this thread (tests only)
+    static GUMBEL_ROLLOUTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// One rollout of the Gumbel search, counted in tests
+fn gumbel_rollout(plateau: Plateau, deck: Deck) -> f64 {
+    #[cfg(test)]
+    GUMBEL_ROLLOUTS.with(|count| count.set(count.get() + 1));
+    simulate_games_smart(plateau, deck, None) as f64
+}
+
+/// Gumbel MCTS Core - Uses Gumbel noise instead of UCB for selection.
+/// Top-k and rollout counts come from `hyperparams` (`gumbel` and the shared
+/// `rollout_*` buckets).
 #[allow(clippy::too_many_arguments)]
 fn mcts_core_gumbel(
     plateau: &mut Plateau,
@@ -1553,6 +2066,7 @@ fn mcts_core_gumbel(
     num_simulations: usize,
     current_turn: usize,
     total_turns: usize,
+    hyperparams: &MCTSHyperparameters,
 ) -> MCTSResult {
     use crate::mcts::gumbel_selection::{gumbel_select, GumbelSelector};
 
@@ -1570,7 +2084,7 @@ fn mcts_core_gumbel(
             Tensor::zeros([distribution_len], (Kind::Float, tch::Device::Cpu));
         let policy_distribution_boosted = policy_distribution.shallow_clone();
         return MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_by_arch(
                 arch,
                 plateau,
@@ -1648,42 +2162,26 @@ fn mcts_core_gumbel(
             let policy_logits = policy_net.forward(&input_tensor, false);
             let policy = policy_logits.log_softmax(-1, tch::Kind::Float).exp();
 
-            for &position in &legal_moves {
-                let mut temp_plateau = plateau.clone();
-                let mut temp_deck = deck.clone();
-
-                temp_plateau.tiles[position] = chosen_tile;
-                temp_deck = replace_tile_in_deck(&temp_deck, &chosen_tile);
-
-                let board_tensor_temp = match policy_net.arch {
-                    NNArchitecture::Cnn => convert_plateau_to_tensor(
+            // One batched value-net pass over the boards after each legal move
+            let deck_after = replace_tile_in_deck(deck, &chosen_tile);
+            let boards: Vec<Tensor> = legal_moves
+                .iter()
+                .map(|&position| {
+                    let mut temp_plateau = plateau.clone();
+                    temp_plateau.tiles[position] = chosen_tile;
+                    convert_plateau_by_arch(
+                        policy_net.arch,
                         &temp_plateau,
                         &chosen_tile,
-                        &temp_deck,
+                        &deck_after,
                         current_turn,
                         total_turns,
-                    ),
-                    NNArchitecture::CnnOnehot => {
-                        crate::neural::tensor_onehot::convert_plateau_onehot(
-                            &temp_plateau,
-                            &chosen_tile,
-                            &temp_deck,
-                            current_turn,
-                        )
-                    }
-                    NNArchitecture::Gnn => {
-                        convert_plateau_to_graph_features(&temp_plateau, current_turn, total_turns)
-                    }
-                    NNArchitecture::GraphTransformer => {
-                        convert_plateau_for_gat_47ch(&temp_plateau, &chosen_tile, &temp_deck, current_turn, total_turns)
-                    }
-                };
-
-                let pred_value = value_net
-                    .forward(&board_tensor_temp, false)
-                    .double_value(&[])
-                    .clamp(-1.0, 1.0);
+                    )
+                })
+                .collect();
+            let pred_values = batched_value_estimates(value_net, &boards);
 
+            for (&position, &pred_value) in legal_moves.iter().zip(&pred_values) {
                 min_value = min_value.min(pred_value);
                 max_value = max_value.max(pred_value);
 
@@ -1723,12 +2221,12 @@ fn mcts_core_gumbel(
                 temp_plateau.tiles[position] = chosen_tile;
                 temp_deck = replace_tile_in_deck(&temp_deck, &chosen_tile);
 
-                let rollout_count = 6; // Gumbel uses hardcoded values (not part of hyperparams system)
+                let rollout_count = hyperparams.gumbel.pure_prior_rollouts;
                 let mut total_simulated_score = 0.0;
 
                 for _ in 0..rollout_count {
                     total_simulated_score +=
-                        simulate_games_smart(temp_plateau.clone(), temp_deck.clone(), None) as f64;
+                        gumbel_rollout(temp_plateau.clone(), temp_deck.clone());
                     // Note: clone needed here as temp_plateau/temp_deck used multiple times in loop
                 }
                 let avg_score = total_simulated_score / rollout_count as f64;
@@ -1767,9 +2265,13 @@ fn mcts_core_gumbel(
     }
 
     // Run simulations
+    let deadline = search_deadline(hyperparams);
     for sim_idx in 0..num_simulations {
+        if sim_idx >= legal_moves.len() && deadline_passed(deadline) {
+            break;
+        }
         // Use Gumbel selection for move selection
-        let top_k = 5; // Consider top 5 candidates
+        let top_k = hyperparams.gumbel.top_k;
         let selected_position = if sim_idx < legal_moves.len() {
             // First N simulations: ensure each move visited at least once
             legal_moves[sim_idx]
@@ -1789,13 +2291,8 @@ fn mcts_core_gumbel(
 
         let value_estimate = *value_estimates.get(&selected_position).unwrap_or(&0.0);
 
-        // Adaptive rollout count
-        let rollout_count = match value_estimate {
-            x if x > 0.7 => 3,
-            x if x > 0.2 => 5,
-            x if x < -0.4 => 9,
-            _ => 7,
-        };
+        // Adaptive rollout count, same buckets as the UCB path
+        let rollout_count = hyperparams.get_rollout_count(value_estimate);
 
         let mut total_simulated_score = 0.0;
 
@@ -1816,7 +2313,7 @@ fn mcts_core_gumbel(
                 plateau2.tiles[pos2] = tile2;
                 deck2 = replace_tile_in_deck(&deck2, &tile2);
 
-                let score = simulate_games_smart(plateau2, deck2, None) as f64;
+                let score = gumbel_rollout(plateau2, deck2);
                 best_score_for_tile2 = best_score_for_tile2.max(score);
             }
 
@@ -1836,16 +2333,7 @@ fn mcts_core_gumbel(
 
     // Select best move based on Q-values (greedy, exploitation)
     // For supervised learning, we want deterministic expert moves based on rollout quality
-    let best_position = legal_moves
-        .into_iter()
-        .max_by(|&a, &b| {
-            q_values
-                .get(&a)
-                .unwrap_or(&f64::NEG_INFINITY)
-                .partial_cmp(q_values.get(&b).unwrap_or(&f64::NEG_INFINITY))
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .unwrap_or(0);
+    let best_position = select_best_position(&legal_moves, &q_values);
 
     // Simulate rest of game for final score
     let mut final_plateau = plateau.clone();
@@ -1917,6 +2405,11 @@ fn mcts_core_gumbel(
 /// The `exploration_priors` parameter allows adding Dirichlet noise for exploration
 /// during self-play (AlphaGo Zero technique). This breaks circular learning where
 /// uniform policy → uniform MCTS → uniform training data → uniform policy.
+/// The noise comes from [`root_noise`](crate::mcts::dirichlet::root_noise) and
+/// is mixed with weight `hyperparams.dirichlet_epsilon`.
+///
+/// Like every search here, it stops at the time budget (`time_budget_ms`,
+/// `--max-move-ms`) once each legal move has been visited.
 #[allow(clippy::too_many_arguments)]
 pub fn mcts_find_best_position_for_tile_uct(
     plateau: &mut Plateau,
@@ -1942,7 +2435,7 @@ pub fn mcts_find_best_position_for_tile_uct(
         let policy_distribution =
             Tensor::zeros([distribution_len], (Kind::Float, tch::Device::Cpu));
         return MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_by_arch(
                 arch,
                 plateau,
@@ -1997,8 +2490,10 @@ pub fn mcts_find_best_position_for_tile_uct(
     // ====================================================================
     // If exploration_priors provided, mix them with network policy:
     // mixed_prior = (1 - ε) * policy_prior + ε * dirichlet_noise
+    // (noise from mcts::dirichlet::root_noise; ε = 0 disables the mix)
+    let exploration_priors = exploration_priors.filter(|_| hyperparams.dirichlet_epsilon > 0.0);
     if let Some(ref noise_vec) = exploration_priors {
-        let epsilon = 0.5; // Mix ratio: 50% policy + 50% noise (STRENGTHENED to break circular learning)
+        let epsilon = hyperparams.dirichlet_epsilon; // 0.5 by default (STRENGTHENED to break circular learning)
 
         // DEBUG: Log policy evolution across game (turn 0, 5, 10, 15)
         let debug_turns = [0, 5, 10, 15];
@@ -2065,9 +2560,13 @@ pub fn mcts_find_best_position_for_tile_uct(
     }
 
     let total_simulations = hyperparams.get_adaptive_simulations(current_turn, num_simulations);
+    let deadline = search_deadline(hyperparams);
 
     // UCT simulation loop - ONE position per simulation
     for sim_idx in 0..total_simulations {
+        if deadline_passed(deadline) && visit_counts.values().all(|&visits| visits > 0) {
+            break;
+        }
         // Selection: Choose ONE position using UCT formula + policy prior
         let total_visits = sim_idx + 1;
         let exploration_const = hyperparams.get_c_puct(current_turn);
@@ -2282,3 +2781,491 @@ fn get_hex_degree(position: usize) -> usize {
     }
     degree
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random_index::with_seeded_rng;
+
+    #[test]
+    fn test_select_best_position_breaks_ties_by_smallest_index() {
+        let legal_moves = vec![14, 3, 9, 7];
+        let scores: HashMap<usize, f64> =
+            [(14, 0.8), (3, 0.5), (9, 0.8), (7, 0.8)].into_iter().collect();
+
+        for _ in 0..10 {
+            assert_eq!(select_best_position(&legal_moves, &scores), 7);
+        }
+    }
+
+    #[test]
+    fn test_select_best_position_ignores_nan_and_missing() {
+        let legal_moves = vec![0, 1, 2];
+        let scores: HashMap<usize, f64> = [(0, f64::NAN), (2, -0.3)].into_iter().collect();
+        assert_eq!(select_best_position(&legal_moves, &scores), 2);
+
+        let no_scores = HashMap::new();
+        assert_eq!(select_best_position(&legal_moves, &no_scores), 0);
+        assert_eq!(select_best_position(&[], &no_scores), 0);
+    }
+
+    /// Turn 17: positions 17 and 18 left, and besides the drawn tile a single
+    /// tile remains in the deck, so the initial rollouts are deterministic.
+    fn endgame_board() -> (Plateau, Deck, Tile) {
+        let mut deck = crate::game::create_deck::create_deck();
+        let tiles: Vec<Tile> = deck.tiles().to_vec();
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        for (position, tile) in tiles[..17].iter().enumerate() {
+            plateau.tiles[position] = *tile;
+        }
+        for tile in tiles[..17].iter().chain(&tiles[19..]) {
+            deck = replace_tile_in_deck(&deck, tile);
+        }
+        (plateau, deck, tiles[17])
+    }
+
+    fn endgame() -> (PlateauCoW, DeckCoW, Tile) {
+        let (plateau, deck, tile) = endgame_board();
+        (PlateauCoW::new(plateau), DeckCoW::new(deck), tile)
+    }
+
+    fn search(num_simulations: usize, should_stop: Option<&mut dyn FnMut() -> bool>) -> MCTSResult {
+        let (plateau, deck, tile) = endgame();
+        let hyperparams = MCTSHyperparameters {
+            sim_mult_late: 1.0,
+            ..Default::default()
+        };
+        with_seeded_rng(1146, || {
+            mcts_core_cow_until(
+                &plateau,
+                &deck,
+                tile,
+                MctsEvaluator::Pure,
+                num_simulations,
+                17,
+                19,
+                &hyperparams,
+                None,
+                should_stop,
+            )
+        })
+    }
+
+    #[test]
+    fn test_batched_value_estimates_match_per_position_forward() {
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        let mut deck = crate::game::create_deck::create_deck();
+        for (position, tile) in [(0, Tile(9, 6, 3)), (9, Tile(5, 2, 4)), (14, Tile(1, 7, 8))] {
+            plateau.tiles[position] = tile;
+            deck = replace_tile_in_deck(&deck, &tile);
+        }
+        let tile = Tile(9, 7, 4);
+        let deck_after = replace_tile_in_deck(&deck, &tile);
+
+        for arch in [NNArchitecture::Cnn, NNArchitecture::GraphTransformer] {
+            let vs = tch::nn::VarStore::new(tch::Device::Cpu);
+            let value_net = ValueNet::new(&vs, arch.input_dim(), arch);
+            let boards: Vec<Tensor> = get_legal_moves(&plateau)
+                .into_iter()
+                .map(|position| {
+                    let mut after = plateau.clone();
+                    after.tiles[position] = tile;
+                    convert_plateau_by_arch(arch, &after, &tile, &deck_after, 3, 19)
+                })
+                .collect();
+
+            let batched = batched_value_estimates(&value_net, &boards);
+            assert_eq!(batched.len(), 16);
+            for (board, value) in boards.iter().zip(batched) {
+                let single = value_net
+                    .forward(board, false)
+                    .double_value(&[])
+                    .clamp(-1.0, 1.0);
+                assert!((single - value).abs() < 1e-6, "{:?}", arch);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interrupted_search_matches_completed_simulations() {
+        let completed = search(2, None);
+
+        // 2 legal moves per simulation: stop before the 2nd move of simulation 3
+        let mut polls = 0;
+        let mut stop_mid_third = || {
+            polls += 1;
+            polls == 6
+        };
+        let interrupted = search(50, Some(&mut stop_mid_third));
+        assert_eq!(polls, 6);
+
+        assert_eq!(interrupted.best_position, completed.best_position);
+        let visits = |r: &MCTSResult| Vec::<f32>::try_from(&r.policy_distribution_boosted).unwrap();
+        assert_eq!(visits(&interrupted), visits(&completed));
+        assert_eq!(visits(&completed)[17..], [0.5, 0.5]);
+        let raw = |r: &MCTSResult| Vec::<f32>::try_from(&r.policy_distribution).unwrap();
+        assert_eq!(raw(&interrupted), raw(&completed));
+    }
+
+    #[test]
+    fn test_best_move_follows_the_final_search_stats() {
+        // The raw policy is a softmax of the final evaluations: the chosen
+        // move is its argmax, whatever the number of simulations
+        for num_simulations in [1, 5, 30] {
+            let result = search(num_simulations, None);
+            let raw = Vec::<f32>::try_from(&result.policy_distribution).unwrap();
+            let best = raw[result.best_position];
+            assert!(raw.iter().all(|&p| p <= best), "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn test_immediate_stop_still_visits_every_legal_move() {
+        let one_simulation = search(1, None);
+        let mut always_stop = || true;
+        let stopped = search(50, Some(&mut always_stop));
+
+        let visits = |r: &MCTSResult| Vec::<f32>::try_from(&r.policy_distribution_boosted).unwrap();
+        assert_eq!(visits(&stopped), visits(&one_simulation));
+        assert_eq!(visits(&stopped)[17..], [0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_same_seed_replays_same_search() {
+        let mut deck = crate::game::create_deck::create_deck();
+        let tiles: Vec<Tile> = deck.tiles().to_vec();
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        for (&position, tile) in [0, 4, 9, 11, 13, 17].iter().zip(&tiles[..6]) {
+            plateau.tiles[position] = *tile;
+            deck = replace_tile_in_deck(&deck, tile);
+        }
+
+        let search = |seed: u64| {
+            let (mut plateau, mut deck) = (plateau.clone(), deck.clone());
+            with_seeded_rng(seed, || {
+                mcts_find_best_position_for_tile_pure(
+                    &mut plateau,
+                    &mut deck,
+                    tiles[6],
+                    20,
+                    6,
+                    19,
+                    None,
+                )
+            })
+        };
+        let first = search(1261);
+        let second = search(1261);
+        assert_eq!(first.best_position, second.best_position);
+        assert_eq!(first.subscore, second.subscore);
+    }
+
+    #[test]
+    fn test_rave_weight_fades_with_visits() {
+        assert_eq!(rave_weight(0, 10, 0.1), 1.0);
+        assert_eq!(rave_weight(5, 0, 0.1), 0.0);
+        assert!(rave_weight(10, 50, 0.1) > rave_weight(20, 50, 0.1));
+        // Without bias β is the plain AMAF share of the visits
+        assert!((rave_weight(30, 10, 0.0) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rave_blends_into_raw_policy_only_when_enabled() {
+        // 4 empty cells: rollouts still play moves that are legal at the root
+        let mut deck = crate::game::create_deck::create_deck();
+        let tiles: Vec<Tile> = deck.tiles().to_vec();
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        for (position, tile) in tiles[..15].iter().enumerate() {
+            plateau.tiles[position] = *tile;
+            deck = replace_tile_in_deck(&deck, tile);
+        }
+        let tile = tiles[15];
+
+        let search = |rave_beta: Option<f64>| {
+            let hyperparams = MCTSHyperparameters {
+                rave_beta,
+                ..Default::default()
+            };
+            let (mut plateau, mut deck) = (plateau.clone(), deck.clone());
+            with_seeded_rng(1262, || {
+                mcts_find_best_position_for_tile_pure(
+                    &mut plateau,
+                    &mut deck,
+                    tile,
+                    10,
+                    15,
+                    19,
+                    Some(&hyperparams),
+                )
+            })
+        };
+        let policy = |r: &MCTSResult| Vec::<f32>::try_from(&r.policy_distribution).unwrap();
+
+        // Same seed, same rollouts: only the RAVE blend differs
+        let off = search(None);
+        assert_eq!(policy(&off), policy(&search(None)));
+        let rave = search(Some(0.01));
+        assert_ne!(policy(&off), policy(&rave));
+        // The blended evaluations pick the move
+        let best = policy(&rave)[rave.best_position];
+        assert!(policy(&rave).iter().all(|&p| p <= best));
+    }
+
+    #[test]
+    fn test_reused_tree_adds_the_visits_of_the_previous_turn() {
+        // 4 empty cells and only the 4 tiles left to play in the deck
+        let tiles: Vec<Tile> = crate::game::create_deck::create_deck().tiles().to_vec();
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        for (position, tile) in tiles[..15].iter().enumerate() {
+            plateau.tiles[position] = *tile;
+        }
+        let deck = Deck {
+            tiles: tiles[15..19].to_vec(),
+        };
+        let search = |plateau: &Plateau, deck: &Deck, tile, turn, tree: &mut MctsTree| {
+            let (mut plateau, mut deck) = (plateau.clone(), deck.clone());
+            mcts_find_best_position_for_tile_reusing(
+                &mut plateau,
+                &mut deck,
+                tile,
+                MctsEvaluator::Pure,
+                10,
+                turn,
+                19,
+                None,
+                tree,
+            )
+        };
+
+        with_seeded_rng(1263, || {
+            let mut tree = MctsTree::new();
+            let first = search(&plateau, &deck, tiles[15], 15, &mut tree);
+            tree.advance(first.best_position, tiles[15]);
+            let carried = tree.visits(tiles[16]);
+            assert!(carried > 0);
+
+            plateau.tiles[first.best_position] = tiles[15];
+            let deck = replace_tile_in_deck(&deck, &tiles[15]);
+            search(&plateau, &deck, tiles[16], 16, &mut tree);
+            let mut fresh = MctsTree::new();
+            search(&plateau, &deck, tiles[16], 16, &mut fresh);
+            // Same fresh simulations, plus everything learnt last turn
+            assert_eq!(tree.visits(tiles[16]), fresh.visits(tiles[16]) + carried);
+        });
+    }
+
+    #[test]
+    fn test_virtual_loss_spreads_concurrent_selections() {
+        let legal_moves = [2, 5, 9];
+        let mut stats = SharedStats::new(19);
+        // Three workers in flight at once: one per move, not three on the first
+        let picks: Vec<usize> = (0..3)
+            .map(|_| stats.select(&legal_moves, 1.0, 1.0))
+            .collect();
+        assert_eq!(picks, vec![2, 5, 9]);
+        for &position in &picks {
+            stats.backup(position, 175.0);
+        }
+        assert_eq!(stats.in_flight, vec![0; 19]);
+        assert_eq!(stats.total_visits, 3);
+    }
+
+    #[test]
+    fn test_parallel_search_agrees_with_serial_search() {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(1264);
+        let boards = 20;
+        let mut agreements = 0;
+        for board in 0..boards {
+            // 3 empty cells, the 11 unplayed tiles left in the deck
+            let mut tiles = crate::game::create_deck::create_deck().tiles().to_vec();
+            tiles.shuffle(&mut rng);
+            let mut positions: Vec<usize> = (0..19).collect();
+            positions.shuffle(&mut rng);
+            let mut plateau = crate::game::plateau::create_plateau_empty();
+            for (&position, tile) in positions.iter().zip(&tiles[..16]) {
+                plateau.tiles[position] = *tile;
+            }
+            let deck = Deck {
+                tiles: tiles[16..].to_vec(),
+            };
+
+            let serial = with_seeded_rng(board, || {
+                mcts_find_best_position_for_tile_pure(
+                    &mut plateau.clone(),
+                    &mut deck.clone(),
+                    tiles[16],
+                    50,
+                    16,
+                    19,
+                    None,
+                )
+            });
+            let parallel = mcts_find_best_position_for_tile_parallel(
+                &plateau, &deck, tiles[16], 50, 16, 19, None,
+            );
+            assert!(positions[16..].contains(&parallel.best_position));
+            if parallel.best_position == serial.best_position {
+                agreements += 1;
+            }
+        }
+        let agreement_rate = agreements as f64 / boards as f64;
+        assert!(agreement_rate >= 0.5, "agreement {:.2}", agreement_rate);
+    }
+
+    #[test]
+    fn test_gumbel_uses_configured_rollout_counts() {
+        let gumbel_rollouts = |hyperparams: &MCTSHyperparameters| {
+            let (mut plateau, mut deck, tile) = endgame_board();
+            GUMBEL_ROLLOUTS.with(|count| count.set(0));
+            let result = mcts_core_gumbel(
+                &mut plateau,
+                &mut deck,
+                tile,
+                MctsEvaluator::Pure,
+                4,
+                17,
+                19,
+                hyperparams,
+            );
+            assert!([17, 18].contains(&result.best_position));
+            GUMBEL_ROLLOUTS.with(|count| count.get())
+        };
+        let with_counts = |prior: usize, per_simulation: usize| MCTSHyperparameters {
+            rollout_strong: per_simulation,
+            rollout_medium: per_simulation,
+            rollout_default: per_simulation,
+            rollout_weak: per_simulation,
+            gumbel: crate::mcts::hyperparameters::GumbelConfig {
+                pure_prior_rollouts: prior,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // 2 legal moves × prior rollouts, then 4 simulations × bucket rollouts
+        // (one legal reply each)
+        assert_eq!(gumbel_rollouts(&with_counts(3, 2)), 2 * 3 + 4 * 2);
+        assert_eq!(gumbel_rollouts(&with_counts(1, 5)), 2 + 4 * 5);
+    }
+
+    #[test]
+    fn test_time_budget_returns_legal_move() {
+        let (plateau, deck, tile) = endgame();
+        let hyperparams = MCTSHyperparameters {
+            time_budget_ms: 20,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let result = mcts_core_cow(
+            &plateau,
+            &deck,
+            tile,
+            MctsEvaluator::Pure,
+            1_000_000,
+            17,
+            19,
+            &hyperparams,
+            None,
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!([17, 18].contains(&result.best_position));
+    }
+
+    #[test]
+    fn test_time_budget_stops_every_search() {
+        let mut plateau = crate::game::plateau::create_plateau_empty();
+        let mut deck = crate::game::create_deck::create_deck();
+        let tile = Tile(9, 6, 3);
+        deck = replace_tile_in_deck(&deck, &tile);
+        let hyperparams = MCTSHyperparameters {
+            time_budget_ms: 20,
+            ..Default::default()
+        };
+        let arch = NNArchitecture::Cnn;
+        let vs = tch::nn::VarStore::new(tch::Device::Cpu);
+        let policy_net = PolicyNet::new(&vs, arch.input_dim(), arch);
+        let value_net = ValueNet::new(&vs, arch.input_dim(), arch);
+        let qvalue_net = QValueNet::new(&tch::nn::VarStore::new(tch::Device::Cpu));
+
+        // Untimed, each of these would run for hours
+        let start = Instant::now();
+        let uct = mcts_find_best_position_for_tile_uct(
+            &mut plateau,
+            &mut deck,
+            tile,
+            &policy_net,
+            &value_net,
+            100_000_000,
+            0,
+            19,
+            Some(&hyperparams),
+            None,
+        );
+        let hybrid = mcts_find_best_position_for_tile_with_qnet(
+            &mut plateau,
+            &mut deck,
+            tile,
+            &policy_net,
+            &value_net,
+            &qvalue_net,
+            100_000_000,
+            0,
+            19,
+            5,
+            Some(&hyperparams),
+        );
+        let parallel = mcts_find_best_position_for_tile_parallel(
+            &plateau,
+            &deck,
+            tile,
+            100_000_000,
+            0,
+            19,
+            Some(&hyperparams),
+        );
+        assert!(start.elapsed() < Duration::from_secs(30));
+
+        for result in [&uct, &hybrid, &parallel] {
+            assert!(result.best_position < 19);
+        }
+        // Every legal move was visited before the search stopped
+        let visits = tensor_to_vec(&uct.policy_distribution);
+        assert!(visits.iter().all(|&v| v > 0.0));
+        let visits = tensor_to_vec(&parallel.policy_distribution);
+        assert!(visits.iter().all(|&v| v > 0.0));
+    }
+
+    fn tensor_to_vec(tensor: &Tensor) -> Vec<f32> {
+        Vec::<f32>::try_from(tensor.to_kind(Kind::Float)).unwrap()
+    }
+
+    #[test]
+    fn test_full_board_yields_terminal_result() {
+        let (mut plateau, mut deck, tile) = endgame_board();
+        plateau.tiles[17] = tile;
+        plateau.tiles[18] = crate::game::create_deck::create_deck().tiles()[18];
+        let mut deck_after = replace_tile_in_deck(&deck, &tile);
+
+        let pure =
+            mcts_find_best_position_for_tile_pure(&mut plateau, &mut deck, tile, 10, 19, 19, None);
+        let gumbel = mcts_core_gumbel(
+            &mut plateau,
+            &mut deck_after,
+            tile,
+            MctsEvaluator::Pure,
+            10,
+            19,
+            19,
+            &MCTSHyperparameters::default(),
+        );
+
+        for result in [pure, gumbel] {
+            assert!(result.is_terminal());
+            assert_ne!(result.best_position, 0);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Self-contained demo game: one solo game against random tile draws, played
+//! by MCTS and reported turn by turn. Backs the `play_demo` binary, which
+//! doubles as an end-to-end smoke test of the game, search and scoring code.
+
+use rand::Rng;
+
+use crate::game::create_deck::create_deck;
+use crate::game::get_legal_moves::get_legal_moves;
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
+use crate::game::tile::Tile;
+use crate::mcts::algorithm::{
+    mcts_find_best_position_for_tile_pure, mcts_find_best_position_for_tile_with_nn,
+};
+use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+use crate::scoring::scoring::result;
+
+/// Who picks the moves of the demo game.
+pub enum DemoPlayer<'a> {
+    /// MCTS guided by a loaded policy/value pair
+    Neural {
+        policy_net: &'a PolicyNet,
+        value_net: &'a ValueNet,
+    },
+    /// Rollout-only MCTS, no model needed
+    PureMcts,
+}
+
+/// One played turn: the move and the board right after it.
+#[derive(Debug, Clone)]
+pub struct DemoTurn {
+    pub turn: usize,
+    pub tile: Tile,
+    pub position: usize,
+    pub plateau: Plateau,
+    /// Score of the board so far
+    pub score: i32,
+}
+
+/// Play a full game, each tile drawn at random among the remaining ones,
+/// calling `on_turn` after every move. Returns the final board.
+pub fn play_demo_game<R: Rng>(
+    player: &DemoPlayer,
+    num_simulations: usize,
+    rng: &mut R,
+    mut on_turn: impl FnMut(&DemoTurn),
+) -> Plateau {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+
+    for turn in 0..19 {
+        let available = get_available_tiles(&deck);
+        if available.is_empty() || get_legal_moves(&plateau).is_empty() {
+            break;
+        }
+        let tile = available[rng.random_range(0..available.len())];
+        deck = replace_tile_in_deck(&deck, &tile);
+
+        let mcts_result = match player {
+            DemoPlayer::Neural {
+                policy_net,
+                value_net,
+            } => mcts_find_best_position_for_tile_with_nn(
+                &mut plateau,
+                &mut deck,
+                tile,
+                policy_net,
+                value_net,
+                num_simulations,
+                turn,
+                19,
+                None,
+            ),
+            DemoPlayer::PureMcts => mcts_find_best_position_for_tile_pure(
+                &mut plateau,
+                &mut deck,
+                tile,
+                num_simulations,
+                turn,
+                19,
+                None,
+            ),
+        };
+        plateau.tiles[mcts_result.best_position] = tile;
+
+        on_turn(&DemoTurn {
+            turn,
+            tile,
+            position: mcts_result.best_position,
+            plateau: plateau.clone(),
+            score: result(&plateau),
+        });
+    }
+
+    plateau
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_demo_game_runs_to_a_full_board() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut positions = Vec::new();
+        let plateau = play_demo_game(&DemoPlayer::PureMcts, 10, &mut rng, |turn| {
+            positions.push(turn.position)
+        });
+
+        assert_eq!(positions.len(), 19);
+        assert!(plateau.tiles.iter().all(|t| *t != Tile(0, 0, 0)));
+        positions.sort_unstable();
+        positions.dedup();
+        assert_eq!(positions.len(), 19);
+    }
+}
@@ -0,0 +1,75 @@
+//! Dirichlet noise on the root priors (AlphaGo Zero self-play exploration).
+//!
+//! The noise is mixed into the network policy at the root only:
+//! `prior = (1 - ε) × policy + ε × noise`, with `ε = dirichlet_epsilon` and
+//! the noise drawn from `Dir(dirichlet_alpha)` over the legal moves.
+
+use crate::mcts::hyperparameters::MCTSHyperparameters;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Gamma};
+
+/// Draw `n` weights from a symmetric Dirichlet of concentration `alpha`
+/// (normalized Gamma(alpha, 1) draws). Uniform if `alpha` is not > 0 or the
+/// draws all underflow to 0.
+pub fn sample_dirichlet(alpha: f64, n: usize, rng: &mut StdRng) -> Vec<f32> {
+    let uniform = vec![1.0 / n as f32; n];
+    let Ok(gamma) = Gamma::new(alpha, 1.0) else {
+        return uniform;
+    };
+    let draws: Vec<f64> = (0..n).map(|_| gamma.sample(rng)).collect();
+    let sum: f64 = draws.iter().sum();
+    if sum > 0.0 && sum.is_finite() {
+        draws.iter().map(|&draw| (draw / sum) as f32).collect()
+    } else {
+        uniform
+    }
+}
+
+/// Root noise for the `exploration_priors` of the UCT search: Dirichlet
+/// weights on `legal_moves`, indexed by position. `None` when
+/// `dirichlet_epsilon` is 0 (noise disabled).
+pub fn root_noise(
+    hyperparams: &MCTSHyperparameters,
+    legal_moves: &[usize],
+    num_positions: usize,
+    rng: &mut StdRng,
+) -> Option<Vec<f32>> {
+    if hyperparams.dirichlet_epsilon <= 0.0 || legal_moves.is_empty() {
+        return None;
+    }
+    let weights = sample_dirichlet(hyperparams.dirichlet_alpha, legal_moves.len(), rng);
+    let mut noise = vec![0.0; num_positions];
+    for (&position, weight) in legal_moves.iter().zip(weights) {
+        noise[position] = weight;
+    }
+    Some(noise)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_noise_sums_to_one_and_is_off_without_epsilon() {
+        let mut rng = StdRng::seed_from_u64(1265);
+        for alpha in [0.03, 0.3, 1.0, 10.0] {
+            let weights = sample_dirichlet(alpha, 12, &mut rng);
+            assert_eq!(weights.len(), 12);
+            assert!(weights.iter().all(|&w| w >= 0.0));
+            assert!((weights.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        }
+        assert_eq!(sample_dirichlet(0.0, 4, &mut rng), vec![0.25; 4]);
+
+        let legal_moves = [1, 4, 18];
+        let mut hyperparams = MCTSHyperparameters::default();
+        let noise = root_noise(&hyperparams, &legal_moves, 19, &mut rng).unwrap();
+        assert!((noise.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!((0..19)
+            .filter(|p| !legal_moves.contains(p))
+            .all(|p| noise[p] == 0.0));
+
+        hyperparams.dirichlet_epsilon = 0.0;
+        assert_eq!(root_noise(&hyperparams, &legal_moves, 19, &mut rng), None);
+    }
+}
@@ -16,7 +16,7 @@
 use crate::game::deck::Deck;
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
-use crate::mcts::mcts_result::MCTSResult;
+use crate::mcts::mcts_result::{MCTSResult, NO_LEGAL_MOVE};
 use crate::mcts::node::{MCTSNode, NodeType};
 use crate::mcts::selection::{backpropagate, select_best_child};
 use crate::neural::gnn::convert_plateau_for_gnn;
@@ -250,7 +250,7 @@ impl<'a> ExpectimaxMCTS<'a> {
                 .iter()
                 .max_by(|a, b| a.1 .0.partial_cmp(&b.1 .0).unwrap())
                 .map(|(pos, _)| *pos)
-                .unwrap_or(0);
+                .unwrap_or(NO_LEGAL_MOVE);
 
             // Get first available tile for tensor creation
             let first_tile = available_tiles.first().copied().unwrap_or(Tile(0, 0, 0));
@@ -276,9 +276,9 @@ impl<'a> ExpectimaxMCTS<'a> {
             };
         }
 
-        // Fallback: return default result
+        // Fallback: no move found (no legal position)
         MCTSResult {
-            best_position: 0,
+            best_position: NO_LEGAL_MOVE,
             board_tensor: convert_plateau_to_tensor(
                 plateau,
                 &Tile(0, 0, 0),
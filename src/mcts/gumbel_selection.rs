@@ -11,6 +11,7 @@
 //! - Theoretically proven convergence for stochastic games
 //! - Used in MuZero Reanalyze
 
+use crate::utils::random_index::with_rng;
 use rand::{rng, Rng};
 use std::collections::HashMap;
 
@@ -58,12 +59,18 @@ impl GumbelSelector {
             return None;
         }
 
-        let mut rng_instance = rng();
         let mut scored_moves: Vec<(usize, f64)> = Vec::new();
 
-        for (&position, &q_value) in q_values.iter() {
-            // Sample Gumbel noise
-            let gumbel_noise = sample_gumbel(&mut rng_instance);
+        // Position order, so a seeded search draws the same noise per position
+        let mut candidates: Vec<(usize, f64)> = q_values.iter().map(|(&p, &q)| (p, q)).collect();
+        candidates.sort_unstable_by_key(|&(position, _)| position);
+
+        for (position, q_value) in candidates {
+            // Sample Gumbel noise (seeded RNG of `with_seeded_rng` if any)
+            let gumbel_noise = with_rng(|seeded| match seeded {
+                Some(rng) => sample_gumbel(rng),
+                None => sample_gumbel(&mut rng()),
+            });
 
             // Gumbel score = Q(s,a) + temperature * Gumbel
             // Higher temperature = more noise = more exploration
@@ -14,9 +14,104 @@
 //! - The 159.95 pts was likely a statistical outlier or achieved with lost weights
 
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Server-wide cap on the duration of one search (`--max-move-ms`)
+static MAX_MOVE_MS: OnceLock<u64> = OnceLock::new();
+
+/// Cap every timed search of this process to `max_move_ms` milliseconds
+/// (0 = no cap). Can only be set once.
+pub fn set_max_move_ms(max_move_ms: u64) -> Result<(), String> {
+    MAX_MOVE_MS
+        .set(max_move_ms)
+        .map_err(|_| "Max move duration already set".to_string())
+}
+
+/// Smaller of two time budgets where 0 means unlimited
+fn cap_time_budget(budget_ms: u64, cap_ms: u64) -> u64 {
+    match (budget_ms, cap_ms) {
+        (0, cap) => cap,
+        (budget, 0) => budget,
+        (budget, cap) => budget.min(cap),
+    }
+}
+
+/// Turn-by-turn c_puct schedule, built on `c_puct_early` / `c_puct_mid` /
+/// `c_puct_late`
+///
+/// In a TOML config:
+/// ```toml
+/// c_puct_early = 4.5
+/// c_puct_late = 2.5
+///
+/// [c_puct_schedule]
+/// kind = "linear_decay"
+/// decay_start = 3
+/// decay_end = 16
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CPuctSchedule {
+    /// Steps: `c_puct_early` before `mid_start`, `c_puct_late` from
+    /// `late_start` on, `c_puct_mid` in between
+    Phases { mid_start: usize, late_start: usize },
+    /// `c_puct_early` up to `decay_start`, `c_puct_late` from `decay_end` on,
+    /// linear in between
+    LinearDecay {
+        decay_start: usize,
+        decay_end: usize,
+    },
+    /// Explicit value per turn; turns past the end keep the last value
+    PerTurn { values: Vec<f64> },
+}
+
+impl Default for CPuctSchedule {
+    fn default() -> Self {
+        CPuctSchedule::Phases {
+            mid_start: 5,
+            late_start: 16,
+        }
+    }
+}
+
+/// Settings of the Gumbel MCTS variant
+/// (`mcts_find_best_position_for_tile_gumbel`); its rollouts per simulation
+/// follow the shared `rollout_*` buckets
+///
+/// In a TOML config:
+/// ```toml
+/// [gumbel]
+/// top_k = 4
+/// pure_prior_rollouts = 8
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GumbelConfig {
+    /// Candidates kept by Gumbel-Top-k selection
+    /// Default: 5
+    pub top_k: usize,
+
+    /// Rollouts per legal move estimating its value when no network is loaded
+    /// Default: 6
+    pub pure_prior_rollouts: usize,
+}
+
+impl Default for GumbelConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            pure_prior_rollouts: 6,
+        }
+    }
+}
 
 /// MCTS hyperparameters configuration
+///
+/// Missing fields take their default value when deserialized, so config files
+/// only need to list the parameters they override.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MCTSHyperparameters {
     // ========== c_puct (Exploration Constant) ==========
     /// c_puct for early game (turns 0-4)
@@ -33,14 +128,41 @@ pub struct MCTSHyperparameters {
     /// Default: 3.0
     pub c_puct_late: f64,
 
-    /// Variance multiplier when uncertainty is high (variance > 0.5)
+    /// How c_puct moves from early to late game
+    /// Default: phases (turns 0-4 / 5-15 / 16+)
+    pub c_puct_schedule: CPuctSchedule,
+
+    // ========== Dynamic c_puct (ValueNet variance) ==========
+    // Step mapping of the variance of the value estimates to a c_puct multiplier:
+    //   variance <= low          -> variance_mult_low
+    //   low  < variance <= mid   -> 1.0
+    //   mid  < variance <= high  -> variance_mult_mid
+    //   high < variance          -> variance_mult_high
+    /// Variance multiplier when uncertainty is high (variance > variance_threshold_high)
     /// Default: 1.3
     pub variance_mult_high: f64,
 
-    /// Variance multiplier when uncertainty is very low (variance < 0.05)
+    /// Variance multiplier for moderate uncertainty
+    /// (variance_threshold_mid < variance <= variance_threshold_high)
+    /// Default: 1.1
+    pub variance_mult_mid: f64,
+
+    /// Variance multiplier when uncertainty is very low (variance <= variance_threshold_low)
     /// Default: 0.85
     pub variance_mult_low: f64,
 
+    /// Variance up to which the search is considered confident
+    /// Default: 0.05
+    pub variance_threshold_low: f64,
+
+    /// Variance above which `variance_mult_mid` applies
+    /// Default: 0.2
+    pub variance_threshold_mid: f64,
+
+    /// Variance above which `variance_mult_high` applies
+    /// Default: 0.5
+    pub variance_threshold_high: f64,
+
     // ========== Dynamic Pruning ==========
     /// Pruning ratio for early game (turns 0-4)
     /// 0.05 = keep top 95% of moves
@@ -78,6 +200,12 @@ pub struct MCTSHyperparameters {
     /// Default: 9
     pub rollout_weak: usize,
 
+    /// Moves played per rollout before the leaf is scored by a heuristic
+    /// estimate instead of simulating to the end (truncated rollout)
+    /// 0 = always play to completion
+    /// Default: 0
+    pub rollout_depth: usize,
+
     // ========== Evaluation Weights (Pattern Rollouts V2) ==========
     /// Weight for CNN value network prediction
     /// Default: 0.6
@@ -129,6 +257,57 @@ pub struct MCTSHyperparameters {
     /// Default: 15
     pub temp_decay_end: usize,
 
+    // ========== Contextual Boost Decay ==========
+    /// Scale applied to the contextual boost from `contextual_decay_end` on
+    /// (1.0 before `contextual_decay_start`, linear in between)
+    /// Lower values = late-game decisions rely more on value/rollouts
+    /// Default: 1.0 (no decay)
+    pub contextual_scale_late: f64,
+
+    /// Turn at which the contextual boost starts decreasing
+    /// Default: 6
+    pub contextual_decay_start: usize,
+
+    /// Turn at which the contextual boost reaches `contextual_scale_late`
+    /// Default: 15
+    pub contextual_decay_end: usize,
+
+    // ========== Wasted Tile Penalty ==========
+    /// Heuristic malus per tile value placed on a line that can no longer
+    /// score (a 9 on a spoiled line costs 9 × this weight)
+    /// Default: 0.0 (disabled until benchmarked)
+    pub wasted_tile_penalty: f64,
+
+    // ========== Gumbel MCTS ==========
+    /// Top-k and prior rollouts of the Gumbel variant
+    pub gumbel: GumbelConfig,
+
+    // ========== Time Budget ==========
+    /// Wall-clock budget of one search in milliseconds; when it runs out the
+    /// search stops and returns its best move so far
+    /// 0 = run all simulations
+    /// Default: 0
+    pub time_budget_ms: u64,
+
+    // ========== Dirichlet Noise ==========
+    /// Concentration of the Dirichlet noise on the root priors (self-play)
+    /// Lower = noise concentrated on fewer moves
+    /// Default: 0.3
+    pub dirichlet_alpha: f64,
+
+    /// Share ε of the noise in the root priors:
+    /// prior = (1-ε)·policy + ε·noise
+    /// 0 = no noise
+    /// Default: 0.5
+    pub dirichlet_epsilon: f64,
+
+    // ========== Parallel Search ==========
+    /// Virtual loss of the multi-threaded search: every simulation still
+    /// running on a position counts as a visit losing this much (value
+    /// scale [-1, 1]), so concurrent workers spread over the moves
+    /// Default: 1.0
+    pub virtual_loss: f64,
+
     // ========== RAVE (Rapid Action Value Estimation) ==========
     /// RAVE blending constant k for adaptive β calculation
     /// Formula: β = sqrt(k / (3*N + k)) where N = visit count
@@ -136,6 +315,13 @@ pub struct MCTSHyperparameters {
     /// Lower values = faster convergence to pure MCTS values
     /// Default: 10 (conservative, avoids early RAVE dominance)
     pub rave_k: f64,
+
+    /// Bias b of the RAVE schedule used by the CoW search:
+    /// β = ñ / (n + ñ + b·n·ñ), with n the visits of a move and ñ its
+    /// All-Moves-As-First visits; the evaluation becomes (1-β)·eval + β·RAVE
+    /// None = RAVE off
+    /// Default: None
+    pub rave_beta: Option<f64>,
 }
 
 impl Default for MCTSHyperparameters {
@@ -145,8 +331,13 @@ impl Default for MCTSHyperparameters {
             c_puct_early: 4.2,
             c_puct_mid: 3.8,
             c_puct_late: 3.0,
+            c_puct_schedule: CPuctSchedule::default(),
             variance_mult_high: 1.3,
+            variance_mult_mid: 1.1,
             variance_mult_low: 0.85,
+            variance_threshold_low: 0.05,
+            variance_threshold_mid: 0.2,
+            variance_threshold_high: 0.5,
 
             // Pruning
             prune_early: 0.05,
@@ -159,6 +350,7 @@ impl Default for MCTSHyperparameters {
             rollout_medium: 5,
             rollout_default: 7,
             rollout_weak: 9,
+            rollout_depth: 0,
 
             // Evaluation weights - Rollout-dominant (CNN has geometry issues)
             // Q-net handles pruning, rollouts handle evaluation
@@ -179,31 +371,136 @@ impl Default for MCTSHyperparameters {
             temp_decay_start: 7, // was 5 → delayed start
             temp_decay_end: 13,  // was 15 → earlier finish
 
+            // Contextual boost decay (disabled until benchmarked)
+            contextual_scale_late: 1.0,
+            contextual_decay_start: 6,
+            contextual_decay_end: 15,
+
+            // Wasted tile penalty (disabled until benchmarked)
+            wasted_tile_penalty: 0.0,
+
+            // Gumbel MCTS (values formerly hardcoded in mcts_core_gumbel)
+            gumbel: GumbelConfig::default(),
+
+            // Time budget (disabled: fixed simulation count)
+            time_budget_ms: 0,
+
+            // Dirichlet noise (ε was hardcoded in the UCT search)
+            dirichlet_alpha: 0.3,
+            dirichlet_epsilon: 0.5,
+
+            // Parallel search
+            virtual_loss: 1.0,
+
             // RAVE (Sprint 3)
             rave_k: 10.0, // Conservative constant to avoid early RAVE dominance
+            rave_beta: None,
         }
     }
 }
 
 impl MCTSHyperparameters {
-    /// Get c_puct value based on current turn
+    /// Parse a TOML config (missing fields keep their default) and validate it
+    pub fn from_toml_str(config: &str) -> Result<Self, String> {
+        let params: Self =
+            toml::from_str(config).map_err(|e| format!("Invalid MCTS config: {}", e))?;
+        params.validate_c_puct_schedule()?;
+        params.validate_variance_mapping()?;
+        params.validate_gumbel()?;
+        Ok(params)
+    }
+
+    /// Load and validate a TOML config file
+    pub fn from_toml_file(path: &Path) -> Result<Self, String> {
+        let config = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read MCTS config {}: {}", path.display(), e))?;
+        Self::from_toml_str(&config)
+    }
+
+    /// `time_budget_ms` capped by [`set_max_move_ms`] (0 = run all simulations)
+    pub fn effective_time_budget_ms(&self) -> u64 {
+        cap_time_budget(self.time_budget_ms, MAX_MOVE_MS.get().copied().unwrap_or(0))
+    }
+
+    /// Get c_puct value based on current turn, following `c_puct_schedule`
     pub fn get_c_puct(&self, current_turn: usize) -> f64 {
-        if current_turn < 5 {
-            self.c_puct_early
-        } else if current_turn > 15 {
-            self.c_puct_late
-        } else {
-            self.c_puct_mid
+        match &self.c_puct_schedule {
+            CPuctSchedule::Phases {
+                mid_start,
+                late_start,
+            } => {
+                if current_turn < *mid_start {
+                    self.c_puct_early
+                } else if current_turn >= *late_start {
+                    self.c_puct_late
+                } else {
+                    self.c_puct_mid
+                }
+            }
+            CPuctSchedule::LinearDecay {
+                decay_start,
+                decay_end,
+            } => {
+                if current_turn <= *decay_start {
+                    self.c_puct_early
+                } else if current_turn >= *decay_end {
+                    self.c_puct_late
+                } else {
+                    // Linear interpolation
+                    let progress =
+                        (current_turn - decay_start) as f64 / (decay_end - decay_start) as f64;
+                    self.c_puct_early + progress * (self.c_puct_late - self.c_puct_early)
+                }
+            }
+            CPuctSchedule::PerTurn { values } => values
+                .get(current_turn)
+                .or(values.last())
+                .copied()
+                .unwrap_or(self.c_puct_mid),
+        }
+    }
+
+    /// Check that every c_puct the schedule can return is > 0 and that its
+    /// turn boundaries are ordered
+    pub fn validate_c_puct_schedule(&self) -> Result<(), String> {
+        let positive = |c: &f64| c.is_finite() && *c > 0.0;
+        let phase_values = [self.c_puct_early, self.c_puct_mid, self.c_puct_late];
+        if !phase_values.iter().all(positive) {
+            return Err(format!("c_puct values must be > 0, got {:?}", phase_values));
+        }
+        match &self.c_puct_schedule {
+            CPuctSchedule::Phases {
+                mid_start,
+                late_start,
+            } if mid_start > late_start => Err(format!(
+                "c_puct phases must satisfy mid_start <= late_start, got {} > {}",
+                mid_start, late_start
+            )),
+            CPuctSchedule::LinearDecay {
+                decay_start,
+                decay_end,
+            } if decay_start >= decay_end => Err(format!(
+                "c_puct decay must satisfy decay_start < decay_end, got {} >= {}",
+                decay_start, decay_end
+            )),
+            CPuctSchedule::PerTurn { values } if values.is_empty() => {
+                Err("c_puct per-turn schedule must list at least one value".to_string())
+            }
+            CPuctSchedule::PerTurn { values } if !values.iter().all(positive) => Err(format!(
+                "c_puct per-turn values must be > 0, got {:?}",
+                values
+            )),
+            _ => Ok(()),
         }
     }
 
     /// Get variance multiplier based on variance level
     pub fn get_variance_multiplier(&self, variance: f64) -> f64 {
-        if variance > 0.5 {
+        if variance > self.variance_threshold_high {
             self.variance_mult_high
-        } else if variance > 0.2 {
-            1.1
-        } else if variance > 0.05 {
+        } else if variance > self.variance_threshold_mid {
+            self.variance_mult_mid
+        } else if variance > self.variance_threshold_low {
             1.0
         } else {
             self.variance_mult_low
@@ -262,6 +559,21 @@ impl MCTSHyperparameters {
         }
     }
 
+    /// Get the contextual boost scale for the current turn
+    /// Heuristics shape the early game; concrete scoring dominates late
+    pub fn get_contextual_scale(&self, current_turn: usize) -> f64 {
+        if current_turn < self.contextual_decay_start {
+            1.0
+        } else if current_turn >= self.contextual_decay_end {
+            self.contextual_scale_late
+        } else {
+            // Linear interpolation
+            let progress = (current_turn - self.contextual_decay_start) as f64
+                / (self.contextual_decay_end - self.contextual_decay_start) as f64;
+            1.0 + progress * (self.contextual_scale_late - 1.0)
+        }
+    }
+
     /// Calculate entropy-based adaptive CNN weight
     ///
     /// When GNN policy has low entropy (confident), use higher CNN weight
@@ -391,6 +703,61 @@ impl MCTSHyperparameters {
         }
     }
 
+    /// Check that the variance mapping is monotonic: thresholds in increasing
+    /// order and multipliers non-decreasing with variance
+    pub fn validate_variance_mapping(&self) -> Result<(), String> {
+        let thresholds = [
+            self.variance_threshold_low,
+            self.variance_threshold_mid,
+            self.variance_threshold_high,
+        ];
+        let multipliers = [
+            self.variance_mult_low,
+            1.0,
+            self.variance_mult_mid,
+            self.variance_mult_high,
+        ];
+
+        if thresholds.iter().any(|t| *t < 0.0) || thresholds.windows(2).any(|w| w[0] > w[1]) {
+            Err(format!(
+                "Variance thresholds must be >= 0 and increasing, got {:?}",
+                thresholds
+            ))
+        } else if multipliers.iter().any(|m| *m <= 0.0)
+            || multipliers.windows(2).any(|w| w[0] > w[1])
+        {
+            Err(format!(
+                "Variance multipliers must be > 0 and satisfy low <= 1.0 <= mid <= high, got {:?}",
+                multipliers
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Gumbel search needs at least one candidate and one rollout per estimate
+    pub fn validate_gumbel(&self) -> Result<(), String> {
+        if self.gumbel.top_k == 0 {
+            return Err("gumbel.top_k must be at least 1".to_string());
+        }
+        if self.gumbel.pure_prior_rollouts == 0 {
+            return Err("gumbel.pure_prior_rollouts must be at least 1".to_string());
+        }
+        let buckets = [
+            self.rollout_strong,
+            self.rollout_medium,
+            self.rollout_default,
+            self.rollout_weak,
+        ];
+        if buckets.contains(&0) {
+            return Err(format!(
+                "Gumbel rollout counts must be at least 1, got {:?}",
+                buckets
+            ));
+        }
+        Ok(())
+    }
+
     /// Create a configuration string for logging
     #[allow(dead_code)] // Used in binaries, not in lib
     pub fn to_config_string(&self) -> String {
@@ -418,6 +785,9 @@ impl MCTSHyperparameters {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::game::tile::Tile;
+    use crate::strategy::contextual_boost::calculate_contextual_boost_entropy;
 
     #[test]
     fn test_default_weights_sum_to_one() {
@@ -425,6 +795,15 @@ mod tests {
         assert!(params.validate_weights().is_ok());
     }
 
+    #[test]
+    fn test_time_budget_cap_ignores_unlimited_side() {
+        assert_eq!(cap_time_budget(0, 0), 0);
+        assert_eq!(cap_time_budget(250, 0), 250);
+        assert_eq!(cap_time_budget(0, 100), 100);
+        assert_eq!(cap_time_budget(250, 100), 100);
+        assert_eq!(cap_time_budget(50, 100), 50);
+    }
+
     #[test]
     fn test_get_c_puct_by_turn() {
         let params = MCTSHyperparameters::default();
@@ -435,6 +814,49 @@ mod tests {
         assert_eq!(params.get_c_puct(16), 3.0); // Late
     }
 
+    #[test]
+    fn test_c_puct_schedule_from_toml() {
+        let decay = MCTSHyperparameters::from_toml_str(
+            r#"
+            c_puct_early = 4.6
+            c_puct_late = 2.6
+
+            [c_puct_schedule]
+            kind = "linear_decay"
+            decay_start = 4
+            decay_end = 14
+            "#,
+        )
+        .unwrap();
+        assert!(decay.get_c_puct(0) > decay.get_c_puct(18));
+        // Boundaries, then halfway through the decay
+        assert_eq!(decay.get_c_puct(4), 4.6);
+        assert_eq!(decay.get_c_puct(14), 2.6);
+        assert!((decay.get_c_puct(9) - 3.6).abs() < 1e-9);
+        assert_eq!(decay.c_puct_mid, 3.8);
+
+        let per_turn = MCTSHyperparameters::from_toml_str(
+            r#"
+            [c_puct_schedule]
+            kind = "per_turn"
+            values = [5.0, 4.0, 3.0]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(per_turn.get_c_puct(1), 4.0);
+        assert_eq!(per_turn.get_c_puct(18), 3.0);
+
+        let invalid = [
+            "[c_puct_schedule]\nkind = \"linear_decay\"\ndecay_start = 10\ndecay_end = 5",
+            "[c_puct_schedule]\nkind = \"phases\"\nmid_start = 12\nlate_start = 8",
+            "[c_puct_schedule]\nkind = \"per_turn\"\nvalues = []",
+            "c_puct_late = -1.0",
+        ];
+        for config in invalid {
+            assert!(MCTSHyperparameters::from_toml_str(config).is_err());
+        }
+    }
+
     #[test]
     fn test_get_rollout_count() {
         let params = MCTSHyperparameters::default();
@@ -444,6 +866,25 @@ mod tests {
         assert_eq!(params.get_rollout_count(-0.5), 9); // Weak
     }
 
+    #[test]
+    fn test_gumbel_config_from_toml() {
+        let params = MCTSHyperparameters::from_toml_str(
+            r#"
+            rollout_weak = 12
+
+            [gumbel]
+            top_k = 3
+            "#,
+        )
+        .unwrap();
+        assert_eq!(params.gumbel.top_k, 3);
+        assert_eq!(params.gumbel.pure_prior_rollouts, 6);
+        assert_eq!(params.get_rollout_count(-0.9), 12);
+
+        let err = MCTSHyperparameters::from_toml_str("[gumbel]\ntop_k = 0").unwrap_err();
+        assert!(err.contains("top_k"), "{}", err);
+    }
+
     #[test]
     fn test_invalid_weights() {
         let mut params = MCTSHyperparameters::default();
@@ -459,4 +900,101 @@ mod tests {
         // CNN disabled for debugging: 0.10,0.80,0.05,0.05
         assert!(config.contains("weights[0.10,0.80,0.05,0.05]"));
     }
+
+    #[test]
+    fn test_variance_multiplier_monotonic_within_configured_bounds() {
+        let default = MCTSHyperparameters::default();
+        let tuned = MCTSHyperparameters {
+            variance_mult_low: 0.6,
+            variance_mult_mid: 1.4,
+            variance_mult_high: 2.0,
+            variance_threshold_low: 0.1,
+            variance_threshold_mid: 0.3,
+            variance_threshold_high: 0.8,
+            ..Default::default()
+        };
+
+        for params in [&default, &tuned] {
+            assert!(params.validate_variance_mapping().is_ok());
+            let sweep: Vec<f64> = (0..=200)
+                .map(|i| params.get_variance_multiplier(i as f64 * 0.01))
+                .collect();
+            assert!(sweep.windows(2).all(|w| w[0] <= w[1]));
+            assert!(sweep
+                .iter()
+                .all(|m| (params.variance_mult_low..=params.variance_mult_high).contains(m)));
+
+            // Extremes: no disagreement at all, and values far apart
+            assert_eq!(
+                params.get_variance_multiplier(0.0),
+                params.variance_mult_low
+            );
+            assert_eq!(
+                params.get_variance_multiplier(100.0),
+                params.variance_mult_high
+            );
+        }
+
+        // Defaults keep the historical 0.85 / 1.0 / 1.1 / 1.3 steps
+        assert_eq!(default.get_variance_multiplier(0.1), 1.0);
+        assert_eq!(default.get_variance_multiplier(0.3), 1.1);
+        assert_eq!(tuned.get_variance_multiplier(0.5), 1.4);
+    }
+
+    #[test]
+    fn test_invalid_variance_mapping() {
+        let inverted = MCTSHyperparameters {
+            variance_threshold_mid: 0.6,
+            ..Default::default()
+        };
+        assert!(inverted.validate_variance_mapping().is_err());
+
+        let decreasing = MCTSHyperparameters {
+            variance_mult_mid: 1.5,
+            ..Default::default()
+        };
+        assert!(decreasing.validate_variance_mapping().is_err());
+    }
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let params: MCTSHyperparameters =
+            serde_json::from_str(r#"{"variance_mult_high": 1.6, "variance_threshold_high": 0.4}"#)
+                .unwrap();
+        assert_eq!(params.variance_mult_high, 1.6);
+        assert_eq!(params.get_variance_multiplier(0.45), 1.6);
+        assert_eq!(params.variance_mult_low, 0.85);
+        assert_eq!(params.c_puct_early, 4.2);
+    }
+
+    #[test]
+    fn test_contextual_scale_defaults_to_no_decay() {
+        let params = MCTSHyperparameters::default();
+        for turn in 0..19 {
+            assert_eq!(params.get_contextual_scale(turn), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_late_turn_contextual_boost_is_lower() {
+        let params = MCTSHyperparameters {
+            contextual_scale_late: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(params.get_contextual_scale(5), 1.0);
+        assert_eq!(params.get_contextual_scale(18), 0.25);
+
+        // Same board shape, early vs late turn
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(5, 6, 4);
+        plateau.tiles[1] = Tile(5, 2, 8);
+        let tile = Tile(5, 7, 3);
+        let boost = |turn: usize| {
+            params.weight_contextual
+                * params.get_contextual_scale(turn)
+                * calculate_contextual_boost_entropy(&plateau, 2, &tile, turn, 1.0)
+        };
+        assert!(boost(2).abs() > 0.0);
+        assert!(boost(16).abs() < boost(2).abs());
+    }
 }
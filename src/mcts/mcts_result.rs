@@ -1,6 +1,10 @@
 use crate::game::plateau::Plateau;
 use tch::Tensor;
 
+/// `best_position` of a search on a board without any legal move. Not a
+/// cell index: placing at it panics instead of silently overwriting cell 0.
+pub const NO_LEGAL_MOVE: usize = usize::MAX;
+
 #[derive(Debug)]
 pub struct MCTSResult {
     pub board_tensor: Tensor,
@@ -36,3 +40,10 @@ impl Clone for MCTSResult {
         }
     }
 }
+
+impl MCTSResult {
+    /// The board had no legal move, `best_position` is `NO_LEGAL_MOVE`
+    pub fn is_terminal(&self) -> bool {
+        self.best_position == NO_LEGAL_MOVE
+    }
+}
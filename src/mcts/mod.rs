@@ -1,4 +1,6 @@
 pub mod algorithm;
+pub mod demo_game;
+pub mod dirichlet;
 pub mod expectimax_algorithm;
 pub mod gumbel_selection;
 pub mod hyperparameters;
@@ -335,6 +335,105 @@ impl MCTSNode {
     }
 }
 
+/// Visits and summed final scores of one move.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EdgeStats {
+    pub visits: usize,
+    pub total_score: f64,
+}
+
+/// Search statistics retained across the turns of a game.
+///
+/// The tree is rooted at the board of the next search. Besides the root moves,
+/// a search records its lookahead: the rollout scores of every reply (next
+/// tile, next position) after each root move. Once a move is committed,
+/// [`advance`](MctsTree::advance) re-roots the tree at that move so the next
+/// search starts from the statistics of the replies to the tile actually drawn.
+#[derive(Debug, Clone, Default)]
+pub struct MctsTree {
+    /// Board the statistics belong to (None = empty tree)
+    plateau: Option<Plateau>,
+    /// Root moves: (tile, position) -> statistics
+    children: HashMap<(Tile, usize), EdgeStats>,
+    /// Replies: (position, next tile, next position) -> statistics
+    replies: HashMap<(usize, Tile, usize), EdgeStats>,
+}
+
+impl MctsTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Statistics kept for placing `tile` on `plateau`, per position; empty
+    /// if the tree is rooted at another board.
+    pub fn root_stats(&self, plateau: &Plateau, tile: Tile) -> HashMap<usize, EdgeStats> {
+        if self.plateau.as_ref() != Some(plateau) {
+            return HashMap::new();
+        }
+        self.children
+            .iter()
+            .filter(|((t, _), _)| *t == tile)
+            .map(|(&(_, position), &stats)| (position, stats))
+            .collect()
+    }
+
+    /// Start a search from `plateau`: replies of an earlier search are
+    /// dropped, and so is everything if the tree was rooted elsewhere.
+    pub fn start_search(&mut self, plateau: &Plateau) {
+        if self.plateau.as_ref() != Some(plateau) {
+            self.children.clear();
+            self.plateau = Some(plateau.clone());
+        }
+        self.replies.clear();
+    }
+
+    /// Store the root statistics of `tile` at `position` after a search.
+    pub fn set_root_stats(&mut self, tile: Tile, position: usize, stats: EdgeStats) {
+        self.children.insert((tile, position), stats);
+    }
+
+    /// Record one rollout scoring `score` after `position`, then `next_tile`
+    /// at `next_position`.
+    pub fn record_reply(
+        &mut self,
+        position: usize,
+        next_tile: Tile,
+        next_position: usize,
+        score: f64,
+    ) {
+        let stats = self
+            .replies
+            .entry((position, next_tile, next_position))
+            .or_default();
+        stats.visits += 1;
+        stats.total_score += score;
+    }
+
+    /// Re-root the tree once `tile` has been played at `position`.
+    pub fn advance(&mut self, position: usize, tile: Tile) {
+        let Some(mut plateau) = self.plateau.take() else {
+            return;
+        };
+        plateau.tiles[position] = tile;
+        self.children = self
+            .replies
+            .drain()
+            .filter(|((p, _, _), _)| *p == position)
+            .map(|((_, next_tile, next_position), stats)| ((next_tile, next_position), stats))
+            .collect();
+        self.plateau = Some(plateau);
+    }
+
+    /// Total root visits of `tile` on the current board.
+    pub fn visits(&self, tile: Tile) -> usize {
+        self.children
+            .iter()
+            .filter(|((t, _), _)| *t == tile)
+            .map(|(_, stats)| stats.visits)
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,6 +528,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tree_advance_keeps_the_replies_of_the_played_move() {
+        let plateau = create_plateau_empty();
+        let (tile, next_tile) = (Tile(1, 5, 9), Tile(2, 6, 7));
+        let mut tree = MctsTree::new();
+        tree.start_search(&plateau);
+        tree.set_root_stats(
+            tile,
+            0,
+            EdgeStats {
+                visits: 3,
+                total_score: 300.0,
+            },
+        );
+        tree.record_reply(0, next_tile, 1, 100.0);
+        tree.record_reply(0, next_tile, 1, 120.0);
+        tree.record_reply(0, Tile(3, 4, 8), 2, 90.0);
+        tree.record_reply(5, next_tile, 1, 80.0);
+
+        tree.advance(0, tile);
+        let mut played = plateau.clone();
+        played.tiles[0] = tile;
+        let stats = tree.root_stats(&played, next_tile);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(
+            stats[&1],
+            EdgeStats {
+                visits: 2,
+                total_score: 220.0
+            }
+        );
+        assert_eq!(tree.visits(next_tile), 2);
+
+        // Another board (new game, other move): nothing is reused
+        assert!(tree.root_stats(&plateau, next_tile).is_empty());
+        tree.start_search(&plateau);
+        assert_eq!(tree.visits(next_tile), 0);
+    }
+
     #[test]
     fn test_expand_one_child() {
         let plateau = create_plateau_empty();
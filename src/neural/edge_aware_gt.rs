@@ -14,31 +14,12 @@
 //! from data via positional embeddings. Edge-Aware GT provides this as a
 //! strong prior, freeing capacity for learning finer-grained patterns.
 
+use crate::game::board_geometry::LINES as LINE_DEFS;
 use tch::{nn, Kind, Tensor};
 
 const NODE_COUNT: i64 = 19;
 const NUM_EDGE_FEATURES: i64 = 7; // 3 share_dir + 3 line_len + 1 distance
 
-/// Line definitions: (positions, direction)
-/// Duplicated here to avoid cross-module dependency
-const LINE_DEFS: [(&[usize], usize); 15] = [
-    (&[0, 1, 2], 0),
-    (&[3, 4, 5, 6], 0),
-    (&[7, 8, 9, 10, 11], 0),
-    (&[12, 13, 14, 15], 0),
-    (&[16, 17, 18], 0),
-    (&[0, 3, 7], 1),
-    (&[1, 4, 8, 12], 1),
-    (&[2, 5, 9, 13, 16], 1),
-    (&[6, 10, 14, 17], 1),
-    (&[11, 15, 18], 1),
-    (&[7, 12, 16], 2),
-    (&[3, 8, 13, 17], 2),
-    (&[0, 4, 9, 14, 18], 2),
-    (&[1, 5, 10, 15], 2),
-    (&[2, 6, 11], 2),
-];
-
 /// Hex adjacency edges (undirected)
 const HEX_EDGES: &[(usize, usize)] = &[
     (0, 1), (1, 2),
@@ -319,13 +319,84 @@ impl GraphTransformerPolicyNet {
     }
 }
 
+/// Score buckets of a distributional (C51-style) value head
+///
+/// The buckets evenly cover `[min, max]` in normalized score units; targets
+/// outside the range are clamped to its ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueSupport {
+    pub min: f64,
+    pub max: f64,
+    pub buckets: i64,
+}
+
+impl Default for ValueSupport {
+    /// ±4 std, i.e. 0..300 pts with the default 140/40 normalization
+    fn default() -> Self {
+        Self {
+            min: -4.0,
+            max: 4.0,
+            buckets: 51,
+        }
+    }
+}
+
+impl ValueSupport {
+    pub fn bucket_width(&self) -> f64 {
+        (self.max - self.min) / (self.buckets - 1) as f64
+    }
+
+    /// Bucket centers: [buckets]
+    pub fn centers(&self, device: tch::Device) -> Tensor {
+        Tensor::linspace(self.min, self.max, self.buckets, (Kind::Float, device))
+    }
+
+    /// Target distributions for scalar targets [batch, 1] -> [batch, buckets]
+    ///
+    /// Each target is split between its two neighbouring buckets so that the
+    /// distribution's mean is exactly the (clamped) target.
+    pub fn project(&self, targets: &Tensor) -> Tensor {
+        let pos = (targets.to_kind(Kind::Float).clamp(self.min, self.max) - self.min)
+            / self.bucket_width();
+        let lower = pos.floor();
+        let upper_weight = &pos - &lower;
+        let lower_idx = lower.to_kind(Kind::Int64).clamp(0, self.buckets - 1);
+        let upper_idx = (&lower_idx + 1).clamp_max(self.buckets - 1);
+
+        Tensor::zeros(
+            [targets.size()[0], self.buckets],
+            (Kind::Float, targets.device()),
+        )
+        .scatter_add(1, &lower_idx, &(1.0 - &upper_weight))
+        .scatter_add(1, &upper_idx, &upper_weight)
+    }
+
+    /// Mean of the distributions given by `logits` [batch, buckets] -> [batch, 1]
+    pub fn mean(&self, logits: &Tensor) -> Tensor {
+        logits
+            .softmax(-1, Kind::Float)
+            .matmul(&self.centers(logits.device()).unsqueeze(1))
+    }
+
+    /// Cross-entropy of `logits` against the projected scalar `targets`
+    pub fn loss(&self, logits: &Tensor, targets: &Tensor) -> Tensor {
+        let target_dist = self.project(targets);
+        -(target_dist * logits.log_softmax(-1, Kind::Float))
+            .sum_dim_intlist(-1, false, Kind::Float)
+            .mean(Kind::Float)
+    }
+}
+
 /// Graph Transformer Value Network
 ///
 /// Shares the same backbone as GraphTransformerPolicyNet but uses mean pooling
 /// over nodes followed by an MLP to produce a single scalar value in [-1, 1].
+/// Built with [`GraphTransformerValueNet::new_distributional`], the head
+/// outputs logits over score buckets and `forward` returns their mean.
 pub struct GraphTransformerValueNet {
     transformer: GraphTransformer,
     value_head: nn::Sequential,
+    support: Option<ValueSupport>,
 }
 
 impl GraphTransformerValueNet {
@@ -336,6 +407,41 @@ impl GraphTransformerValueNet {
         num_layers: usize,
         num_heads: i64,
         dropout: f64,
+    ) -> Self {
+        Self::with_outputs(
+            vs, input_dim, embed_dim, num_layers, num_heads, dropout, None,
+        )
+    }
+
+    /// Value net predicting a distribution over the buckets of `support`
+    pub fn new_distributional(
+        vs: &nn::VarStore,
+        input_dim: i64,
+        embed_dim: i64,
+        num_layers: usize,
+        num_heads: i64,
+        dropout: f64,
+        support: ValueSupport,
+    ) -> Self {
+        Self::with_outputs(
+            vs,
+            input_dim,
+            embed_dim,
+            num_layers,
+            num_heads,
+            dropout,
+            Some(support),
+        )
+    }
+
+    fn with_outputs(
+        vs: &nn::VarStore,
+        input_dim: i64,
+        embed_dim: i64,
+        num_layers: usize,
+        num_heads: i64,
+        dropout: f64,
+        support: Option<ValueSupport>,
     ) -> Self {
         let ff_dim = embed_dim * 4;
 
@@ -353,32 +459,69 @@ impl GraphTransformerValueNet {
         let value_head = nn::seq()
             .add(nn::linear(&p / "value_fc1", embed_dim, 64, Default::default()))
             .add_fn(|x| x.relu())
-            .add(nn::linear(&p / "value_fc2", 64, 1, Default::default()));
+            .add(nn::linear(
+                &p / "value_fc2",
+                64,
+                support.map_or(1, |s| s.buckets),
+                Default::default(),
+            ));
 
         Self {
             transformer,
             value_head,
+            support,
         }
     }
 
-    /// Forward pass
-    /// node_features: [batch, 19, input_dim]
-    /// Returns: [batch, 1] unbounded normalized score
-    pub fn forward(&self, node_features: &Tensor, train: bool) -> Tensor {
+    /// Buckets of the distributional head (`None` for a scalar head)
+    pub fn support(&self) -> Option<ValueSupport> {
+        self.support
+    }
+
+    /// Raw head output: [batch, 1] for a scalar head, [batch, buckets] logits
+    /// for a distributional one
+    pub fn forward_head(&self, node_features: &Tensor, train: bool) -> Tensor {
         // Backbone: [batch, 19, embed_dim]
         let h = self.transformer.forward(node_features, train);
 
         // Mean pool over nodes: [batch, embed_dim]
         let pooled = h.mean_dim(1, false, Kind::Float);
 
-        // Value head: [batch, 1] — linear output, no tanh
+        // Value head — linear output, no tanh
         pooled.apply(&self.value_head)
     }
+
+    /// Forward pass
+    /// node_features: [batch, 19, input_dim]
+    /// Returns: [batch, 1] unbounded normalized score (distribution mean for a
+    /// distributional head)
+    pub fn forward(&self, node_features: &Tensor, train: bool) -> Tensor {
+        self.head_value(&self.forward_head(node_features, train))
+    }
+
+    /// Normalized score [batch, 1] of a `forward_head` output
+    pub fn head_value(&self, head: &Tensor) -> Tensor {
+        match &self.support {
+            Some(support) => support.mean(head),
+            None => head.shallow_clone(),
+        }
+    }
+
+    /// Training loss of a `forward_head` output against normalized targets
+    /// [batch, 1]: MSE for a scalar head, cross-entropy over the buckets for a
+    /// distributional one
+    pub fn head_loss(&self, head: &Tensor, targets: &Tensor) -> Tensor {
+        match &self.support {
+            Some(support) => support.loss(head, targets),
+            None => head.mse_loss(targets, tch::Reduction::Mean),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tch::nn::OptimizerConfig;
 
     #[test]
     fn test_graph_transformer_forward() {
@@ -427,6 +570,49 @@ mod tests {
         assert!(max_val <= 1.0, "Value output should be in [-1, 1], got max abs {}", max_val);
     }
 
+    #[test]
+    fn test_distributional_value_head_recovers_target_mean() {
+        let support = ValueSupport::default();
+        let targets = Tensor::from_slice(&[-1.3f32, 0.0, 0.7, 9.0]).unsqueeze(1);
+        let projected = support.project(&targets);
+        let sums =
+            Vec::<f64>::try_from(&projected.sum_dim_intlist(-1, false, Kind::Float)).unwrap();
+        let means =
+            Vec::<f64>::try_from(&projected.matmul(&support.centers(tch::Device::Cpu))).unwrap();
+        for (sum, (mean, target)) in sums.iter().zip(means.iter().zip([-1.3, 0.0, 0.7, 4.0])) {
+            assert!((sum - 1.0).abs() < 1e-5);
+            // Out-of-range targets clamp to the support
+            assert!(
+                (mean - target).abs() < 1e-5,
+                "mean {} vs target {}",
+                mean,
+                target
+            );
+        }
+
+        tch::manual_seed(3);
+        let vs = nn::VarStore::new(tch::Device::Cpu);
+        let value = GraphTransformerValueNet::new_distributional(&vs, 47, 16, 1, 2, 0.0, support);
+        let mut opt = nn::Adam::default().build(&vs, 0.01).unwrap();
+        let x = Tensor::randn([8, 19, 47], (Kind::Float, tch::Device::Cpu));
+        let target = Tensor::full([8, 1], 0.7, (Kind::Float, tch::Device::Cpu));
+        for _ in 0..150 {
+            let head = value.forward_head(&x, true);
+            opt.backward_step(&value.head_loss(&head, &target));
+        }
+
+        let probs = value.forward_head(&x, false).softmax(-1, Kind::Float);
+        assert_eq!(probs.size(), vec![8, 51]);
+        let max_sum_err = (probs.sum_dim_intlist(-1, false, Kind::Float) - 1.0)
+            .abs()
+            .max();
+        assert!(max_sum_err.double_value(&[]) < 1e-5);
+        let out = value.forward(&x, false);
+        assert_eq!(out.size(), vec![8, 1]);
+        let max_err = (out - 0.7).abs().max().double_value(&[]);
+        assert!(max_err < 0.05, "distribution mean off by {}", max_err);
+    }
+
     #[test]
     fn test_multi_head_attention() {
         let vs = nn::VarStore::new(tch::Device::Cpu);
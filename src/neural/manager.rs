@@ -4,6 +4,7 @@
 //! Handles initialization, loading, and configuration of policy and value networks.
 
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+use crate::TakeItEasyError;
 use std::path::Path;
 use tch::nn::OptimizerConfig;
 use tch::{nn, Device};
@@ -64,7 +65,8 @@ impl NNArchitecture {
 pub struct NeuralConfig {
     /// Input dimensions (channels, height, width)
     pub input_dim: (i64, i64, i64),
-    /// Device to use for computation (CPU/GPU)
+    /// Device to use for computation (CPU/GPU); the networks move their
+    /// inputs there
     pub device: Device,
     /// Model weights directory path
     pub model_path: String,
@@ -80,6 +82,16 @@ pub struct NeuralConfig {
     pub policy_architecture: Option<NNArchitecture>,
     /// Value network architecture (optional, defaults to nn_architecture)
     pub value_architecture: Option<NNArchitecture>,
+    /// tch intra-op threads (None = libtorch default). Limiting them avoids
+    /// oversubscription when many sessions run inference concurrently;
+    /// `scripts/bench_tch_threads.sh` compares the stress-test throughput.
+    pub num_threads: Option<i32>,
+    /// tch inter-op threads (None = libtorch default)
+    pub num_interop_threads: Option<i32>,
+    /// Fail instead of starting with fresh networks when the model directory
+    /// or the policy weights are missing, or when a weights file is corrupt.
+    /// A missing value net file still only disables the value net.
+    pub require_weights: bool,
 }
 
 impl Default for NeuralConfig {
@@ -89,7 +101,7 @@ impl Default for NeuralConfig {
             // Base (17): tile values (3) + empty mask (1) + current tile (3) + turn (1) + bag (9)
             // Line features (30): 15 lines × 2 features (potential + compatibility)
             input_dim: (47, 5, 5),
-            device: Device::Cpu,
+            device: Device::cuda_if_available(),
             model_path: "model_weights".to_string(),
             policy_lr: 1e-3,
             value_lr: 2e-4,
@@ -97,10 +109,35 @@ impl Default for NeuralConfig {
             nn_architecture: NNArchitecture::Cnn,
             policy_architecture: None, // Uses nn_architecture by default
             value_architecture: None,  // Uses nn_architecture by default
+            num_threads: None,
+            num_interop_threads: None,
+            require_weights: false,
         }
     }
 }
 
+impl NeuralConfig {
+    /// Apply the tch thread counts and log the effective values.
+    ///
+    /// Must run once at startup, before any inference: libtorch refuses to
+    /// change the inter-op pool after parallel work has started.
+    pub fn apply_thread_settings(&self) {
+        if let Some(n) = self.num_threads.filter(|&n| n > 0) {
+            tch::set_num_threads(n);
+        }
+        if let Some(n) = self.num_interop_threads.filter(|&n| n > 0) {
+            if n != tch::get_num_interop_threads() {
+                tch::set_num_interop_threads(n);
+            }
+        }
+        log::info!(
+            "🧵 tch threads: intra-op={}, inter-op={}",
+            tch::get_num_threads(),
+            tch::get_num_interop_threads()
+        );
+    }
+}
+
 /// Neural Network Manager that encapsulates all network components
 pub struct NeuralManager {
     config: NeuralConfig,
@@ -108,6 +145,8 @@ pub struct NeuralManager {
     vs_value: nn::VarStore,
     policy_net: PolicyNet,
     value_net: ValueNet,
+    /// Whether the value network weights were loaded from disk
+    value_net_loaded: bool,
     optimizer_policy: nn::Optimizer,
     optimizer_value: nn::Optimizer,
 }
@@ -115,12 +154,16 @@ pub struct NeuralManager {
 #[allow(dead_code)]
 impl NeuralManager {
     /// Create a new neural network manager with default configuration
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, TakeItEasyError> {
         Self::with_config(NeuralConfig::default())
     }
 
     /// Create a new neural network manager with custom configuration
-    pub fn with_config(config: NeuralConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    ///
+    /// Weights that cannot be loaded are logged and replaced by fresh
+    /// networks, unless `config.require_weights` is set: then the error names
+    /// the offending path and the reason.
+    pub fn with_config(config: NeuralConfig) -> Result<Self, TakeItEasyError> {
         // Determine actual architectures for policy and value
         let policy_arch = config.policy_architecture.unwrap_or(config.nn_architecture);
         let value_arch = config.value_architecture.unwrap_or(config.nn_architecture);
@@ -153,6 +196,7 @@ impl NeuralManager {
 
         let policy_net = PolicyNet::new(&vs_policy, policy_input_dim, policy_arch);
         let value_net = ValueNet::new(&vs_value, value_input_dim, value_arch);
+        let mut value_net_loaded = false;
 
         // Load weights if model directory exists
         if Path::new(&config.model_path).exists() {
@@ -170,6 +214,9 @@ impl NeuralManager {
             };
 
             if let Err(e) = policy_net.load_model(&mut vs_policy, &policy_path) {
+                if config.require_weights {
+                    return Err(weights_error("PolicyNet", &policy_path, &e));
+                }
                 log::warn!("⚠️ Failed to load PolicyNet from {}: {:?}", policy_path, e);
             } else {
                 log::info!("✅ PolicyNet ({}) loaded successfully", policy_arch);
@@ -183,10 +230,20 @@ impl NeuralManager {
                 NNArchitecture::GraphTransformer => format!("{}/graph_transformer_value.safetensors", config.model_path),
             };
             if let Err(e) = value_net.load_model(&mut vs_value, &value_path) {
+                // Missing value net: degraded mode; unreadable one: refused
+                if config.require_weights && Path::new(&value_path).exists() {
+                    return Err(weights_error("ValueNet", &value_path, &e));
+                }
                 log::warn!("⚠️ Failed to load ValueNet from {}: {:?}", value_path, e);
             } else {
                 log::info!("✅ ValueNet ({}) loaded successfully", value_arch);
+                value_net_loaded = true;
             }
+        } else if config.require_weights {
+            return Err(TakeItEasyError::Ai(format!(
+                "model directory '{}' not found",
+                config.model_path
+            )));
         } else {
             log::info!(
                 "📁 Model directory {} not found, using fresh networks",
@@ -195,13 +252,18 @@ impl NeuralManager {
         }
 
         // Create optimizers
-        let optimizer_policy = nn::Adam::default().build(&vs_policy, config.policy_lr)?;
+        let optimizer_error =
+            |e: tch::TchError| TakeItEasyError::Ai(format!("cannot create optimizer: {}", e));
+        let optimizer_policy = nn::Adam::default()
+            .build(&vs_policy, config.policy_lr)
+            .map_err(optimizer_error)?;
 
         let optimizer_value = nn::Adam {
             wd: config.value_wd,
             ..Default::default()
         }
-        .build(&vs_value, config.value_lr)?;
+        .build(&vs_value, config.value_lr)
+        .map_err(optimizer_error)?;
 
         log::info!("✅ Neural network manager initialized successfully");
 
@@ -211,6 +273,7 @@ impl NeuralManager {
             vs_value,
             policy_net,
             value_net,
+            value_net_loaded,
             optimizer_policy,
             optimizer_value,
         })
@@ -241,6 +304,12 @@ impl NeuralManager {
         &mut self.value_net
     }
 
+    /// Whether the value network weights were loaded from disk (false means
+    /// the value net is freshly initialized and its estimates are meaningless)
+    pub fn value_net_loaded(&self) -> bool {
+        self.value_net_loaded
+    }
+
     /// Get a reference to the policy VarStore
     pub fn policy_varstore(&self) -> &nn::VarStore {
         &self.vs_policy
@@ -290,6 +359,7 @@ impl NeuralManager {
             vs_value: self.vs_value,
             policy_net: self.policy_net,
             value_net: self.value_net,
+            value_net_loaded: self.value_net_loaded,
             optimizer_policy: self.optimizer_policy,
             optimizer_value: self.optimizer_value,
         }
@@ -337,6 +407,19 @@ impl NeuralManager {
     }
 }
 
+/// Error for weights at `path` that `net` could not load.
+fn weights_error(net: &str, path: &str, error: &tch::TchError) -> TakeItEasyError {
+    let reason = if Path::new(path).exists() {
+        format!("corrupt or incompatible file: {}", error)
+    } else {
+        "file not found".to_string()
+    };
+    TakeItEasyError::Ai(format!(
+        "cannot load {} weights from '{}': {}",
+        net, path, reason
+    ))
+}
+
 /// Components extracted from NeuralManager for ownership transfer
 #[allow(dead_code)]
 pub struct NeuralComponents {
@@ -346,6 +429,7 @@ pub struct NeuralComponents {
     pub vs_value: nn::VarStore,
     pub policy_net: PolicyNet,
     pub value_net: ValueNet,
+    pub value_net_loaded: bool,
     pub optimizer_policy: nn::Optimizer,
     pub optimizer_value: nn::Optimizer,
 }
@@ -392,12 +476,15 @@ mod tests {
     fn test_neural_config_default() {
         let config = NeuralConfig::default();
         assert_eq!(config.input_dim, (47, 5, 5));
-        assert_eq!(config.device, Device::Cpu);
+        assert_eq!(config.device, Device::cuda_if_available());
         assert_eq!(config.model_path, "model_weights");
         assert_eq!(config.policy_lr, 1e-3);
         assert_eq!(config.value_lr, 2e-4);
         assert_eq!(config.value_wd, 1e-6);
         assert_eq!(config.nn_architecture, NNArchitecture::Cnn);
+        assert_eq!(config.num_threads, None);
+        assert_eq!(config.num_interop_threads, None);
+        assert!(!config.require_weights);
     }
 
     #[test]
@@ -412,6 +499,9 @@ mod tests {
             nn_architecture: NNArchitecture::Gnn,
             policy_architecture: None,
             value_architecture: None,
+            num_threads: None,
+            num_interop_threads: None,
+            require_weights: false,
         };
 
         assert_eq!(config.input_dim, (3, 64, 64));
@@ -443,6 +533,9 @@ mod tests {
             nn_architecture: NNArchitecture::Gnn,
             policy_architecture: None,
             value_architecture: None,
+            num_threads: None,
+            num_interop_threads: None,
+            require_weights: false,
         };
 
         let manager = NeuralManager::with_config(config);
@@ -515,4 +608,51 @@ mod tests {
         assert_eq!(components.config.model_path, "model_weights");
         // Components should be properly moved
     }
+
+    #[test]
+    fn test_value_net_not_loaded_without_weights() {
+        let config = NeuralConfig {
+            model_path: "missing_model_dir".to_string(),
+            ..Default::default()
+        };
+        let manager = NeuralManager::with_config(config).unwrap();
+        assert!(!manager.value_net_loaded());
+        assert!(!manager.into_components().value_net_loaded);
+    }
+
+    #[test]
+    fn test_required_weights_report_the_offending_path() {
+        let missing = NeuralConfig {
+            model_path: "missing_model_dir".to_string(),
+            device: Device::Cpu,
+            require_weights: true,
+            ..Default::default()
+        };
+        match NeuralManager::with_config(missing) {
+            Err(TakeItEasyError::Ai(message)) => {
+                assert!(message.contains("'missing_model_dir' not found"));
+            }
+            other => panic!("expected an AI error, got {:?}", other.err()),
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let policy_path = dir.path().join("graph_transformer_policy.safetensors");
+        std::fs::write(&policy_path, b"not a safetensors file").unwrap();
+        let corrupt = NeuralConfig {
+            model_path: dir.path().to_string_lossy().to_string(),
+            device: Device::Cpu,
+            nn_architecture: NNArchitecture::GraphTransformer,
+            require_weights: true,
+            ..Default::default()
+        };
+        match NeuralManager::with_config(corrupt) {
+            Err(TakeItEasyError::Ai(message)) => {
+                assert!(message.contains("PolicyNet"), "{}", message);
+                let path = policy_path.to_string_lossy();
+                assert!(message.contains(&*path), "{}", message);
+                assert!(message.contains("corrupt"), "{}", message);
+            }
+            other => panic!("expected an AI error, got {:?}", other.err()),
+        }
+    }
 }
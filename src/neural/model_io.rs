@@ -2,15 +2,180 @@
 //!
 //! This module provides portable model serialization that works across
 //! different libtorch versions by using the safetensors format instead
-//! of PyTorch's native serialization.
+//! of PyTorch's native serialization. The policy can also be exported as a
+//! traced TorchScript module or an ONNX graph, for runtimes other than Rust.
+//!
+//! Graph Transformer weights are saved with their architecture in a
+//! `<path>.meta.json` sidecar ([`save_varstore_with_meta`]): [`load_policy`]
+//! and [`load_value`] size the network from it instead of trusting the
+//! dimensions passed on the command line.
 
 use safetensors::tensor::{Dtype, SafeTensors, TensorView};
 use safetensors::serialize_to_file;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
-use tch::{nn, Kind, Tensor};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tch::{nn, CModule, Device, Kind, Tensor};
+
+use crate::neural::graph_transformer::{GraphTransformerPolicyNet, GraphTransformerValueNet};
+
+/// `arch` of a Graph Transformer policy in [`ModelMeta`]
+pub const GT_POLICY_ARCH: &str = "graph_transformer_policy";
+/// `arch` of a Graph Transformer value net in [`ModelMeta`]
+pub const GT_VALUE_ARCH: &str = "graph_transformer_value";
+
+/// Architecture a model was saved with, stored in `<path>.meta.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelMeta {
+    pub embed_dim: i64,
+    pub num_layers: usize,
+    pub num_heads: i64,
+    pub feature_channels: i64,
+    pub arch: String,
+}
+
+impl ModelMeta {
+    /// Graph Transformer on the 47-channel features of `convert_plateau_for_gat_47ch`
+    pub fn graph_transformer(
+        arch: &str,
+        embed_dim: i64,
+        num_layers: usize,
+        num_heads: i64,
+    ) -> Self {
+        Self {
+            embed_dim,
+            num_layers,
+            num_heads,
+            feature_channels: 47,
+            arch: arch.to_string(),
+        }
+    }
+}
+
+/// Path of the metadata saved alongside `model_path`
+pub fn meta_path(model_path: impl AsRef<Path>) -> PathBuf {
+    let mut path = model_path.as_ref().as_os_str().to_owned();
+    path.push(".meta.json");
+    PathBuf::from(path)
+}
+
+/// Save a VarStore like [`save_varstore`], plus its architecture in `<path>.meta.json`
+pub fn save_varstore_with_meta(
+    vs: &nn::VarStore,
+    path: impl AsRef<Path>,
+    meta: &ModelMeta,
+) -> Result<(), Box<dyn std::error::Error>> {
+    save_varstore(vs, &path)?;
+    std::fs::write(meta_path(&path), serde_json::to_string_pretty(meta)?)?;
+    Ok(())
+}
+
+/// Architecture saved with `model_path`, `None` for models saved without
+/// metadata (or with an unreadable file)
+pub fn load_meta(model_path: impl AsRef<Path>) -> Option<ModelMeta> {
+    let content = std::fs::read_to_string(meta_path(model_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Architecture to build the Graph Transformer of `path` with: the saved one
+/// when the model has metadata (with a warning if the requested dimensions
+/// differ), the requested one on 47 channels otherwise.
+fn resolve_gt_meta(
+    path: &Path,
+    arch: &str,
+    embed_dim: i64,
+    num_layers: usize,
+    num_heads: i64,
+) -> ModelMeta {
+    let Some(meta) = load_meta(path) else {
+        return ModelMeta::graph_transformer(arch, embed_dim, num_layers, num_heads);
+    };
+    if meta.arch != arch {
+        eprintln!(
+            "Warning: {} was saved as '{}', loading it as '{}'",
+            path.display(),
+            meta.arch,
+            arch
+        );
+    }
+    if (meta.embed_dim, meta.num_layers, meta.num_heads) != (embed_dim, num_layers, num_heads) {
+        eprintln!(
+            "Warning: {} was trained with dim={}, layers={}, heads={}; ignoring dim={}, layers={}, heads={}",
+            path.display(),
+            meta.embed_dim,
+            meta.num_layers,
+            meta.num_heads,
+            embed_dim,
+            num_layers,
+            num_heads
+        );
+    }
+    meta
+}
+
+/// Build a Graph Transformer policy sized for `path` and load its weights.
+///
+/// `embed_dim`, `num_layers` and `num_heads` are only used for models saved
+/// without metadata.
+pub fn load_policy(
+    path: impl AsRef<Path>,
+    device: Device,
+    embed_dim: i64,
+    num_layers: usize,
+    num_heads: i64,
+) -> Result<(nn::VarStore, GraphTransformerPolicyNet), Box<dyn std::error::Error>> {
+    let meta = resolve_gt_meta(
+        path.as_ref(),
+        GT_POLICY_ARCH,
+        embed_dim,
+        num_layers,
+        num_heads,
+    );
+    let mut vs = nn::VarStore::new(device);
+    let net = GraphTransformerPolicyNet::new(
+        &vs,
+        meta.feature_channels,
+        meta.embed_dim,
+        meta.num_layers,
+        meta.num_heads,
+        0.1,
+    );
+    load_varstore(&mut vs, path)?;
+    Ok((vs, net))
+}
+
+/// Build a Graph Transformer value net sized for `path` and load its weights,
+/// like [`load_policy`]. The score normalization is read separately.
+pub fn load_value(
+    path: impl AsRef<Path>,
+    device: Device,
+    embed_dim: i64,
+    num_layers: usize,
+    num_heads: i64,
+) -> Result<(nn::VarStore, GraphTransformerValueNet), Box<dyn std::error::Error>> {
+    let meta = resolve_gt_meta(
+        path.as_ref(),
+        GT_VALUE_ARCH,
+        embed_dim,
+        num_layers,
+        num_heads,
+    );
+    let mut vs = nn::VarStore::new(device);
+    let net = GraphTransformerValueNet::new(
+        &vs,
+        meta.feature_channels,
+        meta.embed_dim,
+        meta.num_layers,
+        meta.num_heads,
+        0.0,
+    );
+    load_varstore(&mut vs, path)?;
+    Ok((vs, net))
+}
 
 /// Save a VarStore to a safetensors file
 pub fn save_varstore(vs: &nn::VarStore, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
@@ -76,6 +241,106 @@ pub fn load_varstore(vs: &mut nn::VarStore, path: impl AsRef<Path>) -> Result<()
     Ok(())
 }
 
+/// Trace the policy forward pass on `example_input` and save it as a
+/// TorchScript module (`torch.jit.load` in Python, libtorch in C++).
+///
+/// The module takes the `[B, 19, 47]` features of `convert_plateau_for_gat_47ch`
+/// and returns the `[B, 19]` logits. Shapes are recorded by the trace, so the
+/// batch size of `example_input` is the one the module expects. For ONNX, see
+/// [`export_onnx`].
+pub fn export_torchscript(
+    vs: &mut nn::VarStore,
+    net: &GraphTransformerPolicyNet,
+    path: impl AsRef<Path>,
+    example_input: &Tensor,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // The trace captures the weights as constants, which must not require grad
+    vs.freeze();
+    let mut forward = |inputs: &[Tensor]| vec![net.forward(&inputs[0], false)];
+    let traced = CModule::create_by_tracing(
+        "GraphTransformerPolicy",
+        "forward",
+        &[example_input.shallow_clone()],
+        &mut forward,
+    );
+    vs.unfreeze();
+    traced?.save(path)?;
+    Ok(())
+}
+
+/// Largest logit difference [`export_onnx`] accepts between the ONNX graph
+/// and the Rust forward pass
+pub const ONNX_PARITY_TOLERANCE: f64 = 1e-4;
+
+/// Python side of [`export_onnx`]: TorchScript → ONNX, then parity check
+const ONNX_CONVERTER: &str = include_str!("torchscript_to_onnx.py");
+
+/// Python interpreter running the ONNX conversion: `TAKE_IT_EASY_PYTHON`,
+/// `python3` by default. It needs torch, numpy and onnxruntime.
+pub fn onnx_python() -> String {
+    std::env::var("TAKE_IT_EASY_PYTHON").unwrap_or_else(|_| "python3".to_string())
+}
+
+/// Trace the policy forward pass on `example_input` and save it as an ONNX
+/// graph (input `features`, output `logits`), checked against the Rust forward.
+///
+/// libtorch has no ONNX exporter: the trace is saved as TorchScript
+/// ([`export_torchscript`]) and converted with `torch.onnx.export` by
+/// [`onnx_python`]. The converter then runs the graph with onnxruntime on
+/// `example_input` and the export fails unless its logits match
+/// `net.forward` within [`ONNX_PARITY_TOLERANCE`]. As for TorchScript, the
+/// graph takes the `[B, 19, 47]` features with the batch size of the trace.
+pub fn export_onnx(
+    vs: &mut nn::VarStore,
+    net: &GraphTransformerPolicyNet,
+    path: impl AsRef<Path>,
+    example_input: &Tensor,
+) -> Result<(), Box<dyn std::error::Error>> {
+    static EXPORTS: AtomicUsize = AtomicUsize::new(0);
+    let work_dir = std::env::temp_dir().join(format!(
+        "take_it_easy_onnx_{}_{}",
+        std::process::id(),
+        EXPORTS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&work_dir)?;
+
+    let mut export = || -> Result<(), Box<dyn std::error::Error>> {
+        let module = work_dir.join("policy.pt");
+        let input = work_dir.join("input.npy");
+        let expected = work_dir.join("expected.npy");
+        export_torchscript(vs, net, &module, example_input)?;
+        let logits = tch::no_grad(|| net.forward(example_input, false));
+        example_input.to_kind(Kind::Float).write_npy(&input)?;
+        logits.to_kind(Kind::Float).write_npy(&expected)?;
+
+        let output = Command::new(onnx_python())
+            .arg("-c")
+            .arg(ONNX_CONVERTER)
+            .arg("--module")
+            .arg(&module)
+            .arg("--out")
+            .arg(path.as_ref())
+            .arg("--input")
+            .arg(&input)
+            .arg("--expected")
+            .arg(&expected)
+            .arg("--atol")
+            .arg(ONNX_PARITY_TOLERANCE.to_string())
+            .output()?;
+        if !output.status.success() {
+            return Err(format!(
+                "ONNX conversion failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        Ok(())
+    };
+    let result = export();
+    std::fs::remove_dir_all(&work_dir).ok();
+    result
+}
+
 #[derive(Debug)]
 struct TensorMetadata {
     shape: Vec<usize>,
@@ -214,4 +479,64 @@ mod tests {
 
         std::fs::remove_file(path).ok();
     }
+
+    #[test]
+    fn test_torchscript_export_matches_rust_forward() {
+        let mut vs = nn::VarStore::new(tch::Device::Cpu);
+        let net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 4, 0.1);
+        let input = Tensor::rand([2, 19, 47], (Kind::Float, tch::Device::Cpu));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.pt");
+        export_torchscript(&mut vs, &net, &path, &input).unwrap();
+
+        let module = CModule::load(&path).unwrap();
+        let exported = module.forward_ts(&[&input]).unwrap();
+        let expected = tch::no_grad(|| net.forward(&input, false));
+        assert_eq!(exported.size(), vec![2, 19]);
+        assert!(exported.allclose(&expected, 1e-4, 1e-4, false));
+    }
+
+    #[test]
+    fn test_onnx_export_matches_rust_forward() {
+        let available = Command::new(onnx_python())
+            .args(["-c", "import numpy, onnxruntime, torch"])
+            .status()
+            .is_ok_and(|status| status.success());
+        if !available {
+            eprintln!("Skipping ONNX export test: no Python with torch and onnxruntime");
+            return;
+        }
+
+        let mut vs = nn::VarStore::new(tch::Device::Cpu);
+        let net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 4, 0.1);
+        let input = Tensor::rand([2, 19, 47], (Kind::Float, tch::Device::Cpu));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.onnx");
+        // Fails unless onnxruntime reproduces the Rust logits within 1e-4
+        export_onnx(&mut vs, &net, &path, &input).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_load_policy_sizes_the_network_from_its_metadata() {
+        let vs = nn::VarStore::new(tch::Device::Cpu);
+        let net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 2, 0.1);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.safetensors");
+        let meta = ModelMeta::graph_transformer(GT_POLICY_ARCH, 32, 1, 2);
+        save_varstore_with_meta(&vs, &path, &meta).unwrap();
+        assert_eq!(load_meta(&path), Some(meta));
+
+        // Wrong CLI dims are overridden by the metadata
+        let (_, loaded) = load_policy(&path, tch::Device::Cpu, 128, 2, 4).unwrap();
+        let input = Tensor::rand([2, 19, 47], (Kind::Float, tch::Device::Cpu));
+        let expected = net.forward(&input, false);
+        let output = loaded.forward(&input, false);
+        assert!(output.allclose(&expected, 1e-6, 1e-6, false));
+
+        // Without metadata, the given dims are used as before
+        std::fs::remove_file(meta_path(&path)).unwrap();
+        assert_eq!(load_meta(&path), None);
+        assert!(load_policy(&path, tch::Device::Cpu, 32, 1, 2).is_ok());
+    }
 }
@@ -1,7 +1,7 @@
 use crate::neural::gnn::{GraphPolicyNet, GraphValueNet};
 use crate::neural::graph_transformer::{GraphTransformerPolicyNet, GraphTransformerValueNet};
 use crate::neural::manager::NNArchitecture;
-use tch::{nn, Tensor};
+use tch::{nn, Device, Tensor};
 
 use crate::neural::res_net_block::ResNetBlock;
 
@@ -53,6 +53,8 @@ pub trait ValueEvaluator: Send {
 
 pub struct PolicyNet {
     pub arch: NNArchitecture,
+    /// Device of the weights; inputs are moved there by `forward`
+    device: Device,
     net: PolicyNetImpl,
 }
 
@@ -68,14 +70,17 @@ impl PolicyNet {
         match arch {
             NNArchitecture::Cnn | NNArchitecture::CnnOnehot => Self {
                 arch,
+                device: vs.device(),
                 net: PolicyNetImpl::Cnn(Box::new(PolicyNetCNN::new(vs, input_dim))),
             },
             NNArchitecture::Gnn => Self {
                 arch,
+                device: vs.device(),
                 net: PolicyNetImpl::Gnn(GraphPolicyNet::new(vs, 8, &[64, 64, 64], 0.1)), // 8 features per node for GNN (matches training data)
             },
             NNArchitecture::GraphTransformer => Self {
                 arch,
+                device: vs.device(),
                 // Graph Transformer: 47 features, 128 embed, 2 layers, 4 heads, 0.1 dropout
                 net: PolicyNetImpl::GraphTransformer(GraphTransformerPolicyNet::new(
                     vs, 47, 128, 2, 4, 0.1,
@@ -92,7 +97,19 @@ impl PolicyNet {
         }
     }
 
+    /// Device the weights live on.
+    pub fn device(&self) -> Device {
+        self.device
+    }
+
+    /// Forward pass on the network's device; the output comes back on the
+    /// device of `input`, so CPU callers work with GPU weights.
     pub fn forward(&self, input: &Tensor, train: bool) -> Tensor {
+        self.forward_on_device(&input.to_device(self.device), train)
+            .to_device(input.device())
+    }
+
+    fn forward_on_device(&self, input: &Tensor, train: bool) -> Tensor {
         match &self.net {
             PolicyNetImpl::Cnn(net) => net.forward(input, train),
             PolicyNetImpl::Gnn(net) => {
@@ -311,6 +328,8 @@ pub fn initialize_weights(vs: &nn::VarStore) {
 pub struct ValueNet {
     #[allow(dead_code)]
     pub arch: NNArchitecture,
+    /// Device of the weights; inputs are moved there by `forward`
+    device: Device,
     net: ValueNetImpl,
 }
 
@@ -326,14 +345,17 @@ impl ValueNet {
         match arch {
             NNArchitecture::Cnn | NNArchitecture::CnnOnehot => Self {
                 arch,
+                device: vs.device(),
                 net: ValueNetImpl::Cnn(Box::new(ValueNetCNN::new(vs, input_dim))),
             },
             NNArchitecture::Gnn => Self {
                 arch,
+                device: vs.device(),
                 net: ValueNetImpl::Gnn(GraphValueNet::new(vs, 8, &[64, 64, 64], 0.1)), // 8 features per node for GNN (matches training data)
             },
             NNArchitecture::GraphTransformer => Self {
                 arch,
+                device: vs.device(),
                 net: ValueNetImpl::GraphTransformer(GraphTransformerValueNet::new(
                     vs, 47, 128, 2, 4, 0.1,
                 )),
@@ -341,7 +363,19 @@ impl ValueNet {
         }
     }
 
+    /// Device the weights live on.
+    pub fn device(&self) -> Device {
+        self.device
+    }
+
+    /// Forward pass on the network's device; the output comes back on the
+    /// device of `input`, so CPU callers work with GPU weights.
     pub fn forward(&self, input: &Tensor, train: bool) -> Tensor {
+        self.forward_on_device(&input.to_device(self.device), train)
+            .to_device(input.device())
+    }
+
+    fn forward_on_device(&self, input: &Tensor, train: bool) -> Tensor {
         match &self.net {
             ValueNetImpl::Cnn(net) => net.forward(input, train),
             ValueNetImpl::Gnn(net) => {
@@ -569,6 +603,20 @@ mod tests {
         assert_eq!(output.size(), vec![1, 19]); // Assuming 19 is the number of actions
     }
 
+    #[test]
+    fn test_forward_runs_on_the_weights_device() {
+        let device = Device::cuda_if_available();
+        let (policy_vs, value_vs) = (nn::VarStore::new(device), nn::VarStore::new(device));
+        let policy_net = PolicyNet::new(&policy_vs, (8, 5, 5), NNArchitecture::Cnn);
+        let value_net = ValueNet::new(&value_vs, (8, 5, 5), NNArchitecture::Cnn);
+        assert_eq!(policy_net.device(), device);
+
+        // CPU input, whatever the weights' device: the output comes back on CPU
+        let input = Tensor::rand([1, 8, 5, 5], (tch::Kind::Float, Device::Cpu));
+        assert_eq!(policy_net.forward(&input, false).device(), Device::Cpu);
+        assert_eq!(value_net.forward(&input, false).device(), Device::Cpu);
+    }
+
     #[test]
     fn test_value_net_creation_and_forward() {
         let vs = nn::VarStore::new(Device::Cpu);
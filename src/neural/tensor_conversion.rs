@@ -1,3 +1,4 @@
+use crate::game::board_geometry::LINES;
 use crate::game::deck::Deck;
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
@@ -97,23 +98,7 @@ const HEX_TO_GRID_MAP: [(usize, usize); GRAPH_NODE_COUNT] = [
 ];
 
 /// Line definitions: indices and orientation (0: horizontal, 1: diag1, 2: diag2)
-pub const LINE_DEFS: &[(&[usize], usize)] = &[
-    (&[0, 1, 2], 0),
-    (&[3, 4, 5, 6], 0),
-    (&[7, 8, 9, 10, 11], 0),
-    (&[12, 13, 14, 15], 0),
-    (&[16, 17, 18], 0),
-    (&[0, 3, 7], 1),
-    (&[1, 4, 8, 12], 1),
-    (&[2, 5, 9, 13, 16], 1),
-    (&[6, 10, 14, 17], 1),
-    (&[11, 15, 18], 1),
-    (&[7, 12, 16], 2),
-    (&[3, 8, 13, 17], 2),
-    (&[0, 4, 9, 14, 18], 2),
-    (&[1, 5, 10, 15], 2),
-    (&[2, 6, 11], 2),
-];
+pub const LINE_DEFS: &[(&[usize], usize)] = &LINES;
 
 // STOCHZERO V2: Extended with EXPLICIT LINE FEATURES
 // - 8 base + 9 bag features + 30 line features = 47 channels
@@ -126,6 +111,8 @@ const GRID_SIZE: usize = 5;
 // - Ch 17+i*2: Line completion potential (0=blocked, 0.5=partial, 1=complete match)
 // - Ch 18+i*2: Current tile compatibility (1 if tile value matches line direction)
 // This gives the CNN direct access to line geometry without needing convolution
+// Optional Ch 47 (board pressure): see `compute_board_pressure` and the
+// `*_with_pressure` / `*_48ch` encoders.
 
 /// Convert hex position (0-18) to 5×5 grid index using proper hexagonal mapping
 /// This preserves spatial relationships so CNN can learn line patterns
@@ -465,6 +452,7 @@ pub fn compute_orientation_scores(plateau: &Plateau) -> [[f32; GRAPH_NODE_COUNT]
 /// - Ch 7: Turn progress
 /// - Ch 8-16: Bag value counts (9 features, broadcast)
 /// - Ch 17-46: Line features (30 features - 2 per line)
+/// - (Ch 47: board pressure, only in `convert_plateau_for_gat_48ch`)
 pub fn convert_plateau_for_gat_47ch(
     plateau: &Plateau,
     tile: &Tile,
@@ -534,6 +522,102 @@ pub fn convert_plateau_for_gat_47ch(
     Tensor::from_slice(&features).view([GRAPH_NODE_COUNT as i64, CHANNELS as i64])
 }
 
+// ── Board pressure: optional extra channel (47 → 48) ──
+//
+// Ch 47: per empty cell, sum over the 3 scoring lines crossing it of the line's
+// remaining potential (length × expected value / 45, 0 when blocked), divided
+// by 3 so the channel stays in [0, 1]. Occupied cells are 0. Junctions where
+// several alive high-value lines meet get the highest pressure.
+// Kept out of the 47-channel encoders so existing models still load; select the
+// 48-channel variants explicitly (e.g. `--board-pressure`).
+
+pub const CHANNELS_WITH_PRESSURE: usize = CHANNELS + 1;
+
+/// Per-cell board pressure in [0, 1] (0 for occupied cells).
+///
+/// A line is alive when all its placed tiles share one value in its direction;
+/// an alive line contributes `len × v / 45` where `v` is that value, or the
+/// mean value 5 when the line is still empty.
+pub fn compute_board_pressure(plateau: &Plateau) -> [f32; GRAPH_NODE_COUNT] {
+    let mut line_potential = [0.0f32; 15];
+    for (line_idx, (positions, direction)) in LINE_DEFS.iter().enumerate() {
+        let mut line_value: Option<i32> = None;
+        let mut blocked = false;
+        for &pos in *positions {
+            let t = &plateau.tiles[pos];
+            if *t == Tile(0, 0, 0) {
+                continue;
+            }
+            let v = match direction {
+                0 => t.0,
+                1 => t.1,
+                _ => t.2,
+            };
+            match line_value {
+                None => line_value = Some(v),
+                Some(lv) if lv != v => {
+                    blocked = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        if !blocked {
+            let v = line_value.unwrap_or(5) as f32;
+            line_potential[line_idx] = positions.len() as f32 * v / 45.0;
+        }
+    }
+
+    let mut pressure = [0.0f32; GRAPH_NODE_COUNT];
+    for (hex_pos, cell) in pressure.iter_mut().enumerate() {
+        if plateau.tiles[hex_pos] != Tile(0, 0, 0) {
+            continue;
+        }
+        let total: f32 = LINE_DEFS
+            .iter()
+            .enumerate()
+            .filter(|(_, (positions, _))| positions.contains(&hex_pos))
+            .map(|(line_idx, _)| line_potential[line_idx])
+            .sum();
+        *cell = (total / 3.0).min(1.0);
+    }
+    pressure
+}
+
+/// CNN encoding with board pressure: 47 channels of `convert_plateau_to_tensor` + Ch 47.
+/// Output shape: [1, 48, 5, 5]
+pub fn convert_plateau_to_tensor_with_pressure(
+    plateau: &Plateau,
+    tile: &Tile,
+    deck: &Deck,
+    current_turn: usize,
+    total_turns: usize,
+) -> Tensor {
+    let base = convert_plateau_to_tensor(plateau, tile, deck, current_turn, total_turns);
+    let pressure = compute_board_pressure(plateau);
+    let mut channel = vec![0.0f32; GRID_SIZE * GRID_SIZE];
+    for (hex_pos, value) in pressure.iter().enumerate() {
+        channel[hex_to_grid_idx(hex_pos)] = *value;
+    }
+    let channel = Tensor::from_slice(&channel).view([1, 1, GRID_SIZE as i64, GRID_SIZE as i64]);
+    Tensor::cat(&[base, channel], 1)
+}
+
+/// GAT encoding with board pressure: 47 features of `convert_plateau_for_gat_47ch` + Ch 47.
+/// Output shape: [19, 48]
+pub fn convert_plateau_for_gat_48ch(
+    plateau: &Plateau,
+    tile: &Tile,
+    deck: &Deck,
+    current_turn: usize,
+    total_turns: usize,
+) -> Tensor {
+    let base = convert_plateau_for_gat_47ch(plateau, tile, deck, current_turn, total_turns);
+    let pressure = compute_board_pressure(plateau);
+    let channel = Tensor::from_slice(&pressure).view([GRAPH_NODE_COUNT as i64, 1]);
+    Tensor::cat(&[base, channel], 1)
+}
+
 // ── Enriched encoding: 47 base + 15 line completion probabilities = 62 channels ──
 
 const CHANNELS_ENRICHED: usize = 62;
@@ -749,3 +833,59 @@ pub fn convert_plateau_for_gat_enriched(
 
     Tensor::from_slice(&features).view([GRAPH_NODE_COUNT as i64, CHANNELS_ENRICHED as i64])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_board_pressure_peaks_at_high_value_junction() {
+        let mut plateau = create_plateau_empty();
+        // Centre cell 9 sits on row 7-11, diag 2-5-9-13-16 and diag 0-4-9-14-18.
+        // Build all three with high values (9, 7, 8); other lines are partly blocked.
+        plateau.tiles[7] = Tile(9, 2, 8);
+        plateau.tiles[8] = Tile(9, 6, 3);
+        plateau.tiles[5] = Tile(1, 7, 4);
+        plateau.tiles[13] = Tile(5, 7, 3);
+        plateau.tiles[4] = Tile(5, 6, 8);
+        plateau.tiles[14] = Tile(1, 2, 8);
+        plateau.tiles[17] = Tile(5, 6, 4);
+        plateau.tiles[18] = Tile(9, 7, 8);
+
+        let pressure = compute_board_pressure(&plateau);
+        let centre = pressure[9];
+        // Occupied cells carry no pressure
+        assert_eq!(pressure[7], 0.0);
+        assert!(pressure.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        // The junction of three alive high-value lines dominates every other cell
+        for (pos, &p) in pressure.iter().enumerate() {
+            if pos != 9 {
+                assert!(
+                    centre > p,
+                    "pos {} pressure {} >= centre {}",
+                    pos,
+                    p,
+                    centre
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pressure_encoders_append_one_channel() {
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(9, 7, 8);
+        let deck = create_deck();
+        let tile = Tile(5, 6, 4);
+
+        let gat = convert_plateau_for_gat_48ch(&plateau, &tile, &deck, 1, 19);
+        assert_eq!(gat.size(), vec![19, CHANNELS_WITH_PRESSURE as i64]);
+        let base = convert_plateau_for_gat_47ch(&plateau, &tile, &deck, 1, 19);
+        assert!(gat.narrow(1, 0, 47).equal(&base));
+
+        let cnn = convert_plateau_to_tensor_with_pressure(&plateau, &tile, &deck, 1, 19);
+        assert_eq!(cnn.size(), vec![1, 48, 5, 5]);
+    }
+}
@@ -0,0 +1,61 @@
+#!/usr/bin/env python3
+"""
+Convert a traced TorchScript policy to ONNX and check it against reference logits.
+
+Run by `neural::model_io::export_onnx`, which passes the example input and the
+logits of the Rust forward pass as .npy files. Exits with status 1 when the
+ONNX logits (onnxruntime) differ from the reference by more than --atol.
+
+Needs torch, numpy and onnxruntime.
+"""
+
+import argparse
+import sys
+
+import numpy as np
+import onnxruntime as ort
+import torch
+
+
+def export(module, example, out):
+    kwargs = dict(input_names=["features"], output_names=["logits"], opset_version=17)
+    try:
+        # torch >= 2.5 defaults to the dynamo exporter, which does not take ScriptModules
+        torch.onnx.export(module, (example,), out, dynamo=False, **kwargs)
+    except TypeError:
+        torch.onnx.export(module, (example,), out, **kwargs)
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("--module", required=True, help="TorchScript module (.pt)")
+    parser.add_argument("--out", required=True, help="ONNX output file")
+    parser.add_argument("--input", required=True, help="Example input [B, 19, C] (.npy)")
+    parser.add_argument("--expected", required=True, help="Rust logits [B, 19] (.npy)")
+    parser.add_argument("--atol", type=float, default=1e-4)
+    args = parser.parse_args()
+
+    module = torch.jit.load(args.module)
+    module.eval()
+    example = torch.from_numpy(np.load(args.input))
+    export(module, example, args.out)
+
+    session = ort.InferenceSession(args.out, providers=["CPUExecutionProvider"])
+    logits = session.run(["logits"], {"features": example.numpy()})[0]
+    expected = np.load(args.expected)
+    if logits.shape != expected.shape:
+        print(f"ONNX logits have shape {logits.shape}, expected {expected.shape}", file=sys.stderr)
+        return 1
+    max_diff = float(np.max(np.abs(logits - expected)))
+    if not max_diff <= args.atol:
+        print(
+            f"ONNX logits differ from the Rust forward: max |diff| {max_diff:.3g} > {args.atol}",
+            file=sys.stderr,
+        )
+        return 1
+    print(f"ONNX parity OK (max |diff| {max_diff:.3g})")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
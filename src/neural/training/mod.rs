@@ -2,4 +2,5 @@
 
 pub mod gradient_clipping;
 pub mod normalization;
+pub mod policy_target;
 pub mod trainer;
@@ -0,0 +1,167 @@
+//! Sélection de la cible de policy pour l'entraînement (one-hot, visites, boostée)
+
+use tch::{Kind, Tensor};
+
+/// Plancher des log-probabilités `-inf` des cases masquées : leurs cibles
+/// nulles y contribuent 0 au lieu de `NaN`.
+const MIN_LOG_PROB: f64 = -100.0;
+
+/// Distribution utilisée comme cible par la tête de policy
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PolicyTargetMode {
+    /// Uniquement la position jouée (`best_position`)
+    #[value(name = "onehot")]
+    OneHot,
+    /// Distribution MCTS brute (`policy_distribution`)
+    #[default]
+    Visits,
+    /// Distribution boostée (`policy_distribution_boosted`)
+    Boosted,
+}
+
+/// Construit la cible de policy de taille `len` selon `mode`.
+///
+/// Une distribution absente ou nulle retombe sur le one-hot de `best_position`,
+/// comme le faisait l'entraînement historique.
+pub fn build_policy_target(
+    mode: PolicyTargetMode,
+    best_position: usize,
+    visits: &[f32],
+    boosted: &[f32],
+    len: usize,
+) -> Vec<f32> {
+    let mut target = vec![0f32; len];
+    if len == 0 {
+        return target;
+    }
+
+    let source = match mode {
+        PolicyTargetMode::OneHot => &[][..],
+        PolicyTargetMode::Visits => visits,
+        PolicyTargetMode::Boosted => boosted,
+    };
+    for (slot, value) in target.iter_mut().zip(source) {
+        *slot = value.max(0.0);
+    }
+
+    let sum: f32 = target.iter().sum();
+    if sum <= f32::EPSILON || !sum.is_finite() {
+        target.iter_mut().for_each(|v| *v = 0.0);
+        target[best_position.min(len - 1)] = 1.0;
+    } else {
+        target.iter_mut().for_each(|v| *v /= sum);
+    }
+    target
+}
+
+/// Visites restreintes aux coups légaux (`is_legal(position)`), les autres à 0.
+///
+/// À passer à [`build_policy_target`] quand la distribution enregistrée peut
+/// porter des visites sur des cases déjà occupées.
+pub fn restrict_to_legal(visits: &[f32], is_legal: impl Fn(usize) -> bool) -> Vec<f32> {
+    visits
+        .iter()
+        .enumerate()
+        .map(|(position, &v)| if is_legal(position) { v } else { 0.0 })
+        .collect()
+}
+
+/// Extrait un tensor de distribution sous forme de vecteur plat
+pub fn tensor_to_distribution(tensor: &Tensor) -> Vec<f32> {
+    let flattened = tensor.to_kind(tch::Kind::Float).flatten(0, -1);
+    let numel = flattened.numel();
+    let mut buffer = vec![0f32; numel];
+    if numel > 0 {
+        flattened.copy_data(&mut buffer, numel);
+    }
+    buffer
+}
+
+/// KL(target || pred) à partir des log-probabilités prédites, par
+/// échantillon : somme sur la dernière dimension (`[batch, n]` → `[batch]`,
+/// `[n]` → scalaire).
+///
+/// Diffère de l'entropie croisée `-Σ p·log q` par l'entropie de la cible, une
+/// constante : les gradients sont identiques, mais la perte vaut 0 quand la
+/// prédiction colle à une cible soft. Les log-probabilités `-inf` (cases
+/// masquées avant le softmax) sont acceptées.
+pub fn policy_kl_loss(target: &Tensor, log_pred: &Tensor) -> Tensor {
+    let target_log = target.clamp_min(1e-12).log();
+    let safe_log_pred = log_pred.clamp_min(MIN_LOG_PROB);
+    (target * (target_log - safe_log_pred)).sum_dim_intlist(-1, false, Kind::Float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_onehot_reproduces_chosen_position_target() {
+        let visits = [0.1, 0.6, 0.3];
+        let target = build_policy_target(PolicyTargetMode::OneHot, 2, &visits, &visits, 3);
+        assert_eq!(target, vec![0.0, 0.0, 1.0]);
+
+        // Comportement historique : distribution vide => one-hot sur best_position
+        let fallback = build_policy_target(PolicyTargetMode::Visits, 2, &[], &[], 3);
+        assert_eq!(fallback, target);
+    }
+
+    #[test]
+    fn test_soft_targets_are_normalized() {
+        let visits = [1.0, 3.0, 0.0, 0.0];
+        let boosted = [0.0, 0.0, 2.0, 2.0];
+        let v = build_policy_target(PolicyTargetMode::Visits, 0, &visits, &boosted, 4);
+        let b = build_policy_target(PolicyTargetMode::Boosted, 0, &visits, &boosted, 4);
+        assert_eq!(v, vec![0.25, 0.75, 0.0, 0.0]);
+        assert_eq!(b, vec![0.0, 0.0, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_kl_loss_matches_cross_entropy_for_onehot() {
+        let target = Tensor::from_slice(&[0.0f32, 1.0, 0.0]);
+        let log_pred = Tensor::from_slice(&[0.2f32, 0.5, 0.3]).log();
+        let kl = policy_kl_loss(&target, &log_pred).double_value(&[]);
+        let ce = (-(&target * &log_pred).sum(Kind::Float)).double_value(&[]);
+        assert!((kl - ce).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kl_loss_is_zero_when_prediction_matches_target() {
+        let target = Tensor::from_slice(&[0.25f32, 0.75]);
+        let kl = policy_kl_loss(&target, &target.log()).double_value(&[]);
+        assert!(kl.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kl_loss_per_sample_with_masked_cells() {
+        // Case 0 masquée : log-probabilité -inf, cible nulle
+        let mut mask = vec![0.0f32; 19];
+        mask[0] = f32::NEG_INFINITY;
+        let logits = Tensor::randn([2, 19], (Kind::Float, tch::Device::Cpu))
+            + Tensor::from_slice(&mask).unsqueeze(0);
+        let log_pred = logits.log_softmax(-1, Kind::Float);
+
+        let mut one_hot = vec![0.0f32; 19];
+        one_hot[4] = 1.0;
+        let targets = Tensor::stack(&[Tensor::from_slice(&one_hot), log_pred.get(1).exp()], 0);
+        let kl = policy_kl_loss(&targets, &log_pred);
+        assert_eq!(kl.size(), vec![2]);
+        let cross_entropy = -log_pred.double_value(&[0, 4]);
+        assert!((kl.double_value(&[0]) - cross_entropy).abs() < 1e-5);
+        assert!(kl.double_value(&[1]).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_occupied_cells_are_dropped_from_visit_targets() {
+        let visits = [0.2, 0.6, 0.2];
+        let legal = restrict_to_legal(&visits, |position| position != 0);
+        let target = build_policy_target(PolicyTargetMode::Visits, 1, &legal, &[], 3);
+        assert_eq!(target[0], 0.0);
+        assert!((target[1] - 0.75).abs() < 1e-6);
+
+        // Aucune visite légale : one-hot sur le coup joué
+        let none = restrict_to_legal(&visits, |_| false);
+        let fallback = build_policy_target(PolicyTargetMode::Visits, 2, &none, &[], 3);
+        assert_eq!(fallback, vec![0.0, 0.0, 1.0]);
+    }
+}
@@ -4,6 +4,9 @@ use crate::neural::manager::NNArchitecture;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::training::gradient_clipping::enhanced_gradient_clipping;
 use crate::neural::training::normalization::robust_state_normalization;
+use crate::neural::training::policy_target::{
+    build_policy_target, policy_kl_loss, tensor_to_distribution, PolicyTargetMode,
+};
 use tch::nn::Optimizer;
 use tch::{nn, Tensor};
 
@@ -17,6 +20,7 @@ pub fn train_network_with_game_data(
     value_net: &ValueNet,
     optimizer_policy: &mut Optimizer,
     optimizer_value: &mut Optimizer,
+    policy_target: PolicyTargetMode,
 ) {
     // Hyperparameters
     let entropy_weight = 0.05;
@@ -96,35 +100,17 @@ pub fn train_network_with_game_data(
         // === Compute Losses ===
         // Policy loss
         let policy_len = pred_policy.size()[1] as usize;
-        let mut policy_vec = vec![0f32; policy_len];
-        let flattened_policy = result
-            .policy_distribution
-            .to_kind(tch::Kind::Float)
-            .flatten(0, -1);
-        let numel = flattened_policy.numel().max(0);
-        if numel > 0 {
-            let mut buffer = vec![0f32; numel];
-            flattened_policy.copy_data(&mut buffer, numel);
-            for (idx, value) in buffer.into_iter().take(policy_vec.len()).enumerate() {
-                policy_vec[idx] = value;
-            }
-        }
-
-        let sum: f32 = policy_vec.iter().sum();
-        if sum <= f32::EPSILON {
-            if !policy_vec.is_empty() {
-                let best_position = result.best_position.min(policy_vec.len() - 1);
-                policy_vec[best_position] = 1.0;
-            }
-        } else {
-            for value in &mut policy_vec {
-                *value /= sum;
-            }
-        }
+        let policy_vec = build_policy_target(
+            policy_target,
+            result.best_position,
+            &tensor_to_distribution(&result.policy_distribution),
+            &tensor_to_distribution(&result.policy_distribution_boosted),
+            policy_len,
+        );
 
         let target_policy = Tensor::from_slice(&policy_vec).view([1, policy_len as i64]);
         let log_policy = pred_policy.log();
-        let policy_loss = -(target_policy * log_policy.shallow_clone()).sum(tch::Kind::Float);
+        let policy_loss = policy_kl_loss(&target_policy, &log_policy).sum(tch::Kind::Float);
         total_policy_loss += policy_loss;
 
         if log::log_enabled!(log::Level::Trace) {
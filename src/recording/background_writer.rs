@@ -0,0 +1,96 @@
+//! Background writer for finished game recordings.
+//!
+//! Finishing a game only queues its record: a dedicated task writes the
+//! queued games through the [`CsvWriter`], off the request path, whole games
+//! at a time and in the order they finished. When the queue is full the game
+//! is dropped with a warning rather than blocking the caller.
+
+use crate::recording::csv_writer::CsvWriter;
+use crate::recording::game_record::GameRecord;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot};
+
+/// Finished games waiting to be written before new ones are dropped
+pub const WRITE_QUEUE_CAPACITY: usize = 256;
+
+/// Queued commands written in one blocking batch
+const WRITE_BATCH: usize = 32;
+
+enum WriteCommand {
+    Game(Box<GameRecord>),
+    /// Acknowledged once every command queued before it is processed
+    Sync(oneshot::Sender<()>),
+}
+
+pub struct BackgroundWriter {
+    sender: mpsc::Sender<WriteCommand>,
+}
+
+impl BackgroundWriter {
+    /// Start the writer task on the current tokio runtime (`None` outside of one).
+    pub fn spawn(csv_writer: Arc<Mutex<CsvWriter>>, capacity: usize) -> Option<Self> {
+        let runtime = tokio::runtime::Handle::try_current().ok()?;
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        runtime.spawn(run_writer(receiver, csv_writer));
+        Some(Self { sender })
+    }
+
+    /// Queue `record` for writing without waiting. Returns false when the
+    /// game is dropped (queue full or writer stopped).
+    pub fn submit(&self, record: GameRecord) -> bool {
+        let game_id = record.game_id.clone();
+        match self.sender.try_send(WriteCommand::Game(Box::new(record))) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log::warn!("Recording queue full, dropping game {}", game_id);
+                false
+            }
+            Err(TrySendError::Closed(_)) => {
+                log::warn!("Recording writer stopped, dropping game {}", game_id);
+                false
+            }
+        }
+    }
+
+    /// Wait until every game queued before this call is written.
+    pub async fn sync(&self) {
+        let (ack, done) = oneshot::channel();
+        if self.sender.send(WriteCommand::Sync(ack)).await.is_ok() {
+            let _ = done.await;
+        }
+    }
+}
+
+async fn run_writer(mut receiver: mpsc::Receiver<WriteCommand>, csv_writer: Arc<Mutex<CsvWriter>>) {
+    let mut batch = Vec::with_capacity(WRITE_BATCH);
+    while receiver.recv_many(&mut batch, WRITE_BATCH).await > 0 {
+        let commands = std::mem::take(&mut batch);
+        let csv_writer = csv_writer.clone();
+        // File I/O on the blocking pool; batches are awaited one after the
+        // other, so games reach the file in queue order
+        let acks = tokio::task::spawn_blocking(move || {
+            let mut writer = csv_writer.lock().unwrap();
+            let mut acks = Vec::new();
+            for command in commands {
+                match command {
+                    WriteCommand::Game(record) => {
+                        if let Err(e) = writer.write_game(&record) {
+                            log::error!("Failed to write recorded game {}: {}", record.game_id, e);
+                        }
+                    }
+                    WriteCommand::Sync(ack) => acks.push(ack),
+                }
+            }
+            acks
+        })
+        .await;
+
+        match acks {
+            Ok(acks) => acks.into_iter().for_each(|ack| {
+                let _ = ack.send(());
+            }),
+            Err(e) => log::error!("Recording writer batch failed: {}", e),
+        }
+    }
+}
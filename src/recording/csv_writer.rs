@@ -2,23 +2,62 @@
 //!
 //! Writes game data in a format compatible with supervised_trainer_csv.rs
 //! Format: game_id,turn,player_type,plateau_0-18,tile_0-2,position,final_score,human_won
-
-use crate::recording::game_record::{GameRecord, MoveRecord, PlayerType};
+//! Optional trailing columns (`candidates`, `model_version`, see [`ExtraColumns`])
+//! go to separate files (`games_<date>_candidates.csv`, `games_<date>_ab.csv`) so
+//! each file keeps a single header. The loader finds them by header name.
+
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+use crate::recording::game_record::{
+    decode_candidates, decode_plateau_value, encode_candidates, GameRecord, MoveCandidate,
+    MoveRecord, PlayerType,
+};
+use crate::scoring::scoring::count_line_completions;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 
+/// Optional trailing columns, fixed for the lifetime of a writer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtraColumns {
+    /// AI top-k candidates of each move
+    pub candidates: bool,
+    /// Model version that played the game (A/B testing)
+    pub model_version: bool,
+}
+
 /// CSV writer for game recordings with daily rotation
 pub struct CsvWriter {
     base_dir: PathBuf,
     current_file: Option<BufWriter<File>>,
     current_date: String,
+    extra: ExtraColumns,
 }
 
 impl CsvWriter {
     /// Create a new CSV writer
     pub fn new<P: AsRef<Path>>(base_dir: P) -> std::io::Result<Self> {
+        Self::with_columns(base_dir, ExtraColumns::default())
+    }
+
+    /// Create a CSV writer, optionally with the AI candidates column
+    pub fn with_candidates<P: AsRef<Path>>(
+        base_dir: P,
+        with_candidates: bool,
+    ) -> std::io::Result<Self> {
+        Self::with_columns(
+            base_dir,
+            ExtraColumns {
+                candidates: with_candidates,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a CSV writer with the given optional columns
+    pub fn with_columns<P: AsRef<Path>>(base_dir: P, extra: ExtraColumns) -> std::io::Result<Self> {
         let base_dir = base_dir.as_ref().to_path_buf();
         fs::create_dir_all(&base_dir)?;
 
@@ -26,6 +65,7 @@ impl CsvWriter {
             base_dir,
             current_file: None,
             current_date: String::new(),
+            extra,
         })
     }
 
@@ -36,7 +76,14 @@ impl CsvWriter {
 
     /// Get the file path for a given date
     fn get_file_path(&self, date: &str) -> PathBuf {
-        self.base_dir.join(format!("games_{}.csv", date))
+        let mut name = format!("games_{}", date);
+        if self.extra.candidates {
+            name.push_str("_candidates");
+        }
+        if self.extra.model_version {
+            name.push_str("_ab");
+        }
+        self.base_dir.join(format!("{}.csv", name))
     }
 
     /// Ensure the file is open for the current date, with rotation
@@ -61,7 +108,7 @@ impl CsvWriter {
 
             // Write header if new file
             if !file_exists {
-                Self::write_header(&mut writer)?;
+                Self::write_header(&mut writer, self.extra)?;
             }
 
             self.current_file = Some(writer);
@@ -72,7 +119,7 @@ impl CsvWriter {
     }
 
     /// Write the CSV header
-    fn write_header<W: Write>(writer: &mut W) -> std::io::Result<()> {
+    fn write_header<W: Write>(writer: &mut W, extra: ExtraColumns) -> std::io::Result<()> {
         let mut header = String::from("game_id,turn,player_type");
 
         // Plateau columns (19 positions)
@@ -85,6 +132,12 @@ impl CsvWriter {
 
         // Position and scores
         header.push_str(",position,final_score,human_won");
+        if extra.candidates {
+            header.push_str(",candidates");
+        }
+        if extra.model_version {
+            header.push_str(",model_version");
+        }
 
         writeln!(writer, "{}", header)
     }
@@ -96,6 +149,8 @@ impl CsvWriter {
         move_record: &MoveRecord,
         final_score: i32,
         human_won: bool,
+        model_version: Option<&str>,
+        extra: ExtraColumns,
     ) -> std::io::Result<()> {
         let player_type = match move_record.player_type {
             PlayerType::Human => "Human",
@@ -125,6 +180,18 @@ impl CsvWriter {
             if human_won { 1 } else { 0 }
         ));
 
+        if extra.candidates {
+            let cell = move_record
+                .candidates
+                .as_deref()
+                .map(encode_candidates)
+                .unwrap_or_default();
+            row.push_str(&format!(",{}", cell));
+        }
+        if extra.model_version {
+            row.push_str(&format!(",{}", model_version.unwrap_or_default()));
+        }
+
         writeln!(writer, "{}", row)
     }
 
@@ -147,6 +214,8 @@ impl CsvWriter {
                     move_record,
                     final_score,
                     record.human_won,
+                    record.model_version.as_deref(),
+                    self.extra,
                 )?;
             }
             writer.flush()?;
@@ -183,6 +252,10 @@ pub fn load_games_from_csv<P: AsRef<Path>>(
     path: P,
 ) -> Result<Vec<LoadedMoveRecord>, Box<dyn std::error::Error>> {
     let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let candidates_column = column("candidates");
+    let model_version_column = column("model_version");
     let mut records = Vec::new();
 
     for result in reader.records() {
@@ -210,6 +283,15 @@ pub fn load_games_from_csv<P: AsRef<Path>>(
         let final_score: i32 = record.get(26).unwrap_or("0").parse().unwrap_or(0);
         let human_won: bool = record.get(27).unwrap_or("0") == "1";
 
+        // Optional trailing columns
+        let candidates = candidates_column
+            .and_then(|i| record.get(i))
+            .and_then(decode_candidates);
+        let model_version = model_version_column
+            .and_then(|i| record.get(i))
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+
         records.push(LoadedMoveRecord {
             game_id,
             turn,
@@ -219,6 +301,8 @@ pub fn load_games_from_csv<P: AsRef<Path>>(
             position,
             final_score,
             human_won,
+            candidates,
+            model_version,
         });
     }
 
@@ -236,12 +320,71 @@ pub struct LoadedMoveRecord {
     pub position: usize,
     pub final_score: i32,
     pub human_won: bool,
+    pub candidates: Option<Vec<MoveCandidate>>,
+    pub model_version: Option<String>,
+}
+
+/// Completed lines on the final board of each (game_id, player_type) in the records.
+///
+/// The final board is rebuilt from the player's last recorded move: the plateau
+/// before that move plus the tile placed at its position.
+pub fn final_board_completions(
+    records: &[LoadedMoveRecord],
+) -> HashMap<(String, PlayerType), usize> {
+    let mut last_moves: HashMap<(String, PlayerType), &LoadedMoveRecord> = HashMap::new();
+    for record in records {
+        let key = (record.game_id.clone(), record.player_type);
+        match last_moves.get(&key) {
+            Some(previous) if previous.turn >= record.turn => {}
+            _ => {
+                last_moves.insert(key, record);
+            }
+        }
+    }
+
+    last_moves
+        .into_iter()
+        .map(|(key, record)| {
+            let mut tiles: Vec<Tile> = record
+                .plateau
+                .iter()
+                .map(|&v| {
+                    let (a, b, c) = decode_plateau_value(v);
+                    Tile(a, b, c)
+                })
+                .collect();
+            tiles.resize(19, Tile(0, 0, 0));
+            if record.position < tiles.len() {
+                let (a, b, c) = record.tile;
+                tiles[record.position] = Tile(a, b, c);
+            }
+            (key, count_line_completions(&Plateau { tiles }))
+        })
+        .collect()
+}
+
+/// Keep only the moves of games whose final board has at least `min_completions` completed lines.
+pub fn filter_by_min_completions(
+    records: Vec<LoadedMoveRecord>,
+    min_completions: usize,
+) -> Vec<LoadedMoveRecord> {
+    if min_completions == 0 {
+        return records;
+    }
+    let completions = final_board_completions(&records);
+    records
+        .into_iter()
+        .filter(|r| {
+            completions
+                .get(&(r.game_id.clone(), r.player_type))
+                .is_some_and(|&c| c >= min_completions)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use tempfile::tempdir;
 
     #[test]
@@ -261,6 +404,7 @@ mod tests {
             position: 5,
             mcts_evaluation: None,
             timestamp: 0,
+            candidates: None,
         };
         record.record_move(move_record);
 
@@ -279,4 +423,95 @@ mod tests {
 
         Ok(())
     }
+
+    fn final_move(game_id: &str, plateau: Vec<i32>, tile: (i32, i32, i32)) -> LoadedMoveRecord {
+        LoadedMoveRecord {
+            game_id: game_id.to_string(),
+            turn: 18,
+            player_type: PlayerType::Human,
+            plateau,
+            tile,
+            position: 2,
+            final_score: 15,
+            human_won: true,
+            candidates: None,
+            model_version: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_by_min_completions_at_equal_score() {
+        // Both games report the same final score, but only "coherent" completed the top row
+        let mut coherent = vec![0; 19];
+        coherent[0] = 524;
+        coherent[1] = 563;
+        let coherent_move = final_move("coherent", coherent, (5, 7, 8));
+
+        let mut scattered = vec![0; 19];
+        scattered[0] = 524;
+        scattered[1] = 163;
+        let scattered_move = final_move("scattered", scattered, (9, 7, 8));
+
+        let records = vec![coherent_move, scattered_move];
+        let completions = final_board_completions(&records);
+        assert_eq!(completions[&("coherent".to_string(), PlayerType::Human)], 1);
+        assert_eq!(completions[&("scattered".to_string(), PlayerType::Human)], 0);
+
+        let kept = filter_by_min_completions(records.clone(), 1);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].game_id, "coherent");
+
+        assert_eq!(filter_by_min_completions(records, 0).len(), 2);
+    }
+
+    #[test]
+    fn test_candidates_column_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempdir()?;
+        let mut writer = CsvWriter::with_candidates(dir.path(), true)?;
+
+        let candidates = vec![
+            MoveCandidate {
+                position: 7,
+                visit_share: 0.625,
+                q_value: Some(148.5),
+            },
+            MoveCandidate {
+                position: 3,
+                visit_share: 0.25,
+                q_value: None,
+            },
+        ];
+        let mut record = GameRecord::new("cand-1".to_string(), "human_vs_mcts".to_string());
+        record.add_player("mcts_ai".to_string(), PlayerType::Mcts);
+        record.add_player("human".to_string(), PlayerType::Human);
+        for (player_id, player_type, candidates) in [
+            ("mcts_ai", PlayerType::Mcts, Some(candidates.clone())),
+            ("human", PlayerType::Human, None),
+        ] {
+            record.record_move(MoveRecord {
+                turn: 0,
+                player_id: player_id.to_string(),
+                player_type,
+                plateau_before: vec![0; 19],
+                tile: (1, 2, 3),
+                position: 7,
+                mcts_evaluation: None,
+                timestamp: 0,
+                candidates,
+            });
+        }
+        record.finalize(HashMap::new());
+        writer.write_game(&record)?;
+        writer.close()?;
+
+        let path = fs::read_dir(dir.path())?.next().unwrap()?.path();
+        assert!(path.to_string_lossy().ends_with("_candidates.csv"));
+        let loaded = load_games_from_csv(&path)?;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].candidates, Some(candidates));
+        assert_eq!(loaded[1].candidates, None);
+        assert_eq!(loaded[0].position, 7);
+
+        Ok(())
+    }
 }
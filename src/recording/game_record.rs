@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Type of player in the game
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlayerType {
     Human,
     Mcts,
@@ -65,6 +65,20 @@ pub struct MoveRecord {
     pub mcts_evaluation: Option<f32>,
     /// Timestamp of the move
     pub timestamp: i64,
+    /// Top-k positions considered by the AI (only when the recorder is
+    /// configured to keep them)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<MoveCandidate>>,
+}
+
+/// A position considered by the AI for a move
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveCandidate {
+    pub position: usize,
+    /// Share of the MCTS visits (normalized policy distribution)
+    pub visit_share: f32,
+    /// Rollout-based Q-value, when the search produced one
+    pub q_value: Option<f32>,
 }
 
 /// Complete record of a game
@@ -84,6 +98,9 @@ pub struct GameRecord {
     pub human_won: bool,
     /// Game mode (e.g., "human_vs_mcts", "ai_vs_ai")
     pub game_mode: String,
+    /// Model version the AI played with (A/B testing)
+    #[serde(default)]
+    pub model_version: Option<String>,
 }
 
 impl GameRecord {
@@ -97,6 +114,7 @@ impl GameRecord {
             final_scores: HashMap::new(),
             human_won: false,
             game_mode,
+            model_version: None,
         }
     }
 
@@ -174,6 +192,68 @@ pub fn encode_plateau(tiles: &[crate::game::tile::Tile]) -> Vec<i32> {
         .collect()
 }
 
+/// The `k` positions with the largest visit share (ties broken by position).
+/// Positions never visited are skipped.
+pub fn top_k_candidates(
+    visit_shares: &[f32],
+    q_values: Option<&[f32]>,
+    k: usize,
+) -> Vec<MoveCandidate> {
+    let mut candidates: Vec<MoveCandidate> = visit_shares
+        .iter()
+        .enumerate()
+        .filter(|(_, &share)| share > 0.0)
+        .map(|(position, &visit_share)| MoveCandidate {
+            position,
+            visit_share,
+            q_value: q_values.and_then(|q| q.get(position).copied()),
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.visit_share
+            .partial_cmp(&a.visit_share)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.position.cmp(&b.position))
+    });
+    candidates.truncate(k);
+    candidates
+}
+
+/// Encodes candidates for a single CSV cell: `pos:share:q|pos:share:q` (empty q if absent)
+pub fn encode_candidates(candidates: &[MoveCandidate]) -> String {
+    candidates
+        .iter()
+        .map(|c| {
+            let q = c.q_value.map(|q| q.to_string()).unwrap_or_default();
+            format!("{}:{}:{}", c.position, c.visit_share, q)
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Inverse of [`encode_candidates`]; `None` for an empty or malformed cell
+pub fn decode_candidates(cell: &str) -> Option<Vec<MoveCandidate>> {
+    if cell.is_empty() {
+        return None;
+    }
+    cell.split('|')
+        .map(|entry| {
+            let mut fields = entry.split(':');
+            let position = fields.next()?.parse().ok()?;
+            let visit_share = fields.next()?.parse().ok()?;
+            let q_value = match fields.next()? {
+                "" => None,
+                q => Some(q.parse().ok()?),
+            };
+            Some(MoveCandidate {
+                position,
+                visit_share,
+                q_value,
+            })
+        })
+        .collect()
+}
+
 /// Decodes a flat vector back to tile values
 pub fn decode_plateau_value(encoded: i32) -> (i32, i32, i32) {
     let v0 = encoded / 100;
@@ -209,4 +289,47 @@ mod tests {
         assert_eq!(record.human_score(), Some(150));
         assert_eq!(record.best_ai_score(), Some(120));
     }
+
+    #[test]
+    fn test_top_k_candidates_round_trip() {
+        let mut shares = vec![0.0f32; 19];
+        shares[4] = 0.5;
+        shares[9] = 0.25;
+        shares[2] = 0.25;
+        shares[11] = 0.125;
+        let mut q_values = vec![0.0f32; 19];
+        q_values[4] = 151.5;
+        q_values[2] = 140.0;
+
+        let candidates = top_k_candidates(&shares, Some(&q_values), 3);
+        let positions: Vec<usize> = candidates.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![4, 2, 9]);
+        assert_eq!(candidates[0].q_value, Some(151.5));
+
+        let cell = encode_candidates(&candidates);
+        assert_eq!(decode_candidates(&cell), Some(candidates.clone()));
+
+        let without_q = top_k_candidates(&shares, None, 1);
+        assert_eq!(
+            decode_candidates(&encode_candidates(&without_q)),
+            Some(without_q)
+        );
+        assert_eq!(decode_candidates(""), None);
+
+        // JSON form of a move record keeps the candidates
+        let move_record = MoveRecord {
+            turn: 3,
+            player_id: "mcts_ai".to_string(),
+            player_type: PlayerType::Mcts,
+            plateau_before: vec![0; 19],
+            tile: (1, 2, 3),
+            position: 4,
+            mcts_evaluation: Some(0.5),
+            timestamp: 0,
+            candidates: Some(candidates),
+        };
+        let json = serde_json::to_string(&move_record).unwrap();
+        let back: MoveRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.candidates, move_record.candidates);
+    }
 }
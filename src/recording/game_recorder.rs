@@ -5,30 +5,82 @@
 
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
-use crate::recording::csv_writer::CsvWriter;
-use crate::recording::game_record::{encode_plateau, GameRecord, MoveRecord, PlayerType};
+use crate::mcts::mcts_result::MCTSResult;
+use crate::neural::training::policy_target::tensor_to_distribution;
+use crate::recording::background_writer::{BackgroundWriter, WRITE_QUEUE_CAPACITY};
+use crate::recording::csv_writer::{CsvWriter, ExtraColumns};
+use crate::recording::game_record::{
+    encode_plateau, top_k_candidates, GameRecord, MoveRecord, PlayerType,
+};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+/// Optional data kept with each recording
+#[derive(Debug, Clone, Default)]
+pub struct RecorderOptions {
+    /// Number of AI candidates kept per move (0 = not recorded)
+    pub candidates_top_k: usize,
+    /// Write the model version of each game (A/B testing)
+    pub model_version_column: bool,
+    /// Write finished games from a background task instead of the caller's
+    /// thread (needs a tokio runtime, synchronous writes otherwise)
+    pub background_writes: bool,
+}
+
 /// Thread-safe game recorder
 pub struct GameRecorder {
     /// Active game records, indexed by session_id
     active_games: Mutex<HashMap<String, GameRecord>>,
     /// CSV writer for persisting completed games
-    csv_writer: Mutex<CsvWriter>,
+    csv_writer: Arc<Mutex<CsvWriter>>,
+    /// Queue of the background writer, if finished games are written there
+    background: Option<BackgroundWriter>,
     /// Whether recording is enabled
     enabled: bool,
+    /// Number of AI candidates kept per move (0 = not recorded)
+    candidates_top_k: usize,
+    /// Model version tags, indexed by session_id (applied at finalization)
+    model_versions: Mutex<HashMap<String, String>>,
 }
 
 impl GameRecorder {
     /// Create a new game recorder
     pub fn new<P: AsRef<Path>>(output_dir: P) -> std::io::Result<Self> {
-        let csv_writer = CsvWriter::new(output_dir)?;
+        Self::with_options(output_dir, RecorderOptions::default())
+    }
+
+    /// Create a recorder that also keeps the AI's top-k candidate positions
+    /// for each MCTS move and/or the model version of each game. Such
+    /// recordings go to separate CSV files (see [`ExtraColumns`]).
+    pub fn with_options<P: AsRef<Path>>(
+        output_dir: P,
+        options: RecorderOptions,
+    ) -> std::io::Result<Self> {
+        let csv_writer = CsvWriter::with_columns(
+            output_dir,
+            ExtraColumns {
+                candidates: options.candidates_top_k > 0,
+                model_version: options.model_version_column,
+            },
+        )?;
+        let csv_writer = Arc::new(Mutex::new(csv_writer));
+        let background = if options.background_writes {
+            let writer = BackgroundWriter::spawn(csv_writer.clone(), WRITE_QUEUE_CAPACITY);
+            if writer.is_none() {
+                log::warn!("No tokio runtime, recorded games are written synchronously");
+            }
+            writer
+        } else {
+            None
+        };
         Ok(Self {
             active_games: Mutex::new(HashMap::new()),
-            csv_writer: Mutex::new(csv_writer),
+            csv_writer,
+            background,
             enabled: true,
+            candidates_top_k: options.candidates_top_k,
+            model_versions: Mutex::new(HashMap::new()),
         })
     }
 
@@ -36,8 +88,11 @@ impl GameRecorder {
     pub fn disabled() -> Self {
         Self {
             active_games: Mutex::new(HashMap::new()),
-            csv_writer: Mutex::new(CsvWriter::new("/dev/null").unwrap()),
+            csv_writer: Arc::new(Mutex::new(CsvWriter::new("/dev/null").unwrap())),
+            background: None,
             enabled: false,
+            candidates_top_k: 0,
+            model_versions: Mutex::new(HashMap::new()),
         }
     }
 
@@ -93,6 +148,7 @@ impl GameRecorder {
             position,
             mcts_evaluation,
             timestamp: chrono::Utc::now().timestamp(),
+            candidates: None,
         };
 
         let mut games = self.active_games.lock().unwrap();
@@ -110,7 +166,52 @@ impl GameRecorder {
         }
     }
 
-    /// Finalize and save a completed game
+    /// Attach the MCTS candidates to the last recorded move of `player_id`.
+    /// No-op unless the recorder was created with a candidate count.
+    pub fn record_mcts_candidates(&self, session_id: &str, player_id: &str, result: &MCTSResult) {
+        if !self.enabled || self.candidates_top_k == 0 {
+            return;
+        }
+
+        let visit_shares = tensor_to_distribution(&result.policy_distribution);
+        let q_values = result
+            .q_value_distribution
+            .as_ref()
+            .map(tensor_to_distribution);
+        let candidates =
+            top_k_candidates(&visit_shares, q_values.as_deref(), self.candidates_top_k);
+
+        let mut games = self.active_games.lock().unwrap();
+        let last_move = games.get_mut(session_id).and_then(|record| {
+            record
+                .moves
+                .iter_mut()
+                .rev()
+                .find(|m| m.player_id == player_id)
+        });
+        match last_move {
+            Some(move_record) => move_record.candidates = Some(candidates),
+            None => log::warn!(
+                "No recorded move to attach candidates to: game={}, player={}",
+                session_id,
+                player_id
+            ),
+        }
+    }
+
+    /// Tag a game with the model version the AI plays with. May be called
+    /// before the game is started; the tag is applied when it is finalized.
+    pub fn tag_model_version(&self, session_id: &str, version: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut versions = self.model_versions.lock().unwrap();
+        versions.insert(session_id.to_string(), version.to_string());
+    }
+
+    /// Finalize and save a completed game. With background writes the game
+    /// is only queued (see [`sync_writes`](Self::sync_writes)).
     pub fn finalize_game(
         &self,
         session_id: &str,
@@ -125,16 +226,24 @@ impl GameRecorder {
             games.remove(session_id)
         };
 
+        let model_version = self.model_versions.lock().unwrap().remove(session_id);
+
         if let Some(mut record) = record {
             record.finalize(final_scores);
+            record.model_version = model_version;
 
             let human_won = record.human_won;
             let human_score = record.human_score();
             let ai_score = record.best_ai_score();
 
-            {
-                let mut writer = self.csv_writer.lock().unwrap();
-                writer.write_game(&record)?;
+            match &self.background {
+                Some(background) => {
+                    background.submit(record);
+                }
+                None => {
+                    let mut writer = self.csv_writer.lock().unwrap();
+                    writer.write_game(&record)?;
+                }
             }
 
             log::info!(
@@ -157,6 +266,7 @@ impl GameRecorder {
             return;
         }
 
+        self.model_versions.lock().unwrap().remove(session_id);
         let mut games = self.active_games.lock().unwrap();
         if games.remove(session_id).is_some() {
             log::info!("Cancelled recording for game: {}", session_id);
@@ -168,6 +278,14 @@ impl GameRecorder {
         self.active_games.lock().unwrap().len()
     }
 
+    /// Wait until the games finalized so far are written (immediate without
+    /// background writes).
+    pub async fn sync_writes(&self) {
+        if let Some(background) = &self.background {
+            background.sync().await;
+        }
+    }
+
     /// Flush any pending writes
     pub fn flush(&self) -> std::io::Result<()> {
         if self.enabled {
@@ -183,7 +301,15 @@ static GAME_RECORDER: std::sync::OnceLock<Arc<GameRecorder>> = std::sync::OnceLo
 
 /// Initialize the global game recorder
 pub fn init_recorder<P: AsRef<Path>>(output_dir: P) -> std::io::Result<()> {
-    let recorder = GameRecorder::new(output_dir)?;
+    init_recorder_with_options(output_dir, RecorderOptions::default())
+}
+
+/// Initialize the global game recorder with optional per-game data
+pub fn init_recorder_with_options<P: AsRef<Path>>(
+    output_dir: P,
+    options: RecorderOptions,
+) -> std::io::Result<()> {
+    let recorder = GameRecorder::with_options(output_dir, options)?;
     GAME_RECORDER
         .set(Arc::new(recorder))
         .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Recorder already initialized"))
@@ -211,6 +337,7 @@ pub fn determine_player_type(player_id: &str, is_mcts: bool, is_hybrid: bool) ->
 mod tests {
     use super::*;
     use crate::game::plateau::create_plateau_empty;
+    use crate::recording::csv_writer::load_games_from_csv;
     use tempfile::tempdir;
 
     #[test]
@@ -266,6 +393,67 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_background_writes_keep_every_game_whole() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let dir = tempdir()?;
+        let options = RecorderOptions {
+            background_writes: true,
+            ..Default::default()
+        };
+        let recorder = Arc::new(GameRecorder::with_options(dir.path(), options)?);
+        let games = 20;
+        let turns = 19;
+
+        let handles: Vec<_> = (0..games)
+            .map(|g| {
+                let recorder = recorder.clone();
+                tokio::spawn(async move {
+                    let session_id = format!("game-{}", g);
+                    recorder.start_game(
+                        &session_id,
+                        "human_vs_mcts",
+                        vec![("human".to_string(), PlayerType::Human)],
+                    );
+                    let plateau = create_plateau_empty();
+                    for turn in 0..turns {
+                        recorder.record_move(
+                            &session_id,
+                            turn,
+                            "human",
+                            PlayerType::Human,
+                            &plateau,
+                            &Tile(1, 2, 3),
+                            turn,
+                            None,
+                        );
+                        tokio::task::yield_now().await;
+                    }
+                    let scores = HashMap::from([("human".to_string(), g as i32)]);
+                    recorder.finalize_game(&session_id, scores).unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await?;
+        }
+        recorder.sync_writes().await;
+
+        let file = std::fs::read_dir(dir.path())?.next().unwrap()?.path();
+        let rows = load_games_from_csv(file)?;
+        assert_eq!(rows.len(), games * turns);
+        // Each game is written in one piece, turns in order
+        for chunk in rows.chunks(turns) {
+            assert!(chunk.iter().all(|row| row.game_id == chunk[0].game_id));
+            let written: Vec<usize> = chunk.iter().map(|row| row.turn).collect();
+            assert_eq!(written, (0..turns).collect::<Vec<_>>());
+        }
+        let mut ids: Vec<&str> = rows.iter().map(|row| row.game_id.as_str()).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), games);
+        Ok(())
+    }
+
     #[test]
     fn test_disabled_recorder() {
         let recorder = GameRecorder::disabled();
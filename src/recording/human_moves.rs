@@ -0,0 +1,198 @@
+//! Human-like opponent built from recorded human moves.
+//!
+//! The book keeps the human moves of the recorded games. Given a board and a
+//! tile, it plays the position a human chose in the closest recorded
+//! situation: same cells and tile first, then the fewest differing cells and
+//! tile values. When several humans were in that situation, the most common
+//! choice wins. Only moves that are legal on the current board are considered.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+use crate::recording::csv_writer::{load_games_from_csv, LoadedMoveRecord};
+use crate::recording::game_record::{encode_plateau, PlayerType};
+
+/// One recorded human decision
+#[derive(Debug, Clone, PartialEq)]
+struct BookMove {
+    plateau: Vec<i32>,
+    tile: (i32, i32, i32),
+    position: usize,
+}
+
+/// Recorded human moves, looked up by nearest board
+#[derive(Debug, Clone, Default)]
+pub struct HumanMoveBook {
+    moves: Vec<BookMove>,
+}
+
+impl HumanMoveBook {
+    /// Book of the human moves among `records` (AI moves are ignored)
+    pub fn from_records(records: &[LoadedMoveRecord]) -> Self {
+        let moves = records
+            .iter()
+            .filter(|r| r.player_type == PlayerType::Human && r.position < 19)
+            .map(|r| {
+                let mut plateau = r.plateau.clone();
+                plateau.resize(19, 0);
+                BookMove {
+                    plateau,
+                    tile: r.tile,
+                    position: r.position,
+                }
+            })
+            .collect();
+        Self { moves }
+    }
+
+    /// Load a recording CSV, or every `.csv` of a recording directory
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let mut records = Vec::new();
+        if path.is_dir() {
+            let mut files: Vec<_> = std::fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "csv"))
+                .collect();
+            files.sort();
+            for file in files {
+                records.extend(load_games_from_csv(file)?);
+            }
+        } else {
+            records = load_games_from_csv(path)?;
+        }
+        Ok(Self::from_records(&records))
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Position a human played in the recorded situation closest to
+    /// (`plateau`, `tile`), `None` if no recorded move is legal here
+    pub fn choose(&self, plateau: &Plateau, tile: &Tile) -> Option<usize> {
+        let board = encode_plateau(&plateau.tiles);
+        let tile = (tile.0, tile.1, tile.2);
+
+        let mut best_distance = usize::MAX;
+        let mut votes: HashMap<usize, usize> = HashMap::new();
+        for m in &self.moves {
+            if board.get(m.position) != Some(&0) {
+                continue;
+            }
+            let distance = board_distance(&board, &m.plateau) + tile_distance(tile, m.tile);
+            if distance < best_distance {
+                best_distance = distance;
+                votes.clear();
+            }
+            if distance == best_distance {
+                *votes.entry(m.position).or_default() += 1;
+            }
+        }
+
+        // Most common choice, lowest position on ties
+        votes
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+            .map(|(position, _)| position)
+    }
+}
+
+/// 0 for an identical cell, 1 for two different tiles, 2 when only one side
+/// has a tile
+fn board_distance(a: &[i32], b: &[i32]) -> usize {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| match (x == y, x == 0 || y == 0) {
+            (true, _) => 0,
+            (false, false) => 1,
+            (false, true) => 2,
+        })
+        .sum()
+}
+
+/// Number of differing tile values
+fn tile_distance(a: (i32, i32, i32), b: (i32, i32, i32)) -> usize {
+    [a.0 != b.0, a.1 != b.1, a.2 != b.2]
+        .iter()
+        .filter(|&&d| d)
+        .count()
+}
+
+/// Global book used by sessions created with the human-like opponent
+static HUMAN_MOVE_BOOK: OnceLock<Arc<HumanMoveBook>> = OnceLock::new();
+
+/// Load the global book from `path`; returns the number of human moves
+pub fn init_human_move_book<P: AsRef<Path>>(path: P) -> Result<usize, Box<dyn Error>> {
+    let book = HumanMoveBook::load(path)?;
+    let moves = book.len();
+    HUMAN_MOVE_BOOK
+        .set(Arc::new(book))
+        .map_err(|_| "Human move book already initialized")?;
+    Ok(moves)
+}
+
+/// Get the global book (`None` if no recordings were loaded)
+pub fn get_human_move_book() -> Option<Arc<HumanMoveBook>> {
+    HUMAN_MOVE_BOOK.get().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    fn record(
+        player_type: PlayerType,
+        plateau: &Plateau,
+        tile: Tile,
+        position: usize,
+    ) -> LoadedMoveRecord {
+        LoadedMoveRecord {
+            game_id: "g".to_string(),
+            turn: 2,
+            player_type,
+            plateau: encode_plateau(&plateau.tiles),
+            tile: (tile.0, tile.1, tile.2),
+            position,
+            final_score: 120,
+            human_won: false,
+            candidates: None,
+            model_version: None,
+        }
+    }
+
+    #[test]
+    fn test_reproduces_human_move_on_recorded_board() {
+        let mut recorded = create_plateau_empty();
+        recorded.tiles[0] = Tile(9, 6, 3);
+        recorded.tiles[7] = Tile(5, 2, 4);
+        let mut other = create_plateau_empty();
+        other.tiles[18] = Tile(1, 6, 8);
+
+        let book = HumanMoveBook::from_records(&[
+            record(PlayerType::Human, &other, Tile(1, 2, 3), 4),
+            record(PlayerType::Human, &recorded, Tile(5, 7, 8), 11),
+            record(PlayerType::Mcts, &recorded, Tile(5, 7, 8), 3),
+        ]);
+        assert_eq!(book.len(), 2);
+
+        assert_eq!(book.choose(&recorded, &Tile(5, 7, 8)), Some(11));
+        // Occupied recorded position: falls back to the next closest situation
+        let mut blocked = recorded.clone();
+        blocked.tiles[11] = Tile(1, 7, 4);
+        assert_eq!(book.choose(&blocked, &Tile(5, 7, 8)), Some(4));
+        assert_eq!(
+            HumanMoveBook::default().choose(&recorded, &Tile(5, 7, 8)),
+            None
+        );
+    }
+}
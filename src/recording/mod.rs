@@ -7,11 +7,16 @@
 //!
 //! - `game_record`: Data structures for game records
 //! - `game_recorder`: Thread-safe game recording service
+//! - `background_writer`: Writes finished games off the request path
 //! - `csv_writer`: CSV output for training data
+//! - `human_moves`: Human-like opponent replaying recorded human moves
 
+pub mod background_writer;
 pub mod csv_writer;
 pub mod game_record;
 pub mod game_recorder;
+pub mod human_moves;
 
 pub use game_record::PlayerType;
-pub use game_recorder::{get_recorder, init_recorder};
+pub use game_recorder::{get_recorder, init_recorder, init_recorder_with_options, RecorderOptions};
+pub use human_moves::{get_human_move_book, init_human_move_book, HumanMoveBook};
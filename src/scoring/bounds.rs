@@ -0,0 +1,121 @@
+//! Optimistic bound on the final score of a game in progress.
+//!
+//! Each line is bounded on its own: a line already holding two different
+//! values is dead, a line holding one value `v` can still score `v × len` if
+//! the deck has enough tiles showing `v` for its empty cells, and an empty
+//! line can take the best value the deck can fill it with. Lines sharing cells
+//! are not reconciled, so the bound is admissible but not tight; unlike
+//! [`score_upper_bound`](super::max_score::score_upper_bound) it accounts for
+//! the tiles already on the board.
+
+use crate::game::board_geometry::{line_value, LINES};
+use crate::game::deck::Deck;
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+
+/// Upper bound of the final score reachable from `plateau` with the tiles
+/// left in `deck` (every tile that may still be placed, including the one in
+/// hand). Never lower than the score of any completion of the board.
+pub fn max_remaining_score(plateau: &Plateau, deck: &Deck) -> i32 {
+    LINES
+        .iter()
+        .map(|(positions, direction)| line_bound(plateau, deck, positions, *direction))
+        .sum()
+}
+
+/// Best points line `positions` can still score.
+fn line_bound(plateau: &Plateau, deck: &Deck, positions: &[usize], direction: usize) -> i32 {
+    let mut placed_value = None;
+    let mut empty = 0;
+    for &position in positions {
+        let tile = &plateau.tiles[position];
+        if *tile == Tile(0, 0, 0) {
+            empty += 1;
+            continue;
+        }
+        let value = line_value(tile, direction);
+        match placed_value {
+            Some(v) if v != value => return 0,
+            _ => placed_value = Some(value),
+        }
+    }
+
+    let available = |value: i32| {
+        deck.tiles
+            .iter()
+            .filter(|t| **t != Tile(0, 0, 0) && line_value(t, direction) == value)
+            .count()
+    };
+    let length = positions.len() as i32;
+    match placed_value {
+        Some(value) if available(value) >= empty => value * length,
+        Some(_) => 0,
+        None => deck
+            .tiles
+            .iter()
+            .filter(|t| **t != Tile(0, 0, 0))
+            .map(|t| line_value(t, direction))
+            .filter(|&value| available(value) >= empty)
+            .max()
+            .map_or(0, |value| value * length),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+    use crate::scoring::scoring::result;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bound_is_never_below_a_reachable_score() {
+        let mut rng = StdRng::seed_from_u64(1256);
+        for _ in 0..200 {
+            let mut tiles = create_deck().tiles;
+            tiles.shuffle(&mut rng);
+            let mut positions: Vec<usize> = (0..19).collect();
+            positions.shuffle(&mut rng);
+
+            // Every prefix of a random game bounds the score it ends with
+            let mut plateau = create_plateau_empty();
+            let mut deck = create_deck();
+            let mut bounds = vec![max_remaining_score(&plateau, &deck)];
+            for (&position, tile) in positions.iter().zip(&tiles) {
+                plateau.tiles[position] = *tile;
+                deck = replace_tile_in_deck(&deck, tile);
+                bounds.push(max_remaining_score(&plateau, &deck));
+            }
+            let final_score = result(&plateau);
+            assert!(bounds.iter().all(|&bound| bound >= final_score));
+            assert_eq!(*bounds.last().unwrap(), final_score);
+        }
+    }
+
+    #[test]
+    fn test_dead_and_unfillable_lines_score_nothing() {
+        let mut plateau = create_plateau_empty();
+        // Row 0 mixes 1 and 5: dead
+        plateau.tiles[0] = Tile(1, 2, 3);
+        plateau.tiles[1] = Tile(5, 2, 4);
+        let deck = replace_tile_in_deck(&create_deck(), &Tile(1, 2, 3));
+        let deck = replace_tile_in_deck(&deck, &Tile(5, 2, 4));
+        assert_eq!(line_bound(&plateau, &deck, LINES[0].0, 0), 0);
+
+        // Empty 5-cell row: 9 tiles of each row value remain, so 9 × 5
+        assert_eq!(line_bound(&plateau, &deck, LINES[2].0, 0), 45);
+        // Only 2 tiles left in the deck: no 3-cell line can be completed
+        let mut small = deck.clone();
+        let keep = [Tile(9, 7, 8), Tile(9, 6, 8)];
+        for tile in small.tiles.iter_mut() {
+            if !keep.contains(tile) {
+                *tile = Tile(0, 0, 0);
+            }
+        }
+        assert_eq!(line_bound(&plateau, &small, LINES[4].0, 0), 0);
+    }
+}
@@ -0,0 +1,98 @@
+//! Incremental scoring.
+//!
+//! A placement only changes the 3 lines through its cell, so the score of a
+//! board being filled can be maintained in O(1) per placement instead of
+//! rescoring the 15 lines with [`result`](super::scoring::result).
+
+use crate::game::board_geometry::{line_value, lines_through, LINES};
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+use crate::scoring::scoring::result;
+
+/// Points of line `line_idx`, reading `tile` at `position` instead of the
+/// board cell.
+fn line_points_with(plateau: &Plateau, line_idx: usize, position: usize, tile: &Tile) -> i32 {
+    let (indices, direction) = LINES[line_idx];
+    let value_at = |i: usize| {
+        let cell = if i == position {
+            tile
+        } else {
+            &plateau.tiles[i]
+        };
+        line_value(cell, direction)
+    };
+    let first = value_at(indices[0]);
+    if indices.iter().all(|&i| value_at(i) == first) {
+        first * indices.len() as i32
+    } else {
+        0
+    }
+}
+
+/// Change of `result(plateau)` when `tile` is put at `position`.
+pub fn score_delta(plateau: &Plateau, position: usize, tile: &Tile) -> i32 {
+    let current = plateau.tiles[position];
+    lines_through(position)
+        .iter()
+        .map(|&line_idx| {
+            line_points_with(plateau, line_idx, position, tile)
+                - line_points_with(plateau, line_idx, position, &current)
+        })
+        .sum()
+}
+
+/// Score of a board kept up to date placement by placement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialScore {
+    score: i32,
+}
+
+impl PartialScore {
+    /// Start from the full score of `plateau`.
+    pub fn new(plateau: &Plateau) -> Self {
+        Self {
+            score: result(plateau),
+        }
+    }
+
+    /// Put `tile` at `position` and update the score.
+    pub fn place(&mut self, plateau: &mut Plateau, position: usize, tile: Tile) {
+        self.score += score_delta(plateau, position, &tile);
+        plateau.tiles[position] = tile;
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_incremental_score_matches_full_rescoring() {
+        let mut rng = StdRng::seed_from_u64(1255);
+        for _ in 0..50 {
+            let mut tiles = create_deck().tiles;
+            tiles.shuffle(&mut rng);
+            let mut positions: Vec<usize> = (0..19).collect();
+            positions.shuffle(&mut rng);
+
+            let mut plateau = create_plateau_empty();
+            let mut score = PartialScore::new(&plateau);
+            for (&position, &tile) in positions.iter().zip(&tiles) {
+                let before = result(&plateau);
+                let delta = score_delta(&plateau, position, &tile);
+                score.place(&mut plateau, position, tile);
+                assert_eq!(result(&plateau) - before, delta);
+                assert_eq!(score.score(), result(&plateau));
+            }
+        }
+    }
+}
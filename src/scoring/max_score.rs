@@ -0,0 +1,296 @@
+//! Best achievable score for a known set of tiles, ignoring draw order.
+//!
+//! Exhaustive search over the 19! placements is out of reach, so the answer is
+//! bracketed:
+//! - an admissible upper bound: each direction's 5 parallel lines partition the
+//!   board, so per direction the best choice of line values whose lengths fit
+//!   the tile counts bounds what any board can score in that direction;
+//! - a heuristic lower bound: the best board found by an iterated local search
+//!   over cell swaps, started from boards realizing each direction's best line
+//!   assignment and from seeded random shuffles. It is an actual arrangement
+//!   of the tiles.
+//!
+//! When both meet the lower bound is the proven optimum.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+use crate::game::board_geometry::{line_value, LINES};
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::tile::Tile;
+use crate::scoring::scoring::line_scores;
+
+const RESTARTS: usize = 8;
+const KICKS_PER_RESTART: usize = 60;
+const KICK_SWAPS: usize = 3;
+const SEARCH_SEED: u64 = 19;
+
+#[derive(Debug, Clone)]
+pub struct ScoreBounds {
+    /// Score of `best_plateau`, achievable with these tiles
+    pub lower: i32,
+    /// No arrangement of these tiles scores more
+    pub upper: i32,
+    pub best_plateau: Plateau,
+}
+
+impl ScoreBounds {
+    /// `lower` is the optimum
+    pub fn is_exact(&self) -> bool {
+        self.lower == self.upper
+    }
+}
+
+/// Best score found for `tiles` (at most 19, empty tiles ignored): the
+/// reference for "you scored X of a possible Y". Exact whenever
+/// [`score_bounds`] reports matching bounds.
+pub fn max_possible_score(tiles: &[Tile]) -> i32 {
+    score_bounds(tiles).lower
+}
+
+/// Upper and lower bounds of the best score for `tiles` (at most 19).
+pub fn score_bounds(tiles: &[Tile]) -> ScoreBounds {
+    assert!(tiles.len() <= 19, "at most 19 tiles fit on the board");
+    let upper = score_upper_bound(tiles);
+
+    let mut cells = create_plateau_empty();
+    for (cell, tile) in cells.tiles.iter_mut().zip(tiles) {
+        *cell = *tile;
+    }
+
+    let mut rng = StdRng::seed_from_u64(SEARCH_SEED);
+    let mut best_plateau = cells.clone();
+    let mut best = board_score(&best_plateau);
+
+    for restart in 0..RESTARTS {
+        if best == upper {
+            break;
+        }
+        // First restarts complete the lines of one direction's best assignment
+        if restart < 3 {
+            cells = direction_seed(tiles, restart);
+        } else {
+            cells.tiles.shuffle(&mut rng);
+        }
+        let mut current = hill_climb(&mut cells);
+
+        for _ in 0..KICKS_PER_RESTART {
+            if current > best {
+                best = current;
+                best_plateau = cells.clone();
+            }
+            if best == upper {
+                break;
+            }
+
+            let mut kicked = cells.clone();
+            for _ in 0..KICK_SWAPS {
+                let (a, b) = (rng.random_range(0..19), rng.random_range(0..19));
+                kicked.tiles.swap(a, b);
+            }
+            let score = hill_climb(&mut kicked);
+            if score >= current {
+                cells = kicked;
+                current = score;
+            }
+        }
+        if current > best {
+            best = current;
+            best_plateau = cells.clone();
+        }
+    }
+
+    ScoreBounds {
+        lower: best,
+        upper,
+        best_plateau,
+    }
+}
+
+/// Admissible bound: sum over the 3 directions of the best assignment of
+/// values to that direction's lines, a value `v` covering at most as many
+/// cells as there are tiles showing `v` in that direction.
+pub fn score_upper_bound(tiles: &[Tile]) -> i32 {
+    (0..3)
+        .map(|direction| direction_assignment(tiles, direction).0)
+        .sum()
+}
+
+/// Lines of `direction`, in `LINES` order.
+fn direction_lines(direction: usize) -> impl Iterator<Item = &'static [usize]> {
+    LINES
+        .iter()
+        .filter(move |(_, d)| *d == direction)
+        .map(|(positions, _)| *positions)
+}
+
+/// Best points for the lines of `direction` alone, with the value given to
+/// each line (`None` = left open).
+fn direction_assignment(tiles: &[Tile], direction: usize) -> (i32, Vec<Option<i32>>) {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for tile in tiles {
+        let value = line_value(tile, direction);
+        if value != 0 {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+    }
+    let mut counts: Vec<(i32, usize)> = counts.into_iter().collect();
+    counts.sort_unstable();
+    let lengths: Vec<usize> = direction_lines(direction).map(<[usize]>::len).collect();
+    assign_lines(&lengths, &mut counts)
+}
+
+/// Max of `sum(len * value)` over lines left open or given a value with
+/// enough remaining tiles.
+fn assign_lines(lengths: &[usize], counts: &mut [(i32, usize)]) -> (i32, Vec<Option<i32>>) {
+    let Some((&length, rest)) = lengths.split_first() else {
+        return (0, Vec::new());
+    };
+    let (mut best, mut best_values) = assign_lines(rest, counts);
+    let mut best_first = None;
+    for i in 0..counts.len() {
+        let (value, available) = counts[i];
+        if available >= length {
+            counts[i].1 -= length;
+            let (score, values) = assign_lines(rest, counts);
+            counts[i].1 += length;
+            if value * length as i32 + score > best {
+                best = value * length as i32 + score;
+                best_first = Some(value);
+                best_values = values;
+            }
+        }
+    }
+    best_values.insert(0, best_first);
+    (best, best_values)
+}
+
+/// Board completing the lines of `direction` as in its best assignment, the
+/// remaining tiles filling the other cells.
+fn direction_seed(tiles: &[Tile], direction: usize) -> Plateau {
+    let (_, values) = direction_assignment(tiles, direction);
+    let mut pool = tiles.to_vec();
+    let mut plateau = create_plateau_empty();
+    let mut filled = [false; 19];
+
+    for (positions, value) in direction_lines(direction).zip(values) {
+        let Some(value) = value else {
+            continue;
+        };
+        for &position in positions {
+            let idx = pool
+                .iter()
+                .position(|t| line_value(t, direction) == value)
+                .expect("assignment fits the tile counts");
+            plateau.tiles[position] = pool.swap_remove(idx);
+            filled[position] = true;
+        }
+    }
+    for position in (0..19).filter(|&p| !filled[p]) {
+        if let Some(tile) = pool.pop() {
+            plateau.tiles[position] = tile;
+        }
+    }
+    plateau
+}
+
+fn board_score(plateau: &Plateau) -> i32 {
+    line_scores(plateau).iter().sum()
+}
+
+/// Apply improving swaps until none is left; returns the local optimum score.
+fn hill_climb(plateau: &mut Plateau) -> i32 {
+    let mut score = board_score(plateau);
+    loop {
+        let mut improved = false;
+        for a in 0..19 {
+            for b in (a + 1)..19 {
+                if plateau.tiles[a] == plateau.tiles[b] {
+                    continue;
+                }
+                plateau.tiles.swap(a, b);
+                let swapped = board_score(plateau);
+                if swapped > score {
+                    score = swapped;
+                    improved = true;
+                } else {
+                    plateau.tiles.swap(a, b);
+                }
+            }
+        }
+        if !improved {
+            return score;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::remove_tile_from_deck::get_available_tiles;
+    use crate::scoring::scoring::result;
+
+    fn sorted(tiles: &[Tile]) -> Vec<Tile> {
+        let mut tiles: Vec<Tile> = tiles
+            .iter()
+            .copied()
+            .filter(|t| *t != Tile(0, 0, 0))
+            .collect();
+        tiles.sort_by_key(|t| (t.0, t.1, t.2));
+        tiles
+    }
+
+    #[test]
+    fn test_identical_tiles_complete_every_line() {
+        // 19 × (9,7,8): all 15 lines score, 19 × (9 + 7 + 8)
+        let tiles = vec![Tile(9, 7, 8); 19];
+        let bounds = score_bounds(&tiles);
+        assert_eq!(bounds.upper, 456);
+        assert_eq!(bounds.lower, 456);
+        assert!(bounds.is_exact());
+    }
+
+    #[test]
+    fn test_three_tiles_complete_the_best_short_line() {
+        // Two lines share at most one cell, so 3 tiles complete one 3-cell
+        // line: the diagonal reading 6 beats the row (5) and the other diagonal (4)
+        let tiles = vec![Tile(5, 6, 4); 3];
+        assert_eq!(max_possible_score(&tiles), 18);
+        assert!(score_upper_bound(&tiles) >= 18);
+    }
+
+    #[test]
+    fn test_rows_plus_best_diagonal_triple() {
+        // Row value 9 everywhere: the 5 rows always score 9 × 19. Diagonal
+        // values repeat at most twice, except tiles 0, 9 and 18 = (9, 1, 5),
+        // which complete one 3-cell diagonal: the v3 one (15) beats v2 (3),
+        // and 3 tiles cannot complete both
+        let tiles: Vec<Tile> = (0..19)
+            .map(|i| Tile(9, 1 + (i % 9) as i32, 1 + ((i + 4) % 9) as i32))
+            .collect();
+        let bounds = score_bounds(&tiles);
+        assert_eq!(bounds.lower, 171 + 15);
+        // The bound counts both diagonals independently
+        assert_eq!(bounds.upper, 171 + 15 + 3);
+        assert!(!bounds.is_exact());
+    }
+
+    #[test]
+    fn test_bounds_bracket_an_arrangement_of_the_tiles() {
+        let deck = get_available_tiles(&create_deck());
+        for offset in [0, 4, 8] {
+            let tiles = &deck[offset..offset + 19];
+            let bounds = score_bounds(tiles);
+            assert!(bounds.lower <= bounds.upper);
+            assert_eq!(result(&bounds.best_plateau), bounds.lower);
+            assert_eq!(sorted(&bounds.best_plateau.tiles), sorted(tiles));
+            // At least as good as filling the board in draw order
+            let mut in_order = create_plateau_empty();
+            in_order.tiles.copy_from_slice(tiles);
+            assert!(bounds.lower >= result(&in_order));
+        }
+    }
+}
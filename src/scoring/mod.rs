@@ -1,2 +1,6 @@
+pub mod bounds;
+pub mod incremental;
+pub mod max_score;
+pub mod rules;
 #[allow(clippy::module_inception)]
 pub mod scoring;
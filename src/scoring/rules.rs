@@ -0,0 +1,209 @@
+//! Scoring rules, and the named variants selectable per game session.
+//!
+//! A ruleset weights the points of each line and may add a bonus on top of
+//! the line points. [`ScoringRules::STANDARD`] is the classic game, which
+//! [`result`] delegates to. The same ruleset scores the players and the AI of
+//! a session, and the board evaluation reports its bonus separately.
+//!
+//! [`result`]: crate::scoring::scoring::result
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::plateau::Plateau;
+use crate::scoring::scoring::{count_line_completions, line_scores, LineId};
+
+/// Bonus of the "bonus-complete" variant when every line of the board scores.
+pub const COMPLETE_BOARD_BONUS: i32 = 100;
+
+/// Weight of the 5-tile lines under the "long-lines" variant.
+pub const LONG_LINE_WEIGHT: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoringRules {
+    /// How many times the points of each line of `LINES` count (same index)
+    pub line_weights: [i32; 15],
+    /// Points added when all 15 lines score, 0 = no bonus
+    pub complete_board_bonus: i32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl ScoringRules {
+    /// Line points only
+    pub const STANDARD: ScoringRules = ScoringRules {
+        line_weights: [1; 15],
+        complete_board_bonus: 0,
+    };
+
+    /// Line points, plus `COMPLETE_BOARD_BONUS` when all 15 lines score
+    pub const BONUS_COMPLETE: ScoringRules = ScoringRules {
+        complete_board_bonus: COMPLETE_BOARD_BONUS,
+        ..Self::STANDARD
+    };
+
+    /// Line points, the three 5-tile lines counting `LONG_LINE_WEIGHT` times
+    pub const LONG_LINES: ScoringRules = Self::STANDARD.with_length_weight(5, LONG_LINE_WEIGHT);
+
+    /// Named variants, in the order of [`Self::parse`]
+    const VARIANTS: [(&'static str, ScoringRules); 3] = [
+        ("classic", Self::STANDARD),
+        ("bonus-complete", Self::BONUS_COMPLETE),
+        ("long-lines", Self::LONG_LINES),
+    ];
+
+    /// Same rules, the lines of `length` tiles counting `weight` times.
+    pub const fn with_length_weight(mut self, length: usize, weight: i32) -> Self {
+        let mut line = 0;
+        while line < 15 {
+            if LineId(line).tile_count() == length {
+                self.line_weights[line] = weight;
+            }
+            line += 1;
+        }
+        self
+    }
+
+    /// Named variant `name` ("classic", "bonus-complete", "long-lines");
+    /// empty = classic.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "" => Ok(Self::STANDARD),
+            name => Self::VARIANTS
+                .iter()
+                .find(|(variant, _)| *variant == name)
+                .map(|(_, rules)| *rules)
+                .ok_or_else(|| "INVALID_SCORING_RULES".to_string()),
+        }
+    }
+
+    /// Name of the variant these rules match, "custom" for other weights.
+    pub fn name(&self) -> &'static str {
+        Self::VARIANTS
+            .iter()
+            .find(|(_, rules)| rules == self)
+            .map_or("custom", |(name, _)| name)
+    }
+
+    /// How many times the points of `line` count.
+    pub fn line_weight(&self, line: LineId) -> i32 {
+        self.line_weights[line.0]
+    }
+
+    /// Weighted points of each line of `LINES` (same index).
+    pub fn line_points(&self, plateau: &Plateau) -> [i32; 15] {
+        let mut points = line_scores(plateau);
+        for (line_points, weight) in points.iter_mut().zip(self.line_weights) {
+            *line_points *= weight;
+        }
+        points
+    }
+
+    /// Points added to the line points of `plateau`.
+    pub fn bonus(&self, plateau: &Plateau) -> i32 {
+        if self.complete_board_bonus != 0 && count_line_completions(plateau) == 15 {
+            self.complete_board_bonus
+        } else {
+            0
+        }
+    }
+}
+
+/// Score of `plateau` under `rules`; [`result`] is `STANDARD`.
+///
+/// [`result`]: crate::scoring::scoring::result
+pub fn result_with_rules(plateau: &Plateau, rules: ScoringRules) -> i32 {
+    rules.line_points(plateau).iter().sum::<i32>() + rules.bonus(plateau)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::create_deck::create_deck;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::game::tile::Tile;
+    use crate::scoring::scoring::{result, result_breakdown};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bonus_ruleset_outscores_classic_on_completed_board() {
+        let mut plateau = create_plateau_empty();
+        plateau.tiles = vec![Tile(9, 7, 8); 19];
+
+        let classic = result_with_rules(&plateau, ScoringRules::STANDARD);
+        let bonus = result_with_rules(&plateau, ScoringRules::BONUS_COMPLETE);
+        assert_eq!(classic, result(&plateau));
+        assert_eq!(classic, 456);
+        assert_eq!(bonus, classic + COMPLETE_BOARD_BONUS);
+
+        // A single broken line loses the bonus
+        plateau.tiles[0] = Tile(1, 2, 3);
+        assert_eq!(
+            result_with_rules(&plateau, ScoringRules::BONUS_COMPLETE),
+            result(&plateau)
+        );
+
+        assert_eq!(ScoringRules::parse("").unwrap(), ScoringRules::STANDARD);
+        assert_eq!(
+            ScoringRules::parse("bonus-complete").unwrap(),
+            ScoringRules::BONUS_COMPLETE
+        );
+        assert_eq!(
+            ScoringRules::parse("nope").unwrap_err(),
+            "INVALID_SCORING_RULES"
+        );
+    }
+
+    #[test]
+    fn test_classic_matches_result_and_long_lines_double_the_5_tile_lines() {
+        let mut rng = StdRng::seed_from_u64(1377);
+        for _ in 0..100 {
+            let mut tiles = create_deck().tiles;
+            tiles.shuffle(&mut rng);
+            let mut plateau = create_plateau_empty();
+            plateau.tiles = tiles[..19].to_vec();
+            assert_eq!(
+                result_with_rules(&plateau, ScoringRules::STANDARD),
+                result_breakdown(&plateau).total()
+            );
+        }
+
+        // Every line scores: the 3 lines of 5 tiles (9 × 5 each) count twice
+        let mut plateau = create_plateau_empty();
+        plateau.tiles = vec![Tile(9, 7, 8); 19];
+        let long_lines = result_with_rules(&plateau, ScoringRules::LONG_LINES);
+        assert_eq!(long_lines, 456 + (9 + 7 + 8) * 5);
+        assert_eq!(
+            ScoringRules::parse("long-lines").unwrap(),
+            ScoringRules::LONG_LINES
+        );
+    }
+
+    #[test]
+    fn test_custom_line_weights_apply_per_line() {
+        let mut plateau = create_plateau_empty();
+        plateau.tiles = vec![Tile(9, 7, 8); 19];
+
+        // Only the 3-tile lines count, three times each
+        let rules = ScoringRules {
+            line_weights: [0; 15],
+            complete_board_bonus: 0,
+        }
+        .with_length_weight(3, 3);
+        let three_tile_lines: i32 = result_breakdown(&plateau)
+            .lines
+            .iter()
+            .filter(|(line, _)| line.tile_count() == 3)
+            .map(|(_, points)| points)
+            .sum();
+        assert_eq!(result_with_rules(&plateau, rules), 3 * three_tile_lines);
+        assert_eq!(rules.name(), "custom");
+        assert_eq!(ScoringRules::LONG_LINES.name(), "long-lines");
+        assert_eq!(ScoringRules::default().name(), "classic");
+    }
+}
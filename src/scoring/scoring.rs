@@ -1,32 +1,11 @@
-// Type alias for pattern tuple
-type AlignmentPattern = (&'static [usize], Box<dyn Fn(&Tile) -> i32>);
-
 pub fn compute_alignment_score(plateau: &Plateau, position: usize, _tile: &Tile) -> f64 {
-    let patterns: Vec<AlignmentPattern> = vec![
-        (&[0, 1, 2], Box::new(|t: &Tile| t.0)),
-        (&[3, 4, 5, 6], Box::new(|t: &Tile| t.0)),
-        (&[7, 8, 9, 10, 11], Box::new(|t: &Tile| t.0)),
-        (&[12, 13, 14, 15], Box::new(|t: &Tile| t.0)),
-        (&[16, 17, 18], Box::new(|t: &Tile| t.0)),
-        (&[0, 3, 7], Box::new(|t: &Tile| t.1)),
-        (&[1, 4, 8, 12], Box::new(|t: &Tile| t.1)),
-        (&[2, 5, 9, 13, 16], Box::new(|t: &Tile| t.1)),
-        (&[6, 10, 14, 17], Box::new(|t: &Tile| t.1)),
-        (&[11, 15, 18], Box::new(|t: &Tile| t.1)),
-        (&[7, 12, 16], Box::new(|t: &Tile| t.2)),
-        (&[3, 8, 13, 17], Box::new(|t: &Tile| t.2)),
-        (&[0, 4, 9, 14, 18], Box::new(|t: &Tile| t.2)),
-        (&[1, 5, 10, 15], Box::new(|t: &Tile| t.2)),
-        (&[2, 6, 11], Box::new(|t: &Tile| t.2)),
-    ];
-
     let mut score = 0.0;
 
-    for (indices, selector) in patterns {
+    for (indices, direction) in LINES {
         if indices.contains(&position) {
             let values: Vec<i32> = indices
                 .iter()
-                .map(|&i| selector(&plateau.tiles[i]))
+                .map(|&i| line_value(&plateau.tiles[i], direction))
                 .filter(|&v| v != 0)
                 .collect();
 
@@ -39,43 +18,134 @@ pub fn compute_alignment_score(plateau: &Plateau, position: usize, _tile: &Tile)
 
     score
 }
+use crate::game::board_geometry::{line_value, LINES};
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
+use crate::scoring::rules::{result_with_rules, ScoringRules};
+
+/// A scoring line, identified by its index in `LINES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineId(pub usize);
+
+impl LineId {
+    /// Board positions of the line.
+    pub fn positions(&self) -> &'static [usize] {
+        LINES[self.0].0
+    }
+
+    /// Tile value read by the line: 0 = tile.0 (rows), 1 = tile.1, 2 = tile.2.
+    pub fn direction(&self) -> usize {
+        LINES[self.0].1
+    }
+
+    /// Number of tiles on the line (3, 4 or 5).
+    pub const fn tile_count(&self) -> usize {
+        LINES[self.0].0.len()
+    }
+}
+
+/// Points scored by each of the 15 lines of a board, in `LINES` order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    pub lines: Vec<(LineId, i32)>,
+}
+
+impl ScoreBreakdown {
+    pub fn total(&self) -> i32 {
+        self.lines.iter().map(|(_, points)| points).sum()
+    }
+
+    /// Lines that score points (filled with a single value).
+    pub fn completed(&self) -> impl Iterator<Item = LineId> + '_ {
+        self.lines
+            .iter()
+            .filter(|(_, points)| *points > 0)
+            .map(|(line, _)| *line)
+    }
+
+    /// Points per direction (rows, tile.1 diagonals, tile.2 diagonals).
+    pub fn by_direction(&self) -> [i32; 3] {
+        let mut points = [0; 3];
+        for (line, line_points) in &self.lines {
+            points[line.direction()] += line_points;
+        }
+        points
+    }
+}
 
-// Type alias for scoring pattern tuple
-type ScoringPattern = (&'static [usize], i32, Box<dyn Fn(&Tile) -> i32>);
+/// Contribution of every scoring line to the score of `plateau`.
+pub fn result_breakdown(plateau: &Plateau) -> ScoreBreakdown {
+    ScoreBreakdown {
+        lines: line_scores(plateau)
+            .into_iter()
+            .enumerate()
+            .map(|(i, points)| (LineId(i), points))
+            .collect(),
+    }
+}
 
 pub fn result(plateau: &Plateau) -> i32 {
-    let mut result = 0;
-
-    // Inline the logic of calculate_score
-    let patterns: Vec<ScoringPattern> = vec![
-        (&[0, 1, 2][..], 3, Box::new(|tile: &Tile| tile.0)),
-        (&[3, 4, 5, 6][..], 4, Box::new(|tile: &Tile| tile.0)),
-        (&[7, 8, 9, 10, 11][..], 5, Box::new(|tile: &Tile| tile.0)),
-        (&[12, 13, 14, 15][..], 4, Box::new(|tile: &Tile| tile.0)),
-        (&[16, 17, 18][..], 3, Box::new(|tile: &Tile| tile.0)),
-        (&[0, 3, 7][..], 3, Box::new(|tile: &Tile| tile.1)),
-        (&[1, 4, 8, 12][..], 4, Box::new(|tile: &Tile| tile.1)),
-        (&[2, 5, 9, 13, 16][..], 5, Box::new(|tile: &Tile| tile.1)),
-        (&[6, 10, 14, 17][..], 4, Box::new(|tile: &Tile| tile.1)),
-        (&[11, 15, 18][..], 3, Box::new(|tile: &Tile| tile.1)),
-        (&[7, 12, 16][..], 3, Box::new(|tile: &Tile| tile.2)),
-        (&[3, 8, 13, 17][..], 4, Box::new(|tile: &Tile| tile.2)),
-        (&[0, 4, 9, 14, 18][..], 5, Box::new(|tile: &Tile| tile.2)),
-        (&[1, 5, 10, 15][..], 4, Box::new(|tile: &Tile| tile.2)),
-        (&[2, 6, 11][..], 3, Box::new(|tile: &Tile| tile.2)),
-    ];
-
-    for (indices, multiplier, selector) in patterns {
-        let first_value = selector(&plateau.tiles[indices[0]]);
+    result_with_rules(plateau, ScoringRules::STANDARD)
+}
+
+/// Points scored by each line of `LINES` (same index), 0 for lines that don't score.
+/// The sum equals [`result`].
+pub fn line_scores(plateau: &Plateau) -> [i32; 15] {
+    let mut scores = [0; 15];
+    for (score, (indices, direction)) in scores.iter_mut().zip(LINES.iter()) {
+        let first = line_value(&plateau.tiles[indices[0]], *direction);
         if indices
             .iter()
-            .all(|&i| selector(&plateau.tiles[i]) == first_value)
+            .all(|&i| line_value(&plateau.tiles[i], *direction) == first)
         {
-            result += first_value * multiplier;
+            *score = first * indices.len() as i32;
         }
     }
+    scores
+}
+
+/// Number of scoring lines fully filled with a single value (lines that score points).
+pub fn count_line_completions(plateau: &Plateau) -> usize {
+    LINES
+        .iter()
+        .filter(|(indices, direction)| {
+            let first = line_value(&plateau.tiles[indices[0]], *direction);
+            first != 0
+                && indices
+                    .iter()
+                    .all(|&i| line_value(&plateau.tiles[i], *direction) == first)
+        })
+        .count()
+}
 
-    result
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_breakdown_sums_to_result() {
+        let mut plateau = create_plateau_empty();
+        // Row 0 with 9s, diagonal 0-3-7 with 6s
+        for (position, tile) in [
+            (0, Tile(9, 6, 3)),
+            (1, Tile(9, 2, 4)),
+            (2, Tile(9, 7, 8)),
+            (3, Tile(5, 6, 4)),
+            (7, Tile(1, 6, 8)),
+        ] {
+            plateau.tiles[position] = tile;
+        }
+
+        let breakdown = result_breakdown(&plateau);
+        assert_eq!(breakdown.lines.len(), 15);
+        assert_eq!(
+            breakdown.completed().collect::<Vec<_>>(),
+            vec![LineId(0), LineId(5)]
+        );
+        assert_eq!(breakdown.by_direction(), [27, 18, 0]);
+        assert_eq!(breakdown.total(), 45);
+        assert_eq!(result(&plateau), 45);
+        assert_eq!(LineId(5).positions(), &[0, 3, 7]);
+    }
 }
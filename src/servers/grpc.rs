@@ -1,8 +1,17 @@
 use crate::auth::JwtManager;
+use crate::game::create_deck::create_deck;
+use crate::game::plateau::create_plateau_empty;
+use crate::game::tile::Tile;
 use crate::generated::takeiteasygame::v1::game_service_server::GameServiceServer;
 use crate::generated::takeiteasygame::v1::session_service_server::SessionServiceServer;
+use crate::generated::takeiteasygame::v1::FILE_DESCRIPTOR_SET;
+use crate::mcts::algorithm::convert_plateau_by_arch;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::qvalue_net::QValueNet;
+use crate::servers::health::Readiness;
+use crate::servers::rest_gateway::{rest_gateway_router, RestGatewayState};
+use crate::services::game_manager::set_server_ai_seed;
+use crate::services::game_service::model_registry::{ModelRegistry, ModelVersion};
 use crate::services::game_service::GameServiceImpl;
 use crate::services::session_manager;
 use crate::services::session_service::SessionServiceImpl;
@@ -12,6 +21,7 @@ use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tokio::try_join;
 use tonic::body::Body as TonicBody;
 use tonic::transport::Server;
@@ -25,8 +35,34 @@ pub struct GrpcConfig {
     pub host: String,
     pub enable_web_layer: bool,
     pub enable_cors: bool,
+    /// Port of the REST/JSON gateway (disabled when `None`)
+    pub rest_port: Option<u16>,
+    /// Maximum number of AI move computations running at once, excess
+    /// requests wait for a free slot (0 = unlimited)
+    pub max_concurrent_ai: usize,
+    /// Serve gRPC server reflection so tools like grpcurl can list the
+    /// services without the .proto files
+    pub enable_reflection: bool,
+    /// GetAiMove requests allowed per minute from one client IP, excess
+    /// requests get RESOURCE_EXHAUSTED (0 = unlimited)
+    pub ai_move_rate_limit: u32,
+    /// Seed of the AI searches: the same board and tile at the same turn
+    /// always get the same AI move (`None` = thread RNG)
+    pub seed: Option<u64>,
+    /// AI-vs-AI games played at once, excess CreateAiVsAiSession requests
+    /// get RESOURCE_EXHAUSTED (0 = unlimited)
+    pub max_ai_vs_ai_games: usize,
+    /// Cap on the MCTS simulations per move requested for an AI-vs-AI game
+    /// (0 = no cap)
+    pub max_ai_vs_ai_simulations: usize,
 }
 
+/// Default `GrpcConfig::max_ai_vs_ai_games`
+pub const DEFAULT_MAX_AI_VS_AI_GAMES: usize = 4;
+
+/// Default `GrpcConfig::max_ai_vs_ai_simulations`, the "hard" solo level
+pub const DEFAULT_MAX_AI_VS_AI_SIMULATIONS: usize = 1000;
+
 #[derive(Clone)]
 pub struct SimpleCors<S> {
     inner: S,
@@ -137,6 +173,13 @@ impl Default for GrpcConfig {
             host: "0.0.0.0".to_string(),
             enable_web_layer: true,
             enable_cors: true,
+            rest_port: None,
+            max_concurrent_ai: 0,
+            enable_reflection: true,
+            ai_move_rate_limit: 0,
+            seed: None,
+            max_ai_vs_ai_games: DEFAULT_MAX_AI_VS_AI_GAMES,
+            max_ai_vs_ai_simulations: DEFAULT_MAX_AI_VS_AI_SIMULATIONS,
         }
     }
 }
@@ -152,6 +195,9 @@ pub struct GrpcServer {
     top_k: usize,
     jwt_manager: Option<Arc<JwtManager>>,
     require_auth: bool,
+    readiness: Readiness,
+    model_registry: Option<Arc<ModelRegistry>>,
+    value_net_available: bool,
 }
 
 impl GrpcServer {
@@ -177,6 +223,9 @@ impl GrpcServer {
             top_k: 6,
             jwt_manager: None,
             require_auth: false,
+            readiness: Readiness::new(),
+            model_registry: None,
+            value_net_available: true,
         }
     }
 
@@ -206,6 +255,9 @@ impl GrpcServer {
             top_k,
             jwt_manager: None,
             require_auth: false,
+            readiness: Readiness::new(),
+            model_registry: None,
+            value_net_available: true,
         }
     }
 
@@ -216,12 +268,103 @@ impl GrpcServer {
         self
     }
 
+    /// Serve without a trained value net (weights failed to load): the AI
+    /// falls back to pure-rollout MCTS or the policy net alone
+    pub fn without_value_net(mut self) -> Self {
+        self.value_net_available = false;
+        self
+    }
+
+    /// A/B test a second model generation: `candidate_fraction` of the new
+    /// sessions play against it, the others against the primary nets
+    pub fn with_ab_model(
+        mut self,
+        primary_name: &str,
+        candidate_name: &str,
+        policy_net: PolicyNet,
+        value_net: ValueNet,
+        candidate_fraction: f64,
+    ) -> Result<Self, String> {
+        let registry = ModelRegistry::new(vec![
+            ModelVersion {
+                name: primary_name.to_string(),
+                policy_net: self.policy_net.clone(),
+                value_net: self.value_net.clone(),
+                weight: 1.0 - candidate_fraction,
+            },
+            ModelVersion {
+                name: candidate_name.to_string(),
+                policy_net: Arc::new(tokio::sync::Mutex::new(policy_net)),
+                value_net: Arc::new(tokio::sync::Mutex::new(value_net)),
+                weight: candidate_fraction,
+            },
+        ])?;
+        log::info!(
+            "🧪 A/B test : {} ({:.0}%) vs {} ({:.0}%)",
+            primary_name,
+            (1.0 - candidate_fraction) * 100.0,
+            candidate_name,
+            candidate_fraction * 100.0
+        );
+        self.model_registry = Some(Arc::new(registry));
+        Ok(self)
+    }
+
     /// Get a reference to the server configuration
     #[allow(dead_code)]
     pub fn config(&self) -> &GrpcConfig {
         &self.config
     }
 
+    /// Readiness reported by the health endpoint (NOT_SERVING until warm-up is done)
+    #[allow(dead_code)]
+    pub fn readiness(&self) -> Readiness {
+        self.readiness.clone()
+    }
+
+    /// Run one forward pass of the policy and value nets on an empty board so
+    /// tch initializes its kernels before the first real game, then mark the
+    /// server as SERVING. Returns the warm-up duration.
+    pub async fn warm_up(&self) -> Duration {
+        let start = Instant::now();
+        {
+            let policy_net = self.policy_net.lock().await;
+            let value_net = self.value_net.lock().await;
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _guard = tch::no_grad_guard();
+                let deck = create_deck();
+                let plateau = create_plateau_empty();
+                let tile = Tile(1, 2, 3);
+                let policy_input =
+                    convert_plateau_by_arch(policy_net.arch, &plateau, &tile, &deck, 0, 19);
+                let value_input =
+                    convert_plateau_by_arch(value_net.arch, &plateau, &tile, &deck, 0, 19);
+                let _ = policy_net.forward(&policy_input, false);
+                let _ = value_net.forward(&value_input, false);
+            }));
+            if outcome.is_err() {
+                log::warn!("⚠️ Model warm-up failed, the first AI move may be slow");
+            }
+        }
+        let elapsed = start.elapsed();
+        log::info!("🔥 Models warmed up in {:.0?}", elapsed);
+
+        self.readiness.set_serving();
+        elapsed
+    }
+
+    /// gRPC reflection service describing the session and game services
+    fn reflection_service() -> Result<
+        tonic_reflection::server::v1::ServerReflectionServer<
+            impl tonic_reflection::server::v1::ServerReflection,
+        >,
+        tonic_reflection::server::Error,
+    > {
+        tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+            .build_v1()
+    }
+
     /// Initialize single-player session if needed - DÉSACTIVÉ pour le mode sélection frontend
     async fn init_single_player_session(&self) -> Result<(), Box<dyn std::error::Error>> {
         // ✅ DÉSACTIVÉ: Les sessions sont maintenant créées via le frontend avec mode sélectionné
@@ -235,6 +378,11 @@ impl GrpcServer {
         let grpc_web_addr: SocketAddr =
             format!("{}:{}", self.config.host, self.config.web_port).parse()?;
 
+        if let Some(seed) = self.config.seed {
+            set_server_ai_seed(seed)?;
+            log::info!("🎲 Recherches IA reproductibles (seed {})", seed);
+        }
+
         // Initialize single-player session if needed
         self.init_single_player_session().await?;
 
@@ -257,7 +405,7 @@ impl GrpcServer {
                 self.single_player,
             ),
         };
-        let game_service = GameServiceImpl::new_with_qnet(
+        let mut game_service = GameServiceImpl::new_with_qnet(
             self.session_manager.clone(),
             self.policy_net.clone(),
             self.value_net.clone(),
@@ -265,9 +413,22 @@ impl GrpcServer {
             self.num_simulations,
             self.top_k,
         );
+        if let Some(registry) = &self.model_registry {
+            game_service = game_service.with_model_registry(registry.clone());
+        }
+        if !self.value_net_available {
+            game_service = game_service.without_value_net();
+        }
+        game_service = game_service
+            .with_max_concurrent_ai(self.config.max_concurrent_ai)
+            .with_ai_move_rate_limit(self.config.ai_move_rate_limit)
+            .with_ai_vs_ai_limits(
+                self.config.max_ai_vs_ai_games,
+                self.config.max_ai_vs_ai_simulations,
+            );
 
         // Log server startup info
-        let ai_mode = if self.qvalue_net.is_some() {
+        let ai_mode = if self.qvalue_net.is_some() && self.value_net_available {
             format!(
                 "HYBRID Q-Net MCTS (top-{}, {} sims)",
                 self.top_k, self.num_simulations
@@ -307,12 +468,40 @@ impl GrpcServer {
             web_layer_info
         );
 
+        if let Some(rest_port) = self.config.rest_port {
+            let rest_addr: SocketAddr = format!("{}:{}", self.config.host, rest_port).parse()?;
+            let rest_router = rest_gateway_router(
+                RestGatewayState::new(session_service.clone(), game_service.clone())
+                    .with_readiness(self.readiness.clone()),
+            );
+            let listener = tokio::net::TcpListener::bind(rest_addr).await?;
+            log::info!("🌉 REST/JSON gateway starting on {}", rest_addr);
+            tokio::spawn(async move {
+                // Client address reaches GetAiMove for its per-IP rate limit
+                let rest_service = rest_router.into_make_service_with_connect_info::<SocketAddr>();
+                if let Err(e) = axum::serve(listener, rest_service).await {
+                    log::error!("❌ REST gateway stopped: {}", e);
+                }
+            });
+        }
+
+        // Health stays NOT_SERVING until the models have run once
+        self.warm_up().await;
+
         let grpc_session_service = session_service.clone();
         let grpc_game_service = game_service.clone();
 
+        let reflection_service = if self.config.enable_reflection {
+            log::info!("🔎 gRPC reflection enabled on port {}", self.config.port);
+            Some(Self::reflection_service()?)
+        } else {
+            None
+        };
+
         let grpc_server = Server::builder()
             .add_service(SessionServiceServer::new(grpc_session_service))
             .add_service(GameServiceServer::new(grpc_game_service))
+            .add_optional_service(reflection_service)
             .serve(grpc_addr);
 
         if self.config.enable_web_layer {
@@ -360,6 +549,16 @@ mod tests {
         assert_eq!(config.host, "0.0.0.0");
         assert!(config.enable_web_layer);
         assert!(config.enable_cors);
+        assert!(config.rest_port.is_none());
+        assert_eq!(config.max_concurrent_ai, 0);
+        assert!(config.enable_reflection);
+        assert_eq!(config.ai_move_rate_limit, 0);
+        assert_eq!(config.seed, None);
+        assert_eq!(config.max_ai_vs_ai_games, DEFAULT_MAX_AI_VS_AI_GAMES);
+        assert_eq!(
+            config.max_ai_vs_ai_simulations,
+            DEFAULT_MAX_AI_VS_AI_SIMULATIONS
+        );
     }
 
     #[test]
@@ -370,12 +569,26 @@ mod tests {
             host: "127.0.0.1".to_string(),
             enable_web_layer: false,
             enable_cors: false,
+            rest_port: Some(8081),
+            max_concurrent_ai: 4,
+            enable_reflection: false,
+            ai_move_rate_limit: 30,
+            seed: Some(7),
+            max_ai_vs_ai_games: 2,
+            max_ai_vs_ai_simulations: 200,
         };
         assert_eq!(config.port, 8080);
         assert_eq!(config.web_port, 18080);
         assert_eq!(config.host, "127.0.0.1");
         assert!(!config.enable_web_layer);
         assert!(!config.enable_cors);
+        assert_eq!(config.rest_port, Some(8081));
+        assert_eq!(config.max_concurrent_ai, 4);
+        assert!(!config.enable_reflection);
+        assert_eq!(config.ai_move_rate_limit, 30);
+        assert_eq!(config.seed, Some(7));
+        assert_eq!(config.max_ai_vs_ai_games, 2);
+        assert_eq!(config.max_ai_vs_ai_simulations, 200);
     }
 
     #[test]
@@ -399,6 +612,24 @@ mod tests {
         assert_eq!(server.num_simulations, 300);
     }
 
+    #[tokio::test]
+    async fn test_readiness_not_serving_until_warm_up() {
+        use crate::neural::manager::NNArchitecture;
+        use crate::servers::health::ServingStatus;
+        use tch::{nn, Device};
+
+        let vs = nn::VarStore::new(Device::Cpu);
+        let policy_net = PolicyNet::new(&vs, (5, 47, 1), NNArchitecture::GraphTransformer);
+        let value_net = ValueNet::new(&vs, (5, 47, 1), NNArchitecture::GraphTransformer);
+
+        let server = GrpcServer::new(GrpcConfig::default(), policy_net, value_net, 10, true);
+        let readiness = server.readiness();
+        assert_eq!(readiness.status(), ServingStatus::NotServing);
+
+        server.warm_up().await;
+        assert_eq!(readiness.status(), ServingStatus::Serving);
+    }
+
     #[test]
     fn test_grpc_server_config_access() {
         use crate::neural::manager::NNArchitecture;
@@ -417,6 +648,13 @@ mod tests {
             host: "localhost".to_string(),
             enable_web_layer: true,
             enable_cors: true,
+            rest_port: None,
+            max_concurrent_ai: 0,
+            enable_reflection: true,
+            ai_move_rate_limit: 0,
+            seed: None,
+            max_ai_vs_ai_games: 0,
+            max_ai_vs_ai_simulations: 0,
         };
 
         let server = GrpcServer::new(config, policy_net, value_net, 500, false);
@@ -427,4 +665,27 @@ mod tests {
         assert!(server_config.enable_web_layer);
         assert!(server_config.enable_cors);
     }
+
+    #[test]
+    fn test_reflection_describes_game_and_session_services() {
+        assert!(GrpcServer::reflection_service().is_ok());
+
+        // Names are stored verbatim in the encoded descriptors
+        let contains = |name: &str| {
+            FILE_DESCRIPTOR_SET
+                .windows(name.len())
+                .any(|w| w == name.as_bytes())
+        };
+        for name in [
+            "takeiteasygame.v1",
+            "GameService",
+            "GetAiMove",
+            "MakeMove",
+            "SessionService",
+            "CreateSession",
+            "JoinSession",
+        ] {
+            assert!(contains(name), "{} missing from the descriptors", name);
+        }
+    }
 }
@@ -0,0 +1,63 @@
+//! Readiness status reported by the health endpoint.
+//!
+//! Uses the gRPC health-checking vocabulary (`NOT_SERVING` / `SERVING`). The
+//! server starts `NOT_SERVING` and only flips once the models are warmed up,
+//! so load balancers don't route the first games to a cold process.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServingStatus {
+    NotServing,
+    Serving,
+}
+
+impl ServingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServingStatus::NotServing => "NOT_SERVING",
+            ServingStatus::Serving => "SERVING",
+        }
+    }
+}
+
+/// Shared readiness flag, cloned into every server exposing a health endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    serving: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self) -> ServingStatus {
+        if self.serving.load(Ordering::Acquire) {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        }
+    }
+
+    pub fn set_serving(&self) {
+        self.serving.store(true, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness_is_shared_between_clones() {
+        let readiness = Readiness::new();
+        let observer = readiness.clone();
+        assert_eq!(observer.status(), ServingStatus::NotServing);
+
+        readiness.set_serving();
+        assert_eq!(observer.status(), ServingStatus::Serving);
+        assert_eq!(observer.status().as_str(), "SERVING");
+    }
+}
@@ -1,7 +1,10 @@
 // Modules for server components
 pub mod grpc;
+pub mod health;
+pub mod rest_gateway;
 pub mod web_ui;
 
 // Re-export public APIs
 pub use grpc::{GrpcConfig, GrpcServer};
+pub use health::{Readiness, ServingStatus};
 pub use web_ui::{WebUiConfig, WebUiServer};
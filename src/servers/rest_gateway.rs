@@ -0,0 +1,872 @@
+//! REST/JSON gateway exposing the core game and session operations over plain HTTP.
+//!
+//! Some frontends cannot use gRPC-web easily. Every endpoint here builds the same protobuf
+//! request as a gRPC client would and dispatches it to the [`SessionService`] / [`GameService`]
+//! implementations, so the game logic stays in a single place.
+use axum::{
+    extract::{ConnectInfo, Json, Path, State},
+    http::{Extensions, HeaderMap, StatusCode},
+    response::{IntoResponse, Response as AxumResponse},
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use tonic::transport::server::TcpConnectInfo;
+use tonic::{Request, Status};
+
+use crate::generated::takeiteasygame::v1::game_service_server::GameService;
+use crate::generated::takeiteasygame::v1::session_service_server::SessionService;
+use crate::generated::takeiteasygame::v1::{
+    create_session_response, join_session_response, make_move_response, CreateSessionRequest,
+    Error, EvaluateBoardRequest, GameState, GetAiMoveRequest, GetGameStateRequest,
+    GetSessionStateRequest, JoinSessionRequest, MakeMoveRequest, Player, SessionState,
+    SetReadyRequest, StartTurnRequest,
+};
+use crate::servers::health::{Readiness, ServingStatus};
+use crate::services::game_service::GameServiceImpl;
+use crate::services::session_service::SessionServiceImpl;
+
+// ============================================================================
+// ÉTAT ET ROUTEUR
+// ============================================================================
+
+/// Services shared between the gRPC server and the REST gateway.
+#[derive(Clone)]
+pub struct RestGatewayState {
+    pub session_service: SessionServiceImpl,
+    pub game_service: GameServiceImpl,
+    pub readiness: Readiness,
+}
+
+impl RestGatewayState {
+    pub fn new(session_service: SessionServiceImpl, game_service: GameServiceImpl) -> Self {
+        Self {
+            session_service,
+            game_service,
+            readiness: Readiness::new(),
+        }
+    }
+
+    /// Report the given readiness on `/api/v1/health` (NOT_SERVING by default)
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+}
+
+/// Build the `/api/v1` router for the REST gateway.
+pub fn rest_gateway_router(state: RestGatewayState) -> Router {
+    Router::new()
+        .route("/api/v1/sessions", post(rest_create_session))
+        .route("/api/v1/sessions/join", post(rest_join_session))
+        .route("/api/v1/sessions/{session_id}", get(rest_get_session_state))
+        .route("/api/v1/sessions/{session_id}/ready", post(rest_set_ready))
+        .route("/api/v1/games/{session_id}/turn", post(rest_start_turn))
+        .route("/api/v1/games/{session_id}/moves", post(rest_make_move))
+        .route("/api/v1/games/{session_id}/state", get(rest_get_game_state))
+        .route("/api/v1/ai-move", post(rest_get_ai_move))
+        .route("/api/v1/evaluate", post(rest_evaluate_board))
+        .route("/api/v1/health", get(rest_health))
+        .route("/api/v1/metrics", get(rest_metrics))
+        .with_state(state)
+}
+
+// ============================================================================
+// CORPS DES REQUÊTES JSON
+// ============================================================================
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestCreateSessionBody {
+    pub player_name: String,
+    #[serde(default = "default_max_players")]
+    pub max_players: i32,
+    #[serde(default)]
+    pub game_mode: String,
+    #[serde(default)]
+    pub num_turns: i32,
+    #[serde(default)]
+    pub ai_think_delay_ms: i32,
+    #[serde(default)]
+    pub starting_board: String,
+    #[serde(default)]
+    pub scoring_rules: String,
+    #[serde(default)]
+    pub opponent: String,
+    #[serde(default)]
+    pub tile_draw: String,
+    #[serde(default)]
+    pub tile_seed: u64,
+    #[serde(default)]
+    pub tile_sequence: String,
+}
+
+fn default_max_players() -> i32 {
+    4
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestJoinSessionBody {
+    pub session_code: String,
+    pub player_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestSetReadyBody {
+    pub player_id: String,
+    #[serde(default = "default_ready")]
+    pub ready: bool,
+}
+
+fn default_ready() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestMakeMoveBody {
+    pub player_id: String,
+    /// Either the JSON string expected by `MakeMove` or the move object itself
+    pub move_data: Value,
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestAiMoveBody {
+    pub tile_code: String,
+    #[serde(default)]
+    pub board_state: Vec<String>,
+    #[serde(default)]
+    pub available_positions: Vec<i32>,
+    #[serde(default)]
+    pub turn_number: i32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RestEvaluateBoardBody {
+    #[serde(default)]
+    pub board_state: Vec<String>,
+    #[serde(default)]
+    pub tile_code: String,
+    #[serde(default)]
+    pub turn_number: i32,
+    #[serde(default)]
+    pub scoring_rules: String,
+}
+
+// ============================================================================
+// HANDLERS - DÉLÉGATION AUX SERVICES GRPC
+// ============================================================================
+
+async fn rest_health(State(state): State<RestGatewayState>) -> AxumResponse {
+    let status = state.readiness.status();
+    let code = match status {
+        ServingStatus::Serving => StatusCode::OK,
+        ServingStatus::NotServing => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (code, Json(json!({ "status": status.as_str() }))).into_response()
+}
+
+/// Per-model-version AI scores (A/B testing; empty list when a single model is served)
+/// and hit rates of the inference caches
+async fn rest_metrics(State(state): State<RestGatewayState>) -> AxumResponse {
+    let models: Vec<Value> = state
+        .game_service
+        .model_metrics()
+        .await
+        .into_iter()
+        .map(|m| {
+            json!({
+                "version": m.name,
+                "sessions": m.sessions,
+                "finished_games": m.finished_games,
+                "average_score": m.average_score,
+            })
+        })
+        .collect();
+    let inference_cache: serde_json::Map<String, Value> = state
+        .game_service
+        .inference_cache_metrics()
+        .into_iter()
+        .map(|(net, m)| {
+            let metrics = json!({
+                "hits": m.hits,
+                "misses": m.misses,
+                "entries": m.entries,
+                "hit_rate": m.hit_rate,
+            });
+            (net.to_string(), metrics)
+        })
+        .collect();
+    Json(json!({ "models": models, "inference_cache": inference_cache })).into_response()
+}
+
+async fn rest_create_session(
+    State(state): State<RestGatewayState>,
+    headers: HeaderMap,
+    Json(body): Json<RestCreateSessionBody>,
+) -> AxumResponse {
+    let request = grpc_request(
+        CreateSessionRequest {
+            player_name: body.player_name,
+            max_players: body.max_players,
+            game_mode: body.game_mode,
+            num_turns: body.num_turns,
+            ai_think_delay_ms: body.ai_think_delay_ms,
+            starting_board: body.starting_board,
+            scoring_rules: body.scoring_rules,
+            opponent: body.opponent,
+            tile_draw: body.tile_draw,
+            tile_seed: body.tile_seed,
+            tile_sequence: body.tile_sequence,
+        },
+        &headers,
+    );
+
+    match state.session_service.create_session(request).await {
+        Ok(response) => match response.into_inner().result {
+            Some(create_session_response::Result::Success(success)) => Json(json!({
+                "success": true,
+                "session_code": success.session_code,
+                "session_id": success.session_id,
+                "player_id": success.player_id,
+                "player": success.player.as_ref().map(player_to_json),
+            }))
+            .into_response(),
+            Some(create_session_response::Result::Error(error)) => error_body(&error),
+            None => empty_result_body(),
+        },
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_join_session(
+    State(state): State<RestGatewayState>,
+    headers: HeaderMap,
+    Json(body): Json<RestJoinSessionBody>,
+) -> AxumResponse {
+    let request = grpc_request(
+        JoinSessionRequest {
+            session_code: body.session_code,
+            player_name: body.player_name,
+        },
+        &headers,
+    );
+
+    match state.session_service.join_session(request).await {
+        Ok(response) => match response.into_inner().result {
+            Some(join_session_response::Result::Success(success)) => Json(json!({
+                "success": true,
+                "session_id": success.session_id,
+                "player_id": success.player_id,
+                "game_state": success.game_state.as_ref().map(game_state_to_json),
+            }))
+            .into_response(),
+            Some(join_session_response::Result::Error(error)) => error_body(&error),
+            None => empty_result_body(),
+        },
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_set_ready(
+    State(state): State<RestGatewayState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    Json(body): Json<RestSetReadyBody>,
+) -> AxumResponse {
+    let request = grpc_request(
+        SetReadyRequest {
+            session_id,
+            player_id: body.player_id,
+            ready: body.ready,
+        },
+        &headers,
+    );
+
+    match state.session_service.set_ready(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            Json(json!({
+                "success": response.success,
+                "game_started": response.game_started,
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_get_session_state(
+    State(state): State<RestGatewayState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> AxumResponse {
+    let request = grpc_request(GetSessionStateRequest { session_id }, &headers);
+
+    match state.session_service.get_session_state(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            Json(json!({
+                "success": response.error.is_none(),
+                "game_state": response.game_state.as_ref().map(game_state_to_json),
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_start_turn(
+    State(state): State<RestGatewayState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> AxumResponse {
+    let request = grpc_request(
+        StartTurnRequest {
+            session_id,
+            forced_tile: String::new(),
+        },
+        &headers,
+    );
+
+    match state.game_service.start_turn(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            Json(json!({
+                "success": response.success,
+                "announced_tile": response.announced_tile,
+                "tile_image": response.tile_image,
+                "turn_number": response.turn_number,
+                "waiting_for_players": response.waiting_for_players,
+                "game_state": embedded_json(&response.game_state),
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_make_move(
+    State(state): State<RestGatewayState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    Json(body): Json<RestMakeMoveBody>,
+) -> AxumResponse {
+    let move_data = match body.move_data {
+        Value::String(raw) => raw,
+        other => other.to_string(),
+    };
+    let request = grpc_request(
+        MakeMoveRequest {
+            session_id,
+            player_id: body.player_id,
+            move_data,
+            timestamp: body.timestamp,
+        },
+        &headers,
+    );
+
+    match state.game_service.make_move(request).await {
+        Ok(response) => match response.into_inner().result {
+            Some(make_move_response::Result::Success(success)) => Json(json!({
+                "success": true,
+                "new_game_state": success.new_game_state.as_ref().map(game_state_to_json),
+                "mcts_response": embedded_json(&success.mcts_response),
+                "points_earned": success.points_earned,
+                "is_game_over": success.is_game_over,
+            }))
+            .into_response(),
+            Some(make_move_response::Result::Error(error)) => error_body(&error),
+            None => empty_result_body(),
+        },
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_get_game_state(
+    State(state): State<RestGatewayState>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> AxumResponse {
+    let request = grpc_request(GetGameStateRequest { session_id }, &headers);
+
+    match state.game_service.get_game_state(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            Json(json!({
+                "success": response.success,
+                "game_state": embedded_json(&response.game_state),
+                "current_tile": response.current_tile,
+                "current_tile_image": response.current_tile_image,
+                "current_turn": response.current_turn,
+                "waiting_for_players": response.waiting_for_players,
+                "is_game_finished": response.is_game_finished,
+                "final_scores": embedded_json(&response.final_scores),
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_get_ai_move(
+    State(state): State<RestGatewayState>,
+    headers: HeaderMap,
+    extensions: Extensions,
+    Json(body): Json<RestAiMoveBody>,
+) -> AxumResponse {
+    let mut request = grpc_request(
+        GetAiMoveRequest {
+            tile_code: body.tile_code,
+            board_state: body.board_state,
+            available_positions: body.available_positions,
+            turn_number: body.turn_number,
+        },
+        &headers,
+    );
+    // Same per-IP rate limit as gRPC callers
+    if let Some(ConnectInfo(addr)) = extensions.get::<ConnectInfo<SocketAddr>>() {
+        request.extensions_mut().insert(TcpConnectInfo {
+            local_addr: None,
+            remote_addr: Some(*addr),
+        });
+    }
+
+    match state.game_service.get_ai_move(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            Json(json!({
+                "success": response.success,
+                "recommended_position": response.recommended_position,
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+async fn rest_evaluate_board(
+    State(state): State<RestGatewayState>,
+    headers: HeaderMap,
+    Json(body): Json<RestEvaluateBoardBody>,
+) -> AxumResponse {
+    let request = grpc_request(
+        EvaluateBoardRequest {
+            board_state: body.board_state,
+            tile_code: body.tile_code,
+            turn_number: body.turn_number,
+            scoring_rules: body.scoring_rules,
+        },
+        &headers,
+    );
+
+    match state.game_service.evaluate_board(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            let breakdown: Vec<Value> = response
+                .score_breakdown
+                .iter()
+                .map(|line| {
+                    json!({
+                        "positions": line.positions,
+                        "direction": line.direction,
+                        "points": line.points,
+                    })
+                })
+                .collect();
+            Json(json!({
+                "success": response.success,
+                "score": response.score,
+                "bonus": response.bonus,
+                "score_breakdown": breakdown,
+                "value_estimate": response.value_estimate,
+                "legal_moves": response.legal_moves,
+                "error": response.error.as_ref().map(error_to_json),
+            }))
+            .into_response()
+        }
+        Err(status) => status_to_response(status),
+    }
+}
+
+// ============================================================================
+// FONCTIONS PURES - CONVERSIONS PROTOBUF -> JSON
+// ============================================================================
+
+/// Wrap a message in a tonic request, forwarding the HTTP `Authorization` header
+/// so the services authenticate REST callers exactly like gRPC callers.
+fn grpc_request<T>(message: T, headers: &HeaderMap) -> Request<T> {
+    let mut request = Request::new(message);
+    if let Some(value) = headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+    {
+        request.metadata_mut().insert("authorization", value);
+    }
+    request
+}
+
+fn player_to_json(player: &Player) -> Value {
+    json!({
+        "id": player.id,
+        "name": player.name,
+        "score": player.score,
+        "is_ready": player.is_ready,
+        "is_connected": player.is_connected,
+        "joined_at": player.joined_at,
+    })
+}
+
+fn game_state_to_json(state: &GameState) -> Value {
+    let session_state = SessionState::try_from(state.state)
+        .map(|s| s.as_str_name().to_string())
+        .unwrap_or_else(|_| state.state.to_string());
+
+    json!({
+        "session_id": state.session_id,
+        "players": state.players.iter().map(player_to_json).collect::<Vec<_>>(),
+        "current_player_id": state.current_player_id,
+        "state": session_state,
+        "board_state": embedded_json(&state.board_state),
+        "turn_number": state.turn_number,
+        "game_mode": state.game_mode,
+    })
+}
+
+fn error_to_json(error: &Error) -> Value {
+    json!({
+        "code": error.code,
+        "message": error.message,
+        "details": error.details,
+    })
+}
+
+/// Services return several JSON payloads as strings; expose them as real JSON when possible.
+fn embedded_json(raw: &str) -> Value {
+    if raw.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn error_body(error: &Error) -> AxumResponse {
+    Json(json!({
+        "success": false,
+        "error": error_to_json(error),
+    }))
+    .into_response()
+}
+
+fn empty_result_body() -> AxumResponse {
+    status_to_response(Status::internal("Empty response from service"))
+}
+
+fn status_to_http(code: tonic::Code) -> StatusCode {
+    match code {
+        tonic::Code::InvalidArgument => StatusCode::BAD_REQUEST,
+        tonic::Code::NotFound => StatusCode::NOT_FOUND,
+        tonic::Code::AlreadyExists => StatusCode::CONFLICT,
+        tonic::Code::PermissionDenied => StatusCode::FORBIDDEN,
+        tonic::Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        tonic::Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        tonic::Code::FailedPrecondition => StatusCode::PRECONDITION_FAILED,
+        tonic::Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        tonic::Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        tonic::Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn status_to_response(status: Status) -> AxumResponse {
+    (
+        status_to_http(status.code()),
+        Json(json!({
+            "success": false,
+            "error": {
+                "code": format!("{:?}", status.code()),
+                "message": status.message(),
+            },
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural::manager::NNArchitecture;
+    use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+    use crate::services::session_manager::new_session_manager;
+    use axum::body::Body;
+    use std::sync::Arc;
+    use tch::{nn, Device};
+    use tower::ServiceExt;
+
+    fn create_test_state(single_player: bool) -> RestGatewayState {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let input_dim = (5, 47, 1);
+        let policy_net = PolicyNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let value_net = ValueNet::new(&vs, input_dim, NNArchitecture::Cnn);
+
+        let session_manager = Arc::new(new_session_manager());
+        let session_service =
+            SessionServiceImpl::new_with_manager_and_mode(session_manager.clone(), single_player);
+        let game_service = GameServiceImpl::new(
+            session_manager,
+            Arc::new(tokio::sync::Mutex::new(policy_net)),
+            Arc::new(tokio::sync::Mutex::new(value_net)),
+            10,
+        );
+        RestGatewayState::new(session_service, game_service)
+    }
+
+    async fn call(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let builder = http::Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => builder
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_rest_create_session_matches_grpc_state() {
+        let state = create_test_state(false);
+        let router = rest_gateway_router(state.clone());
+
+        let (status, created) = call(
+            &router,
+            "POST",
+            "/api/v1/sessions",
+            Some(json!({"player_name": "Alice", "max_players": 4, "game_mode": "multiplayer"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(created["success"], true);
+        let session_id = created["session_id"].as_str().unwrap().to_string();
+
+        let grpc_state = state
+            .session_service
+            .get_session_state(Request::new(GetSessionStateRequest {
+                session_id: session_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .game_state
+            .unwrap();
+
+        let (_, rest_state) = call(
+            &router,
+            "GET",
+            &format!("/api/v1/sessions/{}", session_id),
+            None,
+        )
+        .await;
+
+        assert_eq!(rest_state["game_state"], game_state_to_json(&grpc_state));
+        assert_eq!(
+            rest_state["game_state"]["players"].as_array().unwrap().len(),
+            grpc_state.players.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rest_set_ready_matches_grpc_error() {
+        let state = create_test_state(false);
+        let router = rest_gateway_router(state.clone());
+
+        let grpc = state
+            .session_service
+            .set_ready(Request::new(SetReadyRequest {
+                session_id: "missing".to_string(),
+                player_id: "nobody".to_string(),
+                ready: true,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (status, rest) = call(
+            &router,
+            "POST",
+            "/api/v1/sessions/missing/ready",
+            Some(json!({"player_id": "nobody"})),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(rest["success"], grpc.success);
+        assert_eq!(rest["error"], error_to_json(grpc.error.as_ref().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_rest_solo_turn_and_state_match_grpc() {
+        let state = create_test_state(true);
+        let router = rest_gateway_router(state.clone());
+
+        let (_, created) = call(
+            &router,
+            "POST",
+            "/api/v1/sessions",
+            Some(json!({"player_name": "Solo", "max_players": 2, "game_mode": "single-player"})),
+        )
+        .await;
+        let session_id = created["session_id"].as_str().unwrap().to_string();
+
+        let (status, turn) = call(
+            &router,
+            "POST",
+            &format!("/api/v1/games/{}/turn", session_id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(turn["success"], true);
+
+        let grpc = state
+            .game_service
+            .get_game_state(Request::new(GetGameStateRequest {
+                session_id: session_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (_, rest) = call(
+            &router,
+            "GET",
+            &format!("/api/v1/games/{}/state", session_id),
+            None,
+        )
+        .await;
+
+        assert_eq!(rest["success"], grpc.success);
+        assert_eq!(rest["current_tile"], grpc.current_tile);
+        assert_eq!(rest["current_turn"], grpc.current_turn);
+        assert_eq!(rest["is_game_finished"], grpc.is_game_finished);
+        assert_eq!(turn["announced_tile"], grpc.current_tile);
+    }
+
+    #[tokio::test]
+    async fn test_rest_make_move_accepts_object_move_data() {
+        let state = create_test_state(false);
+        let router = rest_gateway_router(state.clone());
+
+        let grpc = state
+            .game_service
+            .make_move(Request::new(MakeMoveRequest {
+                session_id: "missing".to_string(),
+                player_id: "p1".to_string(),
+                move_data: r#"{"position":3}"#.to_string(),
+                timestamp: 0,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (_, rest) = call(
+            &router,
+            "POST",
+            "/api/v1/games/missing/moves",
+            Some(json!({"player_id": "p1", "move_data": {"position": 3}})),
+        )
+        .await;
+
+        match grpc.result {
+            Some(make_move_response::Result::Error(error)) => {
+                assert_eq!(rest["success"], false);
+                assert_eq!(rest["error"]["code"], error.code);
+            }
+            _ => panic!("Expected an error for an unknown session"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest_ai_move_invalid_tile_matches_grpc() {
+        let state = create_test_state(true);
+        let router = rest_gateway_router(state.clone());
+
+        let grpc = state
+            .game_service
+            .get_ai_move(Request::new(GetAiMoveRequest {
+                tile_code: "000".to_string(),
+                board_state: vec![],
+                available_positions: vec![],
+                turn_number: 0,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (_, rest) = call(
+            &router,
+            "POST",
+            "/api/v1/ai-move",
+            Some(json!({"tile_code": "000"})),
+        )
+        .await;
+
+        assert_eq!(rest["success"], grpc.success);
+        assert_eq!(rest["recommended_position"], grpc.recommended_position);
+        assert_eq!(rest["error"]["code"], "INVALID_TILE");
+    }
+
+    #[tokio::test]
+    async fn test_rest_health_follows_readiness() {
+        let readiness = Readiness::new();
+        let state = create_test_state(false).with_readiness(readiness.clone());
+        let router = rest_gateway_router(state);
+
+        let (status, body) = call(&router, "GET", "/api/v1/health", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "NOT_SERVING");
+
+        readiness.set_serving();
+        let (status, body) = call(&router, "GET", "/api/v1/health", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "SERVING");
+    }
+
+    #[test]
+    fn test_embedded_json_fallback() {
+        assert_eq!(embedded_json(""), Value::Null);
+        assert_eq!(embedded_json(r#"{"a":1}"#), json!({"a": 1}));
+        assert_eq!(embedded_json("5-3-7"), json!("5-3-7"));
+    }
+
+    #[test]
+    fn test_status_to_http_mapping() {
+        assert_eq!(status_to_http(tonic::Code::NotFound), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status_to_http(tonic::Code::Unauthenticated),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_to_http(tonic::Code::Internal),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -3,12 +3,15 @@
 use crate::generated::takeiteasygame::v1::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::Mutex;
 
 use crate::services::session_manager::SessionManager;
 
 // Import de vos modules existants
+use crate::game::board_code::{
+    parse_plateau_code, parse_tile_sequence, reconstruct_deck_from_plateau,
+};
 use crate::game::create_deck::{create_deck, Deck};
 use crate::game::get_legal_moves::get_legal_moves;
 use crate::game::plateau::{create_plateau_empty, Plateau};
@@ -21,9 +24,10 @@ use crate::mcts::algorithm::{
 use crate::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::qvalue_net::QValueNet;
-use crate::recording::{get_recorder, PlayerType as RecorderPlayerType};
-use crate::scoring::scoring::result;
+use crate::recording::{get_human_move_book, get_recorder, PlayerType as RecorderPlayerType};
+use crate::scoring::rules::{result_with_rules, ScoringRules};
 use crate::strategy::gt_boost::gt_beam_v1_select;
+use crate::utils::random_index::with_seeded_rng;
 use rand::rngs::StdRng;
 use rand::{thread_rng, Rng, SeedableRng};
 // ============================================================================
@@ -42,6 +46,126 @@ pub struct TakeItEasyGameState {
     pub game_status: GameStatus,
     pub scores: HashMap<String, i32>,
     pub waiting_for_players: Vec<String>, // Qui doit encore jouer ce tour
+    #[serde(default)]
+    pub tile_draw_strategy: TileDrawStrategy,
+    /// Version monotone, incrémentée à chaque changement visible de l'état
+    #[serde(default)]
+    pub state_version: u64,
+    /// Dernières tuiles posées, utilisées pour les deltas (`compute_state_delta`),
+    /// au plus `PLACEMENT_LOG_CAPACITY` pour ne pas alourdir l'état sérialisé
+    #[serde(default)]
+    pub placements: Vec<TilePlacement>,
+    /// Version de la plus récente tuile sortie du journal (0 = journal complet)
+    #[serde(default)]
+    pub placements_pruned_through: u64,
+    /// Variante de score de la session, appliquée aux joueurs comme à l'IA
+    #[serde(default)]
+    pub scoring_rules: ScoringRules,
+    /// Dernier tour annoncé aux clients par StartTurn (machine à états du tour)
+    #[serde(default)]
+    pub announced_turn: Option<usize>,
+    /// Stratégie de l'adversaire IA de la session
+    #[serde(default)]
+    pub opponent: OpponentStrategy,
+    /// Générateur des tirages `Seeded`, sérialisé avec l'état entre les tours
+    #[serde(default)]
+    pub draw_rng: Option<SessionRng>,
+}
+
+/// Tuiles gardées dans le journal des deltas ; un client plus en retard se resynchronise
+pub const PLACEMENT_LOG_CAPACITY: usize = 16;
+
+/// Tuile posée par un joueur, estampillée avec la version de l'état qui l'a introduite.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TilePlacement {
+    pub version: u64,
+    pub player_id: String,
+    pub position: usize,
+    pub tile: Tile,
+}
+
+impl TakeItEasyGameState {
+    /// Nouvelle version de l'état (tirage, fin de tour, pose de tuile)
+    pub fn bump_version(&mut self) -> u64 {
+        self.state_version += 1;
+        self.state_version
+    }
+
+    /// Enregistre une tuile déjà placée sur le plateau de `player_id`
+    pub fn record_placement(&mut self, player_id: &str, position: usize, tile: Tile) {
+        let version = self.bump_version();
+        self.placements.push(TilePlacement {
+            version,
+            player_id: player_id.to_string(),
+            position,
+            tile,
+        });
+        if self.placements.len() > PLACEMENT_LOG_CAPACITY {
+            let excess = self.placements.len() - PLACEMENT_LOG_CAPACITY;
+            let dropped = self.placements.drain(..excess);
+            self.placements_pruned_through = dropped.last().map_or(0, |p| p.version);
+        }
+    }
+}
+
+/// How the announced tile is drawn from the remaining deck at each turn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileDrawStrategy {
+    /// Uniform draw among the remaining tiles (default game behaviour)
+    #[default]
+    UniformRandom,
+    /// Announce the provided tiles in order (one per turn)
+    FixedSequence(Vec<Tile>),
+    /// Uniform draw driven by the session seed, reproducible across replays
+    Seeded(u64),
+}
+
+/// Tile draw generator of a `Seeded` session: one `StdRng` stream seeded
+/// from the session seed.
+///
+/// The game state only keeps the seed and the number of draws, so the stream
+/// survives serialization between turns; each draw reads one `u64` of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRng {
+    seed: u64,
+    draws: u64,
+}
+
+impl SessionRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, draws: 0 }
+    }
+
+    /// Index in `0..len` (`len > 0`) from the next value of the stream
+    pub fn index_below(&mut self, len: usize) -> usize {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for _ in 0..self.draws {
+            rng.random::<u64>();
+        }
+        self.draws += 1;
+        ((rng.random::<u64>() as u128 * len as u128) >> 64) as usize
+    }
+}
+
+/// How the AI opponent of a session picks its moves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpponentStrategy {
+    /// Graph Transformer policy argmax (default AI)
+    #[default]
+    GtDirect,
+    /// Move a human played in the closest recorded situation, GT fallback
+    HumanLike,
+}
+
+impl OpponentStrategy {
+    /// Opponent named `name` ("gt", "human-like"); empty = gt.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim() {
+            "" | "gt" => Ok(OpponentStrategy::GtDirect),
+            "human-like" => Ok(OpponentStrategy::HumanLike),
+            _ => Err("INVALID_OPPONENT".to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +208,143 @@ pub enum GameStatus {
 pub fn create_take_it_easy_game(
     session_id: String,
     player_ids: Vec<String>,
+) -> TakeItEasyGameState {
+    create_take_it_easy_game_with_strategy(session_id, player_ids, TileDrawStrategy::default())
+}
+
+pub fn create_take_it_easy_game_with_strategy(
+    session_id: String,
+    player_ids: Vec<String>,
+    tile_draw_strategy: TileDrawStrategy,
+) -> TakeItEasyGameState {
+    create_take_it_easy_game_with_options(
+        session_id,
+        player_ids,
+        tile_draw_strategy,
+        FULL_GAME_TURNS,
+    )
+}
+
+/// Nombre de tours d'une partie complète (une tuile par case)
+pub const FULL_GAME_TURNS: usize = 19;
+
+/// Valide la longueur de partie demandée : 0 = partie complète, sinon 1..=19.
+pub fn validate_num_turns(requested: i32) -> Result<usize, String> {
+    match requested {
+        0 => Ok(FULL_GAME_TURNS),
+        n if n >= 1 && n as usize <= FULL_GAME_TURNS => Ok(n as usize),
+        _ => Err("INVALID_NUM_TURNS".to_string()),
+    }
+}
+
+/// Plafond de la pause "réflexion" de l'IA
+pub const MAX_AI_THINK_DELAY_MS: u64 = 3000;
+
+/// Valide la pause "réflexion" demandée : négative refusée, plafonnée à
+/// `MAX_AI_THINK_DELAY_MS`.
+pub fn validate_ai_think_delay(requested_ms: i32) -> Result<u64, String> {
+    if requested_ms < 0 {
+        return Err("INVALID_AI_THINK_DELAY".to_string());
+    }
+    Ok((requested_ms as u64).min(MAX_AI_THINK_DELAY_MS))
+}
+
+/// Valide le plateau de départ (puzzle) : vide = partie normale. Le plateau
+/// doit laisser au moins un coup à jouer dans les `num_turns` tours.
+pub fn validate_starting_board(code: &str, num_turns: usize) -> Result<Option<Plateau>, String> {
+    if code.trim().is_empty() {
+        return Ok(None);
+    }
+    let plateau = parse_plateau_code(code).map_err(|e| {
+        log::warn!("Plateau de départ invalide: {}", e);
+        "INVALID_STARTING_BOARD".to_string()
+    })?;
+    let placed = plateau
+        .tiles
+        .iter()
+        .filter(|t| **t != Tile(0, 0, 0))
+        .count();
+    if placed >= num_turns {
+        return Err("INVALID_STARTING_BOARD".to_string());
+    }
+    Ok(Some(plateau))
+}
+
+/// Valide le tirage des tuiles demandé : vide / "random", "seeded" (graine
+/// `seed`) ou "sequence" (`sequence` au format de `board_code`, une tuile par
+/// tour). La séquence liste exactement les tuiles des tours qui restent à
+/// jouer après le plateau de départ, aucune n'étant déjà posée dessus.
+pub fn validate_tile_draw(
+    kind: &str,
+    seed: u64,
+    sequence: &str,
+    num_turns: usize,
+    starting_board: Option<&Plateau>,
+) -> Result<TileDrawStrategy, String> {
+    match kind.trim() {
+        "" | "random" => Ok(TileDrawStrategy::UniformRandom),
+        "seeded" => Ok(TileDrawStrategy::Seeded(seed)),
+        "sequence" => {
+            let tiles = parse_tile_sequence(sequence).map_err(|e| {
+                log::warn!("Séquence de tuiles invalide: {}", e);
+                "INVALID_TILE_DRAW".to_string()
+            })?;
+            // Les tours déjà joués du plateau de départ ne tirent rien
+            let placed: Vec<Tile> = starting_board
+                .map(|board| {
+                    board
+                        .tiles
+                        .iter()
+                        .copied()
+                        .filter(|t| *t != Tile(0, 0, 0))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if tiles.len() != num_turns.saturating_sub(placed.len())
+                || tiles.iter().any(|tile| placed.contains(tile))
+            {
+                return Err("INVALID_TILE_DRAW".to_string());
+            }
+            Ok(TileDrawStrategy::FixedSequence(tiles))
+        }
+        _ => Err("INVALID_TILE_DRAW".to_string()),
+    }
+}
+
+/// Partie reprise depuis `board` : tous les joueurs partent de ce plateau,
+/// le deck ne contient plus ses tuiles et les tours déjà joués sont comptés.
+pub fn with_starting_board(
+    mut game_state: TakeItEasyGameState,
+    board: &Plateau,
+) -> TakeItEasyGameState {
+    game_state.deck = reconstruct_deck_from_plateau(board);
+    game_state.current_turn = board.tiles.iter().filter(|t| **t != Tile(0, 0, 0)).count();
+
+    let mut player_ids: Vec<String> = game_state.player_plateaus.keys().cloned().collect();
+    // Ordre fixe : les versions du journal ne dépendent pas de l'ordre du HashMap
+    player_ids.sort();
+    for player_id in player_ids {
+        game_state
+            .player_plateaus
+            .insert(player_id.clone(), board.clone());
+        let score = result_with_rules(board, game_state.scoring_rules);
+        game_state.scores.insert(player_id.clone(), score);
+        for (position, tile) in board.tiles.iter().enumerate() {
+            if *tile != Tile(0, 0, 0) {
+                game_state.record_placement(&player_id, position, *tile);
+            }
+        }
+    }
+    game_state
+}
+
+/// Partie de `num_turns` tours (≤ 19) : elle s'arrête après ce tour et le
+/// plateau partiel est scoré tel quel.
+pub fn create_take_it_easy_game_with_options(
+    session_id: String,
+    player_ids: Vec<String>,
+    tile_draw_strategy: TileDrawStrategy,
+    num_turns: usize,
 ) -> TakeItEasyGameState {
     let deck = create_deck();
     let mut player_plateaus = HashMap::new();
@@ -127,10 +388,18 @@ pub fn create_take_it_easy_game(
         player_plateaus,
         current_tile: None,
         current_turn: 0,
-        total_turns: 19, // Comme dans votre implémentation
+        total_turns: num_turns.clamp(1, FULL_GAME_TURNS),
         game_status: GameStatus::InProgress,
         scores: player_ids.iter().map(|id| (id.clone(), 0)).collect(),
         waiting_for_players: vec![],
+        tile_draw_strategy,
+        state_version: 0,
+        placements: Vec::new(),
+        placements_pruned_through: 0,
+        scoring_rules: ScoringRules::default(),
+        announced_turn: None,
+        opponent: OpponentStrategy::default(),
+        draw_rng: None,
     }
 }
 
@@ -156,9 +425,13 @@ pub fn start_new_turn(mut game_state: TakeItEasyGameState) -> Result<TakeItEasyG
         return Err("NO_TILES_REMAINING".to_string());
     }
 
-    // 🎲 Piocher une tuile aléatoire SEULEMENT parmi les tuiles valides
-    let _tile_index = rand::rng().random_range(0..valid_tiles.len());
-    let chosen_tile = valid_tiles[_tile_index];
+    // 🎲 Piocher une tuile SEULEMENT parmi les tuiles valides, selon la stratégie de la session
+    let chosen_tile = draw_tile(
+        &game_state.tile_draw_strategy,
+        &mut game_state.draw_rng,
+        &valid_tiles,
+        game_state.total_turns - game_state.current_turn,
+    )?;
 
     log::info!(
         "🎲 Tuile tirée: {:?} (tour {})",
@@ -172,27 +445,70 @@ pub fn start_new_turn(mut game_state: TakeItEasyGameState) -> Result<TakeItEasyG
 
     // 🔧 TOUS LES JOUEURS (humains + MCTS) peuvent jouer immédiatement
     game_state.waiting_for_players = game_state.player_plateaus.keys().cloned().collect();
+    game_state.waiting_for_players.sort();
+    game_state.bump_version();
 
     Ok(game_state)
 }
 
-// Fonction utilitaire pour vérifier si on peut proposer une nouvelle tuile
-// Dans game_manager.rs - NOUVELLE fonction utilisant vos concepts
-
-// Dans game_manager.rs - AMÉLIORER ensure_current_tile
-pub fn ensure_current_tile(
-    mut game_state: TakeItEasyGameState,
-) -> Result<TakeItEasyGameState, String> {
-    if game_state.current_tile.is_some() {
-        // ✅ Une tuile existe déjà, pas besoin de modification
-        return Ok(game_state);
+/// Pick the announced tile among `valid_tiles` (never empty), `turns_left`
+/// turns (this one included) before the end of the game.
+///
+/// `Seeded` draws from the session generator `draw_rng`, created from the seed
+/// at the first draw, so the same seed replays the same sequence.
+/// `FixedSequence` lists the tiles of the last turns of the game, so its tile
+/// is `sequence[len - turns_left]`.
+pub fn draw_tile(
+    strategy: &TileDrawStrategy,
+    draw_rng: &mut Option<SessionRng>,
+    valid_tiles: &[Tile],
+    turns_left: usize,
+) -> Result<Tile, String> {
+    match strategy {
+        TileDrawStrategy::UniformRandom => {
+            Ok(valid_tiles[rand::rng().random_range(0..valid_tiles.len())])
+        }
+        TileDrawStrategy::Seeded(seed) => {
+            let rng = draw_rng.get_or_insert_with(|| SessionRng::new(*seed));
+            Ok(valid_tiles[rng.index_below(valid_tiles.len())])
+        }
+        TileDrawStrategy::FixedSequence(sequence) => {
+            let tile = sequence
+                .len()
+                .checked_sub(turns_left)
+                .and_then(|index| sequence.get(index))
+                .ok_or_else(|| "TILE_SEQUENCE_EXHAUSTED".to_string())?;
+            if valid_tiles.contains(tile) {
+                Ok(*tile)
+            } else {
+                Err("TILE_NOT_IN_DECK".to_string())
+            }
+        }
     }
+}
 
-    // 🎲 Aucune tuile courante, en tirer une NOUVELLE
-    game_state = start_new_turn(game_state)?;
+/// Seed of the AI searches of the whole server (`GrpcConfig::seed`)
+static SERVER_AI_SEED: OnceLock<u64> = OnceLock::new();
 
-    Ok(game_state)
+/// Make the AI searches of every session reproducible from `seed`; can only
+/// be set once
+pub fn set_server_ai_seed(seed: u64) -> Result<(), String> {
+    SERVER_AI_SEED
+        .set(seed)
+        .map_err(|_| "Server AI seed already set".to_string())
 }
+
+/// Seed of the AI search at `turn`: from the session seed for a `Seeded`
+/// session, so MCTS rollouts replay with the tiles, otherwise from the server
+/// seed if one is set (`None`: thread RNG).
+pub fn ai_search_seed(strategy: &TileDrawStrategy, turn: usize) -> Option<u64> {
+    let mix = |seed: u64| seed ^ (turn as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    match strategy {
+        TileDrawStrategy::Seeded(seed) => Some(mix(!seed)),
+        _ => SERVER_AI_SEED.get().map(|&seed| mix(seed)),
+    }
+}
+
 // game_manager.rs - dans apply_player_move
 // Dans game_manager.rs - AMÉLIORER apply_player_move
 pub fn apply_player_move(
@@ -249,7 +565,14 @@ pub fn apply_player_move(
         .ok_or_else(|| "PLAYER_NOT_FOUND".to_string())?;
 
     // Placer la tuile
-    player_plateau.tiles[player_move.position] = player_move.tile;
+    player_plateau
+        .place(player_move.position, player_move.tile)
+        .map_err(|e| e.code().to_string())?;
+    game_state.record_placement(
+        &player_move.player_id,
+        player_move.position,
+        player_move.tile,
+    );
 
     // Retirer le joueur de la liste d'attente
     game_state
@@ -305,20 +628,30 @@ pub async fn process_mcts_turn(
     let policy_locked = policy_net.lock().await;
     let value_locked = value_net.lock().await;
 
-    let mcts_result = mcts_find_best_position_for_tile_uct(
-        mcts_plateau,
-        &mut deck_clone,
-        current_tile,
-        &policy_locked,
-        &value_locked,
-        num_simulations,
-        game_state.current_turn,
-        game_state.total_turns,
-        None, // Use default hyperparameters
-        None, // No exploration noise (only for self-play training)
-    );
+    let mut search = || {
+        mcts_find_best_position_for_tile_uct(
+            mcts_plateau,
+            &mut deck_clone,
+            current_tile,
+            &policy_locked,
+            &value_locked,
+            num_simulations,
+            game_state.current_turn,
+            game_state.total_turns,
+            None, // Use default hyperparameters
+            None, // No exploration noise (only for self-play training)
+        )
+    };
+    let seed = ai_search_seed(&game_state.tile_draw_strategy, game_state.current_turn);
+    let mcts_result = match seed {
+        Some(seed) => with_seeded_rng(seed, search),
+        None => search(),
+    };
 
-    // ✅ VALIDATION: Position légale
+    // ✅ VALIDATION: Position légale (résultat terminal = aucun coup trouvé)
+    if mcts_result.is_terminal() {
+        return Err("NO_LEGAL_MOVES_FOR_MCTS".to_string());
+    }
     if !legal_moves.contains(&mcts_result.best_position) {
         log::error!(
             "❌ MCTS a choisi un mouvement illégal: {} (légaux: {:?})",
@@ -340,10 +673,14 @@ pub async fn process_mcts_turn(
             mcts_result.best_position,
             Some(mcts_result.subscore as f32),
         );
+        recorder.record_mcts_candidates(&game_state.session_id, "mcts_ai", &mcts_result);
     }
 
     // ✅ PLACEMENT UNIQUE DE LA TUILE
-    mcts_plateau.tiles[mcts_result.best_position] = current_tile;
+    mcts_plateau
+        .place(mcts_result.best_position, current_tile)
+        .map_err(|e| e.code().to_string())?;
+    game_state.record_placement("mcts_ai", mcts_result.best_position, current_tile);
 
     // ✅ RETIRER MCTS DE LA LISTE D'ATTENTE (important !)
     game_state.waiting_for_players.retain(|id| id != "mcts_ai");
@@ -383,44 +720,62 @@ pub async fn process_ai_turn_direct(
         return Err("NO_LEGAL_MOVES_FOR_AI".to_string());
     }
 
-    // GT Direct: argmax over policy logits (distilled expectimax model)
-    let t0 = std::time::Instant::now();
-    let policy_locked = policy_net.lock().await;
-    let gt_net = policy_locked
-        .as_graph_transformer()
-        .ok_or("GT Direct requires GraphTransformer architecture")?;
-
-    let feat = convert_plateau_for_gat_47ch(
-        ai_plateau,
-        &current_tile,
-        &game_state.deck,
-        game_state.current_turn,
-        19,
-    )
-    .unsqueeze(0)
-    .to_device(tch::Device::Cpu);
-    let logits = tch::no_grad(|| gt_net.forward(&feat, false))
-        .squeeze_dim(0)
-        .to_device(tch::Device::Cpu);
-    let logit_values: Vec<f64> = Vec::<f64>::try_from(&logits).unwrap();
-
-    let mut best_position = legal_moves[0];
-    let mut best_val = f64::NEG_INFINITY;
-    for &pos in &legal_moves {
-        if logit_values[pos] > best_val {
-            best_val = logit_values[pos];
-            best_position = pos;
+    // Adversaire "human-like" : coup humain enregistré le plus proche, sinon GT
+    let human_position = match game_state.opponent {
+        OpponentStrategy::HumanLike => {
+            get_human_move_book().and_then(|book| book.choose(ai_plateau, &current_tile))
         }
-    }
-    drop(policy_locked);
-    let elapsed = t0.elapsed();
+        OpponentStrategy::GtDirect => None,
+    };
 
-    log::info!(
-        "🎯 AI GT Direct: tile {:?} → position {} in {:.0?}",
-        current_tile,
-        best_position,
-        elapsed,
-    );
+    let (best_position, best_val, search_depth) = if let Some(position) = human_position {
+        log::info!(
+            "🧑 AI human-like: tile {:?} → position {} (recorded human move)",
+            current_tile,
+            position
+        );
+        (position, 0.0, 0)
+    } else {
+        // GT Direct: argmax over policy logits (distilled expectimax model)
+        let t0 = std::time::Instant::now();
+        let policy_locked = policy_net.lock().await;
+        let gt_net = policy_locked
+            .as_graph_transformer()
+            .ok_or("GT Direct requires GraphTransformer architecture")?;
+
+        let feat = convert_plateau_for_gat_47ch(
+            ai_plateau,
+            &current_tile,
+            &game_state.deck,
+            game_state.current_turn,
+            19,
+        )
+        .unsqueeze(0)
+        .to_device(policy_locked.device());
+        let logits = tch::no_grad(|| gt_net.forward(&feat, false))
+            .squeeze_dim(0)
+            .to_device(tch::Device::Cpu);
+        let logit_values: Vec<f64> = Vec::<f64>::try_from(&logits).unwrap();
+
+        let mut best_position = legal_moves[0];
+        let mut best_val = f64::NEG_INFINITY;
+        for &pos in &legal_moves {
+            if logit_values[pos] > best_val {
+                best_val = logit_values[pos];
+                best_position = pos;
+            }
+        }
+        drop(policy_locked);
+        let elapsed = t0.elapsed();
+
+        log::info!(
+            "🎯 AI GT Direct: tile {:?} → position {} in {:.0?}",
+            current_tile,
+            best_position,
+            elapsed,
+        );
+        (best_position, best_val, 1)
+    };
 
     // Record AI move
     let ai_plateau = game_state
@@ -445,7 +800,10 @@ pub async fn process_ai_turn_direct(
         .player_plateaus
         .get_mut("mcts_ai")
         .ok_or("MCTS_PLAYER_NOT_FOUND")?;
-    ai_plateau.tiles[best_position] = current_tile;
+    ai_plateau
+        .place(best_position, current_tile)
+        .map_err(|e| e.code().to_string())?;
+    game_state.record_placement("mcts_ai", best_position, current_tile);
 
     // Remove AI from waiting list
     game_state.waiting_for_players.retain(|id| id != "mcts_ai");
@@ -454,7 +812,7 @@ pub async fn process_ai_turn_direct(
         position: best_position,
         tile: current_tile,
         evaluation_score: best_val as f32,
-        search_depth: 1,         // GT Direct (no search)
+        search_depth, // 1 = GT Direct (no search), 0 = recorded human move
         variations_considered: legal_moves.len(),
     };
 
@@ -496,20 +854,30 @@ pub async fn process_mcts_turn_hybrid(
     let qvalue_locked = qvalue_net.lock().await;
 
     // Use Q-Net hybrid MCTS for best performance
-    let mcts_result = mcts_find_best_position_for_tile_with_qnet(
-        mcts_plateau,
-        &mut deck_clone,
-        current_tile,
-        &policy_locked,
-        &value_locked,
-        &qvalue_locked,
-        num_simulations,
-        game_state.current_turn,
-        game_state.total_turns,
-        top_k,
-        None,
-    );
+    let mut search = || {
+        mcts_find_best_position_for_tile_with_qnet(
+            mcts_plateau,
+            &mut deck_clone,
+            current_tile,
+            &policy_locked,
+            &value_locked,
+            &qvalue_locked,
+            num_simulations,
+            game_state.current_turn,
+            game_state.total_turns,
+            top_k,
+            None,
+        )
+    };
+    let seed = ai_search_seed(&game_state.tile_draw_strategy, game_state.current_turn);
+    let mcts_result = match seed {
+        Some(seed) => with_seeded_rng(seed, search),
+        None => search(),
+    };
 
+    if mcts_result.is_terminal() {
+        return Err("NO_LEGAL_MOVES_FOR_MCTS".to_string());
+    }
     if !legal_moves.contains(&mcts_result.best_position) {
         log::error!(
             "❌ MCTS HYBRID a choisi un mouvement illégal: {} (légaux: {:?})",
@@ -531,9 +899,13 @@ pub async fn process_mcts_turn_hybrid(
             mcts_result.best_position,
             Some(mcts_result.subscore as f32),
         );
+        recorder.record_mcts_candidates(&game_state.session_id, "mcts_ai", &mcts_result);
     }
 
-    mcts_plateau.tiles[mcts_result.best_position] = current_tile;
+    mcts_plateau
+        .place(mcts_result.best_position, current_tile)
+        .map_err(|e| e.code().to_string())?;
+    game_state.record_placement("mcts_ai", mcts_result.best_position, current_tile);
     game_state.waiting_for_players.retain(|id| id != "mcts_ai");
 
     let mcts_move = MctsMove {
@@ -558,10 +930,11 @@ pub fn check_turn_completion(
         let _completed_turn = game_state.current_turn;
         game_state.current_turn += 1;
         game_state.current_tile = None;
+        game_state.bump_version();
 
         // Mettre à jour les scores après chaque tour
         for (player_id, plateau) in &game_state.player_plateaus {
-            let current_score = result(plateau);
+            let current_score = result_with_rules(plateau, game_state.scoring_rules);
             game_state.scores.insert(player_id.clone(), current_score);
         }
 
@@ -635,6 +1008,30 @@ pub fn get_player_status(game_state: &TakeItEasyGameState, player_id: &str) -> P
     }
 }
 
+/// Tuile annoncée que `player_id` doit poser ce tour-ci.
+///
+/// Le joueur ne choisit jamais sa tuile : un coup n'est accepté que pendant un
+/// tour actif, par un joueur qui n'a pas encore joué ce tour.
+pub fn announced_tile_for_move(
+    game_state: &TakeItEasyGameState,
+    player_id: &str,
+) -> Result<Tile, String> {
+    match get_player_status(game_state, player_id) {
+        PlayerStatus::CanPlay => game_state
+            .current_tile
+            .ok_or_else(|| "NO_ACTIVE_TURN".to_string()),
+        PlayerStatus::GameFinished => Err("GAME_ALREADY_FINISHED".to_string()),
+        PlayerStatus::WaitingForNewTile => Err("NO_ACTIVE_TURN".to_string()),
+        PlayerStatus::WaitingForOthers => {
+            if game_state.player_plateaus.contains_key(player_id) {
+                Err("ALREADY_MOVED_THIS_TURN".to_string())
+            } else {
+                Err("PLAYER_NOT_FOUND".to_string())
+            }
+        }
+    }
+}
+
 pub fn get_all_players_status(game_state: &TakeItEasyGameState) -> HashMap<String, PlayerStatus> {
     let mut status_map = HashMap::new();
 
@@ -694,14 +1091,14 @@ pub async fn process_player_move_with_mcts(
 
     // 4. Calculer et mettre à jour les scores en temps réel
     for (player_id, plateau) in &new_state.player_plateaus {
-        let current_score = result(plateau);
+        let current_score = result_with_rules(plateau, new_state.scoring_rules);
         new_state.scores.insert(player_id.clone(), current_score);
     }
 
     let initial_score = *new_state.scores.get(&player_move.player_id).unwrap_or(&0);
     let points_earned = if let Some(plateau) = new_state.player_plateaus.get(&player_move.player_id)
     {
-        result(plateau) - initial_score
+        result_with_rules(plateau, new_state.scoring_rules) - initial_score
     } else {
         0
     };
@@ -858,7 +1255,8 @@ pub async fn compute_ai_move_background(
                                 .player_plateaus
                                 .insert("mcts_ai".to_string(), ai_plateau.clone());
                             // Recalculate AI score from updated plateau
-                            let ai_score = result(ai_plateau);
+                            let ai_score =
+                                result_with_rules(ai_plateau, current_state.scoring_rules);
                             current_state.scores.insert("mcts_ai".to_string(), ai_score);
                         }
                         session.board_state =
@@ -965,6 +1363,76 @@ pub async fn process_player_move_with_hybrid_mcts(
 // CONVERSION VERS PROTOBUF (COMPATIBLE AVEC VOS TYPES)
 // ============================================================================
 
+// ============================================================================
+// DELTA D'ÉTAT : seulement ce qui a changé depuis une version donnée
+// ============================================================================
+
+/// Changements de l'état depuis `since_version` (voir `compute_state_delta`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameStateDelta {
+    pub version: u64,
+    /// `since_version` inconnue (future) ou antérieure au journal : le delta
+    /// est vide et le client doit relire l'état complet
+    pub resync: bool,
+    pub placed_tiles: Vec<TilePlacement>,
+    /// (player_id, points gagnés depuis `since_version`, score actuel du plateau)
+    pub score_deltas: Vec<(String, i32, i32)>,
+    pub current_turn: usize,
+    pub current_tile: Option<Tile>,
+    pub waiting_for_players: Vec<String>,
+    pub is_game_finished: bool,
+}
+
+/// Delta entre la version `since_version` connue du client et l'état courant.
+///
+/// Les points gagnés sont recalculés en retirant du plateau les tuiles posées
+/// après `since_version`, ce qui ne dépend pas du moment où `scores` est mis à jour.
+/// Si le journal ne couvre plus `since_version`, ou si elle dépasse la version
+/// courante, le delta demande une resynchronisation au lieu d'être partiel.
+pub fn compute_state_delta(game_state: &TakeItEasyGameState, since_version: u64) -> GameStateDelta {
+    let resync = since_version > game_state.state_version
+        || since_version < game_state.placements_pruned_through;
+    let placed_tiles: Vec<TilePlacement> = game_state
+        .placements
+        .iter()
+        .filter(|p| !resync && p.version > since_version)
+        .cloned()
+        .collect();
+
+    let mut players: Vec<&String> = placed_tiles.iter().map(|p| &p.player_id).collect();
+    players.sort();
+    players.dedup();
+
+    let score_deltas = players
+        .into_iter()
+        .filter_map(|player_id| {
+            let plateau = game_state.player_plateaus.get(player_id)?;
+            let mut previous = plateau.clone();
+            for placement in placed_tiles.iter().filter(|p| &p.player_id == player_id) {
+                previous.tiles[placement.position] = Tile(0, 0, 0);
+            }
+            let rules = game_state.scoring_rules;
+            let score = result_with_rules(plateau, rules);
+            Some((
+                player_id.clone(),
+                score - result_with_rules(&previous, rules),
+                score,
+            ))
+        })
+        .collect();
+
+    GameStateDelta {
+        version: game_state.state_version,
+        resync,
+        placed_tiles,
+        score_deltas,
+        current_turn: game_state.current_turn,
+        current_tile: game_state.current_tile,
+        waiting_for_players: game_state.waiting_for_players.clone(),
+        is_game_finished: is_game_finished(game_state),
+    }
+}
+
 pub fn take_it_easy_state_to_protobuf(state: &TakeItEasyGameState, game_mode: &str) -> GameState {
     let players: Vec<crate::generated::takeiteasygame::v1::Player> = state
         .scores
@@ -1004,13 +1472,23 @@ pub fn player_move_from_json(move_data: &str, player_id: &str) -> Result<PlayerM
     #[derive(Deserialize)]
     struct MoveData {
         position: usize,
+        // Ignoré : la tuile est définie par le serveur, quelle que soit sa forme
         #[allow(dead_code)]
-        tile: Option<(i32, i32, i32)>, // Optionnel car défini par le serveur
+        tile: Option<serde::de::IgnoredAny>,
     }
 
     let data: MoveData =
         serde_json::from_str(move_data).map_err(|e| format!("Invalid move format: {}", e))?;
 
+    // Rejeté dès le parsing plutôt que de laisser une position arbitraire
+    // atteindre la logique de jeu
+    if data.position >= 19 {
+        return Err(format!(
+            "Invalid move format: position {} out of range",
+            data.position
+        ));
+    }
+
     Ok(PlayerMove {
         player_id: player_id.to_string(),
         position: data.position,
@@ -1028,6 +1506,7 @@ mod tests {
     use super::*;
     use crate::game::create_deck::create_deck;
     use crate::game::plateau::create_plateau_empty;
+    use crate::scoring::scoring::result;
 
     fn create_test_game_state() -> TakeItEasyGameState {
         let mut player_plateaus = HashMap::new();
@@ -1044,9 +1523,32 @@ mod tests {
             game_status: GameStatus::InProgress,
             scores: HashMap::new(),
             waiting_for_players: vec!["player1".to_string(), "player2".to_string()],
+            tile_draw_strategy: TileDrawStrategy::UniformRandom,
+            state_version: 0,
+            placements: Vec::new(),
+            placements_pruned_through: 0,
+            scoring_rules: ScoringRules::default(),
+            announced_turn: None,
+            opponent: OpponentStrategy::default(),
+            draw_rng: None,
         }
     }
 
+    fn draw_full_sequence(strategy: TileDrawStrategy) -> Result<Vec<Tile>, String> {
+        let mut game = create_take_it_easy_game_with_strategy(
+            "draw_session".to_string(),
+            vec!["player1".to_string()],
+            strategy,
+        );
+        let mut tiles = Vec::new();
+        for _ in 0..19 {
+            game = start_new_turn(game)?;
+            tiles.push(game.current_tile.take().unwrap());
+            game.current_turn += 1;
+        }
+        Ok(tiles)
+    }
+
     #[test]
     fn test_is_game_finished_in_progress() {
         let game_state = create_test_game_state();
@@ -1111,6 +1613,39 @@ mod tests {
         assert!(matches!(status, PlayerStatus::WaitingForOthers));
     }
 
+    #[test]
+    fn test_announced_tile_for_move() {
+        let mut game_state = create_test_game_state();
+        assert_eq!(
+            announced_tile_for_move(&game_state, "player1"),
+            Ok(Tile(1, 2, 3))
+        );
+        assert_eq!(
+            announced_tile_for_move(&game_state, "ghost"),
+            Err("PLAYER_NOT_FOUND".to_string())
+        );
+
+        // Déjà joué ce tour
+        game_state.waiting_for_players = vec!["player2".to_string()];
+        assert_eq!(
+            announced_tile_for_move(&game_state, "player1"),
+            Err("ALREADY_MOVED_THIS_TURN".to_string())
+        );
+
+        // Aucun tour actif : pas de tirage implicite
+        game_state.current_tile = None;
+        assert_eq!(
+            announced_tile_for_move(&game_state, "player2"),
+            Err("NO_ACTIVE_TURN".to_string())
+        );
+
+        game_state.game_status = GameStatus::Finished;
+        assert_eq!(
+            announced_tile_for_move(&game_state, "player2"),
+            Err("GAME_ALREADY_FINISHED".to_string())
+        );
+    }
+
     #[test]
     fn test_get_player_status_waiting_for_new_tile() {
         let mut game_state = create_test_game_state();
@@ -1129,6 +1664,73 @@ mod tests {
         assert!(matches!(status, PlayerStatus::GameFinished));
     }
 
+    #[test]
+    fn test_state_delta_after_one_move_contains_only_that_move() {
+        let mut game_state = create_test_game_state();
+        game_state.player_plateaus.get_mut("player2").unwrap().tiles[0] = Tile(1, 2, 3);
+        game_state.record_placement("player2", 0, Tile(1, 2, 3));
+        game_state.player_plateaus.get_mut("player1").unwrap().tiles[0] = Tile(1, 6, 3);
+        game_state.player_plateaus.get_mut("player1").unwrap().tiles[1] = Tile(1, 6, 4);
+        game_state.record_placement("player1", 0, Tile(1, 6, 3));
+        game_state.record_placement("player1", 1, Tile(1, 6, 4));
+        let since = game_state.state_version;
+
+        // Le coup qui complète la ligne horizontale 0-1-2 (v1 = 1 => 3 points)
+        let player_move = PlayerMove {
+            player_id: "player1".to_string(),
+            position: 2,
+            tile: Tile(1, 2, 3),
+            timestamp: 0,
+        };
+        let game_state = apply_player_move(game_state, player_move).unwrap();
+        let delta = compute_state_delta(&game_state, since);
+
+        assert_eq!(delta.version, since + 1);
+        assert_eq!(
+            delta.placed_tiles,
+            vec![TilePlacement {
+                version: since + 1,
+                player_id: "player1".to_string(),
+                position: 2,
+                tile: Tile(1, 2, 3),
+            }]
+        );
+        assert_eq!(delta.score_deltas, vec![("player1".to_string(), 3, 3)]);
+        assert_eq!(delta.current_turn, 1);
+        assert_eq!(delta.waiting_for_players, vec!["player2".to_string()]);
+
+        // Rien de nouveau depuis la dernière version
+        let empty = compute_state_delta(&game_state, delta.version);
+        assert!(empty.placed_tiles.is_empty());
+        assert!(empty.score_deltas.is_empty());
+    }
+
+    #[test]
+    fn test_state_delta_asks_for_resync_outside_the_log() {
+        let mut game_state = create_test_game_state();
+        for position in 0..PLACEMENT_LOG_CAPACITY + 2 {
+            let tile = Tile(1, 2, 3);
+            game_state.player_plateaus.get_mut("player1").unwrap().tiles[position] = tile;
+            game_state.record_placement("player1", position, tile);
+        }
+        assert_eq!(game_state.placements.len(), PLACEMENT_LOG_CAPACITY);
+        assert_eq!(game_state.placements_pruned_through, 2);
+
+        // Depuis la dernière tuile sortie du journal : delta complet
+        let delta = compute_state_delta(&game_state, 2);
+        assert!(!delta.resync);
+        assert_eq!(delta.placed_tiles.len(), PLACEMENT_LOG_CAPACITY);
+
+        // Tuiles sorties du journal ou version future : resynchronisation
+        for since in [0, 1, game_state.state_version + 1] {
+            let delta = compute_state_delta(&game_state, since);
+            assert!(delta.resync, "since {}", since);
+            assert!(delta.placed_tiles.is_empty());
+            assert!(delta.score_deltas.is_empty());
+            assert_eq!(delta.version, game_state.state_version);
+        }
+    }
+
     #[test]
     fn test_player_move_from_json_valid() {
         let json = r#"{"position": 5}"#;
@@ -1150,6 +1752,74 @@ mod tests {
         assert!(result.unwrap_err().contains("Invalid move format"));
     }
 
+    /// Fuzz léger du chemin parse + validation : aucune entrée ne doit paniquer
+    #[test]
+    fn test_player_move_from_json_never_panics_on_malformed_input() {
+        const VALUES: &[&str] = &[
+            "-1",
+            "3.5",
+            "1e309",
+            "18446744073709551616",
+            "99999999999999999999999",
+            "\"3\"",
+            "null",
+            "true",
+            "[]",
+            "{}",
+            "[1,2,3]",
+            "18",
+            "19",
+        ];
+        const FRAGMENTS: &[&str] = &[
+            "{", "}", "[", "]", ",", ":", "\"", "\\", "position", "tile", "\u{0}", "\u{10FFFF}",
+            "é", " ", "0", "-", "e",
+        ];
+
+        let mut rng = StdRng::seed_from_u64(1124);
+        let mut inputs: Vec<String> = vec![
+            String::new(),
+            "{}".to_string(),
+            r#"{"position": 3, "tile": "garbage"}"#.to_string(),
+            format!("{}{}", "[".repeat(10_000), "]".repeat(10_000)),
+        ];
+        for _ in 0..2000 {
+            let input = match rng.random_range(0..3) {
+                0 => {
+                    let value = VALUES[rng.random_range(0..VALUES.len())];
+                    let tile = VALUES[rng.random_range(0..VALUES.len())];
+                    format!(r#"{{"position": {}, "tile": {}}}"#, value, tile)
+                }
+                1 => (0..rng.random_range(0..24))
+                    .map(|_| FRAGMENTS[rng.random_range(0..FRAGMENTS.len())])
+                    .collect(),
+                _ => {
+                    // JSON valide tronqué à un point arbitraire
+                    let full = r#"{"position": 7, "tile": [1, 2, 3]}"#;
+                    full.chars().take(rng.random_range(0..full.len())).collect()
+                }
+            };
+            inputs.push(input);
+        }
+
+        for input in inputs {
+            let outcome = std::panic::catch_unwind(|| {
+                if let Ok(mut player_move) = player_move_from_json(&input, "player1") {
+                    assert!(player_move.position < 19, "input {:?}", input);
+                    player_move.tile = Tile(1, 2, 3);
+                    let _ = apply_player_move(create_test_game_state(), player_move);
+                }
+            });
+            assert!(outcome.is_ok(), "panic on move_data {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_player_move_from_json_rejects_out_of_range_position() {
+        let err = player_move_from_json(r#"{"position": 19}"#, "player1").unwrap_err();
+        assert!(err.contains("out of range"));
+        assert!(player_move_from_json(r#"{"position": 18, "tile": {"x": 1}}"#, "player1").is_ok());
+    }
+
     #[test]
     fn test_mcts_move_to_json() {
         let mcts_move = MctsMove {
@@ -1198,6 +1868,261 @@ mod tests {
         assert!(game.player_plateaus.contains_key("mcts_ai")); // MCTS always added
     }
 
+    #[test]
+    fn test_validate_num_turns_bounds() {
+        assert_eq!(validate_num_turns(0), Ok(19));
+        assert_eq!(validate_num_turns(1), Ok(1));
+        assert_eq!(validate_num_turns(10), Ok(10));
+        assert_eq!(validate_num_turns(19), Ok(19));
+        assert_eq!(validate_num_turns(20), Err("INVALID_NUM_TURNS".to_string()));
+        assert_eq!(validate_num_turns(-1), Err("INVALID_NUM_TURNS".to_string()));
+    }
+
+    #[test]
+    fn test_validate_ai_think_delay_capped() {
+        assert_eq!(validate_ai_think_delay(0), Ok(0));
+        assert_eq!(validate_ai_think_delay(800), Ok(800));
+        assert_eq!(validate_ai_think_delay(60_000), Ok(MAX_AI_THINK_DELAY_MS));
+        assert_eq!(
+            validate_ai_think_delay(-5),
+            Err("INVALID_AI_THINK_DELAY".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_starting_board() {
+        use crate::game::board_code::plateau_code;
+
+        let mut board = create_plateau_empty();
+        board.tiles[..15].copy_from_slice(&create_deck().tiles[..15]);
+        let code = plateau_code(&board);
+
+        assert_eq!(validate_starting_board("", FULL_GAME_TURNS), Ok(None));
+        assert_eq!(
+            validate_starting_board(&code, FULL_GAME_TURNS),
+            Ok(Some(board))
+        );
+        // 15 tiles already placed: a 15-turn game has nothing left to play
+        assert_eq!(
+            validate_starting_board(&code, 15),
+            Err("INVALID_STARTING_BOARD".to_string())
+        );
+        assert_eq!(
+            validate_starting_board(&code.replacen("123", "999", 1), FULL_GAME_TURNS),
+            Err("INVALID_STARTING_BOARD".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_tile_draw() {
+        assert_eq!(
+            validate_tile_draw("", 5, "", 19, None),
+            Ok(TileDrawStrategy::UniformRandom)
+        );
+        assert_eq!(
+            validate_tile_draw("seeded", 5, "", 19, None),
+            Ok(TileDrawStrategy::Seeded(5))
+        );
+        assert_eq!(
+            validate_tile_draw("shuffled", 5, "", 19, None),
+            Err("INVALID_TILE_DRAW".to_string())
+        );
+
+        assert_eq!(
+            validate_tile_draw("sequence", 0, "963,123", 2, None),
+            Ok(TileDrawStrategy::FixedSequence(vec![
+                Tile(9, 6, 3),
+                Tile(1, 2, 3)
+            ]))
+        );
+        // Trop courte pour la partie, ou tuile invalide
+        assert!(validate_tile_draw("sequence", 0, "963,123", 3, None).is_err());
+        assert!(validate_tile_draw("sequence", 0, "963,963", 2, None).is_err());
+
+        // Trop longue : les tuiles en trop ne sont pas ignorées
+        assert!(validate_tile_draw("sequence", 0, "963,123,578", 2, None).is_err());
+
+        // Plateau de départ à une tuile : la séquence ne couvre que le second tour
+        let mut board = create_plateau_empty();
+        board.tiles[4] = Tile(9, 6, 3);
+        assert_eq!(
+            validate_tile_draw("sequence", 0, "123", 2, Some(&board)),
+            Ok(TileDrawStrategy::FixedSequence(vec![Tile(1, 2, 3)]))
+        );
+        for sequence in ["963,123", "963"] {
+            assert_eq!(
+                validate_tile_draw("sequence", 0, sequence, 2, Some(&board)),
+                Err("INVALID_TILE_DRAW".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_short_game_ends_after_configured_turns() {
+        let mut game = create_take_it_easy_game_with_options(
+            "short_session".to_string(),
+            vec!["player1".to_string()],
+            TileDrawStrategy::Seeded(7),
+            10,
+        );
+        game = start_new_turn(game).unwrap();
+
+        let mut turns_played = 0;
+        while !is_game_finished(&game) {
+            let tile = game.current_tile.unwrap();
+            for player_id in ["player1", "mcts_ai"] {
+                let position = get_available_positions(&game, player_id)[0];
+                let player_move = PlayerMove {
+                    player_id: player_id.to_string(),
+                    position,
+                    tile,
+                    timestamp: 0,
+                };
+                game = apply_player_move(game, player_move).unwrap();
+            }
+            game = check_turn_completion(game).unwrap();
+            turns_played += 1;
+        }
+
+        assert_eq!(turns_played, 10);
+        assert_eq!(game.current_turn, 10);
+        assert!(matches!(game.game_status, GameStatus::Finished));
+        let plateau = &game.player_plateaus["player1"];
+        assert_eq!(
+            plateau.tiles.iter().filter(|t| **t != Tile(0, 0, 0)).count(),
+            10
+        );
+        // Partial board scored as-is
+        assert_eq!(game.scores["player1"], result(plateau));
+        assert!(start_new_turn(game).is_err());
+    }
+
+    #[test]
+    fn test_seeded_draw_is_reproducible() {
+        let first = draw_full_sequence(TileDrawStrategy::Seeded(42)).unwrap();
+        let second = draw_full_sequence(TileDrawStrategy::Seeded(42)).unwrap();
+
+        assert_eq!(first.len(), 19);
+        assert_eq!(first, second);
+
+        let other = draw_full_sequence(TileDrawStrategy::Seeded(43)).unwrap();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_seeded_draws_continue_across_serialization() {
+        let uninterrupted = draw_full_sequence(TileDrawStrategy::Seeded(42)).unwrap();
+
+        let mut game = create_take_it_easy_game_with_strategy(
+            "draw_session".to_string(),
+            vec!["player1".to_string()],
+            TileDrawStrategy::Seeded(42),
+        );
+        let mut tiles = Vec::new();
+        for _ in 0..19 {
+            game = start_new_turn(game).unwrap();
+            tiles.push(game.current_tile.take().unwrap());
+            game.current_turn += 1;
+            // Sauvegarde/restauration entre chaque tour : le générateur suit
+            let json = serde_json::to_string(&game).unwrap();
+            game = serde_json::from_str(&json).unwrap();
+        }
+        assert_eq!(tiles, uninterrupted);
+        assert!(game.draw_rng.is_some());
+    }
+
+    /// Partie scriptée contre l'IA MCTS : le joueur pose toujours sur la
+    /// première case libre. Renvoie les tuiles annoncées et le journal des poses.
+    async fn play_seeded_game(
+        seed: u64,
+        policy_net: &Mutex<PolicyNet>,
+        value_net: &Mutex<ValueNet>,
+    ) -> (Vec<Tile>, TakeItEasyGameState) {
+        let mut game = create_take_it_easy_game_with_strategy(
+            "seeded_session".to_string(),
+            vec!["player1".to_string()],
+            TileDrawStrategy::Seeded(seed),
+        );
+        game = start_new_turn(game).unwrap();
+        let mut tiles = Vec::new();
+        while !is_game_finished(&game) {
+            let tile = game.current_tile.unwrap();
+            tiles.push(tile);
+            let position = get_available_positions(&game, "player1")[0];
+            let player_move = PlayerMove {
+                player_id: "player1".to_string(),
+                position,
+                tile,
+                timestamp: 0,
+            };
+            game = apply_player_move(game, player_move).unwrap();
+            let (after_ai, _) = process_mcts_turn(game, policy_net, value_net, 8)
+                .await
+                .unwrap();
+            game = check_turn_completion(after_ai).unwrap();
+        }
+        (tiles, game)
+    }
+
+    #[tokio::test]
+    async fn test_seeded_session_replays_identical_game() {
+        use crate::neural::manager::NNArchitecture;
+        use tch::{nn, Device};
+
+        let vs = nn::VarStore::new(Device::Cpu);
+        let arch = NNArchitecture::Cnn;
+        let policy_net = Mutex::new(PolicyNet::new(&vs, arch.input_dim(), arch));
+        let value_net = Mutex::new(ValueNet::new(&vs, arch.input_dim(), arch));
+
+        let (tiles, game) = play_seeded_game(7, &policy_net, &value_net).await;
+        assert_eq!(tiles.len(), 19);
+        assert_eq!(game.placements.len(), PLACEMENT_LOG_CAPACITY);
+
+        // Même graine : mêmes tuiles, mêmes coups de l'IA, mêmes versions
+        let (replay_tiles, replay) = play_seeded_game(7, &policy_net, &value_net).await;
+        assert_eq!(replay_tiles, tiles);
+        assert_eq!(replay.player_plateaus, game.player_plateaus);
+        assert_eq!(
+            serde_json::to_string(&replay.placements).unwrap(),
+            serde_json::to_string(&game.placements).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fixed_sequence_respects_order() {
+        let sequence: Vec<Tile> = create_deck().tiles.into_iter().rev().take(19).collect();
+        let drawn = draw_full_sequence(TileDrawStrategy::FixedSequence(sequence.clone())).unwrap();
+        assert_eq!(drawn, sequence);
+    }
+
+    #[test]
+    fn test_fixed_sequence_errors() {
+        let short = vec![Tile(1, 2, 3)];
+        assert_eq!(
+            draw_full_sequence(TileDrawStrategy::FixedSequence(short)),
+            Err("TILE_SEQUENCE_EXHAUSTED".to_string())
+        );
+
+        let mut duplicated: Vec<Tile> = create_deck().tiles.into_iter().take(18).collect();
+        duplicated.push(duplicated[0]);
+        assert_eq!(
+            draw_full_sequence(TileDrawStrategy::FixedSequence(duplicated)),
+            Err("TILE_NOT_IN_DECK".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tile_draw_strategy_survives_serialization() {
+        let game = create_take_it_easy_game_with_strategy(
+            "s".to_string(),
+            vec!["player1".to_string()],
+            TileDrawStrategy::Seeded(7),
+        );
+        let json = serde_json::to_string(&game).unwrap();
+        let restored: TakeItEasyGameState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.tile_draw_strategy, TileDrawStrategy::Seeded(7));
+    }
+
     #[test]
     fn test_get_all_players_status() {
         let game_state = create_test_game_state();
@@ -0,0 +1,131 @@
+// src/services/game_service/ai_limiter.rs - Limite des calculs IA simultanés
+
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Borne le nombre de calculs de coups IA (MCTS, inférence) en parallèle.
+///
+/// Les demandes au-delà de la limite attendent leur tour dans l'ordre
+/// d'arrivée : la latence reste prévisible sous charge au lieu de saturer
+/// CPU et mémoire.
+#[derive(Clone, Default)]
+pub struct AiLimiter {
+    /// None = illimité
+    semaphore: Option<Arc<Semaphore>>,
+    max_concurrent: usize,
+}
+
+/// Place réservée pour un calcul IA, libérée au drop
+pub struct AiPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl AiLimiter {
+    /// `max_concurrent` calculs simultanés au plus (0 = illimité)
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            semaphore: (max_concurrent > 0).then(|| Arc::new(Semaphore::new(max_concurrent))),
+            max_concurrent,
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Limite configurée (0 = illimité)
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Attend une place libre
+    pub async fn acquire(&self) -> AiPermit {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("AI limiter semaphore is never closed"),
+            ),
+            None => None,
+        };
+        AiPermit { _permit: permit }
+    }
+
+    /// Place libre tout de suite, sans attendre (None = limite atteinte)
+    pub fn try_acquire(&self) -> Option<AiPermit> {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(semaphore.clone().try_acquire_owned().ok()?),
+            None => None,
+        };
+        Some(AiPermit { _permit: permit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limit_respected_and_queued_requests_complete() {
+        let limiter = AiLimiter::new(3);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..32)
+            .map(|i| {
+                let limiter = limiter.clone();
+                let in_flight = in_flight.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    i
+                })
+            })
+            .collect();
+
+        let mut completed = 0;
+        for handle in handles {
+            tokio::time::timeout(Duration::from_secs(10), handle)
+                .await
+                .expect("queued request never completed")
+                .unwrap();
+            completed += 1;
+        }
+
+        assert_eq!(completed, 32);
+        assert!(peak.load(Ordering::SeqCst) <= 3, "peak = {:?}", peak);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_zero_means_unlimited() {
+        let limiter = AiLimiter::new(0);
+        assert_eq!(limiter.max_concurrent(), 0);
+        // Aucune attente même avec de nombreux calculs en cours
+        let mut permits = Vec::new();
+        for _ in 0..100 {
+            permits.push(limiter.acquire().await);
+        }
+        assert_eq!(permits.len(), 100);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_try_acquire_fails_at_the_limit() {
+        let limiter = AiLimiter::new(2);
+        let first = limiter.try_acquire().unwrap();
+        let _second = limiter.try_acquire().unwrap();
+        assert!(limiter.try_acquire().is_none());
+
+        drop(first);
+        assert!(limiter.try_acquire().is_some());
+    }
+}
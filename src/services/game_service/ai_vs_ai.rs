@@ -0,0 +1,252 @@
+// src/services/game_service/ai_vs_ai.rs - Parties IA contre IA (démo, collecte de données)
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::generated::takeiteasygame::v1::Player;
+use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+use crate::recording::get_recorder;
+use crate::services::game_manager::{
+    check_turn_completion, create_take_it_easy_game_with_strategy, is_game_finished,
+    start_new_turn, TakeItEasyGameState, TileDrawStrategy,
+};
+use crate::services::session_manager::{
+    create_game_session, get_session_by_id_from_store, get_store_from_manager,
+    transform_session_in_store, update_session_in_store, GameSession, SessionManager,
+};
+
+use super::ai_limiter::AiLimiter;
+use super::mcts_integration::{drop_search_trees, process_mcts_move_for_player};
+
+/// Identifiants des deux IA ; le premier est le joueur IA historique du serveur
+pub const AI_VS_AI_PLAYERS: [&str; 2] = ["mcts_ai", "mcts_ai_b"];
+
+pub const AI_VS_AI_GAME_MODE: &str = "ai-vs-ai";
+
+/// Réseaux et budget de recherche d'une des deux IA
+#[derive(Clone)]
+pub struct AiVsAiPlayer {
+    pub policy_net: Arc<Mutex<PolicyNet>>,
+    /// None : MCTS à rollouts purs
+    pub value_net: Option<Arc<Mutex<ValueNet>>>,
+    pub num_simulations: usize,
+}
+
+// ============================================================================
+// CRÉATION DE LA SESSION SPECTATEUR
+// ============================================================================
+
+/// Crée une session sans joueur humain, déjà en cours : les observateurs la
+/// suivent via GetGameState / GetGameStateDelta.
+pub async fn create_ai_vs_ai_session(
+    session_manager: &Arc<SessionManager>,
+    tile_draw_strategy: TileDrawStrategy,
+) -> Result<GameSession, String> {
+    let mut session = create_game_session(2, AI_VS_AI_GAME_MODE.to_string());
+    let now = chrono::Utc::now().timestamp();
+    for player_id in AI_VS_AI_PLAYERS {
+        session.players.insert(
+            player_id.to_string(),
+            Player {
+                id: player_id.to_string(),
+                name: player_id.to_string(),
+                score: 0,
+                is_ready: true,
+                is_connected: true,
+                joined_at: now,
+            },
+        );
+    }
+    session.state = 1; // IN_PROGRESS
+    session.tile_draw_strategy = tile_draw_strategy;
+
+    update_session_in_store(get_store_from_manager(session_manager), session.clone()).await?;
+    Ok(session)
+}
+
+// ============================================================================
+// BOUCLE DE JEU
+// ============================================================================
+
+/// Joue la partie complète, `players[i]` pour l'IA `AI_VS_AI_PLAYERS[i]`.
+///
+/// L'état est publié dans la session après chaque coup. Sans `record`, la
+/// partie ouverte automatiquement dans l'enregistreur est annulée à la fin.
+/// Chaque coup prend une place dans `ai_limiter`.
+pub async fn run_ai_vs_ai_game(
+    session_manager: Arc<SessionManager>,
+    session_id: String,
+    players: [AiVsAiPlayer; 2],
+    ai_limiter: &AiLimiter,
+    record: bool,
+) -> Result<TakeItEasyGameState, String> {
+    let store = get_store_from_manager(&session_manager);
+    let tile_draw_strategy = get_session_by_id_from_store(store, &session_id)
+        .await
+        .ok_or_else(|| "SESSION_NOT_FOUND".to_string())?
+        .tile_draw_strategy;
+
+    let player_ids = AI_VS_AI_PLAYERS.iter().map(|id| id.to_string()).collect();
+    let mut game =
+        create_take_it_easy_game_with_strategy(session_id.clone(), player_ids, tile_draw_strategy);
+    game = start_new_turn(game)?;
+    publish_state(&session_manager, &game).await?;
+
+    let played = play_to_completion(&session_manager, game, &players, ai_limiter).await;
+    // Partie finie ou interrompue : les arbres de recherche ne resserviront pas
+    drop_search_trees(&session_id).await;
+    let game = played?;
+
+    if let Some(recorder) = get_recorder() {
+        if record {
+            if let Err(e) = recorder.finalize_game(&session_id, game.scores.clone()) {
+                log::error!("Failed to record AI-vs-AI game {}: {}", session_id, e);
+            }
+        } else {
+            recorder.cancel_game(&session_id);
+        }
+    }
+
+    log::info!(
+        "🤖 Partie IA contre IA {} terminée : {:?}",
+        session_id,
+        game.scores
+    );
+    Ok(game)
+}
+
+async fn play_to_completion(
+    session_manager: &Arc<SessionManager>,
+    mut game: TakeItEasyGameState,
+    players: &[AiVsAiPlayer; 2],
+    ai_limiter: &AiLimiter,
+) -> Result<TakeItEasyGameState, String> {
+    while !is_game_finished(&game) {
+        for (player_id, player) in AI_VS_AI_PLAYERS.iter().zip(players) {
+            let permit = ai_limiter.acquire().await;
+            let (next, _) = process_mcts_move_for_player(
+                game,
+                player_id,
+                &player.policy_net,
+                player.value_net.as_deref(),
+                player.num_simulations,
+            )
+            .await?;
+            drop(permit);
+            game = next;
+            publish_state(session_manager, &game).await?;
+        }
+        game = check_turn_completion(game)?;
+        publish_state(session_manager, &game).await?;
+    }
+    Ok(game)
+}
+
+async fn publish_state(
+    session_manager: &Arc<SessionManager>,
+    game: &TakeItEasyGameState,
+) -> Result<(), String> {
+    let board_state = serde_json::to_string(game).map_err(|e| e.to_string())?;
+    let finished = is_game_finished(game);
+    let store = get_store_from_manager(session_manager);
+    transform_session_in_store(store, &game.session_id, |mut session| {
+        session.board_state = board_state;
+        session.turn_number = game.current_turn as i32;
+        for (player_id, score) in &game.scores {
+            if let Some(player) = session.players.get_mut(player_id) {
+                player.score = *score;
+            }
+        }
+        if finished {
+            session.state = 2; // FINISHED
+        }
+        Ok((session, ()))
+    })
+    .await?
+    .ok_or_else(|| "SESSION_NOT_FOUND".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural::NeuralManager;
+    use crate::services::game_manager::PLACEMENT_LOG_CAPACITY;
+    use crate::services::session_manager::new_session_manager;
+
+    #[tokio::test]
+    async fn test_ai_vs_ai_session_plays_to_completion() {
+        let session_manager = Arc::new(new_session_manager());
+        let session = create_ai_vs_ai_session(&session_manager, TileDrawStrategy::Seeded(7))
+            .await
+            .unwrap();
+
+        let components = NeuralManager::new().unwrap().into_components();
+        let policy_net = Arc::new(Mutex::new(components.policy_net));
+        let value_net = Arc::new(Mutex::new(components.value_net));
+        let player = |num_simulations| AiVsAiPlayer {
+            policy_net: policy_net.clone(),
+            value_net: Some(value_net.clone()),
+            num_simulations,
+        };
+
+        let game = run_ai_vs_ai_game(
+            session_manager.clone(),
+            session.id.clone(),
+            [player(2), player(4)],
+            &AiLimiter::unlimited(),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(is_game_finished(&game));
+        assert_eq!(game.player_plateaus.len(), 2);
+        for player_id in AI_VS_AI_PLAYERS {
+            let plateau = &game.player_plateaus[player_id];
+            assert!(plateau.tiles.iter().all(|t| t.0 != 0), "{}", player_id);
+        }
+
+        // Les observateurs voient l'état final dans la session
+        let store = get_store_from_manager(&session_manager);
+        let stored = get_session_by_id_from_store(store, &session.id)
+            .await
+            .unwrap();
+        assert_eq!(stored.state, 2);
+        let published: TakeItEasyGameState = serde_json::from_str(&stored.board_state).unwrap();
+        assert_eq!(published.player_plateaus, game.player_plateaus);
+        // Journal des deltas borné : seules les dernières poses sont gardées
+        assert_eq!(published.placements.len(), PLACEMENT_LOG_CAPACITY);
+        assert!(published.placements_pruned_through > 0);
+    }
+
+    #[tokio::test]
+    async fn test_ai_vs_ai_without_value_net_uses_pure_rollouts() {
+        let session_manager = Arc::new(new_session_manager());
+        let session = create_ai_vs_ai_session(&session_manager, TileDrawStrategy::Seeded(11))
+            .await
+            .unwrap();
+
+        let components = NeuralManager::new().unwrap().into_components();
+        let player = AiVsAiPlayer {
+            policy_net: Arc::new(Mutex::new(components.policy_net)),
+            value_net: None,
+            num_simulations: 4,
+        };
+
+        let game = run_ai_vs_ai_game(
+            session_manager.clone(),
+            session.id.clone(),
+            [player.clone(), player],
+            &AiLimiter::new(1),
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(is_game_finished(&game));
+        for player_id in AI_VS_AI_PLAYERS {
+            let plateau = &game.player_plateaus[player_id];
+            assert!(plateau.tiles.iter().all(|t| t.0 != 0), "{}", player_id);
+        }
+    }
+}
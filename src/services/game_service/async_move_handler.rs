@@ -12,7 +12,7 @@ use crate::generated::takeiteasygame::v1::*;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::qvalue_net::QValueNet;
 use crate::services::game_manager::{
-    compute_ai_move_background, ensure_current_tile, is_game_finished, player_move_from_json,
+    announced_tile_for_move, compute_ai_move_background, is_game_finished, player_move_from_json,
     process_ai_turn_direct, process_player_move_immediate,
     process_player_move_with_direct_inference, process_player_move_with_hybrid_mcts,
     process_player_move_with_mcts, MoveResult, PlayerMove, TakeItEasyGameState,
@@ -21,8 +21,11 @@ use crate::services::session_manager::{
     get_store_from_manager, update_session_in_store, SessionManager,
 };
 
+use super::ai_limiter::AiLimiter;
+use super::mcts_integration::ai_think_pause;
 use super::response_builders::{make_move_error_response, make_move_success_response};
 use super::session_utils::get_session_by_code_or_id_from_store;
+use super::turn_manager::TurnPhase;
 
 // Global pending AI tasks: session_id → JoinHandle
 static PENDING_AI_TASKS: OnceLock<TokioMutex<HashMap<String, JoinHandle<()>>>> = OnceLock::new();
@@ -31,6 +34,22 @@ fn pending_ai_tasks() -> &'static TokioMutex<HashMap<String, JoinHandle<()>>> {
     PENDING_AI_TASKS.get_or_init(|| TokioMutex::new(HashMap::new()))
 }
 
+/// Attend la fin du calcul IA en arrière-plan de la session, s'il y en a un.
+/// Retourne `true` si une tâche était en cours.
+pub(super) async fn await_pending_ai_task(session_id: &str) -> bool {
+    // Le verrou global n'est pris que pour retirer la tâche : l'attendre en le
+    // tenant bloquerait les coups de toutes les autres sessions
+    let pending = pending_ai_tasks().lock().await.remove(session_id);
+    match pending {
+        Some(handle) => {
+            log::info!("Awaiting pending AI task for session {}", session_id);
+            let _ = handle.await;
+            true
+        }
+        None => false,
+    }
+}
+
 pub struct AsyncMoveRequest {
     pub session_id: String,
     pub player_id: String,
@@ -40,17 +59,23 @@ pub struct AsyncMoveRequest {
 
 /// Version asynchrone qui retourne immédiatement une confirmation
 /// et traite MCTS en arrière-plan (supporte Q-Net hybrid)
+#[allow(clippy::too_many_arguments)]
 pub async fn make_move_async_logic(
     session_manager: &Arc<SessionManager>,
     policy_net: &Arc<Mutex<PolicyNet>>,
     value_net: &Arc<Mutex<ValueNet>>,
     qvalue_net: Option<Arc<Mutex<QValueNet>>>,
+    ai_limiter: &AiLimiter,
     _num_simulations: usize, // Unused - simulations come from session config
     top_k: usize,
     request: AsyncMoveRequest,
 ) -> Result<Response<MakeMoveResponse>, Status> {
     let store = get_store_from_manager(session_manager);
 
+    // Le coup IA du tour précédent peut encore être en cours : le tour annoncé
+    // n'est à jour qu'une fois celui-ci appliqué
+    await_pending_ai_task(&request.session_id).await;
+
     let session = match get_session_by_code_or_id_from_store(store, &request.session_id).await {
         Some(session) => session,
         None => {
@@ -75,14 +100,36 @@ pub async fn make_move_async_logic(
                 .map_err(|e| Status::internal(format!("Failed to parse game state: {}", e)))?
         };
 
-    // Vérification: S'assurer qu'une tuile courante existe
-    let game_state = match ensure_current_tile(game_state) {
-        Ok(state) => state,
-        Err(e) => {
-            log::error!("❌ Échec garantie tuile: {}", e);
+    // Machine à états du tour : un coup ne se joue que sur un tour annoncé
+    let phase = TurnPhase::of(&session, Some(&game_state));
+    if let Err(error_code) = phase.check_make_move() {
+        log::warn!(
+            "❌ Coup refusé pour {} (session {}, phase {:?}): {}",
+            request.player_id,
+            request.session_id,
+            phase,
+            error_code
+        );
+        return Ok(Response::new(make_move_error_response(
+            error_code.clone(),
+            format!("MakeMove not allowed in phase {:?}: {}", phase, error_code),
+        )));
+    }
+
+    // Vérification: le coup pose la tuile annoncée du tour en cours, une seule
+    // fois par joueur (pas de tirage implicite hors tour)
+    let announced_tile = match announced_tile_for_move(&game_state, &request.player_id) {
+        Ok(tile) => tile,
+        Err(error_code) => {
+            log::warn!(
+                "❌ Coup refusé pour {} (session {}): {}",
+                request.player_id,
+                request.session_id,
+                error_code
+            );
             return Ok(Response::new(make_move_error_response(
-                "NO_CURRENT_TILE".to_string(),
-                format!("No current tile available: {}", e),
+                error_code.clone(),
+                format!("Move not allowed: {}", error_code),
             )));
         }
     };
@@ -92,9 +139,7 @@ pub async fn make_move_async_logic(
         Ok(mv) => {
             let mut mv = mv;
             mv.timestamp = request.timestamp;
-            if let Some(current_tile) = game_state.current_tile {
-                mv.tile = current_tile;
-            }
+            mv.tile = announced_tile;
             mv
         }
         Err(e) => {
@@ -109,6 +154,7 @@ pub async fn make_move_async_logic(
     // Utiliser le num_simulations de la session (configuré par le frontend)
     let session_simulations = session.num_simulations;
     let game_mode = session.game_mode.clone();
+    let ai_think_delay_ms = session.ai_think_delay_ms;
 
     log::info!(
         "🎯 Graph Transformer avec {} simulations max (mode: {})",
@@ -122,12 +168,14 @@ pub async fn make_move_async_logic(
         policy_net.clone(),
         value_net.clone(),
         qvalue_net.clone(),
+        ai_limiter.clone(),
         session_simulations,
         top_k,
         game_state,
         player_move,
         request.session_id.clone(),
         game_mode,
+        ai_think_delay_ms,
     )
     .await;
 
@@ -173,29 +221,23 @@ async fn process_mcts_and_respond(
     policy_net: Arc<Mutex<PolicyNet>>,
     value_net: Arc<Mutex<ValueNet>>,
     qvalue_net: Option<Arc<Mutex<QValueNet>>>,
+    ai_limiter: AiLimiter,
     num_simulations: usize,
     top_k: usize,
     game_state: TakeItEasyGameState,
     player_move: PlayerMove,
     session_id: String,
     game_mode: String,
+    ai_think_delay_ms: u64,
 ) -> MakeMoveResponse {
     // 1. Await any pending background AI task from the previous turn
-    let had_pending_task;
-    {
-        let mut tasks = pending_ai_tasks().lock().await;
-        if let Some(handle) = tasks.remove(&session_id) {
-            log::info!("Awaiting pending AI task for session {}", session_id);
-            let _ = handle.await;
-            had_pending_task = true;
-        } else {
-            had_pending_task = false;
-        }
-    }
+    let had_pending_task = await_pending_ai_task(&session_id).await;
 
     // Hybrid MCTS mode: fall back to synchronous processing (legacy path)
     if qvalue_net.is_some() {
         log::info!("🎯 Traitement HYBRID MCTS pour joueur {}", player_move.player_id);
+        ai_think_pause(ai_think_delay_ms).await;
+        let _permit = ai_limiter.acquire().await;
         return process_mcts_and_respond_sync(
             session_manager, policy_net, value_net, qvalue_net,
             num_simulations, top_k, game_state, player_move, session_id, game_mode,
@@ -232,12 +274,17 @@ async fn process_mcts_and_respond(
                 if game_over {
                     // Game over: compute AI's last move synchronously for complete final screen
                     log::info!("Game over: computing AI last move synchronously");
+                    ai_think_pause(ai_think_delay_ms).await;
+                    let _permit = ai_limiter.acquire().await;
                     match process_ai_turn_direct(ctx.game_state, &policy_net).await {
                         Ok((updated_ai_state, ai_move)) => {
                             if let Some(ai_plateau) = updated_ai_state.player_plateaus.get("mcts_ai") {
                                 move_result.new_game_state.player_plateaus
                                     .insert("mcts_ai".to_string(), ai_plateau.clone());
-                                let ai_score = crate::scoring::scoring::result(ai_plateau);
+                                let ai_score = crate::scoring::rules::result_with_rules(
+                                    ai_plateau,
+                                    move_result.new_game_state.scoring_rules,
+                                );
                                 move_result.new_game_state.scores
                                     .insert("mcts_ai".to_string(), ai_score);
                             }
@@ -251,6 +298,9 @@ async fn process_mcts_and_respond(
                     let pn = policy_net.clone();
                     let sid = session_id.clone();
                     let handle = tokio::spawn(async move {
+                        // Pause avant de réserver une place de calcul IA
+                        ai_think_pause(ai_think_delay_ms).await;
+                        let _permit = ai_limiter.acquire().await;
                         compute_ai_move_background(ctx, sm, pn).await;
                     });
                     pending_ai_tasks().lock().await.insert(sid, handle);
@@ -490,6 +540,14 @@ mod tests {
             game_status: crate::services::game_manager::GameStatus::InProgress,
             scores: HashMap::new(),
             waiting_for_players: vec!["player1".to_string()],
+            tile_draw_strategy: Default::default(),
+            state_version: 0,
+            placements: Vec::new(),
+            placements_pruned_through: 0,
+            scoring_rules: Default::default(),
+            announced_turn: None,
+            opponent: Default::default(),
+            draw_rng: None,
         }
     }
 
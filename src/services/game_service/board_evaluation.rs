@@ -0,0 +1,311 @@
+// src/services/game_service/board_evaluation.rs - Évaluation d'un plateau sans session (vue "analyse")
+
+use tokio::sync::Mutex;
+use tonic::{Response, Status};
+
+use crate::game::board_geometry::LINES;
+use crate::game::create_deck::create_deck;
+use crate::game::get_legal_moves::get_legal_moves;
+use crate::game::plateau::create_plateau_empty;
+use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+use crate::game::tile::Tile;
+use crate::generated::takeiteasygame::v1::{
+    Error, EvaluateBoardRequest, EvaluateBoardResponse, LineScore,
+};
+use crate::mcts::algorithm::convert_plateau_by_arch;
+use crate::neural::policy_value_net::ValueNet;
+use crate::scoring::rules::{result_with_rules, ScoringRules};
+
+use super::inference_cache::{inference_key, InferenceCache};
+use super::parse_tile_code;
+
+const TOTAL_TURNS: usize = 19;
+
+// ============================================================================
+// ÉVALUATION SANS ÉTAT
+// ============================================================================
+
+/// Score détaillé, estimation du ValueNet et coups légaux d'un plateau envoyé
+/// au format de `GetAiMove`. Aucune session n'est créée ni lue.
+/// Sans ValueNet (mode dégradé), l'estimation vaut 0. Les estimations des
+/// plateaux déjà évalués récemment sont reprises de `value_cache`.
+pub async fn evaluate_board_logic(
+    value_net: Option<&Mutex<ValueNet>>,
+    value_cache: &InferenceCache<f32>,
+    req: EvaluateBoardRequest,
+) -> Result<Response<EvaluateBoardResponse>, Status> {
+    if req.board_state.len() > TOTAL_TURNS {
+        return Ok(Response::new(evaluate_board_error(
+            "INVALID_BOARD",
+            format!("Board has {} cells, expected 19", req.board_state.len()),
+        )));
+    }
+    if !(0..TOTAL_TURNS as i32).contains(&req.turn_number) {
+        return Ok(Response::new(evaluate_board_error(
+            "INVALID_TURN",
+            format!("Invalid turn number: {}", req.turn_number),
+        )));
+    }
+    let rules = match ScoringRules::parse(&req.scoring_rules) {
+        Ok(rules) => rules,
+        Err(code) => {
+            return Ok(Response::new(evaluate_board_error(
+                &code,
+                format!("Unknown scoring rules: {}", req.scoring_rules),
+            )));
+        }
+    };
+
+    // Reconstruire plateau et sac (tuiles posées + tuile courante retirées)
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+    for (i, code) in req.board_state.iter().enumerate() {
+        if code.is_empty() {
+            continue;
+        }
+        match parse_tile_code(code) {
+            Some(tile) => {
+                if let Err(e) = plateau.place(i, tile) {
+                    return Ok(Response::new(evaluate_board_error(e.code(), e.to_string())));
+                }
+                deck = replace_tile_in_deck(&deck, &tile);
+            }
+            None => {
+                return Ok(Response::new(evaluate_board_error(
+                    "INVALID_BOARD",
+                    format!("Invalid tile code at position {}: {}", i, code),
+                )));
+            }
+        }
+    }
+
+    let tile = if req.tile_code.is_empty() {
+        Tile(0, 0, 0)
+    } else {
+        match parse_tile_code(&req.tile_code) {
+            Some(tile) => tile,
+            None => {
+                return Ok(Response::new(evaluate_board_error(
+                    "INVALID_TILE",
+                    format!("Invalid tile code: {}", req.tile_code),
+                )));
+            }
+        }
+    };
+    if tile != Tile(0, 0, 0) {
+        deck = replace_tile_in_deck(&deck, &tile);
+    }
+
+    // Score actuel, ligne par ligne (pondérée par la variante), plus son bonus
+    let bonus = rules.bonus(&plateau);
+    let score_breakdown = LINES
+        .iter()
+        .zip(rules.line_points(&plateau))
+        .filter(|(_, points)| *points > 0)
+        .map(|((positions, direction), points)| LineScore {
+            positions: positions.iter().map(|&p| p as i32).collect(),
+            direction: *direction as i32,
+            points,
+        })
+        .collect();
+
+    // Même encodage que le MCTS pour l'architecture du ValueNet
+    let value_estimate = match value_net {
+        Some(value_net) => {
+            let turn = req.turn_number as usize;
+            value_cache
+                .get_or_compute(inference_key(&plateau, &tile, turn), || async {
+                    let value_net = value_net.lock().await;
+                    let input = convert_plateau_by_arch(
+                        value_net.arch,
+                        &plateau,
+                        &tile,
+                        &deck,
+                        turn,
+                        TOTAL_TURNS,
+                    );
+                    let _guard = tch::no_grad_guard();
+                    value_net
+                        .forward(&input, false)
+                        .flatten(0, -1)
+                        .double_value(&[0]) as f32
+                })
+                .await
+        }
+        None => 0.0,
+    };
+
+    Ok(Response::new(EvaluateBoardResponse {
+        success: true,
+        score: result_with_rules(&plateau, rules),
+        bonus,
+        score_breakdown,
+        value_estimate,
+        legal_moves: get_legal_moves(&plateau)
+            .into_iter()
+            .map(|p| p as i32)
+            .collect(),
+        error: None,
+    }))
+}
+
+fn evaluate_board_error(code: &str, message: String) -> EvaluateBoardResponse {
+    EvaluateBoardResponse {
+        success: false,
+        error: Some(Error {
+            code: code.to_string(),
+            message,
+            details: Default::default(),
+        }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural::manager::NNArchitecture;
+    use crate::neural::tensor_conversion::convert_plateau_for_gat_47ch;
+    use crate::scoring::scoring::result;
+    use tch::{nn, Device};
+
+    #[tokio::test]
+    async fn test_value_matches_direct_forward() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let value_net = ValueNet::new(&vs, (5, 47, 1), NNArchitecture::GraphTransformer);
+
+        // Ligne horizontale du haut complète en 9 + une tuile isolée
+        let mut board_state = vec![String::new(); 19];
+        board_state[0] = "963".to_string();
+        board_state[1] = "974".to_string();
+        board_state[2] = "928".to_string();
+        board_state[9] = "573".to_string();
+        let request = EvaluateBoardRequest {
+            board_state: board_state.clone(),
+            tile_code: "168".to_string(),
+            turn_number: 4,
+            scoring_rules: String::new(),
+        };
+
+        let value_net = Mutex::new(value_net);
+        let response = evaluate_board_logic(Some(&value_net), &InferenceCache::disabled(), request)
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.success);
+
+        // Référence : forward direct du ValueNet sur le même plateau
+        let mut plateau = create_plateau_empty();
+        let mut deck = create_deck();
+        for (i, code) in board_state.iter().enumerate() {
+            if let Some(tile) = parse_tile_code(code) {
+                plateau.tiles[i] = tile;
+                deck = replace_tile_in_deck(&deck, &tile);
+            }
+        }
+        let tile = Tile(1, 6, 8);
+        deck = replace_tile_in_deck(&deck, &tile);
+        let expected = {
+            let value_net = value_net.lock().await;
+            let input = convert_plateau_for_gat_47ch(&plateau, &tile, &deck, 4, 19);
+            value_net
+                .forward(&input, false)
+                .flatten(0, -1)
+                .double_value(&[0]) as f32
+        };
+        assert!((response.value_estimate - expected).abs() < 1e-5);
+
+        assert_eq!(response.score, result(&plateau));
+        assert_eq!(response.score, 27);
+        assert_eq!(response.score_breakdown.len(), 1);
+        assert_eq!(response.score_breakdown[0].positions, vec![0, 1, 2]);
+        assert_eq!(response.legal_moves.len(), 15);
+        assert!(!response.legal_moves.contains(&9));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_request_reuses_cached_value() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let value_net = Mutex::new(ValueNet::new(
+            &vs,
+            (5, 47, 1),
+            NNArchitecture::GraphTransformer,
+        ));
+        let cache = InferenceCache::new(8, std::time::Duration::from_secs(60));
+        let mut board_state = vec![String::new(); 19];
+        board_state[4] = "573".to_string();
+        let request = EvaluateBoardRequest {
+            board_state,
+            tile_code: "168".to_string(),
+            turn_number: 1,
+            scoring_rules: String::new(),
+        };
+
+        let first = evaluate_board_logic(Some(&value_net), &cache, request.clone())
+            .await
+            .unwrap()
+            .into_inner();
+
+        // ValueNet verrouillé : seule une réponse du cache peut aboutir
+        let _locked = value_net.lock().await;
+        let second = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            evaluate_board_logic(Some(&value_net), &cache, request),
+        )
+        .await
+        .expect("cached request must not wait for the ValueNet")
+        .unwrap()
+        .into_inner();
+
+        assert_eq!(second.value_estimate, first.value_estimate);
+        let metrics = cache.metrics();
+        assert_eq!((metrics.hits, metrics.misses), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_without_value_net_still_scores_board() {
+        let mut board_state = vec![String::new(); 19];
+        board_state[4] = "573".to_string();
+        let response = evaluate_board_logic(
+            None,
+            &InferenceCache::disabled(),
+            EvaluateBoardRequest {
+                board_state,
+                tile_code: "168".to_string(),
+                turn_number: 1,
+                scoring_rules: String::new(),
+            },
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        assert!(response.success);
+        assert_eq!(response.value_estimate, 0.0);
+        assert_eq!(response.legal_moves.len(), 18);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tile_is_rejected() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let value_net = Mutex::new(ValueNet::new(
+            &vs,
+            (5, 47, 1),
+            NNArchitecture::GraphTransformer,
+        ));
+        let response = evaluate_board_logic(
+            Some(&value_net),
+            &InferenceCache::disabled(),
+            EvaluateBoardRequest {
+                board_state: vec![],
+                tile_code: "000".to_string(),
+                turn_number: 0,
+                scoring_rules: String::new(),
+            },
+        )
+        .await
+        .unwrap()
+        .into_inner();
+        assert!(!response.success);
+        assert_eq!(response.error.unwrap().code, "INVALID_TILE");
+    }
+}
@@ -0,0 +1,182 @@
+// src/services/game_service/inference_cache.rs - Cache court des sorties réseau par requête
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+
+/// Entrées conservées par défaut
+pub const DEFAULT_CAPACITY: usize = 256;
+/// Durée de vie par défaut d'une entrée
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+/// Clé d'une inférence : plateau, tuile et tour déterminent entièrement
+/// l'entrée du réseau (le sac se déduit du plateau et de la tuile).
+pub fn inference_key(plateau: &Plateau, tile: &Tile, turn: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    plateau.tiles.hash(&mut hasher);
+    tile.hash(&mut hasher);
+    turn.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Compteurs d'un cache, exposés par `/api/v1/metrics`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InferenceCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    /// hits / (hits + misses), None avant la première requête
+    pub hit_rate: Option<f64>,
+}
+
+/// Sorties réseau des dernières requêtes identiques (retries, clients
+/// multiples sur le même plateau), pour sauter le forward pass.
+///
+/// Contrairement à la table de transposition du MCTS, le cache vit au niveau
+/// des requêtes gRPC : taille bornée, entrées expirées après `ttl`, la plus
+/// ancienne évincée quand il est plein. Un cache par réseau servi.
+/// Capacité 0 = désactivé.
+#[derive(Clone)]
+pub struct InferenceCache<V> {
+    entries: Arc<Mutex<HashMap<u64, (Instant, V)>>>,
+    capacity: usize,
+    ttl: Duration,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl<V: Clone> InferenceCache<V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            capacity,
+            ttl,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    /// Valeur en cache pour `key`, sinon calculée par `compute` puis stockée
+    pub async fn get_or_compute<F, Fut>(&self, key: u64, compute: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        if self.capacity == 0 {
+            return compute().await;
+        }
+
+        if let Some(value) = self.get(key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return value;
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let value = compute().await;
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn get(&self, key: u64) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&key) {
+            Some((inserted, value)) if inserted.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: u64, value: V) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+            if entries.len() >= self.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (inserted, _))| *inserted)
+                    .map(|(k, _)| *k);
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, (Instant::now(), value));
+    }
+
+    pub fn metrics(&self) -> InferenceCacheMetrics {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let total = hits + misses;
+        InferenceCacheMetrics {
+            hits,
+            misses,
+            entries: self.entries.lock().unwrap().len(),
+            hit_rate: (total > 0).then(|| hits as f64 / total as f64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    async fn counted(cache: &InferenceCache<f32>, key: u64, calls: &AtomicUsize) -> f32 {
+        cache
+            .get_or_compute(key, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                key as f32
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_repeated_key_skips_compute_and_capacity_is_bounded() {
+        let cache = InferenceCache::new(2, Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        assert_eq!(counted(&cache, 1, &calls).await, 1.0);
+        assert_eq!(counted(&cache, 1, &calls).await, 1.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let metrics = cache.metrics();
+        assert_eq!((metrics.hits, metrics.misses), (1, 1));
+        assert_eq!(metrics.hit_rate, Some(0.5));
+
+        // Plein : la plus ancienne entrée (1) est évincée
+        counted(&cache, 2, &calls).await;
+        counted(&cache, 3, &calls).await;
+        assert_eq!(cache.metrics().entries, 2);
+        counted(&cache, 1, &calls).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_disabled_caches_recompute() {
+        let calls = AtomicUsize::new(0);
+        let expired = InferenceCache::new(8, Duration::ZERO);
+        counted(&expired, 7, &calls).await;
+        counted(&expired, 7, &calls).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let disabled = InferenceCache::disabled();
+        counted(&disabled, 7, &calls).await;
+        counted(&disabled, 7, &calls).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        assert_eq!(disabled.metrics().hit_rate, None);
+    }
+}
@@ -1,27 +1,109 @@
 // src/services/game_service/mcts_integration.rs - Intégration MCTS découplée
 
 use crate::game::get_legal_moves::get_legal_moves;
-use crate::mcts::algorithm::mcts_find_best_position_for_tile_with_nn;
+use crate::mcts::algorithm::{mcts_find_best_position_for_tile_reusing, MctsEvaluator};
+use crate::mcts::node::MctsTree;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
-use crate::services::game_manager::{apply_player_move, MctsMove, PlayerMove, TakeItEasyGameState};
+use crate::recording::get_recorder;
+use crate::services::game_manager::{
+    ai_search_seed, apply_player_move, is_game_finished, MctsMove, PlayerMove, TakeItEasyGameState,
+};
+use crate::utils::random_index::with_seeded_rng;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 // ============================================================================
 // INTÉGRATION MCTS DÉCOUPLÉE
 // ============================================================================
 
+/// Nombre maximal d'arbres conservés (le moins récemment utilisé est évincé)
+const MAX_SEARCH_TREES: usize = 256;
+
+/// Un arbre inutilisé depuis ce délai appartient à une partie abandonnée
+const SEARCH_TREE_TTL: Duration = Duration::from_secs(30 * 60);
+
+type SearchTrees = HashMap<(String, String), (MctsTree, Instant)>;
+
+/// Arbres MCTS conservés d'un tour à l'autre : (session, joueur IA) → arbre
+/// et dernier usage. Retiré au dernier coup de l'IA ou en fin de session ;
+/// les arbres des parties abandonnées expirent, et la table est bornée.
+static SEARCH_TREES: OnceLock<Mutex<SearchTrees>> = OnceLock::new();
+
+fn search_trees() -> &'static Mutex<SearchTrees> {
+    SEARCH_TREES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Range `tree` (utilisé à `now`) pour le prochain tour, après avoir purgé
+/// les arbres expirés et, table pleine, le moins récemment utilisé.
+fn store_search_tree(trees: &mut SearchTrees, key: (String, String), tree: MctsTree, now: Instant) {
+    trees.retain(|_, (_, used)| now.duration_since(*used) < SEARCH_TREE_TTL);
+    if trees.len() >= MAX_SEARCH_TREES && !trees.contains_key(&key) {
+        let oldest = trees
+            .iter()
+            .min_by_key(|(_, (_, used))| *used)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            trees.remove(&oldest);
+        }
+    }
+    trees.insert(key, (tree, now));
+}
+
+/// L'arbre ne sert que si l'IA rejoue dans cette partie.
+fn tree_still_useful(state: &TakeItEasyGameState) -> bool {
+    !is_game_finished(state) && state.current_turn + 1 < state.total_turns
+}
+
+/// Oublie les arbres de recherche d'une session terminée ou interrompue.
+pub async fn drop_search_trees(session_id: &str) {
+    search_trees()
+        .lock()
+        .await
+        .retain(|(session, _), _| session != session_id);
+}
+
+/// Pause "réflexion" de l'IA avant son coup, purement pour l'UX.
+///
+/// `tokio::time::sleep` : seule la tâche de cette session attend, le runtime
+/// continue de servir les autres sessions.
+pub async fn ai_think_pause(delay_ms: u64) {
+    if delay_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    }
+}
+
 #[allow(dead_code)]
 pub async fn process_mcts_move_only(
     game_state: TakeItEasyGameState,
     policy_net: &Mutex<PolicyNet>,
     value_net: &Mutex<ValueNet>,
     num_simulations: usize,
+) -> Result<(TakeItEasyGameState, MctsMove), String> {
+    process_mcts_move_for_player(
+        game_state,
+        "mcts_ai",
+        policy_net,
+        Some(value_net),
+        num_simulations,
+    )
+    .await
+}
+
+/// Joue le coup MCTS du joueur IA `player_id` (plusieurs IA possibles, ex. IA contre IA).
+///
+/// Sans ValueNet (`None`), la recherche se fait en rollouts purs. Les
+/// statistiques de la recherche sont réutilisées au tour suivant.
+pub async fn process_mcts_move_for_player(
+    game_state: TakeItEasyGameState,
+    player_id: &str,
+    policy_net: &Mutex<PolicyNet>,
+    value_net: Option<&Mutex<ValueNet>>,
+    num_simulations: usize,
 ) -> Result<(TakeItEasyGameState, MctsMove), String> {
     // ✅ VÉRIFICATION: MCTS doit être en attente
-    if !game_state
-        .waiting_for_players
-        .contains(&"mcts_ai".to_string())
-    {
+    if !game_state.waiting_for_players.iter().any(|id| id == player_id) {
         return Err("MCTS_NOT_WAITING".to_string());
     }
 
@@ -30,7 +112,7 @@ pub async fn process_mcts_move_only(
     // Récupérer le plateau MCTS
     let mcts_plateau = game_state
         .player_plateaus
-        .get("mcts_ai")
+        .get(player_id)
         .ok_or("MCTS_PLAYER_NOT_FOUND")?
         .clone();
 
@@ -42,32 +124,63 @@ pub async fn process_mcts_move_only(
 
     let mut deck_clone = game_state.deck.clone();
 
-    // Verrouiller les réseaux
-    let policy_locked = policy_net.lock().await;
-    let value_locked = value_net.lock().await;
-
     // ✅ EXÉCUTION MCTS
     let mut mcts_plateau_mut = mcts_plateau.clone();
-    let mcts_result = mcts_find_best_position_for_tile_with_nn(
-        &mut mcts_plateau_mut,
-        &mut deck_clone,
-        current_tile,
-        &policy_locked,
-        &value_locked,
-        num_simulations,
-        game_state.current_turn,
-        game_state.total_turns,
-        None,
-    );
+    // Verrouiller les réseaux
+    let policy_locked = policy_net.lock().await;
+    let value_locked = match value_net {
+        Some(value_net) => Some(value_net.lock().await),
+        None => None,
+    };
+    // Arbre du tour précédent de ce joueur, s'il y en a un
+    let tree_key = (game_state.session_id.clone(), player_id.to_string());
+    let mut tree = search_trees()
+        .lock()
+        .await
+        .remove(&tree_key)
+        .map(|(tree, _)| tree)
+        .unwrap_or_default();
+    let mut search = || {
+        let evaluator = match &value_locked {
+            Some(value_locked) => MctsEvaluator::Neural {
+                policy_net: &policy_locked,
+                value_net: value_locked,
+            },
+            None => MctsEvaluator::Pure,
+        };
+        mcts_find_best_position_for_tile_reusing(
+            &mut mcts_plateau_mut,
+            &mut deck_clone,
+            current_tile,
+            evaluator,
+            num_simulations,
+            game_state.current_turn,
+            game_state.total_turns,
+            None,
+            &mut tree,
+        )
+    };
+    // Graine de session ou du serveur : recherche reproductible
+    let seed = ai_search_seed(&game_state.tile_draw_strategy, game_state.current_turn);
+    let mcts_result = match seed {
+        Some(seed) => with_seeded_rng(seed, search),
+        None => search(),
+    };
+    drop(value_locked);
+    drop(policy_locked);
 
-    // ✅ VALIDATION: Position choisie doit être légale
+    // ✅ VALIDATION: Position choisie doit être légale (résultat terminal =
+    // aucun coup trouvé, jamais la case 0 par défaut)
+    if mcts_result.is_terminal() {
+        return Err("NO_LEGAL_MOVES_FOR_MCTS".to_string());
+    }
     if !legal_moves.contains(&mcts_result.best_position) {
         return Err("MCTS_ILLEGAL_MOVE".to_string());
     }
 
     // Créer le mouvement MCTS
     let mcts_player_move = PlayerMove {
-        player_id: "mcts_ai".to_string(),
+        player_id: player_id.to_string(),
         position: mcts_result.best_position,
         tile: current_tile,
         timestamp: chrono::Utc::now().timestamp(),
@@ -75,6 +188,17 @@ pub async fn process_mcts_move_only(
 
     // Appliquer le mouvement MCTS
     let updated_state = apply_player_move(game_state, mcts_player_move)?;
+    if let Some(recorder) = get_recorder() {
+        recorder.record_mcts_candidates(&updated_state.session_id, player_id, &mcts_result);
+    }
+
+    // Réenraciner l'arbre sur le coup joué pour le tour suivant (parties
+    // courtes comprises : rien n'est gardé après le dernier tour)
+    if tree_still_useful(&updated_state) {
+        tree.advance(mcts_result.best_position, current_tile);
+        let mut trees = search_trees().lock().await;
+        store_search_tree(&mut trees, tree_key, tree, Instant::now());
+    }
 
     let mcts_move = MctsMove {
         position: mcts_result.best_position,
@@ -86,3 +210,55 @@ pub async fn process_mcts_move_only(
 
     Ok((updated_state, mcts_move))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::game_manager::create_take_it_easy_game;
+
+    #[test]
+    fn test_no_tree_kept_after_the_last_turn_of_a_short_game() {
+        let mut game = create_take_it_easy_game("s".to_string(), vec!["mcts_ai".to_string()]);
+        game.total_turns = 5;
+        game.current_turn = 3;
+        assert!(tree_still_useful(&game));
+        // Dernier tour d'une partie courte : plateau loin d'être plein
+        game.current_turn = 4;
+        assert!(!tree_still_useful(&game));
+    }
+
+    #[test]
+    fn test_search_trees_are_bounded_and_expire() {
+        let mut trees = SearchTrees::new();
+        let key = |i: usize| (format!("s{}", i), "mcts_ai".to_string());
+        let start = Instant::now();
+        for i in 0..MAX_SEARCH_TREES + 10 {
+            let now = start + Duration::from_millis(i as u64);
+            store_search_tree(&mut trees, key(i), MctsTree::default(), now);
+        }
+        assert_eq!(trees.len(), MAX_SEARCH_TREES);
+        // Les plus anciens sont évincés en premier
+        assert!(!trees.contains_key(&key(0)));
+        assert!(trees.contains_key(&key(MAX_SEARCH_TREES + 9)));
+
+        // Les arbres des parties abandonnées expirent au prochain rangement
+        let later = start + SEARCH_TREE_TTL + Duration::from_secs(1);
+        store_search_tree(&mut trees, key(1000), MctsTree::default(), later);
+        assert_eq!(trees.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_end_drops_its_trees() {
+        {
+            let mut trees = search_trees().lock().await;
+            for (session, player) in [("ended", "a"), ("ended", "b"), ("other", "a")] {
+                let key = (session.to_string(), player.to_string());
+                store_search_tree(&mut trees, key, MctsTree::default(), Instant::now());
+            }
+        }
+        drop_search_trees("ended").await;
+        let trees = search_trees().lock().await;
+        assert!(!trees.keys().any(|(session, _)| session == "ended"));
+        assert!(trees.contains_key(&("other".to_string(), "a".to_string())));
+    }
+}
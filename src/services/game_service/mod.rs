@@ -4,20 +4,32 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tonic::{Request, Response, Status};
 
+use crate::auth::RateLimiter;
 use crate::generated::takeiteasygame::v1::game_service_server::GameService;
 use crate::generated::takeiteasygame::v1::*;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::qvalue_net::QValueNet;
-use crate::services::session_manager::SessionManager;
+use crate::services::session_manager::{
+    get_store_from_manager, transform_session_in_store, SessionManager,
+};
+use ai_limiter::AiLimiter;
+use inference_cache::{InferenceCache, InferenceCacheMetrics};
+use model_registry::{ModelRegistry, ModelVersionMetrics};
 
 // Modules internes
+pub mod ai_limiter;
+pub mod ai_vs_ai;
 pub mod async_move_handler;
 pub mod available_moves;
+pub mod board_evaluation;
+pub mod inference_cache;
 pub mod mcts_integration;
+pub mod model_registry;
 pub mod move_handler;
 pub mod response_builders;
 pub mod session_utils;
 pub mod state_provider;
+pub mod state_stream;
 pub mod turn_manager;
 
 // Réexports publics pour compatibilité
@@ -34,6 +46,22 @@ pub struct GameServiceImpl {
     qvalue_net: Option<Arc<Mutex<QValueNet>>>,
     num_simulations: usize,
     top_k: usize,
+    /// Versions de modèles en A/B test (None = un seul modèle)
+    model_registry: Option<Arc<ModelRegistry>>,
+    /// false si les poids du ValueNet n'ont pas pu être chargés
+    value_net_available: bool,
+    /// Borne les calculs de coups IA simultanés
+    ai_limiter: AiLimiter,
+    /// Logits du PolicyNet des requêtes GetAiMove récentes
+    policy_cache: InferenceCache<Vec<f32>>,
+    /// Estimations du ValueNet des requêtes EvaluateBoard récentes
+    value_cache: InferenceCache<f32>,
+    /// Requêtes GetAiMove par IP (endpoint sans état ni authentification)
+    ai_move_rate_limiter: RateLimiter,
+    /// Parties IA contre IA en cours, les demandes au-delà sont refusées
+    ai_vs_ai_games: AiLimiter,
+    /// Plafond des simulations par coup d'une partie IA contre IA (0 = aucun)
+    max_ai_vs_ai_simulations: usize,
 }
 
 impl GameServiceImpl {
@@ -50,6 +78,20 @@ impl GameServiceImpl {
             qvalue_net: None,
             num_simulations,
             top_k: 6,
+            model_registry: None,
+            value_net_available: true,
+            ai_limiter: AiLimiter::unlimited(),
+            policy_cache: InferenceCache::new(
+                inference_cache::DEFAULT_CAPACITY,
+                inference_cache::DEFAULT_TTL,
+            ),
+            value_cache: InferenceCache::new(
+                inference_cache::DEFAULT_CAPACITY,
+                inference_cache::DEFAULT_TTL,
+            ),
+            ai_move_rate_limiter: RateLimiter::unlimited(),
+            ai_vs_ai_games: AiLimiter::unlimited(),
+            max_ai_vs_ai_simulations: 0,
         }
     }
 
@@ -69,6 +111,160 @@ impl GameServiceImpl {
             qvalue_net,
             num_simulations,
             top_k,
+            model_registry: None,
+            value_net_available: true,
+            ai_limiter: AiLimiter::unlimited(),
+            policy_cache: InferenceCache::new(
+                inference_cache::DEFAULT_CAPACITY,
+                inference_cache::DEFAULT_TTL,
+            ),
+            value_cache: InferenceCache::new(
+                inference_cache::DEFAULT_CAPACITY,
+                inference_cache::DEFAULT_TTL,
+            ),
+            ai_move_rate_limiter: RateLimiter::unlimited(),
+            ai_vs_ai_games: AiLimiter::unlimited(),
+            max_ai_vs_ai_simulations: 0,
+        }
+    }
+
+    /// Répartir les nouvelles sessions entre plusieurs versions de modèles
+    pub fn with_model_registry(mut self, registry: Arc<ModelRegistry>) -> Self {
+        self.model_registry = Some(registry);
+        self
+    }
+
+    /// Mode dégradé sans ValueNet : MCTS en rollouts purs, et PolicyNet seul
+    /// à la place du MCTS hybride Q-Net.
+    pub fn without_value_net(mut self) -> Self {
+        log::warn!(
+            "⚠️ ValueNet indisponible : MCTS en rollouts purs / PolicyNet seul, force de l'IA réduite"
+        );
+        self.value_net_available = false;
+        self
+    }
+
+    /// Au plus `max_concurrent_ai` calculs IA simultanés, les suivants
+    /// attendent leur tour (0 = illimité)
+    pub fn with_max_concurrent_ai(mut self, max_concurrent_ai: usize) -> Self {
+        if max_concurrent_ai > 0 {
+            log::info!("🚦 Calculs IA simultanés limités à {}", max_concurrent_ai);
+        }
+        self.ai_limiter = AiLimiter::new(max_concurrent_ai);
+        self
+    }
+
+    /// Au plus `max_per_minute` requêtes GetAiMove par minute et par IP,
+    /// les suivantes reçoivent RESOURCE_EXHAUSTED (0 = illimité)
+    pub fn with_ai_move_rate_limit(mut self, max_per_minute: u32) -> Self {
+        if max_per_minute > 0 {
+            log::info!(
+                "🚦 GetAiMove limité à {} requêtes/min par IP",
+                max_per_minute
+            );
+        }
+        self.ai_move_rate_limiter = RateLimiter::per_minute(max_per_minute);
+        self
+    }
+
+    /// Au plus `max_games` parties IA contre IA simultanées, les suivantes
+    /// reçoivent RESOURCE_EXHAUSTED, et au plus `max_simulations` simulations
+    /// par coup (0 = illimité)
+    pub fn with_ai_vs_ai_limits(mut self, max_games: usize, max_simulations: usize) -> Self {
+        if max_games > 0 {
+            log::info!("🚦 Parties IA contre IA limitées à {}", max_games);
+        }
+        self.ai_vs_ai_games = AiLimiter::new(max_games);
+        self.max_ai_vs_ai_simulations = max_simulations;
+        self
+    }
+
+    /// Cache des sorties réseau de GetAiMove / EvaluateBoard : au plus
+    /// `capacity` plateaux par réseau, gardés `ttl` (0 = désactivé)
+    pub fn with_inference_cache(mut self, capacity: usize, ttl: std::time::Duration) -> Self {
+        self.policy_cache = InferenceCache::new(capacity, ttl);
+        self.value_cache = InferenceCache::new(capacity, ttl);
+        self
+    }
+
+    /// Taux de succès du cache d'inférence, par réseau
+    pub fn inference_cache_metrics(&self) -> [(&'static str, InferenceCacheMetrics); 2] {
+        [
+            ("policy", self.policy_cache.metrics()),
+            ("value", self.value_cache.metrics()),
+        ]
+    }
+
+    /// Réseaux de la version `name` du registre A/B (vide = réseaux par défaut)
+    fn nets_for_version(
+        &self,
+        name: &str,
+    ) -> Result<(Arc<Mutex<PolicyNet>>, Arc<Mutex<ValueNet>>), String> {
+        if name.is_empty() {
+            return Ok((self.policy_net.clone(), self.value_net.clone()));
+        }
+        self.model_registry
+            .as_ref()
+            .and_then(|registry| registry.version_named(name))
+            .map(|version| (version.policy_net.clone(), version.value_net.clone()))
+            .ok_or_else(|| "UNKNOWN_MODEL_VERSION".to_string())
+    }
+
+    /// Simulations par coup d'une IA de partie IA contre IA (`requested` ≥ 0,
+    /// 0 = valeur du serveur), plafonnées pour qu'une requête ne monopolise
+    /// pas l'IA
+    fn ai_vs_ai_simulations(&self, requested: i32) -> usize {
+        let sims = match requested {
+            0 => self.num_simulations,
+            n => n as usize,
+        };
+        match self.max_ai_vs_ai_simulations {
+            0 => sims,
+            max => sims.min(max),
+        }
+    }
+
+    /// Q-Net hybride, qui suppose un ValueNet chargé
+    fn available_qvalue_net(&self) -> Option<Arc<Mutex<QValueNet>>> {
+        self.qvalue_net.clone().filter(|_| self.value_net_available)
+    }
+
+    /// Réseaux à utiliser pour une session : la version assignée par le
+    /// registre A/B s'il y en a un, sinon les réseaux par défaut. La version
+    /// est enregistrée sur la session à la première assignation.
+    async fn nets_for_session(
+        &self,
+        session_id_or_code: &str,
+    ) -> (Arc<Mutex<PolicyNet>>, Arc<Mutex<ValueNet>>) {
+        let default_nets = (self.policy_net.clone(), self.value_net.clone());
+        let Some(registry) = &self.model_registry else {
+            return default_nets;
+        };
+
+        let store = get_store_from_manager(&self.session_manager);
+        match session_utils::get_session_by_code_or_id_from_store(store, session_id_or_code).await {
+            Some(session) => {
+                let version = registry.version_for_session(&session);
+                if session.model_version.is_none() {
+                    let name = version.name.clone();
+                    let _ = transform_session_in_store(store, &session.id, |mut session| {
+                        session.model_version.get_or_insert(name);
+                        Ok((session, ()))
+                    })
+                    .await;
+                }
+                (version.policy_net.clone(), version.value_net.clone())
+            }
+            // Session inconnue : la logique de jeu renverra SESSION_NOT_FOUND
+            None => default_nets,
+        }
+    }
+
+    /// Scores IA moyens par version de modèle (vide sans A/B test)
+    pub async fn model_metrics(&self) -> Vec<ModelVersionMetrics> {
+        match &self.model_registry {
+            Some(registry) => registry.collect_metrics(&self.session_manager).await,
+            None => Vec::new(),
         }
     }
 }
@@ -84,13 +280,15 @@ impl GameService for GameServiceImpl {
         request: Request<MakeMoveRequest>,
     ) -> Result<Response<MakeMoveResponse>, Status> {
         let req = request.into_inner();
+        let (policy_net, value_net) = self.nets_for_session(&req.session_id).await;
 
         // ✅ Utiliser le handler asynchrone avec support Q-Net hybrid
         async_move_handler::make_move_async_logic(
             &self.session_manager,
-            &self.policy_net,
-            &self.value_net,
-            self.qvalue_net.clone(),
+            &policy_net,
+            &value_net,
+            self.available_qvalue_net(),
+            &self.ai_limiter,
             self.num_simulations,
             self.top_k,
             async_move_handler::AsyncMoveRequest {
@@ -121,10 +319,11 @@ impl GameService for GameServiceImpl {
         request: Request<StartTurnRequest>,
     ) -> Result<Response<StartTurnResponse>, Status> {
         let req = request.into_inner();
+        let (policy_net, value_net) = self.nets_for_session(&req.session_id).await;
         turn_manager::start_turn_logic(
             &self.session_manager,
-            &self.policy_net,
-            &self.value_net,
+            &policy_net,
+            &value_net,
             self.qvalue_net.clone(),
             self.num_simulations,
             self.top_k,
@@ -141,11 +340,46 @@ impl GameService for GameServiceImpl {
         state_provider::get_game_state_logic(&self.session_manager, req.session_id).await
     }
 
+    async fn get_game_state_delta(
+        &self,
+        request: Request<GetGameStateDeltaRequest>,
+    ) -> Result<Response<GetGameStateDeltaResponse>, Status> {
+        let req = request.into_inner();
+        state_provider::get_game_state_delta_logic(
+            &self.session_manager,
+            req.session_id,
+            req.since_version,
+        )
+        .await
+    }
+
+    type StreamGameStateStream = state_stream::GameStateStream;
+
+    /// État poussé à chaque changement de la session, état courant d'abord
+    async fn stream_game_state(
+        &self,
+        request: Request<StreamGameStateRequest>,
+    ) -> Result<Response<Self::StreamGameStateStream>, Status> {
+        let req = request.into_inner();
+        state_stream::stream_game_state_logic(&self.session_manager, req.session_id).await
+    }
+
     /// Mode Jeu Réel: obtenir la recommandation IA pour une tuile donnée
     async fn get_ai_move(
         &self,
         request: Request<GetAiMoveRequest>,
     ) -> Result<Response<GetAiMoveResponse>, Status> {
+        // Limite par IP de l'appelant (appels internes sans adresse : pas de limite)
+        if let Some(addr) = request.remote_addr() {
+            if !self.ai_move_rate_limiter.check(&addr.ip().to_string()) {
+                return Err(Status::resource_exhausted(format!(
+                    "Too many AI move requests from {}, limit is {} per minute",
+                    addr.ip(),
+                    self.ai_move_rate_limiter.max_requests()
+                )));
+            }
+        }
+
         let req = request.into_inner();
 
         // Importer les modules nécessaires
@@ -160,15 +394,10 @@ impl GameService for GameServiceImpl {
         let _tile = match parse_tile_code(&req.tile_code) {
             Some(t) => t,
             None => {
-                return Ok(Response::new(GetAiMoveResponse {
-                    success: false,
-                    recommended_position: -1,
-                    error: Some(Error {
-                        code: "INVALID_TILE".to_string(),
-                        message: format!("Invalid tile code: {}", req.tile_code),
-                        details: Default::default(),
-                    }),
-                }));
+                return Ok(Response::new(ai_move_error(
+                    "INVALID_TILE",
+                    format!("Invalid tile code: {}", req.tile_code),
+                )));
             }
         };
 
@@ -177,17 +406,38 @@ impl GameService for GameServiceImpl {
         let mut plateau = create_plateau_empty();
         let mut deck = create_deck();
         for (i, tile_str) in req.board_state.iter().enumerate() {
-            if i < 19 && !tile_str.is_empty() {
-                if let Some(t) = parse_tile_code(tile_str) {
-                    plateau.tiles[i] = t;
-                    // Remove placed tile from deck
-                    deck = replace_tile_in_deck(&deck, &t);
-                }
+            if tile_str.is_empty() {
+                continue;
             }
+            // Code invalide ou case hors plateau : erreur explicite plutôt
+            // qu'une case vide silencieuse
+            let Some(t) = parse_tile_code(tile_str) else {
+                return Ok(Response::new(ai_move_error(
+                    "INVALID_BOARD",
+                    format!("Invalid tile code at position {}: {}", i, tile_str),
+                )));
+            };
+            if let Err(e) = plateau.place(i, t) {
+                return Ok(Response::new(ai_move_error(e.code(), e.to_string())));
+            }
+            // Remove placed tile from deck
+            deck = replace_tile_in_deck(&deck, &t);
         }
         // Also remove the current tile from deck
         deck = replace_tile_in_deck(&deck, &_tile);
 
+        // Plateau incohérent avec le tour ou les positions : refus plutôt que
+        // d'envoyer au réseau un encodage qui ne correspond à aucune partie
+        if let Err(error) =
+            validate_ai_move_board(&plateau, req.turn_number, &req.available_positions)
+        {
+            return Ok(Response::new(GetAiMoveResponse {
+                success: false,
+                recommended_position: -1,
+                error: Some(error),
+            }));
+        }
+
         // Obtenir les positions disponibles (où Tile(0,0,0) = vide)
         let available: Vec<usize> = if req.available_positions.is_empty() {
             plateau.tiles.iter().enumerate()
@@ -199,46 +449,59 @@ impl GameService for GameServiceImpl {
         };
 
         if available.is_empty() {
-            return Ok(Response::new(GetAiMoveResponse {
-                success: false,
-                recommended_position: -1,
-                error: Some(Error {
-                    code: "NO_POSITIONS".to_string(),
-                    message: "No available positions".to_string(),
-                    details: Default::default(),
-                }),
-            }));
+            return Ok(Response::new(ai_move_error(
+                "NO_POSITIONS",
+                "No available positions".to_string(),
+            )));
         }
 
-        // Utiliser le PolicyNet pour obtenir la meilleure position
-        let policy_net = self.policy_net.lock().await;
-        let arch = policy_net.arch;
-
         let current_turn = req.turn_number as usize;
         let total_turns = 19;
 
-        // Convertir le plateau en tensor (47 features pour Graph Transformer)
-        let input_tensor = match arch {
-            NNArchitecture::GraphTransformer | NNArchitecture::Gnn => {
-                convert_plateau_for_gat_47ch(&plateau, &_tile, &deck, current_turn, total_turns)
-            }
-            _ => {
-                // Pour CNN, on utilise aussi la version 47ch
-                convert_plateau_for_gat_47ch(&plateau, &_tile, &deck, current_turn, total_turns)
-            }
-        };
-
-        // Forward pass
-        let policy_output = policy_net.forward(&input_tensor, false);
-
-        // Extraire les probabilités pour les positions disponibles
-        let policy_vec: Vec<f32> = {
-            let flat = policy_output.flatten(0, -1);
-            let size = flat.size()[0] as usize;
-            let mut buf = vec![0f32; size];
-            flat.copy_data(&mut buf, size);
-            buf
-        };
+        // Utiliser le PolicyNet pour obtenir la meilleure position
+        // (requête identique récente : logits repris du cache)
+        let key = inference_cache::inference_key(&plateau, &_tile, current_turn);
+        let policy_vec: Vec<f32> = self
+            .policy_cache
+            .get_or_compute(key, || async {
+                let _permit = self.ai_limiter.acquire().await;
+                let policy_net = self.policy_net.lock().await;
+                let arch = policy_net.arch;
+
+                // Convertir le plateau en tensor (47 features pour Graph Transformer)
+                let input_tensor = match arch {
+                    NNArchitecture::GraphTransformer | NNArchitecture::Gnn => {
+                        convert_plateau_for_gat_47ch(
+                            &plateau,
+                            &_tile,
+                            &deck,
+                            current_turn,
+                            total_turns,
+                        )
+                    }
+                    _ => {
+                        // Pour CNN, on utilise aussi la version 47ch
+                        convert_plateau_for_gat_47ch(
+                            &plateau,
+                            &_tile,
+                            &deck,
+                            current_turn,
+                            total_turns,
+                        )
+                    }
+                };
+
+                // Forward pass
+                let policy_output = policy_net.forward(&input_tensor, false);
+
+                // Extraire les probabilités pour les positions disponibles
+                let flat = policy_output.flatten(0, -1);
+                let size = flat.size()[0] as usize;
+                let mut buf = vec![0f32; size];
+                flat.copy_data(&mut buf, size);
+                buf
+            })
+            .await;
 
         // Trouver la meilleure position: GT logits + line completion boost
         use crate::strategy::gt_boost::line_boost;
@@ -267,6 +530,113 @@ impl GameService for GameServiceImpl {
             error: None,
         }))
     }
+
+    /// Partie IA contre IA : la session est créée tout de suite, la partie est
+    /// jouée en tâche de fond et suivie par les observateurs
+    async fn create_ai_vs_ai_session(
+        &self,
+        request: Request<CreateAiVsAiSessionRequest>,
+    ) -> Result<Response<CreateAiVsAiSessionResponse>, Status> {
+        use crate::services::game_manager::TileDrawStrategy;
+
+        let error_response = |code: &str, message: String| {
+            Response::new(CreateAiVsAiSessionResponse {
+                success: false,
+                error: Some(Error {
+                    code: code.to_string(),
+                    message,
+                    details: Default::default(),
+                }),
+                ..Default::default()
+            })
+        };
+
+        let req = request.into_inner();
+        if req.simulations_a < 0 || req.simulations_b < 0 {
+            return Ok(error_response(
+                "INVALID_SIMULATIONS",
+                "Simulation counts must be >= 0".to_string(),
+            ));
+        }
+        let player = |model: &str, requested: i32| {
+            let (policy_net, value_net) = self.nets_for_version(model)?;
+            Ok::<_, String>(ai_vs_ai::AiVsAiPlayer {
+                policy_net,
+                value_net: self.value_net_available.then_some(value_net),
+                num_simulations: self.ai_vs_ai_simulations(requested),
+            })
+        };
+        let players = match (
+            player(&req.model_a, req.simulations_a),
+            player(&req.model_b, req.simulations_b),
+        ) {
+            (Ok(a), Ok(b)) => [a, b],
+            (Err(code), _) | (_, Err(code)) => {
+                return Ok(error_response(
+                    &code,
+                    format!(
+                        "Unknown model version (model_a '{}', model_b '{}')",
+                        req.model_a, req.model_b
+                    ),
+                ));
+            }
+        };
+
+        // Place réservée avant de créer la session, rendue à la fin de la partie
+        let Some(game_slot) = self.ai_vs_ai_games.try_acquire() else {
+            return Err(Status::resource_exhausted(format!(
+                "Too many AI-vs-AI games in progress, limit is {}",
+                self.ai_vs_ai_games.max_concurrent()
+            )));
+        };
+
+        let strategy = match req.seed {
+            0 => TileDrawStrategy::UniformRandom,
+            seed => TileDrawStrategy::Seeded(seed),
+        };
+        let session = match ai_vs_ai::create_ai_vs_ai_session(&self.session_manager, strategy).await
+        {
+            Ok(session) => session,
+            Err(e) => return Ok(error_response("CREATE_SESSION_FAILED", e)),
+        };
+
+        let session_manager = self.session_manager.clone();
+        let ai_limiter = self.ai_limiter.clone();
+        let session_id = session.id.clone();
+        let record = req.record;
+        tokio::spawn(async move {
+            let _game_slot = game_slot;
+            if let Err(e) = ai_vs_ai::run_ai_vs_ai_game(
+                session_manager,
+                session_id.clone(),
+                players,
+                &ai_limiter,
+                record,
+            )
+            .await
+            {
+                log::error!("AI-vs-AI game {} aborted: {}", session_id, e);
+            }
+        });
+
+        Ok(Response::new(CreateAiVsAiSessionResponse {
+            success: true,
+            session_id: session.id,
+            session_code: session.code,
+            error: None,
+        }))
+    }
+
+    /// Analyse d'un plateau arbitraire (score, ValueNet, coups légaux) sans session
+    async fn evaluate_board(
+        &self,
+        request: Request<EvaluateBoardRequest>,
+    ) -> Result<Response<EvaluateBoardResponse>, Status> {
+        let value_net = self.value_net_available.then_some(&*self.value_net);
+        let _permit = self.ai_limiter.acquire().await;
+        board_evaluation::evaluate_board_logic(value_net, &self.value_cache, request.into_inner())
+            .await
+    }
 }
 
 /// Parse un code de tuile (ex: "168") en Tile
@@ -279,19 +649,235 @@ fn parse_tile_code(code: &str) -> Option<crate::game::tile::Tile> {
         .replace(".png", "")
         .replace("../", "");
 
-    if clean_code.len() != 3 {
-        return None;
+    Tile::from_code(&clean_code).ok()
+}
+
+fn ai_move_error(code: &str, message: String) -> GetAiMoveResponse {
+    GetAiMoveResponse {
+        success: false,
+        recommended_position: -1,
+        error: Some(Error {
+            code: code.to_string(),
+            message,
+            details: Default::default(),
+        }),
+    }
+}
+
+/// Vérifie qu'un plateau reconstruit pour GetAiMove correspond à une partie
+/// possible : une tuile posée par tour joué, positions proposées libres
+fn validate_ai_move_board(
+    plateau: &crate::game::plateau::Plateau,
+    turn_number: i32,
+    available_positions: &[i32],
+) -> Result<(), Error> {
+    use crate::game::tile::Tile;
+
+    let error = |code: &str, message: String| Error {
+        code: code.to_string(),
+        message,
+        details: Default::default(),
+    };
+
+    let filled = plateau
+        .tiles
+        .iter()
+        .filter(|t| **t != Tile(0, 0, 0))
+        .count();
+    if !(0..19).contains(&turn_number) || filled != turn_number as usize {
+        return Err(error(
+            "BOARD_TURN_MISMATCH",
+            format!(
+                "Board has {} placed tiles but turn_number is {}",
+                filled, turn_number
+            ),
+        ));
+    }
+
+    for &position in available_positions {
+        let cell = usize::try_from(position)
+            .ok()
+            .and_then(|p| plateau.tiles.get(p));
+        match cell {
+            Some(t) if *t == Tile(0, 0, 0) => {}
+            Some(_) => {
+                return Err(error(
+                    "POSITION_OCCUPIED",
+                    format!("Available position {} is already occupied", position),
+                ))
+            }
+            None => {
+                return Err(error(
+                    "INVALID_POSITION",
+                    format!("Available position {} is outside the board", position),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural::manager::NNArchitecture;
+    use crate::services::session_manager::new_session_manager;
+    use tch::{nn, Device};
+    use tonic::transport::server::TcpConnectInfo;
+
+    fn ai_move_from(ip: &str) -> Request<GetAiMoveRequest> {
+        let mut request = Request::new(GetAiMoveRequest {
+            tile_code: "000".to_string(),
+            board_state: vec![],
+            available_positions: vec![],
+            turn_number: 0,
+        });
+        request.extensions_mut().insert(TcpConnectInfo {
+            local_addr: None,
+            remote_addr: Some(format!("{}:50000", ip).parse().unwrap()),
+        });
+        request
+    }
+
+    #[tokio::test]
+    async fn test_ai_move_rate_limit_is_per_client_ip() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let input_dim = (5, 47, 1);
+        let policy_net = PolicyNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let value_net = ValueNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let service = GameServiceImpl::new(
+            Arc::new(new_session_manager()),
+            Arc::new(Mutex::new(policy_net)),
+            Arc::new(Mutex::new(value_net)),
+            10,
+        )
+        .with_ai_move_rate_limit(3);
+
+        for _ in 0..3 {
+            assert!(service.get_ai_move(ai_move_from("10.0.0.1")).await.is_ok());
+        }
+        let throttled = service
+            .get_ai_move(ai_move_from("10.0.0.1"))
+            .await
+            .unwrap_err();
+        assert_eq!(throttled.code(), tonic::Code::ResourceExhausted);
+
+        // Une autre IP garde son propre quota
+        let other = service.get_ai_move(ai_move_from("10.0.0.2")).await.unwrap();
+        assert_eq!(other.into_inner().error.unwrap().code, "INVALID_TILE");
+    }
+
+    #[tokio::test]
+    async fn test_ai_vs_ai_games_and_simulations_are_capped() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let input_dim = (5, 47, 1);
+        let policy_net = PolicyNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let value_net = ValueNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let service = GameServiceImpl::new(
+            Arc::new(new_session_manager()),
+            Arc::new(Mutex::new(policy_net)),
+            Arc::new(Mutex::new(value_net)),
+            10,
+        )
+        .with_max_concurrent_ai(1)
+        .with_ai_vs_ai_limits(1, 50);
+
+        assert_eq!(service.ai_vs_ai_simulations(0), 10);
+        assert_eq!(service.ai_vs_ai_simulations(30), 30);
+        assert_eq!(service.ai_vs_ai_simulations(1_000_000), 50);
+
+        // Calcul IA en cours : la première partie attend son premier coup
+        let busy = service.ai_limiter.acquire().await;
+        let first = service
+            .create_ai_vs_ai_session(Request::new(CreateAiVsAiSessionRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(first.success, "{:?}", first.error);
+
+        let refused = service
+            .create_ai_vs_ai_session(Request::new(CreateAiVsAiSessionRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::ResourceExhausted);
+
+        // Sans registre A/B, aucune version nommée
+        let unknown = service
+            .create_ai_vs_ai_session(Request::new(CreateAiVsAiSessionRequest {
+                model_b: "candidate".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(unknown.error.unwrap().code, "UNKNOWN_MODEL_VERSION");
+        drop(busy);
     }
 
-    let chars: Vec<char> = clean_code.chars().collect();
-    let v1 = chars[0].to_digit(10)? as i32;
-    let v2 = chars[1].to_digit(10)? as i32;
-    let v3 = chars[2].to_digit(10)? as i32;
+    /// Plateau avec une tuile en 0 et en 7, les autres cases vides
+    fn two_tile_board() -> Vec<String> {
+        let mut board = vec![String::new(); 19];
+        board[0] = "963".to_string();
+        board[7] = "524".to_string();
+        board
+    }
 
-    // Valider les valeurs
-    if ![1, 5, 9].contains(&v1) || ![2, 6, 7].contains(&v2) || ![3, 4, 8].contains(&v3) {
-        return None;
+    #[tokio::test]
+    async fn test_ai_move_rejects_board_inconsistent_with_turn() {
+        let vs = nn::VarStore::new(Device::Cpu);
+        let input_dim = (5, 47, 1);
+        let policy_net = PolicyNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let value_net = ValueNet::new(&vs, input_dim, NNArchitecture::Cnn);
+        let service = GameServiceImpl::new(
+            Arc::new(new_session_manager()),
+            Arc::new(Mutex::new(policy_net)),
+            Arc::new(Mutex::new(value_net)),
+            10,
+        );
+
+        let response = service
+            .get_ai_move(Request::new(GetAiMoveRequest {
+                tile_code: "168".to_string(),
+                board_state: two_tile_board(),
+                available_positions: vec![],
+                turn_number: 5,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!response.success);
+        assert_eq!(response.recommended_position, -1);
+        assert_eq!(response.error.unwrap().code, "BOARD_TURN_MISMATCH");
+
+        // Case illisible : erreur, pas une case vide silencieuse
+        let mut board_state = two_tile_board();
+        board_state[7] = "529".to_string();
+        let response = service
+            .get_ai_move(Request::new(GetAiMoveRequest {
+                tile_code: "168".to_string(),
+                board_state,
+                available_positions: vec![],
+                turn_number: 1,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.error.unwrap().code, "INVALID_BOARD");
     }
 
-    Some(Tile(v1, v2, v3))
+    #[test]
+    fn test_ai_move_rejects_occupied_available_position() {
+        use crate::game::plateau::create_plateau_empty;
+        use crate::game::tile::Tile;
+
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(9, 6, 3);
+        plateau.tiles[7] = Tile(5, 2, 4);
+
+        assert!(validate_ai_move_board(&plateau, 2, &[1, 8, 18]).is_ok());
+        let occupied = validate_ai_move_board(&plateau, 2, &[1, 7]).unwrap_err();
+        assert_eq!(occupied.code, "POSITION_OCCUPIED");
+        let outside = validate_ai_move_board(&plateau, 2, &[19]).unwrap_err();
+        assert_eq!(outside.code, "INVALID_POSITION");
+    }
 }
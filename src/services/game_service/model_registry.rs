@@ -0,0 +1,467 @@
+// src/services/game_service/model_registry.rs - Plusieurs générations de modèles (A/B testing)
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+use crate::recording::get_recorder;
+use crate::services::game_manager::TakeItEasyGameState;
+use crate::services::session_manager::{get_store_from_manager, GameSession, SessionManager};
+
+use super::session_utils::get_session_by_code_or_id_from_store;
+
+/// Joueur IA dont le score sert à comparer les versions
+const AI_PLAYER_ID: &str = "mcts_ai";
+
+/// Sessions suivies au plus ; au-delà, la moins récemment vue est oubliée
+const MAX_TRACKED_SESSIONS: usize = 10_000;
+
+/// Une session sans coup IA depuis ce délai est considérée abandonnée
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Une génération de modèles servie par le serveur
+#[derive(Clone)]
+pub struct ModelVersion {
+    pub name: String,
+    pub policy_net: Arc<Mutex<PolicyNet>>,
+    pub value_net: Arc<Mutex<ValueNet>>,
+    /// Part des nouvelles sessions routées vers cette version
+    pub weight: f64,
+}
+
+/// Scores IA agrégés d'une version
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelVersionMetrics {
+    pub name: String,
+    pub sessions: usize,
+    pub finished_games: usize,
+    pub average_score: Option<f64>,
+}
+
+/// Version assignée à une session suivie
+struct Assignment {
+    version: usize,
+    /// Dernière demande des réseaux de la session
+    last_seen: Instant,
+    /// Score déjà comptabilisé
+    counted: bool,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    /// session_id → assignation, bornée (voir `prune`)
+    assignments: HashMap<String, Assignment>,
+    /// Sessions suivies, de la moins récemment vue à la plus récente
+    by_last_seen: BTreeSet<(Instant, String)>,
+    /// Sessions assignées par version, depuis le démarrage
+    assigned: Vec<usize>,
+    /// (somme des scores, parties terminées) par version
+    totals: Vec<(i64, usize)>,
+}
+
+impl RegistryState {
+    /// Suivre la session, ou la marquer vue à `now` si elle l'est déjà
+    fn track(&mut self, session_id: &str, assignment: Assignment) -> usize {
+        if let Some(tracked) = self.assignments.get_mut(session_id) {
+            self.by_last_seen
+                .remove(&(tracked.last_seen, session_id.to_string()));
+            tracked.last_seen = assignment.last_seen;
+            self.by_last_seen
+                .insert((tracked.last_seen, session_id.to_string()));
+            return tracked.version;
+        }
+        let version = assignment.version;
+        self.by_last_seen
+            .insert((assignment.last_seen, session_id.to_string()));
+        self.assignments.insert(session_id.to_string(), assignment);
+        version
+    }
+
+    fn forget(&mut self, session_id: &str) {
+        if let Some(assignment) = self.assignments.remove(session_id) {
+            self.by_last_seen
+                .remove(&(assignment.last_seen, session_id.to_string()));
+        }
+    }
+
+    /// Oublie les sessions inactives depuis `SESSION_IDLE_TTL` puis, s'il en
+    /// reste trop, les moins récemment vues. Les compteurs par version restent.
+    fn prune(&mut self, now: Instant, max_sessions: usize) {
+        while let Some((last_seen, session_id)) = self.by_last_seen.first().cloned() {
+            let idle = now.duration_since(last_seen) >= SESSION_IDLE_TTL;
+            if !idle && self.assignments.len() <= max_sessions {
+                break;
+            }
+            self.forget(&session_id);
+        }
+    }
+}
+
+// ============================================================================
+// REGISTRE
+// ============================================================================
+
+/// Registre de versions nommées avec une assignation par session.
+///
+/// Les nouvelles sessions sont réparties selon les poids par round-robin
+/// pondéré : après `n` sessions, chaque version en a reçu `n * part` à une
+/// session près.
+pub struct ModelRegistry {
+    versions: Vec<ModelVersion>,
+    state: std::sync::Mutex<RegistryState>,
+}
+
+impl ModelRegistry {
+    pub fn new(versions: Vec<ModelVersion>) -> Result<Self, String> {
+        if versions.is_empty() {
+            return Err("EMPTY_MODEL_REGISTRY".to_string());
+        }
+        let invalid_weight = |v: &ModelVersion| v.weight.is_nan() || v.weight < 0.0;
+        if versions.iter().any(invalid_weight) || versions.iter().all(|v| v.weight == 0.0) {
+            return Err("INVALID_MODEL_WEIGHTS".to_string());
+        }
+
+        let state = RegistryState {
+            assigned: vec![0; versions.len()],
+            totals: vec![(0, 0); versions.len()],
+            ..Default::default()
+        };
+        Ok(Self {
+            versions,
+            state: std::sync::Mutex::new(state),
+        })
+    }
+
+    /// Version de la session, assignée à la première demande. La version est
+    /// aussi transmise à l'enregistreur pour étiqueter la partie.
+    pub fn assign(&self, session_id: &str) -> &ModelVersion {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        if let Some(tracked) = state.assignments.get(session_id) {
+            let assignment = Assignment {
+                version: tracked.version,
+                last_seen: now,
+                counted: tracked.counted,
+            };
+            return &self.versions[state.track(session_id, assignment)];
+        }
+
+        let total_weight: f64 = self.versions.iter().map(|v| v.weight).sum();
+        let next_count = (state.assigned.iter().sum::<usize>() + 1) as f64;
+        let idx = (0..self.versions.len())
+            .max_by(|&a, &b| {
+                let deficit = |i: usize| {
+                    next_count * self.versions[i].weight / total_weight - state.assigned[i] as f64
+                };
+                deficit(a)
+                    .partial_cmp(&deficit(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    // À déficit égal, la première version l'emporte
+                    .then(b.cmp(&a))
+            })
+            .unwrap_or(0);
+
+        if state.assignments.len() >= MAX_TRACKED_SESSIONS {
+            state.prune(now, MAX_TRACKED_SESSIONS - 1);
+        }
+        state.track(
+            session_id,
+            Assignment {
+                version: idx,
+                last_seen: now,
+                counted: false,
+            },
+        );
+        state.assigned[idx] += 1;
+        drop(state);
+
+        let version = &self.versions[idx];
+        if let Some(recorder) = get_recorder() {
+            recorder.tag_model_version(session_id, &version.name);
+        }
+        log::info!("🧪 Session {} → modèle {}", session_id, version.name);
+        version
+    }
+
+    /// Version d'une session : celle enregistrée sur la session (`model_version`)
+    /// si elle existe encore, sinon une nouvelle assignation. Une session que
+    /// le registre a cessé de suivre garde ainsi sa version, et est de nouveau
+    /// suivie sans être recomptée dans les assignations.
+    pub fn version_for_session(&self, session: &GameSession) -> &ModelVersion {
+        let persisted = session
+            .model_version
+            .as_deref()
+            .and_then(|name| self.versions.iter().position(|v| v.name == name));
+        let Some(idx) = persisted else {
+            return self.assign(&session.id);
+        };
+
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        if !state.assignments.contains_key(&session.id)
+            && state.assignments.len() >= MAX_TRACKED_SESSIONS
+        {
+            state.prune(now, MAX_TRACKED_SESSIONS - 1);
+        }
+        // Une partie terminée a pu être comptée avant l'oubli : pas deux fois
+        let assignment = Assignment {
+            version: idx,
+            last_seen: now,
+            counted: session.state == 2,
+        };
+        state.track(&session.id, assignment);
+        &self.versions[idx]
+    }
+
+    /// Version nommée `name`, sans assignation (parties IA contre IA)
+    pub fn version_named(&self, name: &str) -> Option<&ModelVersion> {
+        self.versions.iter().find(|v| v.name == name)
+    }
+
+    /// Comptabilise le score IA d'une partie terminée (une seule fois par session)
+    pub fn record_finished_game(&self, session_id: &str, ai_score: i32) {
+        let mut state = self.state.lock().unwrap();
+        let Some(assignment) = state.assignments.get_mut(session_id) else {
+            return;
+        };
+        if assignment.counted {
+            return;
+        }
+        assignment.counted = true;
+        let idx = assignment.version;
+        state.totals[idx].0 += ai_score as i64;
+        state.totals[idx].1 += 1;
+    }
+
+    /// Relève les parties terminées depuis le dernier appel puis renvoie les
+    /// moyennes par version. Les sessions disparues ou abandonnées cessent
+    /// d'être suivies.
+    pub async fn collect_metrics(
+        &self,
+        session_manager: &Arc<SessionManager>,
+    ) -> Vec<ModelVersionMetrics> {
+        let pending: Vec<String> = {
+            let mut state = self.state.lock().unwrap();
+            state.prune(Instant::now(), MAX_TRACKED_SESSIONS);
+            state
+                .assignments
+                .iter()
+                .filter(|(_, a)| !a.counted)
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+        let store = get_store_from_manager(session_manager);
+        for session_id in pending {
+            let Some(session) = get_session_by_code_or_id_from_store(store, &session_id).await
+            else {
+                self.state.lock().unwrap().forget(&session_id);
+                continue;
+            };
+            if session.state != 2 {
+                continue;
+            }
+            let ai_score = serde_json::from_str::<TakeItEasyGameState>(&session.board_state)
+                .ok()
+                .and_then(|game| game.scores.get(AI_PLAYER_ID).copied());
+            if let Some(score) = ai_score {
+                self.record_finished_game(&session_id, score);
+            }
+        }
+
+        self.metrics()
+    }
+
+    /// Moyennes par version des parties déjà comptabilisées
+    pub fn metrics(&self) -> Vec<ModelVersionMetrics> {
+        let state = self.state.lock().unwrap();
+        self.versions
+            .iter()
+            .enumerate()
+            .map(|(idx, version)| {
+                let (sum, games) = state.totals[idx];
+                ModelVersionMetrics {
+                    name: version.name.clone(),
+                    sessions: state.assigned[idx],
+                    finished_games: games,
+                    average_score: (games > 0).then(|| sum as f64 / games as f64),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+    use crate::game::tile::Tile;
+    use crate::neural::manager::NNArchitecture;
+    use crate::recording::csv_writer::load_games_from_csv;
+    use crate::recording::game_recorder::{GameRecorder, RecorderOptions};
+    use crate::recording::PlayerType;
+    use crate::services::session_manager::{create_game_session, new_session_manager};
+    use tch::{nn, Device};
+
+    fn version(name: &str, weight: f64) -> ModelVersion {
+        let vs = nn::VarStore::new(Device::Cpu);
+        ModelVersion {
+            name: name.to_string(),
+            policy_net: Arc::new(Mutex::new(PolicyNet::new(
+                &vs,
+                (5, 47, 1),
+                NNArchitecture::GraphTransformer,
+            ))),
+            value_net: Arc::new(Mutex::new(ValueNet::new(
+                &vs,
+                (5, 47, 1),
+                NNArchitecture::GraphTransformer,
+            ))),
+            weight,
+        }
+    }
+
+    #[test]
+    fn test_split_ratio_respected_over_many_sessions() {
+        let registry = ModelRegistry::new(vec![version("A", 0.7), version("B", 0.3)]).unwrap();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for i in 0..1000 {
+            let session_id = format!("session-{}", i);
+            let name = registry.assign(&session_id).name.clone();
+            // L'assignation est stable pour une session donnée
+            assert_eq!(registry.assign(&session_id).name, name);
+            *counts.entry(name).or_default() += 1;
+        }
+
+        assert_eq!(counts["A"], 700);
+        assert_eq!(counts["B"], 300);
+        let metrics = registry.metrics();
+        assert_eq!(metrics[0].sessions, 700);
+        assert_eq!(metrics[1].sessions, 300);
+    }
+
+    #[test]
+    fn test_version_named_does_not_assign() {
+        let registry = ModelRegistry::new(vec![version("A", 0.5), version("B", 0.5)]).unwrap();
+        assert_eq!(registry.version_named("B").unwrap().name, "B");
+        assert!(registry.version_named("C").is_none());
+        assert!(registry.metrics().iter().all(|m| m.sessions == 0));
+    }
+
+    #[test]
+    fn test_average_score_per_version() {
+        let registry = ModelRegistry::new(vec![version("A", 0.5), version("B", 0.5)]).unwrap();
+        for i in 0..4 {
+            registry.assign(&format!("s{}", i));
+        }
+        // s0, s2 → A ; s1, s3 → B
+        registry.record_finished_game("s0", 150);
+        registry.record_finished_game("s2", 170);
+        registry.record_finished_game("s1", 120);
+        registry.record_finished_game("s1", 999); // déjà comptabilisée
+
+        let metrics = registry.metrics();
+        assert_eq!(metrics[0].average_score, Some(160.0));
+        assert_eq!(metrics[1].average_score, Some(120.0));
+        assert_eq!(metrics[1].finished_games, 1);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_and_missing_sessions_are_forgotten() {
+        let registry = ModelRegistry::new(vec![version("A", 0.5), version("B", 0.5)]).unwrap();
+        for i in 0..6 {
+            registry.assign(&format!("ghost{}", i));
+        }
+
+        // Sessions absentes du store : plus suivies, compteurs conservés
+        let session_manager = Arc::new(new_session_manager());
+        let metrics = registry.collect_metrics(&session_manager).await;
+        assert!(!registry
+            .state
+            .lock()
+            .unwrap()
+            .assignments
+            .contains_key("ghost0"));
+        assert_eq!(metrics[0].sessions + metrics[1].sessions, 6);
+        // Le round-robin pondéré continue sur le total depuis le démarrage
+        let next: Vec<String> = (6..8)
+            .map(|i| registry.assign(&format!("s{}", i)).name.clone())
+            .collect();
+        assert_eq!(next, vec!["A", "B"]);
+
+        let mut state = registry.state.lock().unwrap();
+        let now = Instant::now();
+        state.prune(now, 1);
+        assert_eq!(state.assignments.len(), 1);
+        assert!(state.assignments.contains_key("s7"));
+        state.prune(now + SESSION_IDLE_TTL, 10);
+        assert!(state.assignments.is_empty());
+    }
+
+    #[test]
+    fn test_forgotten_session_keeps_its_persisted_version() {
+        let registry = ModelRegistry::new(vec![version("A", 0.5), version("B", 0.5)]).unwrap();
+        let mut session = create_game_session(1, "single-player".to_string());
+        registry.assign("other");
+        session.model_version = Some(registry.version_for_session(&session).name.clone());
+        assert_eq!(session.model_version.as_deref(), Some("B"));
+
+        // Oubliée en cours de partie : même version, pas de nouvelle assignation
+        registry.state.lock().unwrap().prune(Instant::now(), 0);
+        for _ in 0..3 {
+            assert_eq!(registry.version_for_session(&session).name, "B");
+        }
+        let metrics = registry.metrics();
+        assert_eq!((metrics[0].sessions, metrics[1].sessions), (1, 1));
+        let state = registry.state.lock().unwrap();
+        assert_eq!(state.assignments.len(), 1);
+        assert_eq!(state.by_last_seen.len(), 1);
+    }
+
+    #[test]
+    fn test_recordings_carry_assigned_version() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let options = RecorderOptions {
+            model_version_column: true,
+            ..Default::default()
+        };
+        let recorder = GameRecorder::with_options(dir.path(), options)?;
+        let registry = ModelRegistry::new(vec![version("A", 0.5), version("B", 0.5)]).unwrap();
+
+        let plateau = create_plateau_empty();
+        let mut assigned = HashMap::new();
+        for i in 0..4 {
+            let session_id = format!("s{}", i);
+            recorder.start_game(
+                &session_id,
+                "human_vs_mcts",
+                vec![("mcts_ai".to_string(), PlayerType::Mcts)],
+            );
+            let name = registry.assign(&session_id).name.clone();
+            recorder.tag_model_version(&session_id, &name);
+            assigned.insert(session_id.clone(), name);
+            recorder.record_move(
+                &session_id,
+                0,
+                "mcts_ai",
+                PlayerType::Mcts,
+                &plateau,
+                &Tile(1, 2, 3),
+                i,
+                None,
+            );
+            recorder.finalize_game(&session_id, HashMap::from([("mcts_ai".to_string(), 10)]))?;
+        }
+        recorder.flush()?;
+
+        let path = std::fs::read_dir(dir.path())?.next().unwrap()?.path();
+        let rows = load_games_from_csv(&path)?;
+        assert_eq!(rows.len(), 4);
+        for row in rows {
+            assert_eq!(row.model_version.as_ref(), assigned.get(&row.game_id));
+        }
+        Ok(())
+    }
+}
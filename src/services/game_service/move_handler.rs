@@ -7,7 +7,8 @@ use tonic::{Response, Status};
 use crate::generated::takeiteasygame::v1::*;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::services::game_manager::{
-    ensure_current_tile, player_move_from_json, process_player_move_with_mcts, TakeItEasyGameState,
+    announced_tile_for_move, player_move_from_json, process_player_move_with_mcts,
+    TakeItEasyGameState,
 };
 use crate::services::session_manager::{
     get_store_from_manager, update_session_in_store, SessionManager,
@@ -62,14 +63,20 @@ pub async fn make_move_logic(
                 .map_err(|e| Status::internal(format!("Failed to parse game state: {}", e)))?
         };
 
-    // Vérification: S'assurer qu'une tuile courante existe
-    let game_state = match ensure_current_tile(game_state) {
-        Ok(state) => state,
-        Err(e) => {
-            log::error!("❌ Échec garantie tuile: {}", e);
+    // Vérification: le coup pose la tuile annoncée du tour en cours, une seule
+    // fois par joueur (pas de tirage implicite hors tour)
+    let announced_tile = match announced_tile_for_move(&game_state, &request.player_id) {
+        Ok(tile) => tile,
+        Err(error_code) => {
+            log::warn!(
+                "❌ Coup refusé pour {} (session {}): {}",
+                request.player_id,
+                request.session_id,
+                error_code
+            );
             return Ok(Response::new(make_move_error_response(
-                "NO_CURRENT_TILE".to_string(),
-                format!("No current tile available: {}", e),
+                error_code.clone(),
+                format!("Move not allowed: {}", error_code),
             )));
         }
     };
@@ -79,9 +86,7 @@ pub async fn make_move_logic(
         Ok(mv) => {
             let mut mv = mv;
             mv.timestamp = request.timestamp;
-            if let Some(current_tile) = game_state.current_tile {
-                mv.tile = current_tile;
-            }
+            mv.tile = announced_tile;
             mv
         }
         Err(e) => {
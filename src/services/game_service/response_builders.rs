@@ -105,6 +105,24 @@ pub fn start_turn_error_response(message: String) -> StartTurnResponse {
     }
 }
 
+/// StartTurn refusé par la machine à états du tour, avec l'annonce en cours
+/// s'il y en a une (`current`) pour que le client se resynchronise
+pub fn start_turn_rejected_response(
+    code: String,
+    message: String,
+    current: Option<StartTurnResponse>,
+) -> StartTurnResponse {
+    StartTurnResponse {
+        success: false,
+        error: Some(Error {
+            code,
+            message,
+            details: std::collections::HashMap::new(),
+        }),
+        ..current.unwrap_or_default()
+    }
+}
+
 pub fn game_state_success_response(
     game_state_json: String,
     current_tile: String,
@@ -144,3 +162,15 @@ pub fn game_state_error_response(message: String) -> GetGameStateResponse {
         }),
     }
 }
+
+pub fn game_state_delta_error_response(message: String) -> GetGameStateDeltaResponse {
+    GetGameStateDeltaResponse {
+        success: false,
+        error: Some(Error {
+            code: "GET_GAME_STATE_DELTA_FAILED".to_string(),
+            message,
+            details: std::collections::HashMap::new(),
+        }),
+        ..Default::default()
+    }
+}
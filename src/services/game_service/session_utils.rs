@@ -1,32 +1,30 @@
 // src/services/game_service/session_utils.rs - Utilitaires de gestion des sessions
 
-use crate::services::session_manager::{GameSession, SessionStoreState};
+use crate::services::session_manager::{
+    get_session_by_code_from_store, get_session_by_id_from_store, GameSession, SessionStore,
+};
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 // ============================================================================
 // UTILITAIRES DE SESSION
 // ============================================================================
 
 pub async fn get_session_by_code_or_id_from_store(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     identifier: &str,
 ) -> Option<GameSession> {
-    let state = store.read().await;
-
-    // Optimized single-pass lookup
+    // Likely UUID format - check sessions directly
     if identifier.len() == 36 && identifier.chars().nth(8) == Some('-') {
-        // Likely UUID format - check sessions directly
-        if let Some(session) = state.sessions.get(identifier) {
-            return Some(session.clone());
+        if let Some(session) = get_session_by_id_from_store(store, identifier).await {
+            return Some(session);
         }
     }
 
     // Check by code
-    if let Some(session_id) = state.sessions_by_code.get(identifier) {
-        return state.sessions.get(session_id).cloned();
+    if let Some(session) = get_session_by_code_from_store(store, identifier).await {
+        return Some(session);
     }
 
     // Fallback: check if identifier is actually a session ID
-    state.sessions.get(identifier).cloned()
+    get_session_by_id_from_store(store, identifier).await
 }
@@ -6,14 +6,17 @@ use tonic::{Response, Status};
 use crate::game::tile::Tile;
 use crate::generated::takeiteasygame::v1::*;
 use crate::services::game_manager::{
-    get_all_players_status, is_game_finished, TakeItEasyGameState,
+    compute_state_delta, get_all_players_status, is_game_finished, TakeItEasyGameState,
 };
 use crate::services::session_manager::{
-    get_session_by_id_from_store, get_store_from_manager, update_session_in_store, SessionManager,
+    get_session_by_id_from_store, get_store_from_manager, update_session_in_store, GameSession,
+    SessionManager,
 };
 use crate::utils::image::generate_tile_image_names;
 
-use super::response_builders::{game_state_error_response, game_state_success_response};
+use super::response_builders::{
+    game_state_delta_error_response, game_state_error_response, game_state_success_response,
+};
 
 // ============================================================================
 // LOGIQUE DE FOURNITURE D'ÉTAT
@@ -35,22 +38,57 @@ pub async fn get_game_state_logic(
         }
     };
 
+    let game_state = match parse_session_game_state(&session) {
+        Ok(state) => state,
+        Err(response) => return Ok(Response::new(response)),
+    };
+
+    // ✅ CRITICAL: Synchroniser les scores avec la session avant réponse
+    let mut updated_session = session.clone();
+    for (player_id, score) in &game_state.scores {
+        if let Some(player) = updated_session.players.get_mut(player_id) {
+            player.score = *score;
+        }
+    }
+    if updated_session.players != session.players {
+        if let Err(e) = update_session_in_store(store, updated_session.clone()).await {
+            log::error!("Failed to sync scores in GetGameState: {}", e);
+        }
+    }
+
+    Ok(Response::new(build_game_state_response(&game_state)))
+}
+
+/// État de la partie d'une session, ou la réponse d'erreur de GetGameState
+fn parse_session_game_state(
+    session: &GameSession,
+) -> Result<TakeItEasyGameState, GetGameStateResponse> {
     if session.board_state.is_empty() || session.board_state == "{}" {
-        return Ok(Response::new(game_state_error_response(
+        return Err(game_state_error_response(
             "Game not started yet".to_string(),
-        )));
+        ));
     }
 
-    let game_state: TakeItEasyGameState = match serde_json::from_str(&session.board_state) {
-        Ok(state) => state,
-        Err(e) => {
-            return Ok(Response::new(game_state_error_response(format!(
-                "Failed to parse game state: {}",
-                e
-            ))))
-        }
-    };
+    serde_json::from_str(&session.board_state)
+        .map_err(|e| game_state_error_response(format!("Failed to parse game state: {}", e)))
+}
 
+/// Réponse GetGameState d'une session, sans effet de bord (utilisée par le stream)
+pub fn game_state_response(session: &GameSession) -> GetGameStateResponse {
+    match parse_session_game_state(session) {
+        Ok(game_state) => build_game_state_response(&game_state),
+        Err(response) => response,
+    }
+}
+
+/// Version de l'état de la partie d'une session (0 avant le début)
+pub fn session_state_version(session: &GameSession) -> u64 {
+    parse_session_game_state(session)
+        .map(|game_state| game_state.state_version)
+        .unwrap_or(0)
+}
+
+fn build_game_state_response(game_state: &TakeItEasyGameState) -> GetGameStateResponse {
     let current_tile_str = game_state
         .current_tile
         .map(|t| format!("{}-{}-{}", t.0, t.1, t.2))
@@ -66,42 +104,29 @@ pub async fn get_game_state_logic(
         })
         .unwrap_or_default(); // ✅ Chaîne vide au lieu de "000.png"
 
-    let final_scores_json = if is_game_finished(&game_state) {
+    let final_scores_json = if is_game_finished(game_state) {
         serde_json::to_string(&game_state.scores).unwrap_or_default()
     } else {
         "{}".to_string()
     };
 
-    // ✅ CRITICAL: Synchroniser les scores avec la session avant réponse
-    let mut updated_session = session.clone();
-    for (player_id, score) in &game_state.scores {
-        if let Some(player) = updated_session.players.get_mut(player_id) {
-            player.score = *score;
-        }
-    }
-    if updated_session.players != session.players {
-        if let Err(e) = update_session_in_store(store, updated_session.clone()).await {
-            log::error!("Failed to sync scores in GetGameState: {}", e);
-        }
-    }
-
     let current_turn = game_state.current_turn as i32;
     let waiting_for_players = game_state.waiting_for_players.clone();
-    let is_finished = is_game_finished(&game_state);
-    let game_state_json = serde_json::to_string(&game_state).unwrap_or_default();
+    let is_finished = is_game_finished(game_state);
+    let game_state_json = serde_json::to_string(game_state).unwrap_or_default();
 
     // ✅ Enrichir avec les images et statuts des joueurs
     let mut enhanced_game_state_json = enhance_game_state_with_images(&game_state_json);
 
     // Ajouter les statuts des joueurs pour le flow indépendant
-    let players_status = get_all_players_status(&game_state);
+    let players_status = get_all_players_status(game_state);
     let mut enhanced_data: serde_json::Value =
         serde_json::from_str(&enhanced_game_state_json).unwrap_or_else(|_| serde_json::json!({}));
 
     enhanced_data["players_status"] = serde_json::to_value(&players_status).unwrap_or_default();
     enhanced_game_state_json = enhanced_data.to_string();
 
-    let response = game_state_success_response(
+    game_state_success_response(
         enhanced_game_state_json,
         current_tile_str,
         current_tile_image, // ✅ Sera vide si pas de tuile
@@ -109,9 +134,77 @@ pub async fn get_game_state_logic(
         waiting_for_players,
         is_finished,
         final_scores_json,
-    );
+    )
+}
+
+// ============================================================================
+// DELTA D'ÉTAT (polling léger)
+// ============================================================================
+
+pub async fn get_game_state_delta_logic(
+    session_manager: &Arc<SessionManager>,
+    session_id: String,
+    since_version: u64,
+) -> Result<Response<GetGameStateDeltaResponse>, Status> {
+    let store = get_store_from_manager(session_manager);
+
+    let session = match get_session_by_id_from_store(store, &session_id).await {
+        Some(session) => session,
+        None => {
+            return Ok(Response::new(game_state_delta_error_response(
+                "Session not found".to_string(),
+            )));
+        }
+    };
+
+    if session.board_state.is_empty() || session.board_state == "{}" {
+        return Ok(Response::new(game_state_delta_error_response(
+            "Game not started yet".to_string(),
+        )));
+    }
+
+    let game_state: TakeItEasyGameState = match serde_json::from_str(&session.board_state) {
+        Ok(state) => state,
+        Err(e) => {
+            return Ok(Response::new(game_state_delta_error_response(format!(
+                "Failed to parse game state: {}",
+                e
+            ))))
+        }
+    };
+
+    let delta = compute_state_delta(&game_state, since_version);
+    let tile_str = |t: Tile| format!("{}-{}-{}", t.0, t.1, t.2);
 
-    Ok(Response::new(response))
+    Ok(Response::new(GetGameStateDeltaResponse {
+        success: true,
+        version: delta.version,
+        placed_tiles: delta
+            .placed_tiles
+            .into_iter()
+            .map(|p| PlacedTile {
+                player_id: p.player_id,
+                position: p.position as i32,
+                tile: tile_str(p.tile),
+                version: p.version,
+            })
+            .collect(),
+        score_deltas: delta
+            .score_deltas
+            .into_iter()
+            .map(|(player_id, points_gained, score)| ScoreDelta {
+                player_id,
+                points_gained,
+                score,
+            })
+            .collect(),
+        current_turn: delta.current_turn as i32,
+        current_tile: delta.current_tile.map(tile_str).unwrap_or_default(),
+        waiting_for_players: delta.waiting_for_players,
+        is_game_finished: delta.is_game_finished,
+        error: None,
+        resync: delta.resync,
+    }))
 }
 
 // ============================================================================
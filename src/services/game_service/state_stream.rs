@@ -0,0 +1,178 @@
+// src/services/game_service/state_stream.rs - État de jeu poussé aux clients (StreamGameState)
+
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Response, Status};
+
+use crate::generated::takeiteasygame::v1::*;
+use crate::services::session_manager::{
+    get_session_by_id_from_store, get_store_from_manager, subscribe_to_session_with_manager,
+    GameSession, SessionManager,
+};
+
+use super::state_provider::{game_state_response, session_state_version};
+
+/// Mises à jour en attente d'envoi par client
+const STREAM_BUFFER: usize = 8;
+
+pub type GameStateStream = ReceiverStream<Result<GameStateUpdate, Status>>;
+
+// ============================================================================
+// STREAM D'ÉTAT
+// ============================================================================
+
+/// Mise à jour poussée pour l'état actuel d'une session
+pub fn game_state_update(session: &GameSession) -> GameStateUpdate {
+    GameStateUpdate {
+        session_id: session.id.clone(),
+        version: session_state_version(session),
+        state: Some(game_state_response(session)),
+    }
+}
+
+/// Envoie l'état courant puis une mise à jour à chaque changement, jusqu'à
+/// la fin de la partie ou la déconnexion du client.
+pub async fn stream_game_state_logic(
+    session_manager: &Arc<SessionManager>,
+    session_id: String,
+) -> Result<Response<GameStateStream>, Status> {
+    let store = get_store_from_manager(session_manager);
+    if get_session_by_id_from_store(store, &session_id)
+        .await
+        .is_none()
+    {
+        return Err(Status::not_found(format!(
+            "Session {} not found",
+            session_id
+        )));
+    }
+
+    // S'abonner avant de relire l'état : aucune écriture n'est perdue entre les deux
+    let mut updates = subscribe_to_session_with_manager(session_manager, &session_id);
+    let current = get_session_by_id_from_store(store, &session_id).await;
+
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    let session_manager = session_manager.clone();
+    tokio::spawn(async move {
+        let mut next = current;
+        let mut last_sent: Option<GameStateUpdate> = None;
+        loop {
+            let session = match next.take() {
+                Some(session) => session,
+                None => {
+                    let received = tokio::select! {
+                        _ = tx.closed() => break,
+                        received = updates.recv() => received,
+                    };
+                    match received {
+                        Ok(session) => session,
+                        // Client en retard : seul l'état le plus récent compte
+                        Err(RecvError::Lagged(skipped)) => {
+                            log::debug!(
+                                "📡 Stream {} en retard de {} écritures",
+                                session_id,
+                                skipped
+                            );
+                            updates = updates.resubscribe();
+                            let store = get_store_from_manager(&session_manager);
+                            match get_session_by_id_from_store(store, &session_id).await {
+                                Some(session) => session,
+                                None => break,
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            };
+
+            // Écritures sans changement visible (synchro des scores, joueurs) : rien à pousser
+            let update = game_state_update(&session);
+            if last_sent.as_ref() == Some(&update) {
+                continue;
+            }
+            let finished = update
+                .state
+                .as_ref()
+                .is_some_and(|state| state.is_game_finished);
+            if tx.send(Ok(update.clone())).await.is_err() || finished {
+                break;
+            }
+            last_sent = Some(update);
+        }
+    });
+
+    Ok(Response::new(ReceiverStream::new(rx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::game_manager::{
+        create_take_it_easy_game, start_new_turn, TakeItEasyGameState,
+    };
+    use crate::services::session_manager::{
+        create_game_session, new_session_manager, transform_session_in_store,
+        update_session_in_store,
+    };
+    use tokio_stream::StreamExt;
+
+    async fn set_board_state(
+        session_manager: &Arc<SessionManager>,
+        session_id: &str,
+        game: &TakeItEasyGameState,
+    ) {
+        let board_state = serde_json::to_string(game).unwrap();
+        let store = get_store_from_manager(session_manager);
+        transform_session_in_store(store, session_id, |mut session| {
+            session.board_state = board_state;
+            Ok((session, ()))
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_current_state_then_each_change() {
+        let session_manager = Arc::new(new_session_manager());
+        let session = create_game_session(1, "single-player-easy".to_string());
+        let store = get_store_from_manager(&session_manager);
+        update_session_in_store(store, session.clone())
+            .await
+            .unwrap();
+
+        let unknown = stream_game_state_logic(&session_manager, "missing".to_string()).await;
+        assert_eq!(unknown.unwrap_err().code(), tonic::Code::NotFound);
+
+        let game = create_take_it_easy_game(session.id.clone(), vec!["p1".to_string()]);
+        set_board_state(&session_manager, &session.id, &game).await;
+
+        // Abonné en retard : l'état courant arrive tout de suite
+        let mut stream = stream_game_state_logic(&session_manager, session.id.clone())
+            .await
+            .unwrap()
+            .into_inner();
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.session_id, session.id);
+        assert_eq!(first.version, game.state_version);
+        assert!(first.state.unwrap().success);
+
+        // Écriture sans changement d'état : pas de mise à jour
+        set_board_state(&session_manager, &session.id, &game).await;
+        let game = start_new_turn(game).unwrap();
+        set_board_state(&session_manager, &session.id, &game).await;
+        let turn = stream.next().await.unwrap().unwrap();
+        assert_eq!(turn.version, game.state_version);
+        assert!(!turn.state.unwrap().current_tile.is_empty());
+
+        // Partie terminée : dernière mise à jour puis fin du stream
+        let mut finished = game.clone();
+        finished.current_turn = finished.total_turns;
+        set_board_state(&session_manager, &session.id, &finished).await;
+        let last = stream.next().await.unwrap().unwrap();
+        assert!(last.state.unwrap().is_game_finished);
+        assert!(stream.next().await.is_none());
+    }
+}
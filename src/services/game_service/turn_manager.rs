@@ -8,16 +8,110 @@ use crate::generated::takeiteasygame::v1::*;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
 use crate::neural::qvalue_net::QValueNet;
 use crate::services::game_manager::{
-    create_take_it_easy_game, start_new_turn, TakeItEasyGameState,
+    create_take_it_easy_game_with_options, is_game_finished, start_new_turn, with_starting_board,
+    TakeItEasyGameState,
 };
 use crate::services::session_manager::{
-    get_store_from_manager, update_session_in_store, SessionManager,
+    get_store_from_manager, transform_session_in_store, GameSession, SessionManager,
 };
 use crate::utils::image::generate_tile_image_names;
 
-use super::response_builders::{start_turn_error_response, start_turn_success_response};
+use super::async_move_handler::await_pending_ai_task;
+use super::response_builders::{
+    start_turn_error_response, start_turn_rejected_response, start_turn_success_response,
+};
 use super::session_utils::get_session_by_code_or_id_from_store;
 
+// ============================================================================
+// MACHINE À ÉTATS DU TOUR
+// ============================================================================
+
+/// Phase d'une session vis-à-vis du tour en cours :
+///
+/// Lobby → AwaitingReady → TurnAnnounced → AwaitingMoves → Resolving →
+/// NextTurn (→ TurnAnnounced) ou GameOver.
+///
+/// StartTurn et MakeMove ne sont acceptés que dans certaines phases
+/// (`check_start_turn`, `check_make_move`), au lieu de supposer que les
+/// clients appellent les RPC dans le bon ordre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnPhase {
+    /// Places libres, la partie n'a pas commencé
+    Lobby,
+    /// Session complète, en attente des SetReady
+    AwaitingReady,
+    /// Tuile annoncée par StartTurn, personne n'a encore joué
+    TurnAnnounced,
+    /// Une partie des joueurs a joué la tuile annoncée
+    AwaitingMoves,
+    /// Tous les joueurs ont joué, fin de tour en cours
+    Resolving,
+    /// Partie lancée ou tour précédent terminé, en attente de StartTurn
+    /// (la tuile suivante peut déjà être tirée)
+    NextTurn,
+    GameOver,
+}
+
+impl TurnPhase {
+    /// Phase de `session`, dont l'état de jeu désérialisé est `game_state`
+    /// (None tant qu'aucune partie n'a été créée)
+    pub fn of(session: &GameSession, game_state: Option<&TakeItEasyGameState>) -> Self {
+        match (session.state, game_state) {
+            (2, _) => TurnPhase::GameOver,
+            (0, _) if session.players.len() < session.max_players as usize => TurnPhase::Lobby,
+            (0, _) => TurnPhase::AwaitingReady,
+            (_, None) => TurnPhase::NextTurn,
+            (_, Some(game)) => {
+                if is_game_finished(game) {
+                    TurnPhase::GameOver
+                } else if game.current_tile.is_none() {
+                    TurnPhase::NextTurn
+                } else if game.waiting_for_players.is_empty() {
+                    TurnPhase::Resolving
+                } else if game.announced_turn != Some(game.current_turn) {
+                    TurnPhase::NextTurn
+                } else if game.waiting_for_players.len() == game.player_plateaus.len() {
+                    TurnPhase::TurnAnnounced
+                } else {
+                    TurnPhase::AwaitingMoves
+                }
+            }
+        }
+    }
+
+    /// StartTurn n'annonce un tour qu'une fois le précédent terminé
+    pub fn check_start_turn(self) -> Result<(), String> {
+        match self {
+            TurnPhase::NextTurn => Ok(()),
+            TurnPhase::Lobby | TurnPhase::AwaitingReady => Err("GAME_NOT_STARTED".to_string()),
+            TurnPhase::TurnAnnounced | TurnPhase::AwaitingMoves => {
+                Err("TURN_ALREADY_STARTED".to_string())
+            }
+            TurnPhase::Resolving => Err("TURN_RESOLVING".to_string()),
+            TurnPhase::GameOver => Err("GAME_ALREADY_FINISHED".to_string()),
+        }
+    }
+
+    /// MakeMove ne joue que la tuile d'un tour annoncé par StartTurn
+    pub fn check_make_move(self) -> Result<(), String> {
+        match self {
+            TurnPhase::TurnAnnounced | TurnPhase::AwaitingMoves => Ok(()),
+            TurnPhase::Lobby | TurnPhase::AwaitingReady => Err("GAME_NOT_STARTED".to_string()),
+            TurnPhase::NextTurn => Err("TURN_NOT_STARTED".to_string()),
+            TurnPhase::Resolving => Err("TURN_RESOLVING".to_string()),
+            TurnPhase::GameOver => Err("GAME_ALREADY_FINISHED".to_string()),
+        }
+    }
+}
+
+/// État de jeu stocké dans la session, None si aucune partie n'a été créée
+pub fn stored_game_state(session: &GameSession) -> Option<TakeItEasyGameState> {
+    if session.board_state.is_empty() || session.board_state == "{}" {
+        return None;
+    }
+    serde_json::from_str(&session.board_state).ok()
+}
+
 // ============================================================================
 // LOGIQUE DE GESTION DES TOURS
 // ============================================================================
@@ -33,71 +127,115 @@ pub async fn start_turn_logic(
     _top_k: usize,
     session_id: String,
 ) -> Result<Response<StartTurnResponse>, Status> {
+    // Le coup IA en arrière-plan peut encore terminer le tour en cours
+    await_pending_ai_task(&session_id).await;
+
     let store = get_store_from_manager(session_manager);
-    let session = match get_session_by_code_or_id_from_store(store, &session_id).await {
-        Some(session) => session,
-        None => {
-            return Ok(Response::new(start_turn_error_response(
-                "Session not found".to_string(),
-            )));
-        }
+    let Some(session_key) = get_session_by_code_or_id_from_store(store, &session_id)
+        .await
+        .map(|session| session.id)
+    else {
+        return Ok(Response::new(start_turn_error_response(
+            "Session not found".to_string(),
+        )));
     };
 
-    // Récupérer ou créer l'état de jeu
-    let game_state: TakeItEasyGameState =
-        if session.board_state.is_empty() || session.board_state == "{}" {
-            // Première fois - créer le jeu
+    // Phase vérifiée et tuile tirée sous le verrou du shard : deux StartTurn
+    // concurrents ne tirent pas chacun une tuile
+    let mut rejected = None;
+    let announced = transform_session_in_store(store, &session_key, |session| {
+        let stored_state = stored_game_state(&session);
+        let phase = TurnPhase::of(&session, stored_state.as_ref());
+        if let Err(code) = phase.check_start_turn() {
+            log::warn!(
+                "❌ StartTurn refusé pour la session {} (phase {:?}): {}",
+                session_id,
+                phase,
+                code
+            );
+            // Tour déjà annoncé (retry, autre client) : l'annonce en cours est jointe
+            let current = stored_state
+                .filter(|state| state.current_tile.is_some())
+                .map(|state| turn_announcement(&state));
+            rejected = Some(start_turn_rejected_response(
+                code.clone(),
+                format!("StartTurn not allowed in phase {:?}: {}", phase, code),
+                current,
+            ));
+            return Err(code);
+        }
+
+        // Nouvelle partie, depuis le plateau de départ de la session s'il y en a un
+        let new_game = || {
             let player_ids: Vec<String> = session.players.keys().cloned().collect();
-            create_take_it_easy_game(session_id.clone(), player_ids)
-        } else {
-            // Désérialiser l'état existant
-            match serde_json::from_str::<TakeItEasyGameState>(&session.board_state) {
-                Ok(mut state) => {
-                    state.session_id = session_id.clone();
-                    state
-                }
-                Err(_e) => {
-                    let player_ids: Vec<String> = session.players.keys().cloned().collect();
-                    create_take_it_easy_game(session_id.clone(), player_ids)
-                }
+            let mut game_state = create_take_it_easy_game_with_options(
+                session_id.clone(),
+                player_ids,
+                session.tile_draw_strategy.clone(),
+                session.num_turns,
+            );
+            game_state.scoring_rules = session.scoring_rules;
+            game_state.opponent = session.opponent;
+            match &session.starting_board {
+                Some(board) => with_starting_board(game_state, board),
+                None => game_state,
             }
         };
 
-    // Vérifier si une tuile existe déjà pour ce tour
-    let new_state = if game_state.current_tile.is_some() {
-        // ✅ Une tuile existe déjà, utiliser l'état actuel
-        game_state
-    } else {
-        match start_new_turn(game_state) {
-            Ok(new_state) => new_state,
-            Err(e) => {
-                return Ok(Response::new(start_turn_error_response(format!(
-                    "Failed to start turn: {}",
-                    e
-                ))));
+        // Récupérer ou créer l'état de jeu (première fois : créer le jeu)
+        let game_state: TakeItEasyGameState = match stored_state {
+            Some(mut state) => {
+                state.session_id = session_id.clone();
+                state
             }
-        }
-    };
+            None => new_game(),
+        };
+
+        // Vérifier si une tuile existe déjà pour ce tour
+        let new_state = if game_state.current_tile.is_some() {
+            // ✅ Une tuile existe déjà, utiliser l'état actuel
+            game_state
+        } else {
+            start_new_turn(game_state).map_err(|e| format!("Failed to start turn: {}", e))?
+        };
 
-    // 🚀 SOLUTION UI RÉACTIVE: NE PAS faire jouer MCTS automatiquement dans start_turn
-    // MCTS jouera seulement après que le joueur humain ait fait son mouvement
-    // Cela permet au joueur de cliquer immédiatement sans attendre 30s
-    let final_state = new_state;
+        // 🚀 SOLUTION UI RÉACTIVE: NE PAS faire jouer MCTS automatiquement dans start_turn
+        // MCTS jouera seulement après que le joueur humain ait fait son mouvement
+        // Cela permet au joueur de cliquer immédiatement sans attendre 30s
+        let mut final_state = new_state;
 
-    // MCTS est gardé dans waiting_for_players mais ne joue pas automatiquement ici
-    // Il jouera via le système async après le clic du joueur humain
+        // MCTS est gardé dans waiting_for_players mais ne joue pas automatiquement ici
+        // Il jouera via le système async après le clic du joueur humain
+        final_state.announced_turn = Some(final_state.current_turn);
 
+        let response = turn_announcement(&final_state);
+
+        // Sauvegarder l'état mis à jour ET enrichi
+        let mut updated_session = session;
+        updated_session.board_state = response.game_state.clone();
+        Ok((updated_session, response))
+    })
+    .await;
+
+    let response = match announced {
+        Ok(Some(response)) => response,
+        Ok(None) => start_turn_error_response("Session not found".to_string()),
+        Err(e) => rejected.unwrap_or_else(|| start_turn_error_response(e)),
+    };
+    Ok(Response::new(response))
+}
+
+/// Annonce du tour en cours de `state`, qui doit avoir une tuile courante
+fn turn_announcement(state: &TakeItEasyGameState) -> StartTurnResponse {
     // Extraire les informations de la tuile
-    let announced_tile = final_state.current_tile.unwrap();
+    let announced_tile = state.current_tile.unwrap();
     let announced_tile_str = format!(
         "{}-{}-{}",
         announced_tile.0, announced_tile.1, announced_tile.2
     );
     let tile_image = generate_tile_image_names(&[announced_tile])[0].clone();
 
-    let turn_number = final_state.current_turn as i32;
-    let waiting_for_players = final_state.waiting_for_players.clone();
-    let game_state_json = serde_json::to_string(&final_state).unwrap_or_default();
+    let game_state_json = serde_json::to_string(state).unwrap_or_default();
 
     // 🚀 SOLUTION RÉACTIVITÉ: Enrichir immédiatement avec available_positions
     // Cela évite d'attendre le polling pour avoir les positions disponibles
@@ -106,23 +244,140 @@ pub async fn start_turn_logic(
             &game_state_json,
         );
 
-    // Sauvegarder l'état mis à jour ET enrichi
-    let mut updated_session = session;
-    updated_session.board_state = enhanced_game_state_json.clone();
-
-    if let Err(e) = update_session_in_store(store, updated_session).await {
-        return Ok(Response::new(start_turn_error_response(format!(
-            "Failed to update session: {}",
-            e
-        ))));
-    }
-
-    let response = start_turn_success_response(
+    start_turn_success_response(
         announced_tile_str,
         tile_image,
-        turn_number,
-        waiting_for_players,
+        state.current_turn as i32,
+        state.waiting_for_players.clone(),
         enhanced_game_state_json,
-    );
-    Ok(Response::new(response))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::tile::Tile;
+    use crate::neural::manager::NNArchitecture;
+    use crate::services::game_manager::{apply_player_move, PlayerMove};
+    use crate::services::session_manager::{
+        add_player_to_session, create_game_session, get_session_by_id_with_manager,
+        new_session_manager, update_session_with_manager,
+    };
+    use tch::{nn, Device};
+
+    #[test]
+    fn test_phases_follow_the_turn_lifecycle() {
+        let session = create_game_session(2, "multiplayer".to_string());
+        let (session, p1) = add_player_to_session(session, "p1".to_string()).unwrap();
+        assert_eq!(TurnPhase::of(&session, None), TurnPhase::Lobby);
+        let (mut session, p2) = add_player_to_session(session, "p2".to_string()).unwrap();
+        assert_eq!(TurnPhase::of(&session, None), TurnPhase::AwaitingReady);
+        assert_eq!(
+            TurnPhase::AwaitingReady.check_start_turn().unwrap_err(),
+            "GAME_NOT_STARTED"
+        );
+
+        session.state = 1;
+        assert_eq!(TurnPhase::of(&session, None), TurnPhase::NextTurn);
+
+        // Tuile tirée mais pas encore annoncée
+        let game = create_take_it_easy_game_with_options(
+            session.id.clone(),
+            vec![p1.clone(), p2.clone()],
+            Default::default(),
+            19,
+        );
+        let mut game = start_new_turn(game).unwrap();
+        assert_eq!(TurnPhase::of(&session, Some(&game)), TurnPhase::NextTurn);
+        assert_eq!(
+            TurnPhase::NextTurn.check_make_move().unwrap_err(),
+            "TURN_NOT_STARTED"
+        );
+
+        game.announced_turn = Some(game.current_turn);
+        assert_eq!(
+            TurnPhase::of(&session, Some(&game)),
+            TurnPhase::TurnAnnounced
+        );
+        assert_eq!(
+            TurnPhase::TurnAnnounced.check_start_turn().unwrap_err(),
+            "TURN_ALREADY_STARTED"
+        );
+
+        let tile = game.current_tile.unwrap();
+        let game = apply_player_move(
+            game,
+            PlayerMove {
+                player_id: p1,
+                position: 0,
+                tile,
+                timestamp: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            TurnPhase::of(&session, Some(&game)),
+            TurnPhase::AwaitingMoves
+        );
+        assert!(TurnPhase::AwaitingMoves.check_make_move().is_ok());
+
+        session.state = 2;
+        assert_eq!(TurnPhase::of(&session, Some(&game)), TurnPhase::GameOver);
+        assert_eq!(
+            TurnPhase::GameOver.check_start_turn().unwrap_err(),
+            "GAME_ALREADY_FINISHED"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_start_turns_announce_one_tile() {
+        let manager = Arc::new(new_session_manager());
+        let session = create_game_session(2, "multiplayer".to_string());
+        let (session, _) = add_player_to_session(session, "p1".to_string()).unwrap();
+        let (mut session, _) = add_player_to_session(session, "p2".to_string()).unwrap();
+        session.state = 1;
+        let session_id = session.id.clone();
+        update_session_with_manager(&manager, session)
+            .await
+            .unwrap();
+
+        let vs = nn::VarStore::new(Device::Cpu);
+        let arch = NNArchitecture::GraphTransformer;
+        let policy_net = Arc::new(Mutex::new(PolicyNet::new(&vs, (5, 47, 1), arch)));
+        let value_net = Arc::new(Mutex::new(ValueNet::new(&vs, (5, 47, 1), arch)));
+        let start_turn = || {
+            start_turn_logic(
+                &manager,
+                &policy_net,
+                &value_net,
+                None,
+                0,
+                0,
+                session_id.clone(),
+            )
+        };
+
+        let (a, b) = tokio::join!(start_turn(), start_turn());
+        let (a, b) = (a.unwrap().into_inner(), b.unwrap().into_inner());
+        assert_eq!([a.success, b.success].iter().filter(|&&ok| ok).count(), 1);
+        assert_eq!(a.announced_tile, b.announced_tile);
+
+        // Une seule tuile tirée du deck
+        let session = get_session_by_id_with_manager(&manager, &session_id)
+            .await
+            .unwrap();
+        let game = stored_game_state(&session).unwrap();
+        let drawn = game
+            .deck
+            .tiles
+            .iter()
+            .filter(|t| **t == Tile(0, 0, 0))
+            .count();
+        assert_eq!(drawn, 1);
+        let tile = game.current_tile.unwrap();
+        assert_eq!(
+            format!("{}-{}-{}", tile.0, tile.1, tile.2),
+            a.announced_tile
+        );
+    }
 }
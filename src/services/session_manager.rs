@@ -1,9 +1,14 @@
 // src/services/session_manager.rs - 100% fonctionnel - TOUTES les fonctions extraites
 
+use crate::game::plateau::Plateau;
 use crate::generated::takeiteasygame::v1::*;
+use crate::scoring::rules::ScoringRules;
+use crate::services::game_manager::{OpponentStrategy, TileDrawStrategy, FULL_GAME_TURNS};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, RwLock as StdRwLock};
+use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
 // ============================================================================
@@ -24,6 +29,13 @@ pub struct GameSession {
     pub created_at: std::time::Instant,
     pub board_state: String,
     pub turn_number: i32,
+    pub tile_draw_strategy: TileDrawStrategy, // Tirage des tuiles annoncées pour cette session
+    pub num_turns: usize,                     // Longueur de la partie (≤ 19)
+    pub ai_think_delay_ms: u64,               // Pause "réflexion" de l'IA avant son coup (UX)
+    pub starting_board: Option<Plateau>,      // Plateau de départ (puzzle), None = plateau vide
+    pub scoring_rules: ScoringRules,          // Variante de score de la partie
+    pub opponent: OpponentStrategy,           // Stratégie de l'adversaire IA
+    pub model_version: Option<String>,        // Version de modèle A/B de la partie
 }
 
 /// Map game mode to MCTS simulation count
@@ -57,10 +69,133 @@ impl SessionStoreState {
     }
 }
 
+/// Nombre de shards par défaut du store de sessions
+pub const SESSION_STORE_SHARDS: usize = 16;
+
+/// Sessions en attente par abonné avant qu'il ne décroche (`Lagged`)
+pub const SESSION_UPDATES_CAPACITY: usize = 32;
+
+/// Store de sessions shardé : chaque session vit dans le shard choisi par le
+/// hash de son id, si bien que des parties indépendantes ne se disputent pas
+/// le même verrou.
+#[derive(Debug)]
+pub struct SessionStore {
+    shards: Vec<RwLock<SessionStoreState>>,
+    /// Index code → id : une recherche par code ne lit que le shard de la session
+    session_ids_by_code: StdRwLock<HashMap<String, String>>,
+    /// Canal par session observée : chaque écriture y publie la session à jour
+    updates: Mutex<HashMap<String, broadcast::Sender<GameSession>>>,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::with_shards(SESSION_STORE_SHARDS)
+    }
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_shards(num_shards: usize) -> Self {
+        Self {
+            shards: (0..num_shards.max(1))
+                .map(|_| RwLock::new(SessionStoreState::new()))
+                .collect(),
+            session_ids_by_code: StdRwLock::new(HashMap::new()),
+            updates: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// S'abonner aux écritures de la session `session_id`
+    pub fn subscribe(&self, session_id: &str) -> broadcast::Receiver<GameSession> {
+        let mut updates = self.updates.lock().unwrap();
+        updates
+            .entry(session_id.to_string())
+            .or_insert_with(|| broadcast::channel(SESSION_UPDATES_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publier la session écrite aux abonnés ; le canal est retiré quand
+    /// plus personne ne l'écoute
+    fn publish(&self, session: &GameSession) {
+        let mut updates = self.updates.lock().unwrap();
+        if let Some(sender) = updates.get(&session.id) {
+            if sender.send(session.clone()).is_err() {
+                updates.remove(&session.id);
+            }
+        }
+    }
+
+    /// Enregistrer le code de la session ; le verrou d'écriture n'est pris
+    /// que pour un code nouveau ou réattribué
+    fn index_code(&self, session: &GameSession) {
+        let indexed =
+            self.session_ids_by_code.read().unwrap().get(&session.code) == Some(&session.id);
+        if !indexed {
+            self.session_ids_by_code
+                .write()
+                .unwrap()
+                .insert(session.code.clone(), session.id.clone());
+        }
+    }
+
+    fn session_id_for_code(&self, code: &str) -> Option<String> {
+        self.session_ids_by_code.read().unwrap().get(code).cloned()
+    }
+
+    pub fn shard_for(&self, session_id: &str) -> &RwLock<SessionStoreState> {
+        let mut hasher = DefaultHasher::new();
+        session_id.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// Une session quelconque (mode single-player) : seule celle-ci est copiée
+    pub async fn first_session(&self) -> Option<GameSession> {
+        for shard in &self.shards {
+            if let Some(session) = shard.read().await.sessions.values().next() {
+                return Some(session.clone());
+            }
+        }
+        None
+    }
+
+    /// Nombre total de sessions, sans copie
+    pub async fn session_count(&self) -> usize {
+        let mut count = 0;
+        for shard in &self.shards {
+            count += shard.read().await.sessions.len();
+        }
+        count
+    }
+
+    /// Copie fusionnée de tous les shards (diagnostic, parcours de toutes les sessions)
+    pub async fn snapshot(&self) -> SessionStoreState {
+        let mut merged = SessionStoreState::new();
+        for shard in &self.shards {
+            let state = shard.read().await;
+            merged.sessions.extend(
+                state
+                    .sessions
+                    .iter()
+                    .map(|(id, session)| (id.clone(), session.clone())),
+            );
+            merged.sessions_by_code.extend(
+                state
+                    .sessions_by_code
+                    .iter()
+                    .map(|(code, id)| (code.clone(), id.clone())),
+            );
+        }
+        merged
+    }
+}
+
 // SessionManager - Structure de données pure (pas de logique)
 #[derive(Clone)]
 pub struct SessionManager {
-    store: Arc<RwLock<SessionStoreState>>,
+    store: Arc<SessionStore>,
 }
 
 // ============================================================================
@@ -69,11 +204,11 @@ pub struct SessionManager {
 
 pub fn new_session_manager() -> SessionManager {
     SessionManager {
-        store: Arc::new(RwLock::new(SessionStoreState::new())),
+        store: Arc::new(SessionStore::new()),
     }
 }
 
-pub fn get_store_from_manager(manager: &SessionManager) -> &Arc<RwLock<SessionStoreState>> {
+pub fn get_store_from_manager(manager: &SessionManager) -> &Arc<SessionStore> {
     &manager.store
 }
 
@@ -139,6 +274,13 @@ pub fn create_game_session(max_players: i32, game_mode: String) -> GameSession {
         created_at: std::time::Instant::now(),
         board_state: "{}".to_string(),
         turn_number: 0,
+        tile_draw_strategy: TileDrawStrategy::default(),
+        num_turns: FULL_GAME_TURNS,
+        ai_think_delay_ms: 0,
+        starting_board: None,
+        scoring_rules: ScoringRules::default(),
+        opponent: OpponentStrategy::default(),
+        model_version: None,
     }
 }
 
@@ -275,8 +417,27 @@ fn generate_session_code() -> String {
 // FONCTIONS DE STORE - OPÉRATIONS ASYNCHRONES COMPOSABLES
 // ============================================================================
 
+fn action_session_id(action: &SessionAction) -> String {
+    match action {
+        SessionAction::CreateSession { session } | SessionAction::UpdateSession { session } => {
+            session.id.clone()
+        }
+    }
+}
+
+/// Écriture sous le verrou du shard, publiée aux abonnés dans l'ordre des écritures
+async fn apply_action_in_store(store: &SessionStore, action: SessionAction) {
+    let session_id = action_session_id(&action);
+    let mut shard = store.shard_for(&session_id).write().await;
+    *shard = apply_session_action(std::mem::take(&mut *shard), action);
+    if let Some(session) = find_session_by_id(&shard, &session_id) {
+        store.index_code(session);
+        store.publish(session);
+    }
+}
+
 pub async fn create_session_in_store<F, T>(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     max_players: i32,
     game_mode: String,
     continuation: F,
@@ -285,63 +446,59 @@ where
     F: FnOnce(String) -> Result<T, String>,
 {
     let (action, session_code) = create_session_action(max_players, game_mode);
-
-    {
-        let mut state = store.write().await;
-        *state = apply_session_action(state.clone(), action);
-    }
-
+    apply_action_in_store(store, action).await;
     continuation(session_code)
 }
 
 pub async fn get_session_by_code_from_store(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     code: &str,
 ) -> Option<GameSession> {
-    let state = store.read().await;
+    let session_id = store.session_id_for_code(code)?;
+    let state = store.shard_for(&session_id).read().await;
     find_session_by_code(&state, code).cloned()
 }
 
 pub async fn get_session_by_id_from_store(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     session_id: &str,
 ) -> Option<GameSession> {
-    let state = store.read().await;
+    let state = store.shard_for(session_id).read().await;
     find_session_by_id(&state, session_id).cloned()
 }
 
 pub async fn update_session_in_store(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     session: GameSession,
 ) -> Result<(), String> {
-    let action = SessionAction::UpdateSession { session };
-
-    {
-        let mut state = store.write().await;
-        *state = apply_session_action(state.clone(), action);
-    }
-
+    apply_action_in_store(store, SessionAction::UpdateSession { session }).await;
     Ok(())
 }
 
+/// Lecture, transformation et écriture sous le verrou du shard : deux
+/// transformations concurrentes d'une même session ne s'écrasent pas.
 pub async fn transform_session_in_store<F, T>(
-    store: &Arc<RwLock<SessionStoreState>>,
+    store: &Arc<SessionStore>,
     session_id: &str,
     transformation: F,
 ) -> Result<Option<T>, String>
 where
     F: FnOnce(GameSession) -> Result<(GameSession, T), String>,
 {
-    let current_session = get_session_by_id_from_store(store, session_id).await;
+    let mut shard = store.shard_for(session_id).write().await;
+    let current_session = find_session_by_id(&shard, session_id).cloned();
 
     match current_session {
-        Some(session) => match transformation(session) {
-            Ok((updated_session, result)) => {
-                update_session_in_store(store, updated_session).await?;
-                Ok(Some(result))
-            }
-            Err(e) => Err(e),
-        },
+        Some(session) => {
+            let (updated_session, result) = transformation(session)?;
+            store.index_code(&updated_session);
+            store.publish(&updated_session);
+            let action = SessionAction::UpdateSession {
+                session: updated_session,
+            };
+            *shard = apply_session_action(std::mem::take(&mut *shard), action);
+            Ok(Some(result))
+        }
         None => Ok(None),
     }
 }
@@ -371,6 +528,15 @@ pub async fn update_session_with_manager(
     update_session_in_store(get_store_from_manager(manager), session).await
 }
 
+/// Recevoir la session à chaque écriture ; les écritures antérieures à
+/// l'abonnement ne sont pas rejouées
+pub fn subscribe_to_session_with_manager(
+    manager: &SessionManager,
+    session_id: &str,
+) -> broadcast::Receiver<GameSession> {
+    get_store_from_manager(manager).subscribe(session_id)
+}
+
 pub async fn create_session_functional_with_manager(
     manager: &SessionManager,
     max_players: i32,
@@ -387,3 +553,141 @@ impl SessionManager {
     // Toutes les fonctions sont maintenant externes !
     // Utilisez les fonctions *_with_manager() à la place
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_create_and_get_across_shards() {
+        let manager = new_session_manager();
+
+        let handles: Vec<_> = (0..100)
+            .map(|_| {
+                let manager = manager.clone();
+                tokio::spawn(async move {
+                    let code = create_session_functional_with_manager(
+                        &manager,
+                        2,
+                        "multiplayer".to_string(),
+                    )
+                    .await
+                    .unwrap();
+                    let by_code = get_session_by_code_with_manager(&manager, &code)
+                        .await
+                        .expect("session created concurrently must be found by code");
+                    let by_id = get_session_by_id_with_manager(&manager, &by_code.id)
+                        .await
+                        .expect("session created concurrently must be found by id");
+                    assert_eq!(by_id.code, code);
+
+                    let (with_player, _) =
+                        add_player_to_session(by_id, "Alice".to_string()).unwrap();
+                    update_session_with_manager(&manager, with_player)
+                        .await
+                        .unwrap();
+                    by_code.id
+                })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for handle in handles {
+            ids.push(handle.await.unwrap());
+        }
+
+        let snapshot = get_store_from_manager(&manager).snapshot().await;
+        assert_eq!(snapshot.sessions.len(), 100);
+        assert_eq!(snapshot.sessions_by_code.len(), 100);
+        let store = get_store_from_manager(&manager);
+        assert_eq!(store.session_count().await, 100);
+        let any = store.first_session().await.unwrap();
+        assert!(ids.contains(&any.id));
+        for id in &ids {
+            let session = get_session_by_id_with_manager(&manager, id).await.unwrap();
+            assert_eq!(session.players.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_transforms_of_one_session_are_not_lost() {
+        let manager = new_session_manager();
+        let code = create_session_functional_with_manager(&manager, 2, "multiplayer".to_string())
+            .await
+            .unwrap();
+        let session_id = get_session_by_code_with_manager(&manager, &code)
+            .await
+            .unwrap()
+            .id;
+        let store = get_store_from_manager(&manager).clone();
+
+        let handles: Vec<_> = (0..50)
+            .map(|_| {
+                let store = store.clone();
+                let session_id = session_id.clone();
+                tokio::spawn(async move {
+                    transform_session_in_store(&store, &session_id, |mut session| {
+                        session.turn_number += 1;
+                        Ok((session, ()))
+                    })
+                    .await
+                    .unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.await.unwrap().is_some());
+        }
+
+        let session = get_session_by_id_from_store(&store, &session_id)
+            .await
+            .unwrap();
+        assert_eq!(session.turn_number, 50);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_every_write_of_their_session() {
+        let manager = new_session_manager();
+        let code = create_session_functional_with_manager(&manager, 2, "multiplayer".to_string())
+            .await
+            .unwrap();
+        let other_code =
+            create_session_functional_with_manager(&manager, 2, "multiplayer".to_string())
+                .await
+                .unwrap();
+        let session = get_session_by_code_with_manager(&manager, &code)
+            .await
+            .unwrap();
+        let other = get_session_by_code_with_manager(&manager, &other_code)
+            .await
+            .unwrap();
+        let mut updates = subscribe_to_session_with_manager(&manager, &session.id);
+
+        let (with_player, _) = add_player_to_session(session.clone(), "Alice".to_string()).unwrap();
+        update_session_with_manager(&manager, with_player)
+            .await
+            .unwrap();
+        update_session_with_manager(&manager, other).await.unwrap();
+        let store = get_store_from_manager(&manager);
+        transform_session_in_store(store, &session.id, |mut session| {
+            session.turn_number = 7;
+            Ok((session, ()))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(updates.recv().await.unwrap().players.len(), 1);
+        assert_eq!(updates.recv().await.unwrap().turn_number, 7);
+        assert!(updates.try_recv().is_err());
+
+        // Dernier abonné parti : le canal est retiré à l'écriture suivante
+        drop(updates);
+        let session = get_session_by_id_with_manager(&manager, &session.id)
+            .await
+            .unwrap();
+        update_session_with_manager(&manager, session)
+            .await
+            .unwrap();
+        assert!(store.updates.lock().unwrap().is_empty());
+    }
+}
@@ -12,6 +12,12 @@ use crate::generated::takeiteasygame::v1::session_service_server::SessionService
 use crate::generated::takeiteasygame::v1::*;
 
 use crate::auth::{try_authenticate_request, JwtManager};
+use crate::game::plateau::Plateau;
+use crate::scoring::rules::ScoringRules;
+use crate::services::game_manager::{
+    validate_ai_think_delay, validate_num_turns, validate_starting_board, validate_tile_draw,
+    OpponentStrategy, TileDrawStrategy,
+};
 use crate::services::session_manager::{
     add_player_to_session, all_players_ready, create_session_functional_with_manager,
     get_session_by_code_with_manager, get_session_by_id_with_manager, get_store_from_manager,
@@ -162,11 +168,18 @@ fn set_ready_error_response(code: String, message: String) -> SetReadyResponse {
 // ============================================================================
 
 // session_service.rs - dans create_session_logic_with_manager
+#[allow(clippy::too_many_arguments)]
 async fn create_session_logic_with_manager(
     service: &SessionServiceImpl,
     player_name: String,
     max_players: i32,
     game_mode: String,
+    num_turns: usize,
+    ai_think_delay_ms: u64,
+    starting_board: Option<Plateau>,
+    scoring_rules: ScoringRules,
+    opponent: OpponentStrategy,
+    tile_draw_strategy: TileDrawStrategy,
 ) -> Result<Response<CreateSessionResponse>, Status> {
     let manager = &service.session_manager;
     match create_session_functional_with_manager(manager, max_players, game_mode).await {
@@ -175,6 +188,13 @@ async fn create_session_logic_with_manager(
                 // Ajouter le joueur humain
                 match add_player_to_session(session.clone(), player_name.clone()) {
                     Ok((mut updated_session, player_id)) => {
+                        updated_session.num_turns = num_turns;
+                        updated_session.ai_think_delay_ms = ai_think_delay_ms;
+                        updated_session.starting_board = starting_board;
+                        updated_session.scoring_rules = scoring_rules;
+                        updated_session.opponent = opponent;
+                        updated_session.tile_draw_strategy = tile_draw_strategy;
+
                         // 🤖 AJOUTER MCTS AUTOMATIQUEMENT POUR LES MODES SINGLE-PLAYER ET MULTIPLAYER
                         if updated_session.game_mode.starts_with("single-player")
                             || updated_session.game_mode == "training"
@@ -271,19 +291,13 @@ async fn join_session_logic(
     let session = if session_code == "AUTO" && service.single_player_mode {
         // Utiliser la première session disponible
         let store = get_store_from_manager(manager);
-        let state = store.read().await;
-
-        if let Some((_session_id, session)) = state.sessions.iter().next() {
-            let session_clone = session.clone();
-            let session_code_found = session.code.clone();
-            drop(state);
+        if let Some(session) = store.first_session().await {
             log::info!(
                 "🔄 AUTO: connexion à la session single-player {}",
-                session_code_found
+                session.code
             );
-            session_clone
+            session
         } else {
-            drop(state);
             log::error!("❌ Aucune session disponible pour AUTO");
             return Ok(Response::new(join_error_response(
                 "NO_SESSION_AVAILABLE".to_string(),
@@ -395,12 +409,22 @@ async fn join_session_logic(
 
                         // ✅ CRÉER ET DÉMARRER LE PREMIER TOUR AUTOMATIQUEMENT
                         use crate::services::game_manager::{
-                            create_take_it_easy_game, start_new_turn,
+                            create_take_it_easy_game_with_options, start_new_turn,
+                            with_starting_board,
                         };
                         let player_ids: Vec<String> =
                             updated_session.players.keys().cloned().collect();
-                        let game_state =
-                            create_take_it_easy_game(updated_session.id.clone(), player_ids);
+                        let mut game_state = create_take_it_easy_game_with_options(
+                            updated_session.id.clone(),
+                            player_ids,
+                            updated_session.tile_draw_strategy.clone(),
+                            updated_session.num_turns,
+                        );
+                        game_state.scoring_rules = updated_session.scoring_rules;
+                        game_state.opponent = updated_session.opponent;
+                        if let Some(board) = &updated_session.starting_board {
+                            game_state = with_starting_board(game_state, board);
+                        }
 
                         // Démarrer immédiatement le premier tour avec une tuile
                         match start_new_turn(game_state) {
@@ -465,19 +489,9 @@ async fn set_ready_logic(
         None => {
             log::error!("❌ Session {} introuvable lors de SET_READY", session_id);
 
-            // 🔍 Debug: Lister toutes les sessions existantes
+            // 🔍 Debug: nombre de sessions existantes (sans les copier)
             let store = get_store_from_manager(manager);
-            let state = store.read().await;
-            log::error!("🔍 Sessions existantes ({} total):", state.sessions.len());
-            for (sid, session) in &state.sessions {
-                log::error!(
-                    "  - id={}, code={}, players={}",
-                    sid,
-                    session.code,
-                    session.players.len()
-                );
-            }
-            drop(state);
+            log::error!("🔍 Sessions existantes: {}", store.session_count().await);
 
             return Ok(Response::new(set_ready_error_response(
                 "SESSION_NOT_FOUND".to_string(),
@@ -535,7 +549,101 @@ impl SessionService for SessionServiceImpl {
 
         let player_name = req.player_name;
 
-        create_session_logic_with_manager(self, player_name, req.max_players, req.game_mode).await
+        let num_turns = match validate_num_turns(req.num_turns) {
+            Ok(num_turns) => num_turns,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    format!("num_turns must be between 1 and 19 (got {})", req.num_turns),
+                )));
+            }
+        };
+
+        let ai_think_delay_ms = match validate_ai_think_delay(req.ai_think_delay_ms) {
+            Ok(delay_ms) => delay_ms,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    format!(
+                        "ai_think_delay_ms must not be negative (got {})",
+                        req.ai_think_delay_ms
+                    ),
+                )));
+            }
+        };
+
+        let starting_board = match validate_starting_board(&req.starting_board, num_turns) {
+            Ok(board) => board,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    "starting_board must be 19 cells of distinct deck tiles (e.g. 963) or '.', \
+                     leaving at least one turn to play"
+                        .to_string(),
+                )));
+            }
+        };
+
+        let scoring_rules = match ScoringRules::parse(&req.scoring_rules) {
+            Ok(rules) => rules,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    format!(
+                        "scoring_rules must be 'classic', 'bonus-complete' or 'long-lines' (got '{}')",
+                        req.scoring_rules
+                    ),
+                )));
+            }
+        };
+
+        let opponent = match OpponentStrategy::parse(&req.opponent) {
+            Ok(opponent) => opponent,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    format!(
+                        "opponent must be 'gt' or 'human-like' (got '{}')",
+                        req.opponent
+                    ),
+                )));
+            }
+        };
+
+        let tile_draw_strategy = match validate_tile_draw(
+            &req.tile_draw,
+            req.tile_seed,
+            &req.tile_sequence,
+            num_turns,
+            starting_board.as_ref(),
+        ) {
+            Ok(strategy) => strategy,
+            Err(code) => {
+                return Ok(Response::new(create_error_response(
+                    code,
+                    format!(
+                        "tile_draw must be 'random', 'seeded' or 'sequence' (got '{}'); a sequence \
+                         lists distinct deck tiles (e.g. 963), one per turn left to play, none \
+                         already on the starting board",
+                        req.tile_draw
+                    ),
+                )));
+            }
+        };
+
+        create_session_logic_with_manager(
+            self,
+            player_name,
+            req.max_players,
+            req.game_mode,
+            num_turns,
+            ai_think_delay_ms,
+            starting_board,
+            scoring_rules,
+            opponent,
+            tile_draw_strategy,
+        )
+        .await
     }
 
     async fn join_session(
@@ -620,30 +728,21 @@ impl SessionService for SessionServiceImpl {
                 // 🎮 EN MODE SINGLE-PLAYER: utiliser la première session disponible
                 if self.single_player_mode {
                     let store = get_store_from_manager(&self.session_manager);
-                    let state = store.read().await;
-
-                    if let Some((session_id, session)) = state.sessions.iter().next() {
-                        let session_id_clone = session_id.clone();
-                        let session_code = session.code.clone();
-                        drop(state);
 
+                    if let Some(session) = store.first_session().await {
                         log::info!(
                             "🔄 Mode single-player: redirection vers session {}",
-                            session_code
+                            session.code
                         );
 
                         // Récursion avec la bonne session
                         let new_request = GetSessionStateRequest {
-                            session_id: session_id_clone,
+                            session_id: session.id,
                         };
                         return self.get_session_state(Request::new(new_request)).await;
                     }
 
-                    log::error!("🔍 Sessions existantes ({} total):", state.sessions.len());
-                    for (sid, session) in &state.sessions {
-                        log::error!("  - id={}, code={}", sid, session.code);
-                    }
-                    drop(state);
+                    log::error!("🔍 Aucune session existante");
                 }
 
                 // Session non trouvée
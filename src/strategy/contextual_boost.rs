@@ -6,32 +6,10 @@
 //! - Line completion proximity
 //! - Game phase (early/mid/late)
 
+use crate::game::board_geometry::LINES;
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
 
-/// Line definitions: (positions, length, band_index)
-/// band_index: 0 = horizontal, 1 = diagonal_type1, 2 = diagonal_type2
-const LINES: &[(&[usize], usize, usize)] = &[
-    // Horizontal lines (band 0)
-    (&[0, 1, 2], 3, 0),
-    (&[3, 4, 5, 6], 4, 0),
-    (&[7, 8, 9, 10, 11], 5, 0),
-    (&[12, 13, 14, 15], 4, 0),
-    (&[16, 17, 18], 3, 0),
-    // Diagonal type 1 (band 1)
-    (&[0, 3, 7], 3, 1),
-    (&[1, 4, 8, 12], 4, 1),
-    (&[2, 5, 9, 13, 16], 5, 1),
-    (&[6, 10, 14, 17], 4, 1),
-    (&[11, 15, 18], 3, 1),
-    // Diagonal type 2 (band 2)
-    (&[7, 12, 16], 3, 2),
-    (&[3, 8, 13, 17], 4, 2),
-    (&[0, 4, 9, 14, 18], 5, 2),
-    (&[1, 5, 10, 15], 4, 2),
-    (&[2, 6, 11], 3, 2),
-];
-
 /// Analyzes how many tiles in a line already have the target value on the target band
 fn count_matching_tiles(
     plateau: &Plateau,
@@ -69,10 +47,11 @@ pub fn calculate_contextual_boost_entropy(
     let tile_bands = [tile.0, tile.1, tile.2];
     let mut score = 0.0;
 
-    for (line_positions, length, band_idx) in LINES {
+    for (line_positions, band_idx) in LINES.iter() {
         if !line_positions.contains(&position) {
             continue;
         }
+        let length = line_positions.len();
 
         let target_value = tile_bands[*band_idx];
         if target_value == 0 {
@@ -105,9 +84,9 @@ pub fn calculate_contextual_boost_entropy(
             .filter(|&&pos| plateau.tiles[pos] != Tile(0, 0, 0))
             .count();
 
-        let completion_ratio = (matches as f64 + 1.0) / (*length as f64);
-        let occupancy_ratio = filled as f64 / (*length as f64);
-        let conflict_penalty = conflicts as f64 / (*length as f64);
+        let completion_ratio = (matches as f64 + 1.0) / (length as f64);
+        let occupancy_ratio = filled as f64 / (length as f64);
+        let conflict_penalty = conflicts as f64 / (length as f64);
 
         score += completion_ratio * (1.0 + occupancy_ratio) - conflict_penalty;
     }
@@ -4,16 +4,22 @@
 //!   over all possible future tiles. ~300 evals per move, <2ms GPU.
 //! - **2-ply**: Looks 2 moves ahead: max over p1, avg over tile1, max over p2,
 //!   avg over tile2. ~30k evals per move at turn 8, chunked forward passes.
+//!
+//! The 1-ply player can add a blunder filter: among the positions whose EV is
+//! within `blunder_epsilon` points of the best, it avoids breaking a line that
+//! is one tile from completion.
 
 use tch::{Device, Kind, Tensor};
 
+use crate::game::board_geometry::{line_value, lines_through, LINES};
 use crate::game::deck::Deck;
-use crate::game::get_legal_moves::get_legal_moves;
+use crate::game::get_legal_moves::{get_legal_moves, get_legal_moves_into};
 use crate::game::plateau::Plateau;
 use crate::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
 use crate::game::tile::Tile;
 use crate::neural::graph_transformer::{GraphTransformerPolicyNet, GraphTransformerValueNet};
 use crate::neural::tensor_conversion::convert_plateau_for_gat_47ch;
+use crate::scoring::scoring::count_line_completions;
 use crate::strategy::gt_boost::line_boost;
 
 pub struct ExpectimaxConfig {
@@ -28,6 +34,9 @@ pub struct ExpectimaxConfig {
     pub top_k_ply1: usize,
     /// Number of top ply-2 positions to keep per (p1, t1) in 3-ply pruning (default 2).
     pub top_k_ply2: usize,
+    /// 1-ply blunder filter: EV tolerance in points within which a position
+    /// that keeps near-complete lines alive is preferred (0 = off).
+    pub blunder_epsilon: f64,
 }
 
 /// 1-ply expectimax: pick the position that maximises E[V | place tile at p].
@@ -93,11 +102,13 @@ pub fn expectimax_select(
     let n_future = future_tiles.len();
     let mut best_pos = legal[0];
     let mut best_ev = f64::NEG_INFINITY;
+    let mut evs: Vec<(usize, f64)> = Vec::with_capacity(legal.len());
 
     for (i, &pos) in legal.iter().enumerate() {
         let start = i * n_future;
         let end = start + n_future;
         let ev: f64 = values_flat[start..end].iter().sum::<f64>() / n_future as f64;
+        evs.push((pos, ev));
 
         if ev > best_ev {
             best_ev = ev;
@@ -105,9 +116,66 @@ pub fn expectimax_select(
         }
     }
 
+    if config.blunder_epsilon > 0.0 {
+        // EVs are normalized scores: convert the tolerance from points
+        return avoid_blunders(
+            plateau,
+            tile,
+            &evs,
+            config.blunder_epsilon / config.score_std,
+        );
+    }
+
     best_pos
 }
 
+/// Lines one tile from completion that placing `tile` at `position` breaks:
+/// every other cell of the line holds the same value, which the tile lacks.
+pub fn broken_near_complete_lines(plateau: &Plateau, tile: &Tile, position: usize) -> usize {
+    lines_through(position)
+        .iter()
+        .filter(|&&line| {
+            let (positions, direction) = LINES[line];
+            let mut others = positions.iter().filter(|&&p| p != position);
+            let Some(&first) = others.next() else {
+                return false;
+            };
+            let value = line_value(&plateau.tiles[first], direction);
+            value != 0
+                && others.all(|&p| line_value(&plateau.tiles[p], direction) == value)
+                && line_value(tile, direction) != value
+        })
+        .count()
+}
+
+/// Blunder filter over `(position, ev)` pairs: among the positions within
+/// `epsilon` of the best EV, pick the one breaking the fewest near-complete
+/// lines, then completing the most lines, then with the best EV.
+pub fn avoid_blunders(plateau: &Plateau, tile: &Tile, evs: &[(usize, f64)], epsilon: f64) -> usize {
+    let best_ev = evs
+        .iter()
+        .map(|&(_, ev)| ev)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let completions_before = count_line_completions(plateau);
+
+    evs.iter()
+        .filter(|&&(_, ev)| ev >= best_ev - epsilon)
+        .map(|&(pos, ev)| {
+            let mut after = plateau.clone();
+            after.tiles[pos] = *tile;
+            let completed = count_line_completions(&after) - completions_before;
+            (
+                pos,
+                ev,
+                broken_near_complete_lines(plateau, tile, pos),
+                completed,
+            )
+        })
+        .min_by(|a, b| a.2.cmp(&b.2).then(b.3.cmp(&a.3)).then(b.1.total_cmp(&a.1)))
+        .map(|(pos, ..)| pos)
+        .unwrap_or(0)
+}
+
 /// 2-ply expectimax: looks 2 moves ahead.
 ///
 /// Tree structure: max(p1) → avg(tile1) → max(p2) → avg(tile2) → V(state)
@@ -491,6 +559,8 @@ pub fn expectimax_3ply_select(
 
     let mut best_pos = top_positions[0];
     let mut best_ev = f64::NEG_INFINITY;
+    // Ply-3 moves, refilled for every (p1, t1, q) instead of reallocated
+    let mut legal_3 = Vec::with_capacity(plateau.tiles.len());
 
     for (p1_idx, &p1) in top_positions.iter().enumerate() {
         let mut plateau_1 = plateau.clone();
@@ -514,7 +584,7 @@ pub fn expectimax_3ply_select(
             for &(_q_idx, q_pos) in selected {
                 let mut plateau_2 = plateau_1.clone();
                 plateau_2.tiles[q_pos] = *ft1;
-                let legal_3 = get_legal_moves(&plateau_2);
+                get_legal_moves_into(&plateau_2, &mut legal_3);
                 let n_r = legal_3.len();
 
                 if n_r == 0 {
@@ -717,3 +787,46 @@ fn gt_direct_gpu(
         })
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_blunder_filter_keeps_the_near_complete_line() {
+        // Row 7..12 reads 9 on four cells, its last cell 11 is empty
+        let mut plateau = create_plateau_empty();
+        for (pos, tile) in [
+            (7, Tile(9, 2, 3)),
+            (8, Tile(9, 6, 4)),
+            (9, Tile(9, 7, 8)),
+            (10, Tile(9, 2, 8)),
+        ] {
+            plateau.tiles[pos] = tile;
+        }
+        let tile = Tile(1, 6, 3);
+        assert_eq!(broken_near_complete_lines(&plateau, &tile, 11), 1);
+        assert_eq!(broken_near_complete_lines(&plateau, &tile, 0), 0);
+        assert_eq!(broken_near_complete_lines(&plateau, &Tile(9, 6, 3), 11), 0);
+
+        // The raw value pick (11) sacrifices the 45-point row
+        let evs = [(11, 0.52), (0, 0.50), (16, 0.10)];
+        assert_eq!(avoid_blunders(&plateau, &tile, &evs, 0.0), 11);
+        assert_eq!(avoid_blunders(&plateau, &tile, &evs, 0.05), 0);
+        // Far worse alternatives are not considered
+        let evs = [(11, 0.52), (16, 0.10)];
+        assert_eq!(avoid_blunders(&plateau, &tile, &evs, 0.05), 11);
+    }
+
+    #[test]
+    fn test_blunder_filter_prefers_completing_the_line() {
+        let mut plateau = create_plateau_empty();
+        for (pos, tile) in [(0, Tile(5, 2, 3)), (1, Tile(5, 6, 4))] {
+            plateau.tiles[pos] = tile;
+        }
+        let tile = Tile(5, 7, 8);
+        let evs = [(3, 0.40), (2, 0.39)];
+        assert_eq!(avoid_blunders(&plateau, &tile, &evs, 0.05), 2);
+    }
+}
@@ -3,49 +3,28 @@
 //! Provides line-completion heuristics that augment Graph Transformer logits:
 //!   - `line_boost`: logit bonus for line completion/near-completion
 //!   - `gt_boosted_select`: GT logits + line_boost → argmax (zero overhead)
+//!   - `gt_greedy_blend_select`: boosted logits blended with immediate score gain (no rollouts)
 //!   - `gt_rollout_boosted`: rollout using GT+Boost as policy
 //!   - `gt_beam_rollout_select`: top-K candidates + M boosted rollouts → best avg
 //!   - `gt_beam_v1_select`: beam + always inject v1-ideal position as candidate
+//!   - `heuristic_select`: network-free greedy line completion (benchmark baseline)
 
 use rand::prelude::*;
 use rand::rngs::StdRng;
 use tch::Tensor;
 
+use crate::game::board_geometry::ROWS;
 use crate::game::deck::Deck;
 use crate::game::get_legal_moves::get_legal_moves;
-use crate::game::plateau::Plateau;
+use crate::game::plateau::{create_plateau_empty, Plateau};
 use crate::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
 use crate::game::tile::Tile;
 use crate::neural::graph_transformer::GraphTransformerPolicyNet;
 use crate::neural::tensor_conversion::convert_plateau_for_gat_47ch;
 use crate::scoring::scoring::result;
 
-/// The 15 scoring lines of a Take It Easy board.
-///
-/// Each entry is (positions, direction_index) where:
-///   - direction 0 = tile.0 (horizontal/v1)
-///   - direction 1 = tile.1 (diagonal v2)
-///   - direction 2 = tile.2 (diagonal v3)
-pub const LINES: [(&[usize], usize); 15] = [
-    // Horizontal rows (v1)
-    (&[0, 1, 2], 0),
-    (&[3, 4, 5, 6], 0),
-    (&[7, 8, 9, 10, 11], 0),
-    (&[12, 13, 14, 15], 0),
-    (&[16, 17, 18], 0),
-    // Diagonal v2
-    (&[0, 3, 7], 1),
-    (&[1, 4, 8, 12], 1),
-    (&[2, 5, 9, 13, 16], 1),
-    (&[6, 10, 14, 17], 1),
-    (&[11, 15, 18], 1),
-    // Diagonal v3
-    (&[7, 12, 16], 2),
-    (&[3, 8, 13, 17], 2),
-    (&[0, 4, 9, 14, 18], 2),
-    (&[1, 5, 10, 15], 2),
-    (&[2, 6, 11], 2),
-];
+/// The 15 scoring lines (re-exported from `game::board_geometry`).
+pub use crate::game::board_geometry::LINES;
 
 /// Compute a logit boost for placing a tile at a given position.
 ///
@@ -130,8 +109,89 @@ pub fn line_boost(plateau: &Plateau, tile: &Tile, position: usize, boost: f64) -
     total
 }
 
-/// Compute masked GT logits for a board state.
-fn gt_masked_logits(
+/// Find positions that would complete a scoring line for this tile.
+pub fn find_line_completing_positions(plateau: &Plateau, tile: &Tile) -> Vec<usize> {
+    let mut positions = Vec::new();
+    for &(line_positions, direction) in &LINES {
+        let get_value = |t: &Tile| match direction {
+            0 => t.0,
+            1 => t.1,
+            _ => t.2,
+        };
+        let tile_value = get_value(tile);
+        if tile_value == 0 {
+            continue;
+        }
+
+        let mut empty_pos = None;
+        let mut all_match = true;
+        let mut empty_count = 0;
+
+        for &pos in line_positions {
+            let t = &plateau.tiles[pos];
+            if *t == Tile(0, 0, 0) {
+                empty_count += 1;
+                empty_pos = Some(pos);
+            } else if get_value(t) != tile_value {
+                all_match = false;
+                break;
+            }
+        }
+
+        if all_match && empty_count == 1 {
+            if let Some(pos) = empty_pos {
+                if !positions.contains(&pos) {
+                    positions.push(pos);
+                }
+            }
+        }
+    }
+    positions
+}
+
+/// Pure greedy line-completion heuristic (no neural network).
+///
+/// Completes a scoring line whenever possible (largest `line_boost` among the
+/// completing positions), otherwise plays the legal move with the highest
+/// `line_boost`. Ties go to the smallest position, so the player is
+/// deterministic and can serve as a fixed benchmark baseline.
+pub fn heuristic_select(plateau: &Plateau, tile: &Tile, boost: f64) -> usize {
+    let legal = get_legal_moves(plateau);
+    let completing: Vec<usize> = find_line_completing_positions(plateau, tile)
+        .into_iter()
+        .filter(|pos| legal.contains(pos))
+        .collect();
+    let candidates = if completing.is_empty() { legal } else { completing };
+
+    let mut best_pos = candidates.iter().copied().min().unwrap_or(0);
+    let mut best_score = f64::NEG_INFINITY;
+    let mut sorted = candidates;
+    sorted.sort_unstable();
+    for pos in sorted {
+        let score = line_boost(plateau, tile, pos, boost);
+        if score > best_score {
+            best_score = score;
+            best_pos = pos;
+        }
+    }
+    best_pos
+}
+
+/// Play a full game with `heuristic_select` on a fixed tile sequence.
+pub fn play_heuristic_game(tiles: &[Tile], boost: f64) -> Plateau {
+    let mut plateau = create_plateau_empty();
+    for tile in tiles {
+        if get_legal_moves(&plateau).is_empty() {
+            break;
+        }
+        let pos = heuristic_select(&plateau, tile, boost);
+        plateau.tiles[pos] = *tile;
+    }
+    plateau
+}
+
+/// Compute masked GT logits for a board state (occupied positions at `-inf`).
+pub fn gt_masked_logits(
     plateau: &Plateau,
     tile: &Tile,
     deck: &Deck,
@@ -178,6 +238,71 @@ pub fn gt_boosted_select(
         .unwrap()
 }
 
+/// Min-max normalize to [0, 1]; all zeros when every value is equal.
+fn min_max_normalize(values: &[f64]) -> Vec<f64> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|&v| if range > 0.0 { (v - min) / range } else { 0.0 })
+        .collect()
+}
+
+/// GT logits + line_boost, blended with the immediate `result` gain of each
+/// placement → argmax. No rollouts, so as cheap as `gt_boosted_select`.
+///
+/// Both signals are min-max normalized over the legal moves; the score is
+/// `policy + greedy_weight × greedy`. With `greedy_weight = 0` this picks the
+/// same position as `gt_boosted_select`.
+pub fn gt_greedy_blend_select(
+    plateau: &Plateau,
+    tile: &Tile,
+    deck: &Deck,
+    turn: usize,
+    policy_net: &GraphTransformerPolicyNet,
+    boost: f64,
+    greedy_weight: f64,
+) -> usize {
+    let legal = get_legal_moves(plateau);
+    if legal.len() <= 1 {
+        return legal.first().copied().unwrap_or(0);
+    }
+
+    let masked = gt_masked_logits(plateau, tile, deck, turn, policy_net);
+    let logit_values: Vec<f64> = Vec::<f64>::try_from(&masked).unwrap();
+
+    let base_score = result(plateau);
+    let policy_scores: Vec<f64> = legal
+        .iter()
+        .map(|&pos| logit_values[pos] + line_boost(plateau, tile, pos, boost))
+        .collect();
+    let greedy_gains: Vec<f64> = legal
+        .iter()
+        .map(|&pos| {
+            let mut next = plateau.clone();
+            next.tiles[pos] = *tile;
+            (result(&next) - base_score) as f64
+        })
+        .collect();
+
+    legal[blend_argmax(&policy_scores, &greedy_gains, greedy_weight)]
+}
+
+/// Index of the best `policy + greedy_weight × greedy` blend, both signals
+/// min-max normalized. NaN scores compare as ties instead of panicking.
+fn blend_argmax(policy_scores: &[f64], greedy_gains: &[f64], greedy_weight: f64) -> usize {
+    let policy_norm = min_max_normalize(policy_scores);
+    let greedy_norm = min_max_normalize(greedy_gains);
+    (0..policy_scores.len())
+        .max_by(|&a, &b| {
+            let sa = policy_norm[a] + greedy_weight * greedy_norm[a];
+            let sb = policy_norm[b] + greedy_weight * greedy_norm[b];
+            sa.partial_cmp(&sb).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap()
+}
+
 /// Rollout a game from a partial state using GT + line_boost as policy.
 ///
 /// Tiles are drawn randomly from the remaining deck. Returns the final score.
@@ -477,15 +602,6 @@ pub fn gt_beam_rollout_select(
 
 // ─── V1-row ideal position helpers ─────────────────────────────
 
-/// Horizontal rows: positions belonging to each row.
-const ROWS: [&[usize]; 5] = [
-    &[0, 1, 2],
-    &[3, 4, 5, 6],
-    &[7, 8, 9, 10, 11],
-    &[12, 13, 14, 15],
-    &[16, 17, 18],
-];
-
 /// Target v1 for each row: row0=1, row1=5, row2=9, row3=5, row4=1.
 const ROW_TARGET_V1: [i32; 5] = [1, 5, 9, 5, 1];
 
@@ -615,3 +731,108 @@ pub fn gt_beam_v1_select(
 
     best_pos
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_argmax_survives_nan_logits() {
+        assert_eq!(blend_argmax(&[0.0, 2.0, 1.0], &[0.0, 0.0, 6.0], 0.0), 1);
+        assert_eq!(blend_argmax(&[0.0, 2.0, 1.0], &[0.0, 0.0, 6.0], 1.0), 2);
+
+        // A NaN logit stays NaN after normalization; it used to panic here
+        let best = blend_argmax(&[f64::NAN, 1.0, 3.0], &[0.0, 0.0, 0.0], 1.0);
+        assert!(best < 3);
+    }
+
+    #[test]
+    fn test_heuristic_completes_line_when_available() {
+        let mut plateau = create_plateau_empty();
+        // Row 0 (v1): positions 0,1 hold v1=9, position 2 completes it
+        plateau.tiles[0] = Tile(9, 2, 3);
+        plateau.tiles[1] = Tile(9, 6, 4);
+        // Distractor: diagonal v3 (7,12,16) two-thirds built with v3=8
+        plateau.tiles[7] = Tile(1, 7, 8);
+        plateau.tiles[12] = Tile(5, 2, 8);
+
+        let tile = Tile(9, 7, 3);
+        assert_eq!(find_line_completing_positions(&plateau, &tile), vec![2]);
+        assert_eq!(heuristic_select(&plateau, &tile, 3.0), 2);
+    }
+
+    #[test]
+    fn test_heuristic_prefers_most_valuable_completion() {
+        let mut plateau = create_plateau_empty();
+        // v1=1 row (0,1,2) missing position 2
+        plateau.tiles[0] = Tile(1, 2, 3);
+        plateau.tiles[1] = Tile(1, 6, 4);
+        // v3=8 diagonal (7,12,16) missing position 16
+        plateau.tiles[7] = Tile(5, 7, 8);
+        plateau.tiles[12] = Tile(9, 2, 8);
+
+        let tile = Tile(1, 6, 8);
+        let mut completing = find_line_completing_positions(&plateau, &tile);
+        completing.sort_unstable();
+        assert_eq!(completing, vec![2, 16]);
+        assert_eq!(heuristic_select(&plateau, &tile, 3.0), 16);
+    }
+
+    #[test]
+    fn test_greedy_blend_zero_weight_matches_boosted_select() {
+        use crate::game::create_deck::create_deck;
+        use tch::{nn, Device};
+
+        tch::manual_seed(3);
+        let vs = nn::VarStore::new(Device::Cpu);
+        let policy_net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 2, 0.0);
+
+        let tiles: Vec<Tile> = get_available_tiles(&create_deck())
+            .into_iter()
+            .take(19)
+            .collect();
+        let mut plateau = create_plateau_empty();
+        let mut deck = create_deck();
+        for (turn, tile) in tiles.iter().enumerate() {
+            let expected = gt_boosted_select(&plateau, tile, &deck, turn, &policy_net, 3.0);
+            let blended =
+                gt_greedy_blend_select(&plateau, tile, &deck, turn, &policy_net, 3.0, 0.0);
+            assert_eq!(blended, expected, "turn {}", turn);
+
+            plateau.tiles[blended] = *tile;
+            deck = replace_tile_in_deck(&deck, tile);
+        }
+    }
+
+    #[test]
+    fn test_greedy_blend_takes_immediate_completion_with_high_weight() {
+        use crate::game::create_deck::create_deck;
+        use tch::{nn, Device};
+
+        tch::manual_seed(3);
+        let vs = nn::VarStore::new(Device::Cpu);
+        let policy_net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 2, 0.0);
+
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(9, 2, 3);
+        plateau.tiles[1] = Tile(9, 6, 4);
+        let tile = Tile(9, 7, 8);
+        let deck = replace_tile_in_deck(&create_deck(), &tile);
+
+        let pos = gt_greedy_blend_select(&plateau, &tile, &deck, 2, &policy_net, 0.0, 100.0);
+        assert_eq!(pos, 2);
+    }
+
+    #[test]
+    fn test_heuristic_game_fills_board() {
+        let tiles: Vec<Tile> = crate::game::create_deck::create_deck()
+            .tiles()
+            .iter()
+            .copied()
+            .filter(|t| *t != Tile(0, 0, 0))
+            .take(19)
+            .collect();
+        let plateau = play_heuristic_game(&tiles, 3.0);
+        assert!(plateau.tiles.iter().all(|t| *t != Tile(0, 0, 0)));
+    }
+}
@@ -1,29 +1,8 @@
+use crate::game::board_geometry::{line_value, lines_through, LINES};
 use crate::game::plateau::Plateau;
 use crate::game::tile::Tile;
 use crate::scoring::scoring::compute_alignment_score;
 
-/// Line definitions: (positions, band_index)
-const LINES: &[(&[usize], usize)] = &[
-    // Horizontal lines (band 0)
-    (&[0, 1, 2], 0),
-    (&[3, 4, 5, 6], 0),
-    (&[7, 8, 9, 10, 11], 0),
-    (&[12, 13, 14, 15], 0),
-    (&[16, 17, 18], 0),
-    // Diagonal type 1 (band 1)
-    (&[0, 3, 7], 1),
-    (&[1, 4, 8, 12], 1),
-    (&[2, 5, 9, 13, 16], 1),
-    (&[6, 10, 14, 17], 1),
-    (&[11, 15, 18], 1),
-    // Diagonal type 2 (band 2)
-    (&[7, 12, 16], 2),
-    (&[3, 8, 13, 17], 2),
-    (&[0, 4, 9, 14, 18], 2),
-    (&[1, 5, 10, 15], 2),
-    (&[2, 6, 11], 2),
-];
-
 // Version simplifiée qui se concentre sur les positions stratégiques
 pub fn calculate_line_completion_bonus(_plateau: &Plateau, position: usize, tile: &Tile) -> f64 {
     let mut bonus = 0.0;
@@ -73,6 +52,7 @@ pub fn enhanced_position_evaluation(
     position: usize,
     tile: &Tile,
     current_turn: usize,
+    wasted_tile_weight: f64,
 ) -> f64 {
     // Score de base alignement (votre fonction existante)
     let alignment_score = compute_alignment_score(plateau, position, tile);
@@ -106,7 +86,37 @@ pub fn enhanced_position_evaluation(
     // Multi-line potential is useful but empirical bonuses were misleading
     let multi_line_bonus = calculate_multi_line_potential(position, tile);
 
-    alignment_score + position_bonus + position_malus + completion_bonus + multi_line_bonus
+    // Malus pour les valeurs de la tuile enterrées sur des lignes déjà gâchées
+    let wasted_malus = -wasted_tile_weight * wasted_tile_value(plateau, position, tile);
+
+    alignment_score
+        + position_bonus
+        + position_malus
+        + completion_bonus
+        + multi_line_bonus
+        + wasted_malus
+}
+
+/// Sum of the tile's values that can never score once placed at `position`:
+/// for each of the three lines through it, the tile's value on that line if
+/// another tile of the line already shows a different value.
+pub fn wasted_tile_value(plateau: &Plateau, position: usize, tile: &Tile) -> f64 {
+    lines_through(position)
+        .iter()
+        .map(|&line| {
+            let (positions, direction) = LINES[line];
+            let value = line_value(tile, direction);
+            let spoiled = positions.iter().any(|&p| {
+                let other = plateau.tiles[p];
+                p != position && other != Tile(0, 0, 0) && line_value(&other, direction) != value
+            });
+            if spoiled {
+                value as f64
+            } else {
+                0.0
+            }
+        })
+        .sum()
 }
 
 /// Calculate bonus for positions that participate in multiple lines
@@ -115,7 +125,7 @@ fn calculate_multi_line_potential(position: usize, tile: &Tile) -> f64 {
     let mut line_count = 0;
 
     // Count how many lines this position belongs to
-    for (line_positions, band_idx) in LINES {
+    for (line_positions, band_idx) in &LINES {
         if !line_positions.contains(&position) {
             continue;
         }
@@ -135,3 +145,30 @@ fn calculate_multi_line_potential(position: usize, tile: &Tile) -> f64 {
         _ => 0.0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_wasted_tile_penalty_prefers_live_line_for_a_nine() {
+        // A 1 spoils the line [3, 4, 5, 6] for 9s; [7..=11] is still empty
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[3] = Tile(1, 2, 3);
+        let nine = Tile(9, 6, 8);
+        let (dead, live) = (6, 9);
+
+        assert_eq!(wasted_tile_value(&plateau, dead, &nine), 9.0);
+        assert_eq!(wasted_tile_value(&plateau, live, &nine), 0.0);
+        // Low values lose less on the same spoiled line
+        assert_eq!(wasted_tile_value(&plateau, dead, &Tile(5, 6, 8)), 5.0);
+
+        let eval = |position: usize, weight: f64| {
+            enhanced_position_evaluation(&plateau, position, &nine, 10, weight)
+        };
+        // Alignment alone leans toward the spoiled line (it holds a tile)
+        assert!(eval(dead, 0.0) > eval(live, 0.0));
+        assert!(eval(live, 0.5) > eval(dead, 0.5));
+    }
+}
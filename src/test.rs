@@ -142,7 +142,7 @@ pub(crate) mod tests {
     use crate::game::simulate_game::simulate_games;
     use crate::mcts::mcts_node::MCTSNode;
     use crate::neural::tensor_conversion::convert_plateau_to_tensor;
-    use crate::scoring::scoring::result;
+    use crate::scoring::scoring::{count_line_completions, result};
     use rand::Rng;
 
     #[test]
@@ -459,6 +459,21 @@ pub(crate) mod tests {
         assert_eq!(point, 9);
     }
 
+    #[test]
+    fn test_count_line_completions() {
+        let deck = create_deck();
+        let mut plateau = create_plateau_empty();
+        assert_eq!(count_line_completions(&plateau), 0);
+
+        placer_tile(&mut plateau, deck.tiles[9].clone(), 0);
+        placer_tile(&mut plateau, deck.tiles[10].clone(), 1);
+        assert_eq!(count_line_completions(&plateau), 0);
+
+        placer_tile(&mut plateau, deck.tiles[11].clone(), 2);
+        assert_eq!(result(&plateau), 15);
+        assert_eq!(count_line_completions(&plateau), 1);
+    }
+
     #[test]
     fn test_get_legal_moves() {
         let mut state = create_game_state();
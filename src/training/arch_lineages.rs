@@ -0,0 +1,366 @@
+//! Co-training of several policy architectures on shared self-play data.
+//!
+//! Each lineage (one per architecture, e.g. CNN and Graph Transformer) plays
+//! the same tile sequences with its own policy. The games of all lineages are
+//! pooled, the top fraction by score kept, and every lineage trains on that
+//! pool encoded for its own architecture. Lineages are then benchmarked on
+//! shared sequences, and `<data-dir>/arch_comparison.csv` gets one row per
+//! generation with each architecture's score and the leader.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use tch::{nn, nn::OptimizerConfig, Device, Kind, Tensor};
+
+use crate::game::create_deck::create_deck;
+use crate::game::deck::Deck;
+use crate::game::get_legal_moves::get_legal_moves;
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::remove_tile_from_deck::{get_available_tiles, replace_tile_in_deck};
+use crate::game::tile::Tile;
+use crate::mcts::algorithm::convert_plateau_by_arch;
+use crate::neural::manager::NNArchitecture;
+use crate::neural::model_io::save_varstore;
+use crate::neural::policy_value_net::PolicyNet;
+use crate::scoring::scoring::result;
+
+pub const COMPARISON_FILE: &str = "arch_comparison.csv";
+
+#[derive(Debug, Clone)]
+pub struct CoTrainConfig {
+    pub generations: usize,
+    /// Shared tile sequences played by every lineage each generation
+    pub games_per_gen: usize,
+    /// Shared benchmark sequences per generation
+    pub eval_games: usize,
+    /// Fraction of the pooled games kept for training
+    pub top_fraction: f64,
+    pub epochs: usize,
+    pub batch_size: usize,
+    pub lr: f64,
+    pub seed: u64,
+}
+
+/// One architecture's policy, trained and benchmarked on its own.
+pub struct Lineage {
+    pub arch: NNArchitecture,
+    pub save_path: PathBuf,
+    /// Best benchmark average so far (the model saved at `save_path`)
+    pub best_score: f64,
+    vs: nn::VarStore,
+    policy_net: PolicyNet,
+}
+
+/// A move of a pooled game, kept architecture-agnostic until training.
+struct PooledMove {
+    plateau: Plateau,
+    tile: Tile,
+    deck: Deck,
+    turn: usize,
+    position: usize,
+    weight: f32,
+}
+
+/// Benchmark of one generation: average score per lineage, in lineage order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    pub generation: usize,
+    pub scores: Vec<(String, f64)>,
+    pub leader: String,
+}
+
+impl Lineage {
+    pub fn new(arch: NNArchitecture, device: Device, save_path: impl Into<PathBuf>) -> Self {
+        let vs = nn::VarStore::new(device);
+        let policy_net = PolicyNet::new(&vs, arch.input_dim(), arch);
+        Self {
+            arch,
+            save_path: save_path.into(),
+            best_score: f64::NEG_INFINITY,
+            vs,
+            policy_net,
+        }
+    }
+
+    /// Features of one state with a batch dimension of 1.
+    fn features(&self, plateau: &Plateau, tile: &Tile, deck: &Deck, turn: usize) -> Tensor {
+        let feat = convert_plateau_by_arch(self.arch, plateau, tile, deck, turn, 19);
+        let feat = match self.arch {
+            NNArchitecture::GraphTransformer => feat.unsqueeze(0),
+            _ => feat,
+        };
+        feat.to_device(self.vs.device())
+    }
+
+    /// Greedy move of this lineage's policy among the empty cells.
+    fn select_move(&self, plateau: &Plateau, tile: &Tile, deck: &Deck, turn: usize) -> usize {
+        let feat = self.features(plateau, tile, deck, turn);
+        let logits = tch::no_grad(|| self.policy_net.forward(&feat, false))
+            .squeeze_dim(0)
+            .to_device(Device::Cpu);
+        (logits + illegal_mask(plateau))
+            .argmax(-1, false)
+            .int64_value(&[]) as usize
+    }
+
+    /// Play `tiles` in order; returns the moves and the final score.
+    fn play(&self, tiles: &[Tile]) -> (Vec<PooledMove>, i32) {
+        let mut plateau = create_plateau_empty();
+        let mut deck = create_deck();
+        let mut moves = Vec::with_capacity(tiles.len());
+        for (turn, tile) in tiles.iter().enumerate() {
+            if get_legal_moves(&plateau).is_empty() {
+                break;
+            }
+            deck = replace_tile_in_deck(&deck, tile);
+            let position = self.select_move(&plateau, tile, &deck, turn);
+            moves.push(PooledMove {
+                plateau: plateau.clone(),
+                tile: *tile,
+                deck: deck.clone(),
+                turn,
+                position,
+                weight: 1.0,
+            });
+            plateau.tiles[position] = *tile;
+        }
+        (moves, result(&plateau))
+    }
+
+    /// One epoch of weighted cross-entropy on the pool. Returns the average loss.
+    fn train_epoch(
+        &self,
+        opt: &mut nn::Optimizer,
+        pool: &[PooledMove],
+        batch_size: usize,
+        rng: &mut StdRng,
+    ) -> f64 {
+        let mut indices: Vec<usize> = (0..pool.len()).collect();
+        indices.shuffle(rng);
+        let n_batches = pool.len() / batch_size;
+        if n_batches == 0 {
+            return 0.0;
+        }
+
+        let device = self.vs.device();
+        let mut total_loss = 0.0;
+        for batch in indices.chunks_exact(batch_size) {
+            let features: Vec<Tensor> = batch
+                .iter()
+                .map(|&i| {
+                    let m = &pool[i];
+                    self.features(&m.plateau, &m.tile, &m.deck, m.turn)
+                })
+                .collect();
+            let masks: Vec<Tensor> = batch
+                .iter()
+                .map(|&i| illegal_mask(&pool[i].plateau))
+                .collect();
+            let targets: Vec<i64> = batch.iter().map(|&i| pool[i].position as i64).collect();
+            let weights: Vec<f32> = batch.iter().map(|&i| pool[i].weight).collect();
+
+            let logits = self.policy_net.forward(&Tensor::cat(&features, 0), true);
+            let log_probs =
+                (logits + Tensor::stack(&masks, 0).to_device(device)).log_softmax(-1, Kind::Float);
+            let targets = Tensor::from_slice(&targets).to_device(device);
+            let weights = Tensor::from_slice(&weights).to_device(device);
+            let per_sample_loss = -log_probs
+                .gather(1, &targets.unsqueeze(1), false)
+                .squeeze_dim(1);
+            let loss = (&per_sample_loss * &weights).sum(Kind::Float) / weights.sum(Kind::Float);
+
+            opt.backward_step(&loss);
+            total_loss += f64::try_from(&loss).unwrap();
+        }
+        total_loss / n_batches as f64
+    }
+}
+
+/// `-inf` on the occupied cells, 0 elsewhere.
+fn illegal_mask(plateau: &Plateau) -> Tensor {
+    let mask: Vec<f32> = plateau
+        .tiles
+        .iter()
+        .map(|t| {
+            if *t == Tile(0, 0, 0) {
+                0.0
+            } else {
+                f32::NEG_INFINITY
+            }
+        })
+        .collect();
+    Tensor::from_slice(&mask)
+}
+
+/// 19 tiles drawn from a shuffled deck.
+fn random_tile_sequence(rng: &mut StdRng) -> Vec<Tile> {
+    let mut tiles = get_available_tiles(&create_deck());
+    tiles.shuffle(rng);
+    tiles.truncate(19);
+    tiles
+}
+
+/// Save path of the `arch` lineage derived from `save_path`:
+/// `gt_exit_best.safetensors` → `gt_exit_best_cnn.safetensors`.
+pub fn lineage_save_path(save_path: &Path, arch: NNArchitecture) -> PathBuf {
+    let stem = save_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("model");
+    let file_name = match save_path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, arch, ext),
+        None => format!("{}_{}", stem, arch),
+    };
+    save_path.with_file_name(file_name)
+}
+
+/// Append `row` to the comparison at `path`, writing the header on creation.
+fn append_comparison(path: &Path, row: &ComparisonRow) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let write_header = !path.exists() || fs::metadata(path)?.len() == 0;
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut wtr = csv::Writer::from_writer(file);
+    if write_header {
+        let mut header = vec!["generation".to_string()];
+        header.extend(row.scores.iter().map(|(arch, _)| arch.clone()));
+        header.push("leader".to_string());
+        wtr.write_record(&header)?;
+    }
+    let mut record = vec![row.generation.to_string()];
+    record.extend(row.scores.iter().map(|(_, score)| format!("{:.2}", score)));
+    record.push(row.leader.clone());
+    wtr.write_record(&record)?;
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Run `config.generations` generations over `lineages`, appending the
+/// comparison rows to `<data_dir>/arch_comparison.csv`. Each lineage saves
+/// its model to its own `save_path` whenever its benchmark improves.
+pub fn run_co_training(
+    lineages: &mut [Lineage],
+    config: &CoTrainConfig,
+    data_dir: &Path,
+) -> Result<Vec<ComparisonRow>, Box<dyn Error>> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let comparison_path = data_dir.join(COMPARISON_FILE);
+    let mut rows = Vec::with_capacity(config.generations);
+
+    for generation in 0..config.generations {
+        // 1. Shared sequences, model-specific moves, pooled games
+        let sequences: Vec<Vec<Tile>> = (0..config.games_per_gen)
+            .map(|_| random_tile_sequence(&mut rng))
+            .collect();
+        let mut games: Vec<(Vec<PooledMove>, i32)> = Vec::new();
+        for lineage in lineages.iter() {
+            games.extend(sequences.iter().map(|tiles| lineage.play(tiles)));
+        }
+
+        // 2. Keep the top fraction, weighted by score
+        let mut scores: Vec<i32> = games.iter().map(|(_, score)| *score).collect();
+        scores.sort_unstable();
+        let threshold_idx = ((1.0 - config.top_fraction) * scores.len() as f64) as usize;
+        let threshold = scores
+            .get(threshold_idx.min(scores.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or(0);
+        let pool: Vec<PooledMove> = games
+            .into_iter()
+            .filter(|(_, score)| *score >= threshold)
+            .flat_map(|(moves, score)| {
+                let weight = (score.max(0) as f64 / 100.0).powf(1.5) as f32;
+                moves.into_iter().map(move |m| PooledMove { weight, ..m })
+            })
+            .collect();
+
+        // 3. Train and benchmark each lineage on its own
+        let eval_sequences: Vec<Vec<Tile>> = (0..config.eval_games)
+            .map(|_| random_tile_sequence(&mut rng))
+            .collect();
+        let mut generation_scores = Vec::with_capacity(lineages.len());
+        for lineage in lineages.iter_mut() {
+            let mut opt = nn::Adam::default().build(&lineage.vs, config.lr)?;
+            for _ in 0..config.epochs {
+                lineage.train_epoch(&mut opt, &pool, config.batch_size, &mut rng);
+            }
+
+            let total: i32 = eval_sequences
+                .iter()
+                .map(|tiles| lineage.play(tiles).1)
+                .sum();
+            let avg = total as f64 / eval_sequences.len().max(1) as f64;
+            if avg > lineage.best_score {
+                lineage.best_score = avg;
+                save_varstore(&lineage.vs, &lineage.save_path)?;
+            }
+            generation_scores.push((lineage.arch.to_string(), avg));
+        }
+
+        let leader = generation_scores
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(arch, _)| arch.clone())
+            .unwrap_or_default();
+        let row = ComparisonRow {
+            generation,
+            scores: generation_scores,
+            leader,
+        };
+        append_comparison(&comparison_path, &row)?;
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_two_arch_loop_saves_each_lineage_and_compares_each_generation() {
+        let dir = tempdir().unwrap();
+        let save_path = dir.path().join("policy.safetensors");
+        let mut lineages: Vec<Lineage> = [NNArchitecture::Cnn, NNArchitecture::GraphTransformer]
+            .into_iter()
+            .map(|arch| Lineage::new(arch, Device::Cpu, lineage_save_path(&save_path, arch)))
+            .collect();
+        let config = CoTrainConfig {
+            generations: 2,
+            games_per_gen: 3,
+            eval_games: 2,
+            top_fraction: 0.5,
+            epochs: 1,
+            batch_size: 8,
+            lr: 1e-3,
+            seed: 7,
+        };
+
+        let rows = run_co_training(&mut lineages, &config, dir.path()).unwrap();
+
+        assert!(dir.path().join("policy_cnn.safetensors").exists());
+        assert!(dir
+            .path()
+            .join("policy_graph-transformer.safetensors")
+            .exists());
+
+        assert_eq!(rows.len(), 2);
+        for (generation, row) in rows.iter().enumerate() {
+            assert_eq!(row.generation, generation);
+            let archs: Vec<&str> = row.scores.iter().map(|(a, _)| a.as_str()).collect();
+            assert_eq!(archs, vec!["cnn", "graph-transformer"]);
+            assert!(archs.contains(&row.leader.as_str()));
+        }
+
+        let csv = fs::read_to_string(dir.path().join(COMPARISON_FILE)).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "generation,cnn,graph-transformer,leader");
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,171 @@
+//! Machine-readable benchmark results, for regression gating.
+//!
+//! `benchmark_strategies` and `tournament` print their results for humans;
+//! with `--json-out` they also write a [`BenchmarkReport`]. `regression_check`
+//! compares two reports and fails when a model's average drops by more than a
+//! tolerance, so automation can refuse a silently weaker model.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Score summary of one model (or strategy) over the games of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelSummary {
+    pub name: String,
+    /// Weights the model was loaded from
+    pub model_path: String,
+    pub games: usize,
+    pub avg: f64,
+    pub median: i32,
+    pub min: i32,
+    pub max: i32,
+    /// Games won against the reference of the run (see [`BenchmarkReport::tool`])
+    pub wins: usize,
+}
+
+impl ModelSummary {
+    pub fn from_scores(name: &str, model_path: &str, scores: &[i32], wins: usize) -> Self {
+        let mut sorted = scores.to_vec();
+        sorted.sort_unstable();
+        let avg = if sorted.is_empty() {
+            0.0
+        } else {
+            sorted.iter().sum::<i32>() as f64 / sorted.len() as f64
+        };
+        Self {
+            name: name.to_string(),
+            model_path: model_path.to_string(),
+            games: sorted.len(),
+            avg,
+            median: sorted.get(sorted.len() / 2).copied().unwrap_or(0),
+            min: sorted.first().copied().unwrap_or(0),
+            max: sorted.last().copied().unwrap_or(0),
+            wins,
+        }
+    }
+}
+
+/// Results of one benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    /// Binary that produced the report. `wins` counts games won against the
+    /// first strategy for `benchmark_strategies`, against every other model
+    /// for `tournament`.
+    pub tool: String,
+    pub seed: u64,
+    pub num_games: usize,
+    pub models: Vec<ModelSummary>,
+}
+
+impl BenchmarkReport {
+    pub fn save_json(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load_json(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Average of one model in the baseline and candidate reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDelta {
+    pub name: String,
+    pub baseline_avg: f64,
+    pub candidate_avg: f64,
+    /// `candidate_avg - baseline_avg`
+    pub delta: f64,
+    /// The candidate lost more than the tolerance
+    pub regressed: bool,
+}
+
+/// Compare the models present in both reports (matched by name, in baseline
+/// order). A model regresses when its average drops by more than `min_delta`
+/// points. Errors when the reports share no model.
+pub fn compare_reports(
+    baseline: &BenchmarkReport,
+    candidate: &BenchmarkReport,
+    min_delta: f64,
+) -> Result<Vec<ModelDelta>, String> {
+    let deltas: Vec<ModelDelta> = baseline
+        .models
+        .iter()
+        .filter_map(|base| {
+            let cand = candidate.models.iter().find(|m| m.name == base.name)?;
+            let delta = cand.avg - base.avg;
+            Some(ModelDelta {
+                name: base.name.clone(),
+                baseline_avg: base.avg,
+                candidate_avg: cand.avg,
+                delta,
+                regressed: delta < -min_delta,
+            })
+        })
+        .collect();
+    if deltas.is_empty() {
+        return Err("baseline and candidate reports have no model in common".to_string());
+    }
+    Ok(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn report(models: &[(&str, &[i32])]) -> BenchmarkReport {
+        BenchmarkReport {
+            tool: "benchmark_strategies".to_string(),
+            seed: 42,
+            num_games: 4,
+            models: models
+                .iter()
+                .map(|(name, scores)| ModelSummary::from_scores(name, "gt.safetensors", scores, 0))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_summary_and_json_round_trip() {
+        let summary =
+            ModelSummary::from_scores("GT Direct", "gt.safetensors", &[150, 90, 120, 100], 2);
+        assert_eq!(summary.games, 4);
+        assert_eq!(summary.avg, 115.0);
+        assert_eq!((summary.median, summary.min, summary.max), (120, 90, 150));
+        assert_eq!(summary.wins, 2);
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("runs/results.json");
+        let path = path.to_str().unwrap();
+        let original = report(&[("GT Direct", &[150, 90, 120, 100])]);
+        original.save_json(path).unwrap();
+        assert_eq!(BenchmarkReport::load_json(path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_regression_beyond_tolerance_only() {
+        let baseline = report(&[("GT Direct", &[150, 150]), ("GT Beam", &[160, 160])]);
+        let candidate = report(&[("GT Beam", &[150, 150]), ("GT Direct", &[148, 148])]);
+
+        let deltas = compare_reports(&baseline, &candidate, 5.0).unwrap();
+        let names: Vec<&str> = deltas.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["GT Direct", "GT Beam"]);
+        assert_eq!(deltas[0].delta, -2.0);
+        assert!(!deltas[0].regressed);
+        assert_eq!(deltas[1].delta, -10.0);
+        assert!(deltas[1].regressed);
+
+        // A zero tolerance flags any drop, not a tie
+        let same = compare_reports(&baseline, &baseline, 0.0).unwrap();
+        assert!(same.iter().all(|d| !d.regressed));
+
+        let unrelated = report(&[("Other", &[100])]);
+        assert!(compare_reports(&baseline, &unrelated, 5.0).is_err());
+    }
+}
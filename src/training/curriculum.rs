@@ -0,0 +1,262 @@
+//! Search-budget curriculum for iterative self-play training.
+//!
+//! Early generations are weak, so spending a large MCTS/rollout budget on them
+//! wastes time. A schedule such as `50,100,150` gives generation `i` the `i`-th
+//! budget and keeps the last value once the schedule is exhausted.
+//!
+//! Exploration knobs (explored turns, sampling temperature) follow a
+//! [`ParamSchedule`]: later generations have a sharper policy and can exploit
+//! more. Within a game, [`TemperatureSchedule`] sets the sampling temperature
+//! of each turn.
+
+/// Per-generation search budget (simulations or rollouts).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimSchedule {
+    budgets: Vec<usize>,
+}
+
+impl SimSchedule {
+    /// Parse a comma-separated list of budgets (`"50,100,150"`).
+    /// An empty string yields an empty schedule, i.e. a flat budget.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let budgets = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse::<usize>()
+                    .map_err(|_| format!("Invalid budget '{}' in sim schedule", s))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if budgets.contains(&0) {
+            return Err("Sim schedule budgets must be positive".to_string());
+        }
+
+        Ok(Self { budgets })
+    }
+
+    /// Budget for `generation` (0-based), falling back to `default` for an empty schedule.
+    pub fn budget_for(&self, generation: usize, default: usize) -> usize {
+        match self.budgets.last() {
+            Some(&last) => self.budgets.get(generation).copied().unwrap_or(last),
+            None => default,
+        }
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.budgets.is_empty()
+    }
+}
+
+/// Per-generation value of an exploration knob (explored turns, temperature).
+///
+/// Either a comma list (`"8,6,4"`, last value kept once exhausted) or an
+/// exponential decay `"decay:START:FACTOR[:MIN]"`, i.e.
+/// `max(START * FACTOR^generation, MIN)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ParamSchedule {
+    /// No schedule: every generation uses the default value
+    #[default]
+    Flat,
+    List(Vec<f64>),
+    Decay {
+        start: f64,
+        factor: f64,
+        min: f64,
+    },
+}
+
+impl ParamSchedule {
+    /// Parse a comma list or a `decay:` formula. An empty string yields a flat schedule.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Ok(Self::Flat);
+        }
+
+        let number = |s: &str| -> Result<f64, String> {
+            s.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("Invalid value '{}' in schedule", s.trim()))
+        };
+
+        if let Some(formula) = spec.strip_prefix("decay:") {
+            let parts: Vec<&str> = formula.split(':').collect();
+            if !(2..=3).contains(&parts.len()) {
+                return Err(format!(
+                    "Invalid decay schedule '{}' (expected decay:START:FACTOR[:MIN])",
+                    spec
+                ));
+            }
+            let start = number(parts[0])?;
+            let factor = number(parts[1])?;
+            let min = parts.get(2).map(|s| number(s)).transpose()?.unwrap_or(0.0);
+            return Ok(Self::Decay { start, factor, min });
+        }
+
+        let values = spec
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(number)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::List(values))
+    }
+
+    /// Value for `generation` (0-based), `default` for a flat schedule.
+    pub fn value_for(&self, generation: usize, default: f64) -> f64 {
+        match self {
+            Self::Flat => default,
+            Self::List(values) => match values.last() {
+                Some(&last) => values.get(generation).copied().unwrap_or(last),
+                None => default,
+            },
+            Self::Decay { start, factor, min } => {
+                (start * factor.powi(generation as i32)).max(*min)
+            }
+        }
+    }
+
+    /// Integer value for `generation` (e.g. a number of turns), rounded.
+    pub fn turns_for(&self, generation: usize, default: usize) -> usize {
+        self.value_for(generation, default as f64).round() as usize
+    }
+
+    pub fn is_flat(&self) -> bool {
+        matches!(self, Self::Flat)
+    }
+}
+
+/// Per-turn sampling temperature of self-play exploration.
+///
+/// `Cutoff` is the plain `--explore-turns`/`--temperature` pair: the opening
+/// turns sample at one temperature (0 = greedy policy move), the rest play the
+/// expert move. `PerTurn` reads a [`ParamSchedule`] keyed by turn instead of
+/// generation (`"1.0,1.0,0.5,0.2,0"`, `"decay:1.0:0.8"`); a temperature of 0
+/// plays the expert move.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureSchedule {
+    Cutoff { turns: usize, temperature: f64 },
+    PerTurn(ParamSchedule),
+}
+
+impl TemperatureSchedule {
+    /// Per-turn schedule from a [`ParamSchedule`] spec, which must not be empty.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match ParamSchedule::parse(spec)? {
+            ParamSchedule::Flat => Err("Empty temperature schedule".to_string()),
+            schedule => Ok(Self::PerTurn(schedule)),
+        }
+    }
+
+    /// Sample the first `turns` turns at `temperature`.
+    pub fn cutoff(turns: usize, temperature: f64) -> Self {
+        Self::Cutoff { turns, temperature }
+    }
+
+    /// Temperature to sample `turn` at, `None` to play the expert move.
+    pub fn temperature_at(&self, turn: usize) -> Option<f64> {
+        match self {
+            Self::Cutoff { turns, temperature } => (turn < *turns).then_some(*temperature),
+            Self::PerTurn(schedule) => {
+                let temperature = schedule.value_for(turn, 0.0);
+                (temperature > 0.0).then_some(temperature)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_applied_per_generation() {
+        let schedule = SimSchedule::parse("50, 100,150").unwrap();
+        assert_eq!(schedule.budget_for(0, 10), 50);
+        assert_eq!(schedule.budget_for(1, 10), 100);
+        assert_eq!(schedule.budget_for(2, 10), 150);
+        // Last budget is kept once the schedule is exhausted
+        assert_eq!(schedule.budget_for(7, 10), 150);
+    }
+
+    #[test]
+    fn test_flat_schedule_reproduces_default_budget() {
+        let schedule = SimSchedule::parse("").unwrap();
+        assert!(schedule.is_flat());
+        for generation in 0..20 {
+            assert_eq!(schedule.budget_for(generation, 10), 10);
+        }
+    }
+
+    #[test]
+    fn test_invalid_schedule_rejected() {
+        assert!(SimSchedule::parse("50,abc").is_err());
+        assert!(SimSchedule::parse("50,0").is_err());
+    }
+
+    #[test]
+    fn test_decay_schedule_reduces_explore_turns() {
+        let schedule = ParamSchedule::parse("decay:10:0.7:2").unwrap();
+        let turns: Vec<usize> = (0..8).map(|g| schedule.turns_for(g, 6)).collect();
+
+        assert_eq!(turns[0], 10);
+        assert!(turns.windows(2).all(|w| w[1] <= w[0]), "{:?}", turns);
+        assert!(turns[1] < turns[0] && turns[3] < turns[1], "{:?}", turns);
+        // The floor is reached and kept
+        assert_eq!(turns[7], 2);
+    }
+
+    #[test]
+    fn test_param_schedule_list_and_flat() {
+        let temperature = ParamSchedule::parse("1.0, 0.5,0.25").unwrap();
+        assert_eq!(temperature.value_for(0, 1.0), 1.0);
+        assert_eq!(temperature.value_for(2, 1.0), 0.25);
+        assert_eq!(temperature.value_for(9, 1.0), 0.25);
+
+        let flat = ParamSchedule::parse("").unwrap();
+        assert!(flat.is_flat());
+        assert_eq!(flat.turns_for(5, 4), 4);
+
+        assert!(ParamSchedule::parse("decay:10").is_err());
+        assert!(ParamSchedule::parse("4,-1").is_err());
+    }
+
+    #[test]
+    fn test_temperature_schedule_is_keyed_by_turn() {
+        let schedule = TemperatureSchedule::parse("1.0, 0.5,0.25,0").unwrap();
+        let temperatures: Vec<Option<f64>> = (0..6).map(|t| schedule.temperature_at(t)).collect();
+        // Temperature 0: no sampling, the expert move is played
+        assert_eq!(
+            temperatures,
+            vec![Some(1.0), Some(0.5), Some(0.25), None, None, None]
+        );
+
+        let decay = TemperatureSchedule::parse("decay:1.0:0.5").unwrap();
+        assert_eq!(decay.temperature_at(0), Some(1.0));
+        assert_eq!(decay.temperature_at(3), Some(0.125));
+
+        assert!(TemperatureSchedule::parse("").is_err());
+        assert!(TemperatureSchedule::parse("1.0,abc").is_err());
+        assert!(TemperatureSchedule::parse("decay:1.0").is_err());
+        assert!(TemperatureSchedule::parse("1.0,-1").is_err());
+    }
+
+    #[test]
+    fn test_cutoff_reproduces_explore_turns() {
+        let schedule = TemperatureSchedule::cutoff(4, 0.8);
+        let temperatures: Vec<Option<f64>> = (0..6).map(|t| schedule.temperature_at(t)).collect();
+        assert_eq!(
+            temperatures,
+            vec![Some(0.8), Some(0.8), Some(0.8), Some(0.8), None, None]
+        );
+        // Greedy opening moves are kept as before
+        assert_eq!(
+            TemperatureSchedule::cutoff(2, 0.0).temperature_at(1),
+            Some(0.0)
+        );
+        assert_eq!(TemperatureSchedule::cutoff(0, 1.0).temperature_at(0), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! Early stopping on the validation loss.
+//!
+//! Training stops once the validation loss has gone `patience` epochs without
+//! beating its best value. The best checkpoint is the one saved when that value
+//! was reached, so stopping never loses it.
+
+/// Best validation loss of a run and the epochs spent since it was reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarlyStopping {
+    patience: Option<usize>,
+    best_val_loss: f64,
+    epochs_without_improvement: usize,
+}
+
+impl EarlyStopping {
+    /// `None` never stops: every epoch runs.
+    pub fn new(patience: Option<usize>) -> Self {
+        Self {
+            patience,
+            best_val_loss: f64::INFINITY,
+            epochs_without_improvement: 0,
+        }
+    }
+
+    /// Record the validation loss of an epoch; `true` when it is a new best.
+    pub fn record(&mut self, val_loss: f64) -> bool {
+        if val_loss < self.best_val_loss {
+            self.best_val_loss = val_loss;
+            self.epochs_without_improvement = 0;
+            true
+        } else {
+            self.epochs_without_improvement += 1;
+            false
+        }
+    }
+
+    /// Whether the last `patience` epochs all failed to improve.
+    pub fn should_stop(&self) -> bool {
+        self.patience
+            .is_some_and(|patience| self.epochs_without_improvement >= patience)
+    }
+
+    pub fn best_val_loss(&self) -> f64 {
+        self.best_val_loss
+    }
+
+    pub fn epochs_without_improvement(&self) -> usize {
+        self.epochs_without_improvement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stops_after_patience_epochs_without_improvement() {
+        let losses = [1.0, 0.8, 0.9, 0.85, 0.7, 0.75, 0.71, 0.72];
+        let run = |patience: Option<usize>| {
+            let mut stopping = EarlyStopping::new(patience);
+            let mut epochs = 0;
+            for &loss in &losses {
+                stopping.record(loss);
+                epochs += 1;
+                if stopping.should_stop() {
+                    break;
+                }
+            }
+            (epochs, stopping.best_val_loss())
+        };
+
+        // 0.7 at epoch 5 resets the count reached after 0.8
+        assert_eq!(run(Some(3)), (8, 0.7));
+        assert_eq!(run(Some(2)), (4, 0.8));
+        assert_eq!(run(None), (losses.len(), 0.7));
+    }
+}
@@ -0,0 +1,181 @@
+//! Elo ratings fitted on round-robin results.
+//!
+//! Ratings follow the Bradley-Terry model: model `i` beats model `j` with
+//! probability `1 / (1 + 10^((R_j - R_i) / 400))`. They are fitted by the
+//! minorization-maximization iteration of Hunter (2004) from every model at
+//! 1500, a draw counting as half a win for each side. Each pairing also gets
+//! one virtual draw, which keeps the ratings finite when a model wins or loses
+//! all its games. Ratings are centered on 1500.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::training::tournament::Pairing;
+
+pub const INITIAL_RATING: f64 = 1500.0;
+const MAX_ITERATIONS: usize = 10_000;
+/// Largest rating change of the last iteration at convergence
+const TOLERANCE: f64 = 1e-6;
+
+/// One row of the leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Rating {
+    pub model: String,
+    pub elo: f64,
+    pub games: usize,
+    /// Wins plus half the draws, over `games`
+    pub score_rate: f64,
+}
+
+/// Ratings of every model of `pairings`, best first.
+pub fn fit_elo(pairings: &[Pairing]) -> Vec<Rating> {
+    let mut names: Vec<String> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for p in pairings {
+        for name in [&p.model_a, &p.model_b] {
+            if !index.contains_key(name) {
+                index.insert(name.clone(), names.len());
+                names.push(name.clone());
+            }
+        }
+    }
+    let n = names.len();
+
+    // points[i][j]: points of i against j, virtual draw included
+    let mut points = vec![vec![0.0; n]; n];
+    let mut games = vec![0usize; n];
+    let mut scored = vec![0.0; n];
+    for p in pairings {
+        let (a, b) = (index[&p.model_a], index[&p.model_b]);
+        let draws = p.draws as f64 / 2.0;
+        points[a][b] += p.wins_a as f64 + draws + 0.5;
+        points[b][a] += p.wins_b as f64 + draws + 0.5;
+        games[a] += p.games;
+        games[b] += p.games;
+        scored[a] += p.wins_a as f64 + draws;
+        scored[b] += p.wins_b as f64 + draws;
+    }
+
+    let to_elo = |strength: &[f64]| -> Vec<f64> {
+        let log_mean = strength.iter().map(|s| s.log10()).sum::<f64>() / n as f64;
+        strength
+            .iter()
+            .map(|s| INITIAL_RATING + 400.0 * (s.log10() - log_mean))
+            .collect()
+    };
+    let mut strength = vec![1.0; n];
+    let mut elo = to_elo(&strength);
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<f64> = (0..n)
+            .map(|i| {
+                let wins: f64 = points[i].iter().sum();
+                let denominator: f64 = (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| (points[i][j] + points[j][i]) / (strength[i] + strength[j]))
+                    .sum();
+                if denominator > 0.0 {
+                    wins / denominator
+                } else {
+                    strength[i]
+                }
+            })
+            .collect();
+        let next_elo = to_elo(&next);
+        let change = elo
+            .iter()
+            .zip(&next_elo)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        strength = next;
+        elo = next_elo;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    let mut ratings: Vec<Rating> = names
+        .into_iter()
+        .enumerate()
+        .map(|(i, model)| Rating {
+            model,
+            elo: elo[i],
+            games: games[i],
+            score_rate: if games[i] > 0 {
+                scored[i] / games[i] as f64
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    ratings.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+    ratings
+}
+
+pub fn save_ratings(ratings: &[Rating], path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut wtr = csv::Writer::from_path(path)?;
+    for rating in ratings {
+        wtr.serialize(rating)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairing(a: &str, b: &str, wins_a: usize, wins_b: usize, draws: usize) -> Pairing {
+        Pairing {
+            model_a: a.to_string(),
+            model_b: b.to_string(),
+            games: wins_a + wins_b + draws,
+            wins_a,
+            wins_b,
+            draws,
+            avg_a: 0.0,
+            avg_b: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_rock_paper_scissors_cycle_rates_everyone_equal() {
+        let cycle = [
+            pairing("A", "B", 80, 20, 0),
+            pairing("B", "C", 80, 20, 0),
+            pairing("C", "A", 80, 20, 0),
+        ];
+        let ratings = fit_elo(&cycle);
+        assert_eq!(ratings.len(), 3);
+        for r in &ratings {
+            assert!((r.elo - INITIAL_RATING).abs() < 1e-3, "{:?}", r);
+            assert!((r.score_rate - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ratings_follow_the_elo_expected_score() {
+        // B scores 76% against C: about 200 points apart
+        let ratings = fit_elo(&[
+            pairing("A", "B", 500, 500, 0),
+            pairing("B", "C", 760, 240, 0),
+            pairing("A", "C", 1500, 480, 20),
+        ]);
+        let elo = |name: &str| ratings.iter().find(|r| r.model == name).unwrap().elo;
+        assert_eq!(ratings.last().unwrap().model, "C");
+        assert!((elo("A") - elo("B")).abs() < 10.0);
+        assert!((elo("B") - elo("C") - 200.0).abs() < 15.0);
+        let mean = ratings.iter().map(|r| r.elo).sum::<f64>() / 3.0;
+        assert!((mean - INITIAL_RATING).abs() < 1e-6);
+
+        // An unbeaten model keeps a finite rating
+        let unbeaten = fit_elo(&[pairing("A", "B", 10, 0, 0)]);
+        assert!(unbeaten.iter().all(|r| r.elo.is_finite()));
+        assert_eq!(unbeaten[0].model, "A");
+    }
+}
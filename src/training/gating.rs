@@ -0,0 +1,149 @@
+//! Acceptance gate for a new training generation.
+//!
+//! Comparing raw averages (`avg_new > avg_old`) lets a lucky benchmark promote
+//! a generation that is no better than the current best, and the next noisy
+//! run may flip back. The gate requires both a minimum average improvement and
+//! a one-sided Welch test (normal approximation, fine for the hundreds of
+//! games a benchmark plays) below a configurable p-value.
+
+/// Acceptance rule. The default reproduces the plain `avg_new > avg_old` check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GatingConfig {
+    /// Minimum improvement of the average score, in points
+    pub min_improvement: f64,
+    /// p-value the improvement must stay under (1.0 = no significance test)
+    pub significance: f64,
+}
+
+impl Default for GatingConfig {
+    fn default() -> Self {
+        Self {
+            min_improvement: 0.0,
+            significance: 1.0,
+        }
+    }
+}
+
+/// Outcome of comparing a candidate's benchmark scores to the current best's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GateDecision {
+    /// `mean(candidate) - mean(best)`
+    pub delta: f64,
+    /// One-sided p-value of "candidate is not better than best"
+    pub p_value: f64,
+    pub accepted: bool,
+}
+
+pub fn mean(scores: &[f64]) -> f64 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<f64>() / scores.len() as f64
+}
+
+fn sample_variance(scores: &[f64]) -> f64 {
+    if scores.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(scores);
+    scores.iter().map(|s| (s - m).powi(2)).sum::<f64>() / (scores.len() - 1) as f64
+}
+
+/// Complementary error function (Abramowitz & Stegun 7.1.26, |error| < 1.5e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let tail = poly * (-z * z).exp();
+    if x >= 0.0 {
+        tail
+    } else {
+        2.0 - tail
+    }
+}
+
+/// One-sided Welch p-value for `mean(candidate) > mean(best)`.
+pub fn improvement_p_value(candidate: &[f64], best: &[f64]) -> f64 {
+    let delta = mean(candidate) - mean(best);
+    let std_error = (sample_variance(candidate) / candidate.len().max(1) as f64
+        + sample_variance(best) / best.len().max(1) as f64)
+        .sqrt();
+    if std_error == 0.0 {
+        return if delta > 0.0 { 0.0 } else { 1.0 };
+    }
+    0.5 * erfc(delta / std_error / std::f64::consts::SQRT_2)
+}
+
+/// Accept `candidate` when its average beats `best` by more than
+/// `min_improvement` and, unless disabled, with `p_value < significance`.
+pub fn evaluate_gate(candidate: &[f64], best: &[f64], config: &GatingConfig) -> GateDecision {
+    let delta = mean(candidate) - mean(best);
+    let p_value = improvement_p_value(candidate, best);
+    let significant = config.significance >= 1.0 || p_value < config.significance;
+    GateDecision {
+        delta,
+        p_value,
+        accepted: delta > config.min_improvement && significant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 300 deterministic benchmark scores around `center` (std ≈ 21 pts).
+    fn benchmark(center: f64) -> Vec<f64> {
+        (0..300)
+            .map(|i| center + 30.0 * (i as f64 * 1.7).sin())
+            .collect()
+    }
+
+    fn strict() -> GatingConfig {
+        GatingConfig {
+            min_improvement: 2.0,
+            significance: 0.05,
+        }
+    }
+
+    #[test]
+    fn test_lucky_generation_rejected() {
+        let best = benchmark(150.0);
+        let lucky = benchmark(151.5);
+
+        // The plain average comparison would accept it
+        assert!(evaluate_gate(&lucky, &best, &GatingConfig::default()).accepted);
+
+        let decision = evaluate_gate(&lucky, &best, &strict());
+        assert!(!decision.accepted);
+        assert!((decision.delta - 1.5).abs() < 1e-9);
+        assert!(decision.p_value > 0.05, "p = {}", decision.p_value);
+    }
+
+    #[test]
+    fn test_clear_improvement_accepted() {
+        let decision = evaluate_gate(&benchmark(158.0), &benchmark(150.0), &strict());
+        assert!(decision.accepted);
+        assert!(decision.p_value < 1e-3, "p = {}", decision.p_value);
+    }
+
+    #[test]
+    fn test_significant_but_too_small_improvement_rejected() {
+        // Low-variance benchmark: +1 pt is significant but below the minimum
+        let best: Vec<f64> = (0..300).map(|i| 150.0 + (i % 3) as f64).collect();
+        let candidate: Vec<f64> = best.iter().map(|s| s + 1.0).collect();
+        let decision = evaluate_gate(&candidate, &best, &strict());
+        assert!(decision.p_value < 0.05);
+        assert!(!decision.accepted);
+    }
+
+    #[test]
+    fn test_p_value_reference_points() {
+        let best = benchmark(150.0);
+        assert!((improvement_p_value(&best, &best) - 0.5).abs() < 1e-6);
+        assert!(improvement_p_value(&benchmark(140.0), &best) > 0.99);
+        // z = 1.645 → p ≈ 0.05
+        assert!((0.5 * erfc(1.645 / std::f64::consts::SQRT_2) - 0.05).abs() < 1e-3);
+    }
+}
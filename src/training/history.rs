@@ -0,0 +1,208 @@
+//! Per-generation training history and its plot-ready JSON report.
+//!
+//! Training runs append one row per evaluated generation to
+//! `<data-dir>/selfplay_history.csv`; [`summarize`] turns it into a
+//! structured report (one array per metric plus computed trends) that
+//! dashboards can consume without re-parsing the CSV.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+pub const HISTORY_FILE: &str = "selfplay_history.csv";
+
+/// One row of `selfplay_history.csv`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationRecord {
+    pub generation: usize,
+    /// Average score of the games generated for training
+    pub expert_avg: f64,
+    /// Benchmark average of the trained generation
+    pub avg_score: f64,
+    /// Loss of the last training epoch
+    pub loss: f64,
+    /// Whether the generation replaced the best model
+    pub accepted: bool,
+    /// Benchmark average of the frozen reference model (`--reference-model`)
+    #[serde(default)]
+    pub reference_avg: Option<f64>,
+    /// Paired win rate of the generation against the reference model
+    #[serde(default)]
+    pub reference_win_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryTrends {
+    /// Best-scoring accepted generation (`None` if none was accepted)
+    pub best_generation: Option<usize>,
+    pub best_avg_score: Option<f64>,
+    /// Least-squares slope of `avg_score`, in points per generation
+    pub improvement_rate: f64,
+    /// Fraction of generations accepted
+    pub acceptance_rate: f64,
+}
+
+/// Plot-ready view of the history: parallel arrays indexed like `generations`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryReport {
+    pub generations: Vec<usize>,
+    pub expert_avgs: Vec<f64>,
+    pub avg_scores: Vec<f64>,
+    pub losses: Vec<f64>,
+    pub accepted: Vec<bool>,
+    pub trends: HistoryTrends,
+}
+
+pub fn history_path(data_dir: &str) -> PathBuf {
+    Path::new(data_dir).join(HISTORY_FILE)
+}
+
+/// Append `record` to the history at `path`, writing the header on creation.
+pub fn append_history(path: &Path, record: &GenerationRecord) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let write_header = !path.exists() || fs::metadata(path)?.len() == 0;
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut wtr = csv::WriterBuilder::new()
+        .has_headers(write_header)
+        .from_writer(file);
+    wtr.serialize(record)?;
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Bytes `record` takes as a history row (header excluded).
+pub fn history_row_bytes(record: &GenerationRecord) -> Result<u64, Box<dyn Error>> {
+    let mut wtr = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    wtr.serialize(record)?;
+    let row = wtr.into_inner().map_err(|e| e.to_string())?;
+    Ok(row.len() as u64)
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<GenerationRecord>, Box<dyn Error>> {
+    let mut rdr = csv::Reader::from_path(path)?;
+    let mut records = Vec::new();
+    for row in rdr.deserialize() {
+        records.push(row?);
+    }
+    Ok(records)
+}
+
+/// Least-squares slope of `ys` against `xs` (0 with fewer than 2 distinct x).
+fn slope(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+    if xs.len() < 2 {
+        return 0.0;
+    }
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let var_x: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    if var_x == 0.0 {
+        return 0.0;
+    }
+    let cov: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    cov / var_x
+}
+
+pub fn summarize(records: &[GenerationRecord]) -> HistoryReport {
+    let generations: Vec<usize> = records.iter().map(|r| r.generation).collect();
+    let avg_scores: Vec<f64> = records.iter().map(|r| r.avg_score).collect();
+
+    let best = records
+        .iter()
+        .filter(|r| r.accepted)
+        .max_by(|a, b| a.avg_score.total_cmp(&b.avg_score));
+    let accepted_count = records.iter().filter(|r| r.accepted).count();
+    let xs: Vec<f64> = generations.iter().map(|&g| g as f64).collect();
+
+    HistoryReport {
+        expert_avgs: records.iter().map(|r| r.expert_avg).collect(),
+        losses: records.iter().map(|r| r.loss).collect(),
+        accepted: records.iter().map(|r| r.accepted).collect(),
+        trends: HistoryTrends {
+            best_generation: best.map(|r| r.generation),
+            best_avg_score: best.map(|r| r.avg_score),
+            improvement_rate: slope(&xs, &avg_scores),
+            acceptance_rate: if records.is_empty() {
+                0.0
+            } else {
+                accepted_count as f64 / records.len() as f64
+            },
+        },
+        generations,
+        avg_scores,
+    }
+}
+
+/// JSON report of the history in `data_dir`.
+pub fn report_json(data_dir: &str) -> Result<String, Box<dyn Error>> {
+    let records = load_history(&history_path(data_dir))?;
+    Ok(serde_json::to_string_pretty(&summarize(&records))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn record(generation: usize, avg_score: f64, accepted: bool) -> GenerationRecord {
+        GenerationRecord {
+            generation,
+            expert_avg: avg_score + 10.0,
+            avg_score,
+            loss: 2.0 / (generation + 1) as f64,
+            accepted,
+            reference_avg: None,
+            reference_win_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_report_from_synthetic_history() {
+        let dir = tempdir().unwrap();
+        let data_dir = dir.path().to_str().unwrap();
+        // +2 pts per generation, except the rejected lucky-looking generation 3
+        let history = vec![
+            record(0, 140.0, true),
+            record(1, 142.0, true),
+            record(2, 144.0, true),
+            record(3, 152.0, false),
+            record(4, 148.0, true),
+        ];
+        for r in &history {
+            append_history(&history_path(data_dir), r).unwrap();
+        }
+        assert_eq!(load_history(&history_path(data_dir)).unwrap(), history);
+
+        let report = summarize(&history);
+        assert_eq!(report.generations, vec![0, 1, 2, 3, 4]);
+        assert_eq!(report.accepted, vec![true, true, true, false, true]);
+        // Best kept model, not the rejected higher benchmark
+        assert_eq!(report.trends.best_generation, Some(4));
+        assert_eq!(report.trends.best_avg_score, Some(148.0));
+        assert!((report.trends.improvement_rate - 2.6).abs() < 1e-9);
+        assert!((report.trends.acceptance_rate - 0.8).abs() < 1e-9);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report_json(data_dir).unwrap()).unwrap();
+        assert_eq!(json["avg_scores"][3], 152.0);
+        assert_eq!(json["trends"]["best_generation"], 4);
+    }
+
+    #[test]
+    fn test_empty_history_has_no_best_generation() {
+        let report = summarize(&[]);
+        assert!(report.generations.is_empty());
+        assert_eq!(report.trends.best_generation, None);
+        assert_eq!(report.trends.improvement_rate, 0.0);
+        assert_eq!(report.trends.acceptance_rate, 0.0);
+    }
+}
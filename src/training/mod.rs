@@ -1,3 +1,21 @@
+pub mod arch_lineages;
+pub mod benchmark_report;
+pub mod curriculum;
+pub mod early_stopping;
+pub mod elo;
 pub mod evaluator;
+pub mod gating;
+pub mod history;
+pub mod loop_state;
+pub mod optimizer_step;
+pub mod policy_divergence;
+pub mod policy_entropy;
+pub mod recency;
+pub mod reference;
+pub mod run_estimate;
+pub mod score_balance;
 pub mod session;
+pub mod tournament;
+pub mod value_normalization;
+pub mod value_target;
 pub mod websocket;
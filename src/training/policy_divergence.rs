@@ -0,0 +1,214 @@
+//! Replay divergence between two policy generations.
+//!
+//! Both policies are queried on the same recorded self-play states. Per game
+//! phase we report how often their argmax moves differ and the mean
+//! `KL(A || B)` of their move distributions over the legal positions. Unlike a
+//! win rate, this shows where a new generation changed its behavior.
+
+use tch::Kind;
+
+use crate::data::selfplay_csv::TurnRecord;
+use crate::game::create_deck::create_deck;
+use crate::game::deck::Deck;
+use crate::game::plateau::{create_plateau_empty, Plateau};
+use crate::game::remove_tile_from_deck::replace_tile_in_deck;
+use crate::game::tile::Tile;
+use crate::neural::graph_transformer::GraphTransformerPolicyNet;
+use crate::strategy::gt_boost::gt_masked_logits;
+
+/// Game phases as (name, first turn after the phase).
+pub const PHASES: [(&str, usize); 3] = [("early", 6), ("mid", 13), ("late", 19)];
+
+/// Index in `PHASES` of the phase containing `turn`.
+pub fn phase_of(turn: usize) -> usize {
+    PHASES
+        .iter()
+        .position(|&(_, end)| turn < end)
+        .unwrap_or(PHASES.len() - 1)
+}
+
+/// Divergence accumulated over the states of one phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhaseDivergence {
+    pub states: usize,
+    /// States where the two argmax moves differ
+    pub argmax_disagreements: usize,
+    pub kl_sum: f64,
+}
+
+impl PhaseDivergence {
+    pub fn disagreement_rate(&self) -> f64 {
+        if self.states == 0 {
+            0.0
+        } else {
+            self.argmax_disagreements as f64 / self.states as f64
+        }
+    }
+
+    pub fn mean_kl(&self) -> f64 {
+        if self.states == 0 {
+            0.0
+        } else {
+            self.kl_sum / self.states as f64
+        }
+    }
+
+    fn merge(&mut self, other: &PhaseDivergence) {
+        self.states += other.states;
+        self.argmax_disagreements += other.argmax_disagreements;
+        self.kl_sum += other.kl_sum;
+    }
+}
+
+/// Divergence per phase of `PHASES`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DivergenceReport {
+    pub phases: [PhaseDivergence; 3],
+}
+
+impl DivergenceReport {
+    /// Add one state, given the log-probabilities of both policies over the
+    /// 19 positions and the legal positions of the state.
+    pub fn add(&mut self, turn: usize, log_probs_a: &[f64], log_probs_b: &[f64], legal: &[usize]) {
+        if legal.is_empty() {
+            return;
+        }
+        let argmax = |log_probs: &[f64]| {
+            legal.iter().copied().fold(legal[0], |best, pos| {
+                if log_probs[pos] > log_probs[best] {
+                    pos
+                } else {
+                    best
+                }
+            })
+        };
+        let kl: f64 = legal
+            .iter()
+            .map(|&pos| log_probs_a[pos].exp() * (log_probs_a[pos] - log_probs_b[pos]))
+            .sum();
+
+        let phase = &mut self.phases[phase_of(turn)];
+        phase.states += 1;
+        if argmax(log_probs_a) != argmax(log_probs_b) {
+            phase.argmax_disagreements += 1;
+        }
+        phase.kl_sum += kl;
+    }
+
+    /// All phases together.
+    pub fn total(&self) -> PhaseDivergence {
+        let mut total = PhaseDivergence::default();
+        for phase in &self.phases {
+            total.merge(phase);
+        }
+        total
+    }
+}
+
+/// Board, remaining deck and drawn tile of a recorded turn.
+fn record_state(record: &TurnRecord) -> (Plateau, Deck, Tile) {
+    let mut plateau = create_plateau_empty();
+    let mut deck = create_deck();
+    for (pos, &code) in record.plateau.iter().enumerate() {
+        if code != 0 {
+            let tile = Tile(code / 100, (code / 10) % 10, code % 10);
+            plateau.tiles[pos] = tile;
+            deck = replace_tile_in_deck(&deck, &tile);
+        }
+    }
+    let tile = Tile(record.tile.0, record.tile.1, record.tile.2);
+    deck = replace_tile_in_deck(&deck, &tile);
+    (plateau, deck, tile)
+}
+
+/// Log-probabilities of `policy_net` over the 19 positions (`-inf` when occupied).
+fn log_probs(
+    policy_net: &GraphTransformerPolicyNet,
+    plateau: &Plateau,
+    deck: &Deck,
+    tile: &Tile,
+    turn: usize,
+) -> Vec<f64> {
+    let masked = gt_masked_logits(plateau, tile, deck, turn, policy_net);
+    Vec::<f64>::try_from(&masked.log_softmax(-1, Kind::Float)).unwrap()
+}
+
+/// Compare `model_a` and `model_b` on every recorded state.
+pub fn replay_divergence(
+    records: &[TurnRecord],
+    model_a: &GraphTransformerPolicyNet,
+    model_b: &GraphTransformerPolicyNet,
+) -> DivergenceReport {
+    let _guard = tch::no_grad_guard();
+    let mut report = DivergenceReport::default();
+    for record in records {
+        let (plateau, deck, tile) = record_state(record);
+        let legal: Vec<usize> = (0..19)
+            .filter(|&pos| plateau.tiles[pos] == Tile(0, 0, 0))
+            .collect();
+        let log_probs_a = log_probs(model_a, &plateau, &deck, &tile, record.turn);
+        let log_probs_b = log_probs(model_b, &plateau, &deck, &tile, record.turn);
+        report.add(record.turn, &log_probs_a, &log_probs_b, &legal);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tch::{nn, Device};
+
+    /// One recorded game, positions filled in order
+    fn records() -> Vec<TurnRecord> {
+        let tiles = create_deck().tiles().to_vec();
+        let mut plateau = [0i32; 19];
+        (0..19)
+            .map(|turn| {
+                let tile = tiles[turn];
+                let record = TurnRecord {
+                    game_idx: 0,
+                    turn,
+                    plateau,
+                    tile: (tile.0, tile.1, tile.2),
+                    chosen_position: turn,
+                    final_score: 0,
+                    distribution: None,
+                };
+                plateau[turn] = tile.0 * 100 + tile.1 * 10 + tile.2;
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_model_against_itself_has_zero_divergence() {
+        tch::manual_seed(5);
+        let vs = nn::VarStore::new(Device::Cpu);
+        let policy_net = GraphTransformerPolicyNet::new(&vs, 47, 32, 1, 2, 0.0);
+
+        let report = replay_divergence(&records(), &policy_net, &policy_net);
+
+        let states: Vec<usize> = report.phases.iter().map(|p| p.states).collect();
+        assert_eq!(states, vec![6, 7, 6]);
+        let total = report.total();
+        assert_eq!(total.argmax_disagreements, 0);
+        assert!(total.mean_kl().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_different_policies_diverge() {
+        let legal = [0, 1, 2];
+        let ln = |p: f64| p.ln();
+        let a = [ln(0.7), ln(0.2), ln(0.1)];
+        let b = [ln(0.1), ln(0.2), ln(0.7)];
+
+        let mut report = DivergenceReport::default();
+        report.add(15, &a, &b, &legal);
+
+        let late = report.phases[2];
+        assert_eq!(late.argmax_disagreements, 1);
+        // KL = 0.7 ln 7 + 0.1 ln(1/7)
+        assert!((late.mean_kl() - 0.6 * 7f64.ln()).abs() < 1e-9);
+        assert_eq!(report.phases[0], PhaseDivergence::default());
+    }
+}
@@ -0,0 +1,35 @@
+//! Recency weighting of self-play samples.
+//!
+//! A sample's weight decays with the age of the game it comes from: a game
+//! `halflife` games older than the newest one counts half as much. The factor
+//! multiplies the score-based weights already assigned to the samples, and an
+//! infinite halflife leaves them untouched.
+
+/// Weight of a sample whose game is `age` games older than the newest game.
+pub fn recency_weight(age: usize, halflife: f64) -> f32 {
+    0.5f64.powf(age as f64 / halflife) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_older_samples_weigh_less_unless_halflife_is_infinite() {
+        // Score weight 2.0 for games aged 0..6, newest first
+        let weighted = |halflife: f64| -> Vec<f32> {
+            (0..6)
+                .map(|age| 2.0 * recency_weight(age, halflife))
+                .collect()
+        };
+
+        let decayed = weighted(2.0);
+        assert!(decayed.windows(2).all(|w| w[0] > w[1]));
+        // Newest game untouched, one halflife older counts half
+        assert!((decayed[0] - 2.0).abs() < 1e-6);
+        assert!((decayed[2] - 1.0).abs() < 1e-6);
+        assert!((decayed[4] - 0.5).abs() < 1e-6);
+
+        assert!(weighted(f64::INFINITY).iter().all(|&w| w == 2.0));
+    }
+}
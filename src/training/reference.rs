@@ -0,0 +1,143 @@
+//! Benchmark against a frozen reference model.
+//!
+//! Each generation is only compared to the current best, so a long run can
+//! keep accepting generations while its absolute strength stalls. Playing
+//! every evaluated generation against the same frozen model (e.g. gen0) on the
+//! same seeds anchors the history to a fixed baseline. The reference is
+//! benchmarked once; a candidate then replays the same tile sequences and wins
+//! a game when it outscores the reference on it (ties count half).
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::training::gating::mean;
+use crate::training::history::GenerationRecord;
+
+/// Reference model scores on a fixed benchmark seed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceBenchmark {
+    seed: u64,
+    scores: Vec<f64>,
+}
+
+/// A generation compared game by game to the reference.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceComparison {
+    pub avg_score: f64,
+    pub reference_avg: f64,
+    /// Fraction of the benchmark games the generation outscored the reference on
+    pub win_rate: f64,
+}
+
+impl ReferenceBenchmark {
+    /// Run `benchmark` (per-game scores of the reference model) from `seed`.
+    pub fn new(seed: u64, mut benchmark: impl FnMut(&mut StdRng) -> Vec<f64>) -> Self {
+        let scores = benchmark(&mut StdRng::seed_from_u64(seed));
+        Self { seed, scores }
+    }
+
+    pub fn avg_score(&self) -> f64 {
+        mean(&self.scores)
+    }
+
+    /// Run `benchmark` for a candidate on the reference seed and compare.
+    pub fn compare(
+        &self,
+        mut benchmark: impl FnMut(&mut StdRng) -> Vec<f64>,
+    ) -> ReferenceComparison {
+        let scores = benchmark(&mut StdRng::seed_from_u64(self.seed));
+        ReferenceComparison {
+            avg_score: mean(&scores),
+            reference_avg: self.avg_score(),
+            win_rate: paired_win_rate(&scores, &self.scores),
+        }
+    }
+}
+
+impl ReferenceComparison {
+    /// Fill the reference columns of a history row.
+    pub fn record_into(&self, record: &mut GenerationRecord) {
+        record.reference_avg = Some(self.reference_avg);
+        record.reference_win_rate = Some(self.win_rate);
+    }
+}
+
+/// Share of paired games won by `candidate`, ties counting half.
+pub fn paired_win_rate(candidate: &[f64], reference: &[f64]) -> f64 {
+    let games = candidate.len().min(reference.len());
+    if games == 0 {
+        return 0.0;
+    }
+    let points: f64 = candidate
+        .iter()
+        .zip(reference)
+        .map(|(c, r)| match c.total_cmp(r) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        })
+        .sum();
+    points / games as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::training::gating::{evaluate_gate, GatingConfig};
+    use crate::training::history::{append_history, history_path, load_history};
+    use rand::Rng;
+    use tempfile::tempdir;
+
+    /// Benchmark of a model `strength` points above the reference on the
+    /// same random tile draws.
+    fn model(strength: f64) -> impl FnMut(&mut StdRng) -> Vec<f64> {
+        move |rng: &mut StdRng| {
+            (0..50)
+                .map(|_| 140.0 + rng.random_range(-30.0..30.0) + strength)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_rejected_generation_still_logs_reference_benchmark() {
+        let dir = tempdir().unwrap();
+        let path = history_path(dir.path().to_str().unwrap());
+        let reference = ReferenceBenchmark::new(7, model(0.0));
+
+        // Better than gen0, but worse than its immediate predecessor
+        let predecessor = model(8.0)(&mut StdRng::seed_from_u64(1));
+        let candidate = model(5.0)(&mut StdRng::seed_from_u64(1));
+        let gate = evaluate_gate(&candidate, &predecessor, &GatingConfig::default());
+        assert!(!gate.accepted);
+
+        let comparison = reference.compare(model(5.0));
+        let mut record = GenerationRecord {
+            generation: 3,
+            expert_avg: 150.0,
+            avg_score: mean(&candidate),
+            loss: 1.2,
+            accepted: gate.accepted,
+            reference_avg: None,
+            reference_win_rate: None,
+        };
+        comparison.record_into(&mut record);
+        append_history(&path, &record).unwrap();
+
+        let logged = &load_history(&path).unwrap()[0];
+        assert!(!logged.accepted);
+        // Same seeds: a constant edge wins every game
+        assert_eq!(logged.reference_win_rate, Some(1.0));
+        assert!((logged.reference_avg.unwrap() - reference.avg_score()).abs() < 1e-9);
+        assert!((comparison.avg_score - comparison.reference_avg - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_paired_win_rate_counts_ties_half() {
+        let reference = [140.0; 4];
+        assert_eq!(
+            paired_win_rate(&[150.0, 140.0, 130.0, 145.0], &reference),
+            0.625
+        );
+        assert_eq!(paired_win_rate(&[], &[140.0]), 0.0);
+    }
+}
@@ -0,0 +1,161 @@
+//! Dry-run projection of a training run.
+//!
+//! A few games are timed on the real hardware and configuration, then
+//! extrapolated to the full run: every phase of an iteration is linear in the
+//! number of games it plays (or of samples it trains on), so per-unit costs
+//! measured on a small sample are enough to tell a one-hour run from a
+//! multi-day one before launching it.
+
+/// Turns (and training samples) per game.
+const TURNS_PER_GAME: usize = 19;
+
+/// Per-unit costs measured on the sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleTimings {
+    /// Seconds per self-play (expert) game
+    pub secs_per_game: f64,
+    /// Seconds per training sample, for one epoch
+    pub secs_per_train_sample: f64,
+    /// Seconds per benchmark game
+    pub secs_per_eval_game: f64,
+}
+
+/// Size of the run to project.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunPlan {
+    pub iterations: usize,
+    pub games_per_iter: usize,
+    /// Fraction of the generated samples kept for training
+    pub kept_fraction: f64,
+    pub epochs_per_iter: usize,
+    /// Benchmark games played each iteration (evaluation + gating)
+    pub eval_games_per_iter: usize,
+    /// Benchmark games played once (baseline, final verification)
+    pub extra_eval_games: usize,
+    /// Bytes written each iteration (history rows, data files)
+    pub bytes_per_iter: u64,
+    /// Bytes written once (model checkpoint)
+    pub fixed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunEstimate {
+    pub generation_secs: f64,
+    pub training_secs: f64,
+    pub benchmark_secs: f64,
+    pub disk_bytes: u64,
+}
+
+impl RunEstimate {
+    pub fn total_secs(&self) -> f64 {
+        self.generation_secs + self.training_secs + self.benchmark_secs
+    }
+}
+
+/// Upper-bound projection: every iteration runs to completion (no early
+/// stopping).
+pub fn estimate_run(plan: &RunPlan, timings: &SampleTimings) -> RunEstimate {
+    let iterations = plan.iterations as f64;
+    let games = plan.games_per_iter as f64;
+    let train_samples = games * TURNS_PER_GAME as f64 * plan.kept_fraction;
+
+    RunEstimate {
+        generation_secs: iterations * games * timings.secs_per_game,
+        training_secs: iterations
+            * plan.epochs_per_iter as f64
+            * train_samples
+            * timings.secs_per_train_sample,
+        benchmark_secs: (iterations * plan.eval_games_per_iter as f64
+            + plan.extra_eval_games as f64)
+            * timings.secs_per_eval_game,
+        disk_bytes: plan.fixed_bytes + plan.iterations as u64 * plan.bytes_per_iter,
+    }
+}
+
+/// `"2d 03h 15m"`, `"1h 05m"`, `"42s"`.
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings() -> SampleTimings {
+        SampleTimings {
+            secs_per_game: 0.8,
+            secs_per_train_sample: 0.002,
+            secs_per_eval_game: 0.05,
+        }
+    }
+
+    fn plan(games_per_iter: usize) -> RunPlan {
+        RunPlan {
+            iterations: 10,
+            games_per_iter,
+            kept_fraction: 0.3,
+            epochs_per_iter: 15,
+            eval_games_per_iter: 300,
+            extra_eval_games: 800,
+            bytes_per_iter: 60,
+            fixed_bytes: 4_000_000,
+        }
+    }
+
+    #[test]
+    fn test_game_phases_scale_linearly_with_games_per_iter() {
+        let base = estimate_run(&plan(1000), &timings());
+        let double = estimate_run(&plan(2000), &timings());
+
+        assert!((base.generation_secs - 10.0 * 1000.0 * 0.8).abs() < 1e-6);
+        assert!((double.generation_secs - 2.0 * base.generation_secs).abs() < 1e-6);
+        assert!((double.training_secs - 2.0 * base.training_secs).abs() < 1e-6);
+        // Benchmarks and disk usage do not depend on the self-play volume
+        assert_eq!(double.benchmark_secs, base.benchmark_secs);
+        assert_eq!(double.disk_bytes, base.disk_bytes);
+        assert_eq!(base.disk_bytes, 4_000_600);
+
+        let none = estimate_run(&plan(0), &timings());
+        assert_eq!(none.generation_secs, 0.0);
+        assert_eq!(none.training_secs, 0.0);
+        assert!((base.total_secs() - none.total_secs() - 8000.0 - 1710.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_human_readable_formats() {
+        assert_eq!(format_duration(42.4), "42s");
+        assert_eq!(format_duration(125.0), "2m 05s");
+        assert_eq!(format_duration(3900.0), "1h 05m");
+        assert_eq!(
+            format_duration(2.0 * 86_400.0 + 3.0 * 3600.0 + 15.0 * 60.0),
+            "2d 03h 15m"
+        );
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5 MB");
+    }
+}
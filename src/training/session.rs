@@ -19,9 +19,11 @@ use crate::mcts::algorithm::mcts_find_best_position_for_tile_with_nn;
 use crate::mcts::mcts_result::MCTSResult;
 use crate::neural::manager::NNArchitecture;
 use crate::neural::policy_value_net::{PolicyNet, ValueNet};
+use crate::neural::training::policy_target::PolicyTargetMode;
 use crate::neural::training::trainer::train_network_with_game_data;
 use crate::scoring::scoring::result;
 use crate::training::evaluator::evaluate_model;
+use crate::training::score_balance::{balance_by_score, DEFAULT_BIN_WIDTH};
 use crate::training::websocket::send_websocket_message;
 use crate::utils::image::generate_tile_image_names;
 use futures_util::StreamExt;
@@ -39,6 +41,9 @@ pub struct TrainingOptions {
     pub min_score_medium: f64,
     pub medium_mix_ratio: f32,
     pub dynamic_sim_boost: usize,
+    pub policy_target: PolicyTargetMode,
+    /// Resample the buffer to a uniform score histogram before training
+    pub balance_scores: bool,
 }
 
 impl Default for TrainingOptions {
@@ -48,6 +53,8 @@ impl Default for TrainingOptions {
             min_score_medium: 120.0,
             medium_mix_ratio: 0.2,
             dynamic_sim_boost: 50,
+            policy_target: PolicyTargetMode::default(),
+            balance_scores: false,
         }
     }
 }
@@ -70,6 +77,14 @@ fn push_sample(buffer: &mut Vec<MCTSResult>, sample: &MCTSResult, copies: usize)
     }
 }
 
+/// Score-balanced buffer when `options.balance_scores`, unchanged otherwise
+fn balance_training_buffer(buffer: &mut Vec<MCTSResult>, options: &TrainingOptions) {
+    if options.balance_scores && !buffer.is_empty() {
+        let samples = std::mem::take(buffer);
+        *buffer = balance_by_score(samples, |r| r.subscore, DEFAULT_BIN_WIDTH, &mut rng());
+    }
+}
+
 fn adjust_num_simulations(
     base: usize,
     current_turn: usize,
@@ -308,6 +323,7 @@ pub async fn train_and_evaluate(
             }
 
             // Update main game counters
+            balance_training_buffer(&mut training_buffer, options);
             if !training_buffer.is_empty() {
                 let batch_size = 16;
                 for batch in training_buffer.chunks(batch_size) {
@@ -320,6 +336,7 @@ pub async fn train_and_evaluate(
                         value_net,
                         optimizer_policy,
                         optimizer_value,
+                        options.policy_target,
                     );
                 }
                 training_buffer.clear();
@@ -556,6 +573,7 @@ pub async fn train_and_evaluate_offline(
             }
         }
 
+        balance_training_buffer(&mut training_buffer, options);
         if !training_buffer.is_empty() {
             let batch_size = 16;
             for batch in training_buffer.chunks(batch_size) {
@@ -568,6 +586,7 @@ pub async fn train_and_evaluate_offline(
                     value_net,
                     optimizer_policy,
                     optimizer_value,
+                    options.policy_target,
                 );
             }
             training_buffer.clear();
@@ -0,0 +1,136 @@
+//! Round-robin comparison of a pool of models.
+//!
+//! Players never interact in Take It Easy, so a model's score on a tile
+//! sequence does not depend on its opponent: every model plays the shared
+//! sequences once, and each pairing compares the two score lists game by game.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Result of one pairing, a row of the tournament CSV.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pairing {
+    pub model_a: String,
+    pub model_b: String,
+    pub games: usize,
+    pub wins_a: usize,
+    pub wins_b: usize,
+    pub draws: usize,
+    pub avg_a: f64,
+    pub avg_b: f64,
+}
+
+fn average(scores: &[i32]) -> f64 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<i32>() as f64 / scores.len() as f64
+}
+
+/// Games model `a` wins against model `b`: `wins[a][b]`, on the sequences
+/// both played (`scores[m][i]` is model `m`'s score on sequence `i`).
+pub fn win_matrix(scores: &[Vec<i32>]) -> Vec<Vec<usize>> {
+    scores
+        .iter()
+        .map(|a| {
+            scores
+                .iter()
+                .map(|b| a.iter().zip(b).filter(|(sa, sb)| sa > sb).count())
+                .collect()
+        })
+        .collect()
+}
+
+/// Every pairing of `models` (name, scores on the shared sequences), in the
+/// order (0, 1), (0, 2), ... (1, 2), ...
+pub fn round_robin(models: &[(String, Vec<i32>)]) -> Vec<Pairing> {
+    let mut pairings = Vec::new();
+    for (i, (name_a, scores_a)) in models.iter().enumerate() {
+        for (name_b, scores_b) in &models[i + 1..] {
+            let games = scores_a.len().min(scores_b.len());
+            let (scores_a, scores_b) = (&scores_a[..games], &scores_b[..games]);
+            let wins_a = scores_a.iter().zip(scores_b).filter(|(a, b)| a > b).count();
+            let wins_b = scores_a.iter().zip(scores_b).filter(|(a, b)| a < b).count();
+            pairings.push(Pairing {
+                model_a: name_a.clone(),
+                model_b: name_b.clone(),
+                games,
+                wins_a,
+                wins_b,
+                draws: games - wins_a - wins_b,
+                avg_a: average(scores_a),
+                avg_b: average(scores_b),
+            });
+        }
+    }
+    pairings
+}
+
+pub fn save_pairings(pairings: &[Pairing], path: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut wtr = csv::Writer::from_path(path)?;
+    for pairing in pairings {
+        wtr.serialize(pairing)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn load_pairings(path: &str) -> Result<Vec<Pairing>, Box<dyn Error>> {
+    let mut rdr = csv::Reader::from_path(path)?;
+    let mut pairings = Vec::new();
+    for row in rdr.deserialize() {
+        pairings.push(row?);
+    }
+    Ok(pairings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_round_robin_compares_every_pair_game_by_game() {
+        let models = vec![
+            ("gen1".to_string(), vec![100, 150, 120, 90]),
+            ("gen2".to_string(), vec![110, 150, 100, 95]),
+            ("gen3".to_string(), vec![90, 140, 130, 80]),
+        ];
+        let pairings = round_robin(&models);
+        let pairs: Vec<(&str, &str)> = pairings
+            .iter()
+            .map(|p| (p.model_a.as_str(), p.model_b.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("gen1", "gen2"), ("gen1", "gen3"), ("gen2", "gen3")]
+        );
+
+        let first = &pairings[0];
+        assert_eq!((first.wins_a, first.wins_b, first.draws), (1, 2, 1));
+        assert_eq!((first.avg_a, first.avg_b), (115.0, 113.75));
+
+        let scores: Vec<Vec<i32>> = models.iter().map(|(_, s)| s.clone()).collect();
+        let wins = win_matrix(&scores);
+        assert_eq!(wins, vec![vec![0, 1, 3], vec![2, 0, 3], vec![1, 1, 0]]);
+        for p in &pairings {
+            assert_eq!(p.wins_a + p.wins_b + p.draws, p.games);
+        }
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("tournament.csv");
+        save_pairings(&pairings, path.to_str().unwrap()).unwrap();
+        let csv = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            csv.lines().next(),
+            Some("model_a,model_b,games,wins_a,wins_b,draws,avg_a,avg_b")
+        );
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(load_pairings(path.to_str().unwrap()).unwrap(), pairings);
+    }
+}
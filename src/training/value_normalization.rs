@@ -0,0 +1,165 @@
+//! Normalization of value-network targets.
+//!
+//! The value net is trained on `(score - mean) / std` and its outputs are
+//! mapped back to points with the same constants. They default to 140/40, or
+//! are measured on the training set (`--auto-normalize`). The constants used
+//! for training are written next to the model (`<model>.norm.json`) so that
+//! inference un-normalizes with the right values: [`load_value_varstore`]
+//! reads them back with the weights.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tch::nn;
+
+use crate::neural::model_io::load_varstore;
+
+/// Smallest std accepted, so a constant-score dataset doesn't divide by zero
+const MIN_STD: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreNormalization {
+    pub mean: f64,
+    pub std: f64,
+}
+
+impl Default for ScoreNormalization {
+    fn default() -> Self {
+        Self {
+            mean: 140.0,
+            std: 40.0,
+        }
+    }
+}
+
+impl ScoreNormalization {
+    /// Mean and (population) std of the given scores.
+    pub fn from_scores(scores: &[f64]) -> Self {
+        if scores.is_empty() {
+            return Self::default();
+        }
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean,
+            std: variance.sqrt().max(MIN_STD),
+        }
+    }
+
+    pub fn normalize(&self, score: f64) -> f64 {
+        (score - self.mean) / self.std
+    }
+
+    pub fn denormalize(&self, value: f64) -> f64 {
+        value * self.std + self.mean
+    }
+
+    /// Path of the constants saved alongside `model_path`.
+    pub fn sidecar_path(model_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = model_path.as_ref().as_os_str().to_owned();
+        path.push(".norm.json");
+        PathBuf::from(path)
+    }
+
+    pub fn save(&self, model_path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(
+            Self::sidecar_path(model_path),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    /// Constants saved with `model_path`, `None` for models trained before
+    /// they were saved (or with an unreadable file).
+    pub fn load(model_path: impl AsRef<Path>) -> Option<Self> {
+        let content = fs::read_to_string(Self::sidecar_path(model_path)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+/// Load a value model's weights and the constants it was trained with.
+///
+/// Models saved without constants fall back to the legacy 140/40.
+pub fn load_value_varstore(
+    vs: &mut nn::VarStore,
+    model_path: impl AsRef<Path>,
+) -> Result<ScoreNormalization, Box<dyn Error>> {
+    load_varstore(vs, &model_path)?;
+    Ok(ScoreNormalization::load(model_path).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural::graph_transformer::GraphTransformerValueNet;
+    use crate::neural::model_io::save_varstore;
+    use tch::{Device, Kind, Tensor};
+
+    #[test]
+    fn test_auto_normalized_targets_have_zero_mean_unit_variance() {
+        let scores = [92.0, 121.0, 140.0, 155.0, 163.0, 171.0, 188.0, 204.0];
+        let norm = ScoreNormalization::from_scores(&scores);
+
+        let targets: Vec<f64> = scores.iter().map(|&s| norm.normalize(s)).collect();
+        let n = targets.len() as f64;
+        let mean = targets.iter().sum::<f64>() / n;
+        let variance = targets.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / n;
+        assert!(mean.abs() < 1e-9, "mean = {}", mean);
+        assert!((variance - 1.0).abs() < 1e-9, "variance = {}", variance);
+
+        for &score in &scores {
+            assert!((norm.denormalize(norm.normalize(score)) - score).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_constants_round_trip_next_to_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let model_path = dir.path().join("value.safetensors");
+        assert_eq!(ScoreNormalization::load(&model_path), None);
+
+        let norm = ScoreNormalization {
+            mean: 151.25,
+            std: 27.5,
+        };
+        norm.save(&model_path).unwrap();
+        assert!(dir.path().join("value.safetensors.norm.json").exists());
+        assert_eq!(ScoreNormalization::load(&model_path), Some(norm));
+    }
+
+    #[test]
+    fn test_model_with_custom_constants_evaluates_consistently() {
+        let dir = tempfile::tempdir().unwrap();
+        let model_path = dir.path().join("value.safetensors");
+        let norm = ScoreNormalization {
+            mean: 163.0,
+            std: 22.0,
+        };
+
+        let vs = nn::VarStore::new(Device::Cpu);
+        let net = GraphTransformerValueNet::new(&vs, 47, 32, 1, 2, 0.0);
+        save_varstore(&vs, &model_path).unwrap();
+        norm.save(&model_path).unwrap();
+
+        let mut loaded_vs = nn::VarStore::new(Device::Cpu);
+        let loaded_net = GraphTransformerValueNet::new(&loaded_vs, 47, 32, 1, 2, 0.0);
+        let loaded_norm = load_value_varstore(&mut loaded_vs, &model_path).unwrap();
+        assert_eq!(loaded_norm, norm);
+
+        let x = Tensor::randn([3, 19, 47], (Kind::Float, Device::Cpu));
+        let expected = net.forward(&x, false);
+        let actual = loaded_net.forward(&x, false);
+        for i in 0..3 {
+            let trained = norm.denormalize(expected.double_value(&[i, 0]));
+            let served = loaded_norm.denormalize(actual.double_value(&[i, 0]));
+            assert!((trained - served).abs() < 1e-4, "{} vs {}", trained, served);
+        }
+
+        // Without the sidecar the legacy constants apply
+        fs::remove_file(ScoreNormalization::sidecar_path(&model_path)).unwrap();
+        let legacy = load_value_varstore(&mut loaded_vs, &model_path).unwrap();
+        assert_eq!(legacy, ScoreNormalization::default());
+    }
+}
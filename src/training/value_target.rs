@@ -0,0 +1,90 @@
+//! Value-network targets: full Monte Carlo or n-step bootstrap.
+//!
+//! With a horizon of 0 every state of a game is trained towards the game's
+//! final score (historical behaviour). With a horizon `n > 0`, a state at turn
+//! `t` is trained towards the value net's estimate of the state at turn `t + n`
+//! of the same game. States whose horizon falls past the end of the game keep
+//! the final score.
+//!
+//! The usual n-step target adds the points observed between `t` and `t + n` to
+//! an estimate of what comes after the horizon. The value nets here predict the
+//! final score of the board, points already on it included, so their estimate
+//! at `t + n` is already "observed score up to the horizon + estimated
+//! remainder": adding the observed points again would count them twice, and
+//! the target is the estimate alone.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// For each sample, index of the sample `horizon` turns later in the same game,
+/// or `None` for horizon 0 or when that turn is not in the dataset.
+pub fn horizon_indices<K: Hash + Eq>(
+    games: &[K],
+    turns: &[usize],
+    horizon: usize,
+) -> Vec<Option<usize>> {
+    if horizon == 0 {
+        return vec![None; games.len()];
+    }
+
+    let by_game_turn: HashMap<(&K, usize), usize> = games
+        .iter()
+        .zip(turns)
+        .enumerate()
+        .map(|(idx, (game, &turn))| ((game, turn), idx))
+        .collect();
+
+    games
+        .iter()
+        .zip(turns)
+        .map(|(game, &turn)| by_game_turn.get(&(game, turn + horizon)).copied())
+        .collect()
+}
+
+/// Training targets: the bootstrap estimate (a final-score prediction, see the
+/// module docs) when one is available, otherwise the final score.
+pub fn value_targets(final_scores: &[f64], bootstrap: &[Option<f64>]) -> Vec<f64> {
+    final_scores
+        .iter()
+        .zip(bootstrap)
+        .map(|(&score, estimate)| estimate.unwrap_or(score))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_horizon_zero_reproduces_final_score_targets() {
+        let games = [0, 0, 0, 1, 1];
+        let turns = [0, 1, 2, 0, 1];
+        let finals = [150.0, 150.0, 150.0, 90.0, 90.0];
+
+        let links = horizon_indices(&games, &turns, 0);
+        assert!(links.iter().all(Option::is_none));
+
+        // No bootstrap estimate is ever requested => targets are the final scores
+        let bootstrap: Vec<Option<f64>> = links.iter().map(|l| l.map(|_| 0.0)).collect();
+        assert_eq!(value_targets(&finals, &bootstrap), finals.to_vec());
+    }
+
+    #[test]
+    fn test_horizon_links_stay_within_a_game() {
+        let games = ["a", "a", "a", "b", "b"];
+        let turns = [0, 1, 2, 0, 1];
+
+        let links = horizon_indices(&games, &turns, 2);
+        assert_eq!(links, vec![Some(2), None, None, None, None]);
+
+        let links = horizon_indices(&games, &turns, 1);
+        assert_eq!(links, vec![Some(1), Some(2), None, Some(4), None]);
+    }
+
+    #[test]
+    fn test_bootstrap_estimate_replaces_final_score() {
+        let finals = [150.0, 150.0];
+        let targets = value_targets(&finals, &[Some(132.5), None]);
+        assert_eq!(targets, vec![132.5, 150.0]);
+    }
+}
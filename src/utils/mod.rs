@@ -1,2 +1,3 @@
 pub mod image;
 pub mod random_index;
+pub mod render;
@@ -1,5 +1,34 @@
+//! Randomness shared by the MCTS rollouts.
+//!
+//! Draws come from the thread RNG, unless the caller runs inside
+//! [`with_seeded_rng`]: then every draw of the current thread comes from one
+//! seeded RNG, so a search (expansion draws and rollouts) replays identically
+//! from the same seed.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
 pub fn random_index(max: usize) -> usize {
-    use rand::Rng;
-    let mut rng = rand::rng();
-    rng.random_range(0..max)
+    with_rng(|rng| match rng {
+        Some(rng) => rng.random_range(0..max),
+        None => rand::rng().random_range(0..max),
+    })
+}
+
+/// Run `f` with the seeded RNG installed by [`with_seeded_rng`], if any.
+pub fn with_rng<T>(f: impl FnOnce(Option<&mut StdRng>) -> T) -> T {
+    SEEDED_RNG.with(|cell| f(cell.borrow_mut().as_mut()))
+}
+
+/// Run `f` with every draw of this thread coming from an RNG seeded by `seed`.
+pub fn with_seeded_rng<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    let previous = SEEDED_RNG.with(|cell| cell.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = f();
+    SEEDED_RNG.with(|cell| *cell.borrow_mut() = previous);
+    result
 }
@@ -0,0 +1,104 @@
+//! ASCII rendering of a board for terminal output, shared by the bins and the
+//! server logs.
+//!
+//! One line per row of `ROWS` (3, 4, 5, 4, 3 cells), each cell showing the
+//! 3-digit tile code or `.` when empty. Rows are indented by half a cell per
+//! missing cell so adjacent rows interleave like on the hexagonal board:
+//!
+//! ```text
+//!        963   .   528
+//!      .    .    .    .
+//!   .    .   573   .    .
+//!      .    .    .    .
+//!         .    .    .
+//! ```
+//!
+//! `render_plateau_highlighted` brackets the given positions, e.g. `[573]`.
+
+use crate::game::board_geometry::ROWS;
+use crate::game::plateau::Plateau;
+use crate::game::tile::Tile;
+
+const CELL_WIDTH: usize = 5;
+
+pub fn render_plateau(plateau: &Plateau) -> String {
+    render_plateau_highlighted(plateau, &[])
+}
+
+/// Same layout as `render_plateau`, with `highlighted` cells in brackets
+pub fn render_plateau_highlighted(plateau: &Plateau, highlighted: &[usize]) -> String {
+    ROWS.iter()
+        .map(|row| {
+            let indent = " ".repeat((5 - row.len()) * CELL_WIDTH.div_ceil(2));
+            let cells: Vec<String> = row
+                .iter()
+                .map(|&pos| {
+                    let tile = plateau.tiles[pos];
+                    let code = if tile == Tile(0, 0, 0) {
+                        ".".to_string()
+                    } else {
+                        format!("{}{}{}", tile.0, tile.1, tile.2)
+                    };
+                    let code = if highlighted.contains(&pos) {
+                        format!("[{}]", code)
+                    } else {
+                        code
+                    };
+                    format!("{:^width$}", code, width = CELL_WIDTH)
+                })
+                .collect();
+            format!("{}{}", indent, cells.join(""))
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::plateau::create_plateau_empty;
+
+    #[test]
+    fn test_render_shows_tiles_row_by_row() {
+        let mut plateau = create_plateau_empty();
+        plateau.tiles[0] = Tile(9, 6, 3);
+        plateau.tiles[9] = Tile(5, 7, 3);
+        plateau.tiles[18] = Tile(1, 2, 8);
+
+        let rendered = render_plateau(&plateau);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains("963"));
+        assert!(lines[2].contains("573"));
+        assert!(lines[4].trim_end().ends_with("128"));
+        assert_eq!(rendered.matches('.').count(), 16);
+        // Shorter rows are indented further
+        let indent = |line: &str| line.len() - line.trim_start().len();
+        assert!(indent(lines[0]) > indent(lines[1]));
+        assert!(indent(lines[1]) > indent(lines[2]));
+    }
+
+    #[test]
+    fn test_empty_board_has_19_placeholders_in_hex_rows() {
+        let plateau = create_plateau_empty();
+
+        let rendered = render_plateau(&plateau);
+        let per_row: Vec<usize> = rendered
+            .lines()
+            .map(|line| line.matches('.').count())
+            .collect();
+        assert_eq!(per_row, vec![3, 4, 5, 4, 3]);
+
+        // Highlighting keeps the layout, only the chosen cells get brackets
+        let highlighted = render_plateau_highlighted(&plateau, &[0, 9]);
+        assert_eq!(highlighted.matches("[.]").count(), 2);
+        assert_eq!(highlighted.matches('.').count(), 19);
+        let dot_columns = |text: &str| -> Vec<usize> {
+            let row = text.lines().nth(2).unwrap();
+            row.match_indices('.').map(|(col, _)| col).collect()
+        };
+        assert_eq!(dot_columns(&highlighted), dot_columns(&rendered));
+    }
+}
@@ -0,0 +1,171 @@
+//! `ai_think_delay_ms`: the AI move of a session is held back by the
+//! configured pause, while other sessions keep being served.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tonic::Request;
+
+use take_it_easy::generated::takeiteasygame::v1::game_service_server::GameService;
+use take_it_easy::generated::takeiteasygame::v1::session_service_server::SessionService;
+use take_it_easy::generated::takeiteasygame::v1::*;
+use take_it_easy::neural::{NeuralConfig, NeuralManager};
+use take_it_easy::services::game_manager::TakeItEasyGameState;
+use take_it_easy::services::game_service::GameServiceImpl;
+use take_it_easy::services::session_manager::new_session_manager;
+use take_it_easy::services::session_service::SessionServiceImpl;
+
+const THINK_DELAY_MS: u64 = 1500;
+
+fn services() -> (SessionServiceImpl, GameServiceImpl) {
+    let session_manager = Arc::new(new_session_manager());
+
+    // Dossier inexistant : réseaux aléatoires
+    let config = NeuralConfig {
+        model_path: "missing_model_dir".to_string(),
+        ..Default::default()
+    };
+    let components = NeuralManager::with_config(config)
+        .unwrap()
+        .into_components();
+
+    let session_service =
+        SessionServiceImpl::new_with_manager_and_mode(session_manager.clone(), true);
+    let game_service = GameServiceImpl::new(
+        session_manager,
+        Arc::new(Mutex::new(components.policy_net)),
+        Arc::new(Mutex::new(components.value_net)),
+        2,
+    );
+    (session_service, game_service)
+}
+
+/// Ready solo session, returns (session_id, player_id)
+async fn create_solo(session_service: &SessionServiceImpl, delay_ms: i32) -> (String, String) {
+    let created = session_service
+        .create_session(Request::new(CreateSessionRequest {
+            player_name: "solo".to_string(),
+            max_players: 2,
+            game_mode: "single-player".to_string(),
+            num_turns: 0,
+            ai_think_delay_ms: delay_ms,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(create_session_response::Result::Success(created)) = created.result else {
+        panic!("CreateSession failed: {:?}", created.result);
+    };
+
+    let ready = session_service
+        .set_ready(Request::new(SetReadyRequest {
+            session_id: created.session_id.clone(),
+            player_id: created.player_id.clone(),
+            ready: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ready.success, "SetReady failed: {:?}", ready.error);
+    (created.session_id, created.player_id)
+}
+
+/// start_turn + first available move of the human player
+async fn play_turn(
+    game_service: &GameServiceImpl,
+    (session_id, player_id): &(String, String),
+) -> MakeMoveSuccess {
+    let started = game_service
+        .start_turn(Request::new(StartTurnRequest {
+            session_id: session_id.clone(),
+            forced_tile: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(started.success, "StartTurn failed: {:?}", started.error);
+
+    let moves = game_service
+        .get_available_moves(Request::new(GetAvailableMovesRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let moved = game_service
+        .make_move(Request::new(MakeMoveRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            move_data: moves.available_moves[0].clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(make_move_response::Result::Success(moved)) = moved.result else {
+        panic!("MakeMove failed: {:?}", moved.result);
+    };
+    moved
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_think_delay_does_not_block_other_sessions() {
+    let (session_service, game_service) = services();
+    let slow = create_solo(&session_service, THINK_DELAY_MS as i32).await;
+    let fast = create_solo(&session_service, 0).await;
+
+    let start = Instant::now();
+    play_turn(&game_service, &slow).await;
+
+    // L'IA de la session lente "réfléchit" : l'autre session joue pendant ce temps
+    play_turn(&game_service, &fast).await;
+    assert!(
+        start.elapsed() < Duration::from_millis(THINK_DELAY_MS),
+        "other session blocked for {:?}",
+        start.elapsed()
+    );
+
+    // Le coup suivant attend le coup IA, joué seulement après la pause
+    let moved = play_turn(&game_service, &slow).await;
+    assert!(start.elapsed() >= Duration::from_millis(THINK_DELAY_MS));
+
+    let state = moved.new_game_state.expect("game state");
+    let game: TakeItEasyGameState = serde_json::from_str(&state.board_state).unwrap();
+    let ai_tiles = game.player_plateaus["mcts_ai"]
+        .tiles
+        .iter()
+        .filter(|t| t.0 != 0)
+        .count();
+    assert!(ai_tiles >= 1, "AI move not applied");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_waiting_for_an_ai_move_does_not_block_other_sessions() {
+    let (session_service, game_service) = services();
+    let slow = create_solo(&session_service, THINK_DELAY_MS as i32).await;
+    let fast = create_solo(&session_service, 0).await;
+    play_turn(&game_service, &slow).await;
+    play_turn(&game_service, &fast).await;
+
+    // Les deux sessions attendent le coup IA de leur tour précédent en même
+    // temps : la session rapide n'attend pas la pause de la lente
+    let start = Instant::now();
+    let (_, fast_elapsed) = tokio::join!(play_turn(&game_service, &slow), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        play_turn(&game_service, &fast).await;
+        start.elapsed()
+    });
+    assert!(
+        fast_elapsed < Duration::from_millis(THINK_DELAY_MS / 2),
+        "other session blocked for {:?}",
+        fast_elapsed
+    );
+}
@@ -0,0 +1,79 @@
+//! Session store throughput: 100 concurrent games (create, then lookups by
+//! code and turn updates) against a single-lock store and the sharded one.
+//!
+//! Prints the operations per second of both layouts; run in release with
+//! `cargo test --release --test session_store_throughput_test -- --nocapture`
+//! to compare them. The live-server figure comes from
+//! `e2e_stress_test --mode stress --concurrent 100`.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use take_it_easy::services::session_manager::{
+    create_session_in_store, get_session_by_code_from_store, get_session_by_id_from_store,
+    transform_session_in_store, SessionStore, SESSION_STORE_SHARDS,
+};
+
+const CONCURRENT_GAMES: usize = 100;
+const TURNS_PER_GAME: usize = 200;
+
+/// Run every game to completion and return the operations per second
+async fn operations_per_second(store: Arc<SessionStore>) -> f64 {
+    let start = Instant::now();
+    let handles: Vec<_> = (0..CONCURRENT_GAMES)
+        .map(|_| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                let code = create_session_in_store(&store, 2, "multiplayer".to_string(), Ok)
+                    .await
+                    .unwrap();
+                for _ in 0..TURNS_PER_GAME {
+                    let session = get_session_by_code_from_store(&store, &code).await.unwrap();
+                    transform_session_in_store(&store, &session.id, |mut session| {
+                        session.turn_number += 1;
+                        Ok((session, ()))
+                    })
+                    .await
+                    .unwrap()
+                    .unwrap();
+                }
+                code
+            })
+        })
+        .collect();
+
+    let mut codes = Vec::new();
+    for handle in handles {
+        codes.push(handle.await.unwrap());
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    for code in &codes {
+        let session = get_session_by_code_from_store(&store, code).await.unwrap();
+        let by_id = get_session_by_id_from_store(&store, &session.id)
+            .await
+            .unwrap();
+        assert_eq!(by_id.turn_number, TURNS_PER_GAME as i32);
+    }
+    assert_eq!(store.session_count().await, CONCURRENT_GAMES);
+
+    // Création + (lecture par code + mise à jour) par tour
+    let operations = CONCURRENT_GAMES * (1 + 2 * TURNS_PER_GAME);
+    operations as f64 / elapsed.max(f64::EPSILON)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_session_store_throughput_single_lock_vs_sharded() {
+    let single_lock = operations_per_second(Arc::new(SessionStore::with_shards(1))).await;
+    let sharded =
+        operations_per_second(Arc::new(SessionStore::with_shards(SESSION_STORE_SHARDS))).await;
+
+    println!(
+        "session store, {} concurrent games: 1 shard {:.0} ops/s, {} shards {:.0} ops/s (x{:.2})",
+        CONCURRENT_GAMES,
+        single_lock,
+        SESSION_STORE_SHARDS,
+        sharded,
+        sharded / single_lock
+    );
+}
@@ -0,0 +1,439 @@
+//! In-process solo game: create → ready → 19 × (start_turn → make_move) → game over,
+//! the turn state machine rejecting out-of-order calls, and the tile draw
+//! requested at creation.
+//!
+//! Drives `SessionServiceImpl` / `GameServiceImpl` directly (no network) with
+//! randomly initialized networks, so regressions in the game flow show up
+//! without a live server (see the `e2e_stress_test` binary for that).
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tonic::Request;
+
+use take_it_easy::game::board_code::plateau_code;
+use take_it_easy::game::create_deck::create_deck;
+use take_it_easy::game::plateau::create_plateau_empty;
+use take_it_easy::game::tile::Tile;
+use take_it_easy::generated::takeiteasygame::v1::game_service_server::GameService;
+use take_it_easy::generated::takeiteasygame::v1::session_service_server::SessionService;
+use take_it_easy::generated::takeiteasygame::v1::*;
+use take_it_easy::neural::{NeuralConfig, NeuralManager};
+use take_it_easy::scoring::scoring::result;
+use take_it_easy::services::game_manager::TakeItEasyGameState;
+use take_it_easy::services::game_service::GameServiceImpl;
+use take_it_easy::services::session_manager::new_session_manager;
+use take_it_easy::services::session_service::SessionServiceImpl;
+
+fn services() -> (SessionServiceImpl, GameServiceImpl) {
+    let session_manager = Arc::new(new_session_manager());
+
+    // Dossier inexistant : réseaux aléatoires
+    let config = NeuralConfig {
+        model_path: "missing_model_dir".to_string(),
+        ..Default::default()
+    };
+    let components = NeuralManager::with_config(config)
+        .unwrap()
+        .into_components();
+
+    let session_service =
+        SessionServiceImpl::new_with_manager_and_mode(session_manager.clone(), true);
+    let game_service = GameServiceImpl::new(
+        session_manager,
+        Arc::new(Mutex::new(components.policy_net)),
+        Arc::new(Mutex::new(components.value_net)),
+        2,
+    );
+    (session_service, game_service)
+}
+
+#[tokio::test]
+async fn test_solo_game_reaches_scored_terminal_state() {
+    let (session_service, game_service) = services();
+
+    // Création : en solo, l'IA est ajoutée et la partie démarre d'elle-même
+    let created = session_service
+        .create_session(Request::new(CreateSessionRequest {
+            player_name: "solo".to_string(),
+            max_players: 2,
+            game_mode: "single-player".to_string(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(create_session_response::Result::Success(created)) = created.result else {
+        panic!("CreateSession failed: {:?}", created.result);
+    };
+    let session_id = created.session_id;
+    let player_id = created.player_id;
+
+    let ready = session_service
+        .set_ready(Request::new(SetReadyRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            ready: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ready.success, "SetReady failed: {:?}", ready.error);
+
+    let mut game_over = false;
+    let mut final_state = None;
+    for turn in 0..19 {
+        let started = game_service
+            .start_turn(Request::new(StartTurnRequest {
+                session_id: session_id.clone(),
+                forced_tile: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(
+            started.success,
+            "StartTurn {} failed: {:?}",
+            turn, started.error
+        );
+
+        let moves = game_service
+            .get_available_moves(Request::new(GetAvailableMovesRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(moves.available_moves.len(), 19 - turn, "turn {}", turn);
+
+        // Chaque coup disponible est déjà un move_data valide
+        let moved = game_service
+            .make_move(Request::new(MakeMoveRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+                move_data: moves.available_moves[0].clone(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(make_move_response::Result::Success(moved)) = moved.result else {
+            panic!("MakeMove {} failed: {:?}", turn, moved.result);
+        };
+
+        if moved.is_game_over {
+            assert_eq!(turn, 18, "game ended early");
+            game_over = true;
+            final_state = moved.new_game_state;
+            break;
+        }
+    }
+    assert!(game_over, "game not over after 19 turns");
+
+    // Plateaux complets et scores finaux cohérents avec le plateau
+    let final_state = final_state.expect("final game state");
+    assert_eq!(final_state.state, 2);
+    let game: TakeItEasyGameState = serde_json::from_str(&final_state.board_state).unwrap();
+    for id in [player_id.as_str(), "mcts_ai"] {
+        let plateau = &game.player_plateaus[id];
+        assert!(
+            plateau.tiles.iter().all(|t| t.0 != 0),
+            "{} board not full",
+            id
+        );
+        assert_eq!(game.scores.get(id), Some(&result(plateau)), "{}", id);
+    }
+
+    let state = game_service
+        .get_game_state(Request::new(GetGameStateRequest {
+            session_id: session_id.clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(state.is_game_finished);
+    assert!(!state.final_scores.is_empty());
+}
+
+#[tokio::test]
+async fn test_puzzle_from_15_tile_board_plays_final_4_turns() {
+    let (session_service, game_service) = services();
+
+    // Puzzle : les 15 premières tuiles du deck déjà posées sur les cases 0..15
+    let mut board = create_plateau_empty();
+    board.tiles[..15].copy_from_slice(&create_deck().tiles()[..15]);
+
+    let created = session_service
+        .create_session(Request::new(CreateSessionRequest {
+            player_name: "puzzle".to_string(),
+            max_players: 2,
+            game_mode: "single-player".to_string(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: plateau_code(&board),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(create_session_response::Result::Success(created)) = created.result else {
+        panic!("CreateSession failed: {:?}", created.result);
+    };
+    let session_id = created.session_id;
+    let player_id = created.player_id;
+
+    let ready = session_service
+        .set_ready(Request::new(SetReadyRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            ready: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ready.success, "SetReady failed: {:?}", ready.error);
+
+    let mut final_state = None;
+    for turn in 15..19 {
+        let started = game_service
+            .start_turn(Request::new(StartTurnRequest {
+                session_id: session_id.clone(),
+                forced_tile: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(
+            started.success,
+            "StartTurn {} failed: {:?}",
+            turn, started.error
+        );
+        // Seules les tuiles restantes du deck peuvent être annoncées
+        let game: TakeItEasyGameState = serde_json::from_str(&started.game_state).unwrap();
+        let tile = game.current_tile.expect("announced tile");
+        assert!(
+            !board.tiles.contains(&tile),
+            "turn {}: {:?} already on board",
+            turn,
+            tile
+        );
+
+        let moves = game_service
+            .get_available_moves(Request::new(GetAvailableMovesRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(moves.available_moves.len(), 19 - turn, "turn {}", turn);
+
+        let moved = game_service
+            .make_move(Request::new(MakeMoveRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+                move_data: moves.available_moves[0].clone(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(make_move_response::Result::Success(moved)) = moved.result else {
+            panic!("MakeMove {} failed: {:?}", turn, moved.result);
+        };
+        assert_eq!(moved.is_game_over, turn == 18, "turn {}", turn);
+        final_state = moved.new_game_state;
+    }
+
+    // Plateaux complétés autour des 15 tuiles imposées
+    let final_state = final_state.expect("final game state");
+    let game: TakeItEasyGameState = serde_json::from_str(&final_state.board_state).unwrap();
+    for id in [player_id.as_str(), "mcts_ai"] {
+        let plateau = &game.player_plateaus[id];
+        assert_eq!(plateau.tiles[..15], board.tiles[..15], "{}", id);
+        assert!(
+            plateau.tiles.iter().all(|t| t.0 != 0),
+            "{} board not full",
+            id
+        );
+        assert_eq!(game.scores.get(id), Some(&result(plateau)), "{}", id);
+    }
+}
+
+#[tokio::test]
+async fn test_turn_state_machine_rejects_out_of_order_calls() {
+    let (session_service, game_service) = services();
+
+    let created = session_service
+        .create_session(Request::new(CreateSessionRequest {
+            player_name: "order".to_string(),
+            max_players: 2,
+            game_mode: "single-player".to_string(),
+            num_turns: 0,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: String::new(),
+            tile_seed: 0,
+            tile_sequence: String::new(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(create_session_response::Result::Success(created)) = created.result else {
+        panic!("CreateSession failed: {:?}", created.result);
+    };
+    let session_id = created.session_id;
+    let player_id = created.player_id;
+
+    let make_move = |position: usize| {
+        game_service.make_move(Request::new(MakeMoveRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            move_data: format!(r#"{{"position": {}}}"#, position),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }))
+    };
+    let start_turn = || {
+        game_service.start_turn(Request::new(StartTurnRequest {
+            session_id: session_id.clone(),
+            forced_tile: String::new(),
+        }))
+    };
+
+    let ready = session_service
+        .set_ready(Request::new(SetReadyRequest {
+            session_id: session_id.clone(),
+            player_id: player_id.clone(),
+            ready: true,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ready.success, "SetReady failed: {:?}", ready.error);
+
+    // Tuile tirée au démarrage mais pas encore annoncée : MakeMove refusé
+    let moved = make_move(0).await.unwrap().into_inner();
+    let Some(make_move_response::Result::Error(error)) = moved.result else {
+        panic!("MakeMove before StartTurn accepted: {:?}", moved.result);
+    };
+    assert_eq!(error.code, "TURN_NOT_STARTED");
+
+    let first = start_turn().await.unwrap().into_inner();
+    assert!(first.success, "StartTurn failed: {:?}", first.error);
+
+    // Second StartTurn du même tour : refusé, avec l'annonce en cours
+    let second = start_turn().await.unwrap().into_inner();
+    assert!(!second.success);
+    assert_eq!(second.error.unwrap().code, "TURN_ALREADY_STARTED");
+    assert_eq!(second.announced_tile, first.announced_tile);
+    assert_eq!(second.turn_number, first.turn_number);
+
+    let moved = make_move(0).await.unwrap().into_inner();
+    assert!(
+        matches!(moved.result, Some(make_move_response::Result::Success(_))),
+        "MakeMove failed: {:?}",
+        moved.result
+    );
+
+    // Tour suivant : StartTurn de nouveau accepté
+    let next = start_turn().await.unwrap().into_inner();
+    assert!(next.success, "next StartTurn failed: {:?}", next.error);
+    assert_eq!(next.turn_number, first.turn_number + 1);
+}
+
+#[tokio::test]
+async fn test_sequence_tile_draw_announces_requested_tiles() {
+    let (session_service, game_service) = services();
+
+    let create = |tile_draw: &str, tile_sequence: &str| {
+        session_service.create_session(Request::new(CreateSessionRequest {
+            player_name: "sequence".to_string(),
+            max_players: 2,
+            game_mode: "single-player".to_string(),
+            num_turns: 3,
+            ai_think_delay_ms: 0,
+            starting_board: String::new(),
+            scoring_rules: String::new(),
+            opponent: String::new(),
+            tile_draw: tile_draw.to_string(),
+            tile_seed: 0,
+            tile_sequence: tile_sequence.to_string(),
+        }))
+    };
+
+    // Séquence trop courte pour 3 tours, tirage inconnu : refusés
+    for (tile_draw, tile_sequence) in [("sequence", "963,123"), ("shuffled", "")] {
+        let refused = create(tile_draw, tile_sequence).await.unwrap().into_inner();
+        let Some(create_session_response::Result::Error(error)) = refused.result else {
+            panic!("{} accepted: {:?}", tile_draw, refused.result);
+        };
+        assert_eq!(error.code, "INVALID_TILE_DRAW");
+    }
+
+    let created = create("sequence", "963,123,578")
+        .await
+        .unwrap()
+        .into_inner();
+    let Some(create_session_response::Result::Success(created)) = created.result else {
+        panic!("CreateSession failed: {:?}", created.result);
+    };
+    let session_id = created.session_id;
+    let player_id = created.player_id;
+
+    for (turn, expected) in [Tile(9, 6, 3), Tile(1, 2, 3), Tile(5, 7, 8)]
+        .into_iter()
+        .enumerate()
+    {
+        let started = game_service
+            .start_turn(Request::new(StartTurnRequest {
+                session_id: session_id.clone(),
+                forced_tile: String::new(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(
+            started.success,
+            "StartTurn {} failed: {:?}",
+            turn, started.error
+        );
+        let game: TakeItEasyGameState = serde_json::from_str(&started.game_state).unwrap();
+        assert_eq!(game.current_tile, Some(expected), "turn {}", turn);
+
+        let moves = game_service
+            .get_available_moves(Request::new(GetAvailableMovesRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let moved = game_service
+            .make_move(Request::new(MakeMoveRequest {
+                session_id: session_id.clone(),
+                player_id: player_id.clone(),
+                move_data: moves.available_moves[0].clone(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(make_move_response::Result::Success(moved)) = moved.result else {
+            panic!("MakeMove {} failed: {:?}", turn, moved.result);
+        };
+        assert_eq!(moved.is_game_over, turn == 2, "turn {}", turn);
+    }
+}